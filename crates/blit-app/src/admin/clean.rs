@@ -0,0 +1,165 @@
+//! `clean` — remove staging leftovers of crashed transfers.
+//!
+//! Lists a tree (locally, or on a daemon with the `Find` RPC), picks
+//! the staging entries nothing has modified for `older_than` (see
+//! [`blit_core::staging`]) and removes them — directly for a local
+//! tree, through the daemon's `Purge` RPC for a module path, so the
+//! module's read-only and `allowed_subpaths` rules still apply.
+
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use blit_core::enumeration::FileEnumerator;
+use blit_core::fs_enum::FileFilter;
+use blit_core::remote::RemoteEndpoint;
+use blit_core::staging::{stale_entries, ListedEntry};
+use eyre::{bail, Context, Result};
+
+use super::find::{self, FindEntry, FindParams};
+use super::rm;
+
+/// What a clean would remove, relative to the cleaned directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CleanPlan {
+    /// Stale staging entries in path-component order. A directory
+    /// stands for everything under it.
+    pub stale: Vec<String>,
+    /// Entries the listing returned.
+    pub scanned: usize,
+}
+
+/// Plan the clean of the local directory `root`.
+pub async fn plan_local(root: &Path, older_than: Duration) -> Result<CleanPlan> {
+    if !root.is_dir() {
+        bail!("clean target is not a directory: {}", root.display());
+    }
+    let cutoff = cutoff_seconds(older_than);
+    let root = root.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let entries: Vec<ListedEntry> = FileEnumerator::new(FileFilter::default())
+            .include_symlinks(true)
+            .enumerate_local(&root)
+            .with_context(|| format!("enumerate {}", root.display()))?
+            .into_iter()
+            .map(|entry| ListedEntry {
+                path: posix(&entry.relative_path),
+                mtime_seconds: entry
+                    .metadata
+                    .modified()
+                    .ok()
+                    .and_then(|mtime| mtime.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |age| age.as_secs() as i64),
+            })
+            .collect();
+        Ok(CleanPlan {
+            stale: stale_entries(&entries, cutoff),
+            scanned: entries.len(),
+        })
+    })
+    .await
+    .context("clean scan task panicked")?
+}
+
+/// Remove `plan`'s entries from the local directory `root`. Returns the
+/// number removed; an entry already gone is not counted.
+pub fn execute_local(root: &Path, plan: &CleanPlan) -> Result<u64> {
+    let mut removed = 0;
+    for rel in &plan.stale {
+        let path = root.join(rel);
+        let meta = match std::fs::symlink_metadata(&path) {
+            Ok(meta) => meta,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err).with_context(|| format!("stat {}", path.display())),
+        };
+        if meta.is_dir() {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        }
+        .with_context(|| format!("removing {}", path.display()))?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// Plan the clean of `remote` (a module path) from a `Find` listing.
+pub async fn plan_remote(remote: &RemoteEndpoint, older_than: Duration) -> Result<CleanPlan> {
+    let (module, rel_path) = rm::extract_module_and_path(remote)?;
+    let prefix = posix(&rel_path);
+    let cutoff = cutoff_seconds(older_than);
+
+    let mut entries = Vec::new();
+    find::stream(
+        remote,
+        FindParams {
+            module,
+            start_path: prefix.clone(),
+            pattern: String::new(),
+            case_sensitive: true,
+            include_files: true,
+            include_directories: true,
+            max_results: 0,
+            exclude: Vec::new(),
+        },
+        |entry: FindEntry| {
+            let path = if prefix.is_empty() {
+                Some(entry.path)
+            } else {
+                entry
+                    .path
+                    .strip_prefix(&format!("{prefix}/"))
+                    .map(str::to_string)
+            };
+            if let Some(path) = path.filter(|path| !path.is_empty() && path != ".") {
+                entries.push(ListedEntry {
+                    path,
+                    mtime_seconds: entry.mtime_seconds,
+                });
+            }
+            Ok(())
+        },
+    )
+    .await?;
+
+    Ok(CleanPlan {
+        stale: stale_entries(&entries, cutoff),
+        scanned: entries.len(),
+    })
+}
+
+/// Delete `plan`'s entries from `remote`. Returns the entry count the
+/// daemon reports.
+pub async fn execute_remote(remote: &RemoteEndpoint, plan: &CleanPlan) -> Result<u64> {
+    let (module, rel_path) = rm::extract_module_and_path(remote)?;
+    let prefix = posix(&rel_path);
+    let paths: Vec<String> = plan
+        .stale
+        .iter()
+        .map(|path| {
+            if prefix.is_empty() {
+                path.clone()
+            } else {
+                format!("{prefix}/{path}")
+            }
+        })
+        .collect();
+    rm::purge_batched(remote, &module, &paths).await
+}
+
+/// Unix time `older_than` ago: entries modified before it are stale.
+fn cutoff_seconds(older_than: Duration) -> i64 {
+    SystemTime::now()
+        .checked_sub(older_than)
+        .and_then(|cutoff| cutoff.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |cutoff| cutoff.as_secs() as i64)
+}
+
+fn posix(path: &Path) -> String {
+    path.components()
+        .filter_map(|component| match component {
+            std::path::Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
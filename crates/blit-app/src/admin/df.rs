@@ -5,13 +5,14 @@
 //! and returns the structured response.
 
 use blit_core::generated::FilesystemStatsRequest;
+use blit_core::remote::error_category::RemoteError;
 use blit_core::remote::RemoteEndpoint;
 use eyre::Result;
 use serde::Serialize;
 
 /// Filesystem usage for a remote module. Deserialized as-is by the
-/// CLI's `--json` path and rendered as a three-line block by the
-/// CLI's text path. The TUI will consume this directly for the
+/// CLI's `--json` path and rendered as a short block by the CLI's
+/// text path. The TUI will consume this directly for the
 /// F1 daemon-detail pane.
 #[derive(Debug, Clone, Serialize)]
 pub struct FilesystemStats {
@@ -19,18 +20,21 @@ pub struct FilesystemStats {
     pub total_bytes: u64,
     pub used_bytes: u64,
     pub free_bytes: u64,
+    /// Mount point and filesystem type the figures come from; `None`
+    /// from a daemon that predates the fields.
+    pub mount_point: Option<String>,
+    pub filesystem: Option<String>,
 }
 
 /// Issue the `FilesystemStats` RPC against `remote` for `module`.
 /// Caller handles formatting; presenter-agnostic.
 pub async fn query(remote: &RemoteEndpoint, module: String) -> Result<FilesystemStats> {
-    let uri = remote.control_plane_uri();
-    let mut client = crate::client::connect_with_timeout(uri.clone()).await?;
+    let mut client = crate::client::connect_cached(remote).await?;
 
     let response = client
         .filesystem_stats(FilesystemStatsRequest { module })
         .await
-        .map_err(|status| eyre::eyre!(status.message().to_string()))?
+        .map_err(RemoteError::from)?
         .into_inner();
 
     Ok(FilesystemStats {
@@ -38,5 +42,7 @@ pub async fn query(remote: &RemoteEndpoint, module: String) -> Result<Filesystem
         total_bytes: response.total_bytes,
         used_bytes: response.used_bytes,
         free_bytes: response.free_bytes,
+        mount_point: (!response.mount_point.is_empty()).then_some(response.mount_point),
+        filesystem: (!response.filesystem.is_empty()).then_some(response.filesystem),
     })
 }
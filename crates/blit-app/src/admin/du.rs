@@ -6,6 +6,7 @@
 //! all formatting.
 
 use blit_core::generated::DiskUsageRequest;
+use blit_core::remote::error_category::RemoteError;
 use blit_core::remote::RemoteEndpoint;
 use eyre::Result;
 use serde::Serialize;
@@ -24,35 +25,33 @@ pub struct DiskUsageEntry {
 /// Stream disk-usage entries from `remote`, invoking `on_entry`
 /// per row. The closure decides what to do with each entry; the
 /// CLI's text mode prints, the CLI's JSON mode collects into a
-/// vec, the TUI forwards to an event channel.
+/// vec, the TUI forwards to an event channel. `exclude` globs are
+/// skipped by the daemon's walk and count toward no total.
 pub async fn stream<F>(
     remote: &RemoteEndpoint,
     module: String,
     start_path: String,
     max_depth: u32,
+    exclude: Vec<String>,
     mut on_entry: F,
 ) -> Result<()>
 where
     F: FnMut(DiskUsageEntry) -> Result<()>,
 {
-    let uri = remote.control_plane_uri();
-    let mut client = crate::client::connect_with_timeout(uri.clone()).await?;
+    let mut client = crate::client::connect_cached(remote).await?;
 
     let mut stream = client
         .disk_usage(DiskUsageRequest {
             module,
             start_path,
             max_depth,
+            exclude,
         })
         .await
-        .map_err(|status| eyre::eyre!(status.message().to_string()))?
+        .map_err(RemoteError::from)?
         .into_inner();
 
-    while let Some(entry) = stream
-        .message()
-        .await
-        .map_err(|status| eyre::eyre!(status.message().to_string()))?
-    {
+    while let Some(entry) = stream.message().await.map_err(RemoteError::from)? {
         on_entry(DiskUsageEntry {
             path: entry.relative_path,
             bytes: entry.byte_total,
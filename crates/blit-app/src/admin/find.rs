@@ -6,6 +6,7 @@
 //! event loop while the CLI prints inline.
 
 use blit_core::generated::FindRequest;
+use blit_core::remote::error_category::RemoteError;
 use blit_core::remote::RemoteEndpoint;
 use eyre::Result;
 use serde::Serialize;
@@ -21,6 +22,9 @@ pub struct FindParams {
     pub include_files: bool,
     pub include_directories: bool,
     pub max_results: u32,
+    /// Globs the daemon's walk skips; a matching directory is not
+    /// searched.
+    pub exclude: Vec<String>,
 }
 
 /// One row from the streamed find response.
@@ -38,8 +42,7 @@ pub async fn stream<F>(remote: &RemoteEndpoint, params: FindParams, mut on_entry
 where
     F: FnMut(FindEntry) -> Result<()>,
 {
-    let uri = remote.control_plane_uri();
-    let mut client = crate::client::connect_with_timeout(uri.clone()).await?;
+    let mut client = crate::client::connect_cached(remote).await?;
 
     let mut stream = client
         .find(FindRequest {
@@ -50,16 +53,13 @@ where
             include_files: params.include_files,
             include_directories: params.include_directories,
             max_results: params.max_results,
+            exclude: params.exclude,
         })
         .await
-        .map_err(|status| eyre::eyre!(status.message().to_string()))?
+        .map_err(RemoteError::from)?
         .into_inner();
 
-    while let Some(entry) = stream
-        .message()
-        .await
-        .map_err(|status| eyre::eyre!(status.message().to_string()))?
-    {
+    while let Some(entry) = stream.message().await.map_err(RemoteError::from)? {
         on_entry(FindEntry {
             path: entry.relative_path,
             is_dir: entry.is_dir,
@@ -70,3 +70,19 @@ where
 
     Ok(())
 }
+
+/// Run a find and return what a `find --delete` removes: the matched
+/// paths (module-relative), collapsed to the top-most of each matched
+/// subtree, without the module root itself. Feed the result to
+/// [`super::rm::purge_batched`].
+pub async fn deletion_targets(remote: &RemoteEndpoint, params: FindParams) -> Result<Vec<String>> {
+    let mut matched = Vec::new();
+    stream(remote, params, |entry| {
+        if !entry.path.is_empty() && entry.path != "." {
+            matched.push(entry.path);
+        }
+        Ok(())
+    })
+    .await?;
+    Ok(super::rm::top_most_paths(matched))
+}
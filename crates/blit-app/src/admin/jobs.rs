@@ -11,6 +11,8 @@ use blit_core::generated::{
     SubscribeRequest,
 };
 use blit_core::remote::endpoint::RemoteEndpoint;
+use blit_core::remote::error_category::RemoteError;
+use blit_core::remote::ADMIN_TOKEN_METADATA;
 use eyre::Result;
 use tonic::Code;
 
@@ -20,13 +22,12 @@ use tonic::Code;
 /// values larger than the ring return everything the ring
 /// holds, no error.
 pub async fn query(remote: &RemoteEndpoint, recent_limit: u32) -> Result<DaemonState> {
-    let uri = remote.control_plane_uri();
-    let mut client = crate::client::connect_with_timeout(uri.clone()).await?;
+    let mut client = crate::client::connect_cached(remote).await?;
 
     let response = client
         .get_state(GetStateRequest { recent_limit })
         .await
-        .map_err(|status| eyre::eyre!(status.message().to_string()))?;
+        .map_err(RemoteError::from)?;
 
     Ok(response.into_inner())
 }
@@ -59,15 +60,28 @@ pub enum CancelJobOutcome {
 /// are part of the contract (NotFound, FailedPrecondition,
 /// Ok) get mapped onto [`CancelJobOutcome`] for the caller to
 /// render.
-pub async fn cancel(remote: &RemoteEndpoint, transfer_id: &str) -> Result<CancelJobOutcome> {
-    let uri = remote.control_plane_uri();
-    let mut client = crate::client::connect_with_timeout(uri.clone()).await?;
+///
+/// `admin_token` rides the [`ADMIN_TOKEN_METADATA`] header; a
+/// daemon whose `[daemon] admin_token` matches lets the call cancel
+/// transfers started from other hosts. A rejected token surfaces as
+/// the daemon's `PermissionDenied` error.
+pub async fn cancel(
+    remote: &RemoteEndpoint,
+    transfer_id: &str,
+    admin_token: Option<&str>,
+) -> Result<CancelJobOutcome> {
+    let mut client = crate::client::connect_cached(remote).await?;
 
-    let result = client
-        .cancel_job(CancelJobRequest {
-            transfer_id: transfer_id.to_string(),
-        })
-        .await;
+    let mut request = tonic::Request::new(CancelJobRequest {
+        transfer_id: transfer_id.to_string(),
+    });
+    if let Some(token) = admin_token {
+        let value = token
+            .parse()
+            .map_err(|_| eyre::eyre!("admin token must be printable ASCII"))?;
+        request.metadata_mut().insert(ADMIN_TOKEN_METADATA, value);
+    }
+    let result = client.cancel_job(request).await;
 
     match result {
         Ok(response) => {
@@ -108,8 +122,7 @@ pub async fn cancel(remote: &RemoteEndpoint, transfer_id: &str) -> Result<Cancel
 /// unexpected-status failures; clearing an already-empty list is a
 /// well-defined no-op that returns `0`.
 pub async fn clear_recent(remote: &RemoteEndpoint) -> Result<u32> {
-    let uri = remote.control_plane_uri();
-    let mut client = crate::client::connect_with_timeout(uri.clone()).await?;
+    let mut client = crate::client::connect_cached(remote).await?;
 
     let response = client
         .clear_recent(ClearRecentRequest {})
@@ -146,8 +159,7 @@ pub async fn subscribe(
     transfer_id_filter: &str,
     replay_recent: bool,
 ) -> Result<tonic::Streaming<DaemonEvent>> {
-    let uri = remote.control_plane_uri();
-    let mut client = crate::client::connect_with_timeout(uri.clone()).await?;
+    let mut client = crate::client::connect_cached(remote).await?;
     let response = client
         .subscribe(SubscribeRequest {
             event_mask: 0,
@@ -155,7 +167,7 @@ pub async fn subscribe(
             transfer_id_filter: transfer_id_filter.to_string(),
         })
         .await
-        .map_err(|status| eyre::eyre!(status.message().to_string()))?;
+        .map_err(RemoteError::from)?;
     Ok(response.into_inner())
 }
 
@@ -8,6 +8,7 @@
 
 use blit_core::generated::ListModulesRequest;
 use blit_core::remote::endpoint::RemoteEndpoint;
+use blit_core::remote::error_category::RemoteError;
 use eyre::Result;
 use serde::Serialize;
 
@@ -23,13 +24,12 @@ pub struct Module {
 /// Issue the `ListModules` RPC against `remote` and return the
 /// modules. Caller handles formatting.
 pub async fn query(remote: &RemoteEndpoint) -> Result<Vec<Module>> {
-    let uri = remote.control_plane_uri();
-    let mut client = crate::client::connect_with_timeout(uri.clone()).await?;
+    let mut client = crate::client::connect_cached(remote).await?;
 
     let response = client
         .list_modules(ListModulesRequest {})
         .await
-        .map_err(|status| eyre::eyre!(status.message().to_string()))?
+        .map_err(RemoteError::from)?
         .into_inner();
 
     Ok(response
@@ -8,6 +8,7 @@
 
 use blit_core::generated::ListRequest;
 use blit_core::remote::endpoint::RemoteEndpoint;
+use blit_core::remote::error_category::RemoteError;
 use eyre::{Context, Result};
 use serde::Serialize;
 use std::fs;
@@ -23,6 +24,37 @@ pub struct DirEntry {
     pub is_dir: bool,
     pub size: u64,
     pub mtime_seconds: i64,
+    /// Unix mode bits; zero where the listing side has none (Windows).
+    pub permissions: u32,
+}
+
+/// Listing order for [`sort_entries`]. `Size` and `Time` put the
+/// largest / newest first, as `ls -S` / `ls -t` do; ties fall back
+/// to name order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListSort {
+    #[default]
+    Name,
+    Size,
+    Time,
+}
+
+/// Order `entries` in place by `sort`.
+pub fn sort_entries(entries: &mut [DirEntry], sort: ListSort) {
+    match sort {
+        ListSort::Name => entries.sort_by(|a, b| a.name.cmp(&b.name)),
+        ListSort::Size => entries.sort_by(|a, b| b.size.cmp(&a.size).then(a.name.cmp(&b.name))),
+        ListSort::Time => entries.sort_by(|a, b| {
+            b.mtime_seconds
+                .cmp(&a.mtime_seconds)
+                .then(a.name.cmp(&b.name))
+        }),
+    }
+}
+
+/// Drop dotfiles (names starting with `.`), as `ls` does without `-a`.
+pub fn retain_visible(entries: &mut Vec<DirEntry>) {
+    entries.retain(|entry| !entry.name.starts_with('.'));
 }
 
 /// Outcome of `list_local`. Discriminates "target is a directory,
@@ -65,12 +97,11 @@ pub async fn list_remote(
     module: String,
     path: String,
 ) -> Result<Vec<DirEntry>> {
-    let uri = remote.control_plane_uri();
-    let mut client = crate::client::connect_with_timeout(uri.clone()).await?;
+    let mut client = crate::client::connect_cached(remote).await?;
     let response = client
         .list(ListRequest { module, path })
         .await
-        .map_err(|status| eyre::eyre!(status.message().to_string()))?
+        .map_err(RemoteError::from)?
         .into_inner();
 
     Ok(response
@@ -81,6 +112,7 @@ pub async fn list_remote(
             is_dir: info.is_dir,
             size: info.size,
             mtime_seconds: info.mtime_seconds,
+            permissions: info.permissions,
         })
         .collect())
 }
@@ -112,6 +144,7 @@ pub fn list_local(path: &Path) -> Result<LocalListing> {
                 is_dir,
                 size,
                 mtime_seconds: blit_core::wire_metadata::mtime_seconds(&meta).unwrap_or(0),
+                permissions: blit_core::wire_metadata::permissions_mode(&meta),
             });
         }
         Ok(LocalListing::Directory { entries })
@@ -126,6 +159,7 @@ pub fn list_local(path: &Path) -> Result<LocalListing> {
                 is_dir: false,
                 size: metadata.len(),
                 mtime_seconds: blit_core::wire_metadata::mtime_seconds(&metadata).unwrap_or(0),
+                permissions: blit_core::wire_metadata::permissions_mode(&metadata),
             },
         })
     }
@@ -0,0 +1,175 @@
+//! `prune` — the delete half of a mirror, with no transfer.
+//!
+//! Enumerates a local source tree, lists the remote destination with
+//! the `Find` RPC, and removes every destination entry whose path the
+//! source does not have through the daemon's `Purge` RPC — the same
+//! deletion machinery `rm` drives. No file content moves in either
+//! direction, so a destination whose content was synced by another
+//! channel can have its deletions brought in line on their own.
+//!
+//! Like mirror, a source scan that could not read every directory is
+//! refused: an unreadable source subtree would otherwise read as
+//! "absent" and its destination copy would be deleted.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use blit_core::enumeration::FileEnumerator;
+use blit_core::fs_enum::FileFilter;
+use blit_core::remote::RemoteEndpoint;
+use eyre::{bail, Context, Result};
+
+use super::find::{self, FindEntry, FindParams};
+use super::rm;
+
+/// What a prune would remove, relative to the destination directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrunePlan {
+    /// Top-most extraneous entries, in path-component order. A
+    /// directory stands for everything under it.
+    pub extraneous: Vec<String>,
+    /// Entries the destination listing returned.
+    pub dest_entries: usize,
+}
+
+/// The destination entries `source_paths` does not contain, keeping
+/// only the top-most of each extraneous subtree. Both sides are
+/// `/`-separated and relative to their roots.
+pub fn plan_extraneous_entries(
+    source_paths: &HashSet<String>,
+    dest_paths: impl IntoIterator<Item = String>,
+) -> Vec<String> {
+    rm::top_most_paths(
+        dest_paths
+            .into_iter()
+            .filter(|path| !source_paths.contains(path)),
+    )
+}
+
+/// Plan the prune of `remote` against the local tree at `src_root`.
+pub async fn plan(src_root: &Path, remote: &RemoteEndpoint) -> Result<PrunePlan> {
+    let (module, rel_path) = rm::extract_module_and_path(remote)?;
+    let dest_prefix = posix(&rel_path);
+
+    let root = src_root.to_path_buf();
+    let source_paths = tokio::task::spawn_blocking(move || source_paths(&root))
+        .await
+        .context("source scan task panicked")??;
+
+    let mut dest_paths = Vec::new();
+    find::stream(
+        remote,
+        FindParams {
+            module,
+            start_path: dest_prefix.clone(),
+            pattern: String::new(),
+            case_sensitive: true,
+            include_files: true,
+            include_directories: true,
+            max_results: 0,
+            exclude: Vec::new(),
+        },
+        |entry: FindEntry| {
+            if dest_prefix.is_empty() {
+                dest_paths.push(entry.path);
+            } else if let Some(rest) = entry.path.strip_prefix(&format!("{dest_prefix}/")) {
+                dest_paths.push(rest.to_string());
+            }
+            Ok(())
+        },
+    )
+    .await?;
+
+    let dest_entries = dest_paths.len();
+    Ok(PrunePlan {
+        extraneous: plan_extraneous_entries(&source_paths, dest_paths),
+        dest_entries,
+    })
+}
+
+/// Delete `plan`'s extraneous entries from `remote`. Returns the
+/// entry count the daemon reports.
+pub async fn execute(remote: &RemoteEndpoint, plan: &PrunePlan) -> Result<u64> {
+    let (module, rel_path) = rm::extract_module_and_path(remote)?;
+    let dest_prefix = posix(&rel_path);
+    let paths: Vec<String> = plan
+        .extraneous
+        .iter()
+        .map(|path| {
+            if dest_prefix.is_empty() {
+                path.clone()
+            } else {
+                format!("{dest_prefix}/{path}")
+            }
+        })
+        .collect();
+    rm::purge_batched(remote, &module, &paths).await
+}
+
+/// Every entry under `root`, as `/`-separated relative paths. Fails
+/// when the scan skipped anything.
+fn source_paths(root: &Path) -> Result<HashSet<String>> {
+    if !root.is_dir() {
+        bail!("prune source is not a directory: {}", root.display());
+    }
+    let (entries, outcome) = FileEnumerator::new(FileFilter::default())
+        .enumerate_local_capturing(root)
+        .with_context(|| format!("enumerate source {}", root.display()))?;
+    if let Some(first) = outcome.suppressed_errors.first() {
+        bail!(
+            "refusing to prune: the source scan could not read {} entr{} (first: {}); \
+             a destination copy of an unreadable path would be deleted",
+            outcome.suppressed_errors.len(),
+            if outcome.suppressed_errors.len() == 1 {
+                "y"
+            } else {
+                "ies"
+            },
+            first.path
+        );
+    }
+    Ok(entries
+        .iter()
+        .map(|entry| posix(&entry.relative_path))
+        .filter(|path| !path.is_empty())
+        .collect())
+}
+
+fn posix(path: &Path) -> String {
+    path.components()
+        .filter_map(|component| match component {
+            std::path::Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_top_of_each_extraneous_subtree_is_planned() {
+        let source: HashSet<String> = ["a.txt", "keep", "keep/x.txt"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let dest = [
+            "a.txt",
+            "keep",
+            "keep/x.txt",
+            "keep/stale.txt",
+            "gone",
+            "gone/deep",
+            "gone/deep/f",
+            "gone.txt",
+        ]
+        .into_iter()
+        .map(String::from);
+        assert_eq!(
+            plan_extraneous_entries(&source, dest),
+            vec!["gone", "gone.txt", "keep/stale.txt"]
+        );
+    }
+}
@@ -6,7 +6,9 @@
 //! `delete_remote_path` helper for its source-side removal step.
 
 use blit_core::generated::PurgeRequest;
+use blit_core::path_posix::collapse_to_topmost;
 use blit_core::remote::endpoint::{RemoteEndpoint, RemotePath};
+use blit_core::remote::error_category::RemoteError;
 use eyre::{bail, Result};
 use std::path::PathBuf;
 
@@ -18,8 +20,7 @@ pub async fn purge(
     module: String,
     paths_to_delete: Vec<String>,
 ) -> Result<u64> {
-    let uri = remote.control_plane_uri();
-    let mut client = crate::client::connect_with_timeout(uri.clone()).await?;
+    let mut client = crate::client::connect_cached(remote).await?;
 
     let response = client
         .purge(PurgeRequest {
@@ -27,12 +28,38 @@ pub async fn purge(
             paths_to_delete,
         })
         .await
-        .map_err(|status| eyre::eyre!(status.message().to_string()))?
+        .map_err(RemoteError::from)?
         .into_inner();
 
     Ok(response.files_deleted)
 }
 
+/// Paths per `Purge` request, so a large deletion stays well under the
+/// gRPC message limit.
+const PURGE_BATCH_PATHS: usize = 1024;
+
+/// [`purge`] `paths_to_delete` in batches of [`PURGE_BATCH_PATHS`].
+/// Returns the summed count the daemon reports; stops at the first
+/// failing batch.
+pub async fn purge_batched(
+    remote: &RemoteEndpoint,
+    module: &str,
+    paths_to_delete: &[String],
+) -> Result<u64> {
+    let mut deleted = 0;
+    for batch in paths_to_delete.chunks(PURGE_BATCH_PATHS) {
+        deleted += purge(remote, module.to_string(), batch.to_vec()).await?;
+    }
+    Ok(deleted)
+}
+
+/// Keep only the top-most of `paths` (`/`-separated): an entry under
+/// another one in the set is dropped, since deleting the ancestor
+/// removes it too. Returned in path-component order.
+pub fn top_most_paths(paths: impl IntoIterator<Item = String>) -> Vec<String> {
+    collapse_to_topmost(paths, |path| path, |_| true, |_, _| {})
+}
+
 /// Convenience wrapper around [`purge`] that derives the module
 /// from the `RemoteEndpoint` and accepts a single relative path.
 /// Used by `blit move` for its source-side removal step.
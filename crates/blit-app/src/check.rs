@@ -237,8 +237,9 @@ mod equivalence_tests {
     #[test]
     fn empty_directories_are_not_part_of_equivalence() {
         // Source has only an empty directory; destination is empty.
-        // Equivalence model: matches transfer behavior, which doesn't
-        // replicate empty directories. Result: zero diffs.
+        // Equivalence model: only files are compared; a destination
+        // written before transfers recreated empty directories would
+        // otherwise mismatch. Result: zero diffs.
         let tmp = tempdir().unwrap();
         let src = tmp.path().join("src");
         let dst = tmp.path().join("dst");
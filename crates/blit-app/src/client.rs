@@ -5,44 +5,108 @@
 //! handshake, network partition) made admin verbs and transfer commands
 //! hang for the OS TCP timeout (60-127s). [`connect_with_timeout`]
 //! centralizes the connection and bounds the whole `connect()` future
-//! (DNS resolution + TCP handshake) in an outer `tokio::time::timeout`,
+//! (DNS resolution + TCP handshake) in an outer `tokio::time::timeout`
+//! (`blit_core::remote::connect`, shared with the transfer session),
 //! matching the `feedback-server-await-timeouts` principle. (An inner
 //! `Endpoint::connect_timeout` alone does NOT bound slow DNS — see the
 //! function docs.)
+//!
+//! [`connect_cached`] is the pooled form for callers that talk to the
+//! same daemon more than once in one process (the TUI's panes, a
+//! sequence of `ls`/`find`/`du`): the first call per daemon connects
+//! through [`connect_with_timeout`]'s path, later calls share its
+//! channel — tonic multiplexes concurrent RPCs over the one HTTP/2
+//! connection and reconnects it if the daemon drops it.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
 
 use blit_core::generated::blit_client::BlitClient;
+use blit_core::remote::connect::connect_channel;
+use blit_core::remote::endpoint::RemoteEndpoint;
+use blit_core::remote::message_limits::blit_client;
 use eyre::{Context, Result};
-use std::time::Duration;
 use tonic::transport::{Channel, Endpoint};
 
-/// Connect deadline for control-plane gRPC connections. Used two ways in
-/// [`connect_with_timeout`]: as the outer `tokio::time::timeout` bounding
-/// the whole `connect()` future (DNS + TCP), and as the inner
-/// `Endpoint::connect_timeout` bounding just the post-resolution TCP
-/// phase. The outer bound is what makes an unreachable daemon (incl. slow
-/// DNS) fail predictably rather than hanging for the OS timeout.
-pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
-
 /// Connect a [`BlitClient`] to `uri` with a bounded connect deadline.
 /// Drop-in replacement for `BlitClient::connect(uri)` (which connects
-/// with no deadline). Errors carry the URI for context.
+/// with no deadline). Errors name the host and port.
 ///
 /// audit-2a round 2: `Endpoint::connect_timeout` alone does NOT bound
 /// slow DNS — tonic/hyper-util resolve the name *before* applying the
 /// connect timeout, so it only bounds the post-resolution TCP attempt.
-/// To honor the "slow DNS / hung handshake / partition" contract we
-/// wrap the whole `connect()` future (DNS + TCP) in an outer
-/// `tokio::time::timeout`. `connect_timeout` is kept as the inner,
-/// TCP-phase bound.
+/// [`connect_channel`] wraps the whole `connect()` future (DNS + TCP)
+/// in an outer `tokio::time::timeout` of `--contimeout` (default
+/// 30 s), keeping `connect_timeout` as the inner, TCP-phase bound.
 pub async fn connect_with_timeout(uri: String) -> Result<BlitClient<Channel>> {
     let endpoint = Endpoint::from_shared(uri.clone())
-        .with_context(|| format!("invalid daemon endpoint {uri}"))?
-        .connect_timeout(CONNECT_TIMEOUT);
-    let channel = tokio::time::timeout(CONNECT_TIMEOUT, endpoint.connect())
-        .await
-        .map_err(|_| eyre::eyre!("connecting to {uri} timed out after {CONNECT_TIMEOUT:?}"))?
-        .with_context(|| format!("connecting to {uri}"))?;
-    Ok(BlitClient::new(channel))
+        .with_context(|| format!("invalid daemon endpoint {uri}"))?;
+    Ok(blit_client(connect_channel(endpoint).await?))
+}
+
+/// Control-plane channels keyed by daemon URI and tokio runtime.
+///
+/// The key is the full `control_plane_uri` — scheme, host and port —
+/// so endpoints that would connect differently never share a channel,
+/// plus the current runtime's id: a channel's connection is driven by
+/// a task on the runtime that opened it, so it is only handed out
+/// there.
+/// Everything else a client carries is applied per client or per call,
+/// not per channel: the message limits by [`blit_client`] on every
+/// checkout, the admin token as request metadata. A failed connect
+/// leaves nothing in the pool.
+#[derive(Debug, Default)]
+pub struct ClientPool {
+    channels: Mutex<HashMap<(tokio::runtime::Id, String), Channel>>,
+    opened: AtomicUsize,
+}
+
+impl ClientPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A client on the pooled channel to `endpoint`, connecting (with
+    /// the bounded deadline) only when the pool has none. Two first
+    /// calls racing for one daemon may both connect; the first channel
+    /// stored wins and the other is dropped.
+    pub async fn connect(&self, endpoint: &RemoteEndpoint) -> Result<BlitClient<Channel>> {
+        let uri = endpoint.control_plane_uri();
+        let runtime = tokio::runtime::Handle::current().id();
+        if let Some(channel) = self.lock().get(&(runtime, uri.clone())) {
+            return Ok(blit_client(channel.clone()));
+        }
+        let target = Endpoint::from_shared(uri.clone())
+            .with_context(|| format!("invalid daemon endpoint {uri}"))?;
+        let channel = connect_channel(target).await?;
+        self.opened.fetch_add(1, Ordering::Relaxed);
+        let channel = self.lock().entry((runtime, uri)).or_insert(channel).clone();
+        Ok(blit_client(channel))
+    }
+
+    /// Channels this pool has opened so far.
+    pub fn channels_opened(&self) -> usize {
+        self.opened.load(Ordering::Relaxed)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<(tokio::runtime::Id, String), Channel>> {
+        self.channels
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The process-wide pool behind [`connect_cached`].
+pub fn shared_pool() -> &'static ClientPool {
+    static POOL: OnceLock<ClientPool> = OnceLock::new();
+    POOL.get_or_init(ClientPool::new)
+}
+
+/// Connect to `endpoint` through the process-wide [`ClientPool`], so
+/// successive operations against one daemon reuse a single channel.
+pub async fn connect_cached(endpoint: &RemoteEndpoint) -> Result<BlitClient<Channel>> {
+    shared_pool().connect(endpoint).await
 }
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
-//! Diagnostics surfaces: dump (one-shot snapshot for bug reports)
-//! and perf (enable/disable/clear performance history).
+//! Diagnostics surfaces: dump (one-shot snapshot for bug reports),
+//! perf (enable/disable/clear performance history), and bench
+//! (loopback throughput self-test).
 
+pub mod bench;
 pub mod dump;
 pub mod perf;
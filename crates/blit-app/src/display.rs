@@ -31,10 +31,133 @@ pub fn format_bps(bytes_per_second: u64) -> String {
     format!("{}/s", format_bytes(bytes_per_second))
 }
 
+/// Render a Unix mode as `ls -l` does: a type letter (`d` for a
+/// directory, `-` otherwise) and three `rwx` triplets. The set-id and
+/// sticky bits replace the matching `x` with `s`/`S` and `t`/`T`. A
+/// zero mode (no Unix permissions on the listing side) renders as
+/// dashes.
+pub fn format_mode(is_dir: bool, mode: u32) -> String {
+    let mut out = String::with_capacity(10);
+    out.push(if is_dir { 'd' } else { '-' });
+    let special = [(0o4000, 's'), (0o2000, 's'), (0o1000, 't')];
+    for (triplet, (special_bit, special_char)) in special.into_iter().enumerate() {
+        let bits = (mode >> (6 - 3 * triplet)) & 0o7;
+        out.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        out.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        out.push(match (bits & 0o1 != 0, mode & special_bit != 0) {
+            (true, true) => special_char,
+            (false, true) => special_char.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+    out
+}
+
+/// Render paths under `start` as an indented tree: each directory
+/// (suffixed `/`) is followed by its children, two spaces deeper,
+/// siblings in name order. Paths are `/`-separated and include
+/// `start` as a prefix, as `find` reports them; `start` itself is
+/// the caller's header and is skipped. Directories missing from the
+/// input (filtered out by a pattern, say) are filled in so every row
+/// has its parent. `max_depth` counts levels below `start`; deeper
+/// paths contribute only their ancestors within the bound.
+pub fn tree_lines<I>(start: &str, paths: I, max_depth: Option<usize>) -> Vec<String>
+where
+    I: IntoIterator<Item = (String, bool)>,
+{
+    let start = start.trim_matches('/');
+    let start = if start == "." { "" } else { start };
+    let mut nodes: std::collections::BTreeMap<Vec<String>, bool> = Default::default();
+    for (path, is_dir) in paths {
+        let path = path.trim_matches('/');
+        let rel = if start.is_empty() {
+            path
+        } else if let Some(rest) = path.strip_prefix(start) {
+            match rest.strip_prefix('/') {
+                Some(rest) => rest,
+                None if rest.is_empty() => continue,
+                None => path,
+            }
+        } else {
+            path
+        };
+        if rel.is_empty() || rel == "." {
+            continue;
+        }
+        let components: Vec<String> = rel.split('/').map(str::to_string).collect();
+        let shown = max_depth.map_or(components.len(), |max| components.len().min(max));
+        for depth in 1..=shown {
+            let node_is_dir = depth < components.len() || is_dir;
+            let entry = nodes.entry(components[..depth].to_vec()).or_insert(false);
+            *entry |= node_is_dir;
+        }
+    }
+    // Component-wise ordering of the keys is a depth-first walk with
+    // each directory ahead of its children.
+    nodes
+        .into_iter()
+        .map(|(components, is_dir)| {
+            format!(
+                "{}{}{}",
+                "  ".repeat(components.len()),
+                components.last().map(String::as_str).unwrap_or_default(),
+                if is_dir { "/" } else { "" }
+            )
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn tree_nests_children_under_their_directories() {
+        let found = [
+            ("docs/b.txt", false),
+            ("docs", true),
+            ("docs/a/deep/x.txt", false),
+            ("docs/a", true),
+            ("docs/a/deep", true),
+            ("docs/z.md", false),
+            ("docs/a-b", true),
+        ]
+        .map(|(p, d)| (p.to_string(), d));
+        assert_eq!(
+            tree_lines("docs", found.clone(), None),
+            [
+                "  a/",
+                "    deep/",
+                "      x.txt",
+                "  a-b/",
+                "  b.txt",
+                "  z.md",
+            ]
+        );
+        assert_eq!(
+            tree_lines("docs", found, Some(1)),
+            ["  a/", "  a-b/", "  b.txt", "  z.md"]
+        );
+
+        // A pattern that matched only a deep file still shows its parents.
+        let only_file = [("src/core/lib.rs".to_string(), false)];
+        assert_eq!(
+            tree_lines("", only_file, None),
+            ["  src/", "    core/", "      lib.rs"]
+        );
+    }
+
+    #[test]
+    fn mode_renders_like_ls() {
+        assert_eq!(format_mode(true, 0o40755), "drwxr-xr-x");
+        assert_eq!(format_mode(false, 0o100640), "-rw-r-----");
+        assert_eq!(format_mode(false, 0o4755), "-rwsr-xr-x");
+        assert_eq!(format_mode(true, 0o1777), "drwxrwxrwt");
+        assert_eq!(format_mode(false, 0o2640), "-rw-r-S---");
+        assert_eq!(format_mode(false, 0), "----------");
+    }
+
     #[test]
     fn zero_is_b() {
         assert_eq!(format_bytes(0), "0 B");
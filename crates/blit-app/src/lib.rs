@@ -19,6 +19,7 @@ pub mod client;
 pub mod diagnostics;
 pub mod display;
 pub mod endpoints;
+pub mod manifest;
 pub mod profile;
 pub mod scan;
 pub mod transfers;
@@ -0,0 +1,130 @@
+//! `blit scan --manifest` — a byte-stable listing of a local tree.
+//!
+//! Two machines that hold the same tree produce byte-identical
+//! manifests, so a plain `diff` or `cmp` of the two shows exactly the
+//! files that differ. The format is line-oriented text:
+//!
+//! ```text
+//! # blit-manifest v1 blake3
+//! <digest>  <size>  <path>
+//! ```
+//!
+//! - The header names the format version and the digest algorithm.
+//! - One line per regular file, sorted by the bytes of its path.
+//!   Directories and symlinks are not listed, matching `blit check`.
+//! - `<digest>` is the lowercase hex BLAKE3 of the whole file
+//!   (`checksum::hash_file`, unkeyed, so it depends on content alone).
+//! - `<size>` is the length in bytes, in decimal.
+//! - `<path>` is relative to the scanned root, `/`-separated. A `\`,
+//!   newline or carriage return in a name is written as `\\`, `\n` or
+//!   `\r`, and a byte that is not UTF-8 as `\xHH`, so every file stays
+//!   on its own line.
+//!
+//! Nothing else goes in: no timestamps, owners, modes, or host names,
+//! since those differ between machines holding the same content.
+
+use std::io::Write;
+use std::path::Path;
+
+use blit_core::checksum::{hash_file, ChecksumType};
+use blit_core::enumeration::{EntryKind, FileEnumerator};
+use blit_core::fs_enum::FileFilter;
+use eyre::{Context, Result};
+
+/// Version written in the manifest header. Bump it when a change to
+/// the format would make an older manifest diff against a newer one.
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Write the manifest of the tree under `root` to `out`, returning the
+/// number of files listed. Synchronous; async callers should run it on
+/// `spawn_blocking`.
+pub fn write_manifest(root: &Path, filter: FileFilter, out: &mut dyn Write) -> Result<usize> {
+    let entries = FileEnumerator::new(filter)
+        .enumerate_local(root)
+        .with_context(|| format!("enumerate {}", root.display()))?;
+
+    let mut files: Vec<(String, &Path, u64)> = entries
+        .iter()
+        .filter_map(|entry| match entry.kind {
+            EntryKind::File { size } => Some((
+                manifest_path(&entry.relative_path),
+                entry.absolute_path.as_path(),
+                size,
+            )),
+            _ => None,
+        })
+        .collect();
+    files.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+
+    writeln!(out, "# blit-manifest v{MANIFEST_VERSION} blake3")?;
+    for (path, absolute, size) in &files {
+        let digest = hash_file(absolute, ChecksumType::Blake3)
+            .with_context(|| format!("hashing {}", absolute.display()))?;
+        writeln!(out, "{}  {size}  {path}", hex(&digest))?;
+    }
+    out.flush()?;
+    Ok(files.len())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// `relative` as a manifest path: components joined by `/`, escaped
+/// so the path fits on one line.
+fn manifest_path(relative: &Path) -> String {
+    let mut out = String::new();
+    for (index, component) in relative.components().enumerate() {
+        if index > 0 {
+            out.push('/');
+        }
+        escape_component(component.as_os_str(), &mut out);
+    }
+    out
+}
+
+#[cfg(unix)]
+fn escape_component(name: &std::ffi::OsStr, out: &mut String) {
+    use std::os::unix::ffi::OsStrExt;
+    for chunk in name.as_bytes().utf8_chunks() {
+        escape_str(chunk.valid(), out);
+        for byte in chunk.invalid() {
+            out.push_str(&format!("\\x{byte:02x}"));
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn escape_component(name: &std::ffi::OsStr, out: &mut String) {
+    escape_str(&name.to_string_lossy(), out);
+}
+
+fn escape_str(text: &str, out: &mut String) {
+    for ch in text.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            _ => out.push(ch),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_are_slash_separated_and_escaped() {
+        assert_eq!(manifest_path(Path::new("a/b c/d.txt")), "a/b c/d.txt");
+        assert_eq!(manifest_path(Path::new("x\ny\\z")), "x\\ny\\\\z");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_bytes_are_hex_escaped() {
+        use std::os::unix::ffi::OsStrExt;
+        let name = std::ffi::OsStr::from_bytes(b"caf\xe9.txt");
+        assert_eq!(manifest_path(Path::new(name)), "caf\\xe9.txt");
+    }
+}
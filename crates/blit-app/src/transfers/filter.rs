@@ -9,7 +9,7 @@
 //! struct, which is also the shape the TUI's transfer-options
 //! modal will use.
 
-use blit_core::fs_enum::{parse_duration, parse_size, FileFilter};
+use blit_core::fs_enum::{parse_attributes, parse_duration, parse_size, FileFilter};
 use eyre::{eyre, Context, Result};
 use std::path::PathBuf;
 use std::time::SystemTime;
@@ -22,11 +22,19 @@ use std::time::SystemTime;
 pub struct FilterInputs<'a> {
     pub include: &'a [String],
     pub exclude: &'a [String],
+    /// `--include-from` / `--exclude-from` pattern files, appended after
+    /// the inline patterns of the same kind.
+    pub include_from: &'a [PathBuf],
+    pub exclude_from: &'a [PathBuf],
     pub files_from: Option<&'a PathBuf>,
     pub min_size: Option<&'a str>,
     pub max_size: Option<&'a str>,
     pub min_age: Option<&'a str>,
     pub max_age: Option<&'a str>,
+    /// `--no-hidden`: shorthand for `--exclude-attributes hidden`.
+    pub no_hidden: bool,
+    /// `--exclude-attributes`: comma-separated Windows attribute names.
+    pub exclude_attributes: Option<&'a str>,
 }
 
 /// Build a `FileFilter` from filter inputs. Used by every command
@@ -38,8 +46,8 @@ pub struct FilterInputs<'a> {
 /// malformed globs with a `--include`/`--exclude` pointer (R58-F12).
 pub fn build(inputs: &FilterInputs<'_>) -> Result<FileFilter> {
     let mut filter = FileFilter::default();
-    filter.include_files = inputs.include.to_vec();
-    filter.exclude_files = inputs.exclude.to_vec();
+    filter.include_files = merged_patterns(inputs.include, inputs.include_from)?;
+    filter.exclude_files = merged_patterns(inputs.exclude, inputs.exclude_from)?;
     if let Some(s) = inputs.min_size {
         filter.min_size = Some(parse_size(s).with_context(|| format!("--min-size {s}"))?);
     }
@@ -60,6 +68,7 @@ pub fn build(inputs: &FilterInputs<'_>) -> Result<FileFilter> {
     if let Some(path) = inputs.files_from {
         filter.files_from = Some(FileFilter::load_files_from(path)?);
     }
+    filter.exclude_attributes = attribute_mask(inputs)?;
     // R58-F12: validate glob patterns at filter-construction
     // time. The runtime build_globset silently drops invalid
     // patterns (which is OK as a defense-in-depth fallback for
@@ -74,6 +83,31 @@ pub fn build(inputs: &FilterInputs<'_>) -> Result<FileFilter> {
     Ok(filter)
 }
 
+/// The `FILE_ATTRIBUTE_*` mask `--no-hidden` / `--exclude-attributes`
+/// ask for; 0 when neither is set.
+pub fn attribute_mask(inputs: &FilterInputs<'_>) -> Result<u32> {
+    let mut mask = 0;
+    if inputs.no_hidden {
+        mask |= parse_attributes("hidden")?;
+    }
+    if let Some(s) = inputs.exclude_attributes {
+        mask |= parse_attributes(s).with_context(|| format!("--exclude-attributes {s}"))?;
+    }
+    Ok(mask)
+}
+
+/// Inline patterns followed by each pattern file's lines, in the order
+/// given. Order never changes the outcome — an exclude match vetoes
+/// wherever it sits and includes are a whitelist — but keeping it makes
+/// the merged list read the way the command line did.
+fn merged_patterns(inline: &[String], files: &[PathBuf]) -> Result<Vec<String>> {
+    let mut patterns = inline.to_vec();
+    for path in files {
+        patterns.extend(FileFilter::load_patterns_from(path)?);
+    }
+    Ok(patterns)
+}
+
 /// Build the wire-side `FilterSpec` proto message from the same
 /// filter inputs. Used by the remote push path so the daemon
 /// enforces the same filter the CLI would have applied locally.
@@ -82,8 +116,8 @@ pub fn build(inputs: &FilterInputs<'_>) -> Result<FileFilter> {
 pub fn build_spec(inputs: &FilterInputs<'_>) -> Result<blit_core::generated::FilterSpec> {
     use blit_core::generated::FilterSpec;
     let mut spec = FilterSpec {
-        include: inputs.include.to_vec(),
-        exclude: inputs.exclude.to_vec(),
+        include: merged_patterns(inputs.include, inputs.include_from)?,
+        exclude: merged_patterns(inputs.exclude, inputs.exclude_from)?,
         min_size: None,
         max_size: None,
         min_age_secs: None,
@@ -143,11 +177,15 @@ mod tests {
         FilterInputs {
             include,
             exclude,
+            include_from: &[],
+            exclude_from: &[],
             files_from: None,
             min_size: None,
             max_size: None,
             min_age: None,
             max_age: None,
+            no_hidden: false,
+            exclude_attributes: None,
         }
     }
 
@@ -240,4 +278,83 @@ mod tests {
             "expected a glob-pattern pointer, got: {err:#}"
         );
     }
+
+    #[test]
+    fn pattern_files_merge_after_inline_patterns_and_filter_enumeration() {
+        use blit_core::enumeration::{EntryKind, FileEnumerator};
+
+        let lists = tempfile::tempdir().unwrap();
+        let exclude_file = lists.path().join("exclude.txt");
+        std::fs::write(&exclude_file, "# build output\n\n*.o\n  *.tmp  \n").unwrap();
+        let include_file = lists.path().join("include.txt");
+        std::fs::write(&include_file, "*.c\n#*.h\n*.o\n").unwrap();
+
+        let inc = vec!["*.md".to_string()];
+        let exc = vec!["skip.*".to_string()];
+        let mut i = inputs(&inc, &exc);
+        let include_from = [include_file];
+        let exclude_from = [exclude_file];
+        i.include_from = &include_from;
+        i.exclude_from = &exclude_from;
+
+        let f = build(&i).unwrap();
+        assert_eq!(f.include_files, ["*.md", "*.c", "*.o"]);
+        assert_eq!(f.exclude_files, ["skip.*", "*.o", "*.tmp"]);
+        let spec = build_spec(&i).unwrap();
+        assert_eq!(spec.include, f.include_files);
+        assert_eq!(spec.exclude, f.exclude_files);
+
+        let tree = tempfile::tempdir().unwrap();
+        for name in [
+            "main.c",
+            "main.o",
+            "scratch.tmp",
+            "README.md",
+            "skip.c",
+            "util.h",
+        ] {
+            std::fs::write(tree.path().join(name), name).unwrap();
+        }
+        let mut kept: Vec<String> = FileEnumerator::new(f)
+            .enumerate_local(tree.path())
+            .unwrap()
+            .into_iter()
+            .filter(|e| matches!(e.kind, EntryKind::File { .. }))
+            .map(|e| e.relative_path.to_string_lossy().into_owned())
+            .collect();
+        kept.sort();
+        // `main.o` matches an include from the file but an exclude from
+        // the same set vetoes it; `util.h`'s include is commented out.
+        assert_eq!(kept, ["README.md", "main.c"]);
+    }
+
+    #[test]
+    fn missing_pattern_file_names_the_path() {
+        let mut i = inputs(&[], &[]);
+        let missing = [PathBuf::from("/nonexistent/blit-excludes.txt")];
+        i.exclude_from = &missing;
+        let err = build(&i).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("/nonexistent/blit-excludes.txt"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn attribute_flags_build_one_mask() {
+        let mut i = inputs(&[], &[]);
+        i.no_hidden = true;
+        i.exclude_attributes = Some("System, archive");
+        let f = build(&i).unwrap();
+        // hidden (0x2) | system (0x4) | archive (0x20)
+        assert_eq!(f.exclude_attributes, 0x26);
+        assert!(!f.is_empty());
+
+        i.exclude_attributes = Some("hidden,sparkly");
+        let err = build(&i).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("unknown file attribute 'sparkly'"),
+            "{err:#}"
+        );
+    }
 }
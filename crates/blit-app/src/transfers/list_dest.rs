@@ -0,0 +1,275 @@
+//! `--list-dest` — read-only preview of a push.
+//!
+//! Enumerates the local source with the transfer's `FileFilter`,
+//! asks the daemon for the destination's files through the `find`
+//! RPC, and classifies every path by size + mtime. Unlike
+//! `--dry-run` no session opens: the daemon never builds a need
+//! list, claims a path, or plans a delete, so the preview is safe
+//! against a destination another transfer is writing into.
+//!
+//! The comparison is the default SizeMtime shape at whole-second
+//! resolution (the `find` wire carries `mtime_seconds`). A path the
+//! filter excludes is left out on both sides, so a filtered preview
+//! does not report the excluded remainder as destination-only.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use blit_core::enumeration::{EntryKind, FileEnumerator};
+use blit_core::fs_enum::FileFilter;
+use blit_core::remote::error_category::{ErrorCategory, RemoteError};
+use blit_core::remote::RemoteEndpoint;
+use eyre::{bail, Context, Result};
+use serde::Serialize;
+
+use crate::admin::find::{self, FindParams};
+use crate::endpoints::{module_and_rel_path, rel_path_to_string};
+
+/// How a path differs between the source and the destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListDestChange {
+    /// Only the source has it; a push would create it.
+    NewOnSource,
+    /// Both have it; the source copy is newer.
+    NewerOnSource,
+    /// Both have it; the destination copy is newer. A default push
+    /// still overwrites it — the preview is where that shows up.
+    NewerOnDestination,
+    /// Same mtime, different size.
+    SizeDiffers,
+    /// Only the destination has it; a mirror would delete it.
+    DestinationOnly,
+}
+
+impl ListDestChange {
+    /// The itemized-report marker for this change.
+    pub fn marker(self) -> char {
+        match self {
+            ListDestChange::NewOnSource => '+',
+            ListDestChange::NewerOnSource => '>',
+            ListDestChange::NewerOnDestination => '<',
+            ListDestChange::SizeDiffers => '~',
+            ListDestChange::DestinationOnly => '*',
+        }
+    }
+}
+
+/// One differing path, relative to the transfer root.
+#[derive(Debug, Clone, Serialize)]
+pub struct ListDestEntry {
+    pub path: String,
+    pub change: ListDestChange,
+    pub src_size: Option<u64>,
+    pub dst_size: Option<u64>,
+}
+
+/// Result of a `list_dest` call. `entries` is sorted by path;
+/// `unchanged` counts the files a push would skip.
+#[derive(Debug, Default, Serialize)]
+pub struct ListDestReport {
+    pub entries: Vec<ListDestEntry>,
+    pub unchanged: usize,
+}
+
+impl ListDestReport {
+    pub fn count(&self, change: ListDestChange) -> usize {
+        self.entries.iter().filter(|e| e.change == change).count()
+    }
+}
+
+struct FileStat {
+    size: u64,
+    mtime: i64,
+}
+
+/// Compare the local directory `src` against the remote directory
+/// `dst` without opening a transfer session.
+pub async fn list_dest(
+    src: &Path,
+    dst: &RemoteEndpoint,
+    filter: FileFilter,
+) -> Result<ListDestReport> {
+    if !src.is_dir() {
+        bail!(
+            "--list-dest needs a source directory: {} is not one",
+            src.display()
+        );
+    }
+
+    let src_root = src.to_path_buf();
+    let source_filter = filter.clone_without_cache();
+    let source = tokio::task::spawn_blocking(move || local_files(&src_root, source_filter))
+        .await
+        .context("source enumeration task panicked")??;
+    let destination = remote_files(dst, &filter).await?;
+
+    Ok(classify(source, destination))
+}
+
+fn local_files(root: &Path, filter: FileFilter) -> Result<BTreeMap<String, FileStat>> {
+    let entries = FileEnumerator::new(filter)
+        .enumerate_local(root)
+        .with_context(|| format!("enumerate source {}", root.display()))?;
+    let mut files = BTreeMap::new();
+    for entry in entries {
+        let EntryKind::File { size } = entry.kind else {
+            continue;
+        };
+        let mtime = entry
+            .metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        files.insert(
+            rel_path_to_string(&entry.relative_path),
+            FileStat { size, mtime },
+        );
+    }
+    Ok(files)
+}
+
+async fn remote_files(
+    dst: &RemoteEndpoint,
+    filter: &FileFilter,
+) -> Result<BTreeMap<String, FileStat>> {
+    let (module, rel) = module_and_rel_path(dst)?;
+    let start_path = rel_path_to_string(&rel);
+    let prefix = if start_path.is_empty() {
+        String::new()
+    } else {
+        format!("{}/", start_path.trim_end_matches('/'))
+    };
+
+    let mut files = BTreeMap::new();
+    let params = FindParams {
+        module,
+        start_path,
+        pattern: String::new(),
+        case_sensitive: true,
+        include_files: true,
+        include_directories: false,
+        max_results: 0,
+        exclude: Vec::new(),
+    };
+    let streamed = find::stream(dst, params, |entry| {
+        // The daemon reports paths relative to the module root.
+        let path = entry
+            .path
+            .strip_prefix(prefix.as_str())
+            .unwrap_or(&entry.path)
+            .to_string();
+        let rel = PathBuf::from(&path);
+        let mtime = u64::try_from(entry.mtime_seconds)
+            .ok()
+            .map(|secs| UNIX_EPOCH + std::time::Duration::from_secs(secs));
+        if filter.allows_entry(Some(&rel), &rel, entry.size, mtime) {
+            files.insert(
+                path,
+                FileStat {
+                    size: entry.size,
+                    mtime: entry.mtime_seconds,
+                },
+            );
+        }
+        Ok(())
+    })
+    .await;
+
+    match streamed {
+        Ok(()) => Ok(files),
+        // A destination that does not exist yet is an empty one: the
+        // push would create every source file.
+        Err(err)
+            if err
+                .downcast_ref::<RemoteError>()
+                .is_some_and(|e| e.category == ErrorCategory::NotFound) =>
+        {
+            Ok(BTreeMap::new())
+        }
+        Err(err) => Err(err.wrap_err(format!("listing destination {}", dst.display()))),
+    }
+}
+
+fn classify(
+    source: BTreeMap<String, FileStat>,
+    mut destination: BTreeMap<String, FileStat>,
+) -> ListDestReport {
+    let mut report = ListDestReport::default();
+    for (path, src) in source {
+        let dst = destination.remove(&path);
+        let change = match &dst {
+            None => ListDestChange::NewOnSource,
+            Some(dst) if src.mtime > dst.mtime => ListDestChange::NewerOnSource,
+            Some(dst) if dst.mtime > src.mtime => ListDestChange::NewerOnDestination,
+            Some(dst) if src.size != dst.size => ListDestChange::SizeDiffers,
+            Some(_) => {
+                report.unchanged += 1;
+                continue;
+            }
+        };
+        report.entries.push(ListDestEntry {
+            path,
+            change,
+            src_size: Some(src.size),
+            dst_size: dst.map(|d| d.size),
+        });
+    }
+    for (path, dst) in destination {
+        report.entries.push(ListDestEntry {
+            path,
+            change: ListDestChange::DestinationOnly,
+            src_size: None,
+            dst_size: Some(dst.size),
+        });
+    }
+    report.entries.sort_by(|a, b| a.path.cmp(&b.path));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stat(size: u64, mtime: i64) -> FileStat {
+        FileStat { size, mtime }
+    }
+
+    #[test]
+    fn classify_sorts_every_category() {
+        let source = BTreeMap::from([
+            ("new.txt".to_string(), stat(1, 100)),
+            ("newer.txt".to_string(), stat(2, 200)),
+            ("older.txt".to_string(), stat(3, 100)),
+            ("resized.txt".to_string(), stat(4, 100)),
+            ("same.txt".to_string(), stat(5, 100)),
+        ]);
+        let destination = BTreeMap::from([
+            ("newer.txt".to_string(), stat(2, 100)),
+            ("older.txt".to_string(), stat(3, 200)),
+            ("resized.txt".to_string(), stat(9, 100)),
+            ("same.txt".to_string(), stat(5, 100)),
+            ("extra.txt".to_string(), stat(6, 100)),
+        ]);
+        let report = classify(source, destination);
+        let got: Vec<(&str, ListDestChange)> = report
+            .entries
+            .iter()
+            .map(|e| (e.path.as_str(), e.change))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("extra.txt", ListDestChange::DestinationOnly),
+                ("new.txt", ListDestChange::NewOnSource),
+                ("newer.txt", ListDestChange::NewerOnSource),
+                ("older.txt", ListDestChange::NewerOnDestination),
+                ("resized.txt", ListDestChange::SizeDiffers),
+            ]
+        );
+        assert_eq!(report.unchanged, 1);
+    }
+}
@@ -14,6 +14,7 @@
 //! `LocalMirrorOptions` from its Verify form — same shape, no clap
 //! coupling.
 
+use blit_core::remote::transfer::RemoteTransferProgress;
 use blit_core::transfer_session::{LocalMirrorOptions, LocalMirrorSummary};
 use eyre::{Context, Result};
 use std::path::Path;
@@ -32,9 +33,20 @@ pub async fn run(
     src: &Path,
     dst: &Path,
     options: LocalMirrorOptions,
+) -> Result<LocalMirrorSummary> {
+    run_with_progress(src, dst, options, None).await
+}
+
+/// [`run`] feeding `progress` the same w6-1 events a remote transfer
+/// reports, for the CLI's `--numeric-progress` monitor.
+pub async fn run_with_progress(
+    src: &Path,
+    dst: &Path,
+    options: LocalMirrorOptions,
+    progress: Option<RemoteTransferProgress>,
 ) -> Result<LocalMirrorSummary> {
     let mirror = options.mirror;
-    blit_core::transfer_session::run_local_session(src, dst, options)
+    blit_core::transfer_session::run_local_session_with_progress(src, dst, options, progress)
         .await
         .with_context(|| {
             format!(
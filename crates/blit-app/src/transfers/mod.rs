@@ -13,6 +13,7 @@
 pub mod compare;
 pub mod dispatch;
 pub mod filter;
+pub mod list_dest;
 pub mod local;
 pub mod remote;
 pub mod resolution;
@@ -44,27 +44,37 @@
 //! retains only the clap-arg wrappers and presentation
 //! (progress monitor + JSON / human printers).
 
+use blit_core::enumeration::SymlinkDeref;
 use blit_core::generated::delegated_pull_error::Phase as DelegatedPullPhase;
 use blit_core::generated::delegated_pull_progress::Payload as DelegatedPayload;
 use blit_core::generated::{
     BytesProgress, ComparisonMode, DelegatedPullRequest, DelegatedPullStarted,
-    DelegatedPullSummary, FilterSpec, MirrorMode, RemoteSourceLocator, TransferSummary,
+    DelegatedPullSummary, DialRequest, FilterSpec, MirrorMode, RemoteSourceLocator, RenameRule,
+    TransferSummary,
 };
+use blit_core::remote::transfer::archive_source::ArchiveTransferSource;
 use blit_core::remote::transfer::operation_spec::{
     delegated_spec_from_options, DelegatedSpecOptions,
 };
 use blit_core::remote::transfer::session_client::{
     run_pull_session, run_push_session, PullSessionOptions, PushSessionOptions,
 };
-use blit_core::remote::transfer::source::{FsTransferSource, TransferSource};
+use blit_core::remote::transfer::source::{
+    FileCountWarning, FsTransferSource, SentFilesSource, TransferSource,
+};
 use blit_core::remote::transfer::{
-    RemoteTransferProgress, SessionPhaseRole, TransferLifecycleFailure, TransferLifecycleOutcome,
-    TransferLifecycleTrace,
+    ArchiveSink, RemoteTransferProgress, SessionPhaseRole, SharedStreamBytes, StreamBytes,
+    TransferLifecycleFailure, TransferLifecycleOutcome, TransferLifecycleTrace, TransferSink,
 };
 use blit_core::remote::{RemoteEndpoint, RemotePath};
+use blit_core::source_snapshot::SourceSnapshot;
+use blit_core::transfer_plan::PlanOptions;
+use blit_core::xattrs::XattrFilter;
 use eyre::{bail, eyre, Context, Result};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tonic::Code;
 
 /// Inputs for [`run_remote_push`]. Primitive fields only — no
@@ -89,6 +99,32 @@ pub struct PushExecution {
     pub source: PathBuf,
     pub remote: RemoteEndpoint,
     pub filter: Option<FilterSpec>,
+    /// `--rename` rules (`blit_core::path_rename::parse_rules`); they
+    /// ride `SessionOpen.rename` and this end's SOURCE applies them.
+    pub rename: Vec<RenameRule>,
+    /// `--unpack`: `source` is a tar archive whose members are the
+    /// tree to push ([`ArchiveTransferSource`]), not a file to copy.
+    pub unpack: bool,
+    /// `--copy-links` / `--copy-dirlinks` / `--copy-unsafe-links`: which
+    /// source symlinks the scan dereferences. Ignored with `unpack` (an archive has no
+    /// links to follow).
+    pub symlink_deref: SymlinkDeref,
+    /// `--safe-links`: drop source symlinks that resolve outside the
+    /// source tree instead of dereferencing them.
+    pub safe_links: bool,
+    /// `--vss` (Windows): push from a Volume Shadow Copy of the source's
+    /// volume ([`SourceSnapshot::create_vss`]), deleted after the
+    /// session. Ignored with `unpack`.
+    pub vss: bool,
+    /// `--snapshot` (Unix): push from a temporary read-only btrfs / APFS
+    /// snapshot ([`SourceSnapshot::create_fs`]); other filesystems warn
+    /// and push live. Ignored with `unpack` or `vss`.
+    pub snapshot: bool,
+    /// `--streams` / `--chunk-size` pins for this end's SOURCE dial.
+    pub dial_request: Option<DialRequest>,
+    /// `--shard-max-bytes` / `--shard-max-files` / `--no-shard` for this
+    /// end's SOURCE payload planner.
+    pub plan_options: PlanOptions,
     pub mirror_mode: bool,
     pub mirror_kind: MirrorMode,
     pub force_grpc: bool,
@@ -97,6 +133,22 @@ pub struct PushExecution {
     /// Explicitly discard Windows attributes and named data streams at the
     /// source before manifest emission.
     pub drop_windows_metadata: bool,
+    /// `--crtimes`: carry creation times and set them on the destination
+    /// where its platform allows.
+    pub crtimes: bool,
+    /// `--super`: carry owners and modes for the destination to apply.
+    pub owner: bool,
+    /// `--fake-super`: read the carried owners from the source's
+    /// `user.blit.stat` xattrs ([`blit_core::ownership`]).
+    pub fake_super: bool,
+    /// `--manifest-checkpoint`: checkpoint the source scan's directory
+    /// listings here so a re-run after a failure reuses them
+    /// ([`FsTransferSource::with_manifest_checkpoint`]). Deleted once
+    /// the push succeeds. Ignored with `unpack`.
+    pub manifest_checkpoint: Option<PathBuf>,
+    /// `--prune-empty-dirs`: send no directory that holds no file once
+    /// the filter has run.
+    pub prune_empty_dirs: bool,
     /// otp-10a: negotiate the resume block phase (`--resume`) — changed
     /// destination partials are patched block-wise instead of
     /// re-transferred whole. `resume_block_size` in bytes; 0 lets the
@@ -117,6 +169,42 @@ pub struct PushExecution {
     /// axis, riding `SessionOpen.ignore_existing` (otp-10b-2; the old
     /// push driver silently ignored the flag).
     pub ignore_existing: bool,
+    /// Skip files the destination does not already have
+    /// (`--existing`), riding `SessionOpen.existing_only`. Only updates,
+    /// never creates.
+    pub existing_only: bool,
+    /// `--max-files`: refuse a source of more files before sending any,
+    /// riding `SessionOpen.max_files` (contract v18). 0 = no cap.
+    pub max_files: u64,
+    /// `--checksum-seed`: key the resume block hashes, riding
+    /// `SessionOpen.checksum_seed` (contract v19). 0 = unkeyed.
+    pub checksum_seed: u32,
+    /// `--chmod` clauses, riding `SessionOpen.chmod` (contract v20).
+    /// Empty = modes cross as scanned.
+    pub chmod: String,
+    /// `--chown` override, riding `SessionOpen.chown` (contract v21);
+    /// the daemon DESTINATION resolves and applies it. Empty = off.
+    pub chown: String,
+    /// `--xattrs` filter spec (contract v22): this SOURCE reads the
+    /// attributes it admits and the daemon sets them. Empty = off.
+    pub xattrs: String,
+    /// `--remote-option`s for the daemon DESTINATION (contract v23).
+    pub remote_options: HashMap<String, String>,
+    /// `--warn-files`: the local scan asks before going past the
+    /// threshold. `None` never asks.
+    pub file_count_warning: Option<FileCountWarning>,
+    /// `--metadata-only`: transfer no bytes; the DESTINATION repairs
+    /// mtime/mode on content-equal files only. `compare_mode` must be
+    /// `Checksum`.
+    pub metadata_only: bool,
+    /// `--reconnect`: ride a resumable control lane that survives a
+    /// control-stream drop for this long (contract v17).
+    pub reconnect: Option<Duration>,
+    /// `--remove-source-files`: once the session succeeds, delete each
+    /// source file whose bytes it sent ([`SentFilesSource`]). Skipped
+    /// and unchanged files stay, as do all directories. Ignored with
+    /// `unpack` (the CLI refuses the combination).
+    pub remove_source_files: bool,
     pub remote_label: String,
     pub lifecycle_trace: TransferLifecycleTrace,
 }
@@ -129,15 +217,58 @@ pub struct PushExecution {
 /// `remote_label` echoed back — the printer consumes it.
 /// `show_progress` is intentionally **not** here; it's a
 /// CLI-side presentation hint that the CLI threads directly
-/// into its own `DeferredPushState`.
+/// into its own `DeferredPushState`. `elapsed` is the wall time of the
+/// whole push (snapshot and session), for `--stats`.
+/// `source_files_removed` counts the deletions `remove_source_files`
+/// made (0 without it). `bandwidth` is the per-stream data-plane
+/// breakdown for `--stats` / `--json`.
 pub struct PushExecutionOutcome {
     pub summary: TransferSummary,
     pub destination: String,
+    pub elapsed: Duration,
+    pub source_files_removed: u64,
+    pub bandwidth: StreamBandwidth,
+}
+
+/// Bytes each TCP data-plane stream carried during one push session,
+/// and the session's wall time. Every stream shares the session's
+/// window, so a stream's throughput and the aggregate are both bytes
+/// over `elapsed`. Empty on the in-stream carrier.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamBandwidth {
+    pub streams: Vec<StreamBytes>,
+    pub elapsed: Duration,
+}
+
+impl StreamBandwidth {
+    /// Sum of every stream's payload bytes.
+    pub fn total_bytes(&self) -> u64 {
+        self.streams
+            .iter()
+            .fold(0_u64, |total, stream| total.saturating_add(stream.bytes))
+    }
+
+    /// `bytes` over the session's wall time; 0 for an instant session.
+    pub fn bytes_per_sec(&self, bytes: u64) -> u64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            (bytes as f64 / seconds) as u64
+        } else {
+            0
+        }
+    }
+
+    /// Aggregate throughput: the streams' summed bytes over the
+    /// session's wall time.
+    pub fn aggregate_bytes_per_sec(&self) -> u64 {
+        self.bytes_per_sec(self.total_bytes())
+    }
 }
 
 /// Run a remote push end-to-end (otp-10a: the push-shaped verb on the
 /// unified transfer session): wrap the local source root in an
-/// `FsTransferSource`, then initiate one SOURCE-role `Transfer`
+/// `FsTransferSource` (or, with `unpack`, index the archive as an
+/// `ArchiveTransferSource`), then initiate one SOURCE-role `Transfer`
 /// session against the destination daemon via `run_push_session`. No
 /// mirror-purge step exists on the push side — mirror deletes happen
 /// on the DESTINATION (the one delete rule, otp-6b) and surface
@@ -159,26 +290,90 @@ pub struct PushExecutionOutcome {
 /// ```
 ///
 /// Unlike the pull side, there is no need to split this into
-/// pre-/post-purge halves — push has no post-RPC destructive
-/// step on the caller's filesystem, so the monitor's lifetime
-/// already lines up cleanly with the RPC.
+/// pre-/post-purge halves — push's only post-RPC step on the
+/// caller's filesystem is `remove_source_files`, which reports no
+/// progress, so the monitor's lifetime already lines up cleanly
+/// with the RPC.
 pub async fn run_remote_push(
     execution: PushExecution,
     progress: Option<&RemoteTransferProgress>,
 ) -> Result<PushExecutionOutcome> {
-    let source: Arc<dyn TransferSource> = Arc::new(FsTransferSource::new(execution.source));
+    let start = Instant::now();
+    // Held to the end of the push: dropping it deletes the snapshot.
+    let snapshot = if (execution.vss || execution.snapshot) && !execution.unpack {
+        let live = execution.source.clone();
+        let vss = execution.vss;
+        tokio::task::spawn_blocking(move || {
+            if vss {
+                SourceSnapshot::create_vss(&live).map(Some)
+            } else {
+                SourceSnapshot::create_fs(&live)
+            }
+        })
+        .await
+        .map_err(|err| eyre!("source snapshot worker failed: {err}"))??
+    } else {
+        None
+    };
+    let live_root = execution.source.clone();
+    let source: Arc<dyn TransferSource> = if execution.unpack {
+        let archive = execution.source;
+        Arc::new(
+            tokio::task::spawn_blocking(move || ArchiveTransferSource::open(&archive))
+                .await
+                .map_err(|err| eyre!("archive index worker failed: {err}"))??,
+        )
+    } else {
+        let read_root = match snapshot.as_ref() {
+            Some(snapshot) => snapshot.read_root().to_path_buf(),
+            None => execution.source,
+        };
+        Arc::new(
+            FsTransferSource::new(read_root)
+                .with_symlink_deref(execution.symlink_deref)
+                .with_safe_links(execution.safe_links)
+                .with_sort_order(execution.plan_options.sort_order)
+                .with_crtimes(execution.crtimes)
+                .with_owner(execution.owner, execution.fake_super)
+                .with_xattrs(XattrFilter::from_spec(&execution.xattrs)?)
+                .with_manifest_checkpoint(execution.manifest_checkpoint.clone()),
+        )
+    };
 
+    let sent: Arc<std::sync::Mutex<Vec<String>>> = Arc::default();
+    let source: Arc<dyn TransferSource> = if execution.remove_source_files && !execution.unpack {
+        Arc::new(SentFilesSource::new(source, Arc::clone(&sent)))
+    } else {
+        source
+    };
+
+    let stream_bytes: SharedStreamBytes = Arc::default();
     let options = PushSessionOptions {
         compare_mode: execution.compare_mode,
         ignore_existing: execution.ignore_existing,
+        existing_only: execution.existing_only,
+        max_files: execution.max_files,
+        checksum_seed: execution.checksum_seed,
+        chmod: execution.chmod,
+        chown: execution.chown,
+        xattrs: execution.xattrs,
+        remote_options: execution.remote_options,
+        file_count_warning: execution.file_count_warning,
+        metadata_only: execution.metadata_only,
         require_complete_scan: execution.require_complete_scan,
         drop_windows_metadata: execution.drop_windows_metadata,
+        preserve_crtimes: execution.crtimes,
+        preserve_owner: execution.owner,
+        prune_empty_dirs: execution.prune_empty_dirs,
+        plan_options: execution.plan_options,
         // `--force-grpc`: the session's in-stream byte carrier is the
         // gRPC-fallback lane (otp-8).
         in_stream_bytes: execution.force_grpc,
         resume: execution.resume,
         resume_block_size: execution.resume_block_size,
         filter: execution.filter,
+        rename: execution.rename,
+        dial_request: execution.dial_request,
         mirror_enabled: execution.mirror_mode,
         mirror_kind: if execution.mirror_mode {
             execution.mirror_kind
@@ -188,19 +383,93 @@ pub async fn run_remote_push(
         progress: progress.cloned(),
         trace_data_plane: execution.trace_data_plane,
         lifecycle_trace: execution.lifecycle_trace,
+        reconnect: execution.reconnect,
+        stream_bytes: Some(Arc::clone(&stream_bytes)),
         ..PushSessionOptions::default()
     };
 
+    let session_start = Instant::now();
     let summary = run_push_session(&execution.remote, source, options)
         .await
         .with_context(|| format!("pushing to {}", execution.remote_label))?;
+    let bandwidth = StreamBandwidth {
+        streams: std::mem::take(
+            &mut *stream_bytes
+                .lock()
+                .map_err(|err| eyre!("stream byte accumulator poisoned: {err}"))?,
+        ),
+        elapsed: session_start.elapsed(),
+    };
+    // Only a successful session gets here: every recorded file landed.
+    drop(snapshot);
+    if let Some(checkpoint) = &execution.manifest_checkpoint {
+        match std::fs::remove_file(checkpoint) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => log::warn!(
+                "removing manifest checkpoint {}: {err}",
+                checkpoint.display()
+            ),
+        }
+    }
+    let sent = std::mem::take(
+        &mut *sent
+            .lock()
+            .map_err(|err| eyre!("sent-file recorder poisoned: {err}"))?,
+    );
+    let source_files_removed = if sent.is_empty() {
+        0
+    } else {
+        tokio::task::spawn_blocking(move || remove_sent_files(&live_root, sent))
+            .await
+            .map_err(|err| eyre!("source-file removal worker failed: {err}"))??
+    };
 
     Ok(PushExecutionOutcome {
         summary,
         destination: execution.remote_label,
+        elapsed: start.elapsed(),
+        source_files_removed,
+        bandwidth,
     })
 }
 
+/// `--remove-source-files`: delete the sent files under the live source
+/// `root` (a single-file push records the empty path — `root` itself),
+/// leaving directories in place. A file already gone is not an error;
+/// any other failure is reported after every file has been tried.
+fn remove_sent_files(root: &Path, mut sent: Vec<String>) -> Result<u64> {
+    sent.sort();
+    sent.dedup();
+    let mut removed = 0u64;
+    let mut failed = Vec::new();
+    for rel in &sent {
+        let path = if rel.is_empty() {
+            root.to_path_buf()
+        } else {
+            root.join(rel)
+        };
+        match std::fs::remove_file(&path) {
+            Ok(()) => removed += 1,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => failed.push(format!("{}: {err}", path.display())),
+        }
+    }
+    if !failed.is_empty() {
+        let preview: Vec<_> = failed.iter().take(5).cloned().collect();
+        let mut message = format!(
+            "the push succeeded, but {} source file(s) could not be removed: {}",
+            failed.len(),
+            preview.join(", ")
+        );
+        if failed.len() > preview.len() {
+            message.push_str(&format!(" (and {} more)", failed.len() - preview.len()));
+        }
+        bail!(message);
+    }
+    Ok(removed)
+}
+
 /// Inputs for [`run_remote_pull`] (otp-10b-2: the pull-shaped verb on
 /// the unified transfer session). Primitive fields only — no clap, no
 /// presentation. CLI builds this from `&TransferArgs`; the TUI builds
@@ -217,7 +486,18 @@ pub struct PullExecution {
     /// relative path, so the session writes AT this path — the old
     /// pull's exact convention).
     pub dest_root: PathBuf,
+    /// `dest_root` is a pipe ([`is_pipe`]): write the pulled tree into
+    /// it as one tar ([`ArchiveSink`]) instead of landing files. The
+    /// session runs against an empty scratch root, so every file is
+    /// sent; mirror, resume and `--metadata-only` have nothing to act
+    /// on and the CLI refuses them.
+    pub pack: bool,
     pub filter: Option<FilterSpec>,
+    /// `--rename` rules — applied by the daemon SOURCE, so the paths
+    /// this end receives are already rewritten.
+    pub rename: Vec<RenameRule>,
+    /// `--streams` / `--chunk-size` pins for the daemon SOURCE's dial.
+    pub dial_request: Option<DialRequest>,
     pub mirror_mode: bool,
     pub mirror_kind: MirrorMode,
     pub force_grpc: bool,
@@ -229,12 +509,41 @@ pub struct PullExecution {
     /// Explicitly discard Windows attributes and named data streams at the
     /// source before manifest emission.
     pub drop_windows_metadata: bool,
+    /// See [`PushExecution::crtimes`]; the daemon SOURCE stamps them.
+    pub crtimes: bool,
+    /// See [`PushExecution::owner`]; the daemon SOURCE stamps them.
+    pub owner: bool,
+    /// `--fake-super`: record the pulled owners in `user.blit.stat`
+    /// xattrs instead of chowning.
+    pub fake_super: bool,
+    /// See [`PushExecution::prune_empty_dirs`]; the daemon SOURCE prunes.
+    pub prune_empty_dirs: bool,
     pub resume: bool,
     pub resume_block_size: u32,
     /// See [`PushExecution::compare_mode`] — the same mapping serves
     /// both verbs; move verbs pass `move_comparison_mode`'s result.
     pub compare_mode: ComparisonMode,
     pub ignore_existing: bool,
+    /// See [`PushExecution::existing_only`].
+    pub existing_only: bool,
+    /// See [`PushExecution::max_files`]; the daemon SOURCE enforces it.
+    pub max_files: u64,
+    /// See [`PushExecution::checksum_seed`].
+    pub checksum_seed: u32,
+    /// See [`PushExecution::chmod`]; the daemon SOURCE applies it.
+    pub chmod: String,
+    /// See [`PushExecution::chown`]; this end, the DESTINATION,
+    /// applies it.
+    pub chown: String,
+    /// See [`PushExecution::xattrs`]; the daemon SOURCE reads them and
+    /// this end sets them.
+    pub xattrs: String,
+    /// `--remote-option`s for the daemon SOURCE (contract v23).
+    pub remote_options: HashMap<String, String>,
+    /// See [`PushExecution::metadata_only`].
+    pub metadata_only: bool,
+    /// See [`PushExecution::reconnect`].
+    pub reconnect: Option<Duration>,
     pub remote_label: String,
     pub lifecycle_trace: TransferLifecycleTrace,
 }
@@ -244,10 +553,12 @@ pub struct PullExecution {
 /// scorer — here that's us) plus the destination root echoed back for
 /// the printer. Mirror deletions ran in-session (the one delete rule,
 /// otp-6b) and are scored in `summary.entries_deleted` — there is no
-/// post-transfer purge step and no separate purge stats.
+/// post-transfer purge step and no separate purge stats. `elapsed` is
+/// the session's wall time, for `--stats`.
 pub struct PullVerbOutcome {
     pub summary: TransferSummary,
     pub dest_root: PathBuf,
+    pub elapsed: Duration,
 }
 
 /// Run a remote pull end-to-end (otp-10b-2): initiate one
@@ -268,6 +579,14 @@ pub async fn run_remote_pull(
     execution: PullExecution,
     progress: Option<&RemoteTransferProgress>,
 ) -> Result<PullVerbOutcome> {
+    let start = Instant::now();
+    // Held to the end of the pull: dropping it removes the scratch root.
+    let (session_root, archive) = if execution.pack {
+        let (scratch, sink) = open_pull_archive(&execution).await?;
+        (scratch.path().join("root"), Some((scratch, sink)))
+    } else {
+        (execution.dest_root.clone(), None)
+    };
     // No pre-created destination directories: the session sink creates
     // each write target's parent chain itself (including the
     // single-file case, where `dest_root` IS the target file path and
@@ -278,14 +597,28 @@ pub async fn run_remote_pull(
     let options = PullSessionOptions {
         compare_mode: execution.compare_mode,
         ignore_existing: execution.ignore_existing,
+        existing_only: execution.existing_only,
+        max_files: execution.max_files,
+        checksum_seed: execution.checksum_seed,
+        chmod: execution.chmod,
+        chown: execution.chown,
+        xattrs: execution.xattrs,
+        remote_options: execution.remote_options,
+        metadata_only: execution.metadata_only,
         require_complete_scan: execution.require_complete_scan,
         drop_windows_metadata: execution.drop_windows_metadata,
+        preserve_crtimes: execution.crtimes,
+        preserve_owner: execution.owner,
+        fake_super: execution.fake_super,
+        prune_empty_dirs: execution.prune_empty_dirs,
         // `--force-grpc`: the session's in-stream byte carrier is the
         // gRPC-fallback lane (otp-8).
         in_stream_bytes: execution.force_grpc,
         resume: execution.resume,
         resume_block_size: execution.resume_block_size,
         filter: execution.filter,
+        rename: execution.rename,
+        dial_request: execution.dial_request,
         mirror_enabled: execution.mirror_mode,
         mirror_kind: if execution.mirror_mode {
             execution.mirror_kind
@@ -296,8 +629,13 @@ pub async fn run_remote_pull(
         progress: progress.cloned(),
         trace_data_plane: execution.trace_data_plane,
         lifecycle_trace: execution.lifecycle_trace,
+        reconnect: execution.reconnect,
+        receive_sink: archive
+            .as_ref()
+            .map(|(_, sink)| Arc::clone(sink) as Arc<dyn TransferSink>),
+        ..PullSessionOptions::default()
     };
-    let outcome = run_pull_session(&execution.remote, execution.dest_root.clone(), options)
+    let outcome = run_pull_session(&execution.remote, session_root, options)
         .await
         .with_context(|| {
             format!(
@@ -306,13 +644,70 @@ pub async fn run_remote_pull(
                 execution.dest_root.display()
             )
         })?;
+    if let Some((_, sink)) = &archive {
+        sink.close()
+            .await
+            .with_context(|| format!("closing the archive on {}", execution.dest_root.display()))?;
+    }
 
     Ok(PullVerbOutcome {
         summary: outcome.summary,
         dest_root: execution.dest_root,
+        elapsed: start.elapsed(),
     })
 }
 
+/// Whether `path` is a pipe — a FIFO, or a `/dev/stdout` that is one
+/// (the metadata follows the link) — which a pull writes a tar into
+/// ([`PullExecution::pack`]) rather than treating as a file to replace.
+pub fn is_pipe(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        std::fs::metadata(path).is_ok_and(|meta| meta.file_type().is_fifo())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        false
+    }
+}
+
+/// Open [`PullExecution::pack`]'s pipe and the empty scratch root the
+/// session diffs against. Opening a FIFO for writing blocks until a
+/// reader opens the other end. A single-file source's member is named
+/// after the remote file.
+async fn open_pull_archive(
+    execution: &PullExecution,
+) -> Result<(tempfile::TempDir, Arc<ArchiveSink>)> {
+    let pipe = execution.dest_root.clone();
+    let out = tokio::task::spawn_blocking(move || {
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(&pipe)
+            .with_context(|| format!("opening {} for writing", pipe.display()))
+    })
+    .await
+    .context("pipe open task panicked")??;
+    let scratch = tempfile::Builder::new()
+        .prefix(".blit-pack-")
+        .tempdir()
+        .context("creating the pull's scratch root")?;
+    let root_name = match &execution.remote.path {
+        RemotePath::Module { rel_path, .. } | RemotePath::Root { rel_path } => rel_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned()),
+        RemotePath::Discovery => None,
+    }
+    .unwrap_or_else(|| "file".to_string());
+    let sink = ArchiveSink::new(
+        Box::new(std::io::BufWriter::new(out)),
+        scratch.path().join("root"),
+        root_name,
+    );
+    Ok((scratch, Arc::new(sink)))
+}
+
 /// Inputs for [`run_delegated_pull`]. Primitive fields only —
 /// no clap, no presentation. CLI builds this from
 /// `&TransferArgs`; the future TUI builds it directly.
@@ -336,11 +731,13 @@ pub struct DelegatedPullExecution {
 
 /// Output of [`run_delegated_pull`]. The `src` / `dst` endpoints
 /// are echoed back so the caller's printer can reference them
-/// without keeping its own copies.
+/// without keeping its own copies. `elapsed` is the wall time from
+/// connecting to the destination until its summary, for `--stats`.
 pub struct DelegatedPullOutcome {
     pub summary: DelegatedPullSummary,
     pub src: RemoteEndpoint,
     pub dst: RemoteEndpoint,
+    pub elapsed: Duration,
 }
 
 /// Per-stream state tracked while consuming `BytesProgress`
@@ -504,6 +901,7 @@ pub async fn run_delegated_pull<F>(
 where
     F: FnMut(&DelegatedPullStarted),
 {
+    let start = Instant::now();
     let lifecycle_trace = execution.lifecycle_trace.clone();
     lifecycle_trace.attach_initiator_role(SessionPhaseRole::Destination);
     let spec = delegated_spec_from_options(&execution.src, &execution.options)?;
@@ -643,6 +1041,7 @@ where
         summary,
         src: execution.src,
         dst: execution.dst,
+        elapsed: start.elapsed(),
     })
 }
 
@@ -835,6 +1234,37 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn stream_bandwidth_aggregate_is_summed_stream_bytes_over_elapsed() {
+        use blit_core::remote::transfer::StreamId;
+        let bandwidth = StreamBandwidth {
+            streams: vec![
+                StreamBytes {
+                    stream: StreamId(0),
+                    bytes: 6_000,
+                },
+                StreamBytes {
+                    stream: StreamId(1),
+                    bytes: 2_000,
+                },
+                StreamBytes {
+                    stream: StreamId(3),
+                    bytes: 4_000,
+                },
+            ],
+            elapsed: Duration::from_secs(4),
+        };
+        assert_eq!(bandwidth.total_bytes(), 12_000);
+        assert_eq!(bandwidth.aggregate_bytes_per_sec(), 3_000);
+        assert_eq!(bandwidth.bytes_per_sec(6_000), 1_500);
+        // An instant session reports no rate rather than dividing by zero.
+        let instant = StreamBandwidth {
+            elapsed: Duration::ZERO,
+            ..bandwidth
+        };
+        assert_eq!(instant.aggregate_bytes_per_sec(), 0);
+    }
+
     #[tokio::test]
     async fn run_delegated_pull_until_started_refuses_non_detach() {
         // Guard: if a caller asks for the "exit after Started"
@@ -13,6 +13,9 @@
 //!   local directory)? Matches rsync `main.c:get_local_name`.
 //! - [`resolve_destination`] — applies both above to produce the
 //!   final target path.
+//! - [`resolve_relative_destination`] — the `--relative` (rsync
+//!   `-R`) alternative: the source's own path, from its `/./`
+//!   anchor or its root, is reproduced under the destination.
 //!
 //! These were `pub(crate)` in the CLI; widened to `pub` for the
 //! cross-crate consumers. `source_basename` (private helper) is
@@ -22,7 +25,9 @@
 
 use crate::endpoints::Endpoint;
 use blit_core::remote::RemotePath;
-use std::ffi::OsString;
+use eyre::{bail, Result};
+use std::ffi::{OsStr, OsString};
+use std::path::{Component, Path, PathBuf};
 
 /// Returns true if the raw CLI source string specifies "copy
 /// contents" mode, matching rsync's DOTDIR_NAME classification
@@ -153,16 +158,66 @@ pub fn resolve_destination(
     let Some(basename) = source_basename(src) else {
         return dst;
     };
+    join_endpoint(dst, &basename)
+}
+
+/// The source path `--relative` reproduces under the destination
+/// (rsync `-R`): everything after the first `/./` anchor when the
+/// path has one, otherwise the whole path less its root. `.`
+/// components are dropped; a `..` is refused, since it would place
+/// files above the destination. A remote source's path is taken
+/// inside its module (or root export).
+pub fn implied_source_path(src: &Endpoint) -> Result<PathBuf> {
+    let path: &Path = match src {
+        Endpoint::Local(p) => p,
+        Endpoint::Remote(r) => match &r.path {
+            RemotePath::Module { rel_path, .. } | RemotePath::Root { rel_path } => rel_path,
+            RemotePath::Discovery => Path::new(""),
+        },
+    };
+    // `Path::components` folds interior `.` away, so the anchor is
+    // found on the raw string.
+    let anchored = path
+        .to_str()
+        .and_then(|raw| raw.find("/./").map(|at| Path::new(&raw[at + 3..])));
+    let mut implied = PathBuf::new();
+    for component in anchored.unwrap_or(path).components() {
+        match component {
+            Component::Normal(part) => implied.push(part),
+            Component::ParentDir => bail!(
+                "--relative cannot reproduce '{}': the path contains '..'",
+                path.display()
+            ),
+            Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+        }
+    }
+    Ok(implied)
+}
+
+/// `--relative` destination: the destination taken as the anchor the
+/// [`implied_source_path`] is rebuilt under. Trailing-slash rules do
+/// not apply — `/a/b/src` and `/a/b/src/` both land at `DST/a/b/src`.
+///
+/// Examples:
+///   blit copy -R /a/b/src      /dst/  ->  /dst/a/b/src/...
+///   blit copy -R /a/./b/src    /dst/  ->  /dst/b/src/...
+///   blit copy -R /a/b/f.txt    /dst/  ->  /dst/a/b/f.txt
+pub fn resolve_relative_destination(src: &Endpoint, dst: Endpoint) -> Result<Endpoint> {
+    let implied = implied_source_path(src)?;
+    Ok(join_endpoint(dst, implied.as_os_str()))
+}
+
+fn join_endpoint(dst: Endpoint, tail: &OsStr) -> Endpoint {
     match dst {
-        Endpoint::Local(p) => Endpoint::Local(p.join(&basename)),
+        Endpoint::Local(p) => Endpoint::Local(p.join(tail)),
         Endpoint::Remote(mut r) => {
             r.path = match r.path {
                 RemotePath::Module { module, rel_path } => RemotePath::Module {
                     module,
-                    rel_path: rel_path.join(&basename),
+                    rel_path: rel_path.join(tail),
                 },
                 RemotePath::Root { rel_path } => RemotePath::Root {
-                    rel_path: rel_path.join(&basename),
+                    rel_path: rel_path.join(tail),
                 },
                 other => other,
             };
@@ -394,4 +449,52 @@ mod tests {
             _ => panic!("expected local endpoint"),
         }
     }
+
+    #[test]
+    fn implied_source_path_drops_the_root_or_starts_at_the_anchor() {
+        let local = |p: &str| implied_source_path(&Endpoint::Local(PathBuf::from(p))).unwrap();
+        assert_eq!(local("/a/b/src"), PathBuf::from("a/b/src"));
+        assert_eq!(local("/a/b/src/"), PathBuf::from("a/b/src"));
+        assert_eq!(local("/a/./b/src"), PathBuf::from("b/src"));
+        assert_eq!(local("./src/f.txt"), PathBuf::from("src/f.txt"));
+        assert_eq!(local("."), PathBuf::new());
+        assert!(implied_source_path(&Endpoint::Local(PathBuf::from("/a/../b"))).is_err());
+    }
+
+    #[test]
+    fn resolve_relative_destination_rebuilds_the_source_path() {
+        let src = Endpoint::Remote(RemoteEndpoint {
+            host: "h".into(),
+            port: 9031,
+            path: RemotePath::Module {
+                module: "m".into(),
+                rel_path: PathBuf::from("Games/./DOOM/maps"),
+            },
+        });
+        let resolved =
+            resolve_relative_destination(&src, Endpoint::Local(PathBuf::from("/dst"))).unwrap();
+        match resolved {
+            Endpoint::Local(p) => assert_eq!(p, PathBuf::from("/dst/DOOM/maps")),
+            _ => panic!("expected local endpoint"),
+        }
+
+        let src = Endpoint::Local(PathBuf::from("/a/b/src/"));
+        let dst = Endpoint::Remote(RemoteEndpoint {
+            host: "h".into(),
+            port: 9031,
+            path: RemotePath::Module {
+                module: "m".into(),
+                rel_path: PathBuf::from("backup"),
+            },
+        });
+        match resolve_relative_destination(&src, dst).unwrap() {
+            Endpoint::Remote(r) => match r.path {
+                RemotePath::Module { rel_path, .. } => {
+                    assert_eq!(rel_path, PathBuf::from("backup/a/b/src"))
+                }
+                _ => panic!("expected module path"),
+            },
+            _ => panic!("expected remote endpoint"),
+        }
+    }
 }
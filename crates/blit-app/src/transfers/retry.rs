@@ -8,6 +8,10 @@
 //! When resume is enabled, eligible partial files continue at block
 //! granularity. The audit-1c stall timeout turns an infinite stall into the
 //! bounded, retryable failure this loop catches.
+//!
+//! [`run_resumable`] is the last step for a `--resume` run: an interrupt or a
+//! transient failure that outlived the retries becomes a distinct,
+//! resumable outcome instead of a plain error.
 
 use std::future::Future;
 use std::time::Duration;
@@ -50,6 +54,48 @@ where
     }
 }
 
+/// Exit status of a `--resume` transfer that stopped early and left its
+/// partial files in place for the next run to continue (sysexits
+/// `EX_TEMPFAIL`: try again later).
+pub const RESUMABLE_EXIT_CODE: u8 = 75;
+
+/// Why a `--resume` transfer stopped before it completed.
+#[derive(Debug)]
+pub enum Interrupted {
+    /// The operator interrupted it (Ctrl-C).
+    Signal,
+    /// A transient failure [`is_retryable`] accepts, left over once
+    /// `--retry` was exhausted.
+    Transient(eyre::Report),
+}
+
+/// Race `transfer` against `interrupt`. With `resume` set, the interrupt
+/// firing or a retryable failure returns `Ok(Some(..))`: the transfer
+/// stopped where the next `--resume` run can continue it. Without
+/// `resume` the interrupt is not watched and every failure stays an
+/// error. A fatal failure is always an error.
+pub async fn run_resumable<T, I>(
+    resume: bool,
+    transfer: T,
+    interrupt: I,
+) -> Result<Option<Interrupted>>
+where
+    T: Future<Output = Result<()>>,
+    I: Future<Output = ()>,
+{
+    if !resume {
+        return transfer.await.map(|()| None);
+    }
+    tokio::select! {
+        result = transfer => match result {
+            Ok(()) => Ok(None),
+            Err(err) if is_retryable(&err) => Ok(Some(Interrupted::Transient(err))),
+            Err(err) => Err(err),
+        },
+        () = interrupt => Ok(Some(Interrupted::Signal)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,6 +168,34 @@ mod tests {
         assert_eq!(calls.get(), 1, "retries=0 ⇒ no retries even if retryable");
     }
 
+    #[tokio::test]
+    async fn resumable_run_reports_an_interrupt_or_a_transient_failure() {
+        let outcome = run_resumable(true, std::future::pending(), async {})
+            .await
+            .unwrap();
+        assert!(matches!(outcome, Some(Interrupted::Signal)));
+
+        let transient = async { Err(io_err(io::ErrorKind::ConnectionReset)) };
+        let outcome = run_resumable(true, transient, std::future::pending())
+            .await
+            .unwrap();
+        assert!(matches!(outcome, Some(Interrupted::Transient(_))));
+
+        let done = run_resumable(true, async { Ok(()) }, std::future::pending());
+        assert!(done.await.unwrap().is_none());
+
+        let fatal = async { Err(eyre::eyre!("invalid argument")) };
+        assert!(run_resumable(true, fatal, std::future::pending())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn without_resume_an_interrupted_run_stays_an_error() {
+        let transient = async { Err(io_err(io::ErrorKind::ConnectionReset)) };
+        assert!(run_resumable(false, transient, async {}).await.is_err());
+    }
+
     #[tokio::test]
     async fn exhausts_retry_budget_then_returns_last_error() {
         let calls = Cell::new(0u32);
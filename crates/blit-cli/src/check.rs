@@ -33,11 +33,15 @@ pub async fn run_check(args: &CheckArgs) -> Result<ExitCode> {
     let filter = filter::build(&FilterInputs {
         include: &args.include,
         exclude: &args.exclude,
+        include_from: &args.include_from,
+        exclude_from: &args.exclude_from,
         files_from: args.files_from.as_ref(),
         min_size: args.min_size.as_deref(),
         max_size: args.max_size.as_deref(),
         min_age: args.min_age.as_deref(),
         max_age: args.max_age.as_deref(),
+        no_hidden: false,
+        exclude_attributes: None,
     })?;
 
     let use_checksum = args.checksum;
@@ -0,0 +1,114 @@
+use crate::cli::CleanArgs;
+use blit_app::admin::clean::{self, CleanPlan};
+use blit_app::endpoints::{parse_endpoint_or_local, Endpoint};
+use blit_core::fs_enum::parse_duration;
+use blit_core::remote::endpoint::format_host_port;
+use eyre::{Context, Result};
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// Entries listed in the text preview before it is cut short.
+const PREVIEW_ENTRIES: usize = 20;
+
+pub async fn run_clean(args: CleanArgs) -> Result<()> {
+    let older_than = parse_duration(&args.older_than)
+        .with_context(|| format!("invalid --older-than '{}'", args.older_than))?;
+    let endpoint = parse_endpoint_or_local(&args.target);
+    let (plan, location) = match &endpoint {
+        Endpoint::Local(path) => (
+            clean::plan_local(path, older_than).await?,
+            path.display().to_string(),
+        ),
+        Endpoint::Remote(remote) => (
+            clean::plan_remote(remote, older_than).await?,
+            format_host_port(&remote.host, remote.port),
+        ),
+    };
+
+    let entries_removed = if plan.stale.is_empty() || args.dry_run {
+        0
+    } else {
+        if !args.yes {
+            print_preview(&plan.stale);
+            print!(
+                "Remove {} from {}? [y/N]: ",
+                stale_entries(plan.stale.len()),
+                args.target
+            );
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let decision = input.trim().to_ascii_lowercase();
+            if !(decision == "y" || decision == "yes") {
+                println!("Aborted.");
+                return Ok(());
+            }
+        }
+        execute(&endpoint, &plan).await?
+    };
+
+    if args.json {
+        #[derive(Serialize)]
+        struct CleanResult<'a> {
+            target: &'a str,
+            older_than_seconds: u64,
+            dry_run: bool,
+            stale: &'a [String],
+            entries_removed: u64,
+        }
+        let result = CleanResult {
+            target: &args.target,
+            older_than_seconds: older_than.as_secs(),
+            dry_run: args.dry_run,
+            stale: &plan.stale,
+            entries_removed,
+        };
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else if plan.stale.is_empty() {
+        println!(
+            "Nothing to clean: no stale staging entries among {} on {}.",
+            plan.scanned, location
+        );
+    } else if args.dry_run {
+        println!(
+            "Would remove {} on {}:",
+            stale_entries(plan.stale.len()),
+            location
+        );
+        for path in &plan.stale {
+            println!("  - {path}");
+        }
+    } else {
+        println!(
+            "Cleaned {} ({} removed) on {}.",
+            stale_entries(plan.stale.len()),
+            entries_removed,
+            location
+        );
+    }
+
+    Ok(())
+}
+
+async fn execute(endpoint: &Endpoint, plan: &CleanPlan) -> Result<u64> {
+    match endpoint {
+        Endpoint::Local(path) => clean::execute_local(path, plan),
+        Endpoint::Remote(remote) => clean::execute_remote(remote, plan).await,
+    }
+}
+
+fn print_preview(stale: &[String]) {
+    for path in stale.iter().take(PREVIEW_ENTRIES) {
+        println!("  - {path}");
+    }
+    if stale.len() > PREVIEW_ENTRIES {
+        println!("  ... and {} more", stale.len() - PREVIEW_ENTRIES);
+    }
+}
+
+fn stale_entries(count: usize) -> String {
+    match count {
+        1 => "1 stale staging entry".to_string(),
+        count => format!("{count} stale staging entries"),
+    }
+}
@@ -1,3 +1,5 @@
+use blit_core::copy::SparseMode;
+use blit_core::enumeration::{SortOrder, SymlinkDeref};
 use clap::{Args, Parser, Subcommand};
 use std::io::IsTerminal;
 use std::path::PathBuf;
@@ -33,6 +35,9 @@ EQUIVALENCE MODEL:
 If you need full filesystem-tree equivalence (symlinks-as-targets,
 empty-dir presence, etc.), use `diff -r` or a similar tool.";
 
+/// `--warn-files` default: a source past a million files is asked about.
+const DEFAULT_WARN_FILES: u64 = 1_000_000;
+
 #[derive(Parser)]
 #[command(name = "blit")]
 #[command(version = blit_core::transfer_session::session_build_id())]
@@ -56,6 +61,56 @@ pub struct Cli {
     #[arg(long, global = true, value_name = "PATH", hide_short_help = true)]
     pub diagnostics_counter_file: Option<PathBuf>,
 
+    /// Fixed send/receive buffer size for every data-plane TCP socket
+    /// (e.g. `4Mi`). Default: the tuner's size where one is computed,
+    /// kernel default elsewhere.
+    #[arg(long, global = true, value_name = "SIZE", hide_short_help = true)]
+    pub tcp_buffer_size: Option<String>,
+
+    /// Idle time before the first TCP keepalive probe on data-plane
+    /// sockets (e.g. `20s`). Default 60s; lower it when a firewall
+    /// drops idle flows sooner.
+    #[arg(long, global = true, value_name = "DURATION", hide_short_help = true)]
+    pub tcp_keepalive: Option<String>,
+
+    /// Bind every outgoing data-plane connection to this network
+    /// interface (e.g. `eth0`); a link-local IPv6 daemon address given
+    /// without a zone (`[fe80::1]`) is scoped to it.
+    #[arg(long, global = true, value_name = "NAME", hide_short_help = true)]
+    pub data_plane_interface: Option<String>,
+
+    /// Originate every outgoing connection, control and data plane,
+    /// from this local IP address (e.g. `10.0.0.5`) on a multi-homed
+    /// host. Daemon addresses of the other IP family are not dialed.
+    #[arg(long, global = true, value_name = "IP", hide_short_help = true)]
+    pub address: Option<std::net::IpAddr>,
+
+    /// Deadline for reaching a daemon's control port (e.g. `5s`),
+    /// covering name resolution and the TCP handshake. Default 30s.
+    #[arg(long, global = true, value_name = "DURATION")]
+    pub contimeout: Option<String>,
+
+    /// Largest gRPC message this invocation sends or accepts on the
+    /// control connection (e.g. `128Mi`). Default 64Mi; at least 4Mi.
+    #[arg(long, global = true, value_name = "SIZE", hide_short_help = true)]
+    pub max_message_size: Option<String>,
+
+    /// Payload bytes per control-plane frame when file data rides the
+    /// gRPC stream (e.g. `4Mi`). Default 1Mi; a daemon older than the
+    /// message-size settings accepts at most 4Mi frames.
+    #[arg(long, global = true, value_name = "SIZE", hide_short_help = true)]
+    pub control_chunk_size: Option<String>,
+
+    /// Manifest entries the receiving side diffs per batch. Default 128.
+    #[arg(long, global = true, value_name = "N", hide_short_help = true)]
+    pub manifest_batch_entries: Option<String>,
+
+    /// Encoded manifest bytes the receiving side diffs per batch (e.g.
+    /// `256Ki`), whichever of this and the entry count fills first.
+    /// Default 1Mi; between 4Ki and just under 4Mi.
+    #[arg(long, global = true, value_name = "SIZE", hide_short_help = true)]
+    pub manifest_batch_size: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -68,6 +123,10 @@ pub enum Commands {
     Mirror(TransferArgs),
     /// Move files (copy + remove source, rsync-style slash semantics)
     Move(TransferArgs),
+    /// Delete remote destination entries the local source lacks, copying nothing (the delete half of mirror)
+    Prune(PruneArgs),
+    /// Remove staging files crashed transfers left behind (local or remote)
+    Clean(CleanArgs),
     /// Discover daemons advertising via mDNS
     Scan(ScanArgs),
     /// List modules exported by a remote daemon
@@ -150,6 +209,10 @@ pub struct JobsCancelArgs {
     /// Transfer id to cancel — typically obtained from
     /// `blit jobs list <remote>`.
     pub transfer_id: String,
+    /// File holding the daemon's `[daemon] admin_token`. Lets the
+    /// cancel reach transfers started from other hosts.
+    #[arg(long, value_name = "PATH")]
+    pub admin_token_file: Option<PathBuf>,
     /// Output as JSON
     #[arg(long)]
     pub json: bool,
@@ -161,6 +224,24 @@ pub enum DiagnosticsCommand {
     Perf(PerfArgs),
     /// Emit a diagnostic snapshot for a SRC -> DEST invocation (no transfer performed)
     Dump(DiagnosticsDumpArgs),
+    /// Push a synthetic tree through an in-process loopback daemon over the data plane and the gRPC fallback, reporting throughput
+    Bench(DiagnosticsBenchArgs),
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct DiagnosticsBenchArgs {
+    /// Total bytes spread across the large files (e.g. 256Mi, 1G)
+    #[arg(long, value_name = "SIZE", default_value = "256Mi")]
+    pub size: String,
+    /// Number of large files sharing --size
+    #[arg(long, value_name = "N", default_value_t = 16)]
+    pub files: usize,
+    /// Number of additional 4 KiB files (exercises the small-file path)
+    #[arg(long, value_name = "N", default_value_t = 256)]
+    pub small_files: usize,
+    /// Emit JSON instead of the default human-readable report
+    #[arg(long)]
+    pub json: bool,
 }
 
 #[derive(Args, Clone, Debug)]
@@ -216,6 +297,15 @@ pub struct TransferArgs {
     /// Emit detailed transfer diagnostics
     #[arg(long, short = 'v')]
     pub verbose: bool,
+    /// Print nothing but errors and warnings: no banner, progress or
+    /// summary. The exit status still reports the outcome. Combines with
+    /// --numeric-progress.
+    #[arg(
+        long,
+        short = 'q',
+        conflicts_with_all = ["verbose", "progress", "json", "stats", "info", "list_dest", "detach"]
+    )]
+    pub quiet: bool,
     /// Show an interactive progress indicator.
     ///
     /// Auto-enabled when stdout is a TTY (and --json is not set) so
@@ -224,6 +314,12 @@ pub struct TransferArgs {
     /// force-enable when stdout is not a TTY (e.g. under `tee`).
     #[arg(long, short = 'p')]
     pub progress: bool,
+    /// Report progress for scripts: one `PERCENT% DONE/TOTAL bytes` line
+    /// on stderr, rewritten in place each second, ending with a 100%
+    /// line once the transfer completes. Replaces the interactive
+    /// indicator.
+    #[arg(long, conflicts_with_all = ["progress", "json", "detach"])]
+    pub numeric_progress: bool,
     /// Skip confirmation prompt for destructive operations (mirror deletions, move)
     #[arg(long, short = 'y')]
     pub yes: bool,
@@ -231,6 +327,41 @@ pub struct TransferArgs {
     /// transfer summary is written to stdout as a JSON object.
     #[arg(long)]
     pub json: bool,
+    /// Print a transfer-statistics block after the summary: files
+    /// examined and transferred, total vs transferred size, elapsed time
+    /// and effective throughput (rsync `--stats`). `--json` already
+    /// carries these totals.
+    #[arg(long, conflicts_with = "json")]
+    pub stats: bool,
+    /// Choose which kinds of output to show, as a comma list (rsync
+    /// `--info`): `progress` (the progress indicator), `name` (one line
+    /// per transferred file), `del` (one `deleting PATH` line per mirror
+    /// deletion) and `stats` (as --stats). Adds to what the other flags
+    /// show; `-v` implies `name`. Deletions are named only where this
+    /// end deletes — a local copy or a pull; a push reports the count.
+    #[arg(
+        long,
+        value_name = "FLAGS",
+        value_delimiter = ',',
+        value_parser = ["progress", "name", "del", "stats"]
+    )]
+    pub info: Vec<String>,
+    /// Render byte counts in the summary and `--stats` block with binary
+    /// units (KiB, MiB, GiB) instead of raw byte counts.
+    #[arg(long)]
+    pub human_readable: bool,
+    /// Compare the source against a remote destination and print what
+    /// differs (new, newer on either side, destination-only) without
+    /// transferring. Read-only: unlike `--dry-run` no transfer session
+    /// opens on the daemon.
+    #[arg(long, conflicts_with_all = ["dry_run", "detach"])]
+    pub list_dest: bool,
+    /// Reproduce the source's path under the destination (rsync `-R`):
+    /// `/a/b/src` lands at `DST/a/b/src`. A `/./` in the source marks
+    /// where the reproduced path starts (`/a/./b/src` lands at
+    /// `DST/b/src`). Replaces the trailing-slash rules.
+    #[arg(long, short = 'R', conflicts_with = "unpack")]
+    pub relative: bool,
 
     // -- Comparison options: how blit decides which files to transfer.
     /// Force checksum comparison of files (slower but more accurate)
@@ -245,6 +376,37 @@ pub struct TransferArgs {
     /// Skip files that already exist on the destination (regardless of differences)
     #[arg(long, conflicts_with = "force", help_heading = "Comparison")]
     pub ignore_existing: bool,
+    /// Overwrite policy for files already on the destination: `never`
+    /// skips them (as --ignore-existing), `always` re-copies them (as
+    /// --ignore-times), `if-newer` keeps the default size + mtime
+    /// comparison
+    #[arg(
+        long,
+        value_name = "POLICY",
+        value_parser = ["never", "if-newer", "always"],
+        conflicts_with_all = ["checksum", "size_only", "ignore_times", "ignore_existing", "force", "metadata_only"],
+        help_heading = "Comparison"
+    )]
+    pub overwrite: Option<String>,
+    /// Skip files that do not already exist on the destination: update
+    /// existing files only, and create no new files or directories
+    #[arg(long, help_heading = "Comparison")]
+    pub existing: bool,
+    /// Copy no bytes: a destination file whose content already matches
+    /// (size + checksum) gets the source's mtime and permissions; every
+    /// other file is left alone. Implies --checksum. Not for mirror,
+    /// move, or remote-to-remote transfers.
+    #[arg(
+        long,
+        conflicts_with_all = ["size_only", "ignore_times", "force", "ignore_existing", "resume"],
+        help_heading = "Comparison"
+    )]
+    pub metadata_only: bool,
+    /// Keep each hashed file's Blake3 in a cache under the config dir,
+    /// keyed by path, size and mtime, so later checksum runs skip
+    /// rehashing unchanged files. Needs --checksum or --metadata-only.
+    #[arg(long, help_heading = "Comparison")]
+    pub checksum_cache: bool,
     /// Force exact mirror even if destination files are newer (dangerous)
     #[arg(long, help_heading = "Comparison")]
     pub force: bool,
@@ -258,15 +420,120 @@ pub struct TransferArgs {
 
     // -- Reliability options: recovery + retries.
     /// Use block-level comparison to continue eligible partial files for
-    /// local, push, pull, and remote-to-remote transfers
+    /// local, push, pull, and remote-to-remote transfers. An interrupted
+    /// run keeps its partial files and exits 75; run it again to continue
     #[arg(long, help_heading = "Reliability")]
     pub resume: bool,
+    /// Key the --resume block hashes with N, so a partial file's
+    /// contents cannot be crafted ahead of time to match them. Both
+    /// ends use the seed for this session only; 0 leaves them unkeyed.
+    /// Push and pull only.
+    #[arg(long, value_name = "N", help_heading = "Reliability")]
+    pub checksum_seed: Option<u32>,
+    /// Skip source files another process holds locked (a Windows
+    /// sharing or lock violation) instead of failing the transfer; they
+    /// are reported as unreadable. Local transfers only.
+    #[arg(long, help_heading = "Reliability")]
+    pub skip_locked: bool,
+    /// Windows: read the source from a Volume Shadow Copy of its volume —
+    /// a consistent point-in-time view with no locked files — deleted
+    /// when the transfer ends. Needs an elevated shell. Local sources
+    /// only (local copy or push).
+    #[arg(long, help_heading = "Reliability", conflicts_with = "snapshot")]
+    pub vss: bool,
+    /// Read the source from a temporary read-only filesystem snapshot
+    /// (btrfs subvolume snapshot on Linux, APFS local snapshot on macOS),
+    /// deleted when the transfer ends. Other filesystems warn and read
+    /// the live tree. Usually needs root. Local sources only.
+    #[arg(long, help_heading = "Reliability")]
+    pub snapshot: bool,
+    /// Stage every written file and rename the whole batch into place
+    /// only once the transfer succeeded (mirror deletions run after the
+    /// batch lands), so readers never see a half-updated tree. Local
+    /// transfers only.
+    #[arg(long, conflicts_with = "resume", help_heading = "Reliability")]
+    pub delay_updates: bool,
+    /// Stage --delay-updates writes in DIR instead of inside the
+    /// destination (e.g. on faster storage). DIR must be on the
+    /// destination's filesystem; otherwise blit warns and stages beside
+    /// the destination, so the final rename stays atomic.
+    #[arg(
+        long,
+        value_name = "DIR",
+        requires = "delay_updates",
+        help_heading = "Reliability"
+    )]
+    pub temp_dir: Option<PathBuf>,
     /// Discard Windows file attributes and named data streams.
     ///
     /// This is a lossy cross-platform escape hatch. Without it, Blit refuses
     /// to copy Windows metadata to a destination that cannot preserve it.
     #[arg(long, help_heading = "Reliability")]
     pub drop_windows_metadata: bool,
+    /// Preserve file creation times (birthtime). Set where the
+    /// destination platform allows it (macOS, Windows); elsewhere the
+    /// flag is a no-op. Not for remote-to-remote transfers.
+    #[arg(long, help_heading = "Reliability")]
+    pub crtimes: bool,
+    /// Preserve owner and group: carry each entry's uid, gid and mode
+    /// and chown the destination copy to them (which needs privilege
+    /// there, unless the daemon module fakes ownership). Not for
+    /// remote-to-remote transfers or --unpack.
+    #[arg(long = "super", help_heading = "Reliability")]
+    pub super_user: bool,
+    /// Like --super, but this host's end keeps ownership in a
+    /// `user.blit.stat` xattr instead of on the inode: a push reads the
+    /// owners it sends from there, a pull or local copy records them
+    /// there rather than chowning. For backups kept without privilege.
+    #[arg(long, help_heading = "Reliability")]
+    pub fake_super: bool,
+    /// Set destination permissions regardless of the source: rsync-style
+    /// comma-separated clauses, each octal (`644`) or symbolic (`g+r`,
+    /// `u=rwX`), optionally prefixed `D` (directories only) or `F`
+    /// (files only). Repeatable; clauses apply in order. Not for
+    /// remote-to-remote transfers.
+    #[arg(long, value_name = "SPEC", help_heading = "Reliability")]
+    pub chmod: Vec<String>,
+    /// Give every transferred entry this owner and group (`USER:GROUP`,
+    /// `USER` or `:GROUP`; names or numeric ids, resolved on the
+    /// destination), overriding any preserved ownership. Needs root on
+    /// the destination; without it the override is skipped with a
+    /// warning. Not for remote-to-remote transfers.
+    #[arg(long, value_name = "USER:GROUP", help_heading = "Reliability")]
+    pub chown: Option<String>,
+    /// Carry extended attributes in the `user` namespace. Other
+    /// namespaces stay behind unless admitted by --xattrs-all or
+    /// --xattrs-include; setting one the destination refuses is a
+    /// warning. Blit's own `user.blit.*` attributes never transfer.
+    /// Unix only; not for remote-to-remote transfers.
+    #[arg(long, short = 'X', help_heading = "Reliability")]
+    pub xattrs: bool,
+    /// Like --xattrs, but carry every namespace (`security`, `trusted`,
+    /// `system`, ...), less any --xattrs-exclude.
+    #[arg(long, help_heading = "Reliability")]
+    pub xattrs_all: bool,
+    /// Also carry namespace NS (`trusted`) or the single attribute NS
+    /// (`security.capability`). Repeatable; implies --xattrs.
+    #[arg(long, value_name = "NS", help_heading = "Reliability")]
+    pub xattrs_include: Vec<String>,
+    /// Never carry namespace or attribute NS, even when included.
+    /// Repeatable; implies --xattrs.
+    #[arg(long, value_name = "NS", help_heading = "Reliability")]
+    pub xattrs_exclude: Vec<String>,
+    /// Ask the daemon end of a push or pull to apply a behavior toggle
+    /// (`KEY=VALUE`; `fsync=true` has a receiving daemon sync every file
+    /// it writes). Repeatable. The daemon refuses a bad value for a key
+    /// it knows and ignores, with a warning, a key it does not.
+    #[arg(long, value_name = "KEY=VALUE", help_heading = "Reliability")]
+    pub remote_option: Vec<String>,
+    /// Record the source scan's directory listings in FILE as it runs.
+    /// If the push fails, re-running it with the same FILE takes every
+    /// directory unchanged since then from the checkpoint instead of
+    /// reading it again (entries are still stat'ed, so metadata is
+    /// current). The file is deleted once the push succeeds. Push only;
+    /// not with --copy-links / --copy-dirlinks or --unpack.
+    #[arg(long, value_name = "FILE", help_heading = "Reliability")]
+    pub manifest_checkpoint: Option<PathBuf>,
     /// Retry the transfer up to N times on a transient failure (network
     /// drop, stall timeout). Each retry re-runs destination comparison, so
     /// normal comparison skips files now complete; flags that force copying
@@ -287,6 +554,28 @@ pub struct TransferArgs {
         help_heading = "Reliability"
     )]
     pub wait: u64,
+    /// Keep the control connection resumable: if it drops mid-transfer,
+    /// reconnect and carry on where the transfer was, for up to DURATION
+    /// (e.g. 30s; the daemon holds at most 10m), instead of failing.
+    /// Unlike --retry nothing is compared or sent again. Push and pull
+    /// only.
+    #[arg(long, value_name = "DURATION", help_heading = "Reliability")]
+    pub reconnect: Option<String>,
+    /// Refuse the transfer, before anything is copied, if the source
+    /// holds more than N files (after filtering). Not for
+    /// remote-to-remote transfers.
+    #[arg(long, value_name = "N", help_heading = "Reliability")]
+    pub max_files: Option<u64>,
+    /// Once the source scan passes N files, ask on the terminal whether
+    /// to go on; with --yes or without a terminal, only warn. 0 turns
+    /// it off. Local and push sources only.
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = DEFAULT_WARN_FILES,
+        help_heading = "Reliability"
+    )]
+    pub warn_files: u64,
 
     // -- Filtering: restrict which files are eligible for transfer.
     // Filters apply identically to all source/destination combinations
@@ -299,6 +588,14 @@ pub struct TransferArgs {
     /// any include match is required; excludes still apply on top.
     #[arg(long, action = clap::ArgAction::Append, value_name = "PATTERN", help_heading = "Filtering")]
     pub include: Vec<String>,
+    /// Read --exclude patterns from FILE, one per line (blank lines and
+    /// # comments skipped; repeatable)
+    #[arg(long, action = clap::ArgAction::Append, value_name = "FILE", help_heading = "Filtering")]
+    pub exclude_from: Vec<PathBuf>,
+    /// Read --include patterns from FILE, one per line (blank lines and
+    /// # comments skipped; repeatable)
+    #[arg(long, action = clap::ArgAction::Append, value_name = "FILE", help_heading = "Filtering")]
+    pub include_from: Vec<PathBuf>,
     /// Only transfer files listed in FILE (one relative path per line, # comments allowed)
     #[arg(long, value_name = "FILE", help_heading = "Filtering")]
     pub files_from: Option<PathBuf>,
@@ -314,6 +611,64 @@ pub struct TransferArgs {
     /// Only transfer files newer than this duration (e.g. 1h, 7d, 30m)
     #[arg(long, value_name = "DURATION", help_heading = "Filtering")]
     pub max_age: Option<String>,
+    /// Skip files and directories carrying the Windows hidden attribute
+    /// (same as `--exclude-attributes hidden`). Windows only; accepted
+    /// with a warning elsewhere. Local and push sources only.
+    #[arg(long, help_heading = "Filtering")]
+    pub no_hidden: bool,
+    /// Skip entries carrying any of these Windows file attributes
+    /// (comma-separated: readonly, hidden, system, archive, temporary,
+    /// offline). A matching directory is skipped with its subtree.
+    /// Windows only; accepted with a warning elsewhere.
+    #[arg(long, value_name = "ATTRS", help_heading = "Filtering")]
+    pub exclude_attributes: Option<String>,
+    /// Don't create directories that hold no transferred file once the
+    /// filters have run (rsync's `-m`). Not for remote-to-remote
+    /// transfers.
+    #[arg(long, help_heading = "Filtering")]
+    pub prune_empty_dirs: bool,
+    /// Rewrite each relative path before it is written at the
+    /// destination: `<from-regex>=<to>`, `$1`-style captures allowed
+    /// (repeatable; rules apply in order, first match each). Rewrites
+    /// that escape the destination root or collide are errors.
+    #[arg(long, action = clap::ArgAction::Append, value_name = "FROM=TO", help_heading = "Filtering")]
+    pub rename: Vec<String>,
+    /// Treat the source as a tar archive (plain or gzip) and transfer its
+    /// members as the tree, unpacking it at the destination. Push only:
+    /// the destination must be a remote endpoint, and the archive's
+    /// contents land directly under it (no basename is appended).
+    #[arg(long, help_heading = "Filtering")]
+    pub unpack: bool,
+    /// After a successful push, delete each source file whose bytes were
+    /// sent, leaving directories in place (rsync's --remove-source-files).
+    /// Files skipped as unchanged or unreadable are kept. Push only.
+    #[arg(long)]
+    pub remove_source_files: bool,
+    /// Follow every symlink in the source and transfer what it points at
+    /// (rsync `-L`). Symlinks are skipped by default; a symlink loop is
+    /// detected and skipped. Local and push sources only.
+    #[arg(long, short = 'L', help_heading = "Filtering")]
+    pub copy_links: bool,
+    /// Follow only source symlinks that point at directories, descending
+    /// into them as real directories (rsync `--copy-dirlinks`). Symlinks
+    /// to files are still skipped. Local and push sources only.
+    #[arg(long, conflicts_with = "copy_links", help_heading = "Filtering")]
+    pub copy_dirlinks: bool,
+    /// Follow only source symlinks whose referent resolves outside the
+    /// source tree, transferring what they point at (rsync
+    /// `--copy-unsafe-links`). Links within the tree are still skipped.
+    /// Local and push sources only.
+    #[arg(
+        long,
+        conflicts_with_all = ["copy_links", "copy_dirlinks"],
+        help_heading = "Filtering"
+    )]
+    pub copy_unsafe_links: bool,
+    /// Never follow a source symlink whose referent resolves outside the
+    /// source tree, even under `--copy-links` / `--copy-dirlinks`; such
+    /// links are dropped (rsync `--safe-links`).
+    #[arg(long, conflicts_with = "copy_unsafe_links", help_heading = "Filtering")]
+    pub safe_links: bool,
 
     // -- Performance / debug knobs — niche, kept at the bottom so new
     // users aren't distracted by them.
@@ -360,6 +715,88 @@ pub struct TransferArgs {
     /// real-transfer profiling never learns from null-sink runs.
     #[arg(long, help_heading = "Performance / debug")]
     pub null: bool,
+    /// Pin the remote data plane to exactly N TCP streams instead of
+    /// letting the dial start at its floor and ramp. Clamped to the
+    /// receiving end's advertised stream limit (32 by default).
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        help_heading = "Performance / debug"
+    )]
+    pub streams: Option<u32>,
+    /// Pin the remote data-plane chunk size (e.g. 4Mi) instead of
+    /// letting the dial ramp it. Clamped to 64KiB..the receiver's
+    /// advertised chunk limit (64Mi by default).
+    #[arg(long, value_name = "SIZE", help_heading = "Performance / debug")]
+    pub chunk_size: Option<String>,
+    /// Use SIZE (e.g. 256Ki; 4Ki..64Mi) as the copy buffer of local
+    /// streaming copies and as the --resume block size, replacing the
+    /// derived sizes. Remote daemons clamp the resume block to their
+    /// carrier's bounds.
+    #[arg(long, value_name = "SIZE", help_heading = "Performance / debug")]
+    pub block_size: Option<String>,
+    /// Cap each tar shard (the bundle small files travel in) at SIZE
+    /// bytes (e.g. 2Mi), replacing the derived target. Local sources only.
+    #[arg(
+        long,
+        value_name = "SIZE",
+        conflicts_with = "no_shard",
+        help_heading = "Performance / debug"
+    )]
+    pub shard_max_bytes: Option<String>,
+    /// Cap each tar shard at N files, replacing the derived count.
+    /// Local sources only.
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        conflicts_with = "no_shard",
+        help_heading = "Performance / debug"
+    )]
+    pub shard_max_files: Option<u32>,
+    /// Never bundle small files into tar shards: every file travels as
+    /// its own record. Local sources only.
+    #[arg(long, help_heading = "Performance / debug")]
+    pub no_shard: bool,
+    /// Order the source scan and the tar-shard planner work in: `name`
+    /// sorts each directory's entries byte-wise by name, so the same
+    /// tree always yields the same manifest and byte-identical shards;
+    /// `none` (default) keeps the faster filesystem listing order.
+    /// Local sources only.
+    #[arg(
+        long,
+        value_name = "ORDER",
+        default_value = "none",
+        value_parser = ["name", "none"],
+        help_heading = "Performance / debug"
+    )]
+    pub sort_order: String,
+    /// Leave source holes and all-zero blocks unallocated at the
+    /// destination, skipping the clone / zero-copy fast paths that don't
+    /// report them. Local copies only.
+    #[arg(long, help_heading = "Performance / debug")]
+    pub sparse: bool,
+    /// Write every byte, zeros included, so the destination is fully
+    /// allocated even when the source is sparse. Local copies only.
+    #[arg(long, conflicts_with = "sparse", help_heading = "Performance / debug")]
+    pub no_sparse: bool,
+    /// Reserve each copied file's full size at the destination before
+    /// writing it, failing early when space runs out. Local copies only.
+    #[arg(long, conflicts_with = "sparse", help_heading = "Performance / debug")]
+    pub preallocate: bool,
+    /// Once the copy succeeds, replace destination files with identical
+    /// content (and the same mode and mtime) by hard links to one copy,
+    /// to save space on backup targets. Local copies on Unix only.
+    #[arg(long)]
+    pub dedupe: bool,
+    /// Reference directory for unchanged files: a file DIR holds at the
+    /// same relative path with the source's size and mtime is cloned
+    /// copy-on-write from it instead of copied from the source (rsync's
+    /// `--copy-dest`, with reflinks). Files it cannot clone are copied
+    /// as usual. Local copies only.
+    #[arg(long, value_name = "DIR", conflicts_with = "resume")]
+    pub reflink_dest: Option<PathBuf>,
 
     // -- Hidden flags (don't appear in --help).
     /// Limit worker threads (advanced debugging only)
@@ -372,20 +809,84 @@ pub struct TransferArgs {
 
 impl TransferArgs {
     /// Effective progress setting: explicit `--progress` wins; otherwise
-    /// enable automatically when stdout is a TTY and `--json` is off. This
-    /// matches rsync/rclone/restic defaults so a first-time interactive
-    /// user isn't staring at a silent terminal for 60+ seconds on a big
+    /// enable automatically when stdout is a TTY and none of `--json`,
+    /// `--quiet` or `--numeric-progress` is set. This matches
+    /// rsync/rclone/restic defaults so a first-time interactive user
+    /// isn't staring at a silent terminal for 60+ seconds on a big
     /// transfer, while piped/redirected stdout keeps scripts unaffected.
     pub fn effective_progress(&self) -> bool {
-        if self.progress {
+        if self.progress || self.info_has("progress") {
             return true;
         }
-        if self.json {
+        if self.json || self.quiet || self.numeric_progress {
             return false;
         }
         std::io::stdout().is_terminal()
     }
 
+    /// Whether `--info` lists `category`.
+    fn info_has(&self, category: &str) -> bool {
+        self.info.iter().any(|listed| listed == category)
+    }
+
+    /// One line per transferred file: `--info=name`, or `-v`.
+    pub fn show_names(&self) -> bool {
+        self.verbose || self.info_has("name")
+    }
+
+    /// One `deleting PATH` line per mirror deletion: `--info=del`.
+    pub fn show_deletions(&self) -> bool {
+        self.info_has("del")
+    }
+
+    /// The `--stats` block: `--stats`, or `--info=stats`.
+    pub fn show_stats(&self) -> bool {
+        self.stats || self.info_has("stats")
+    }
+
+    /// Whether any `--xattrs*` flag asks for extended attributes.
+    pub fn wants_xattrs(&self) -> bool {
+        self.xattrs
+            || self.xattrs_all
+            || !self.xattrs_include.is_empty()
+            || !self.xattrs_exclude.is_empty()
+    }
+
+    /// `--copy-links` / `--copy-dirlinks` / `--copy-unsafe-links` as the
+    /// scan's symlink policy.
+    pub fn symlink_deref(&self) -> SymlinkDeref {
+        if self.copy_links {
+            SymlinkDeref::All
+        } else if self.copy_dirlinks {
+            SymlinkDeref::Dirs
+        } else if self.copy_unsafe_links {
+            SymlinkDeref::Unsafe
+        } else {
+            SymlinkDeref::None
+        }
+    }
+
+    /// `--sparse` / `--no-sparse` as the copy engine's hole policy.
+    pub fn sparse_mode(&self) -> SparseMode {
+        if self.sparse {
+            SparseMode::Always
+        } else if self.no_sparse {
+            SparseMode::Never
+        } else {
+            SparseMode::Auto
+        }
+    }
+
+    /// `--ignore-existing`, or `--overwrite never`.
+    pub fn skip_existing(&self) -> bool {
+        self.ignore_existing || self.overwrite.as_deref() == Some("never")
+    }
+
+    /// `--ignore-times`, or `--overwrite always`.
+    pub fn overwrite_always(&self) -> bool {
+        self.ignore_times || self.overwrite.as_deref() == Some("always")
+    }
+
     /// True when `--delete-scope all` was passed. Maps onto
     /// `MirrorMode::All` on the wire — every destination file absent
     /// from the (filtered) source set is purged, including files
@@ -393,6 +894,15 @@ impl TransferArgs {
     pub fn delete_scope_all(&self) -> bool {
         self.delete_scope.eq_ignore_ascii_case("all")
     }
+
+    /// `--sort-order` as the scan and planner's [`SortOrder`].
+    pub fn sort_order(&self) -> SortOrder {
+        if self.sort_order.eq_ignore_ascii_case("name") {
+            SortOrder::Name
+        } else {
+            SortOrder::None
+        }
+    }
 }
 
 #[derive(Args, Clone, Debug)]
@@ -403,6 +913,10 @@ pub struct ScanArgs {
     /// Output as JSON
     #[arg(long)]
     pub json: bool,
+    /// Instead of discovering daemons, print a byte-stable manifest
+    /// (BLAKE3 digest, size, path per file) of this local tree
+    #[arg(long, value_name = "DIR", conflicts_with = "json")]
+    pub manifest: Option<PathBuf>,
 }
 
 #[derive(Args, Clone, Debug)]
@@ -419,6 +933,25 @@ pub struct ListArgs {
     pub target: String,
     #[arg(long)]
     pub json: bool,
+    /// Long listing: mode, size, modification time and name per entry
+    #[arg(long, short = 'l', conflicts_with = "json")]
+    pub long: bool,
+    /// Include entries whose names start with `.`
+    #[arg(long, short = 'a')]
+    pub all: bool,
+    /// Order entries by name, by size (largest first) or by
+    /// modification time (newest first)
+    #[arg(long, value_name = "KEY", default_value = "name", value_parser = ["name", "size", "time"])]
+    pub sort: String,
+    /// Show sizes in binary units (KiB, MiB, GiB) in the long listing
+    #[arg(long)]
+    pub human_readable: bool,
+    /// List the whole subtree as an indented tree
+    #[arg(long, conflicts_with_all = ["json", "long"])]
+    pub tree: bool,
+    /// With --tree, levels below the target to show (0 = unlimited)
+    #[arg(long, requires = "tree")]
+    pub max_depth: Option<u32>,
 }
 
 #[derive(Args, Clone, Debug)]
@@ -428,6 +961,11 @@ pub struct DuArgs {
     /// Max depth to traverse (0 = unlimited)
     #[arg(long)]
     pub max_depth: Option<u32>,
+    /// Skip entries matching this glob (repeatable); a matching
+    /// directory is not walked and adds nothing to any total, e.g.
+    /// `--exclude node_modules --exclude .git`
+    #[arg(long, value_name = "PATTERN")]
+    pub exclude: Vec<String>,
     /// Output as JSON
     #[arg(long)]
     pub json: bool,
@@ -454,6 +992,44 @@ pub struct RmArgs {
     pub json: bool,
 }
 
+#[derive(Args, Clone, Debug)]
+pub struct PruneArgs {
+    /// Local source directory whose contents are kept
+    pub source: String,
+    /// Remote destination directory to prune (e.g., server:/module/path)
+    pub destination: String,
+    /// List what would be deleted without deleting it
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Skip confirmation prompt
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct CleanArgs {
+    /// Directory to clean: a local path or a module path (e.g.,
+    /// server:/module/path)
+    pub target: String,
+    /// Only remove staging entries nothing has modified for this long
+    /// (e.g. 30m, 12h, 7d); a transfer still running keeps touching
+    /// its own
+    #[arg(long, value_name = "DURATION", default_value = "24h")]
+    pub older_than: String,
+    /// List what would be removed without removing it
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Skip confirmation prompt
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
 #[derive(Args, Clone, Debug)]
 pub struct FindArgs {
     /// Remote path to search (e.g., server:/module/path)
@@ -482,9 +1058,30 @@ pub struct FindArgs {
     /// Limit number of results
     #[arg(long)]
     pub limit: Option<u32>,
+    /// Skip entries matching this glob (repeatable); a matching
+    /// directory is not searched
+    #[arg(long, value_name = "PATTERN")]
+    pub exclude: Vec<String>,
     /// Output as JSON
     #[arg(long)]
     pub json: bool,
+    /// Render results as an indented tree under the start path
+    #[arg(long, conflicts_with = "json")]
+    pub tree: bool,
+    /// With --tree, levels below the start path to show (0 = unlimited)
+    #[arg(long, requires = "tree")]
+    pub max_depth: Option<u32>,
+    /// Delete the matched entries on the daemon. Matches files only
+    /// unless --dirs is given, in which case a matched directory is
+    /// removed with everything under it
+    #[arg(long, conflicts_with = "tree")]
+    pub delete: bool,
+    /// With --delete, list what would be deleted without deleting it
+    #[arg(long, requires = "delete")]
+    pub dry_run: bool,
+    /// With --delete, skip the confirmation prompt
+    #[arg(long, short = 'y', requires = "delete")]
+    pub yes: bool,
 }
 
 #[derive(Args, Clone, Debug)]
@@ -558,8 +1155,9 @@ pub struct ProfileArgs {
 ///     trees that differ only in their symlinks will be reported
 ///     identical.
 ///   - **Empty directories** are not part of the diff. Transfers
-///     don't preserve them today, so verifying their presence
-///     would report false negatives on legitimate trees.
+///     recreate them, but a destination written by an older build
+///     lacks them, so verifying their presence would report false
+///     negatives on otherwise equivalent trees.
 ///   - **File-vs-directory mismatches** at the same path produce a
 ///     diff entry on the entry that's a file (the directory side
 ///     contributes nothing on its own).
@@ -590,6 +1188,10 @@ pub struct CheckArgs {
     pub exclude: Vec<String>,
     #[arg(long, action = clap::ArgAction::Append, value_name = "PATTERN", help_heading = "Filtering")]
     pub include: Vec<String>,
+    #[arg(long, action = clap::ArgAction::Append, value_name = "FILE", help_heading = "Filtering")]
+    pub exclude_from: Vec<PathBuf>,
+    #[arg(long, action = clap::ArgAction::Append, value_name = "FILE", help_heading = "Filtering")]
+    pub include_from: Vec<PathBuf>,
     #[arg(long, value_name = "FILE", help_heading = "Filtering")]
     pub files_from: Option<PathBuf>,
     #[arg(long, value_name = "SIZE", help_heading = "Filtering")]
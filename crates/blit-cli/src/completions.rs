@@ -3,6 +3,7 @@ use blit_app::endpoints::{
     module_and_rel_path, parse_endpoint_or_local, rel_path_to_string, Endpoint,
 };
 use blit_core::generated::CompletionRequest;
+use blit_core::remote::error_category::RemoteError;
 use clap::CommandFactory;
 use eyre::{bail, Result};
 use std::path::Path;
@@ -70,8 +71,7 @@ async fn run_remote_completions(args: RemoteCompletionArgs) -> Result<()> {
     };
     let prefix = append_completion_prefix(&rel_path, args.prefix.as_deref());
 
-    let uri = remote.control_plane_uri();
-    let mut client = blit_app::client::connect_with_timeout(uri).await?;
+    let mut client = blit_app::client::connect_cached(&remote).await?;
 
     let response = client
         .complete_path(CompletionRequest {
@@ -81,7 +81,7 @@ async fn run_remote_completions(args: RemoteCompletionArgs) -> Result<()> {
             include_directories: include_dirs,
         })
         .await
-        .map_err(|status| eyre::eyre!(status.message().to_string()))?
+        .map_err(RemoteError::from)?
         .into_inner();
 
     if response.completions.is_empty() {
@@ -21,6 +21,12 @@ pub async fn run_df(args: DfArgs) -> Result<()> {
         println!("{}", serde_json::to_string_pretty(&stats)?);
     } else {
         println!("Module: {}", stats.module);
+        if let Some(mount) = &stats.mount_point {
+            match &stats.filesystem {
+                Some(fs) => println!("Mount : {mount} ({fs})"),
+                None => println!("Mount : {mount}"),
+            }
+        }
         println!(
             "Total: {} ({} bytes)",
             format_bytes(stats.total_bytes),
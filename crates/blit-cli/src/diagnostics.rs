@@ -1,7 +1,9 @@
-use crate::cli::{DiagnosticsDumpArgs, PerfArgs};
+use crate::cli::{DiagnosticsBenchArgs, DiagnosticsDumpArgs, PerfArgs};
 use crate::context::AppContext;
+use blit_app::diagnostics::bench::{run_bench, BenchOptions, BENCH_SMALL_FILE_BYTES};
 use blit_app::diagnostics::dump::{endpoint_display, endpoint_snapshot, same_device};
 use blit_app::diagnostics::perf;
+use blit_app::display::{format_bps, format_bytes};
 use blit_app::endpoints::parse_transfer_endpoint;
 use blit_app::transfers::resolution::{dest_is_container, resolve_destination, source_is_contents};
 use chrono::{DateTime, Utc};
@@ -144,6 +146,82 @@ pub fn run_diagnostics_perf(ctx: &mut AppContext, args: &PerfArgs) -> Result<()>
     Ok(())
 }
 
+/// Push a synthetic tree through an in-process loopback daemon over
+/// both carriers (`blit_app::diagnostics::bench::run_bench`) and print
+/// each one's throughput — a table, or one JSON document with `--json`.
+pub async fn run_diagnostics_bench(args: &DiagnosticsBenchArgs) -> Result<()> {
+    let options = BenchOptions {
+        total_bytes: blit_core::fs_enum::parse_size(&args.size)?,
+        files: args.files,
+        small_files: args.small_files,
+    };
+    if !args.json {
+        println!(
+            "Loopback bench: {} file(s) sharing {}, {} x {} small file(s)",
+            options.files,
+            format_bytes(options.total_bytes),
+            options.small_files,
+            format_bytes(BENCH_SMALL_FILE_BYTES),
+        );
+    }
+    let report = run_bench(&options).await?;
+
+    if args.json {
+        let runs: Vec<Value> = report
+            .runs
+            .iter()
+            .map(|run| {
+                json!({
+                    "carrier": run.carrier,
+                    "files": run.files,
+                    "bytes": run.bytes,
+                    "elapsed_ms": run.elapsed_ms,
+                    "bytes_per_second": run.bytes_per_second(),
+                    "in_stream_carrier_used": run.in_stream_carrier_used,
+                })
+            })
+            .collect();
+        let output = json!({
+            "tuning": report.tuning,
+            "runs": runs,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let tuning = &report.tuning;
+    println!(
+        "Tuning: chunk {}, prefetch {}, streams {} (max {}), tcp buffer {}",
+        format_bytes(tuning.chunk_bytes as u64),
+        tuning.prefetch,
+        tuning.initial_streams,
+        tuning.max_streams,
+        tuning
+            .tcp_buffer_bytes
+            .map(|bytes| format_bytes(bytes as u64))
+            .unwrap_or_else(|| "kernel default".to_string()),
+    );
+    for run in &report.runs {
+        let label = match run.carrier {
+            "data-plane" => "TCP data plane",
+            _ => "gRPC fallback",
+        };
+        println!(
+            "  {label:<15} {:>14}  ({} files, {} in {:.2}s){}",
+            format_bps(run.bytes_per_second()),
+            run.files,
+            format_bytes(run.bytes),
+            run.elapsed_ms as f64 / 1000.0,
+            if run.carrier == "data-plane" && run.in_stream_carrier_used {
+                "  [fell back to in-stream carrier]"
+            } else {
+                ""
+            },
+        );
+    }
+    Ok(())
+}
+
 /// Emit a diagnostic snapshot for a SRC/DEST pair without performing a
 /// transfer. Motivation: bug reporters and bisectors need a consistent
 /// way to answer "what did blit see when you ran this?" — parse results,
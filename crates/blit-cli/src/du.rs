@@ -18,10 +18,11 @@ pub async fn run_du(args: DuArgs) -> Result<()> {
     let (module, rel_path) = module_and_rel_path(&remote)?;
     let start_path = rel_path_to_string(&rel_path);
     let max_depth = args.max_depth.unwrap_or(0);
+    let exclude = args.exclude;
 
     if args.json {
         let mut rows: Vec<DiskUsageEntry> = Vec::new();
-        du::stream(&remote, module, start_path, max_depth, |entry| {
+        du::stream(&remote, module, start_path, max_depth, exclude, |entry| {
             rows.push(entry);
             Ok(())
         })
@@ -32,7 +33,7 @@ pub async fn run_du(args: DuArgs) -> Result<()> {
             "{:<40} {:>12} {:>8} {:>8}",
             "PATH", "BYTES", "FILES", "DIRS"
         );
-        du::stream(&remote, module, start_path, max_depth, |entry| {
+        du::stream(&remote, module, start_path, max_depth, exclude, |entry| {
             println!(
                 "{:<40} {:>12} {:>8} {:>8}",
                 entry.path, entry.bytes, entry.files, entry.dirs
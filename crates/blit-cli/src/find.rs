@@ -1,9 +1,19 @@
 use crate::cli::FindArgs;
 use blit_app::admin::find::{self, FindEntry, FindParams};
+use blit_app::admin::rm;
+use blit_app::display::tree_lines;
 use blit_app::endpoints::{
     module_and_rel_path, parse_endpoint_or_local, rel_path_to_string, Endpoint,
 };
+use blit_core::remote::endpoint::format_host_port;
+use blit_core::remote::RemoteEndpoint;
 use eyre::{bail, Result};
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// Entries listed in the `--delete` confirmation preview before it is
+/// cut short.
+const PREVIEW_ENTRIES: usize = 20;
 
 pub async fn run_find(args: FindArgs) -> Result<()> {
     let remote = match parse_endpoint_or_local(&args.target) {
@@ -17,6 +27,8 @@ pub async fn run_find(args: FindArgs) -> Result<()> {
     };
 
     let (module, rel_path) = module_and_rel_path(&remote)?;
+    // `--delete` with neither filter removes files only: a directory
+    // goes (with its contents) only when asked for by `--dirs`.
     let include_files = if args.files || args.dirs {
         args.files
     } else {
@@ -25,20 +37,48 @@ pub async fn run_find(args: FindArgs) -> Result<()> {
     let include_dirs = if args.files || args.dirs {
         args.dirs
     } else {
-        true
+        !args.delete
     };
 
+    let start_path = rel_path_to_string(&rel_path);
     let params = FindParams {
         module,
-        start_path: rel_path_to_string(&rel_path),
+        start_path: start_path.clone(),
         pattern: args.pattern.unwrap_or_default(),
         case_sensitive: !args.case_insensitive,
         include_files,
         include_directories: include_dirs,
         max_results: args.limit.unwrap_or(0),
+        exclude: args.exclude,
     };
 
-    if args.json {
+    if args.delete {
+        return delete_matches(
+            &args.target,
+            &remote,
+            params,
+            args.dry_run,
+            args.yes,
+            args.json,
+        )
+        .await;
+    }
+
+    if args.tree {
+        // Results stream in walk order, not tree order; buffer them so
+        // every directory prints ahead of its children.
+        let mut found = Vec::new();
+        find::stream(&remote, params, |entry| {
+            found.push((entry.path, entry.is_dir));
+            Ok(())
+        })
+        .await?;
+        let max_depth = args.max_depth.filter(|&d| d > 0).map(|d| d as usize);
+        println!("{}", args.target);
+        for line in tree_lines(&start_path, found, max_depth) {
+            println!("{line}");
+        }
+    } else if args.json {
         let mut rows: Vec<FindEntry> = Vec::new();
         find::stream(&remote, params, |entry| {
             rows.push(entry);
@@ -63,3 +103,96 @@ pub async fn run_find(args: FindArgs) -> Result<()> {
 
     Ok(())
 }
+
+async fn delete_matches(
+    target: &str,
+    remote: &RemoteEndpoint,
+    params: FindParams,
+    dry_run: bool,
+    yes: bool,
+    json: bool,
+) -> Result<()> {
+    let endpoint_display = format_host_port(&remote.host, remote.port);
+    let module = params.module.clone();
+    let matched = find::deletion_targets(remote, params).await?;
+
+    let entries_deleted = if matched.is_empty() || dry_run {
+        0
+    } else {
+        if !yes {
+            print_preview(&matched);
+            print!(
+                "Delete {} from {} on {}? [y/N]: ",
+                matched_entries(matched.len()),
+                target,
+                endpoint_display
+            );
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let decision = input.trim().to_ascii_lowercase();
+            if !(decision == "y" || decision == "yes") {
+                println!("Aborted.");
+                return Ok(());
+            }
+        }
+        rm::purge_batched(remote, &module, &matched).await?
+    };
+
+    if json {
+        #[derive(Serialize)]
+        struct FindDeleteResult<'a> {
+            target: &'a str,
+            host: &'a str,
+            port: u16,
+            dry_run: bool,
+            matched: &'a [String],
+            entries_deleted: u64,
+        }
+        let result = FindDeleteResult {
+            target,
+            host: &remote.host,
+            port: remote.port,
+            dry_run,
+            matched: &matched,
+            entries_deleted,
+        };
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else if matched.is_empty() {
+        println!("Nothing to delete: no entries matched on {endpoint_display}.");
+    } else if dry_run {
+        println!(
+            "Would delete {} on {}:",
+            matched_entries(matched.len()),
+            endpoint_display
+        );
+        for path in &matched {
+            println!("  - {path}");
+        }
+    } else {
+        println!(
+            "Deleted {} ({} removed) on {}.",
+            matched_entries(matched.len()),
+            entries_deleted,
+            endpoint_display
+        );
+    }
+
+    Ok(())
+}
+
+fn print_preview(matched: &[String]) {
+    for path in matched.iter().take(PREVIEW_ENTRIES) {
+        println!("  - {path}");
+    }
+    if matched.len() > PREVIEW_ENTRIES {
+        println!("  ... and {} more", matched.len() - PREVIEW_ENTRIES);
+    }
+}
+
+fn matched_entries(count: usize) -> String {
+    match count {
+        1 => "1 matched entry".to_string(),
+        count => format!("{count} matched entries"),
+    }
+}
@@ -45,7 +45,16 @@ async fn run_jobs_list(args: JobsListArgs) -> Result<()> {
 async fn run_jobs_cancel(args: JobsCancelArgs) -> Result<ExitCode> {
     let remote = RemoteEndpoint::parse(&args.remote)
         .with_context(|| format!("parsing remote endpoint '{}'", args.remote))?;
-    let outcome = jobs::cancel(&remote, &args.transfer_id).await?;
+    let admin_token = args
+        .admin_token_file
+        .as_ref()
+        .map(|path| {
+            std::fs::read_to_string(path)
+                .map(|token| token.trim().to_string())
+                .with_context(|| format!("reading admin token from {}", path.display()))
+        })
+        .transpose()?;
+    let outcome = jobs::cancel(&remote, &args.transfer_id, admin_token.as_deref()).await?;
     if args.json {
         print_cancel_json(&outcome);
     } else {
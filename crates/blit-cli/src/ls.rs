@@ -1,22 +1,124 @@
 use crate::cli::ListArgs;
-use blit_app::admin::ls::{self, DirEntry, LocalListing};
-use blit_app::display::format_bytes;
+use blit_app::admin::find::{self, FindParams};
+use blit_app::admin::ls::{self, DirEntry, ListSort, LocalListing};
+use blit_app::display::{format_bytes, format_mode, tree_lines};
 use blit_app::endpoints::{parse_endpoint_or_local, rel_path_to_string, Endpoint};
 use blit_core::remote::endpoint::{RemoteEndpoint, RemotePath};
+use chrono::{DateTime, Local};
 use eyre::{bail, Result};
 use std::path::Path;
 
 pub async fn run_ls(args: ListArgs) -> Result<()> {
     match parse_endpoint_or_local(&args.target) {
-        Endpoint::Local(path) => list_local_path(&path, args.json),
-        Endpoint::Remote(remote) => list_remote_path(remote, args.json).await,
+        Endpoint::Local(path) => list_local_path(&path, &args),
+        Endpoint::Remote(remote) => list_remote_path(remote, &args).await,
     }
 }
 
-fn list_local_path(path: &Path, json: bool) -> Result<()> {
-    let listing = ls::list_local(path)?;
+fn list_sort(args: &ListArgs) -> ListSort {
+    match args.sort.as_str() {
+        "size" => ListSort::Size,
+        "time" => ListSort::Time,
+        _ => ListSort::Name,
+    }
+}
+
+/// Apply `-a` and `--sort` to a directory's entries.
+fn arrange(entries: &mut Vec<DirEntry>, args: &ListArgs) {
+    if !args.all {
+        ls::retain_visible(entries);
+    }
+    ls::sort_entries(entries, list_sort(args));
+}
 
-    if json {
+/// One `-l` row: mode, size, local modification time, name (with a
+/// trailing `/` on directories). Directory sizes print as `-`, as in
+/// the short listing.
+fn long_line(entry: &DirEntry, name: &str, human: bool) -> String {
+    let size = if entry.is_dir {
+        "-".to_string()
+    } else if human {
+        format_bytes(entry.size)
+    } else {
+        entry.size.to_string()
+    };
+    let mtime = DateTime::from_timestamp(entry.mtime_seconds, 0)
+        .map(|utc| {
+            utc.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "-".to_string());
+    let suffix = if entry.is_dir { "/" } else { "" };
+    format!(
+        "{} {:>12} {} {}{}",
+        format_mode(entry.is_dir, entry.permissions),
+        size,
+        mtime,
+        name,
+        suffix
+    )
+}
+
+fn tree_depth(args: &ListArgs) -> Option<usize> {
+    args.max_depth.filter(|&d| d > 0).map(|d| d as usize)
+}
+
+fn print_tree(label: &str, start: &str, paths: Vec<(String, bool)>, args: &ListArgs) {
+    println!("{label}");
+    for line in tree_lines(start, paths, tree_depth(args)) {
+        println!("{line}");
+    }
+}
+
+/// Collect `dir`'s subtree as `/`-joined paths relative to the
+/// listing root, stopping at `--max-depth` and skipping dotfiles
+/// (and everything under hidden directories) unless `-a`. Symlinked
+/// directories are listed, not followed.
+fn walk_local(
+    dir: &Path,
+    prefix: &str,
+    depth: usize,
+    args: &ListArgs,
+    out: &mut Vec<(String, bool)>,
+) -> Result<()> {
+    let LocalListing::Directory { mut entries } = ls::list_local(dir)? else {
+        return Ok(());
+    };
+    if !args.all {
+        ls::retain_visible(&mut entries);
+    }
+    for entry in entries {
+        let path = if prefix.is_empty() {
+            entry.name.clone()
+        } else {
+            format!("{prefix}/{}", entry.name)
+        };
+        out.push((path.clone(), entry.is_dir));
+        if entry.is_dir && tree_depth(args).is_none_or(|max| depth < max) {
+            walk_local(&dir.join(&entry.name), &path, depth + 1, args, out)?;
+        }
+    }
+    Ok(())
+}
+
+fn list_local_path(path: &Path, args: &ListArgs) -> Result<()> {
+    if args.tree {
+        let mut paths = Vec::new();
+        walk_local(path, "", 1, args, &mut paths)?;
+        print_tree(&path.display().to_string(), "", paths, args);
+        return Ok(());
+    }
+
+    let listing = match ls::list_local(path)? {
+        LocalListing::Directory { mut entries } => {
+            arrange(&mut entries, args);
+            LocalListing::Directory { entries }
+        }
+        target => target,
+    };
+
+    if args.json {
         // Pre-A.0 the JSON path emitted a single-entry vec for the
         // non-directory case and the full sorted vec for the
         // directory case. `LocalListing::into_entries` preserves
@@ -30,12 +132,19 @@ fn list_local_path(path: &Path, json: bool) -> Result<()> {
             // Single non-directory target (file, device, FIFO,
             // socket). Pre-A.0 text printed the full path here,
             // not the basename — preserved.
-            println!("FILE {:>12} {}", format_bytes(entry.size), path.display());
+            if args.long {
+                let name = path.display().to_string();
+                println!("{}", long_line(&entry, &name, args.human_readable));
+            } else {
+                println!("FILE {:>12} {}", format_bytes(entry.size), path.display());
+            }
         }
         LocalListing::Directory { entries } => {
             println!("Listing {}:", path.display());
             for entry in entries {
-                if entry.is_dir {
+                if args.long {
+                    println!("{}", long_line(&entry, &entry.name, args.human_readable));
+                } else if entry.is_dir {
                     println!("DIR  {:>12} {}/", "-", entry.name);
                 } else {
                     println!("FILE {:>12} {}", format_bytes(entry.size), entry.name);
@@ -46,7 +155,7 @@ fn list_local_path(path: &Path, json: bool) -> Result<()> {
     Ok(())
 }
 
-async fn list_remote_path(remote: RemoteEndpoint, json: bool) -> Result<()> {
+async fn list_remote_path(remote: RemoteEndpoint, args: &ListArgs) -> Result<()> {
     // Smart-dispatch: bare-host targets (no module, no path) list
     // modules, matching the v6 plan's `blit list server` semantics.
     // Module/path targets fall through to the directory-listing
@@ -55,7 +164,7 @@ async fn list_remote_path(remote: RemoteEndpoint, json: bool) -> Result<()> {
     // `blit list <target>` (which aliases `ls`) just routes
     // intelligently between the two. (R41-prev / Plan §2.3.)
     if matches!(remote.path, RemotePath::Discovery) {
-        return crate::list_modules::list_modules_remote(remote, json).await;
+        return crate::list_modules::list_modules_remote(remote, args.json).await;
     }
 
     let (module, rel_path) = match &remote.path {
@@ -69,9 +178,14 @@ async fn list_remote_path(remote: RemoteEndpoint, json: bool) -> Result<()> {
     };
 
     let path_str = rel_path_to_string(&rel_path);
-    let entries: Vec<DirEntry> = ls::list_remote(&remote, module.clone(), path_str.clone()).await?;
+    if args.tree {
+        return tree_remote(&remote, module, path_str, args).await;
+    }
+    let mut entries: Vec<DirEntry> =
+        ls::list_remote(&remote, module.clone(), path_str.clone()).await?;
+    arrange(&mut entries, args);
 
-    if json {
+    if args.json {
         println!("{}", serde_json::to_string_pretty(&entries)?);
     } else if entries.is_empty() {
         println!(
@@ -86,6 +200,10 @@ async fn list_remote_path(remote: RemoteEndpoint, json: bool) -> Result<()> {
             if path_str.is_empty() { "" } else { &path_str }
         );
         for entry in entries {
+            if args.long {
+                println!("{}", long_line(&entry, &entry.name, args.human_readable));
+                continue;
+            }
             let indicator = if entry.is_dir { "DIR " } else { "FILE" };
             let size_str = if entry.is_dir {
                 "-".to_string()
@@ -98,3 +216,38 @@ async fn list_remote_path(remote: RemoteEndpoint, json: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// `--tree` over a daemon: one `Find` for the whole subtree, buffered
+/// and rendered once the stream ends (results arrive in walk order).
+async fn tree_remote(
+    remote: &RemoteEndpoint,
+    module: String,
+    start_path: String,
+    args: &ListArgs,
+) -> Result<()> {
+    let params = FindParams {
+        module: module.clone(),
+        start_path: start_path.clone(),
+        pattern: String::new(),
+        case_sensitive: true,
+        include_files: true,
+        include_directories: true,
+        max_results: 0,
+        exclude: Vec::new(),
+    };
+    let start = start_path.trim_matches('/').to_string();
+    let mut paths = Vec::new();
+    find::stream(remote, params, |entry| {
+        let below = entry
+            .path
+            .strip_prefix(start.as_str())
+            .unwrap_or(&entry.path);
+        if args.all || !below.split('/').any(|c| c.starts_with('.')) {
+            paths.push((entry.path, entry.is_dir));
+        }
+        Ok(())
+    })
+    .await?;
+    print_tree(&format!("{module}:/{start_path}"), &start_path, paths, args);
+    Ok(())
+}
@@ -1,4 +1,5 @@
 mod check;
+mod clean;
 mod cli;
 mod completions;
 mod context;
@@ -10,6 +11,7 @@ mod jobs;
 mod list_modules;
 mod ls;
 mod profile;
+mod prune;
 mod rm;
 mod scan;
 mod transfers;
@@ -17,17 +19,23 @@ mod transfers;
 use crate::check::run_check;
 use crate::cli::{Cli, Commands, DiagnosticsCommand};
 use crate::context::AppContext;
-use crate::diagnostics::{run_diagnostics_dump, run_diagnostics_perf};
+use crate::diagnostics::{run_diagnostics_bench, run_diagnostics_dump, run_diagnostics_perf};
 use crate::jobs::run_jobs;
 use crate::transfers::{run_move, run_transfer};
 use blit_app::transfers::dispatch::TransferKind;
-use blit_app::transfers::retry::run_with_retries;
+use blit_app::transfers::retry::{
+    run_resumable, run_with_retries, Interrupted, RESUMABLE_EXIT_CODE,
+};
 use blit_core::config;
+use blit_core::remote::message_limits::MessageLimits;
+use blit_core::remote::transfer::socket::DataSocketOverrides;
 use blit_core::remote::transfer::{
     outcome_for_report, TransferLifecycleOutcome, TransferLifecycleTrace,
 };
+use blit_core::transfer_session::diff_batch::DiffBatchLimits;
 use clap::Parser;
-use eyre::Result;
+use eyre::{Context, Result};
+use std::future::Future;
 use std::process::ExitCode;
 use std::time::Duration;
 
@@ -47,6 +55,15 @@ async fn run_cli(lifecycle_trace: &TransferLifecycleTrace) -> Result<ExitCode> {
     let Cli {
         config_dir,
         diagnostics_counter_file,
+        tcp_buffer_size,
+        tcp_keepalive,
+        data_plane_interface,
+        address,
+        contimeout,
+        max_message_size,
+        control_chunk_size,
+        manifest_batch_entries,
+        manifest_batch_size,
         command,
     } = Cli::parse();
     lifecycle_trace.record(
@@ -64,6 +81,36 @@ async fn run_cli(lifecycle_trace: &TransferLifecycleTrace) -> Result<ExitCode> {
         blit_core::remote::instrumentation::set_counter_path(path);
     }
 
+    blit_core::remote::transfer::socket::set_data_socket_overrides(DataSocketOverrides::parse(
+        tcp_buffer_size.as_deref(),
+        tcp_keepalive.as_deref(),
+    )?);
+    if let Some(interface) = data_plane_interface {
+        blit_core::remote::transfer::socket::set_data_plane_interface(interface);
+    }
+    if let Some(address) = address {
+        blit_core::remote::transfer::socket::set_local_address(address);
+    }
+    if let Some(raw) = contimeout.as_deref() {
+        let timeout = blit_core::fs_enum::parse_duration(raw)
+            .with_context(|| format!("invalid --contimeout '{raw}'"))?;
+        if timeout.is_zero() {
+            eyre::bail!("invalid --contimeout '{raw}': must be greater than zero");
+        }
+        blit_core::remote::connect::set_connect_timeout(timeout);
+    }
+    blit_core::remote::message_limits::set_message_limits(
+        MessageLimits::parse(max_message_size.as_deref(), control_chunk_size.as_deref())
+            .context("invalid --max-message-size/--control-chunk-size")?,
+    );
+    blit_core::transfer_session::diff_batch::set_diff_batch_limits(
+        DiffBatchLimits::parse(
+            manifest_batch_entries.as_deref(),
+            manifest_batch_size.as_deref(),
+        )
+        .context("invalid --manifest-batch-entries/--manifest-batch-size")?,
+    );
+
     lifecycle_trace.record("context_load_begin", None);
     let mut ctx = AppContext::load();
     lifecycle_trace.record("context_load_end", Some(TransferLifecycleOutcome::Success));
@@ -71,26 +118,31 @@ async fn run_cli(lifecycle_trace: &TransferLifecycleTrace) -> Result<ExitCode> {
     let exit_code = match command {
         Commands::Copy(args) => {
             let wait = Duration::from_secs(args.wait);
-            run_with_retries(args.retry, wait, |_n| {
+            let transfer = run_with_retries(args.retry, wait, |_n| {
                 run_transfer(&ctx, &args, TransferKind::Copy, lifecycle_trace)
-            })
-            .await?;
-            ExitCode::SUCCESS
+            });
+            resumable_exit(args.resume, transfer).await?
         }
         Commands::Mirror(args) => {
             let wait = Duration::from_secs(args.wait);
-            run_with_retries(args.retry, wait, |_n| {
+            let transfer = run_with_retries(args.retry, wait, |_n| {
                 run_transfer(&ctx, &args, TransferKind::Mirror, lifecycle_trace)
-            })
-            .await?;
-            ExitCode::SUCCESS
+            });
+            resumable_exit(args.resume, transfer).await?
         }
         Commands::Move(args) => {
             let wait = Duration::from_secs(args.wait);
-            run_with_retries(args.retry, wait, |_n| {
+            let transfer = run_with_retries(args.retry, wait, |_n| {
                 run_move(&ctx, &args, lifecycle_trace)
-            })
-            .await?;
+            });
+            resumable_exit(args.resume, transfer).await?
+        }
+        Commands::Prune(args) => {
+            prune::run_prune(args).await?;
+            ExitCode::SUCCESS
+        }
+        Commands::Clean(args) => {
+            clean::run_clean(args).await?;
             ExitCode::SUCCESS
         }
         Commands::Scan(args) => {
@@ -129,9 +181,10 @@ async fn run_cli(lifecycle_trace: &TransferLifecycleTrace) -> Result<ExitCode> {
             profile::run_profile(args)?;
             ExitCode::SUCCESS
         }
-        // `check` is the only command whose exit code carries semantic
-        // info (0 identical / 1 differences / 2 errors), so we propagate
-        // it directly. Other commands return Ok(()) and use the default 0.
+        // `check`'s exit code carries semantic info (0 identical / 1
+        // differences / 2 errors), so we propagate it directly. Other
+        // commands return Ok(()) and use the default 0 (transfers: see
+        // `resumable_exit`).
         Commands::Check(args) => run_check(&args).await?,
         Commands::Diagnostics { command } => match command {
             DiagnosticsCommand::Perf(args) => {
@@ -142,6 +195,10 @@ async fn run_cli(lifecycle_trace: &TransferLifecycleTrace) -> Result<ExitCode> {
                 run_diagnostics_dump(&args)?;
                 ExitCode::SUCCESS
             }
+            DiagnosticsCommand::Bench(args) => {
+                run_diagnostics_bench(&args).await?;
+                ExitCode::SUCCESS
+            }
         },
         // `jobs cancel` exits 0/1/2 (Cancelled / NotFound /
         // Unsupported) per the §6.5 contract; `jobs list`
@@ -154,6 +211,35 @@ async fn run_cli(lifecycle_trace: &TransferLifecycleTrace) -> Result<ExitCode> {
     Ok(exit_code)
 }
 
+/// A `--resume` transfer stopped by Ctrl-C, or by a transient failure
+/// `--retry` did not outlast, exits [`RESUMABLE_EXIT_CODE`] with a hint
+/// to re-run it; its partial files stay in place for that run.
+async fn resumable_exit(
+    resume: bool,
+    transfer: impl Future<Output = Result<()>>,
+) -> Result<ExitCode> {
+    let interrupt = async {
+        // No handler (e.g. no signal support) means no interrupt to race.
+        if tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    };
+    match run_resumable(resume, transfer, interrupt).await? {
+        None => Ok(ExitCode::SUCCESS),
+        Some(interrupted) => {
+            match interrupted {
+                Interrupted::Signal => eprintln!("blit: transfer interrupted"),
+                Interrupted::Transient(err) => eprintln!("blit: transfer interrupted: {err:#}"),
+            }
+            eprintln!(
+                "blit: partial files were kept; run the same command again \
+                 (with --resume) to continue it (exit {RESUMABLE_EXIT_CODE})"
+            );
+            Ok(ExitCode::from(RESUMABLE_EXIT_CODE))
+        }
+    }
+}
+
 fn lifecycle_result_outcome<T>(result: &Result<T>) -> TransferLifecycleOutcome {
     match result {
         Ok(_) => TransferLifecycleOutcome::Success,
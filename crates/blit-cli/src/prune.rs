@@ -0,0 +1,115 @@
+use crate::cli::PruneArgs;
+use blit_app::admin::prune;
+use blit_app::endpoints::{parse_endpoint_or_local, Endpoint};
+use blit_core::remote::endpoint::format_host_port;
+use eyre::{bail, Result};
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// Entries listed in the text preview before it is cut short.
+const PREVIEW_ENTRIES: usize = 20;
+
+pub async fn run_prune(args: PruneArgs) -> Result<()> {
+    let source = match parse_endpoint_or_local(&args.source) {
+        Endpoint::Local(path) => path,
+        Endpoint::Remote(_) => {
+            bail!(
+                "`blit prune` needs a local source (received {})",
+                args.source
+            )
+        }
+    };
+    let remote = match parse_endpoint_or_local(&args.destination) {
+        Endpoint::Local(path) => bail!(
+            "`blit prune` only supports remote destinations (received local path: {})",
+            path.display()
+        ),
+        Endpoint::Remote(remote) => remote,
+    };
+    let endpoint_display = format_host_port(&remote.host, remote.port);
+
+    let plan = prune::plan(&source, &remote).await?;
+
+    let entries_deleted = if plan.extraneous.is_empty() || args.dry_run {
+        0
+    } else {
+        if !args.yes {
+            print_preview(&plan.extraneous);
+            print!(
+                "Delete {} from {} on {}? [y/N]: ",
+                extraneous_entries(plan.extraneous.len()),
+                args.destination,
+                endpoint_display
+            );
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let decision = input.trim().to_ascii_lowercase();
+            if !(decision == "y" || decision == "yes") {
+                println!("Aborted.");
+                return Ok(());
+            }
+        }
+        prune::execute(&remote, &plan).await?
+    };
+
+    if args.json {
+        #[derive(Serialize)]
+        struct PruneResult<'a> {
+            destination: &'a str,
+            host: &'a str,
+            port: u16,
+            dry_run: bool,
+            extraneous: &'a [String],
+            entries_deleted: u64,
+        }
+        let result = PruneResult {
+            destination: &args.destination,
+            host: &remote.host,
+            port: remote.port,
+            dry_run: args.dry_run,
+            extraneous: &plan.extraneous,
+            entries_deleted,
+        };
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else if plan.extraneous.is_empty() {
+        println!(
+            "Nothing to prune: all {} entries on {} exist in the source.",
+            plan.dest_entries, endpoint_display
+        );
+    } else if args.dry_run {
+        println!(
+            "Would delete {} on {}:",
+            extraneous_entries(plan.extraneous.len()),
+            endpoint_display
+        );
+        for path in &plan.extraneous {
+            println!("  - {path}");
+        }
+    } else {
+        println!(
+            "Pruned {} ({} removed) on {}.",
+            extraneous_entries(plan.extraneous.len()),
+            entries_deleted,
+            endpoint_display
+        );
+    }
+
+    Ok(())
+}
+
+fn print_preview(extraneous: &[String]) {
+    for path in extraneous.iter().take(PREVIEW_ENTRIES) {
+        println!("  - {path}");
+    }
+    if extraneous.len() > PREVIEW_ENTRIES {
+        println!("  ... and {} more", extraneous.len() - PREVIEW_ENTRIES);
+    }
+}
+
+fn extraneous_entries(count: usize) -> String {
+    match count {
+        1 => "1 extraneous entry".to_string(),
+        count => format!("{count} extraneous entries"),
+    }
+}
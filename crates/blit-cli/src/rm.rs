@@ -1,6 +1,7 @@
 use crate::cli::RmArgs;
 use blit_app::admin::rm;
 use blit_app::endpoints::{parse_endpoint_or_local, Endpoint};
+use blit_core::remote::endpoint::format_host_port;
 use eyre::{bail, Result};
 use serde::Serialize;
 use std::io::{self, Write};
@@ -43,7 +44,7 @@ pub async fn run_rm(args: RmArgs) -> Result<()> {
     }
 
     let module_display = format!("{}:/{}", module, rel_string);
-    let endpoint_display = format!("{}:{}", remote.host, remote.port);
+    let endpoint_display = format_host_port(&remote.host, remote.port);
 
     if !args.yes {
         print!("Delete {} on {}? [y/N]: ", module_display, endpoint_display);
@@ -1,7 +1,8 @@
 use crate::cli::ScanArgs;
 use blit_app::scan;
-use eyre::Result;
+use eyre::{bail, Context, Result};
 use serde::Serialize;
+use std::path::PathBuf;
 use std::time::Duration;
 
 #[derive(Serialize)]
@@ -24,6 +25,9 @@ struct ScanEntryJson {
 }
 
 pub async fn run_scan(args: ScanArgs) -> Result<()> {
+    if let Some(root) = args.manifest {
+        return run_manifest(root).await;
+    }
     let json = args.json;
     let wait_secs = args.wait;
     let services = scan::discover(Duration::from_secs(wait_secs)).await?;
@@ -105,3 +109,18 @@ pub async fn run_scan(args: ScanArgs) -> Result<()> {
 
     Ok(())
 }
+
+/// `scan --manifest DIR`: write the tree's manifest to stdout.
+async fn run_manifest(root: PathBuf) -> Result<()> {
+    if !root.is_dir() {
+        bail!("manifest root is not a directory: {}", root.display());
+    }
+    tokio::task::spawn_blocking(move || {
+        let stdout = std::io::stdout();
+        let mut out = std::io::BufWriter::new(stdout.lock());
+        blit_app::manifest::write_manifest(&root, Default::default(), &mut out)
+    })
+    .await
+    .context("manifest task panicked")??;
+    Ok(())
+}
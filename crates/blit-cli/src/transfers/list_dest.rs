@@ -0,0 +1,56 @@
+//! `--list-dest`: print the read-only source-vs-destination preview
+//! computed by `blit_app::transfers::list_dest`.
+
+use std::path::Path;
+
+use blit_app::display::format_bytes;
+use blit_app::transfers::list_dest::{list_dest, ListDestChange, ListDestReport};
+use blit_core::remote::RemoteEndpoint;
+use eyre::Result;
+
+use super::build_filter;
+use crate::cli::TransferArgs;
+
+pub(crate) async fn run_list_dest(
+    args: &TransferArgs,
+    src: &Path,
+    dst: &RemoteEndpoint,
+) -> Result<()> {
+    let report = list_dest(src, dst, build_filter(args)?).await?;
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report, args.human_readable);
+    }
+    Ok(())
+}
+
+fn render_size(size: Option<u64>, human: bool) -> String {
+    match size {
+        None => "-".to_string(),
+        Some(bytes) if human => format_bytes(bytes),
+        Some(bytes) => bytes.to_string(),
+    }
+}
+
+fn print_report(report: &ListDestReport, human: bool) {
+    for entry in &report.entries {
+        println!(
+            "{} {} ({} -> {})",
+            entry.change.marker(),
+            entry.path,
+            render_size(entry.src_size, human),
+            render_size(entry.dst_size, human)
+        );
+    }
+    println!(
+        "List-dest: {} new, {} newer on source, {} newer on destination, \
+         {} size differs, {} destination-only, {} unchanged",
+        report.count(ListDestChange::NewOnSource),
+        report.count(ListDestChange::NewerOnSource),
+        report.count(ListDestChange::NewerOnDestination),
+        report.count(ListDestChange::SizeDiffers),
+        report.count(ListDestChange::DestinationOnly),
+        report.unchanged
+    );
+}
@@ -1,3 +1,4 @@
+use super::stats::TransferStats;
 use crate::cli::TransferArgs;
 use crate::context::AppContext;
 use blit_app::display::{format_bps, format_bytes};
@@ -70,6 +71,9 @@ pub fn print_local_transfer_summary(
 ) -> Result<()> {
     // Only presentation fields are read here; the compare mode (and
     // thus the move_verb flag) is irrelevant to printing.
+    if args.quiet {
+        return Ok(());
+    }
     let options = build_local_options(ctx, args, mirror, false)?;
     if args.json {
         print_summary_json(mirror, summary, elapsed, src_path, dest_path);
@@ -84,6 +88,9 @@ pub fn print_local_transfer_summary(
             summary,
             elapsed,
         );
+        if args.show_stats() {
+            super::stats::print_stats(&local_stats(summary, elapsed), args.human_readable);
+        }
     }
     Ok(())
 }
@@ -105,7 +112,10 @@ async fn run_local_transfer_inner(
     let options = build_local_options(ctx, args, mirror, move_verb)?;
     let dry_run = options.dry_run;
     let null_sink = options.null_sink;
+    let quiet = args.quiet;
     let json_output = args.json;
+    let show_stats = args.show_stats();
+    let human_readable = args.human_readable;
     let verbose = options.verbose;
     let debug_mode = options.debug_mode;
     let workers = options.workers;
@@ -134,24 +144,47 @@ async fn run_local_transfer_inner(
         Some(pb)
     };
 
+    // The spinner above is the interactive indicator; the monitor only
+    // carries `--numeric-progress`, or the `--info` name / deletion lines.
+    let (monitor, monitor_task) = if args.numeric_progress {
+        super::remote::spawn_numeric_progress_monitor(defer_output)
+    } else {
+        let lines = super::remote::MonitorLines {
+            progress: false,
+            ..super::remote::MonitorLines::for_args(args)
+        };
+        super::remote::spawn_progress_monitor_with_options(lines, json_output, defer_output)
+    };
+
     let start = Instant::now();
-    let summary = blit_app::transfers::local::run(src_path, dest_path, options).await?;
+    let result =
+        blit_app::transfers::local::run_with_progress(src_path, dest_path, options, monitor).await;
 
     if let Some(pb) = progress_bar {
         pb.finish_and_clear();
     }
+    if let Some(task) = monitor_task {
+        let _ = task.await;
+    }
+    let summary = result?;
 
     let elapsed = start.elapsed();
     if !defer_output {
         super::render_result(
             lifecycle_trace.expect("inline local output has a lifecycle trace"),
             || {
+                if quiet {
+                    return Ok(());
+                }
                 if json_output {
                     print_summary_json(mirror, &summary, elapsed, src_path, dest_path);
                 } else {
                     print_summary(
                         mirror, dry_run, null_sink, verbose, debug_mode, workers, &summary, elapsed,
                     );
+                    if show_stats {
+                        super::stats::print_stats(&local_stats(&summary, elapsed), human_readable);
+                    }
                 }
                 Ok(())
             },
@@ -197,13 +230,13 @@ fn build_local_options(
         } else {
             LocalCompareMode::IgnoreTimes
         }
-    } else if args.ignore_times {
+    } else if args.overwrite_always() {
         LocalCompareMode::IgnoreTimes
     } else if args.force {
         LocalCompareMode::Force
     } else if args.size_only {
         LocalCompareMode::SizeOnly
-    } else if args.checksum {
+    } else if args.checksum || args.metadata_only {
         LocalCompareMode::Checksum
     } else {
         LocalCompareMode::SizeMtime
@@ -227,14 +260,39 @@ fn build_local_options(
         verbose: args.verbose,
         progress: args.effective_progress(),
         perf_history: ctx.perf_history_enabled,
-        checksum: args.checksum,
-        ignore_existing: args.ignore_existing,
+        checksum: args.checksum || args.metadata_only,
+        ignore_existing: args.skip_existing(),
+        existing_only: args.existing,
+        max_files: args.max_files.unwrap_or(0),
+        file_count_warning: super::build_file_count_warning(args),
+        metadata_only: args.metadata_only,
         drop_windows_metadata: args.drop_windows_metadata,
+        crtimes: args.crtimes,
+        owner: args.super_user || args.fake_super,
+        fake_super: args.fake_super,
+        prune_empty_dirs: args.prune_empty_dirs,
+        chmod: super::build_chmod(args)?,
+        chown: args.chown.clone().unwrap_or_default(),
+        xattrs: super::build_xattrs(args)?,
         compare_mode,
         delete_scope,
         resume: args.resume,
         null_sink: args.null,
         filter: super::build_filter(args)?,
+        rename: super::build_rename_rules(args)?,
+        symlink_deref: args.symlink_deref(),
+        safe_links: args.safe_links,
+        sparse: args.sparse_mode(),
+        preallocate: args.preallocate,
+        dedupe: args.dedupe,
+        reflink_dest: args.reflink_dest.clone(),
+        block_size: super::build_block_size(args)?,
+        skip_locked: args.skip_locked,
+        vss: args.vss,
+        snapshot: args.snapshot,
+        delay_updates: args.delay_updates,
+        temp_dir: args.temp_dir.clone(),
+        plan_options: super::build_plan_options(args)?,
         ..LocalMirrorOptions::default()
     };
     if let Some(workers) = args.workers {
@@ -310,6 +368,31 @@ fn print_summary(
             summary.deleted_files, summary.deleted_dirs
         );
     }
+    if dry_run && summary.wire_bytes > 0 {
+        // What a push of these changes would put on the data plane:
+        // the planned file bytes plus record framing and tar overhead.
+        let framing = summary
+            .wire_bytes
+            .saturating_sub(summary.tar_shard_bytes + summary.large_bytes);
+        println!(
+            "• On the wire if pushed: ~{} ({} framing)",
+            format_bytes(summary.wire_bytes),
+            format_bytes(framing)
+        );
+    }
+    if summary.sparse_bytes > 0 {
+        println!(
+            "• Sparse: {} left as holes",
+            format_bytes(summary.sparse_bytes)
+        );
+    }
+    if summary.dedupe.files_linked > 0 {
+        println!(
+            "• Deduplicated: {} file(s) hard-linked, {} saved",
+            summary.dedupe.files_linked,
+            format_bytes(summary.dedupe.bytes_saved)
+        );
+    }
 
     // Suppress throughput/workers noise on small transfers where startup
     // dominates wall time and the numbers are meaningless. Keep it for
@@ -357,6 +440,27 @@ fn print_summary(
     }
 }
 
+/// `--stats` view of a local run: the scan's post-filter totals are
+/// the source size, the apply pipeline's writes the transferred size.
+/// Local copies are never zero-copy-scored, so that line is omitted.
+fn local_stats(summary: &LocalMirrorSummary, elapsed: Duration) -> TransferStats {
+    TransferStats {
+        files_examined: Some(summary.scanned_files as u64),
+        files_transferred: summary.copied_files as u64,
+        files_resumed: 0,
+        total_bytes: Some(summary.scanned_bytes),
+        transferred_bytes: summary.total_bytes,
+        zero_copy_bytes: None,
+        sparse_bytes: summary.sparse_bytes,
+        entries_deleted: (summary.deleted_files + summary.deleted_dirs) as u64,
+        elapsed: if summary.duration.is_zero() {
+            elapsed
+        } else {
+            summary.duration
+        },
+    }
+}
+
 fn print_summary_json(
     mirror: bool,
     summary: &LocalMirrorSummary,
@@ -384,6 +488,10 @@ fn print_summary_json(
         "total_bytes": summary.total_bytes,
         "deleted_files": summary.deleted_files,
         "deleted_dirs": summary.deleted_dirs,
+        "wire_bytes_estimate": summary.wire_bytes,
+        "sparse_bytes": summary.sparse_bytes,
+        "deduped_files": summary.dedupe.files_linked,
+        "dedupe_bytes_saved": summary.dedupe.bytes_saved,
         "duration_ms": duration.as_millis() as u64,
         "dry_run": summary.dry_run,
         "outcome": outcome,
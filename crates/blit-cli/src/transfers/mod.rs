@@ -1,7 +1,9 @@
 mod endpoints;
+mod list_dest;
 mod local;
 mod remote;
 mod remote_remote_direct;
+mod stats;
 
 // Endpoint types come from `blit_app::endpoints` directly. The
 // `transfers/endpoints.rs` shim now contains only the two
@@ -14,17 +16,23 @@ use crate::cli::TransferArgs;
 use crate::context::AppContext;
 use eyre::{bail, Context, Result};
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+use std::sync::Arc;
+use std::time::Duration;
 
 use crate::rm::delete_remote_path;
 use blit_app::transfers::dispatch::{select_transfer_route, TransferKind, TransferRoute};
 use blit_app::transfers::filter::{self, FilterInputs};
-use blit_app::transfers::resolution::resolve_destination;
+use blit_app::transfers::resolution::{resolve_destination, resolve_relative_destination};
+use blit_core::checksum_cache;
+use blit_core::enumeration::{SortOrder, SymlinkDeref};
 use blit_core::fs_enum::FileFilter;
+use blit_core::remote::transfer::source::FileCountWarning;
 use blit_core::remote::transfer::{
     SessionPhaseRole, TransferLifecycleOutcome, TransferLifecycleTrace,
 };
 use blit_core::remote::RemotePath;
+use blit_core::transfer_plan::PlanOptions;
 
 /// Build a `FilterInputs` view over a `TransferArgs`. Lives here
 /// because the orphan rule prevents `impl From<&TransferArgs>` on
@@ -35,11 +43,15 @@ fn filter_inputs(args: &TransferArgs) -> FilterInputs<'_> {
     FilterInputs {
         include: &args.include,
         exclude: &args.exclude,
+        include_from: &args.include_from,
+        exclude_from: &args.exclude_from,
         files_from: args.files_from.as_ref(),
         min_size: args.min_size.as_deref(),
         max_size: args.max_size.as_deref(),
         min_age: args.min_age.as_deref(),
         max_age: args.max_age.as_deref(),
+        no_hidden: args.no_hidden,
+        exclude_attributes: args.exclude_attributes.as_deref(),
     }
 }
 use blit_app::endpoints::{ensure_remote_destination_supported, ensure_remote_source_supported};
@@ -86,6 +98,173 @@ pub(crate) fn build_filter_spec(args: &TransferArgs) -> Result<blit_core::genera
     filter::build_spec(&filter_inputs(args))
 }
 
+/// Parse `--rename` specs into wire rules. Fails before any connection
+/// so a malformed regex never reaches a daemon.
+pub(crate) fn build_rename_rules(
+    args: &TransferArgs,
+) -> Result<Vec<blit_core::generated::RenameRule>> {
+    blit_core::path_rename::parse_rules(&args.rename)
+}
+
+/// Every `--chmod` clause as one `SessionOpen.chmod` spec, parsed here
+/// so a malformed one fails before any connection is made. Empty when
+/// the flag is absent.
+pub(crate) fn build_chmod(args: &TransferArgs) -> Result<String> {
+    let spec = args.chmod.join(",");
+    if !spec.is_empty() {
+        blit_core::chmod::ChmodSpec::parse(&spec)?;
+    }
+    Ok(spec)
+}
+
+/// The `--xattrs*` flags as one `SessionOpen.xattrs` filter spec,
+/// validated here so a malformed namespace fails before any connection
+/// is made. Empty when no xattr flag is set.
+pub(crate) fn build_xattrs(args: &TransferArgs) -> Result<String> {
+    if !args.wants_xattrs() {
+        return Ok(String::new());
+    }
+    let filter = blit_core::xattrs::XattrFilter::new(
+        args.xattrs_all,
+        &args.xattrs_include,
+        &args.xattrs_exclude,
+    )?;
+    Ok(filter.spec())
+}
+
+/// Fold `--streams` / `--chunk-size` into the wire dial pins. `None`
+/// when neither is set, so the open stays byte-identical to an
+/// unpinned one. Range clamping is the dial's job (it knows the
+/// receiver's ceilings); this only rejects unparseable or zero sizes.
+pub(crate) fn build_dial_request(
+    args: &TransferArgs,
+) -> Result<Option<blit_core::generated::DialRequest>> {
+    let chunk_bytes = match args.chunk_size.as_deref() {
+        Some(raw) => {
+            let bytes = blit_core::fs_enum::parse_size(raw)
+                .with_context(|| format!("invalid --chunk-size '{raw}'"))?;
+            if bytes == 0 {
+                bail!("--chunk-size must be greater than zero");
+            }
+            bytes
+        }
+        None => 0,
+    };
+    if args.streams.is_none() && chunk_bytes == 0 {
+        return Ok(None);
+    }
+    Ok(Some(blit_core::generated::DialRequest {
+        streams: args.streams.unwrap_or(0),
+        chunk_bytes,
+    }))
+}
+
+/// Parse `--block-size` into validated bytes; `0` when unset, which
+/// every consumer reads as "keep the derived size".
+pub(crate) fn build_block_size(args: &TransferArgs) -> Result<usize> {
+    match args.block_size.as_deref() {
+        Some(raw) => {
+            let bytes = blit_core::fs_enum::parse_size(raw)
+                .with_context(|| format!("invalid --block-size '{raw}'"))?;
+            blit_core::buffer::validate_block_size(bytes)
+                .with_context(|| format!("invalid --block-size '{raw}'"))
+        }
+        None => Ok(0),
+    }
+}
+
+/// Parse `--reconnect` into the control lane's reconnect window;
+/// `None` when unset.
+pub(crate) fn build_reconnect(args: &TransferArgs) -> Result<Option<Duration>> {
+    let Some(raw) = args.reconnect.as_deref() else {
+        return Ok(None);
+    };
+    let window = blit_core::fs_enum::parse_duration(raw)
+        .with_context(|| format!("invalid --reconnect '{raw}'"))?;
+    if window.is_zero() {
+        bail!("invalid --reconnect '{raw}': must be greater than zero");
+    }
+    let max = blit_core::transfer_session::lane::MAX_RECONNECT_WINDOW;
+    if window > max {
+        bail!("invalid --reconnect '{raw}': at most {}s", max.as_secs());
+    }
+    Ok(Some(window))
+}
+
+/// `--warn-files` as the source scan's confirmation hook: on a terminal
+/// it asks (on stderr, clear of `--json` output); with `--yes` or no
+/// terminal it only warns. 0 = no hook.
+pub(crate) fn build_file_count_warning(args: &TransferArgs) -> Option<FileCountWarning> {
+    if args.warn_files == 0 {
+        return None;
+    }
+    let ask = !args.yes && io::stdin().is_terminal() && io::stderr().is_terminal();
+    Some(FileCountWarning {
+        threshold: args.warn_files,
+        confirm: Arc::new(move |threshold| {
+            if !ask {
+                eprintln!("blit: warning: the source has more than {threshold} files");
+                return true;
+            }
+            eprint!("blit: the source has more than {threshold} files; continue? [y/N]: ");
+            let _ = io::stderr().flush();
+            let mut input = String::new();
+            if io::stdin().read_line(&mut input).is_err() {
+                return false;
+            }
+            let decision = input.trim().to_ascii_lowercase();
+            decision == "y" || decision == "yes"
+        }),
+    })
+}
+
+/// Fold `--shard-max-bytes` / `--shard-max-files` / `--no-shard` /
+/// `--sort-order` into the SOURCE planner's options; unset flags keep
+/// the derived tuning.
+pub(crate) fn build_plan_options(args: &TransferArgs) -> Result<PlanOptions> {
+    let shard_max_bytes = match args.shard_max_bytes.as_deref() {
+        Some(raw) => {
+            let bytes = blit_core::fs_enum::parse_size(raw)
+                .with_context(|| format!("invalid --shard-max-bytes '{raw}'"))?;
+            if bytes == 0 {
+                bail!("--shard-max-bytes must be greater than zero");
+            }
+            Some(bytes)
+        }
+        None => None,
+    };
+    Ok(PlanOptions {
+        shard_max_bytes,
+        shard_max_files: args.shard_max_files.map(|n| n as usize),
+        no_shard: args.no_shard,
+        sort_order: args.sort_order(),
+        ..PlanOptions::default()
+    })
+}
+
+/// `--reflink-dest` clones from a local reference tree into a local
+/// destination; it must name an existing directory.
+fn validate_reflink_dest(args: &TransferArgs, route: &TransferRoute) -> Result<()> {
+    let Some(dir) = args.reflink_dest.as_deref() else {
+        return Ok(());
+    };
+    if !matches!(route, TransferRoute::LocalToLocal { .. }) {
+        bail!("--reflink-dest only applies to a local copy (both endpoints local paths)");
+    }
+    if !dir.is_dir() {
+        bail!("--reflink-dest {} is not a directory", dir.display());
+    }
+    Ok(())
+}
+
+/// True when any tar-shard planner flag is set.
+fn shard_flags_set(args: &TransferArgs) -> bool {
+    args.shard_max_bytes.is_some()
+        || args.shard_max_files.is_some()
+        || args.no_shard
+        || args.sort_order() != SortOrder::None
+}
+
 /// Prompt for confirmation of a destructive operation. Returns true if the user confirms.
 /// Always returns true if `skip_prompt` is true.
 fn confirm_destructive_operation(message: &str, skip_prompt: bool) -> Result<bool> {
@@ -118,8 +297,16 @@ fn prepare_transfer_route(
         let src_endpoint = parse_transfer_endpoint(&args.source)?;
         let raw_dst = parse_transfer_endpoint(&args.destination)?;
         let pre_resolve_display = display_endpoint(&raw_dst);
-        let dst_endpoint =
-            resolve_destination(&args.source, &args.destination, &src_endpoint, raw_dst);
+        // `--unpack` lands the archive's members under the destination
+        // as a tree; appending the archive's file name would nest them
+        // under a directory called `foo.tar.gz`.
+        let dst_endpoint = if args.unpack {
+            raw_dst
+        } else if args.relative {
+            resolve_relative_destination(&src_endpoint, raw_dst)?
+        } else {
+            resolve_destination(&args.source, &args.destination, &src_endpoint, raw_dst)
+        };
         let src_display = display_endpoint(&src_endpoint);
         let dst_display = display_endpoint(&dst_endpoint);
         let route = select_transfer_route(src_endpoint, dst_endpoint, mode);
@@ -169,6 +356,143 @@ pub(super) fn render_result(
     result
 }
 
+/// `--manifest-checkpoint` checkpoints this host's own walk of a push
+/// source; the checkpointed walk neither follows links nor reads
+/// archives.
+fn check_manifest_checkpoint(args: &TransferArgs, route: &TransferRoute) -> Result<()> {
+    if !matches!(route, TransferRoute::LocalToRemote { .. }) {
+        bail!("--manifest-checkpoint only applies to a push (local source, remote destination)");
+    }
+    if args.symlink_deref() != SymlinkDeref::None || args.unpack {
+        bail!("--manifest-checkpoint cannot be combined with --copy-links, --copy-dirlinks, --copy-unsafe-links or --unpack");
+    }
+    Ok(())
+}
+
+/// Flags the delegated remote→remote route cannot honor: its spec has
+/// no field to carry `--crtimes`, `--prune-empty-dirs`, `--chmod`,
+/// `--chown`, `--xattrs` or `--existing` to the pulling daemon, and
+/// `--checksum-cache` has nothing local to cache when both ends hash on
+/// their daemons. Copy and move share this one list; add a new flag here.
+fn check_delegated_unsupported(args: &TransferArgs, route: &TransferRoute) -> Result<()> {
+    if !matches!(route, TransferRoute::RemoteToRemoteDelegated { .. }) {
+        return Ok(());
+    }
+    for (set, flag) in [
+        (args.crtimes, "--crtimes"),
+        (args.prune_empty_dirs, "--prune-empty-dirs"),
+        (!args.chmod.is_empty(), "--chmod"),
+        (args.chown.is_some(), "--chown"),
+        (args.wants_xattrs(), "--xattrs"),
+        (args.existing, "--existing"),
+        (args.checksum_cache, "--checksum-cache"),
+    ] {
+        if set {
+            bail!("{flag} is not supported for remote-to-remote transfers");
+        }
+    }
+    Ok(())
+}
+
+/// `--max-files` rides the session open, which the delegated
+/// remote→remote spec cannot carry.
+fn check_max_files(args: &TransferArgs, route: &TransferRoute) -> Result<()> {
+    match args.max_files {
+        None => Ok(()),
+        Some(0) => bail!("invalid --max-files 0: must be greater than zero"),
+        Some(_) if matches!(route, TransferRoute::RemoteToRemoteDelegated { .. }) => {
+            bail!("--max-files is not supported for remote-to-remote transfers")
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+/// `--checksum-seed` keys the block hashes a push or pull exchanges
+/// over the wire. A local resume compares bytes directly and the
+/// delegated remote→remote spec cannot carry the seed.
+fn check_checksum_seed(args: &TransferArgs, route: &TransferRoute) -> Result<()> {
+    if args.checksum_seed.is_some()
+        && !matches!(
+            route,
+            TransferRoute::LocalToRemote { .. } | TransferRoute::RemoteToLocal { .. }
+        )
+    {
+        bail!("--checksum-seed only applies to a push or pull (exactly one remote endpoint)");
+    }
+    Ok(())
+}
+
+/// `--remote-option`s are addressed to the daemon a push or pull opens
+/// its session with; a local copy has none and the delegated
+/// remote→remote spec cannot carry them. Malformed pairs fail here,
+/// before any connection is made.
+fn check_remote_options(args: &TransferArgs, route: &TransferRoute) -> Result<()> {
+    if args.remote_option.is_empty() {
+        return Ok(());
+    }
+    if !matches!(
+        route,
+        TransferRoute::LocalToRemote { .. } | TransferRoute::RemoteToLocal { .. }
+    ) {
+        bail!("--remote-option only applies to a push or pull (exactly one remote endpoint)");
+    }
+    blit_core::remote_options::parse_remote_options(&args.remote_option)?;
+    Ok(())
+}
+
+/// Only a push or pull holds a control connection of its own to keep
+/// resumable.
+fn check_reconnect(args: &TransferArgs, route: &TransferRoute) -> Result<()> {
+    if args.reconnect.is_some()
+        && !matches!(
+            route,
+            TransferRoute::LocalToRemote { .. } | TransferRoute::RemoteToLocal { .. }
+        )
+    {
+        bail!("--reconnect only applies to a push or pull (exactly one remote endpoint)");
+    }
+    Ok(())
+}
+
+/// `--super` / `--fake-super` ride the session open, which the
+/// delegated remote→remote spec cannot carry; an archive source has no
+/// inodes to read owners from.
+fn check_ownership(args: &TransferArgs, route: &TransferRoute) -> Result<()> {
+    if !(args.super_user || args.fake_super) {
+        return Ok(());
+    }
+    if matches!(route, TransferRoute::RemoteToRemoteDelegated { .. }) {
+        bail!("--super / --fake-super are not supported for remote-to-remote transfers");
+    }
+    if args.unpack {
+        bail!("--super / --fake-super cannot be combined with --unpack");
+    }
+    Ok(())
+}
+
+/// `--checksum-cache`: load the cache from the config dir and install
+/// it process-wide for every hash this transfer computes locally.
+fn install_checksum_cache(args: &TransferArgs) -> Result<()> {
+    if !args.checksum_cache {
+        return Ok(());
+    }
+    if !(args.checksum || args.metadata_only) {
+        bail!("--checksum-cache needs --checksum (or --metadata-only)");
+    }
+    let path = blit_core::config::config_dir()?.join(checksum_cache::CACHE_FILENAME);
+    checksum_cache::install(checksum_cache::ChecksumCache::load(path));
+    Ok(())
+}
+
+/// Save the installed cache, if any. Every entry it holds is valid
+/// whether or not the transfer succeeded, so this runs either way; a
+/// failed save only costs the next run its hits.
+fn flush_checksum_cache() {
+    if let Err(err) = checksum_cache::flush() {
+        log::warn!("could not save the checksum cache: {err:#}");
+    }
+}
+
 pub async fn run_transfer(
     ctx: &AppContext,
     args: &TransferArgs,
@@ -177,6 +501,7 @@ pub async fn run_transfer(
 ) -> Result<()> {
     lifecycle_trace.record("transfer_dispatch_begin", None);
     let result = run_transfer_inner(ctx, args, mode, lifecycle_trace).await;
+    flush_checksum_cache();
     lifecycle_trace.record(
         "transfer_dispatch_end",
         Some(crate::lifecycle_result_outcome(&result)),
@@ -262,6 +587,167 @@ async fn run_transfer_inner(
         }
     }
 
+    // `--unpack` reads the archive through an archive-backed
+    // TransferSource, which only the push session consumes: the local
+    // copy engine and a daemon SOURCE both read their own filesystem.
+    if args.unpack && !matches!(&route, TransferRoute::LocalToRemote { .. }) {
+        bail!(
+            "--unpack is only supported when pushing a local archive to a \
+             remote destination (e.g. `blit copy --unpack site.tar.gz \
+             server:/module/site/`)"
+        );
+    }
+
+    // `--remove-source-files` deletes what this host's push session
+    // sent; no other route has a local source with a per-file record.
+    if args.remove_source_files {
+        if !matches!(&route, TransferRoute::LocalToRemote { .. }) {
+            bail!(
+                "--remove-source-files only applies to a push (local source, remote \
+                 destination); use `blit move` to move a whole tree"
+            );
+        }
+        if args.unpack {
+            bail!(
+                "--remove-source-files cannot be combined with --unpack: the archive's \
+                 members are not files to remove"
+            );
+        }
+    }
+
+    if args.manifest_checkpoint.is_some() {
+        check_manifest_checkpoint(args, &route)?;
+    }
+
+    // `--copy-links` / `--copy-dirlinks` / `--copy-unsafe-links` shape
+    // this host's own scan. A daemon SOURCE never dereferences links:
+    // following one could serve files from outside the module root.
+    if args.symlink_deref() != SymlinkDeref::None
+        && matches!(
+            &route,
+            TransferRoute::RemoteToLocal { .. } | TransferRoute::RemoteToRemoteDelegated { .. }
+        )
+    {
+        bail!(
+            "--copy-links / --copy-dirlinks / --copy-unsafe-links only apply to a local source: \
+             a daemon serving a pull never follows symlinks out of its module"
+        );
+    }
+
+    // `--metadata-only` is a touch pass: it must never delete, and the
+    // delegated remote→remote spec has no field to carry it.
+    if args.metadata_only {
+        if mode.is_mirror() {
+            bail!(
+                "--metadata-only cannot be combined with mirror: a metadata repair never deletes"
+            );
+        }
+        if matches!(&route, TransferRoute::RemoteToRemoteDelegated { .. }) {
+            bail!("--metadata-only is not supported for remote-to-remote transfers");
+        }
+    }
+
+    check_delegated_unsupported(args, &route)?;
+    check_max_files(args, &route)?;
+    check_checksum_seed(args, &route)?;
+    check_remote_options(args, &route)?;
+    check_reconnect(args, &route)?;
+    check_ownership(args, &route)?;
+
+    install_checksum_cache(args)?;
+
+    // `--sparse` / `--no-sparse` steer the local copy engine's hole
+    // handling; pushed and pulled bytes are written by the receiving
+    // stream, which never consults them.
+    if (args.sparse || args.no_sparse) && !matches!(&route, TransferRoute::LocalToLocal { .. }) {
+        bail!("--sparse / --no-sparse only apply to a local copy (both endpoints local paths)");
+    }
+    if args.preallocate && !matches!(&route, TransferRoute::LocalToLocal { .. }) {
+        bail!("--preallocate only applies to a local copy (both endpoints local paths)");
+    }
+    if args.dedupe && !matches!(&route, TransferRoute::LocalToLocal { .. }) {
+        bail!("--dedupe only applies to a local copy (both endpoints local paths)");
+    }
+    validate_reflink_dest(args, &route)?;
+
+    // `--vss` / `--snapshot` snapshot a volume on THIS host: the source
+    // must be local.
+    for (set, flag) in [(args.vss, "--vss"), (args.snapshot, "--snapshot")] {
+        if !set {
+            continue;
+        }
+        if !matches!(
+            &route,
+            TransferRoute::LocalToLocal { .. } | TransferRoute::LocalToRemote { .. }
+        ) {
+            bail!("{flag} only applies to a local source (local copy or push)");
+        }
+        if args.unpack {
+            bail!("{flag} cannot be combined with --unpack: an archive is read as one file");
+        }
+    }
+
+    // The tar-shard planner runs on the SOURCE: a remote source plans
+    // its own shards and no open field carries these ceilings.
+    if shard_flags_set(args)
+        && !matches!(
+            &route,
+            TransferRoute::LocalToLocal { .. } | TransferRoute::LocalToRemote { .. }
+        )
+    {
+        bail!("--shard-max-bytes / --shard-max-files / --no-shard / --sort-order only apply to a local source (local copy or push)");
+    }
+
+    // `--skip-locked` acts at the local apply's availability check; a
+    // push reads each file as it streams, with no check to skip at.
+    if args.skip_locked && !matches!(&route, TransferRoute::LocalToLocal { .. }) {
+        bail!("--skip-locked only applies to a local transfer (both endpoints local paths)");
+    }
+
+    // `--delay-updates` stages in the local apply's sink; the remote
+    // receive paths write through their own streams.
+    if args.delay_updates && !matches!(&route, TransferRoute::LocalToLocal { .. }) {
+        bail!("--delay-updates only applies to a local transfer (both endpoints local paths)");
+    }
+
+    // `--no-hidden` / `--exclude-attributes` act in this host's own
+    // scan. A daemon source enumerates without them, and a daemon
+    // destination scopes mirror deletions by the wire `FilterSpec`,
+    // which has no attribute rules — it would delete the very
+    // destination entries the source skipped.
+    if args.no_hidden || args.exclude_attributes.is_some() {
+        if matches!(
+            &route,
+            TransferRoute::RemoteToLocal { .. } | TransferRoute::RemoteToRemoteDelegated { .. }
+        ) {
+            bail!("--no-hidden / --exclude-attributes only apply to a local source (local copy or push)");
+        }
+        if mode.is_mirror() && matches!(&route, TransferRoute::LocalToRemote { .. }) {
+            bail!(
+                "--no-hidden / --exclude-attributes cannot be combined with a mirror to a \
+                 remote destination: the daemon would delete the destination entries \
+                 the source skipped"
+            );
+        }
+        warn_if_attributes_inert();
+    }
+
+    // `--list-dest` previews a push: the source is enumerated here and
+    // the destination is listed through the daemon's `find` RPC.
+    if args.list_dest {
+        let TransferRoute::LocalToRemote { src, dst, .. } = &route else {
+            bail!(
+                "--list-dest compares a local source against a remote destination \
+                 (e.g. `blit copy --list-dest ./site/ server:/module/site/`)"
+            );
+        };
+        if !src.exists() {
+            bail!("source path does not exist: {}", src.display());
+        }
+        ensure_remote_destination_supported(dst)?;
+        return list_dest::run_list_dest(args, src, dst).await;
+    }
+
     warn_if_dropping_windows_metadata(args);
 
     // For mirror operations, prompt unless --yes or --dry-run
@@ -279,7 +765,7 @@ async fn run_transfer_inner(
     // Banner goes to stderr so stdout stays reserved for the summary /
     // JSON output. Version dropped — `blit --version` is the right place
     // for that, not every invocation.
-    if !args.json {
+    if !args.json && !args.quiet {
         eprintln!("starting {} {} -> {}", operation, src_display, dst_display);
         if args.verbose && dst_display != pre_resolve_display {
             eprintln!(
@@ -343,6 +829,7 @@ pub async fn run_move(
 ) -> Result<()> {
     lifecycle_trace.record("transfer_dispatch_begin", None);
     let result = run_move_inner(ctx, args, lifecycle_trace).await;
+    flush_checksum_cache();
     lifecycle_trace.record(
         "transfer_dispatch_end",
         Some(crate::lifecycle_result_outcome(&result)),
@@ -364,6 +851,20 @@ async fn run_move_inner(
     if args.dry_run {
         bail!("move does not support --dry-run");
     }
+    if args.list_dest {
+        bail!("move does not support --list-dest; preview with `blit copy --list-dest SRC DST`");
+    }
+    if args.remove_source_files {
+        bail!(
+            "move does not support --remove-source-files: move already deletes the \
+             source. Use `blit copy --remove-source-files SRC DST` to remove only \
+             the files a push sent."
+        );
+    }
+    if args.dedupe && !matches!(&route, TransferRoute::LocalToLocal { .. }) {
+        bail!("--dedupe only applies to a local copy (both endpoints local paths)");
+    }
+    validate_reflink_dest(args, &route)?;
 
     if args.detach {
         // `blit move` runs a source-delete step after the
@@ -396,12 +897,14 @@ async fn run_move_inner(
         || args.max_size.is_some()
         || args.min_age.is_some()
         || args.max_age.is_some()
-        || args.files_from.is_some();
+        || args.files_from.is_some()
+        || args.no_hidden
+        || args.exclude_attributes.is_some();
     if filters_set {
         bail!(
             "move does not support filters (--exclude / --include / \
              --min-size / --max-size / --min-age / --max-age / \
-             --files-from): the source-delete step would silently \
+             --files-from / --no-hidden / --exclude-attributes): the source-delete step would silently \
              remove files that were filtered out of the transfer. \
              Run `blit copy` with filters first, then `blit rm` the \
              remaining source manually if needed."
@@ -426,6 +929,39 @@ async fn run_move_inner(
         );
     }
 
+    // `--overwrite never` is `--ignore-existing` under another name, with
+    // the same data-loss shape. `if-newer` and `always` name compare
+    // rules that move already overrides by transferring every file, so
+    // the flag is rejected outright, as `--force` is below.
+    if args.overwrite.is_some() {
+        bail!(
+            "move does not support --overwrite: move already \
+             transfers every file unconditionally, and `never` would \
+             skip a source file the source-delete step then removes."
+        );
+    }
+
+    // Same data-loss shape as `--ignore-existing`, inverted: every
+    // source file absent from the destination would be skipped and
+    // then removed by the source-delete step.
+    if args.existing {
+        bail!(
+            "move does not support --existing: source files missing \
+             from the destination would be skipped during the transfer \
+             and then permanently removed by the source-delete step."
+        );
+    }
+
+    // With `--unpack` the source is an archive read as a tree; the
+    // source-delete step would remove the archive file itself.
+    if args.unpack {
+        bail!(
+            "move does not support --unpack: the source-delete step \
+             would remove the archive itself. Run `blit copy --unpack` \
+             and delete the archive once the transfer has completed."
+        );
+    }
+
     // R52-F1 (data-loss): reject `--null`. The flag routes the
     // local transfer into `null_sink`, which deliberately writes
     // nothing — then move's source-delete step removes the
@@ -443,6 +979,43 @@ async fn run_move_inner(
         );
     }
 
+    if args.metadata_only {
+        bail!(
+            "move does not support --metadata-only: no bytes would be \
+             transferred, and the source-delete step would then remove \
+             files the destination never received."
+        );
+    }
+
+    if args.delay_updates && !matches!(&route, TransferRoute::LocalToLocal { .. }) {
+        bail!("--delay-updates only applies to a local transfer (both endpoints local paths)");
+    }
+
+    if args.skip_locked && !matches!(&route, TransferRoute::LocalToLocal { .. }) {
+        bail!("--skip-locked only applies to a local transfer (both endpoints local paths)");
+    }
+
+    if shard_flags_set(args)
+        && !matches!(
+            &route,
+            TransferRoute::LocalToLocal { .. } | TransferRoute::LocalToRemote { .. }
+        )
+    {
+        bail!("--shard-max-bytes / --shard-max-files / --no-shard / --sort-order only apply to a local source (local copy or push)");
+    }
+
+    if args.manifest_checkpoint.is_some() {
+        check_manifest_checkpoint(args, &route)?;
+    }
+
+    if args.vss || args.snapshot {
+        bail!(
+            "move does not support --vss / --snapshot: the copy would come \
+             from the snapshot, but the source-delete step would remove the \
+             live files, including edits made after the snapshot."
+        );
+    }
+
     // R54-F2 (data-loss), refreshed at otp-10b-2 (and its codex F3):
     // reject `--force`, `--ignore-times`, and `--size-only` for move.
     //
@@ -492,6 +1065,15 @@ async fn run_move_inner(
         );
     }
 
+    check_delegated_unsupported(args, &route)?;
+    check_max_files(args, &route)?;
+    check_checksum_seed(args, &route)?;
+    check_remote_options(args, &route)?;
+    check_reconnect(args, &route)?;
+    check_ownership(args, &route)?;
+
+    install_checksum_cache(args)?;
+
     warn_if_dropping_windows_metadata(args);
 
     // Prompt for confirmation before move (which deletes source)
@@ -504,7 +1086,7 @@ async fn run_move_inner(
         return Ok(());
     }
 
-    if !args.json {
+    if !args.json && !args.quiet {
         eprintln!("starting move {} -> {}", src_display, dst_display);
         if args.verbose && dst_display != pre_resolve_display {
             eprintln!(
@@ -713,6 +1295,17 @@ async fn run_move_inner(
 const DROP_WINDOWS_METADATA_WARNING: &str =
     "warning: --drop-windows-metadata permanently discards Windows file attributes and named data streams";
 
+/// File attributes are a Windows concept; elsewhere the flags parse
+/// and validate but skip nothing.
+fn warn_if_attributes_inert() {
+    if !cfg!(windows) {
+        eprintln!(
+            "warning: --no-hidden / --exclude-attributes have no effect on this platform \
+             (Windows file attributes only)"
+        );
+    }
+}
+
 fn warn_if_dropping_windows_metadata(args: &TransferArgs) {
     if args.drop_windows_metadata {
         eprintln!("{DROP_WINDOWS_METADATA_WARNING}");
@@ -751,6 +1344,9 @@ mod tests {
             size_only: false,
             ignore_times: false,
             ignore_existing: false,
+            overwrite: None,
+            existing: false,
+            metadata_only: false,
             force: false,
             verbose: false,
             progress: false,
@@ -760,19 +1356,71 @@ mod tests {
             force_grpc: false,
             detach: false,
             resume: false,
+            skip_locked: false,
+            vss: false,
+            stats: false,
+            info: Vec::new(),
+            human_readable: false,
+            list_dest: false,
+            no_hidden: false,
+            exclude_attributes: None,
+            relative: false,
+            prune_empty_dirs: false,
+            crtimes: false,
+            super_user: false,
+            fake_super: false,
+            manifest_checkpoint: None,
+            checksum_cache: false,
+            remove_source_files: false,
+            snapshot: false,
+            delay_updates: false,
+            temp_dir: None,
             drop_windows_metadata: false,
             retry: 0,
             wait: 5,
+            reconnect: None,
+            max_files: None,
+            checksum_seed: None,
+            chmod: Vec::new(),
+            chown: None,
+            xattrs: false,
+            xattrs_all: false,
+            xattrs_include: Vec::new(),
+            xattrs_exclude: Vec::new(),
+            remote_option: Vec::new(),
+            quiet: false,
+            numeric_progress: false,
+            warn_files: 0,
             null: false,
             json: false,
             exclude: vec![],
             include: vec![],
+            exclude_from: Vec::new(),
+            include_from: Vec::new(),
             files_from: None,
             min_size: None,
             max_size: None,
             min_age: None,
             max_age: None,
+            rename: Vec::new(),
+            unpack: false,
+            copy_links: false,
+            copy_dirlinks: false,
+            copy_unsafe_links: false,
+            safe_links: false,
+            sparse: false,
+            no_sparse: false,
+            preallocate: false,
+            dedupe: false,
+            reflink_dest: None,
+            streams: None,
+            chunk_size: None,
+            block_size: None,
+            shard_max_bytes: None,
+            shard_max_files: None,
+            no_shard: false,
             delete_scope: "subset".into(),
+            sort_order: "none".into(),
         };
 
         runtime().block_on(run_local_transfer(
@@ -807,6 +1455,9 @@ mod tests {
             size_only: false,
             ignore_times: false,
             ignore_existing: false,
+            overwrite: None,
+            existing: false,
+            metadata_only: false,
             force: false,
             verbose: false,
             progress: false,
@@ -816,19 +1467,71 @@ mod tests {
             force_grpc: false,
             detach: false,
             resume: false,
+            skip_locked: false,
+            vss: false,
+            stats: false,
+            info: Vec::new(),
+            human_readable: false,
+            list_dest: false,
+            no_hidden: false,
+            exclude_attributes: None,
+            relative: false,
+            prune_empty_dirs: false,
+            crtimes: false,
+            super_user: false,
+            fake_super: false,
+            manifest_checkpoint: None,
+            checksum_cache: false,
+            remove_source_files: false,
+            snapshot: false,
+            delay_updates: false,
+            temp_dir: None,
             drop_windows_metadata: false,
             retry: 0,
             wait: 5,
+            reconnect: None,
+            max_files: None,
+            checksum_seed: None,
+            chmod: Vec::new(),
+            chown: None,
+            xattrs: false,
+            xattrs_all: false,
+            xattrs_include: Vec::new(),
+            xattrs_exclude: Vec::new(),
+            remote_option: Vec::new(),
+            quiet: false,
+            numeric_progress: false,
+            warn_files: 0,
             null: false,
             json: false,
             exclude: vec![],
             include: vec![],
+            exclude_from: Vec::new(),
+            include_from: Vec::new(),
             files_from: None,
             min_size: None,
             max_size: None,
             min_age: None,
             max_age: None,
+            rename: Vec::new(),
+            unpack: false,
+            copy_links: false,
+            copy_dirlinks: false,
+            copy_unsafe_links: false,
+            safe_links: false,
+            sparse: false,
+            no_sparse: false,
+            preallocate: false,
+            dedupe: false,
+            reflink_dest: None,
+            streams: None,
+            chunk_size: None,
+            block_size: None,
+            shard_max_bytes: None,
+            shard_max_files: None,
+            no_shard: false,
             delete_scope: "subset".into(),
+            sort_order: "none".into(),
         };
 
         runtime().block_on(run_local_transfer(
@@ -869,6 +1572,9 @@ mod tests {
             size_only: false,
             ignore_times: false,
             ignore_existing: false,
+            overwrite: None,
+            existing: false,
+            metadata_only: false,
             force: false,
             verbose: false,
             progress: false,
@@ -878,19 +1584,71 @@ mod tests {
             force_grpc: false,
             detach,
             resume: false,
+            skip_locked: false,
+            vss: false,
+            stats: false,
+            info: Vec::new(),
+            human_readable: false,
+            list_dest: false,
+            no_hidden: false,
+            exclude_attributes: None,
+            relative: false,
+            prune_empty_dirs: false,
+            crtimes: false,
+            super_user: false,
+            fake_super: false,
+            manifest_checkpoint: None,
+            checksum_cache: false,
+            remove_source_files: false,
+            snapshot: false,
+            delay_updates: false,
+            temp_dir: None,
             drop_windows_metadata: false,
             retry: 0,
             wait: 5,
+            reconnect: None,
+            max_files: None,
+            checksum_seed: None,
+            chmod: Vec::new(),
+            chown: None,
+            xattrs: false,
+            xattrs_all: false,
+            xattrs_include: Vec::new(),
+            xattrs_exclude: Vec::new(),
+            remote_option: Vec::new(),
+            quiet: false,
+            numeric_progress: false,
+            warn_files: 0,
             null: false,
             json: false,
             exclude: vec![],
             include: vec![],
+            exclude_from: Vec::new(),
+            include_from: Vec::new(),
             files_from: None,
             min_size: None,
             max_size: None,
             min_age: None,
             max_age: None,
+            rename: Vec::new(),
+            unpack: false,
+            copy_links: false,
+            copy_dirlinks: false,
+            copy_unsafe_links: false,
+            safe_links: false,
+            sparse: false,
+            no_sparse: false,
+            preallocate: false,
+            dedupe: false,
+            reflink_dest: None,
+            streams: None,
+            chunk_size: None,
+            block_size: None,
+            shard_max_bytes: None,
+            shard_max_files: None,
+            no_shard: false,
             delete_scope: "subset".into(),
+            sort_order: "none".into(),
         }
     }
 
@@ -934,6 +1692,123 @@ mod tests {
         assert!(msg.contains("move does not support --detach"), "got: {msg}");
     }
 
+    #[test]
+    fn unpack_rejected_without_a_remote_destination() {
+        let tmp = tempdir().unwrap();
+        let archive = tmp.path().join("site.tar");
+        std::fs::write(&archive, b"").unwrap();
+        let dst = tmp.path().join("dst");
+        let ctx = ctx();
+        let mut args = gate_args(
+            archive.to_str().unwrap(),
+            dst.to_str().unwrap(),
+            false,
+            true,
+        );
+        args.unpack = true;
+        let err = runtime()
+            .block_on(run_transfer(
+                &ctx,
+                &args,
+                TransferKind::Copy,
+                &TransferLifecycleTrace::disabled(),
+            ))
+            .expect_err("local→local must reject --unpack");
+        let msg = format!("{err:#}");
+        assert!(msg.contains("--unpack is only supported"), "got: {msg}");
+        assert!(!dst.exists(), "the gate must fire before any copy");
+    }
+
+    #[test]
+    fn reconnect_rejected_off_push_and_pull_and_out_of_range() {
+        let tmp = tempdir().unwrap();
+        let src = tmp.path().join("src");
+        let dst = tmp.path().join("dst");
+        std::fs::create_dir_all(&src).unwrap();
+        let ctx = ctx();
+        let mut args = gate_args(src.to_str().unwrap(), dst.to_str().unwrap(), false, true);
+        args.reconnect = Some("30s".into());
+        let err = runtime()
+            .block_on(run_transfer(
+                &ctx,
+                &args,
+                TransferKind::Copy,
+                &TransferLifecycleTrace::disabled(),
+            ))
+            .expect_err("local→local must reject --reconnect");
+        let msg = format!("{err:#}");
+        assert!(msg.contains("--reconnect only applies"), "got: {msg}");
+        assert!(!dst.exists(), "the gate must fire before any copy");
+
+        let err = runtime()
+            .block_on(run_move(&ctx, &args, &TransferLifecycleTrace::disabled()))
+            .expect_err("a local move must reject --reconnect");
+        let msg = format!("{err:#}");
+        assert!(msg.contains("--reconnect only applies"), "got: {msg}");
+        assert!(
+            src.exists() && !dst.exists(),
+            "the gate must fire before any move"
+        );
+
+        assert_eq!(
+            build_reconnect(&args).unwrap(),
+            Some(Duration::from_secs(30))
+        );
+        for bad in ["0s", "11m", "soon"] {
+            args.reconnect = Some(bad.into());
+            let msg = format!("{:#}", build_reconnect(&args).unwrap_err());
+            assert!(msg.contains("invalid --reconnect"), "{bad}: {msg}");
+        }
+    }
+
+    #[test]
+    fn delegated_unsupported_flags_rejected_on_copy_and_move() {
+        let ctx = ctx();
+        let mut args = gate_args("host-a:/m/", "host-b:/m/", false, true);
+        args.chmod = vec!["D755".into()];
+        let err = runtime()
+            .block_on(run_transfer(
+                &ctx,
+                &args,
+                TransferKind::Copy,
+                &TransferLifecycleTrace::disabled(),
+            ))
+            .expect_err("remote→remote copy must reject --chmod");
+        assert!(
+            format!("{err:#}").contains("--chmod is not supported for remote-to-remote"),
+            "got: {err:#}"
+        );
+        let err = runtime()
+            .block_on(run_move(&ctx, &args, &TransferLifecycleTrace::disabled()))
+            .expect_err("remote→remote move must reject --chmod");
+        assert!(
+            format!("{err:#}").contains("--chmod is not supported for remote-to-remote"),
+            "got: {err:#}"
+        );
+    }
+
+    #[test]
+    fn max_files_zero_is_rejected_before_any_copy() {
+        let tmp = tempdir().unwrap();
+        let src = tmp.path().join("src");
+        let dst = tmp.path().join("dst");
+        std::fs::create_dir_all(&src).unwrap();
+        let ctx = ctx();
+        let mut args = gate_args(src.to_str().unwrap(), dst.to_str().unwrap(), false, true);
+        args.max_files = Some(0);
+        let err = runtime()
+            .block_on(run_transfer(
+                &ctx,
+                &args,
+                TransferKind::Copy,
+                &TransferLifecycleTrace::disabled(),
+            ))
+            .expect_err("--max-files 0 must be refused");
+        let msg = format!("{err:#}");
+        assert!(msg.contains("invalid --max-files 0"), "got: {msg}");
+        assert!(!dst.exists(), "the gate must fire before any copy");
+    }
+
     fn captured_trace() -> (
         TransferLifecycleTrace,
         Arc<Mutex<Vec<blit_core::remote::transfer::TransferLifecycleEvent>>>,
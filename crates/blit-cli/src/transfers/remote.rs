@@ -1,5 +1,6 @@
+use super::stats::{print_stats, render_bytes, render_stream_bandwidth, TransferStats};
 use crate::cli::TransferArgs;
-use eyre::Result;
+use eyre::{bail, Result};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
@@ -8,7 +9,8 @@ use tokio::time::{interval, MissedTickBehavior};
 
 use blit_app::transfers::compare::{comparison_mode, move_comparison_mode, CompareFlags};
 use blit_app::transfers::remote::{
-    run_remote_pull, run_remote_push, PullExecution, PullVerbOutcome, PushExecution,
+    is_pipe, run_remote_pull, run_remote_push, PullExecution, PullVerbOutcome, PushExecution,
+    StreamBandwidth,
 };
 use blit_core::remote::transfer::{
     ProgressEvent, ProgressTotals, RemoteTransferProgress, TransferLifecycleTrace,
@@ -23,6 +25,32 @@ use blit_app::endpoints::format_remote_endpoint;
 /// preserved across the retype.
 pub type DeferredPullState = PullVerbOutcome;
 
+/// Which lines the progress monitor prints: `--info` categories, with
+/// `-p` / `-v` folded in by [`MonitorLines::for_args`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct MonitorLines {
+    /// The once-a-second `[progress]` lines and the final one.
+    pub progress: bool,
+    /// One line per finished file.
+    pub names: bool,
+    /// One `deleting PATH` line per mirror deletion.
+    pub deletions: bool,
+}
+
+impl MonitorLines {
+    pub(crate) fn for_args(args: &TransferArgs) -> Self {
+        Self {
+            progress: args.effective_progress() || args.verbose,
+            names: args.show_names(),
+            deletions: args.show_deletions(),
+        }
+    }
+
+    fn any(self) -> bool {
+        self.progress || self.names || self.deletions
+    }
+}
+
 /// Spawn the per-transfer progress monitor. `suppress_final_line=true`
 /// lets move callers gate the post-transfer "[progress] final: …"
 /// line so a transfer-looking success summary doesn't appear on
@@ -31,12 +59,11 @@ pub type DeferredPullState = PullVerbOutcome;
 /// user wants liveness signal during the transfer; only the
 /// post-transfer "final:" line is gated (R53-F1).
 pub(crate) fn spawn_progress_monitor_with_options(
-    enabled: bool,
-    verbose: bool,
+    lines: MonitorLines,
     json: bool,
     suppress_final_line: bool,
 ) -> (Option<RemoteTransferProgress>, Option<JoinHandle<()>>) {
-    if !enabled {
+    if !lines.any() {
         return (None, None);
     }
 
@@ -60,8 +87,10 @@ pub(crate) fn spawn_progress_monitor_with_options(
                     match event {
                         Some(event) => {
                             totals.apply(&event);
-                            if let ProgressEvent::FileComplete { path } = &event {
-                                if json {
+                            match &event {
+                                ProgressEvent::FileComplete { path }
+                                    if json && (lines.progress || lines.names) =>
+                                {
                                     // `bytes` stays in the JSON shape for
                                     // stream compatibility; per-event bytes
                                     // no longer exist under the contract
@@ -69,17 +98,29 @@ pub(crate) fn spawn_progress_monitor_with_options(
                                     // always 0.
                                     eprintln!(
                                         "{{\"event\":\"file_complete\",\"path\":\"{}\",\"bytes\":0}}",
-                                        path.replace('\\', "\\\\").replace('"', "\\\""),
+                                        json_escape(path),
                                     );
-                                } else if verbose {
+                                }
+                                ProgressEvent::FileComplete { path } if !json && lines.names => {
                                     println!("{}", path);
                                 }
+                                ProgressEvent::Deleted { path } if lines.deletions => {
+                                    if json {
+                                        eprintln!(
+                                            "{{\"event\":\"deleted\",\"path\":\"{}\"}}",
+                                            json_escape(path),
+                                        );
+                                    } else {
+                                        println!("deleting {path}");
+                                    }
+                                }
+                                _ => {}
                             }
                         }
                         None => break,
                     }
                 }
-                _ = ticker.tick() => {
+                _ = ticker.tick(), if lines.progress => {
                     if totals.started() {
                         let now = Instant::now();
                         let elapsed = now.duration_since(start).as_secs_f64().max(1e-6);
@@ -123,6 +164,9 @@ pub(crate) fn spawn_progress_monitor_with_options(
             }
         }
 
+        if !lines.progress {
+            return;
+        }
         if totals.started() && !suppress_final_line {
             let elapsed = start.elapsed().as_secs_f64().max(1e-6);
             let avg_bps = (totals.bytes as f64) / elapsed;
@@ -158,13 +202,76 @@ pub(crate) fn spawn_progress_monitor_with_options(
     (Some(progress), Some(join))
 }
 
+/// `path` escaped for the hand-built NDJSON progress lines.
+fn json_escape(path: &str) -> String {
+    path.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// The progress monitor `args` asks for: `--numeric-progress`'s
+/// one-line counter, or the interactive / `--verbose` / `--info` /
+/// `--json` one.
+/// `suppress_final_line` as for [`spawn_progress_monitor_with_options`].
+pub(crate) fn spawn_transfer_progress(
+    args: &TransferArgs,
+    suppress_final_line: bool,
+) -> (Option<RemoteTransferProgress>, Option<JoinHandle<()>>) {
+    if args.numeric_progress {
+        return spawn_numeric_progress_monitor(suppress_final_line);
+    }
+    spawn_progress_monitor_with_options(
+        MonitorLines::for_args(args),
+        args.json,
+        suppress_final_line,
+    )
+}
+
+/// `--numeric-progress`: `PERCENT% DONE/TOTAL bytes` on stderr,
+/// rewritten in place (`\r`) every second. TOTAL is the size of every
+/// file the source enumerated, so a run that skips unchanged files
+/// stays below 100% until the final line, which reports what was
+/// actually sent (`100% N/N bytes`) and ends with a newline.
+pub(crate) fn spawn_numeric_progress_monitor(
+    suppress_final_line: bool,
+) -> (Option<RemoteTransferProgress>, Option<JoinHandle<()>>) {
+    fn render(done: u64, total: u64) {
+        let percent = done
+            .saturating_mul(100)
+            .checked_div(total)
+            .map_or(0, |percent| percent.min(99));
+        eprint!("\r{percent}% {done}/{} bytes", total.max(done));
+    }
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<ProgressEvent>();
+    let progress = RemoteTransferProgress::new(tx);
+    let join = tokio::spawn(async move {
+        let mut totals = ProgressTotals::default();
+        let mut ticker = interval(Duration::from_secs(1));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                biased;
+                event = rx.recv() => match event {
+                    Some(event) => totals.apply(&event),
+                    None => break,
+                },
+                _ = ticker.tick() => render(totals.bytes, totals.manifest_bytes),
+            }
+        }
+        if !suppress_final_line {
+            eprintln!("\r100% {0}/{0} bytes", totals.bytes);
+        }
+    });
+    (Some(progress), Some(join))
+}
+
 /// The user's compare flags, lifted off clap once for both verbs —
 /// the inputs to the one `transfers::compare` mapping (otp-10b-2).
 fn verb_compare_flags(args: &TransferArgs) -> CompareFlags {
     CompareFlags {
-        checksum: args.checksum,
+        // `--metadata-only` keys its repair on content equality.
+        checksum: args.checksum || args.metadata_only,
         size_only: args.size_only,
-        ignore_times: args.ignore_times,
+        ignore_times: args.overwrite_always(),
         force: args.force,
     }
 }
@@ -223,13 +330,64 @@ pub async fn run_remote_push_transfer_deferred(
 pub struct DeferredPushState {
     pub summary: blit_core::generated::TransferSummary,
     pub destination: String,
+    pub elapsed: Duration,
+    pub source_files_removed: u64,
+    pub bandwidth: StreamBandwidth,
 }
 
 pub fn print_deferred_push_result(args: &TransferArgs, state: &DeferredPushState) {
+    if args.quiet {
+        return;
+    }
+    let removed = args
+        .remove_source_files
+        .then_some(state.source_files_removed);
     if args.json {
-        print_push_json(&state.summary, &state.destination);
+        print_push_json(
+            &state.summary,
+            &state.destination,
+            removed,
+            &state.bandwidth,
+        );
     } else {
-        describe_push_result(&state.summary, &state.destination);
+        describe_push_result(&state.summary, &state.destination, args.human_readable);
+        if let Some(removed) = removed {
+            println!("Removed {removed} source file(s).");
+        }
+        if args.show_stats() {
+            print_stats(
+                &session_stats(&state.summary, state.elapsed),
+                args.human_readable,
+            );
+            print!(
+                "{}",
+                render_stream_bandwidth(&state.bandwidth, args.human_readable)
+            );
+        } else if args.verbose {
+            // Diagnostics stay on stderr and only under --verbose.
+            eprint!(
+                "{}",
+                render_stream_bandwidth(&state.bandwidth, args.human_readable)
+            );
+        }
+    }
+}
+
+/// `--stats` view of a push or pull: the session summary scores only
+/// what the destination wrote, so the source-size lines (examined,
+/// unchanged, speedup) are omitted, as is zero-copy — the session
+/// carriers never score it (otp-10a).
+fn session_stats(
+    summary: &blit_core::generated::TransferSummary,
+    elapsed: Duration,
+) -> TransferStats {
+    TransferStats {
+        files_transferred: summary.files_transferred,
+        files_resumed: summary.files_resumed,
+        transferred_bytes: summary.bytes_transferred,
+        entries_deleted: summary.entries_deleted,
+        elapsed,
+        ..TransferStats::default()
     }
 }
 
@@ -269,13 +427,8 @@ async fn run_remote_push_transfer_inner(
     defer_output: bool,
     lifecycle_trace: &TransferLifecycleTrace,
 ) -> Result<DeferredPushState> {
-    let show_progress = args.effective_progress() || args.verbose;
-    let (progress_handle, progress_task) = spawn_progress_monitor_with_options(
-        show_progress,
-        args.verbose,
-        args.json,
-        defer_output, // R53-F1: suppress the final progress line on move
-    );
+    // R53-F1: suppress the final progress line on move.
+    let (progress_handle, progress_task) = spawn_transfer_progress(args, defer_output);
 
     // Filter parity: the wire FilterSpec rides `SessionOpen.filter`
     // (otp-10a); the session's SOURCE end applies it through the
@@ -312,6 +465,14 @@ async fn run_remote_push_transfer_inner(
         source,
         remote: remote.clone(),
         filter: Some(filter_spec),
+        rename: super::build_rename_rules(args)?,
+        unpack: args.unpack,
+        symlink_deref: args.symlink_deref(),
+        safe_links: args.safe_links,
+        vss: args.vss,
+        snapshot: args.snapshot,
+        dial_request: super::build_dial_request(args)?,
+        plan_options: super::build_plan_options(args)?,
         mirror_mode,
         mirror_kind,
         force_grpc: args.force_grpc,
@@ -322,16 +483,35 @@ async fn run_remote_push_transfer_inner(
         // print + source-delete gate never fire.
         require_complete_scan: mirror_mode,
         drop_windows_metadata: args.drop_windows_metadata,
+        crtimes: args.crtimes,
+        owner: args.super_user || args.fake_super,
+        fake_super: args.fake_super,
+        manifest_checkpoint: args.manifest_checkpoint.clone(),
+        prune_empty_dirs: args.prune_empty_dirs,
         resume: args.resume,
-        resume_block_size: 0, // destination default (1 MiB)
+        // `--block-size`, else the destination default (1 MiB).
+        resume_block_size: super::build_block_size(args)? as u32,
         compare_mode,
-        ignore_existing: args.ignore_existing,
+        ignore_existing: args.skip_existing(),
+        existing_only: args.existing,
+        max_files: args.max_files.unwrap_or(0),
+        checksum_seed: args.checksum_seed.unwrap_or(0),
+        chmod: super::build_chmod(args)?,
+        chown: args.chown.clone().unwrap_or_default(),
+        xattrs: super::build_xattrs(args)?,
+        remote_options: blit_core::remote_options::parse_remote_options(&args.remote_option)?,
+        file_count_warning: super::build_file_count_warning(args),
+        metadata_only: args.metadata_only,
+        reconnect: super::build_reconnect(args)?,
+        remove_source_files: args.remove_source_files,
         remote_label: format_remote_endpoint(&remote),
         lifecycle_trace: lifecycle_trace.clone(),
     };
 
-    // Push has no caller-side destructive step (mirror-delete is
-    // daemon-side and surfaces via the summary), so unlike the pull
+    // Push's only caller-side destructive step is
+    // `--remove-source-files`, inside the library call after the
+    // session (mirror-delete is daemon-side and surfaces via the
+    // summary), so unlike the pull
     // lifecycle there is no need to drop the progress handle
     // *before* a follow-up library call — the monitor's lifetime
     // already matches the RPC.
@@ -352,6 +532,9 @@ async fn run_remote_push_transfer_inner(
     let state = DeferredPushState {
         summary: outcome.summary,
         destination: outcome.destination,
+        elapsed: outcome.elapsed,
+        source_files_removed: outcome.source_files_removed,
+        bandwidth: outcome.bandwidth,
     };
     if !defer_output {
         super::render_result(lifecycle_trace, || {
@@ -408,13 +591,42 @@ pub async fn run_remote_pull_transfer_deferred(
 }
 
 pub fn print_deferred_pull_result(args: &TransferArgs, state: &DeferredPullState) {
+    if args.quiet {
+        return;
+    }
     if args.json {
         print_pull_json(&state.summary, &state.dest_root);
     } else {
-        describe_pull_result(&state.summary, &state.dest_root);
+        describe_pull_result(&state.summary, &state.dest_root, args.human_readable);
+        if args.show_stats() {
+            print_stats(
+                &session_stats(&state.summary, state.elapsed),
+                args.human_readable,
+            );
+        }
     }
 }
 
+/// A pipe takes a tar of every pulled file and nothing else: there is
+/// no destination tree to delete from, patch or restamp, and a move
+/// would delete a source whose copy only the pipe's reader holds.
+fn check_pipe_destination(args: &TransferArgs, mirror_mode: bool, move_verb: bool) -> Result<()> {
+    let refused = if mirror_mode {
+        "a mirror"
+    } else if move_verb {
+        "a move"
+    } else if args.resume {
+        "--resume"
+    } else if args.metadata_only {
+        "--metadata-only"
+    } else {
+        return Ok(());
+    };
+    bail!(
+        "{refused} cannot write into a pipe; a pipe destination takes a plain copy as a tar stream"
+    )
+}
+
 async fn run_remote_pull_transfer_inner(
     args: &TransferArgs,
     remote: RemoteEndpoint,
@@ -430,13 +642,14 @@ async fn run_remote_pull_transfer_inner(
     // deletions with it — identical rules to push, by construction.
     let filter_spec = super::build_filter_spec(args)?;
 
-    let show_progress = args.effective_progress() || args.verbose;
-    let (progress_handle, progress_task) = spawn_progress_monitor_with_options(
-        show_progress,
-        args.verbose,
-        args.json,
-        defer_output, // R53-F1: suppress final progress line on move
-    );
+    // A pipe destination receives the tree as one tar (`PullExecution::pack`).
+    let pack = is_pipe(dest_root);
+    if pack {
+        check_pipe_destination(args, mirror_mode, move_verb)?;
+    }
+
+    // R53-F1: suppress the final progress line on move.
+    let (progress_handle, progress_task) = spawn_transfer_progress(args, defer_output);
 
     // R59 #1 F2: --delete-scope → wire MirrorMode, same mapping as the
     // push verb (FilteredSubset default so `--include … --mirror`
@@ -461,7 +674,10 @@ async fn run_remote_pull_transfer_inner(
     let execution = PullExecution {
         remote: remote.clone(),
         dest_root: dest_root.to_path_buf(),
+        pack,
         filter: Some(filter_spec),
+        rename: super::build_rename_rules(args)?,
+        dial_request: super::build_dial_request(args)?,
         mirror_mode,
         mirror_kind,
         force_grpc: args.force_grpc,
@@ -472,10 +688,24 @@ async fn run_remote_pull_transfer_inner(
         // the session refuses an incomplete-scan mirror on its own.
         require_complete_scan: move_verb,
         drop_windows_metadata: args.drop_windows_metadata,
+        crtimes: args.crtimes,
+        owner: args.super_user || args.fake_super,
+        fake_super: args.fake_super,
+        prune_empty_dirs: args.prune_empty_dirs,
         resume: args.resume,
-        resume_block_size: 0, // destination default (1 MiB)
+        // `--block-size`, else the destination default (1 MiB).
+        resume_block_size: super::build_block_size(args)? as u32,
         compare_mode,
-        ignore_existing: args.ignore_existing,
+        ignore_existing: args.skip_existing(),
+        existing_only: args.existing,
+        max_files: args.max_files.unwrap_or(0),
+        checksum_seed: args.checksum_seed.unwrap_or(0),
+        chmod: super::build_chmod(args)?,
+        chown: args.chown.clone().unwrap_or_default(),
+        xattrs: super::build_xattrs(args)?,
+        remote_options: blit_core::remote_options::parse_remote_options(&args.remote_option)?,
+        metadata_only: args.metadata_only,
+        reconnect: super::build_reconnect(args)?,
         remote_label: format_remote_endpoint(&remote),
         lifecycle_trace: lifecycle_trace.clone(),
     };
@@ -532,30 +762,58 @@ fn print_pull_json(summary: &blit_core::generated::TransferSummary, dest_root: &
         "bytes_transferred": summary.bytes_transferred,
         "files_resumed": summary.files_resumed,
         "entries_deleted": summary.entries_deleted,
+        "vanished_paths": summary.vanished_paths,
         "tcp_fallback": summary.in_stream_carrier_used,
     });
     println!("{}", serde_json::to_string_pretty(&summary).unwrap());
 }
 
-fn print_push_json(summary: &blit_core::generated::TransferSummary, destination: &str) {
+fn print_push_json(
+    summary: &blit_core::generated::TransferSummary,
+    destination: &str,
+    source_files_removed: Option<u64>,
+    bandwidth: &StreamBandwidth,
+) {
     use serde_json::json;
     // otp-10a: the push verb reports the session's destination-computed
     // summary. Keys that only the deleted driver could fill
     // (files_requested, bytes_zero_copy, first_payload_ms) are gone;
     // files_resumed is new with push-side --resume.
-    let summary = json!({
+    let mut summary = json!({
         "operation": "push",
         "destination": destination,
         "files_transferred": summary.files_transferred,
         "bytes_transferred": summary.bytes_transferred,
         "files_resumed": summary.files_resumed,
         "entries_deleted": summary.entries_deleted,
+        "vanished_paths": summary.vanished_paths,
         "tcp_fallback": summary.in_stream_carrier_used,
     });
+    if let Some(removed) = source_files_removed {
+        summary["source_files_removed"] = json!(removed);
+    }
+    summary["streams"] = bandwidth
+        .streams
+        .iter()
+        .map(|stream| {
+            json!({
+                "stream": stream.stream.0,
+                "bytes": stream.bytes,
+                "bytes_per_sec": bandwidth.bytes_per_sec(stream.bytes),
+            })
+        })
+        .collect();
+    summary["stream_bytes"] = json!(bandwidth.total_bytes());
+    summary["aggregate_bytes_per_sec"] = json!(bandwidth.aggregate_bytes_per_sec());
+    summary["stream_elapsed_ms"] = json!(bandwidth.elapsed.as_millis() as u64);
     println!("{}", serde_json::to_string_pretty(&summary).unwrap());
 }
 
-pub fn describe_pull_result(summary: &blit_core::generated::TransferSummary, dest_root: &Path) {
+pub fn describe_pull_result(
+    summary: &blit_core::generated::TransferSummary,
+    dest_root: &Path,
+    human_readable: bool,
+) {
     // otp-10b-2: the session's DESTINATION (this end) is the scorer.
     // The pinned `Pull complete:` prefix and `[gRPC fallback]` marker
     // keep their exact wording; the old driver-only zero-copy clause
@@ -567,9 +825,9 @@ pub fn describe_pull_result(summary: &blit_core::generated::TransferSummary, des
         String::new()
     };
     println!(
-        "Pull complete: {} file(s), {} bytes{}{} -> {}.",
+        "Pull complete: {} file(s), {}{}{} -> {}.",
         summary.files_transferred,
-        summary.bytes_transferred,
+        render_bytes(summary.bytes_transferred, human_readable),
         resumed,
         if summary.in_stream_carrier_used {
             " [gRPC fallback]"
@@ -589,9 +847,14 @@ pub fn describe_pull_result(summary: &blit_core::generated::TransferSummary, des
             summary.entries_deleted, plural
         );
     }
+    warn_vanished(summary);
 }
 
-pub fn describe_push_result(summary: &blit_core::generated::TransferSummary, destination: &str) {
+pub fn describe_push_result(
+    summary: &blit_core::generated::TransferSummary,
+    destination: &str,
+    human_readable: bool,
+) {
     // otp-10a: the session's DESTINATION is the scorer; the old
     // negotiation-phase lines (file counts scheduled, data port) died
     // with the per-direction driver. `[gRPC fallback]` keeps its exact
@@ -608,9 +871,9 @@ pub fn describe_push_result(summary: &blit_core::generated::TransferSummary, des
         String::new()
     };
     println!(
-        "Transfer complete: {} file(s), {} bytes{}{}.",
+        "Transfer complete: {} file(s), {}{}{}.",
         summary.files_transferred,
-        summary.bytes_transferred,
+        render_bytes(summary.bytes_transferred, human_readable),
         resumed,
         if summary.in_stream_carrier_used {
             " [gRPC fallback]"
@@ -631,9 +894,30 @@ pub fn describe_push_result(summary: &blit_core::generated::TransferSummary, des
             summary.entries_deleted, plural
         );
     }
+    warn_vanished(summary);
     println!("Destination: {}", destination);
 }
 
+/// Contract v15: files deleted at the source after the manifest listed
+/// them were skipped, not sent — say so, like rsync's "file has
+/// vanished", without failing a backup of a live tree.
+fn warn_vanished(summary: &blit_core::generated::TransferSummary) {
+    let vanished = &summary.vanished_paths;
+    if vanished.is_empty() {
+        return;
+    }
+    let preview: Vec<&str> = vanished.iter().take(5).map(String::as_str).collect();
+    let mut message = format!(
+        "warning: {} file(s) vanished from the source before they could be sent: {}",
+        vanished.len(),
+        preview.join(", ")
+    );
+    if vanished.len() > preview.len() {
+        message.push_str(&format!(" (and {} more)", vanished.len() - preview.len()));
+    }
+    eprintln!("{message}");
+}
+
 // This module's test surface is reserved for CLI-entry-point
 // behavior; library behavior is pinned in blit_app/blit_core.
 
@@ -653,6 +937,7 @@ mod session_fault_summary_tests {
             peer_notified: true,
             relative_path: Some(path.into()),
             io_kind: None,
+            data_plane_unreachable: false,
         }
     }
 
@@ -1,5 +1,6 @@
 use crate::cli::TransferArgs;
 use eyre::Result;
+use std::time::Duration;
 
 use blit_app::transfers::remote::{
     run_delegated_pull, run_delegated_pull_until_started, DelegatedPullExecution,
@@ -9,7 +10,8 @@ use blit_core::generated::DelegatedPullSummary;
 use blit_core::remote::transfer::{operation_spec::DelegatedSpecOptions, TransferLifecycleTrace};
 use blit_core::remote::RemoteEndpoint;
 
-use super::remote::spawn_progress_monitor_with_options;
+use super::remote::spawn_transfer_progress;
+use super::stats::{print_stats, render_bytes, TransferStats};
 use blit_app::endpoints::format_remote_endpoint;
 
 /// CLI-facing alias for the library's delegated-pull outcome.
@@ -86,14 +88,18 @@ fn delegated_pull_options(
         delete_all_scope: args.delete_scope_all(),
         filter: Some(filter_spec),
         size_only: args.size_only,
-        ignore_times: args.ignore_times || (move_verb && !args.checksum),
-        ignore_existing: args.ignore_existing,
+        ignore_times: args.overwrite_always() || (move_verb && !args.checksum),
+        ignore_existing: args.skip_existing(),
         force: args.force,
         checksum: args.checksum,
         resume: args.resume,
         block_size: 0,
         require_complete_scan: move_verb,
         drop_windows_metadata: args.drop_windows_metadata,
+        // Parsed (fallibly) by the caller — see `build_rename_rules`,
+        // `build_dial_request` and `build_block_size`.
+        rename: Vec::new(),
+        dial_request: None,
     }
 }
 
@@ -104,10 +110,28 @@ fn delegated_pull_options(
 // this A.0 sub-slice.
 
 pub fn print_deferred_delegated_result(args: &TransferArgs, state: &DeferredDelegatedState) {
+    if args.quiet {
+        return;
+    }
     if args.json {
         print_delegated_json(&state.summary, &state.src, &state.dst);
     } else {
-        describe_delegated_result(&state.summary, &state.src, &state.dst);
+        describe_delegated_result(&state.summary, &state.src, &state.dst, args.human_readable);
+        if args.show_stats() {
+            // The destination daemon scores the pull; only it knows the
+            // zero-copy share, and nothing here sees the source size.
+            print_stats(
+                &TransferStats {
+                    files_transferred: state.summary.files_transferred,
+                    transferred_bytes: state.summary.bytes_transferred,
+                    zero_copy_bytes: Some(state.summary.bytes_zero_copy),
+                    entries_deleted: state.summary.entries_deleted,
+                    elapsed: state.elapsed,
+                    ..TransferStats::default()
+                },
+                args.human_readable,
+            );
+        }
     }
 }
 
@@ -121,15 +145,13 @@ async fn run_remote_to_remote_direct_inner(
     lifecycle_trace: &TransferLifecycleTrace,
 ) -> Result<DeferredDelegatedState> {
     let filter_spec = super::build_filter_spec(args)?;
-    let options = delegated_pull_options(args, filter_spec, mirror_mode, move_verb);
+    let mut options = delegated_pull_options(args, filter_spec, mirror_mode, move_verb);
+    options.rename = super::build_rename_rules(args)?;
+    options.dial_request = super::build_dial_request(args)?;
+    options.block_size = super::build_block_size(args)? as u32;
 
-    let show_progress = args.effective_progress() || args.verbose;
-    let (progress_handle, progress_task) = spawn_progress_monitor_with_options(
-        show_progress,
-        args.verbose,
-        args.json,
-        defer_output, // R53-F1: suppress final progress line on move
-    );
+    // R53-F1: suppress the final progress line on move.
+    let (progress_handle, progress_task) = spawn_transfer_progress(args, defer_output);
 
     let dst_label = format_remote_endpoint(&dst);
     let execution = DelegatedPullExecution {
@@ -195,6 +217,7 @@ async fn run_remote_to_remote_direct_inner(
             summary,
             src: dst_for_state.clone(), // source endpoint not surfaced on Started
             dst: dst_for_state,
+            elapsed: Duration::ZERO,
         };
         super::render_result(lifecycle_trace, || {
             if args.json {
@@ -278,12 +301,13 @@ fn describe_delegated_result(
     summary: &DelegatedPullSummary,
     src: &RemoteEndpoint,
     dst: &RemoteEndpoint,
+    human_readable: bool,
 ) {
     println!(
-        "Delegated remote-to-remote transfer complete: {} file(s), {} bytes (zero-copy {} bytes){} from {} to {}.",
+        "Delegated remote-to-remote transfer complete: {} file(s), {} (zero-copy {}){} from {} to {}.",
         summary.files_transferred,
-        summary.bytes_transferred,
-        summary.bytes_zero_copy,
+        render_bytes(summary.bytes_transferred, human_readable),
+        render_bytes(summary.bytes_zero_copy, human_readable),
         if summary.tcp_fallback_used {
             " [gRPC fallback]"
         } else {
@@ -324,6 +348,9 @@ mod delegated_options_tests {
             size_only,
             ignore_times: false,
             ignore_existing: false,
+            overwrite: None,
+            existing: false,
+            metadata_only: false,
             force: false,
             verbose: false,
             progress: false,
@@ -333,19 +360,71 @@ mod delegated_options_tests {
             force_grpc: false,
             detach: false,
             resume: false,
+            skip_locked: false,
+            vss: false,
+            stats: false,
+            info: Vec::new(),
+            human_readable: false,
+            list_dest: false,
+            no_hidden: false,
+            exclude_attributes: None,
+            relative: false,
+            prune_empty_dirs: false,
+            crtimes: false,
+            super_user: false,
+            fake_super: false,
+            manifest_checkpoint: None,
+            checksum_cache: false,
+            remove_source_files: false,
+            snapshot: false,
+            delay_updates: false,
+            temp_dir: None,
             drop_windows_metadata: false,
             retry: 0,
             wait: 5,
+            reconnect: None,
+            max_files: None,
+            checksum_seed: None,
+            chmod: Vec::new(),
+            chown: None,
+            xattrs: false,
+            xattrs_all: false,
+            xattrs_include: Vec::new(),
+            xattrs_exclude: Vec::new(),
+            remote_option: Vec::new(),
+            quiet: false,
+            numeric_progress: false,
+            warn_files: 0,
             null: false,
             json: false,
             exclude: vec![],
             include: vec![],
+            exclude_from: Vec::new(),
+            include_from: Vec::new(),
             files_from: None,
             min_size: None,
             max_size: None,
             min_age: None,
             max_age: None,
+            rename: Vec::new(),
+            unpack: false,
+            copy_links: false,
+            copy_dirlinks: false,
+            copy_unsafe_links: false,
+            safe_links: false,
+            sparse: false,
+            no_sparse: false,
+            preallocate: false,
+            dedupe: false,
+            reflink_dest: None,
+            streams: None,
+            chunk_size: None,
+            block_size: None,
+            shard_max_bytes: None,
+            shard_max_files: None,
+            no_shard: false,
             delete_scope: "subset".into(),
+            sort_order: "none".into(),
         }
     }
 
@@ -0,0 +1,249 @@
+//! `--stats`: the rsync-style statistics block printed after a
+//! transfer's one-line summary. Every route fills a [`TransferStats`]
+//! from whatever its summary actually scores — a total the route
+//! cannot know (the source tree size of a pull, zero-copy bytes on the
+//! session carriers) is `None` and its line is left out rather than
+//! printed as a misleading zero.
+
+use blit_app::display::{format_bps, format_bytes};
+use blit_app::transfers::remote::StreamBandwidth;
+use std::time::Duration;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct TransferStats {
+    /// Source files the scan considered (after filtering).
+    pub files_examined: Option<u64>,
+    pub files_transferred: u64,
+    /// Files continued block-wise by `--resume`.
+    pub files_resumed: u64,
+    /// Total size of the examined source files.
+    pub total_bytes: Option<u64>,
+    /// Bytes the destination actually wrote.
+    pub transferred_bytes: u64,
+    pub zero_copy_bytes: Option<u64>,
+    pub sparse_bytes: u64,
+    pub entries_deleted: u64,
+    pub elapsed: Duration,
+}
+
+/// Render a byte count: binary units under `--human-readable`, the
+/// exact count otherwise (what scripts diffing two runs want).
+pub(crate) fn render_bytes(bytes: u64, human_readable: bool) -> String {
+    if human_readable {
+        format_bytes(bytes)
+    } else {
+        format!("{bytes} bytes")
+    }
+}
+
+/// Render a byte rate, with the same unit choice as [`render_bytes`].
+fn render_rate(rate: u64, human_readable: bool) -> String {
+    if human_readable {
+        format_bps(rate)
+    } else {
+        format!("{rate} bytes/s")
+    }
+}
+
+/// The statistics block, one `label: value` line each. Throughput is
+/// transferred bytes over wall time; the speedup (rsync's "total size
+/// / bytes sent") is only meaningful when the source size is known
+/// and something was written.
+pub(crate) fn render_stats(stats: &TransferStats, human_readable: bool) -> String {
+    let mut lines: Vec<(&str, String)> = Vec::new();
+    if let Some(examined) = stats.files_examined {
+        lines.push(("Files examined", examined.to_string()));
+    }
+    lines.push(("Files transferred", stats.files_transferred.to_string()));
+    if stats.files_resumed > 0 {
+        lines.push(("Files resumed", stats.files_resumed.to_string()));
+    }
+    if stats.entries_deleted > 0 {
+        lines.push(("Entries deleted", stats.entries_deleted.to_string()));
+    }
+    if let Some(total) = stats.total_bytes {
+        lines.push(("Total file size", render_bytes(total, human_readable)));
+    }
+    lines.push((
+        "Transferred size",
+        render_bytes(stats.transferred_bytes, human_readable),
+    ));
+    if let Some(total) = stats.total_bytes {
+        lines.push((
+            "Unchanged size",
+            render_bytes(
+                total.saturating_sub(stats.transferred_bytes),
+                human_readable,
+            ),
+        ));
+    }
+    if let Some(zero_copy) = stats.zero_copy_bytes {
+        lines.push(("Zero-copy size", render_bytes(zero_copy, human_readable)));
+    }
+    if stats.sparse_bytes > 0 {
+        lines.push((
+            "Sparse holes",
+            render_bytes(stats.sparse_bytes, human_readable),
+        ));
+    }
+    lines.push(("Elapsed", format!("{:.2?}", stats.elapsed)));
+    let seconds = stats.elapsed.as_secs_f64();
+    let rate = if seconds > 0.0 {
+        (stats.transferred_bytes as f64 / seconds) as u64
+    } else {
+        0
+    };
+    lines.push(("Throughput", render_rate(rate, human_readable)));
+    if let Some(total) = stats.total_bytes {
+        if stats.transferred_bytes > 0 {
+            lines.push((
+                "Speedup",
+                format!("{:.2}", total as f64 / stats.transferred_bytes as f64),
+            ));
+        }
+    }
+
+    let width = lines
+        .iter()
+        .map(|(label, _)| label.len())
+        .max()
+        .unwrap_or(0)
+        + 1;
+    let mut out = String::from("Transfer statistics:\n");
+    for (label, value) in lines {
+        out.push_str(&format!("  {:<width$} {value}\n", format!("{label}:")));
+    }
+    out
+}
+
+pub(crate) fn print_stats(stats: &TransferStats, human_readable: bool) {
+    print!("{}", render_stats(stats, human_readable));
+}
+
+/// The push's per-stream block: what each TCP data-plane stream
+/// carried, then the aggregate. Empty when no data plane ran (the
+/// in-stream carrier), so nothing is printed.
+pub(crate) fn render_stream_bandwidth(bandwidth: &StreamBandwidth, human_readable: bool) -> String {
+    if bandwidth.streams.is_empty() {
+        return String::new();
+    }
+    let line = |bytes: u64| {
+        format!(
+            "{} ({})",
+            render_bytes(bytes, human_readable),
+            render_rate(bandwidth.bytes_per_sec(bytes), human_readable)
+        )
+    };
+    let mut lines: Vec<(String, String)> = bandwidth
+        .streams
+        .iter()
+        .map(|stream| (format!("Stream {}:", stream.stream.0), line(stream.bytes)))
+        .collect();
+    lines.push(("Aggregate:".into(), line(bandwidth.total_bytes())));
+    let width = lines
+        .iter()
+        .map(|(label, _)| label.len())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::from("Data-plane streams:\n");
+    for (label, value) in lines {
+        out.push_str(&format!("  {label:<width$} {value}\n"));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn known_transfer() -> TransferStats {
+        TransferStats {
+            files_examined: Some(10),
+            files_transferred: 4,
+            total_bytes: Some(8 * 1024 * 1024),
+            transferred_bytes: 2 * 1024 * 1024,
+            elapsed: Duration::from_secs(2),
+            ..TransferStats::default()
+        }
+    }
+
+    #[test]
+    fn stats_block_reports_totals_rate_and_speedup() {
+        let block = render_stats(&known_transfer(), false);
+        assert!(block.starts_with("Transfer statistics:\n"), "{block}");
+        assert!(block.contains("Files examined:    10\n"), "{block}");
+        assert!(block.contains("Files transferred: 4\n"), "{block}");
+        assert!(
+            block.contains("Total file size:   8388608 bytes\n"),
+            "{block}"
+        );
+        assert!(
+            block.contains("Transferred size:  2097152 bytes\n"),
+            "{block}"
+        );
+        assert!(
+            block.contains("Unchanged size:    6291456 bytes\n"),
+            "{block}"
+        );
+        assert!(
+            block.contains("Throughput:        1048576 bytes/s\n"),
+            "{block}"
+        );
+        assert!(block.contains("Speedup:           4.00\n"), "{block}");
+        // Unknown totals are left out, not printed as zero.
+        assert!(!block.contains("Zero-copy"), "{block}");
+        assert!(!block.contains("Files resumed"), "{block}");
+    }
+
+    #[test]
+    fn human_readable_uses_binary_units() {
+        let block = render_stats(&known_transfer(), true);
+        assert!(block.contains("Total file size:   8.00 MiB\n"), "{block}");
+        assert!(block.contains("Transferred size:  2.00 MiB\n"), "{block}");
+        assert!(block.contains("Throughput:        1.00 MiB/s\n"), "{block}");
+    }
+
+    #[test]
+    fn unknown_source_size_drops_unchanged_and_speedup() {
+        let stats = TransferStats {
+            files_transferred: 3,
+            transferred_bytes: 300,
+            zero_copy_bytes: Some(0),
+            elapsed: Duration::ZERO,
+            ..TransferStats::default()
+        };
+        let block = render_stats(&stats, false);
+        assert!(!block.contains("Files examined"), "{block}");
+        assert!(!block.contains("Unchanged size"), "{block}");
+        assert!(!block.contains("Speedup"), "{block}");
+        assert!(block.contains("Zero-copy size:    0 bytes\n"), "{block}");
+        assert!(block.contains("Throughput:        0 bytes/s\n"), "{block}");
+    }
+
+    #[test]
+    fn stream_block_lists_each_stream_then_the_aggregate() {
+        use blit_core::remote::transfer::{StreamBytes, StreamId};
+        let bandwidth = StreamBandwidth {
+            streams: vec![
+                StreamBytes {
+                    stream: StreamId(0),
+                    bytes: 3 * 1024 * 1024,
+                },
+                StreamBytes {
+                    stream: StreamId(1),
+                    bytes: 1024 * 1024,
+                },
+            ],
+            elapsed: Duration::from_secs(2),
+        };
+        let block = render_stream_bandwidth(&bandwidth, true);
+        assert_eq!(
+            block,
+            "Data-plane streams:\n\
+             \x20 Stream 0:  3.00 MiB (1.50 MiB/s)\n\
+             \x20 Stream 1:  1.00 MiB (512.00 KiB/s)\n\
+             \x20 Aggregate: 4.00 MiB (2.00 MiB/s)\n"
+        );
+        assert!(render_stream_bandwidth(&StreamBandwidth::default(), false).is_empty());
+    }
+}
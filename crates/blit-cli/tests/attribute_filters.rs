@@ -0,0 +1,82 @@
+//! `--no-hidden` / `--exclude-attributes`: Windows file attributes
+//! filter the local scan, and are accepted but inert elsewhere.
+
+use std::fs;
+#[cfg(windows)]
+use std::path::Path;
+#[cfg(windows)]
+use std::process::Command;
+
+mod common;
+use common::copy;
+
+#[cfg(windows)]
+fn set_attributes(path: &Path, flags: &[&str]) {
+    let status = Command::new("attrib")
+        .args(flags)
+        .arg(path)
+        .status()
+        .expect("run attrib");
+    assert!(status.success(), "attrib {flags:?} {}", path.display());
+}
+
+#[cfg(windows)]
+#[test]
+fn no_hidden_skips_hidden_files_and_directories() {
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(src.join("secret")).unwrap();
+    fs::write(src.join("visible.txt"), b"visible").unwrap();
+    fs::write(src.join("hidden.txt"), b"hidden").unwrap();
+    fs::write(src.join("system.txt"), b"system").unwrap();
+    fs::write(src.join("secret/inside.txt"), b"inside").unwrap();
+    set_attributes(&src.join("hidden.txt"), &["+h"]);
+    set_attributes(&src.join("system.txt"), &["+s"]);
+    set_attributes(&src.join("secret"), &["+h"]);
+
+    copy(&["--no-hidden"], &src, &dst);
+    assert!(dst.join("visible.txt").exists());
+    assert!(dst.join("system.txt").exists());
+    assert!(!dst.join("hidden.txt").exists());
+    assert!(
+        !dst.join("secret").exists(),
+        "a hidden directory is skipped with its subtree"
+    );
+
+    // Composes with a glob exclude.
+    let dst2 = tmp.path().join("dst2");
+    copy(
+        &["--exclude-attributes", "system", "--exclude", "visible.txt"],
+        &src,
+        &dst2,
+    );
+    assert!(dst2.join("hidden.txt").exists());
+    assert!(!dst2.join("system.txt").exists());
+    assert!(!dst2.join("visible.txt").exists());
+}
+
+#[cfg(not(windows))]
+#[test]
+fn attribute_flags_are_accepted_but_inert_off_windows() {
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join(".dotfile"), b"dot").unwrap();
+    fs::write(src.join("plain.txt"), b"plain").unwrap();
+
+    let output = copy(
+        &["--no-hidden", "--exclude-attributes", "hidden,system"],
+        &src,
+        &dst,
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("have no effect on this platform"),
+        "{stderr}"
+    );
+    // A Unix dotfile has no hidden attribute: nothing is skipped.
+    assert!(dst.join(".dotfile").exists());
+    assert!(dst.join("plain.txt").exists());
+}
@@ -131,6 +131,33 @@ fn test_utils_list_modules_json() {
 
 // ── ls ────────────────────────────────────────────────────────────────
 
+#[test]
+fn ls_against_a_closed_port_fails_promptly_naming_the_port() {
+    let port = common::pick_unused_port();
+    let config = tempfile::tempdir().expect("config dir");
+    let mut cmd = Command::new(common::cli_bin());
+    cmd.arg("--config-dir")
+        .arg(config.path())
+        .arg("--contimeout")
+        .arg("5s")
+        .arg("ls")
+        .arg(format!("127.0.0.1:{port}:/test/"));
+
+    let started = std::time::Instant::now();
+    let output = run_with_timeout(cmd, Duration::from_secs(20));
+    assert!(!output.status.success(), "ls of a closed port must fail");
+    assert!(
+        started.elapsed() < Duration::from_secs(10),
+        "closed port took {:?} to fail",
+        started.elapsed()
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!("connection refused by 127.0.0.1:{port}")),
+        "expected a refused error naming the port:\n{stderr}"
+    );
+}
+
 #[test]
 fn test_utils_ls_remote() {
     let ctx = TestContext::new();
@@ -205,6 +232,118 @@ fn test_utils_ls_json() {
     );
 }
 
+/// Names of the entry rows (everything after the `Listing ...:` line),
+/// in printed order.
+fn ls_rows(stdout: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .skip(1)
+        .map(|line| line.rsplit(' ').next().unwrap().to_string())
+        .collect()
+}
+
+fn run_ls(ctx: &TestContext, args: &[&str]) -> String {
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.arg("ls").args(args);
+    let output = run_with_timeout(cmd, Duration::from_secs(10));
+    assert!(
+        output.status.success(),
+        "blit ls {args:?} failed:\nstderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[cfg(unix)]
+#[test]
+fn test_utils_ls_long_shows_mode_size_and_mtime() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let ctx = TestContext::new();
+    let mtime = 1_700_000_000;
+    let file = ctx.module_dir.join("report.txt");
+    fs::write(&file, "12345").expect("write");
+    fs::set_permissions(&file, fs::Permissions::from_mode(0o640)).unwrap();
+    filetime::set_file_mtime(&file, filetime::FileTime::from_unix_time(mtime, 0)).unwrap();
+    let dir = ctx.module_dir.join("logs");
+    fs::create_dir(&dir).expect("mkdir");
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o750)).unwrap();
+    let when = chrono::DateTime::from_timestamp(mtime, 0)
+        .unwrap()
+        .with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string();
+
+    // The daemon sends the mode over `List`; a local listing reads it.
+    let remote = format!("127.0.0.1:{}:/test/", ctx.daemon_port);
+    let module = ctx.module_dir.display().to_string();
+    for target in [remote.as_str(), module.as_str()] {
+        let stdout = run_ls(&ctx, &["-l", target]);
+        let rows: Vec<&str> = stdout.lines().skip(1).collect();
+        let file_row = rows
+            .iter()
+            .find(|row| row.ends_with(" report.txt"))
+            .unwrap_or_else(|| panic!("no report.txt row:\n{stdout}"));
+        let columns: Vec<&str> = file_row.split_whitespace().collect();
+        assert_eq!(
+            columns,
+            ["-rw-r-----", "5", &when[..10], &when[11..], "report.txt"],
+            "{target}"
+        );
+        let dir_row = rows
+            .iter()
+            .find(|row| row.ends_with(" logs/"))
+            .unwrap_or_else(|| panic!("no logs/ row:\n{stdout}"));
+        assert!(dir_row.starts_with("drwxr-x--- "), "{dir_row}");
+    }
+
+    let stdout = run_ls(&ctx, &["-l", "--human-readable", &remote]);
+    assert!(stdout.contains(" 5 B "), "{stdout}");
+}
+
+#[test]
+fn test_utils_ls_sort_and_dotfiles() {
+    let ctx = TestContext::new();
+    // name order: alpha, beta, gamma; size order: beta, gamma, alpha;
+    // time order: gamma, alpha, beta.
+    for (name, size, mtime) in [
+        ("alpha", 1, 1_700_000_200),
+        ("beta", 300, 1_700_000_100),
+        ("gamma", 20, 1_700_000_300),
+        (".hidden", 5000, 1_700_000_900),
+    ] {
+        let path = ctx.module_dir.join(name);
+        fs::write(&path, vec![b'x'; size]).expect("write");
+        filetime::set_file_mtime(&path, filetime::FileTime::from_unix_time(mtime, 0)).unwrap();
+    }
+    let remote = format!("127.0.0.1:{}:/test/", ctx.daemon_port);
+    let module = ctx.module_dir.display().to_string();
+
+    for target in [remote.as_str(), module.as_str()] {
+        assert_eq!(
+            ls_rows(&run_ls(&ctx, &[target])),
+            ["alpha", "beta", "gamma"],
+            "dotfiles hidden by default ({target})"
+        );
+        assert_eq!(
+            ls_rows(&run_ls(&ctx, &["-a", target])),
+            [".hidden", "alpha", "beta", "gamma"]
+        );
+        assert_eq!(
+            ls_rows(&run_ls(&ctx, &["--sort=size", target])),
+            ["beta", "gamma", "alpha"]
+        );
+        assert_eq!(
+            ls_rows(&run_ls(&ctx, &["-l", "--sort", "time", target])),
+            ["gamma", "alpha", "beta"]
+        );
+        assert_eq!(
+            ls_rows(&run_ls(&ctx, &["-a", "--sort=time", target])),
+            [".hidden", "gamma", "alpha", "beta"]
+        );
+    }
+}
+
 // ── find ──────────────────────────────────────────────────────────────
 
 #[test]
@@ -314,6 +453,187 @@ fn test_utils_find_limit() {
     );
 }
 
+fn write_find_delete_fixture(root: &Path) {
+    fs::create_dir_all(root.join("logs/old")).expect("mkdir");
+    fs::write(root.join("app.log"), "a").expect("write");
+    fs::write(root.join("logs/web.log"), "w").expect("write");
+    fs::write(root.join("logs/old/web.log"), "o").expect("write");
+    fs::write(root.join("logs/keep.txt"), "k").expect("write");
+    fs::write(root.join("readme.md"), "r").expect("write");
+}
+
+#[test]
+fn test_utils_find_delete_removes_exactly_the_matches() {
+    let ctx = TestContext::new();
+    write_find_delete_fixture(&ctx.module_dir);
+
+    let remote = format!("127.0.0.1:{}:/test/", ctx.daemon_port);
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.arg("find")
+        .arg(&remote)
+        .arg("--pattern")
+        .arg("*.log")
+        .arg("--delete")
+        .arg("--yes");
+
+    let output = run_with_timeout(cmd, Duration::from_secs(10));
+    assert!(
+        output.status.success(),
+        "blit find --delete failed:\nstderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    for gone in ["app.log", "logs/web.log", "logs/old/web.log"] {
+        assert!(
+            !ctx.module_dir.join(gone).exists(),
+            "{gone} should be deleted"
+        );
+    }
+    for kept in ["logs/keep.txt", "readme.md", "logs/old"] {
+        assert!(ctx.module_dir.join(kept).exists(), "{kept} should survive");
+    }
+}
+
+#[test]
+fn test_utils_find_delete_dirs_removes_matched_subtrees() {
+    let ctx = TestContext::new();
+    write_find_delete_fixture(&ctx.module_dir);
+
+    let remote = format!("127.0.0.1:{}:/test/", ctx.daemon_port);
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.arg("find")
+        .arg(&remote)
+        .arg("--pattern")
+        .arg("old")
+        .arg("--dirs")
+        .arg("--delete")
+        .arg("--yes");
+
+    let output = run_with_timeout(cmd, Duration::from_secs(10));
+    assert!(output.status.success());
+
+    assert!(!ctx.module_dir.join("logs/old").exists());
+    assert!(ctx.module_dir.join("logs/web.log").exists());
+}
+
+#[test]
+fn test_utils_find_delete_dry_run_lists_and_keeps() {
+    let ctx = TestContext::new();
+    write_find_delete_fixture(&ctx.module_dir);
+
+    let remote = format!("127.0.0.1:{}:/test/", ctx.daemon_port);
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.arg("find")
+        .arg(&remote)
+        .arg("--pattern")
+        .arg("*.log")
+        .arg("--delete")
+        .arg("--dry-run")
+        .arg("--json");
+
+    let output = run_with_timeout(cmd, Duration::from_secs(10));
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["dry_run"], true);
+    assert_eq!(json["entries_deleted"], 0);
+    assert_eq!(
+        json["matched"],
+        serde_json::json!(["app.log", "logs/old/web.log", "logs/web.log"])
+    );
+    assert!(ctx.module_dir.join("app.log").exists());
+    assert!(ctx.module_dir.join("logs/old/web.log").exists());
+}
+
+/// Lines after the header line, as printed (indentation kept).
+fn tree_body(stdout: &str) -> Vec<&str> {
+    stdout.lines().skip(1).collect()
+}
+
+fn write_tree_fixture(root: &Path) {
+    fs::create_dir_all(root.join("site/css/vendor")).expect("mkdir");
+    fs::write(root.join("site/index.html"), "<p>").expect("write");
+    fs::write(root.join("site/css/site.css"), "p{}").expect("write");
+    fs::write(root.join("site/css/vendor/reset.css"), "*{}").expect("write");
+    fs::write(root.join("site/.env"), "k=v").expect("write");
+    fs::write(root.join("notes.md"), "#").expect("write");
+}
+
+#[test]
+fn test_utils_find_and_ls_tree() {
+    let ctx = TestContext::new();
+    write_tree_fixture(&ctx.module_dir);
+    let remote = format!("127.0.0.1:{}:/test/", ctx.daemon_port);
+
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.arg("find").arg(&remote).arg("--tree");
+    let output = run_with_timeout(cmd, Duration::from_secs(10));
+    assert!(
+        output.status.success(),
+        "blit find --tree failed:\nstderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        tree_body(&stdout),
+        [
+            "  notes.md",
+            "  site/",
+            "    .env",
+            "    css/",
+            "      site.css",
+            "      vendor/",
+            "        reset.css",
+            "    index.html",
+        ],
+        "{stdout}"
+    );
+
+    // A pattern keeps the matches' parent directories; --max-depth
+    // bounds the levels below the start path.
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.arg("find").arg(format!("{remote}site")).args([
+        "--tree",
+        "--pattern",
+        "*.css",
+        "--max-depth",
+        "2",
+    ]);
+    let output = run_with_timeout(cmd, Duration::from_secs(10));
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        tree_body(&stdout),
+        ["  css/", "    site.css", "    vendor/"],
+        "{stdout}"
+    );
+
+    // `ls --tree` renders the same shape, remote and local, with
+    // dotfiles hidden unless -a.
+    let module = ctx.module_dir.join("site").display().to_string();
+    for target in [format!("{remote}site"), module] {
+        assert_eq!(
+            tree_body(&run_ls(&ctx, &["--tree", &target])),
+            [
+                "  css/",
+                "    site.css",
+                "    vendor/",
+                "      reset.css",
+                "  index.html",
+            ],
+            "{target}"
+        );
+        assert_eq!(
+            tree_body(&run_ls(
+                &ctx,
+                &["--tree", "-a", "--max-depth", "1", &target]
+            )),
+            ["  .env", "  css/", "  index.html"],
+            "{target}"
+        );
+    }
+}
+
 // ── du ────────────────────────────────────────────────────────────────
 
 #[test]
@@ -416,6 +736,16 @@ fn test_utils_df_json() {
     );
     assert!(parsed["free_bytes"].is_u64(), "expected free_bytes as u64");
     assert!(parsed["module"].is_string(), "expected module as string");
+    let mount = parsed["mount_point"]
+        .as_str()
+        .expect("expected mount_point as string");
+    let module_root = fs::canonicalize(&ctx.module_dir).expect("canonical module dir");
+    assert!(
+        module_root.starts_with(mount),
+        "module {} is not under reported mount {mount}",
+        module_root.display()
+    );
+    assert!(parsed["filesystem"].is_string(), "expected filesystem type");
 }
 
 // ── rm ────────────────────────────────────────────────────────────────
@@ -0,0 +1,106 @@
+//! `--checksum-cache`: digests computed by a checksum transfer are kept
+//! under the config dir keyed by (path, size, mtime) and reused until the
+//! key changes.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use filetime::{set_file_mtime, FileTime};
+use serde_json::Value;
+use tempfile::tempdir;
+
+mod common;
+use common::{cli_bin, run_with_timeout};
+
+const MTIME: i64 = 1_700_000_000;
+
+fn copy_with_cache(config_dir: &Path, src: &Path, dst: &Path) {
+    let mut cmd = Command::new(cli_bin());
+    cmd.arg("--config-dir")
+        .arg(config_dir)
+        .arg("copy")
+        .arg("--yes")
+        .arg("--checksum")
+        .arg("--checksum-cache")
+        .arg(format!("{}/", src.display()))
+        .arg(format!("{}/", dst.display()));
+    let output = run_with_timeout(cmd, Duration::from_secs(60));
+    assert!(
+        output.status.success(),
+        "blit copy failed\nstdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Cached entries, keyed by absolute path.
+fn cache_entries(config_dir: &Path) -> serde_json::Map<String, Value> {
+    let text = fs::read_to_string(config_dir.join("checksum_cache.json")).expect("cache file");
+    let state: Value = serde_json::from_str(&text).expect("cache json");
+    state["entries"].as_object().expect("entries").clone()
+}
+
+/// The entry for `path`. Keys are absolute but not canonical, so match
+/// through `canonicalize` (macOS temp dirs sit behind a symlink).
+fn entry<'a>(entries: &'a serde_json::Map<String, Value>, path: &Path) -> &'a Value {
+    let want = path.canonicalize().unwrap();
+    entries
+        .iter()
+        .find(|(key, _)| Path::new(key).canonicalize().ok() == Some(want.clone()))
+        .map(|(_, value)| value)
+        .unwrap_or_else(|| panic!("no cache entry for {}: {entries:?}", want.display()))
+}
+
+fn write_settled(path: &Path, body: &[u8], mtime: i64) {
+    fs::write(path, body).unwrap();
+    set_file_mtime(path, FileTime::from_unix_time(mtime, 0)).unwrap();
+}
+
+#[test]
+fn cache_is_populated_reused_and_invalidated() {
+    let tmp = tempdir().unwrap();
+    let config_dir = tmp.path().join("config");
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(&src).unwrap();
+    write_settled(&src.join("a.txt"), b"alpha", MTIME);
+    write_settled(&src.join("b.txt"), b"bravo", MTIME);
+
+    // First run: the source side hashes every file; the destination
+    // has nothing to hash yet.
+    copy_with_cache(&config_dir, &src, &dst);
+    let entries = cache_entries(&config_dir);
+    let a_src = entry(&entries, &src.join("a.txt")).clone();
+    assert_eq!(a_src["size"], 5);
+    assert_eq!(a_src["mtime_seconds"], MTIME);
+    assert_eq!(a_src["blake3"].as_str().map(str::len), Some(64));
+    let b_digest = entry(&entries, &src.join("b.txt"))["blake3"].clone();
+
+    // Second run: the copied destination files (mtime preserved) are
+    // hashed and cached too.
+    copy_with_cache(&config_dir, &src, &dst);
+    let entries = cache_entries(&config_dir);
+    entry(&entries, &dst.join("a.txt"));
+    entry(&entries, &dst.join("b.txt"));
+
+    // Reuse: rewrite a destination file in place with the same size and
+    // restore its mtime. Only a hash would notice — and the cache
+    // answers for it instead, so the file is left alone.
+    write_settled(&dst.join("a.txt"), b"ALPHA", MTIME);
+    copy_with_cache(&config_dir, &src, &dst);
+    assert_eq!(fs::read(dst.join("a.txt")).unwrap(), b"ALPHA");
+
+    // Invalidation: a new mtime (same size) drops the stale entry, the
+    // file is rehashed, found different, and recopied.
+    write_settled(&dst.join("a.txt"), b"ALPHA", MTIME + 60);
+    write_settled(&src.join("b.txt"), b"BRAVO", MTIME + 60);
+    copy_with_cache(&config_dir, &src, &dst);
+    assert_eq!(fs::read(dst.join("a.txt")).unwrap(), b"alpha");
+    assert_eq!(fs::read(dst.join("b.txt")).unwrap(), b"BRAVO");
+    let entries = cache_entries(&config_dir);
+    let b_src = entry(&entries, &src.join("b.txt"));
+    assert_eq!(b_src["mtime_seconds"], MTIME + 60);
+    assert_ne!(b_src["blake3"], b_digest, "stale digest kept");
+}
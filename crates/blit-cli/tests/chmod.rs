@@ -0,0 +1,158 @@
+//! `--chmod`: destination modes set from the spec rather than the
+//! source — an octal override and a symbolic `g+r`, each on files and
+//! directories, locally, through a push and through a pull (where the
+//! daemon is the SOURCE rewriting the manifest).
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+mod common;
+use common::{assert_success, blit, write_fixture, TestContext};
+
+/// (spec, directory mode, file mode) over a source of 0700 directories
+/// and 0600 files.
+const CASES: [(&str, u32, u32); 2] = [("D750,F604", 0o750, 0o604), ("g+r", 0o740, 0o640)];
+
+fn write_private_fixture(src: &Path) {
+    write_fixture(src);
+    for file in ["top.txt", "sub/nested.txt"] {
+        fs::set_permissions(src.join(file), fs::Permissions::from_mode(0o600)).unwrap();
+    }
+    fs::set_permissions(src.join("sub"), fs::Permissions::from_mode(0o700)).unwrap();
+}
+
+fn mode(path: &Path) -> u32 {
+    fs::metadata(path).unwrap().permissions().mode() & 0o7777
+}
+
+fn assert_modes(dst: &Path, dir_mode: u32, file_mode: u32, spec: &str) {
+    assert_eq!(mode(&dst.join("sub")), dir_mode, "--chmod {spec}: sub/");
+    for file in ["top.txt", "sub/nested.txt"] {
+        assert_eq!(mode(&dst.join(file)), file_mode, "--chmod {spec}: {file}");
+    }
+    assert_eq!(fs::read(dst.join("sub/nested.txt")).unwrap(), b"nested");
+}
+
+#[test]
+fn local_copy_applies_chmod_to_files_and_directories() {
+    let ctx = TestContext::new();
+    for (i, (spec, dir_mode, file_mode)) in CASES.into_iter().enumerate() {
+        let src = ctx.workspace.join(format!("src{i}"));
+        let dst = ctx.workspace.join(format!("dst{i}"));
+        write_private_fixture(&src);
+
+        assert_success(&blit(
+            &ctx,
+            &[
+                "copy",
+                "--yes",
+                "--chmod",
+                spec,
+                &format!("{}/", src.display()),
+                &format!("{}/", dst.display()),
+            ],
+        ));
+
+        assert_modes(&dst, dir_mode, file_mode, spec);
+        // The source keeps its own modes.
+        assert_eq!(mode(&src.join("top.txt")), 0o600);
+    }
+}
+
+#[test]
+fn push_applies_chmod_on_the_daemon() {
+    let ctx = TestContext::new();
+    for (i, (spec, dir_mode, file_mode)) in CASES.into_iter().enumerate() {
+        let src = ctx.workspace.join(format!("src{i}"));
+        write_private_fixture(&src);
+
+        assert_success(&blit(
+            &ctx,
+            &[
+                "copy",
+                "--yes",
+                "--chmod",
+                spec,
+                &format!("{}/", src.display()),
+                &format!("127.0.0.1:{}:/test/push{i}/", ctx.daemon_port),
+            ],
+        ));
+
+        assert_modes(
+            &ctx.module_dir.join(format!("push{i}")),
+            dir_mode,
+            file_mode,
+            spec,
+        );
+    }
+}
+
+#[test]
+fn pull_applies_chmod_from_the_daemon_source() {
+    let ctx = TestContext::new();
+    for (i, (spec, dir_mode, file_mode)) in CASES.into_iter().enumerate() {
+        write_private_fixture(&ctx.module_dir.join(format!("pull{i}")));
+        let dst = ctx.workspace.join(format!("pulled{i}"));
+
+        assert_success(&blit(
+            &ctx,
+            &[
+                "copy",
+                "--yes",
+                "--chmod",
+                spec,
+                &format!("127.0.0.1:{}:/test/pull{i}/", ctx.daemon_port),
+                &format!("{}/", dst.display()),
+            ],
+        ));
+
+        assert_modes(&dst, dir_mode, file_mode, spec);
+    }
+}
+
+/// A rerun moves no bytes for unchanged files but still brings their
+/// modes (and the directory's) in line with the spec.
+#[test]
+fn chmod_converges_an_unchanged_destination() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    let dst = ctx.workspace.join("dst");
+    write_private_fixture(&src);
+    let (src_arg, dst_arg) = (format!("{}/", src.display()), format!("{}/", dst.display()));
+
+    assert_success(&blit(&ctx, &["copy", "--yes", &src_arg, &dst_arg]));
+    assert_eq!(mode(&dst.join("top.txt")), 0o600);
+
+    assert_success(&blit(
+        &ctx,
+        &["copy", "--yes", "--chmod", "g+r", &src_arg, &dst_arg],
+    ));
+    assert_modes(&dst, 0o740, 0o640, "g+r");
+}
+
+#[test]
+fn malformed_chmod_spec_is_rejected_before_copying() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    let dst = ctx.workspace.join("dst");
+    write_private_fixture(&src);
+
+    let output = blit(
+        &ctx,
+        &[
+            "copy",
+            "--yes",
+            "--chmod",
+            "g+z",
+            &format!("{}/", src.display()),
+            &format!("{}/", dst.display()),
+        ],
+    );
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid --chmod"), "stderr:\n{stderr}");
+    assert!(!dst.exists());
+}
@@ -0,0 +1,114 @@
+//! `--chown USER:GROUP`: destination ownership set from the override
+//! rather than left to whoever ran the copy — locally and through a push,
+//! where the daemon destination applies it. Changing ownership needs root,
+//! so the override assertions only run privileged; an unknown name is
+//! refused either way.
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+mod common;
+use common::{assert_success, blit, write_fixture, TestContext};
+
+const UID: u32 = 4321;
+const GID: u32 = 8765;
+
+/// Whether this process can chown: judged by who owns a file it just
+/// created, so the test needs no libc of its own.
+fn running_as_root(ctx: &TestContext) -> bool {
+    let probe = ctx.workspace.join("uid-probe");
+    fs::write(&probe, b"").unwrap();
+    let root = fs::metadata(&probe).unwrap().uid() == 0;
+    if !root {
+        eprintln!("skipping: --chown needs root to change ownership");
+    }
+    root
+}
+
+fn assert_owned(dst: &Path) {
+    for entry in ["sub", "top.txt", "sub/nested.txt"] {
+        let meta = fs::symlink_metadata(dst.join(entry)).unwrap();
+        assert_eq!((meta.uid(), meta.gid()), (UID, GID), "{entry}");
+    }
+    assert_eq!(fs::read(dst.join("sub/nested.txt")).unwrap(), b"nested");
+}
+
+#[test]
+fn local_copy_applies_chown_to_files_and_directories() {
+    let ctx = TestContext::new();
+    if !running_as_root(&ctx) {
+        return;
+    }
+    let src = ctx.workspace.join("src");
+    let dst = ctx.workspace.join("dst");
+    write_fixture(&src);
+
+    let owner = format!("{UID}:{GID}");
+    assert_success(&blit(
+        &ctx,
+        &[
+            "copy",
+            "--yes",
+            "--chown",
+            &owner,
+            &format!("{}/", src.display()),
+            &format!("{}/", dst.display()),
+        ],
+    ));
+
+    assert_owned(&dst);
+    // The source keeps its own owner.
+    assert_ne!(fs::metadata(src.join("top.txt")).unwrap().uid(), UID);
+}
+
+#[test]
+fn push_applies_chown_on_the_daemon() {
+    let ctx = TestContext::new();
+    if !running_as_root(&ctx) {
+        return;
+    }
+    let src = ctx.workspace.join("src");
+    write_fixture(&src);
+
+    let owner = format!("{UID}:{GID}");
+    assert_success(&blit(
+        &ctx,
+        &[
+            "copy",
+            "--yes",
+            "--chown",
+            &owner,
+            &format!("{}/", src.display()),
+            &format!("127.0.0.1:{}:/test/pushed/", ctx.daemon_port),
+        ],
+    ));
+
+    assert_owned(&ctx.module_dir.join("pushed"));
+}
+
+#[test]
+fn unknown_chown_user_is_rejected_before_copying() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    let dst = ctx.workspace.join("dst");
+    write_fixture(&src);
+
+    let output = blit(
+        &ctx,
+        &[
+            "copy",
+            "--yes",
+            "--chown",
+            "no-such-blit-user:root",
+            &format!("{}/", src.display()),
+            &format!("{}/", dst.display()),
+        ],
+    );
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no-such-blit-user"), "stderr:\n{stderr}");
+    assert!(!dst.join("top.txt").exists());
+}
@@ -0,0 +1,133 @@
+//! `blit clean`: removes staging entries crashed transfers left behind
+//! once they are older than `--older-than`, and nothing else — in a
+//! local tree and in a daemon module.
+
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use filetime::FileTime;
+use serde_json::Value;
+
+mod common;
+use common::{assert_success, blit, write_fixture, TestContext};
+
+fn age(path: &Path, by: Duration) {
+    let mtime = FileTime::from_system_time(SystemTime::now() - by);
+    filetime::set_file_mtime(path, mtime).unwrap();
+}
+
+/// A tree holding real files, stale staging leftovers (two days old) and
+/// fresh ones (a live transfer's). Returns what a clean must remove.
+fn write_stale_fixture(root: &Path) -> serde_json::Value {
+    let day = Duration::from_secs(2 * 24 * 3600);
+    write_fixture(root);
+    // An old real file is never a candidate.
+    age(&root.join("sub/nested.txt"), day);
+
+    fs::write(root.join(".top.txt.blit-verify"), b"half").unwrap();
+    age(&root.join(".top.txt.blit-verify"), day);
+    fs::write(root.join("sub/.nested.txt.blit-dedupe"), b"half").unwrap();
+    age(&root.join("sub/.nested.txt.blit-dedupe"), day);
+    let stage = root.join(".blit-delay-updates");
+    fs::create_dir_all(stage.join("sub")).unwrap();
+    fs::write(stage.join("sub/staged.txt"), b"staged").unwrap();
+    age(&stage.join("sub/staged.txt"), day);
+    age(&stage.join("sub"), day);
+    age(&stage, day);
+
+    // Recent: a transfer may still be writing these.
+    fs::write(root.join("sub/.fresh.bin.blit-verify"), b"live").unwrap();
+    let busy = root.join("busy/.blit-delay-updates");
+    fs::create_dir_all(&busy).unwrap();
+    fs::write(busy.join("live.bin"), b"live").unwrap();
+    age(&busy, day);
+
+    serde_json::json!([
+        ".blit-delay-updates",
+        ".top.txt.blit-verify",
+        "sub/.nested.txt.blit-dedupe"
+    ])
+}
+
+fn assert_cleaned(root: &Path) {
+    assert!(!root.join(".top.txt.blit-verify").exists());
+    assert!(!root.join("sub/.nested.txt.blit-dedupe").exists());
+    assert!(!root.join(".blit-delay-updates").exists());
+    assert_eq!(fs::read(root.join("top.txt")).unwrap(), b"top");
+    assert_eq!(fs::read(root.join("sub/nested.txt")).unwrap(), b"nested");
+    assert!(root.join("sub/.fresh.bin.blit-verify").exists());
+    assert!(root.join("busy/.blit-delay-updates/live.bin").exists());
+}
+
+fn clean(ctx: &TestContext, target: &str, extra: &[&str]) -> Value {
+    let args: Vec<&str> = ["clean"]
+        .into_iter()
+        .chain(extra.iter().copied())
+        .chain(["--json", target])
+        .collect();
+    let output = blit(ctx, &args);
+    assert_success(&output);
+    serde_json::from_slice(&output.stdout).expect("clean json")
+}
+
+#[test]
+fn clean_removes_stale_staging_from_a_local_tree() {
+    let ctx = TestContext::new();
+    let root = ctx.workspace.join("dest");
+    let expected = write_stale_fixture(&root);
+    let target = root.display().to_string();
+
+    let planned = clean(&ctx, &target, &["--dry-run"]);
+    assert_eq!(planned["stale"], expected);
+    assert_eq!(planned["entries_removed"], 0);
+    assert!(
+        root.join(".top.txt.blit-verify").exists(),
+        "dry run removed"
+    );
+
+    let cleaned = clean(&ctx, &target, &["--yes"]);
+    assert_eq!(cleaned["stale"], expected);
+    assert_eq!(cleaned["entries_removed"], 3);
+    assert_cleaned(&root);
+
+    // Once they are older than the threshold, the fresh ones go too.
+    let hours = Duration::from_secs(2 * 3600);
+    age(&root.join("sub/.fresh.bin.blit-verify"), hours);
+    age(&root.join("busy/.blit-delay-updates/live.bin"), hours);
+    let all = clean(&ctx, &target, &["--yes", "--older-than", "1h"]);
+    assert_eq!(
+        all["stale"],
+        serde_json::json!(["busy/.blit-delay-updates", "sub/.fresh.bin.blit-verify"])
+    );
+    assert!(!root.join("busy/.blit-delay-updates").exists());
+}
+
+#[test]
+fn clean_removes_stale_staging_from_a_daemon_module() {
+    let ctx = TestContext::new();
+    let root = ctx.module_dir.join("dest");
+    let expected = write_stale_fixture(&root);
+    // Outside the cleaned directory: never considered.
+    fs::write(ctx.module_dir.join(".outside.txt.blit-verify"), b"x").unwrap();
+    age(
+        &ctx.module_dir.join(".outside.txt.blit-verify"),
+        Duration::from_secs(2 * 24 * 3600),
+    );
+    let target = format!("127.0.0.1:{}:/test/dest/", ctx.daemon_port);
+
+    let planned = clean(&ctx, &target, &["--dry-run"]);
+    assert_eq!(planned["stale"], expected);
+    assert!(
+        root.join(".top.txt.blit-verify").exists(),
+        "dry run removed"
+    );
+
+    let cleaned = clean(&ctx, &target, &["--yes"]);
+    assert_eq!(cleaned["stale"], expected);
+    assert_cleaned(&root);
+    assert!(ctx.module_dir.join(".outside.txt.blit-verify").exists());
+
+    let again = clean(&ctx, &target, &["--yes"]);
+    assert_eq!(again["stale"], serde_json::json!([]));
+}
@@ -10,6 +10,24 @@
 //! Covers:
 //!   - R54-F1: `--null` gated to local copy only
 //!   - R54-F2: `--force` / `--ignore-times` rejected on move
+//!   - `--sparse` / `--no-sparse` gated to local copy only
+//!   - `--metadata-only` rejected on mirror
+//!   - `--delay-updates` / `--skip-locked` gated to local transfers only
+//!   - `move --vss` / `move --snapshot` rejected (source delete would hit post-snapshot edits)
+//!   - `--shard-max-bytes` / `--shard-max-files` / `--no-shard` / `--sort-order` gated to a local source
+//!   - `--list-dest` gated to a local source and remote destination
+//!   - `--no-hidden` / `--exclude-attributes` gated to a local source
+//!   - `--crtimes` rejected for remote-to-remote transfers
+//!   - `--prune-empty-dirs` rejected for remote-to-remote transfers
+//!   - `--chmod` rejected for remote-to-remote transfers
+//!   - `--chown` rejected for remote-to-remote transfers
+//!   - `--xattrs` rejected for remote-to-remote transfers
+//!   - `--super` / `--fake-super` rejected for remote-to-remote transfers
+//!   - `--checksum-cache` rejected for remote-to-remote transfers and
+//!     without a checksum compare
+//!   - `--remove-source-files` gated to a push, and rejected on move
+//!   - `--manifest-checkpoint` gated to a push
+//!   - `--block-size` outside 4Ki..64Mi rejected
 
 use std::fs;
 use std::process::Command;
@@ -144,6 +162,84 @@ fn local_copy_null_still_accepted() {
     assert!(!dst.join("file.txt").exists());
 }
 
+/// `--sparse` only steers the local copy engine; a push writes through
+/// the daemon's receive stream, which would silently ignore it.
+#[test]
+fn copy_rejects_sparse_with_remote_destination() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("file.txt"), b"payload").unwrap();
+
+    assert_rejected(
+        &[
+            "copy",
+            "--sparse",
+            &format!("{}/", src.display()),
+            "127.0.0.1:12349:/mod/",
+        ],
+        "--sparse / --no-sparse only apply to a local copy",
+    );
+}
+
+/// `--preallocate` reserves space through the local copy engine only.
+#[test]
+fn copy_rejects_preallocate_with_remote_destination() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("file.txt"), b"payload").unwrap();
+
+    assert_rejected(
+        &[
+            "copy",
+            "--preallocate",
+            &format!("{}/", src.display()),
+            "127.0.0.1:12349:/mod/",
+        ],
+        "--preallocate only applies to a local copy",
+    );
+}
+
+/// `--dedupe` links files on a local destination only.
+#[test]
+fn copy_rejects_dedupe_with_remote_destination() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("file.txt"), b"payload").unwrap();
+
+    assert_rejected(
+        &[
+            "copy",
+            "--dedupe",
+            &format!("{}/", src.display()),
+            "127.0.0.1:12349:/mod/",
+        ],
+        "--dedupe only applies to a local copy",
+    );
+}
+
+/// `--reflink-dest` clones from a local reference tree only.
+#[test]
+fn copy_rejects_reflink_dest_with_remote_destination() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("file.txt"), b"payload").unwrap();
+
+    assert_rejected(
+        &[
+            "copy",
+            "--reflink-dest",
+            &tmp.path().display().to_string(),
+            &format!("{}/", src.display()),
+            "127.0.0.1:12349:/mod/",
+        ],
+        "--reflink-dest only applies to a local copy",
+    );
+}
+
 // ── R54-F2: --force / --ignore-times rejected on move ──────────────
 
 #[test]
@@ -299,3 +395,395 @@ fn windows_metadata_downgrade_warns_and_copies_primary_bytes() {
     );
     assert_eq!(fs::read(dst).unwrap(), b"primary bytes");
 }
+
+/// `--metadata-only` never deletes; pairing it with mirror would
+/// silently drop the purge half of the request.
+#[test]
+fn mirror_rejects_metadata_only() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(&src).unwrap();
+    fs::create_dir_all(&dst).unwrap();
+    fs::write(src.join("file.txt"), b"payload").unwrap();
+    fs::write(dst.join("stale.txt"), b"stale").unwrap();
+
+    assert_rejected(
+        &[
+            "mirror",
+            "--metadata-only",
+            &format!("{}/", src.display()),
+            &format!("{}/", dst.display()),
+        ],
+        "--metadata-only cannot be combined with mirror",
+    );
+    assert!(dst.join("stale.txt").exists());
+}
+
+/// `--delay-updates` stages inside the local apply; a push writes
+/// through the daemon's receive stream, which would silently ignore it.
+#[test]
+fn copy_rejects_delay_updates_with_remote_destination() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("file.txt"), b"payload").unwrap();
+
+    assert_rejected(
+        &[
+            "copy",
+            "--delay-updates",
+            &format!("{}/", src.display()),
+            "127.0.0.1:12349:/mod/",
+        ],
+        "--delay-updates only applies to a local transfer",
+    );
+}
+
+/// `--skip-locked` acts at the local apply's availability check; a push
+/// has no such check and would fail on the locked file anyway.
+#[test]
+fn copy_rejects_skip_locked_with_remote_destination() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("file.txt"), b"payload").unwrap();
+
+    assert_rejected(
+        &[
+            "copy",
+            "--skip-locked",
+            &format!("{}/", src.display()),
+            "127.0.0.1:12349:/mod/",
+        ],
+        "--skip-locked only applies to a local transfer",
+    );
+}
+
+/// `move --vss` would copy the frozen view but delete the live tree,
+/// losing any edit made after the snapshot.
+#[test]
+fn move_rejects_vss() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("file.txt"), b"payload").unwrap();
+
+    assert_rejected(
+        &[
+            "move",
+            "--vss",
+            &src.display().to_string(),
+            &dst.display().to_string(),
+        ],
+        "move does not support --vss",
+    );
+    assert!(src.join("file.txt").exists(), "source must be untouched");
+}
+
+/// `move --snapshot`: same hazard as `--vss`, on Unix snapshots.
+#[test]
+fn move_rejects_snapshot() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("file.txt"), b"payload").unwrap();
+
+    assert_rejected(
+        &[
+            "move",
+            "--snapshot",
+            &src.display().to_string(),
+            &dst.display().to_string(),
+        ],
+        "move does not support --vss / --snapshot",
+    );
+    assert!(src.join("file.txt").exists(), "source must be untouched");
+}
+
+/// A remote SOURCE plans its own tar shards; the pull open carries no
+/// shard ceilings, so the flags would be silently ignored.
+#[test]
+fn copy_rejects_no_shard_with_remote_source() {
+    let tmp = tempdir().expect("tempdir");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(&dst).unwrap();
+
+    assert_rejected(
+        &[
+            "copy",
+            "--no-shard",
+            "127.0.0.1:12349:/mod/",
+            &format!("{}/", dst.display()),
+        ],
+        "only apply to a local source",
+    );
+}
+
+/// Nor does it carry `--sort-order`: the daemon scans in its own order.
+#[test]
+fn copy_rejects_sort_order_with_remote_source() {
+    let tmp = tempdir().expect("tempdir");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(&dst).unwrap();
+
+    assert_rejected(
+        &[
+            "copy",
+            "--sort-order",
+            "name",
+            "127.0.0.1:12349:/mod/",
+            &format!("{}/", dst.display()),
+        ],
+        "only apply to a local source",
+    );
+}
+
+/// `--list-dest` lists the destination through the daemon's `find`
+/// RPC; between two local paths there is no daemon to ask, and a
+/// local copy must not silently run instead of the preview.
+#[test]
+fn copy_rejects_list_dest_with_local_destination() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("file.txt"), b"payload").unwrap();
+
+    assert_rejected(
+        &[
+            "copy",
+            "--list-dest",
+            &format!("{}/", src.display()),
+            &format!("{}/", dst.display()),
+        ],
+        "--list-dest compares a local source against a remote destination",
+    );
+    assert!(!dst.exists(), "the rejected preview must not copy anything");
+}
+
+/// Attribute filters act in this host's scan; a daemon source would
+/// enumerate without them and send the hidden files anyway.
+#[test]
+fn copy_rejects_no_hidden_with_remote_source() {
+    let tmp = tempdir().expect("tempdir");
+    let dst = tmp.path().join("dst");
+
+    assert_rejected(
+        &[
+            "copy",
+            "--no-hidden",
+            "127.0.0.1:12349:/mod/",
+            &format!("{}/", dst.display()),
+        ],
+        "--no-hidden / --exclude-attributes only apply to a local source",
+    );
+}
+
+/// The delegated remote→remote spec has no field for `--crtimes`: the
+/// pulling daemon would drop creation times without a word.
+#[test]
+fn copy_rejects_crtimes_for_remote_to_remote() {
+    assert_rejected(
+        &[
+            "copy",
+            "--crtimes",
+            "127.0.0.1:12349:/mod/",
+            "127.0.0.1:12350:/mod/",
+        ],
+        "--crtimes is not supported for remote-to-remote transfers",
+    );
+}
+
+/// Nor for `--chmod`: the pulling daemon would keep the source's modes.
+#[test]
+fn copy_rejects_chmod_for_remote_to_remote() {
+    assert_rejected(
+        &[
+            "copy",
+            "--chmod",
+            "g+r",
+            "127.0.0.1:12349:/mod/",
+            "127.0.0.1:12350:/mod/",
+        ],
+        "--chmod is not supported for remote-to-remote transfers",
+    );
+}
+
+/// Nor for `--chown`: the pulling daemon would keep its own owners.
+#[test]
+fn copy_rejects_chown_for_remote_to_remote() {
+    assert_rejected(
+        &[
+            "copy",
+            "--chown",
+            "0:0",
+            "127.0.0.1:12349:/mod/",
+            "127.0.0.1:12350:/mod/",
+        ],
+        "--chown is not supported for remote-to-remote transfers",
+    );
+}
+
+/// Nor for `--xattrs` (or the flags that imply it): the delegated spec
+/// has no field for the namespace filter.
+#[test]
+fn copy_rejects_xattrs_for_remote_to_remote() {
+    assert_rejected(
+        &[
+            "copy",
+            "--xattrs-include",
+            "trusted",
+            "127.0.0.1:12349:/mod/",
+            "127.0.0.1:12350:/mod/",
+        ],
+        "--xattrs is not supported for remote-to-remote transfers",
+    );
+}
+
+/// Nor for `--super` / `--fake-super`: the pulling daemon would
+/// leave every file owned by itself.
+#[test]
+fn copy_rejects_super_for_remote_to_remote() {
+    assert_rejected(
+        &[
+            "copy",
+            "--fake-super",
+            "127.0.0.1:12349:/mod/",
+            "127.0.0.1:12350:/mod/",
+        ],
+        "--super / --fake-super are not supported for remote-to-remote transfers",
+    );
+}
+
+/// Nor has it a field for `--prune-empty-dirs`: the source daemon
+/// would send every directory regardless.
+#[test]
+fn copy_rejects_prune_empty_dirs_for_remote_to_remote() {
+    assert_rejected(
+        &[
+            "copy",
+            "--prune-empty-dirs",
+            "127.0.0.1:12349:/mod/",
+            "127.0.0.1:12350:/mod/",
+        ],
+        "--prune-empty-dirs is not supported for remote-to-remote transfers",
+    );
+}
+
+/// Both ends of a delegated remote→remote transfer hash on their
+/// daemons; a local `--checksum-cache` would never be consulted.
+#[test]
+fn copy_rejects_checksum_cache_for_remote_to_remote() {
+    assert_rejected(
+        &[
+            "copy",
+            "--checksum",
+            "--checksum-cache",
+            "127.0.0.1:12349:/mod/",
+            "127.0.0.1:12350:/mod/",
+        ],
+        "--checksum-cache is not supported for remote-to-remote transfers",
+    );
+}
+
+/// Without a checksum compare nothing is hashed, so the cache would
+/// silently do nothing.
+#[test]
+fn copy_rejects_checksum_cache_without_checksum() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    assert_rejected(
+        &[
+            "copy",
+            "--checksum-cache",
+            &format!("{}/", src.display()),
+            &format!("{}/", tmp.path().join("dst").display()),
+        ],
+        "--checksum-cache needs --checksum",
+    );
+}
+
+/// A local copy has no per-file record of what was sent; removing the
+/// source there is `blit move`'s job.
+#[test]
+fn copy_rejects_remove_source_files_outside_a_push() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("keep.txt"), b"keep").unwrap();
+    assert_rejected(
+        &[
+            "copy",
+            "--remove-source-files",
+            &format!("{}/", src.display()),
+            &format!("{}/", tmp.path().join("dst").display()),
+        ],
+        "--remove-source-files only applies to a push",
+    );
+    assert!(src.join("keep.txt").exists(), "source must be untouched");
+}
+
+#[test]
+fn move_rejects_remove_source_files() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("keep.txt"), b"keep").unwrap();
+    assert_rejected(
+        &[
+            "move",
+            "--yes",
+            "--remove-source-files",
+            &format!("{}/", src.display()),
+            &format!("{}/", tmp.path().join("dst").display()),
+        ],
+        "move does not support --remove-source-files",
+    );
+    assert!(src.join("keep.txt").exists(), "source must be untouched");
+}
+
+#[test]
+fn copy_rejects_manifest_checkpoint_outside_a_push() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("keep.txt"), b"keep").unwrap();
+    let checkpoint = tmp.path().join("walk.ckpt");
+    assert_rejected(
+        &[
+            "copy",
+            "--manifest-checkpoint",
+            checkpoint.to_str().unwrap(),
+            &format!("{}/", src.display()),
+            &format!("{}/", tmp.path().join("dst").display()),
+        ],
+        "--manifest-checkpoint only applies to a push",
+    );
+    assert!(!checkpoint.exists(), "nothing may be written");
+    assert!(!tmp.path().join("dst").exists());
+}
+
+#[test]
+fn copy_rejects_out_of_range_block_size() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("keep.txt"), b"keep").unwrap();
+    for size in ["1Ki", "128Mi"] {
+        assert_rejected(
+            &[
+                "copy",
+                "--block-size",
+                size,
+                &format!("{}/", src.display()),
+                &format!("{}/", tmp.path().join("dst").display()),
+            ],
+            "invalid --block-size",
+        );
+    }
+    assert!(!tmp.path().join("dst").exists());
+}
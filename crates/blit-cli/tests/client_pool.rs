@@ -0,0 +1,63 @@
+//! `connect_cached`: successive admin operations against one daemon in
+//! one process share a single control-plane channel.
+
+use std::fs;
+
+use blit_app::admin::{find, ls};
+use blit_app::client::shared_pool;
+use blit_core::remote::endpoint::RemoteEndpoint;
+
+mod common;
+use common::TestContext;
+
+#[test]
+fn successive_operations_reuse_one_channel() {
+    let ctx = TestContext::new();
+    fs::create_dir_all(ctx.module_dir.join("docs")).unwrap();
+    fs::write(ctx.module_dir.join("docs/readme.txt"), b"hello").unwrap();
+    let remote = RemoteEndpoint::parse(&format!("127.0.0.1:{}:/test/", ctx.daemon_port)).unwrap();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let before = shared_pool().channels_opened();
+
+        let first = ls::list_remote(&remote, "test".into(), String::new())
+            .await
+            .expect("first ls");
+        assert_eq!(first.len(), 1);
+        let again = ls::list_remote(&remote, "test".into(), "docs".into())
+            .await
+            .expect("second ls");
+        assert_eq!(again[0].name, "readme.txt");
+        let mut found = Vec::new();
+        find::stream(
+            &remote,
+            find::FindParams {
+                module: "test".into(),
+                start_path: String::new(),
+                pattern: "*.txt".into(),
+                case_sensitive: true,
+                include_files: true,
+                include_directories: false,
+                max_results: 0,
+                exclude: Vec::new(),
+            },
+            |entry| {
+                found.push(entry.path);
+                Ok(())
+            },
+        )
+        .await
+        .expect("find");
+        assert_eq!(found, ["docs/readme.txt"]);
+
+        assert_eq!(
+            shared_pool().channels_opened() - before,
+            1,
+            "three operations, one channel"
+        );
+    });
+}
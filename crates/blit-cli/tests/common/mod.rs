@@ -3,10 +3,11 @@
 //! The single daemon-spawn implementation for every blit-cli test
 //! binary: config serialization, port picking, the once-per-binary
 //! `cargo build`, spawn + readiness poll, plus the shared `cli_bin()`
-//! / `run_with_timeout` helpers and the production-shaped fake-server
-//! scaffold. The per-file clones of this logic (remote_remote,
-//! remote_pull_mirror, remote_checksum_negotiation,
-//! remote_tcp_fallback, and the newer jobs_lifecycle /
+//! / `run_with_timeout` / `blit` / `assert_success` / `copy` /
+//! `write_fixture` helpers and the production-shaped fake-server scaffold. The
+//! per-file clones of this logic (remote_remote, remote_pull_mirror,
+//! remote_checksum_negotiation, remote_tcp_fallback, and the newer
+//! jobs_lifecycle /
 //! readonly_enforcement mini-harnesses) were deleted in its favor —
 //! extend this file instead of re-cloning it
 //! (tests-five-daemon-harness-clones).
@@ -17,7 +18,7 @@
 // setting for a shared harness, not a mask for genuinely dead code.
 #![allow(dead_code)]
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::io::Read;
 use std::net::{TcpListener, TcpStream};
@@ -27,7 +28,7 @@ use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
 use std::time::Duration;
 
-use blit_core::generated::{blit_client::BlitClient, ListModulesRequest};
+use blit_core::generated::{blit_client::BlitClient, ListModulesRequest, RenameRule};
 use serde::Serialize;
 use tempfile::tempdir;
 use tonic::transport::Endpoint;
@@ -599,24 +600,146 @@ impl TestContext {
 pub fn run_with_timeout(mut cmd: Command, timeout: Duration) -> std::process::Output {
     cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
     let mut child = cmd.spawn().expect("spawn command");
-
-    match child.wait_timeout(timeout).expect("wait for process") {
-        Some(_status) => child
-            .wait_with_output()
-            .expect("collect command output after completion"),
-        None => {
-            let _ = child.kill();
-            let output = child
-                .wait_with_output()
-                .expect("collect output after killing command");
-            panic!(
-                "command timed out after {:?}\nstdout:\n{}\nstderr:\n{}",
-                timeout,
-                String::from_utf8_lossy(&output.stdout),
-                String::from_utf8_lossy(&output.stderr)
-            );
+    // Drain both pipes while waiting: a child whose output outgrows the
+    // pipe buffer (e.g. a full completion script) would otherwise block
+    // on write and read as a timeout.
+    let drain = |pipe: Option<Box<dyn Read + Send>>| {
+        thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
+    };
+    let stdout = drain(
+        child
+            .stdout
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+    );
+    let stderr = drain(
+        child
+            .stderr
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+    );
+
+    let status = child.wait_timeout(timeout).expect("wait for process");
+    let timed_out = status.is_none();
+    if timed_out {
+        let _ = child.kill();
+    }
+    let status = match status {
+        Some(status) => status,
+        None => child.wait().expect("reap killed command"),
+    };
+    let output = std::process::Output {
+        status,
+        stdout: stdout.join().expect("stdout drain thread"),
+        stderr: stderr.join().expect("stderr drain thread"),
+    };
+    if timed_out {
+        panic!(
+            "command timed out after {:?}\nstdout:\n{}\nstderr:\n{}",
+            timeout,
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    output
+}
+
+/// Run the CLI with `args` against `ctx`'s config dir.
+pub fn blit(ctx: &TestContext, args: &[&str]) -> std::process::Output {
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.arg("--config-dir").arg(&ctx.config_dir).args(args);
+    run_with_timeout(cmd, Duration::from_secs(60))
+}
+
+/// Fail with both streams unless `output` exited successfully; returns
+/// its stderr for the tests that inspect it.
+pub fn assert_success(output: &std::process::Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    assert!(
+        output.status.success(),
+        "blit failed\nstdout:\n{}\nstderr:\n{stderr}",
+        String::from_utf8_lossy(&output.stdout),
+    );
+    stderr
+}
+
+/// Run a daemon-free `blit copy --yes <args> src/ dst/` and fail with
+/// both streams unless it succeeds.
+pub fn copy(args: &[&str], src: &Path, dst: &Path) -> std::process::Output {
+    let mut cmd = Command::new(cli_bin());
+    cmd.arg("copy")
+        .arg("--yes")
+        .args(args)
+        .arg(format!("{}/", src.display()))
+        .arg(format!("{}/", dst.display()));
+    let output = run_with_timeout(cmd, Duration::from_secs(30));
+    assert_success(&output);
+    output
+}
+
+/// The small tree most CLI tests start from: `top.txt` and
+/// `sub/nested.txt` under `src`. Tests needing more write it on top.
+pub fn write_fixture(src: &Path) {
+    fs::create_dir_all(src.join("sub")).unwrap();
+    fs::write(src.join("top.txt"), b"top").unwrap();
+    fs::write(src.join("sub/nested.txt"), b"nested").unwrap();
+}
+
+/// Relative path → content for every file under `root`.
+pub fn tree_contents(root: &Path) -> BTreeMap<String, Vec<u8>> {
+    let mut out = BTreeMap::new();
+    for entry in walkdir::WalkDir::new(root) {
+        let entry = entry.expect("walk tree");
+        if entry.file_type().is_file() {
+            let rel = entry
+                .path()
+                .strip_prefix(root)
+                .expect("relative path")
+                .to_string_lossy()
+                .replace('\\', "/");
+            out.insert(rel, fs::read(entry.path()).expect("read file"));
         }
     }
+    out
+}
+
+/// The `--rename` rules the push and pull session tests apply:
+/// `nested/` moves to `flat/` and `big.bin` becomes `huge.bin`.
+pub fn rename_rules() -> Vec<RenameRule> {
+    vec![
+        RenameRule {
+            pattern: "^nested/".into(),
+            replacement: "flat/".into(),
+        },
+        RenameRule {
+            pattern: r"^big\.bin$".into(),
+            replacement: "huge.bin".into(),
+        },
+    ]
+}
+
+/// `rename_rules` applied to the fixture: every file lands, byte-exact,
+/// under its rewritten path and nothing under the original names.
+pub fn assert_renamed_tree(src: &Path, dst: &Path) {
+    let expected: BTreeMap<String, Vec<u8>> = tree_contents(src)
+        .into_iter()
+        .map(|(rel, bytes)| {
+            let renamed = match rel.strip_prefix("nested/") {
+                Some(rest) => format!("flat/{rest}"),
+                None if rel == "big.bin" => "huge.bin".to_string(),
+                None => rel,
+            };
+            (renamed, bytes)
+        })
+        .collect();
+    assert_eq!(tree_contents(dst), expected);
+    assert!(!dst.join("nested").exists());
 }
 
 pub struct ChildGuard {
@@ -720,13 +843,12 @@ where
             .build()
             .expect("fake server runtime");
         runtime.block_on(async move {
-            use blit_core::generated::blit_server::BlitServer;
             use tokio_stream::wrappers::TcpListenerStream;
 
             let listener =
                 tokio::net::TcpListener::from_std(listener).expect("tokio fake listener");
             blit_core::remote::grpc_server::production_server_builder()
-                .add_service(BlitServer::new(svc))
+                .add_service(blit_core::remote::message_limits::blit_server(svc))
                 .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async {
                     let _ = shutdown_rx.await;
                 })
@@ -0,0 +1,187 @@
+//! `--crtimes`: creation times ride the manifest and are set on the
+//! destination where the platform allows (macOS, Windows). Elsewhere
+//! the flag is accepted and the copy proceeds without them.
+
+use std::fs;
+use std::path::Path;
+
+use filetime::{set_file_mtime, FileTime};
+
+mod common;
+use common::{assert_success, blit, write_fixture, TestContext};
+
+const MTIME: i64 = 1_700_000_000;
+
+fn write_dated_fixture(src: &Path) {
+    write_fixture(src);
+    for rel in ["top.txt", "sub/nested.txt"] {
+        set_file_mtime(src.join(rel), FileTime::from_unix_time(MTIME, 0)).unwrap();
+    }
+}
+
+fn assert_contents_and_mtime(dst: &Path) {
+    for (rel, bytes) in [("top.txt", &b"top"[..]), ("sub/nested.txt", b"nested")] {
+        let path = dst.join(rel);
+        assert_eq!(fs::read(&path).unwrap(), bytes);
+        let meta = fs::metadata(&path).unwrap();
+        assert_eq!(
+            FileTime::from_last_modification_time(&meta).unix_seconds(),
+            MTIME,
+            "{rel}: mtime"
+        );
+    }
+}
+
+#[cfg(any(target_os = "macos", windows))]
+mod settable {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use blit_core::fs_capability::{get_platform_capability, FilesystemCapability};
+
+    /// 2001-09-09 — long before any fixture could have been created.
+    const CRTIME: i64 = 1_000_000_000;
+
+    fn stamp_crtimes(src: &Path) {
+        let capability = get_platform_capability();
+        for rel in ["top.txt", "sub/nested.txt"] {
+            let created = UNIX_EPOCH + Duration::from_secs(CRTIME as u64);
+            assert!(capability
+                .set_creation_time(&src.join(rel), created)
+                .unwrap());
+        }
+    }
+
+    fn crtime(path: &Path) -> i64 {
+        fs::metadata(path)
+            .unwrap()
+            .created()
+            .unwrap()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64
+    }
+
+    fn assert_crtimes(dst: &Path) {
+        for rel in ["top.txt", "sub/nested.txt"] {
+            assert_eq!(crtime(&dst.join(rel)), CRTIME, "{rel}: creation time");
+        }
+    }
+
+    #[test]
+    fn local_copy_round_trips_creation_times() {
+        let ctx = TestContext::new();
+        let src = ctx.workspace.join("src");
+        write_dated_fixture(&src);
+        stamp_crtimes(&src);
+        let src_arg = format!("{}/", src.display());
+
+        let dst = ctx.workspace.join("dst");
+        assert_success(&blit(
+            &ctx,
+            &[
+                "copy",
+                "--yes",
+                "--crtimes",
+                &src_arg,
+                &format!("{}/", dst.display()),
+            ],
+        ));
+        assert_contents_and_mtime(&dst);
+        assert_crtimes(&dst);
+    }
+
+    #[test]
+    fn push_and_pull_round_trip_creation_times() {
+        let ctx = TestContext::new();
+        let src = ctx.workspace.join("src");
+        write_dated_fixture(&src);
+        stamp_crtimes(&src);
+        let remote = format!("127.0.0.1:{}:/test/", ctx.daemon_port);
+
+        assert_success(&blit(
+            &ctx,
+            &[
+                "copy",
+                "--yes",
+                "--crtimes",
+                &format!("{}/", src.display()),
+                &remote,
+            ],
+        ));
+        assert_contents_and_mtime(&ctx.module_dir);
+        assert_crtimes(&ctx.module_dir);
+
+        let pulled = ctx.workspace.join("pulled");
+        assert_success(&blit(
+            &ctx,
+            &[
+                "copy",
+                "--yes",
+                "--crtimes",
+                &remote,
+                &format!("{}/", pulled.display()),
+            ],
+        ));
+        assert_contents_and_mtime(&pulled);
+        assert_crtimes(&pulled);
+    }
+}
+
+/// Linux can read a birth time but has no call to set one: `--crtimes`
+/// still carries it on the wire, and every destination write skips it.
+#[cfg(all(unix, not(target_os = "macos")))]
+#[test]
+fn crtimes_is_a_no_op_where_creation_time_cannot_be_set() {
+    use blit_core::fs_capability::{get_platform_capability, FilesystemCapability};
+
+    let capability = get_platform_capability();
+    assert!(!capability.capabilities().creation_time);
+
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    write_dated_fixture(&src);
+    let now = std::time::SystemTime::now();
+    assert!(!capability
+        .set_creation_time(&src.join("top.txt"), now)
+        .unwrap());
+
+    let dst = ctx.workspace.join("dst");
+    assert_success(&blit(
+        &ctx,
+        &[
+            "copy",
+            "--yes",
+            "--crtimes",
+            &format!("{}/", src.display()),
+            &format!("{}/", dst.display()),
+        ],
+    ));
+    assert_contents_and_mtime(&dst);
+
+    let remote = format!("127.0.0.1:{}:/test/", ctx.daemon_port);
+    assert_success(&blit(
+        &ctx,
+        &[
+            "copy",
+            "--yes",
+            "--crtimes",
+            &format!("{}/", src.display()),
+            &remote,
+        ],
+    ));
+    assert_contents_and_mtime(&ctx.module_dir);
+
+    let pulled = ctx.workspace.join("pulled");
+    assert_success(&blit(
+        &ctx,
+        &[
+            "copy",
+            "--yes",
+            "--crtimes",
+            &remote,
+            &format!("{}/", pulled.display()),
+        ],
+    ));
+    assert_contents_and_mtime(&pulled);
+}
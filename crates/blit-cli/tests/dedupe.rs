@@ -0,0 +1,56 @@
+//! `--dedupe`: identical-content source files that were never linked
+//! at the source end up as one inode on the destination.
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::process::Command;
+use std::time::Duration;
+
+mod common;
+use common::{cli_bin, run_with_timeout};
+
+#[test]
+fn dedupe_links_identical_source_files_on_the_destination() {
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(src.join("nested")).unwrap();
+    let payload = vec![7u8; 64 * 1024];
+    fs::write(src.join("one.bin"), &payload).unwrap();
+    fs::write(src.join("nested/two.bin"), &payload).unwrap();
+    fs::write(src.join("other.bin"), vec![8u8; 64 * 1024]).unwrap();
+    assert_ne!(
+        fs::metadata(src.join("one.bin")).unwrap().ino(),
+        fs::metadata(src.join("nested/two.bin")).unwrap().ino()
+    );
+
+    let mut cmd = Command::new(cli_bin());
+    cmd.arg("copy")
+        .arg("--yes")
+        .arg("--dedupe")
+        .arg(format!("{}/", src.display()))
+        .arg(format!("{}/", dst.display()));
+    let output = run_with_timeout(cmd, Duration::from_secs(30));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "blit copy --dedupe failed\nstdout:\n{stdout}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let one = fs::metadata(dst.join("one.bin")).unwrap();
+    let two = fs::metadata(dst.join("nested/two.bin")).unwrap();
+    let other = fs::metadata(dst.join("other.bin")).unwrap();
+    assert_eq!(one.ino(), two.ino(), "identical files share one inode");
+    assert_eq!(one.nlink(), 2);
+    assert_ne!(one.ino(), other.ino());
+    assert_eq!(fs::read(dst.join("nested/two.bin")).unwrap(), payload);
+    assert!(stdout.contains("Deduplicated: 1 file(s)"), "{stdout}");
+    // The source is untouched.
+    assert_eq!(
+        fs::metadata(src.join("one.bin")).unwrap().nlink(),
+        1,
+        "the source files stay unlinked"
+    );
+}
@@ -0,0 +1,44 @@
+//! Smoke test for `blit diagnostics bench`: the verb runs the in-process
+//! loopback daemon end to end and its JSON carries the tuning snapshot
+//! plus one nonzero-throughput run per carrier.
+
+use std::process::Command;
+use std::time::Duration;
+
+mod common;
+use common::{cli_bin, run_with_timeout};
+
+#[test]
+fn bench_json_reports_both_carriers() {
+    let mut cmd = Command::new(cli_bin());
+    cmd.args([
+        "diagnostics",
+        "bench",
+        "--size",
+        "2Mi",
+        "--files",
+        "2",
+        "--small-files",
+        "8",
+        "--json",
+    ]);
+    let out = run_with_timeout(cmd, Duration::from_secs(60));
+    assert!(
+        out.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let v: serde_json::Value = serde_json::from_str(&stdout)
+        .unwrap_or_else(|e| panic!("invalid JSON: {e}\nstdout:\n{stdout}"));
+
+    assert!(v["tuning"]["chunk_bytes"].as_u64().unwrap_or(0) > 0);
+    assert!(v["tuning"]["initial_streams"].as_u64().unwrap_or(0) > 0);
+    let runs = v["runs"].as_array().expect("runs array");
+    let carriers: Vec<&str> = runs.iter().filter_map(|r| r["carrier"].as_str()).collect();
+    assert_eq!(carriers, ["data-plane", "grpc"]);
+    for run in runs {
+        assert_eq!(run["files"], 10, "{run}");
+        assert!(run["bytes_per_second"].as_u64().unwrap_or(0) > 0, "{run}");
+    }
+}
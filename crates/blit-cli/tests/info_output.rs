@@ -0,0 +1,87 @@
+//! `--info`, the per-category output switch: `--info=del` names each
+//! mirror deletion (`deleting PATH`) without the per-file transfer
+//! lines `name` adds — locally and through a pull, the two shapes
+//! where this end runs the delete pass.
+
+use std::fs;
+use std::path::Path;
+
+mod common;
+use common::{assert_success, blit, write_fixture, TestContext};
+
+/// Two files to send, and a destination holding one the source lacks.
+fn write_mirror_fixture(src: &Path, dst: &Path) {
+    write_fixture(src);
+    fs::create_dir_all(dst.join("gone")).unwrap();
+    fs::write(dst.join("gone/stale.txt"), b"stale").unwrap();
+}
+
+fn mirror(ctx: &TestContext, info: &str, src: &str, dst: &Path) -> Vec<String> {
+    let dst = format!("{}/", dst.display());
+    let output = blit(ctx, &["mirror", "--yes", "--info", info, src, &dst]);
+    assert_success(&output);
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+fn assert_deletions_only(stdout: &[String]) {
+    assert!(
+        stdout.iter().any(|line| line == "deleting gone/stale.txt"),
+        "no deletion line: {stdout:?}"
+    );
+    assert!(
+        stdout.iter().any(|line| line == "deleting gone"),
+        "no directory deletion line: {stdout:?}"
+    );
+    assert!(
+        !stdout
+            .iter()
+            .any(|line| line == "top.txt" || line == "sub/nested.txt"),
+        "per-file transfer lines under --info=del: {stdout:?}"
+    );
+}
+
+#[test]
+fn info_del_names_local_deletions_but_not_transfers() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    let dst = ctx.workspace.join("dst");
+    write_mirror_fixture(&src, &dst);
+
+    let stdout = mirror(&ctx, "del", &format!("{}/", src.display()), &dst);
+
+    assert_deletions_only(&stdout);
+    assert!(!dst.join("gone").exists());
+    assert_eq!(fs::read(dst.join("sub/nested.txt")).unwrap(), b"nested");
+
+    // `name` adds the transfer lines back; with nothing left to delete
+    // there are no deletion lines.
+    fs::write(src.join("later.txt"), b"later").unwrap();
+    let stdout = mirror(&ctx, "name,del", &format!("{}/", src.display()), &dst);
+    assert!(stdout.iter().any(|line| line == "later.txt"), "{stdout:?}");
+    assert!(
+        !stdout.iter().any(|line| line.starts_with("deleting ")),
+        "{stdout:?}"
+    );
+}
+
+#[test]
+fn info_del_names_pull_deletions_but_not_transfers() {
+    let ctx = TestContext::new();
+    let src = ctx.module_dir.join("src");
+    let dst = ctx.workspace.join("dst");
+    write_mirror_fixture(&src, &dst);
+
+    let stdout = mirror(
+        &ctx,
+        "del",
+        &format!("127.0.0.1:{}:/test/src/", ctx.daemon_port),
+        &dst,
+    );
+
+    assert_deletions_only(&stdout);
+    assert!(!dst.join("gone").exists());
+    assert_eq!(fs::read(dst.join("top.txt")).unwrap(), b"top");
+}
@@ -81,6 +81,29 @@ fn jobs_cancel_unknown_id_exits_one() {
     );
 }
 
+#[test]
+fn jobs_cancel_admin_token_is_refused_by_a_daemon_without_one() {
+    let ctx = TestContext::new();
+    let remote = format!("127.0.0.1:{}", ctx.daemon_port);
+    let token_file = ctx.workspace.join("admin-token");
+    fs::write(&token_file, "s3cret\n").unwrap();
+
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.arg("jobs")
+        .arg("cancel")
+        .arg(&remote)
+        .arg("no-such-transfer-id")
+        .arg("--admin-token-file")
+        .arg(&token_file);
+    let output = run_with_timeout(cmd, Duration::from_secs(30));
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !output.status.success() && stderr.contains("admin_token"),
+        "a token the daemon cannot check must be refused, not ignored\nstderr:\n{stderr}"
+    );
+}
+
 #[test]
 fn jobs_watch_unknown_id_exits_two() {
     let ctx = TestContext::new();
@@ -324,6 +347,9 @@ impl blit_core::generated::blit_server::Blit for StallingTransferBlit {
     type DiskUsageStream = tokio_stream::wrappers::ReceiverStream<
         Result<blit_core::generated::DiskUsageEntry, tonic::Status>,
     >;
+    type ChecksumStream = tokio_stream::wrappers::ReceiverStream<
+        Result<blit_core::generated::ChecksumEntry, tonic::Status>,
+    >;
     type DelegatedPullStream = tokio_stream::wrappers::ReceiverStream<
         Result<blit_core::generated::DelegatedPullProgress, tonic::Status>,
     >;
@@ -406,6 +432,13 @@ impl blit_core::generated::blit_server::Blit for StallingTransferBlit {
         Err(tonic::Status::unimplemented("stalling fake source"))
     }
 
+    async fn checksum(
+        &self,
+        _: tonic::Request<blit_core::generated::ChecksumRequest>,
+    ) -> Result<tonic::Response<Self::ChecksumStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("stalling fake source"))
+    }
+
     async fn delegated_pull(
         &self,
         _: tonic::Request<blit_core::generated::DelegatedPullRequest>,
@@ -413,6 +446,20 @@ impl blit_core::generated::blit_server::Blit for StallingTransferBlit {
         Err(tonic::Status::unimplemented("stalling fake source"))
     }
 
+    async fn capabilities(
+        &self,
+        _: tonic::Request<blit_core::generated::CapabilitiesRequest>,
+    ) -> Result<tonic::Response<blit_core::generated::CapabilitiesResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("stalling fake source"))
+    }
+
+    async fn health(
+        &self,
+        _: tonic::Request<blit_core::generated::HealthRequest>,
+    ) -> Result<tonic::Response<blit_core::generated::HealthResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("stalling fake source"))
+    }
+
     async fn get_state(
         &self,
         _: tonic::Request<blit_core::generated::GetStateRequest>,
@@ -348,6 +348,67 @@ fn local_move_rejects_ignore_existing() {
     );
 }
 
+/// `--overwrite never` is `--ignore-existing` under another name, so
+/// move refuses the whole option before any work.
+#[test]
+fn local_move_rejects_overwrite_policy() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(&src).unwrap();
+    fs::create_dir_all(&dst).unwrap();
+    fs::write(src.join("file.txt"), b"new content").unwrap();
+    fs::write(dst.join("file.txt"), b"stale dst content").unwrap();
+
+    let mut cmd = Command::new(cli_bin());
+    cmd.arg("move")
+        .arg("--yes")
+        .arg("--overwrite")
+        .arg("never")
+        .arg(format!("{}/", src.display()))
+        .arg(format!("{}/", dst.display()));
+    let output = run_with_timeout(cmd, Duration::from_secs(30));
+    assert!(!output.status.success(), "move --overwrite must fail");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("move does not support --overwrite"),
+        "got stderr: {stderr}"
+    );
+    assert!(src.join("file.txt").exists());
+}
+
+/// `blit move --existing` must refuse: every source file absent on
+/// the destination would be skipped and then deleted with the source.
+#[test]
+fn local_move_rejects_existing() {
+    let tmp = tempdir().expect("tempdir");
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(&src).unwrap();
+    fs::create_dir_all(&dst).unwrap();
+    fs::write(src.join("file.txt"), b"only copy").unwrap();
+
+    let mut cmd = Command::new(cli_bin());
+    cmd.arg("move")
+        .arg("--yes")
+        .arg("--existing")
+        .arg(format!("{}/", src.display()))
+        .arg(format!("{}/", dst.display()));
+    let output = run_with_timeout(cmd, Duration::from_secs(30));
+    assert!(
+        !output.status.success(),
+        "move with --existing must fail; stdout: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("move does not support --existing"),
+        "expected existing rejection, got stderr: {}",
+        stderr
+    );
+    assert!(src.join("file.txt").exists());
+}
+
 /// R52-F1 regression: `blit move --null` must refuse. --null
 /// routes the transfer into a sink that writes nothing, and
 /// move would then delete the source — net effect, source
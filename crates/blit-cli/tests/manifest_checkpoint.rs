@@ -0,0 +1,66 @@
+//! `--manifest-checkpoint`: a push that fails leaves its scan's
+//! directory listings behind, the re-run reuses them, and a successful
+//! push deletes the checkpoint.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::time::Duration;
+
+use filetime::{set_file_mtime, FileTime};
+
+mod common;
+use common::{run_with_timeout, TestContext};
+
+fn push(ctx: &TestContext, src: &Path, checkpoint: &Path) -> Output {
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.arg("--config-dir")
+        .arg(&ctx.config_dir)
+        .arg("copy")
+        .arg("--yes")
+        .arg("--manifest-checkpoint")
+        .arg(checkpoint)
+        .arg(format!("{}/", src.display()))
+        .arg(format!("127.0.0.1:{}:/test/", ctx.daemon_port));
+    run_with_timeout(cmd, Duration::from_secs(60))
+}
+
+#[test]
+fn failed_push_leaves_a_checkpoint_the_rerun_reuses_then_removes() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    for rel in ["a/one.txt", "a/b/two.txt", "c/three.txt"] {
+        let path = src.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, rel.as_bytes()).unwrap();
+    }
+    // Old enough to be recorded (outside the racy window).
+    for dir in ["", "a", "a/b", "c"] {
+        set_file_mtime(src.join(dir), FileTime::from_unix_time(1_700_000_000, 0)).unwrap();
+    }
+    let checkpoint = ctx.workspace.join("walk.ckpt");
+
+    // A file where the push needs directory `a` fails the transfer.
+    fs::write(ctx.module_dir.join("a"), b"in the way").unwrap();
+    let failed = push(&ctx, &src, &checkpoint);
+    assert!(
+        !failed.status.success(),
+        "push must fail\nstderr:\n{}",
+        String::from_utf8_lossy(&failed.stderr)
+    );
+    assert!(checkpoint.exists(), "a failed push keeps its checkpoint");
+
+    fs::remove_file(ctx.module_dir.join("a")).unwrap();
+    let rerun = push(&ctx, &src, &checkpoint);
+    let stderr = String::from_utf8_lossy(&rerun.stderr);
+    assert!(rerun.status.success(), "re-run failed\nstderr:\n{stderr}");
+    assert!(
+        stderr.contains("Manifest checkpoint:") && !stderr.contains(" 0 directories reused"),
+        "re-run must reuse recorded listings\nstderr:\n{stderr}"
+    );
+    assert!(!checkpoint.exists(), "a successful push removes it");
+    assert_eq!(
+        fs::read(ctx.module_dir.join("a/b/two.txt")).unwrap(),
+        b"a/b/two.txt"
+    );
+}
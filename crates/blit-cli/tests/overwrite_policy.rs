@@ -0,0 +1,101 @@
+//! `--overwrite never|if-newer|always`: each policy against a
+//! destination that already holds a same-size file with the same,
+//! an older, and a newer mtime — locally and through a push, where
+//! the daemon applies it while building the need list.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use filetime::FileTime;
+
+mod common;
+use common::{cli_bin, run_with_timeout, TestContext};
+
+const SOURCE: &[u8] = b"source-bytes";
+const DEST: &[u8] = b"dest---bytes";
+
+/// Seed `src` with `existing`, `older`, `newer` and `fresh`, and `dst`
+/// with same-size different-content twins of the first three whose
+/// mtimes equal, trail and lead the source by an hour.
+fn seed(src: &Path, dst: &Path) {
+    fs::create_dir_all(src).unwrap();
+    fs::create_dir_all(dst).unwrap();
+    let base = FileTime::from_unix_time(1_700_000_000, 0);
+    for (name, offset) in [("existing", 0i64), ("older", -3600), ("newer", 3600)] {
+        fs::write(src.join(name), SOURCE).unwrap();
+        filetime::set_file_mtime(src.join(name), base).unwrap();
+        fs::write(dst.join(name), DEST).unwrap();
+        let dest_mtime = FileTime::from_unix_time(base.unix_seconds() + offset, 0);
+        filetime::set_file_mtime(dst.join(name), dest_mtime).unwrap();
+    }
+    fs::write(src.join("fresh"), SOURCE).unwrap();
+}
+
+/// Which of `existing`, `older`, `newer` hold the source bytes.
+fn overwritten(dst: &Path) -> [bool; 3] {
+    ["existing", "older", "newer"].map(|name| fs::read(dst.join(name)).unwrap() == SOURCE)
+}
+
+fn run_copy(config_dir: Option<&Path>, policy: &str, src: &Path, dest: &str) {
+    let mut cmd = Command::new(cli_bin());
+    if let Some(dir) = config_dir {
+        cmd.arg("--config-dir").arg(dir);
+    }
+    cmd.arg("copy")
+        .arg("--yes")
+        .arg("--overwrite")
+        .arg(policy)
+        .arg(format!("{}/", src.display()))
+        .arg(dest);
+    let output = run_with_timeout(cmd, Duration::from_secs(60));
+    assert!(
+        output.status.success(),
+        "blit copy --overwrite {policy} failed\nstdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn expected(policy: &str) -> [bool; 3] {
+    match policy {
+        "never" => [false, false, false],
+        // The default compare: same size and an mtime that isn't older
+        // than the source's is treated as up to date.
+        "if-newer" => [false, true, false],
+        "always" => [true, true, true],
+        other => unreachable!("{other}"),
+    }
+}
+
+#[test]
+fn local_copy_applies_each_overwrite_policy() {
+    for policy in ["never", "if-newer", "always"] {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        let dst = tmp.path().join("dst");
+        seed(&src, &dst);
+
+        run_copy(None, policy, &src, &format!("{}/", dst.display()));
+
+        assert_eq!(overwritten(&dst), expected(policy), "--overwrite {policy}");
+        assert_eq!(fs::read(dst.join("fresh")).unwrap(), SOURCE, "{policy}");
+    }
+}
+
+#[test]
+fn push_applies_each_overwrite_policy_in_the_daemon_need_list() {
+    let ctx = TestContext::new();
+    for policy in ["never", "if-newer", "always"] {
+        let src = ctx.workspace.join(format!("src-{policy}"));
+        let dst = ctx.module_dir.join(policy);
+        seed(&src, &dst);
+
+        let dest = format!("127.0.0.1:{}:/test/{policy}/", ctx.daemon_port);
+        run_copy(Some(&ctx.config_dir), policy, &src, &dest);
+
+        assert_eq!(overwritten(&dst), expected(policy), "--overwrite {policy}");
+        assert_eq!(fs::read(dst.join("fresh")).unwrap(), SOURCE, "{policy}");
+    }
+}
@@ -0,0 +1,74 @@
+//! `blit prune`: deletes only the destination entries the source lacks
+//! and transfers nothing.
+
+use std::fs;
+use std::process::Command;
+use std::time::Duration;
+
+use serde_json::Value;
+
+mod common;
+use common::{run_with_timeout, TestContext};
+
+fn prune(ctx: &TestContext, src: &std::path::Path, extra: &[&str]) -> Value {
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.arg("--config-dir")
+        .arg(&ctx.config_dir)
+        .arg("prune")
+        .args(extra)
+        .arg("--json")
+        .arg(format!("{}/", src.display()))
+        .arg(format!("127.0.0.1:{}:/test/dest/", ctx.daemon_port));
+    let output = run_with_timeout(cmd, Duration::from_secs(60));
+    assert!(
+        output.status.success(),
+        "blit prune failed\nstdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("prune json")
+}
+
+#[test]
+fn prune_removes_only_extraneous_entries_and_copies_nothing() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    fs::create_dir_all(src.join("kept")).unwrap();
+    fs::write(src.join("same.txt"), b"source").unwrap();
+    fs::write(src.join("kept/inner.txt"), b"source").unwrap();
+    // Present only in the source: prune must not copy it.
+    fs::write(src.join("source-only.txt"), b"new").unwrap();
+
+    let dest = ctx.module_dir.join("dest");
+    fs::create_dir_all(dest.join("kept")).unwrap();
+    fs::create_dir_all(dest.join("stale-dir/deeper")).unwrap();
+    // Same path, different content: prune must leave it alone.
+    fs::write(dest.join("same.txt"), b"dest copy").unwrap();
+    fs::write(dest.join("kept/inner.txt"), b"dest copy").unwrap();
+    fs::write(dest.join("kept/stale.txt"), b"x").unwrap();
+    fs::write(dest.join("stale-dir/deeper/f.txt"), b"x").unwrap();
+    fs::write(dest.join("stale.txt"), b"x").unwrap();
+    // Outside the pruned directory: never considered.
+    fs::write(ctx.module_dir.join("sibling.txt"), b"x").unwrap();
+
+    let planned = prune(&ctx, &src, &["--dry-run"]);
+    assert_eq!(
+        planned["extraneous"],
+        serde_json::json!(["kept/stale.txt", "stale-dir", "stale.txt"])
+    );
+    assert_eq!(planned["entries_deleted"], 0);
+    assert!(dest.join("stale.txt").exists(), "dry run deleted");
+
+    let pruned = prune(&ctx, &src, &["--yes"]);
+    assert_eq!(pruned["extraneous"], planned["extraneous"]);
+    assert!(!dest.join("kept/stale.txt").exists());
+    assert!(!dest.join("stale-dir").exists());
+    assert!(!dest.join("stale.txt").exists());
+    assert_eq!(fs::read(dest.join("same.txt")).unwrap(), b"dest copy");
+    assert_eq!(fs::read(dest.join("kept/inner.txt")).unwrap(), b"dest copy");
+    assert!(!dest.join("source-only.txt").exists(), "prune copied");
+    assert!(ctx.module_dir.join("sibling.txt").exists());
+
+    let again = prune(&ctx, &src, &["--yes"]);
+    assert_eq!(again["extraneous"], serde_json::json!([]));
+}
@@ -0,0 +1,146 @@
+//! A pull whose local destination is a pipe (a FIFO, or `/dev/stdout`
+//! on one) writes the pulled tree into it as one tar stream instead of
+//! landing files: `blit copy server:/m/src/ pipe` with `tar x` reading
+//! the other end. Destructive and in-place modes are refused.
+#![cfg(unix)]
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread::{self, JoinHandle};
+
+mod common;
+use common::{assert_success, blit, write_fixture, TestContext};
+
+fn write_mixed_fixture(src: &Path) {
+    write_fixture(src);
+    fs::create_dir_all(src.join("sub/deeper")).unwrap();
+    for i in 0..40 {
+        fs::write(
+            src.join(format!("sub/deeper/small{i}.txt")),
+            format!("small {i}"),
+        )
+        .unwrap();
+    }
+    let large: Vec<u8> = (0..3 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+    fs::write(src.join("large.bin"), large).unwrap();
+}
+
+fn make_fifo(path: &Path) {
+    let status = Command::new("mkfifo").arg(path).status().unwrap();
+    assert!(status.success(), "mkfifo failed");
+}
+
+/// Read the FIFO to EOF on a thread: the pull's open blocks until a
+/// reader is there.
+fn drain(fifo: &Path) -> JoinHandle<Vec<u8>> {
+    let fifo: PathBuf = fifo.to_path_buf();
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        fs::File::open(&fifo)
+            .unwrap()
+            .read_to_end(&mut bytes)
+            .unwrap();
+        bytes
+    })
+}
+
+/// Every regular member of `bytes`, sorted by path.
+fn members(bytes: &[u8]) -> Vec<(String, Vec<u8>)> {
+    let mut archive = tar::Archive::new(bytes);
+    let mut members: Vec<(String, Vec<u8>)> = archive
+        .entries()
+        .unwrap()
+        .map(|entry| {
+            let mut entry = entry.unwrap();
+            let path = entry.path().unwrap().to_string_lossy().into_owned();
+            let mut contents = Vec::new();
+            entry.read_to_end(&mut contents).unwrap();
+            (path, contents)
+        })
+        .collect();
+    members.sort();
+    members
+}
+
+#[test]
+fn pull_into_a_fifo_streams_a_tar_of_the_tree() {
+    let ctx = TestContext::new();
+    let src = ctx.module_dir.join("src");
+    write_mixed_fixture(&src);
+    let fifo = ctx.workspace.join("out.pipe");
+    make_fifo(&fifo);
+
+    let reader = drain(&fifo);
+    assert_success(&blit(
+        &ctx,
+        &[
+            "copy",
+            &format!("127.0.0.1:{}:/test/src/", ctx.daemon_port),
+            fifo.to_str().unwrap(),
+        ],
+    ));
+    let archive = members(&reader.join().unwrap());
+
+    let mut expected: Vec<(String, Vec<u8>)> = walkdir::WalkDir::new(&src)
+        .into_iter()
+        .map(Result::unwrap)
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| {
+            let rel = entry.path().strip_prefix(&src).unwrap();
+            (
+                rel.to_string_lossy().into_owned(),
+                fs::read(entry.path()).unwrap(),
+            )
+        })
+        .collect();
+    expected.sort();
+    assert_eq!(archive.len(), 43);
+    assert_eq!(archive, expected);
+    assert!(fs::metadata(&fifo).is_ok(), "the pipe itself was replaced");
+}
+
+#[test]
+fn single_file_pull_into_a_fifo_names_the_member_after_the_file() {
+    let ctx = TestContext::new();
+    let src = ctx.module_dir.join("src");
+    write_mixed_fixture(&src);
+    let fifo = ctx.workspace.join("out.pipe");
+    make_fifo(&fifo);
+
+    let reader = drain(&fifo);
+    assert_success(&blit(
+        &ctx,
+        &[
+            "copy",
+            &format!("127.0.0.1:{}:/test/src/sub/nested.txt", ctx.daemon_port),
+            fifo.to_str().unwrap(),
+        ],
+    ));
+
+    assert_eq!(
+        members(&reader.join().unwrap()),
+        [("nested.txt".to_string(), b"nested".to_vec())]
+    );
+}
+
+#[test]
+fn mirror_and_resume_into_a_fifo_are_refused() {
+    let ctx = TestContext::new();
+    write_mixed_fixture(&ctx.module_dir.join("src"));
+    let fifo = ctx.workspace.join("out.pipe");
+    make_fifo(&fifo);
+    let source = format!("127.0.0.1:{}:/test/src/", ctx.daemon_port);
+
+    // No reader: a refusal must come before the pipe is opened.
+    for args in [
+        vec!["mirror", "--yes", &source, fifo.to_str().unwrap()],
+        vec!["copy", "--resume", &source, fifo.to_str().unwrap()],
+    ] {
+        let output = blit(&ctx, &args);
+        assert!(!output.status.success(), "{args:?} was accepted");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("cannot write into a pipe"), "{stderr}");
+    }
+}
@@ -15,12 +15,11 @@
 //! the clap/printer skin (which `remote_parity` and friends cover by
 //! running the actual binary). Mirrors `push_session_cutover.rs`.
 
-use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 mod common;
-use common::TestContext;
+use common::{assert_renamed_tree, rename_rules, tree_contents, TestContext};
 
 use blit_app::transfers::remote::{run_remote_pull, PullExecution};
 use blit_core::generated::{ComparisonMode, FilterSpec, MirrorMode};
@@ -43,17 +42,33 @@ fn pull_execution(port: u16, dest_root: &Path) -> PullExecution {
     PullExecution {
         remote: module_endpoint(port),
         dest_root: dest_root.to_path_buf(),
+        pack: false,
         filter: None,
+        rename: Vec::new(),
+        dial_request: None,
         mirror_mode: false,
         mirror_kind: MirrorMode::Off,
         force_grpc: false,
         trace_data_plane: false,
         require_complete_scan: false,
         drop_windows_metadata: false,
+        crtimes: false,
+        owner: false,
+        fake_super: false,
+        prune_empty_dirs: false,
         resume: false,
         resume_block_size: 0,
         compare_mode: ComparisonMode::SizeMtime,
         ignore_existing: false,
+        existing_only: false,
+        max_files: 0,
+        checksum_seed: 0,
+        chmod: String::new(),
+        chown: String::new(),
+        xattrs: String::new(),
+        remote_options: Default::default(),
+        metadata_only: false,
+        reconnect: None,
         remote_label: format!("127.0.0.1:{port}:/test/"),
         lifecycle_trace: Default::default(),
     }
@@ -84,24 +99,6 @@ fn write_fixture(src: &Path) -> (usize, u64) {
     (files.len(), total)
 }
 
-/// Relative path → content for every file under `root`.
-fn tree_contents(root: &Path) -> BTreeMap<String, Vec<u8>> {
-    let mut out = BTreeMap::new();
-    for entry in walkdir::WalkDir::new(root) {
-        let entry = entry.expect("walk tree");
-        if entry.file_type().is_file() {
-            let rel = entry
-                .path()
-                .strip_prefix(root)
-                .expect("relative path")
-                .to_string_lossy()
-                .replace('\\', "/");
-            out.insert(rel, fs::read(entry.path()).expect("read file"));
-        }
-    }
-    out
-}
-
 fn runtime() -> tokio::runtime::Runtime {
     tokio::runtime::Builder::new_multi_thread()
         .enable_all()
@@ -152,6 +149,66 @@ fn pull_verb_lands_identical_tree_with_exact_counts() {
     );
 }
 
+/// The library boundary an embedder (a GUI on blit-core) uses
+/// directly: `run_pull_session` with a progress channel and no CLI or
+/// blit-app layer. The stream opens with the manifest denominator,
+/// names each per-file completion once, and folds to the fixture's
+/// file count.
+#[test]
+fn library_pull_session_streams_structured_progress_events() {
+    use blit_core::remote::transfer::session_client::{run_pull_session, PullSessionOptions};
+
+    let ctx = TestContext::new();
+    let (fixture_files, fixture_bytes) = write_fixture(&ctx.module_dir);
+    let dest = ctx.workspace.join("dest");
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<ProgressEvent>();
+    runtime()
+        .block_on(run_pull_session(
+            &module_endpoint(ctx.daemon_port),
+            dest.clone(),
+            PullSessionOptions {
+                progress: Some(RemoteTransferProgress::new(tx)),
+                ..Default::default()
+            },
+        ))
+        .expect("library pull");
+
+    let mut events = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        events.push(event);
+    }
+    assert!(
+        matches!(events.first(), Some(ProgressEvent::ManifestBatch { .. })),
+        "the denominator arrives before any transfer event, got {:?}",
+        events.first()
+    );
+    let mut completed: Vec<&str> = events
+        .iter()
+        .filter_map(|event| match event {
+            ProgressEvent::FileComplete { path } => Some(path.as_str()),
+            _ => None,
+        })
+        .collect();
+    let before = completed.len();
+    completed.sort_unstable();
+    completed.dedup();
+    assert_eq!(completed.len(), before, "each file completes once");
+    let source = tree_contents(&ctx.module_dir);
+    assert!(completed.iter().all(|path| source.contains_key(*path)));
+
+    let mut totals = ProgressTotals::default();
+    for event in &events {
+        totals.apply(event);
+    }
+    assert_eq!(totals.manifest_files, fixture_files as u64);
+    assert_eq!(totals.manifest_bytes, fixture_bytes);
+    assert_eq!(totals.files, fixture_files as u64);
+    // Tar-shard payloads count their archive framing as moved bytes.
+    assert!(totals.bytes >= fixture_bytes);
+    assert_eq!(tree_contents(&dest), source);
+}
+
 /// Mirror through the verb entry: the session DESTINATION (this end)
 /// deletes the extraneous local entry at SourceDone (the one delete
 /// rule — no post-pull purge step exists on this path) and scores it.
@@ -186,6 +243,55 @@ fn pull_verb_mirror_purges_extraneous_and_scores_deletions() {
     assert_eq!(tree_contents(&dest), tree_contents(&ctx.module_dir));
 }
 
+/// Mirror over an up-to-date local copy: the remote's manifest is the
+/// authoritative set, so local-only entries (nested ones and a whole
+/// local-only directory) are deleted while every remote-present file is
+/// kept in place — nothing needs re-sending.
+#[test]
+fn pull_mirror_deletes_local_only_entries_and_keeps_remote_present_ones() {
+    let ctx = TestContext::new();
+    let (fixture_files, _) = write_fixture(&ctx.module_dir);
+    let dest = ctx.workspace.join("dest");
+
+    runtime().block_on(async {
+        run_remote_pull(pull_execution(ctx.daemon_port, &dest), None)
+            .await
+            .expect("seed pull");
+    });
+    fs::write(dest.join("nested/local_only.txt"), b"not on the remote").expect("seed nested");
+    fs::create_dir_all(dest.join("local_dir/inner")).expect("seed local dir");
+    fs::write(dest.join("local_dir/inner/x.txt"), b"x").expect("seed local dir file");
+
+    let summary = runtime()
+        .block_on(async {
+            let execution = PullExecution {
+                mirror_mode: true,
+                mirror_kind: MirrorMode::All,
+                ..pull_execution(ctx.daemon_port, &dest)
+            };
+            run_remote_pull(execution, None).await.expect("mirror pull")
+        })
+        .summary;
+
+    assert!(!dest.join("nested/local_only.txt").exists());
+    assert!(
+        !dest.join("local_dir").exists(),
+        "a local-only directory goes with its contents"
+    );
+    assert_eq!(
+        summary.files_transferred, 0,
+        "remote-present files are already current"
+    );
+    assert!(
+        summary.entries_deleted >= 2,
+        "summary must score the purge, got {}",
+        summary.entries_deleted
+    );
+    let landed = tree_contents(&dest);
+    assert_eq!(landed.len(), fixture_files);
+    assert_eq!(landed, tree_contents(&ctx.module_dir));
+}
+
 /// The verb's filter rides `SessionOpen.filter` and scopes the daemon
 /// SOURCE's scan (otp-6a chokepoint): excluded files never land.
 #[test]
@@ -215,6 +321,27 @@ fn pull_verb_wire_filter_scopes_the_source_scan() {
     assert!(dest.join("big.bin").exists(), "in-scope files must land");
 }
 
+/// `--rename` rides `SessionOpen.rename`; the daemon SOURCE rewrites
+/// its manifest, so this DESTINATION writes only the rewritten names.
+#[test]
+fn pull_verb_rename_rewrites_destination_paths() {
+    let ctx = TestContext::new();
+    write_fixture(&ctx.module_dir);
+    let dest = ctx.workspace.join("dest");
+
+    runtime().block_on(async {
+        let execution = PullExecution {
+            rename: rename_rules(),
+            ..pull_execution(ctx.daemon_port, &dest)
+        };
+        run_remote_pull(execution, None)
+            .await
+            .expect("renamed pull")
+    });
+
+    assert_renamed_tree(&ctx.module_dir, &dest);
+}
+
 /// `--force-grpc` maps to the session's in-stream byte carrier and the
 /// summary attests to it (the printers' `[gRPC fallback]` marker).
 #[test]
@@ -405,6 +532,53 @@ fn checksum_pull_skips_content_equal_files_regardless_of_mtime() {
     );
 }
 
+/// Repeat pulls are conditional: the DESTINATION diffs the source
+/// manifest against what it already holds (size + mtime under the
+/// default compare) and asks only for the paths in its `NeedBatch`es —
+/// the pull-side counterpart of the push daemon's old
+/// `file_requires_upload`. An unchanged tree moves zero files; one
+/// edited source file moves exactly that file.
+#[test]
+fn repeat_pull_transfers_only_changed_files() {
+    let ctx = TestContext::new();
+    let (fixture_files, _) = write_fixture(&ctx.module_dir);
+    let dest = ctx.workspace.join("dest");
+    let pull = || {
+        runtime()
+            .block_on(run_remote_pull(
+                pull_execution(ctx.daemon_port, &dest),
+                None,
+            ))
+            .expect("session pull")
+            .summary
+    };
+
+    assert_eq!(pull().files_transferred, fixture_files as u64);
+
+    let summary = pull();
+    assert_eq!(
+        summary.files_transferred, 0,
+        "unchanged tree must transfer nothing on a repeat pull"
+    );
+    assert_eq!(summary.bytes_transferred, 0);
+
+    let edited = ctx.module_dir.join("nested/small_b.txt");
+    fs::write(&edited, b"beta-beta-edited").expect("edit source");
+    filetime::set_file_mtime(
+        &edited,
+        filetime::FileTime::from_unix_time(2_000_000_000, 0),
+    )
+    .expect("bump source mtime");
+
+    let summary = pull();
+    assert_eq!(
+        summary.files_transferred, 1,
+        "only the edited file may transfer"
+    );
+    assert_eq!(summary.bytes_transferred, b"beta-beta-edited".len() as u64);
+    assert_eq!(tree_contents(&dest), tree_contents(&ctx.module_dir));
+}
+
 /// `--ignore-existing` rides `SessionOpen.ignore_existing` (otp-10b-2
 /// wired it for both verbs): an existing destination file is skipped
 /// whatever its content, and never overwritten.
@@ -436,6 +610,49 @@ fn ignore_existing_pull_skips_existing_destination_files() {
     );
 }
 
+/// `--max-files` rides `SessionOpen.max_files` (contract v18): the
+/// daemon SOURCE refuses a module over the cap with FILE_LIMIT before
+/// any of its manifest reaches this end, and serves one within it.
+#[test]
+fn max_files_pull_refuses_over_the_cap_and_proceeds_within() {
+    let ctx = TestContext::new();
+    let (fixture_files, _) = write_fixture(&ctx.module_dir);
+    let dest = ctx.workspace.join("dest");
+
+    let err = runtime()
+        .block_on(run_remote_pull(
+            PullExecution {
+                max_files: fixture_files as u64 - 1,
+                ..pull_execution(ctx.daemon_port, &dest)
+            },
+            None,
+        ))
+        .err()
+        .expect("a module over the cap must refuse");
+    let msg = format!("{err:#}");
+    assert!(
+        msg.contains("FILE_LIMIT") && msg.contains("--max-files"),
+        "got: {msg}"
+    );
+    assert!(
+        !dest.exists() || tree_contents(&dest).is_empty(),
+        "nothing lands from a refused pull"
+    );
+
+    let summary = runtime()
+        .block_on(run_remote_pull(
+            PullExecution {
+                max_files: fixture_files as u64,
+                ..pull_execution(ctx.daemon_port, &dest)
+            },
+            None,
+        ))
+        .expect("a module within the cap pulls")
+        .summary;
+    assert_eq!(summary.files_transferred, fixture_files as u64);
+    assert_eq!(tree_contents(&dest), tree_contents(&ctx.module_dir));
+}
+
 /// Single-file pull layout (the old pull's convention, kept by the
 /// session): the source manifests a file root with an empty relative
 /// path, and the verb's `dest_root` is the target FILE path — the
@@ -627,3 +844,70 @@ fn pull_verb_resume_patches_changed_partials_blockwise() {
         v3.len()
     );
 }
+
+/// `--checksum-seed` rides `SessionOpen.checksum_seed` (contract v19):
+/// the daemon SOURCE diffs under the same key this DESTINATION hashed
+/// its partial with, so a seeded resume still sends only the stale
+/// blocks — a key mismatch would read every block as stale.
+#[test]
+fn pull_resume_with_checksum_seed_sends_only_stale_blocks() {
+    let ctx = TestContext::new();
+    let big: Vec<u8> = (0..u32::try_from(4 * 1024 * 1024).unwrap())
+        .map(|i| (i % 239) as u8)
+        .collect();
+    fs::write(ctx.module_dir.join("seeded.bin"), &big).expect("write v1");
+    let dest = ctx.workspace.join("dest");
+
+    runtime().block_on(async {
+        run_remote_pull(pull_execution(ctx.daemon_port, &dest), None)
+            .await
+            .expect("seed pull");
+    });
+
+    let mut v2 = big.clone();
+    for b in &mut v2[..4096] {
+        *b ^= 0x5A;
+    }
+    fs::write(ctx.module_dir.join("seeded.bin"), &v2).expect("write v2");
+    let bumped = filetime::FileTime::from_unix_time(
+        filetime::FileTime::from_last_modification_time(
+            &fs::metadata(ctx.module_dir.join("seeded.bin")).expect("meta"),
+        )
+        .unix_seconds()
+            + 5,
+        0,
+    );
+    filetime::set_file_mtime(ctx.module_dir.join("seeded.bin"), bumped).expect("bump mtime");
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<ProgressEvent>();
+    let summary = runtime()
+        .block_on(async {
+            let progress = RemoteTransferProgress::new(tx);
+            let execution = PullExecution {
+                resume: true,
+                checksum_seed: 0x5eed_1234,
+                ..pull_execution(ctx.daemon_port, &dest)
+            };
+            run_remote_pull(execution, Some(&progress))
+                .await
+                .expect("seeded resume pull")
+        })
+        .summary;
+
+    assert_eq!(summary.files_resumed, 1, "changed partial must resume");
+    assert_eq!(
+        fs::read(dest.join("seeded.bin")).expect("read dest"),
+        v2,
+        "seeded resume must land the new source"
+    );
+    let mut totals = ProgressTotals::default();
+    while let Ok(event) = rx.try_recv() {
+        totals.apply(&event);
+    }
+    assert!(
+        totals.bytes > 0 && totals.bytes < v2.len() as u64,
+        "both ends must hash under one key, got {} of {} bytes",
+        totals.bytes,
+        v2.len()
+    );
+}
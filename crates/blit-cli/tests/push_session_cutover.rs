@@ -15,17 +15,18 @@
 //! the clap/printer skin (which `remote_parity` and friends cover by
 //! running the actual binary).
 
-use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 mod common;
-use common::TestContext;
+use common::{assert_renamed_tree, rename_rules, tree_contents, TestContext};
 
 use blit_app::transfers::remote::{run_remote_push, PushExecution};
+use blit_core::enumeration::SymlinkDeref;
 use blit_core::generated::{ComparisonMode, FilterSpec, MirrorMode};
 use blit_core::remote::transfer::{ProgressEvent, ProgressTotals, RemoteTransferProgress};
 use blit_core::remote::{RemoteEndpoint, RemotePath};
+use blit_core::transfer_plan::PlanOptions;
 
 fn module_endpoint(port: u16) -> RemoteEndpoint {
     RemoteEndpoint {
@@ -44,16 +45,40 @@ fn push_execution(src: &Path, port: u16) -> PushExecution {
         source: src.to_path_buf(),
         remote: module_endpoint(port),
         filter: None,
+        rename: Vec::new(),
+        unpack: false,
+        symlink_deref: SymlinkDeref::None,
+        safe_links: false,
+        vss: false,
+        snapshot: false,
+        dial_request: None,
+        plan_options: PlanOptions::default(),
         mirror_mode: false,
         mirror_kind: MirrorMode::Off,
         force_grpc: false,
         trace_data_plane: false,
         require_complete_scan: false,
         drop_windows_metadata: false,
+        crtimes: false,
+        owner: false,
+        fake_super: false,
+        manifest_checkpoint: None,
+        prune_empty_dirs: false,
         resume: false,
         resume_block_size: 0,
         compare_mode: ComparisonMode::SizeMtime,
         ignore_existing: false,
+        existing_only: false,
+        max_files: 0,
+        checksum_seed: 0,
+        chmod: String::new(),
+        chown: String::new(),
+        xattrs: String::new(),
+        remote_options: Default::default(),
+        file_count_warning: None,
+        metadata_only: false,
+        reconnect: None,
+        remove_source_files: false,
         remote_label: format!("127.0.0.1:{port}:/test/"),
         lifecycle_trace: Default::default(),
     }
@@ -84,24 +109,6 @@ fn write_fixture(src: &Path) -> (usize, u64) {
     (files.len(), total)
 }
 
-/// Relative path → content for every file under `root`.
-fn tree_contents(root: &Path) -> BTreeMap<String, Vec<u8>> {
-    let mut out = BTreeMap::new();
-    for entry in walkdir::WalkDir::new(root) {
-        let entry = entry.expect("walk tree");
-        if entry.file_type().is_file() {
-            let rel = entry
-                .path()
-                .strip_prefix(root)
-                .expect("relative path")
-                .to_string_lossy()
-                .replace('\\', "/");
-            out.insert(rel, fs::read(entry.path()).expect("read file"));
-        }
-    }
-    out
-}
-
 fn runtime() -> tokio::runtime::Runtime {
     tokio::runtime::Builder::new_multi_thread()
         .enable_all()
@@ -215,6 +222,28 @@ fn push_verb_wire_filter_scopes_the_source_scan() {
     );
 }
 
+/// `--rename` rides `SessionOpen.rename`; this SOURCE rewrites its
+/// manifest, so the daemon writes only the rewritten names — tar-shard
+/// members and the data-plane File payload alike.
+#[test]
+fn push_verb_rename_rewrites_destination_paths() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    write_fixture(&src);
+
+    runtime().block_on(async {
+        let execution = PushExecution {
+            rename: rename_rules(),
+            ..push_execution(&src, ctx.daemon_port)
+        };
+        run_remote_push(execution, None)
+            .await
+            .expect("renamed push")
+    });
+
+    assert_renamed_tree(&src, &ctx.module_dir);
+}
+
 /// `--force-grpc` maps to the session's in-stream byte carrier and the
 /// summary attests to it (the printers' `[gRPC fallback]` marker).
 #[test]
@@ -242,6 +271,49 @@ fn push_verb_force_grpc_rides_the_in_stream_carrier() {
     assert_eq!(tree_contents(&ctx.module_dir), tree_contents(&src));
 }
 
+/// The push outcome's bandwidth report carries one entry per TCP
+/// data-plane stream, and its aggregate throughput is the streams'
+/// summed bytes over the session's wall time. Stream bytes include
+/// record framing, so they cover at least the fixture's bytes.
+#[test]
+fn push_verb_reports_per_stream_bytes_and_aggregate_throughput() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    let (_, fixture_bytes) = write_fixture(&src);
+
+    let outcome = runtime()
+        .block_on(run_remote_push(push_execution(&src, ctx.daemon_port), None))
+        .expect("session push");
+    let bandwidth = &outcome.bandwidth;
+
+    assert!(!bandwidth.streams.is_empty(), "{bandwidth:?}");
+    let ids: Vec<u32> = bandwidth.streams.iter().map(|s| s.stream.0).collect();
+    let mut sorted = ids.clone();
+    sorted.sort_unstable();
+    sorted.dedup();
+    assert_eq!(ids, sorted, "one entry per stream, in id order");
+    let total: u64 = bandwidth.streams.iter().map(|s| s.bytes).sum();
+    assert_eq!(bandwidth.total_bytes(), total);
+    assert!(total >= fixture_bytes, "{total} < {fixture_bytes}");
+    assert!(bandwidth.elapsed <= outcome.elapsed);
+    assert_eq!(
+        bandwidth.aggregate_bytes_per_sec(),
+        (total as f64 / bandwidth.elapsed.as_secs_f64()) as u64
+    );
+
+    // The in-stream carrier runs no data-plane streams.
+    let forced = runtime()
+        .block_on(run_remote_push(
+            PushExecution {
+                force_grpc: true,
+                ..push_execution(&src, ctx.daemon_port)
+            },
+            None,
+        ))
+        .expect("forced in-stream push");
+    assert!(forced.bandwidth.streams.is_empty());
+}
+
 /// The verb emits the w6-1 progress contract from the session SOURCE:
 /// need batches as the denominator, one FileComplete per file, bytes
 /// riding Payload — folded through the shared `ProgressTotals`, they
@@ -362,6 +434,43 @@ fn move_shaped_push_transfers_same_size_newer_destination() {
     );
 }
 
+/// `--existing` rides `SessionOpen.existing_only`: the daemon
+/// DESTINATION updates the files it already has and requests none of
+/// the rest — not even their directories.
+#[test]
+fn existing_only_push_updates_present_files_and_creates_nothing() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    fs::create_dir_all(src.join("fresh")).expect("src dirs");
+    fs::write(src.join("present.txt"), b"updated-source-content").expect("write source");
+    fs::write(src.join("absent.txt"), b"new").expect("write source");
+    fs::write(src.join("fresh/inner.txt"), b"new").expect("write source");
+    fs::write(ctx.module_dir.join("present.txt"), b"old").expect("seed dest");
+
+    let summary = runtime()
+        .block_on(async {
+            let execution = PushExecution {
+                existing_only: true,
+                ..push_execution(&src, ctx.daemon_port)
+            };
+            run_remote_push(execution, None)
+                .await
+                .expect("existing-only push")
+        })
+        .summary;
+
+    assert_eq!(
+        summary.files_transferred, 1,
+        "only the present file updates"
+    );
+    assert_eq!(
+        fs::read(ctx.module_dir.join("present.txt")).expect("read dest"),
+        b"updated-source-content"
+    );
+    assert!(!ctx.module_dir.join("absent.txt").exists());
+    assert!(!ctx.module_dir.join("fresh").exists());
+}
+
 /// codex otp-10a F3: a daemon started with `--force-grpc-data` never
 /// grants a TCP data plane — a session push against it rides the
 /// in-stream carrier even though the client did not ask for it, the
@@ -0,0 +1,150 @@
+//! `--quiet` and `--numeric-progress`, the scripting output modes:
+//! `--quiet` leaves stdout empty on success and still fails with a
+//! non-zero status, and `--numeric-progress` ends stderr with a
+//! parseable `PERCENT% DONE/TOTAL bytes` line — locally and through a
+//! push.
+
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+mod common;
+use common::{assert_success, blit, write_fixture, TestContext};
+
+/// The shared fixture's 9 bytes plus two sized files, 1509 in all.
+const TOTAL_BYTES: u64 = 1509;
+
+fn write_sized_fixture(src: &Path) {
+    write_fixture(src);
+    fs::write(src.join("a.bin"), vec![b'a'; 1000]).unwrap();
+    fs::write(src.join("sub/b.bin"), vec![b'b'; 500]).unwrap();
+}
+
+/// The `(percent, done, total)` of the last progress line on stderr,
+/// split on both the in-place `\r` and the closing newline.
+fn last_progress(output: &Output) -> (u64, u64, u64) {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let line = stderr
+        .split(['\r', '\n'])
+        .rfind(|line| line.ends_with(" bytes"))
+        .unwrap_or_else(|| panic!("no progress line in stderr:\n{stderr:?}"));
+    let (percent, rest) = line.split_once("% ").expect("PERCENT% prefix");
+    let (done, total) = rest
+        .strip_suffix(" bytes")
+        .and_then(|counts| counts.split_once('/'))
+        .expect("DONE/TOTAL bytes");
+    (
+        percent.parse().unwrap(),
+        done.parse().unwrap(),
+        total.parse().unwrap(),
+    )
+}
+
+#[test]
+fn quiet_local_copy_prints_nothing_on_success() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    let dst = ctx.workspace.join("dst");
+    write_sized_fixture(&src);
+
+    let output = blit(
+        &ctx,
+        &[
+            "copy",
+            "--yes",
+            "--quiet",
+            &format!("{}/", src.display()),
+            &format!("{}/", dst.display()),
+        ],
+    );
+
+    assert_success(&output);
+    assert!(output.stdout.is_empty(), "stdout: {:?}", output.stdout);
+    assert!(
+        !String::from_utf8_lossy(&output.stderr).contains("starting"),
+        "banner printed under --quiet"
+    );
+    assert_eq!(fs::read(dst.join("sub/b.bin")).unwrap(), vec![b'b'; 500]);
+}
+
+#[test]
+fn quiet_push_prints_nothing_on_success() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    write_sized_fixture(&src);
+
+    let output = blit(
+        &ctx,
+        &[
+            "copy",
+            "--yes",
+            "-q",
+            &format!("{}/", src.display()),
+            &format!("127.0.0.1:{}:/test/pushed/", ctx.daemon_port),
+        ],
+    );
+
+    assert_success(&output);
+    assert!(output.stdout.is_empty(), "stdout: {:?}", output.stdout);
+    assert!(ctx.module_dir.join("pushed/a.bin").exists());
+}
+
+#[test]
+fn quiet_failure_still_exits_non_zero_with_the_error() {
+    let ctx = TestContext::new();
+    let missing = ctx.workspace.join("missing");
+    let dst = ctx.workspace.join("dst");
+
+    let output = blit(
+        &ctx,
+        &[
+            "copy",
+            "--yes",
+            "--quiet",
+            &format!("{}/", missing.display()),
+            &format!("{}/", dst.display()),
+        ],
+    );
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty(), "stdout: {:?}", output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("does not exist"), "stderr:\n{stderr}");
+}
+
+#[test]
+fn numeric_progress_ends_with_a_parseable_complete_line() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    let dst = ctx.workspace.join("dst");
+    write_sized_fixture(&src);
+
+    let local = blit(
+        &ctx,
+        &[
+            "copy",
+            "--yes",
+            "--numeric-progress",
+            &format!("{}/", src.display()),
+            &format!("{}/", dst.display()),
+        ],
+    );
+    assert_success(&local);
+    assert_eq!(last_progress(&local), (100, TOTAL_BYTES, TOTAL_BYTES));
+
+    let pushed = blit(
+        &ctx,
+        &[
+            "copy",
+            "--yes",
+            "--quiet",
+            "--numeric-progress",
+            &format!("{}/", src.display()),
+            &format!("127.0.0.1:{}:/test/pushed/", ctx.daemon_port),
+        ],
+    );
+    assert_success(&pushed);
+    // Under --quiet the progress line is the only output.
+    assert!(pushed.stdout.is_empty(), "stdout: {:?}", pushed.stdout);
+    assert_eq!(last_progress(&pushed), (100, TOTAL_BYTES, TOTAL_BYTES));
+}
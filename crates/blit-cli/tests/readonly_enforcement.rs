@@ -123,6 +123,33 @@ fn purge_on_read_only_module_is_rejected_and_file_survives() {
     );
 }
 
+#[test]
+fn find_delete_on_read_only_module_is_rejected_and_files_survive() {
+    let ctx = TestContext::new_read_only();
+
+    let victim = ctx.module_dir.join("old.log");
+    fs::write(&victim, b"survives").expect("seed module file");
+
+    let target = format!("127.0.0.1:{}:/test/", ctx.daemon_port);
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.arg("--config-dir")
+        .arg(&ctx.config_dir)
+        .arg("find")
+        .arg(&target)
+        .arg("--pattern")
+        .arg("*.log")
+        .arg("--delete")
+        .arg("-y");
+    let output = run_with_timeout(cmd, Duration::from_secs(60));
+
+    assert_read_only_rejection(&output, "find --delete");
+    assert_eq!(
+        fs::read(&victim).expect("file must still exist"),
+        b"survives",
+        "rejected find --delete must not delete anything"
+    );
+}
+
 // ---------------------------------------------------------------
 // Delegated pull: needs a delegation-enabled destination whose
 // module is read-only. The gate fires before the destination ever
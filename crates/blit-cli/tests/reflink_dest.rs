@@ -0,0 +1,100 @@
+//! `--reflink-dest DIR`: a file the reference tree holds unchanged
+//! (same size and mtime) is cloned copy-on-write from it rather than
+//! copied from the source.
+//!
+//! The clone assertions need a reflink-capable filesystem (btrfs, XFS
+//! with reflink, APFS, ReFS). Point `BLIT_REFLINK_TEST_DIR` at one to
+//! run them; elsewhere the test checks the copy-from-source fallback.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use filetime::FileTime;
+
+mod common;
+use common::{cli_bin, run_with_timeout};
+
+fn scratch() -> tempfile::TempDir {
+    match std::env::var_os("BLIT_REFLINK_TEST_DIR") {
+        Some(dir) => tempfile::tempdir_in(dir).unwrap(),
+        None => tempfile::tempdir().unwrap(),
+    }
+}
+
+/// Whether `dir`'s filesystem can clone files.
+fn reflink_supported(dir: &Path) -> bool {
+    let probe_src = dir.join(".probe-src");
+    let probe_dst = dir.join(".probe-dst");
+    fs::write(&probe_src, b"probe").unwrap();
+    let supported = blit_core::fs_capability::reflink(&probe_src, &probe_dst).unwrap_or(false);
+    let _ = fs::remove_file(&probe_src);
+    let _ = fs::remove_file(&probe_dst);
+    supported
+}
+
+fn copy_with_reference(src: &Path, reference: &Path, dst: &Path) {
+    let mut cmd = Command::new(cli_bin());
+    cmd.arg("copy")
+        .arg("--yes")
+        .arg("--reflink-dest")
+        .arg(reference)
+        .arg(format!("{}/", src.display()))
+        .arg(format!("{}/", dst.display()));
+    let output = run_with_timeout(cmd, Duration::from_secs(30));
+    assert!(
+        output.status.success(),
+        "blit copy --reflink-dest failed\nstdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn reflink_dest_clones_unchanged_files_as_independent_copies() {
+    let tmp = scratch();
+    let src = tmp.path().join("src");
+    let reference = tmp.path().join("ref");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(&src).unwrap();
+    fs::create_dir_all(&reference).unwrap();
+    // The reference copy has the source's size and mtime but different
+    // bytes, so the destination shows which side it came from.
+    let mtime = FileTime::from_unix_time(1_700_000_000, 0);
+    fs::write(src.join("same.bin"), vec![1u8; 256 * 1024]).unwrap();
+    fs::write(reference.join("same.bin"), vec![2u8; 256 * 1024]).unwrap();
+    filetime::set_file_mtime(src.join("same.bin"), mtime).unwrap();
+    filetime::set_file_mtime(reference.join("same.bin"), mtime).unwrap();
+    // An older reference copy never matches.
+    fs::write(src.join("changed.bin"), vec![3u8; 1024]).unwrap();
+    fs::write(reference.join("changed.bin"), vec![4u8; 1024]).unwrap();
+    filetime::set_file_mtime(
+        reference.join("changed.bin"),
+        FileTime::from_unix_time(1, 0),
+    )
+    .unwrap();
+
+    copy_with_reference(&src, &reference, &dst);
+
+    assert_eq!(fs::read(dst.join("changed.bin")).unwrap(), vec![3u8; 1024]);
+    if !reflink_supported(tmp.path()) {
+        eprintln!("reflink unsupported here; checked the copy-from-source fallback");
+        assert_eq!(
+            fs::read(dst.join("same.bin")).unwrap(),
+            vec![1u8; 256 * 1024]
+        );
+        return;
+    }
+    assert_eq!(
+        fs::read(dst.join("same.bin")).unwrap(),
+        vec![2u8; 256 * 1024],
+        "an unchanged reference file is cloned, not copied from the source"
+    );
+    // A CoW clone, not a hard link: writing the reference leaves it be.
+    fs::write(reference.join("same.bin"), vec![9u8; 256 * 1024]).unwrap();
+    assert_eq!(
+        fs::read(dst.join("same.bin")).unwrap(),
+        vec![2u8; 256 * 1024]
+    );
+}
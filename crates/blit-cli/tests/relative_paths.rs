@@ -0,0 +1,94 @@
+//! `--relative` (`-R`): the source's path is reproduced under the
+//! destination — from the source's root, or from a `/./` anchor —
+//! instead of the trailing-slash rules placing only its last component.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+mod common;
+use common::{run_with_timeout, TestContext};
+
+const FILES: [&str; 2] = ["index.html", "css/site.css"];
+
+fn write_site(site: &Path) {
+    for rel in FILES {
+        let path = site.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, rel.as_bytes()).unwrap();
+    }
+}
+
+fn blit_in(ctx: &TestContext, cwd: &Path, args: &[&str]) {
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.current_dir(cwd)
+        .arg("--config-dir")
+        .arg(&ctx.config_dir);
+    for a in args {
+        cmd.arg(a);
+    }
+    let output = run_with_timeout(cmd, Duration::from_secs(60));
+    assert!(
+        output.status.success(),
+        "blit {args:?} failed\nstdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn assert_site_at(root: &Path) {
+    for rel in FILES {
+        assert_eq!(
+            fs::read(root.join(rel)).unwrap(),
+            rel.as_bytes(),
+            "{} missing",
+            root.join(rel).display()
+        );
+    }
+}
+
+#[test]
+fn local_copy_with_and_without_relative() {
+    let ctx = TestContext::new();
+    write_site(&ctx.workspace.join("srv/www/site"));
+
+    // Without --relative only the last component is nested.
+    let plain = ctx.workspace.join("plain");
+    blit_in(&ctx, &ctx.workspace, &["copy", "srv/www/site", "plain/"]);
+    assert_site_at(&plain.join("site"));
+    assert!(!plain.join("srv").exists());
+
+    // With it the whole source path is rebuilt, trailing slash or not.
+    let relative = ctx.workspace.join("relative");
+    blit_in(
+        &ctx,
+        &ctx.workspace,
+        &["copy", "--relative", "srv/www/site/", "relative/"],
+    );
+    assert_site_at(&relative.join("srv/www/site"));
+
+    // A `/./` anchor starts the rebuilt path after it.
+    let anchored = ctx.workspace.join("anchored");
+    blit_in(
+        &ctx,
+        &ctx.workspace,
+        &["copy", "-R", "srv/./www/site", "anchored/"],
+    );
+    assert_site_at(&anchored.join("www/site"));
+    assert!(!anchored.join("srv").exists());
+}
+
+#[test]
+fn push_with_relative_rebuilds_the_anchored_path_in_the_module() {
+    let ctx = TestContext::new();
+    write_site(&ctx.workspace.join("srv/www/site"));
+    let dest = format!("127.0.0.1:{}:/test/", ctx.daemon_port);
+
+    blit_in(&ctx, &ctx.workspace, &["copy", "srv/www/site", &dest]);
+    assert_site_at(&ctx.module_dir.join("site"));
+
+    let anchored = format!("{}/./www/site", ctx.workspace.join("srv").display());
+    blit_in(&ctx, &ctx.workspace, &["copy", "-R", &anchored, &dest]);
+    assert_site_at(&ctx.module_dir.join("www/site"));
+}
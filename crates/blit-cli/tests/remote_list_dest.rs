@@ -0,0 +1,129 @@
+//! `--list-dest`: a read-only preview of a push classifies every path
+//! against a live daemon module and leaves the destination untouched.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::time::Duration;
+
+use filetime::{set_file_mtime, FileTime};
+
+mod common;
+use common::{run_with_timeout, TestContext};
+
+fn write_at(path: &Path, contents: &[u8], mtime: i64) {
+    fs::write(path, contents).unwrap();
+    set_file_mtime(path, FileTime::from_unix_time(mtime, 0)).unwrap();
+}
+
+fn list_dest(ctx: &TestContext, src: &Path, extra: &[&str]) -> Output {
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.arg("--config-dir")
+        .arg(&ctx.config_dir)
+        .arg("copy")
+        .arg("--list-dest");
+    for a in extra {
+        cmd.arg(a);
+    }
+    cmd.arg(format!("{}/", src.display()))
+        .arg(format!("127.0.0.1:{}:/test/", ctx.daemon_port));
+    let output = run_with_timeout(cmd, Duration::from_secs(60));
+    assert!(
+        output.status.success(),
+        "blit copy --list-dest failed:\nstdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+#[test]
+fn list_dest_classifies_new_changed_and_extra_files() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    fs::create_dir_all(src.join("sub")).unwrap();
+    let dst = &ctx.module_dir;
+    fs::create_dir_all(dst.join("sub")).unwrap();
+
+    write_at(&src.join("new.txt"), b"brand new", 1_700_000_000);
+    write_at(&src.join("sub/newer.txt"), b"source edit", 1_700_000_200);
+    write_at(&dst.join("sub/newer.txt"), b"old", 1_700_000_100);
+    write_at(&src.join("older.txt"), b"stale source", 1_700_000_100);
+    write_at(&dst.join("older.txt"), b"destination edit", 1_700_000_200);
+    write_at(&src.join("resized.txt"), b"12345", 1_700_000_000);
+    write_at(&dst.join("resized.txt"), b"123", 1_700_000_000);
+    write_at(&src.join("same.txt"), b"same", 1_700_000_000);
+    write_at(&dst.join("same.txt"), b"same", 1_700_000_000);
+    write_at(&dst.join("sub/extra.txt"), b"only here", 1_700_000_000);
+
+    let output = list_dest(&ctx, &src, &["--json"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let got: Vec<(String, String)> = report["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| {
+            (
+                e["path"].as_str().unwrap().to_string(),
+                e["change"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    let want: Vec<(String, String)> = [
+        ("new.txt", "new_on_source"),
+        ("older.txt", "newer_on_destination"),
+        ("resized.txt", "size_differs"),
+        ("sub/extra.txt", "destination_only"),
+        ("sub/newer.txt", "newer_on_source"),
+    ]
+    .iter()
+    .map(|(p, c)| (p.to_string(), c.to_string()))
+    .collect();
+    assert_eq!(got, want);
+    assert_eq!(report["unchanged"], 1);
+
+    // The itemized report carries the same verdicts.
+    let stdout = String::from_utf8(list_dest(&ctx, &src, &[]).stdout).unwrap();
+    for line in [
+        "+ new.txt (9 -> -)",
+        "< older.txt (12 -> 16)",
+        "~ resized.txt (5 -> 3)",
+        "* sub/extra.txt (- -> 9)",
+        "> sub/newer.txt (11 -> 3)",
+    ] {
+        assert!(stdout.contains(line), "missing {line:?} in:\n{stdout}");
+    }
+    assert!(stdout.contains("1 new, 1 newer on source, 1 newer on destination, 1 size differs, 1 destination-only, 1 unchanged"), "{stdout}");
+
+    // Read-only: nothing was pushed and nothing was deleted.
+    assert!(!dst.join("new.txt").exists());
+    assert_eq!(fs::read(dst.join("sub/newer.txt")).unwrap(), b"old");
+    assert!(dst.join("sub/extra.txt").exists());
+}
+
+#[test]
+fn list_dest_treats_a_missing_destination_as_empty() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("a.txt"), b"a").unwrap();
+
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.arg("--config-dir")
+        .arg(&ctx.config_dir)
+        .arg("copy")
+        .arg("--list-dest")
+        .arg("--json")
+        .arg(format!("{}/", src.display()))
+        .arg(format!("127.0.0.1:{}:/test/not-yet/", ctx.daemon_port));
+    let output = run_with_timeout(cmd, Duration::from_secs(60));
+    assert!(
+        output.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["entries"][0]["path"], "a.txt");
+    assert_eq!(report["entries"][0]["change"], "new_on_source");
+    assert!(!ctx.module_dir.join("not-yet").exists());
+}
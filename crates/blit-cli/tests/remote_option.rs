@@ -0,0 +1,130 @@
+//! `--remote-option KEY=VALUE`: toggles the daemon end of a push or
+//! pull applies. A receiving daemon honors `fsync=true`, refuses a
+//! malformed value for it, and ignores a key it does not know with a
+//! warning the client repeats; a pull's source daemon knows no keys.
+
+use std::fs;
+use std::path::Path;
+
+mod common;
+use common::{assert_success, blit, write_fixture, TestContext};
+
+fn write_fixture_with_large(src: &Path) {
+    write_fixture(src);
+    fs::write(src.join("large.bin"), vec![7u8; 2 * 1024 * 1024]).unwrap();
+}
+
+fn assert_copied(src: &Path, dst: &Path) {
+    for rel in ["top.txt", "sub/nested.txt", "large.bin"] {
+        assert_eq!(
+            fs::read(dst.join(rel)).unwrap(),
+            fs::read(src.join(rel)).unwrap(),
+            "{rel}"
+        );
+    }
+}
+
+#[test]
+fn push_honors_fsync_and_ignores_an_unknown_option() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    write_fixture_with_large(&src);
+
+    let stderr = assert_success(&blit(
+        &ctx,
+        &[
+            "copy",
+            "--remote-option",
+            "fsync=true",
+            "--remote-option",
+            "bogus=1",
+            &format!("{}/", src.display()),
+            &format!("127.0.0.1:{}:/test/pushed/", ctx.daemon_port),
+        ],
+    ));
+
+    assert_copied(&src, &ctx.module_dir.join("pushed"));
+    assert!(
+        stderr.contains("ignored remote option bogus"),
+        "no warning for the unknown key: {stderr}"
+    );
+    // The warnings render the daemon's SessionAccept.ignored_remote_options:
+    // a receiving daemon knows fsync, so it is not among them.
+    assert!(
+        !stderr.contains("remote option fsync"),
+        "fsync was not honored: {stderr}"
+    );
+}
+
+#[test]
+fn push_refuses_a_malformed_known_option() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    write_fixture_with_large(&src);
+
+    let output = blit(
+        &ctx,
+        &[
+            "copy",
+            "--remote-option",
+            "fsync=sometimes",
+            &format!("{}/", src.display()),
+            &format!("127.0.0.1:{}:/test/pushed/", ctx.daemon_port),
+        ],
+    );
+
+    assert!(!output.status.success(), "a bad fsync value was accepted");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("fsync"), "{stderr}");
+    assert!(!ctx.module_dir.join("pushed/top.txt").exists());
+}
+
+#[test]
+fn pull_source_ignores_destination_options() {
+    let ctx = TestContext::new();
+    let src = ctx.module_dir.join("src");
+    write_fixture_with_large(&src);
+    let dst = ctx.workspace.join("dst");
+
+    let stderr = assert_success(&blit(
+        &ctx,
+        &[
+            "copy",
+            "--remote-option",
+            "fsync=true",
+            &format!("127.0.0.1:{}:/test/src/", ctx.daemon_port),
+            &format!("{}/", dst.display()),
+        ],
+    ));
+
+    assert_copied(&src, &dst);
+    assert!(
+        stderr.contains("ignored remote option fsync"),
+        "no warning for a key the source does not know: {stderr}"
+    );
+}
+
+#[test]
+fn local_copy_refuses_remote_options() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    write_fixture_with_large(&src);
+
+    let output = blit(
+        &ctx,
+        &[
+            "copy",
+            "--remote-option",
+            "fsync=true",
+            &format!("{}/", src.display()),
+            &format!("{}/", ctx.workspace.join("dst").display()),
+        ],
+    );
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("only applies to a push or pull"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -153,6 +153,67 @@ fn test_pull_multisocket_many_files() {
     }
 }
 
+/// `--streams N` pins the data plane: the grant carries exactly N
+/// epoch-0 sockets, the initiator dials exactly N, and the pinned dial
+/// never proposes a resize that would open another.
+#[test]
+fn test_streams_flag_pins_data_plane_socket_count() {
+    let ctx = TestContext::new();
+    let src_dir = ctx.workspace.join("src");
+    fs::create_dir_all(&src_dir).expect("src dir");
+    for i in 0..64 {
+        fs::write(
+            src_dir.join(format!("file_{i:02}.bin")),
+            vec![i as u8; 64 * 1024],
+        )
+        .expect("write source file");
+    }
+
+    let count_dials = |args: &[&str]| {
+        let mut cli_cmd = Command::new(&ctx.cli_bin);
+        cli_cmd
+            .arg("--config-dir")
+            .arg(&ctx.config_dir)
+            .arg("copy")
+            .arg("--trace-data-plane")
+            .args(args);
+        let output = run_with_timeout(cli_cmd, Duration::from_secs(60));
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        assert!(output.status.success(), "blit failed: {stderr}");
+        stderr.matches("[data-plane-client] connecting to").count()
+    };
+
+    // Push: this CLI is the SOURCE initiator and dials the grant.
+    let src_arg = format!("{}/", src_dir.display());
+    let dest_remote = format!("127.0.0.1:{}:/test/pinned/", ctx.daemon_port);
+    assert_eq!(
+        count_dials(&[
+            "--streams",
+            "4",
+            "--chunk-size",
+            "1Mi",
+            &src_arg,
+            &dest_remote
+        ]),
+        4
+    );
+    assert!(ctx.module_dir.join("pinned/file_63.bin").exists());
+
+    // Pull: the daemon SOURCE grants the pinned count; this DESTINATION
+    // initiator dials it.
+    let pull_dest = ctx.workspace.join("pulled");
+    let pull_src = format!("127.0.0.1:{}:/test/pinned/", ctx.daemon_port);
+    let pull_dest_arg = pull_dest.display().to_string();
+    assert_eq!(
+        count_dials(&["--streams", "2", &pull_src, &pull_dest_arg]),
+        2
+    );
+    assert_eq!(
+        fs::read(pull_dest.join("file_07.bin")).expect("pulled file"),
+        vec![7u8; 64 * 1024]
+    );
+}
+
 #[test]
 fn test_pull_grpc_fallback() {
     let ctx = TestContext::new();
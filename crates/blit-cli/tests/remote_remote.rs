@@ -90,6 +90,39 @@ fn remote_to_remote_copy_delegates_directly_without_cli_byte_path() {
     );
 }
 
+/// A whole tree — nested directories, an empty directory, small files
+/// and a large one — lands on the destination daemon intact, pulled
+/// straight from the source daemon with the CLI out of the byte path.
+#[test]
+fn remote_to_remote_copy_transfers_a_tree_between_two_daemons() {
+    let ctx = DualDaemonContext::new(true);
+    let tree = ctx.module_a_dir.join("tree");
+    fs::create_dir_all(tree.join("a/b/c")).unwrap();
+    fs::create_dir_all(tree.join("empty")).unwrap();
+    fs::write(tree.join("top.txt"), b"top").unwrap();
+    fs::write(tree.join("a/one.txt"), b"one").unwrap();
+    fs::write(tree.join("a/b/two.txt"), b"two").unwrap();
+    fs::write(tree.join("a/b/c/zero.bin"), b"").unwrap();
+    fs::write(
+        tree.join("a/b/c/large.bin"),
+        (0..3 * 1024 * 1024u32)
+            .map(|i| (i % 251) as u8)
+            .collect::<Vec<_>>(),
+    )
+    .unwrap();
+
+    let counter = ctx.counter_path("tree");
+    let source = format!("127.0.0.1:{}:/test/tree/", ctx.daemon_a_port);
+    let dest = format!("127.0.0.1:{}:/test/copied/", ctx.daemon_b_port);
+    let output = run_blit(&ctx, &["copy", &source, &dest], Some(&counter));
+    assert_success(&output);
+
+    let copied = ctx.module_b_dir.join("copied");
+    assert_eq!(tree_listing(&copied), tree_listing(&tree));
+    assert!(copied.join("empty").is_dir(), "empty directory must land");
+    assert_eq!(read_counters(&counter).cli_data_plane_outbound_bytes, 0);
+}
+
 /// codex otp-10c-1 F1: the POSITIVE control for the diagnostics
 /// counter. Every other assertion in this file is
 /// `cli_data_plane_outbound_bytes == 0`, and `read_counters` maps a
@@ -258,6 +291,26 @@ fn assert_success(output: &std::process::Output) {
     }
 }
 
+/// Every entry under `root` by relative path: `None` for a directory,
+/// the content for a file.
+fn tree_listing(root: &Path) -> std::collections::BTreeMap<PathBuf, Option<Vec<u8>>> {
+    let mut out = std::collections::BTreeMap::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            let rel = path.strip_prefix(root).unwrap().to_path_buf();
+            if path.is_dir() {
+                out.insert(rel, None);
+                stack.push(path);
+            } else {
+                out.insert(rel, Some(fs::read(&path).unwrap()));
+            }
+        }
+    }
+    out
+}
+
 #[derive(Debug, Default)]
 struct CounterValues {
     cli_data_plane_outbound_bytes: u64,
@@ -293,6 +346,9 @@ impl blit_core::generated::blit_server::Blit for UnimplementedBlit {
     type DiskUsageStream = tokio_stream::wrappers::ReceiverStream<
         Result<blit_core::generated::DiskUsageEntry, tonic::Status>,
     >;
+    type ChecksumStream = tokio_stream::wrappers::ReceiverStream<
+        Result<blit_core::generated::ChecksumEntry, tonic::Status>,
+    >;
     type DelegatedPullStream = tokio_stream::wrappers::ReceiverStream<
         Result<blit_core::generated::DelegatedPullProgress, tonic::Status>,
     >;
@@ -373,6 +429,13 @@ impl blit_core::generated::blit_server::Blit for UnimplementedBlit {
         Err(tonic::Status::unimplemented("stale daemon"))
     }
 
+    async fn checksum(
+        &self,
+        _: tonic::Request<blit_core::generated::ChecksumRequest>,
+    ) -> Result<tonic::Response<Self::ChecksumStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("stale daemon"))
+    }
+
     async fn delegated_pull(
         &self,
         _: tonic::Request<blit_core::generated::DelegatedPullRequest>,
@@ -380,6 +443,20 @@ impl blit_core::generated::blit_server::Blit for UnimplementedBlit {
         Err(tonic::Status::unimplemented("stale daemon"))
     }
 
+    async fn capabilities(
+        &self,
+        _: tonic::Request<blit_core::generated::CapabilitiesRequest>,
+    ) -> Result<tonic::Response<blit_core::generated::CapabilitiesResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("stale daemon"))
+    }
+
+    async fn health(
+        &self,
+        _: tonic::Request<blit_core::generated::HealthRequest>,
+    ) -> Result<tonic::Response<blit_core::generated::HealthResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("stale daemon"))
+    }
+
     async fn get_state(
         &self,
         _: tonic::Request<blit_core::generated::GetStateRequest>,
@@ -412,6 +489,9 @@ impl blit_core::generated::blit_server::Blit for RejectingTransferBlit {
     type DiskUsageStream = tokio_stream::wrappers::ReceiverStream<
         Result<blit_core::generated::DiskUsageEntry, tonic::Status>,
     >;
+    type ChecksumStream = tokio_stream::wrappers::ReceiverStream<
+        Result<blit_core::generated::ChecksumEntry, tonic::Status>,
+    >;
     type DelegatedPullStream = tokio_stream::wrappers::ReceiverStream<
         Result<blit_core::generated::DelegatedPullProgress, tonic::Status>,
     >;
@@ -512,6 +592,15 @@ impl blit_core::generated::blit_server::Blit for RejectingTransferBlit {
         ))
     }
 
+    async fn checksum(
+        &self,
+        _: tonic::Request<blit_core::generated::ChecksumRequest>,
+    ) -> Result<tonic::Response<Self::ChecksumStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "test only exercises pull_sync",
+        ))
+    }
+
     async fn delegated_pull(
         &self,
         _: tonic::Request<blit_core::generated::DelegatedPullRequest>,
@@ -521,6 +610,24 @@ impl blit_core::generated::blit_server::Blit for RejectingTransferBlit {
         ))
     }
 
+    async fn capabilities(
+        &self,
+        _: tonic::Request<blit_core::generated::CapabilitiesRequest>,
+    ) -> Result<tonic::Response<blit_core::generated::CapabilitiesResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "test only exercises pull_sync",
+        ))
+    }
+
+    async fn health(
+        &self,
+        _: tonic::Request<blit_core::generated::HealthRequest>,
+    ) -> Result<tonic::Response<blit_core::generated::HealthResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "test only exercises pull_sync",
+        ))
+    }
+
     async fn get_state(
         &self,
         _: tonic::Request<blit_core::generated::GetStateRequest>,
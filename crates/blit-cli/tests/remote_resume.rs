@@ -127,3 +127,139 @@ fn test_pull_resume_grpc_fallback() {
     let result = fs::read(dest_dir.join("grpc_resume.txt")).expect("read result file");
     assert_eq!(result, server_content, "file should have server content");
 }
+
+/// A half-present file over the forced gRPC carrier resumes block-wise:
+/// only the missing half crosses the wire, in both directions. The
+/// in-stream carrier shares the session's resume phase with the data
+/// plane (block hashes up, changed blocks down), clamped to its 2 MiB
+/// block ceiling.
+#[test]
+fn test_resume_half_present_file_over_grpc_fallback() {
+    const MIB: usize = 1024 * 1024;
+    let ctx = TestContext::new();
+    let content: Vec<u8> = (0..4 * MIB).map(|i| (i % 251) as u8).collect();
+    let half = &content[..2 * MIB];
+
+    let run = |args: &[&str]| {
+        let mut cli_cmd = Command::new(&ctx.cli_bin);
+        cli_cmd
+            .arg("--config-dir")
+            .arg(&ctx.config_dir)
+            .arg("copy")
+            .arg("--resume")
+            .arg("--force-grpc")
+            .args(args);
+        let output = run_with_timeout(cli_cmd, Duration::from_secs(60));
+        assert!(
+            output.status.success(),
+            "blit failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    let expected = format!(
+        "1 file(s), {} bytes (1 resumed block-wise) [gRPC fallback]",
+        2 * MIB
+    );
+
+    // Pull: the local destination holds the first half.
+    fs::write(ctx.module_dir.join("half.bin"), &content).expect("write server file");
+    let dest_dir = ctx.workspace.join("dest");
+    fs::create_dir_all(&dest_dir).expect("dest dir");
+    fs::write(dest_dir.join("half.bin"), half).expect("write local half");
+    let src_remote = format!("127.0.0.1:{}:/test/half.bin", ctx.daemon_port);
+    let stdout = run(&[&src_remote, &dest_dir.join("half.bin").to_string_lossy()]);
+    assert!(stdout.contains(&expected), "pull stdout:\n{stdout}");
+    assert_eq!(fs::read(dest_dir.join("half.bin")).unwrap(), content);
+
+    // Push: the daemon's module holds the first half.
+    let src_dir = ctx.workspace.join("src");
+    fs::create_dir_all(&src_dir).expect("src dir");
+    fs::write(src_dir.join("up.bin"), &content).expect("write local file");
+    fs::write(ctx.module_dir.join("up.bin"), half).expect("write remote half");
+    let dest_remote = format!("127.0.0.1:{}:/test/up.bin", ctx.daemon_port);
+    let stdout = run(&[&src_dir.join("up.bin").to_string_lossy(), &dest_remote]);
+    assert!(stdout.contains(&expected), "push stdout:\n{stdout}");
+    assert_eq!(fs::read(ctx.module_dir.join("up.bin")).unwrap(), content);
+}
+
+/// An interrupted `--resume` pull exits with the resumable code (75),
+/// says how to continue, and keeps its partial file; re-running the
+/// same command completes it. The daemon is frozen (SIGSTOP) as soon as
+/// the first bytes land, so Ctrl-C is guaranteed to catch the pull
+/// mid-file.
+#[cfg(unix)]
+#[test]
+fn interrupted_resume_pull_exits_resumable_and_a_rerun_completes() {
+    use std::process::Stdio;
+    use std::time::Instant;
+    use wait_timeout::ChildExt;
+
+    let signal = |pid: u32, sig: &str| {
+        let status = Command::new("kill")
+            .arg(format!("-{sig}"))
+            .arg(pid.to_string())
+            .status()
+            .expect("run kill");
+        assert!(status.success(), "kill -{sig} {pid}");
+    };
+
+    let ctx = TestContext::new();
+    let dest_dir = ctx.workspace.join("dest");
+    fs::create_dir_all(&dest_dir).expect("dest dir");
+    let content: Vec<u8> = (0..64 * 1024 * 1024u32).map(|i| (i % 251) as u8).collect();
+    fs::write(ctx.module_dir.join("big.bin"), &content).expect("write server file");
+
+    let src_remote = format!("127.0.0.1:{}:/test/", ctx.daemon_port);
+    let pull = || {
+        let mut cmd = Command::new(&ctx.cli_bin);
+        cmd.arg("--config-dir")
+            .arg(&ctx.config_dir)
+            .arg("copy")
+            .arg("--resume")
+            .arg(&src_remote)
+            .arg(&dest_dir);
+        cmd
+    };
+
+    let mut child = pull()
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("spawn pull");
+    let partial = dest_dir.join("big.bin");
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while fs::metadata(&partial).map_or(0, |meta| meta.len()) == 0 {
+        assert!(Instant::now() < deadline, "the pull never started writing");
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    let daemon_pid = ctx.daemon.child.as_ref().expect("daemon child").id();
+    signal(daemon_pid, "STOP");
+    signal(child.id(), "INT");
+    let status = child
+        .wait_timeout(Duration::from_secs(30))
+        .expect("wait for pull");
+    signal(daemon_pid, "CONT");
+    let Some(status) = status else {
+        let _ = child.kill();
+        panic!("an interrupted pull must exit");
+    };
+    let mut stderr = String::new();
+    std::io::Read::read_to_string(&mut child.stderr.take().unwrap(), &mut stderr).unwrap();
+    assert_eq!(status.code(), Some(75), "stderr: {stderr}");
+    assert!(stderr.contains("transfer interrupted"), "stderr: {stderr}");
+    assert!(stderr.contains("--resume"), "stderr: {stderr}");
+    let kept = fs::metadata(&partial).expect("partial file kept").len();
+    assert!(
+        kept > 0 && kept < content.len() as u64,
+        "the partial file stays in place ({kept} bytes)"
+    );
+
+    let output = run_with_timeout(pull(), Duration::from_secs(60));
+    assert!(
+        output.status.success(),
+        "resumed pull failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(fs::read(&partial).expect("read result"), content);
+}
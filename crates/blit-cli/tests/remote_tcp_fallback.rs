@@ -160,3 +160,40 @@ fn forced_grpc_push_overflows_old_upload_channel_capacity() {
         "pre-w4-2 this hung forever at entry 262,145"
     );
 }
+
+/// A control chunk above tonic's old 4 MiB default decode limit rides
+/// the in-stream carrier intact: the daemon now accepts frames up to
+/// its (64 MiB default) message ceiling instead of failing the RPC.
+#[test]
+fn forced_grpc_push_with_chunks_over_the_old_4mib_limit() {
+    let mut ctx = forced_grpc_ctx();
+
+    let src_dir = ctx.workspace.join("src");
+    fs::create_dir_all(&src_dir).expect("src dir");
+    let payload: Vec<u8> = (0..20 * 1024 * 1024u32).map(|i| (i % 251) as u8).collect();
+    fs::write(src_dir.join("big.bin"), &payload).expect("write file");
+
+    let mut cli_cmd = Command::new(&ctx.cli_bin);
+    cli_cmd
+        .arg("--config-dir")
+        .arg(&ctx.config_dir)
+        .arg("--control-chunk-size")
+        .arg("8Mi")
+        .arg("copy")
+        .arg("--yes")
+        .arg("--force-grpc")
+        .arg(format!("{}/", src_dir.display()))
+        .arg(format!("127.0.0.1:{}:/test/", ctx.daemon_port));
+    let output = run_with_timeout(cli_cmd, Duration::from_secs(120));
+
+    ctx.daemon.terminate();
+
+    assert!(
+        output.status.success(),
+        "blit failed\nstdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let landed = fs::read(ctx.module_dir.join("big.bin")).expect("read remote file");
+    assert!(landed == payload, "pushed bytes differ");
+}
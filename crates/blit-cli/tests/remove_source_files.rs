@@ -0,0 +1,69 @@
+//! `--remove-source-files`: after a successful push, each source file
+//! whose bytes were sent is deleted; directories, and files the
+//! destination already had, stay.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use filetime::{set_file_mtime, FileTime};
+
+mod common;
+use common::{run_with_timeout, TestContext};
+
+const MTIME: i64 = 1_700_000_000;
+
+fn write_settled(path: &Path, body: &[u8]) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, body).unwrap();
+    set_file_mtime(path, FileTime::from_unix_time(MTIME, 0)).unwrap();
+}
+
+#[test]
+fn push_removes_sent_files_and_keeps_directories() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    let sent = ["top.txt", "a/one.txt", "a/b/two.txt"];
+    for rel in sent {
+        write_settled(&src.join(rel), rel.as_bytes());
+    }
+    fs::create_dir_all(src.join("empty")).unwrap();
+    // Already at the destination with the same size and mtime: the
+    // push skips it, so the source copy must survive.
+    write_settled(&src.join("a/unchanged.txt"), b"same");
+    write_settled(&ctx.module_dir.join("a/unchanged.txt"), b"same");
+
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.arg("--config-dir")
+        .arg(&ctx.config_dir)
+        .arg("copy")
+        .arg("--yes")
+        .arg("--remove-source-files")
+        .arg(format!("{}/", src.display()))
+        .arg(format!("127.0.0.1:{}:/test/", ctx.daemon_port));
+    let output = run_with_timeout(cmd, Duration::from_secs(60));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "push failed\nstdout:\n{stdout}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        stdout.contains("Removed 3 source file(s)."),
+        "stdout:\n{stdout}"
+    );
+
+    for rel in sent {
+        assert_eq!(
+            fs::read(ctx.module_dir.join(rel)).unwrap(),
+            rel.as_bytes(),
+            "{rel} landed"
+        );
+        assert!(!src.join(rel).exists(), "{rel} removed from the source");
+    }
+    assert!(src.join("a/unchanged.txt").exists(), "skipped file kept");
+    for dir in ["a", "a/b", "empty"] {
+        assert!(src.join(dir).is_dir(), "{dir}/ kept");
+    }
+}
@@ -0,0 +1,73 @@
+//! `blit scan --manifest`: the same tree yields the same bytes, and a
+//! changed file changes exactly its own line.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use filetime::{set_file_mtime, FileTime};
+use tempfile::tempdir;
+
+mod common;
+use common::{cli_bin, run_with_timeout};
+
+fn manifest(root: &Path) -> String {
+    let mut cmd = Command::new(cli_bin());
+    cmd.arg("scan").arg("--manifest").arg(root);
+    let output = run_with_timeout(cmd, Duration::from_secs(60));
+    assert!(
+        output.status.success(),
+        "blit scan --manifest failed\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).expect("manifest is UTF-8")
+}
+
+fn populate(root: &Path) {
+    fs::create_dir_all(root.join("b/nested")).unwrap();
+    fs::create_dir_all(root.join("empty")).unwrap();
+    fs::write(root.join("z.txt"), b"last").unwrap();
+    fs::write(root.join("a.txt"), b"first").unwrap();
+    fs::write(root.join("b/nested/deep.bin"), vec![7u8; 300_000]).unwrap();
+    fs::write(root.join("b/space name.txt"), b"").unwrap();
+}
+
+#[test]
+fn same_tree_gives_identical_manifests_and_one_change_one_line() {
+    let tmp = tempdir().unwrap();
+    let one = tmp.path().join("one");
+    let two = tmp.path().join("two");
+    populate(&one);
+    populate(&two);
+    // Metadata that differs between copies must not reach the manifest.
+    set_file_mtime(two.join("a.txt"), FileTime::from_unix_time(1_000_000, 0)).unwrap();
+
+    let first = manifest(&one);
+    assert_eq!(first, manifest(&one), "rescanning changed the bytes");
+    assert_eq!(first, manifest(&two), "identical trees differ");
+
+    let lines: Vec<&str> = first.lines().collect();
+    assert_eq!(lines[0], "# blit-manifest v1 blake3");
+    let paths: Vec<&str> = lines[1..]
+        .iter()
+        .map(|line| line.splitn(3, "  ").nth(2).unwrap())
+        .collect();
+    assert_eq!(
+        paths,
+        ["a.txt", "b/nested/deep.bin", "b/space name.txt", "z.txt"]
+    );
+    assert!(lines[3]
+        .starts_with("af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262  0  "));
+
+    fs::write(two.join("b/nested/deep.bin"), vec![8u8; 300_000]).unwrap();
+    let changed = manifest(&two);
+    let differing: Vec<(&str, &str)> = first
+        .lines()
+        .zip(changed.lines())
+        .filter(|(a, b)| a != b)
+        .collect();
+    assert_eq!(first.lines().count(), changed.lines().count());
+    assert_eq!(differing.len(), 1, "{differing:?}");
+    assert!(differing[0].1.ends_with("  300000  b/nested/deep.bin"));
+}
@@ -141,3 +141,50 @@ fn single_file_missing_source_fails() {
         "blit copy should fail when source is missing"
     );
 }
+
+/// `--sparse` leaves the zero middle of a large file as holes and the
+/// JSON summary reports how much; `--no-sparse` reports none and the
+/// destination is fully allocated.
+#[cfg(unix)]
+#[test]
+fn sparse_flags_control_holes_and_report_them() {
+    use std::os::unix::fs::{FileExt, MetadataExt};
+
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("disk.img");
+    let len = 16 * 1024 * 1024u64;
+    let file = fs::File::create(&src).unwrap();
+    file.set_len(len).unwrap();
+    file.write_all_at(b"head", 0).unwrap();
+    file.write_all_at(b"tail", len - 4).unwrap();
+    drop(file);
+
+    let sparse_bytes = |flag: &str, dst: &std::path::Path| -> u64 {
+        let output = run_copy(&[
+            "--json",
+            flag,
+            &src.to_string_lossy(),
+            &dst.to_string_lossy(),
+        ]);
+        assert!(
+            output.status.success(),
+            "blit copy {flag} failed\nstdout:\n{}\nstderr:\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let summary: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(fs::read(dst).unwrap(), fs::read(&src).unwrap());
+        summary["sparse_bytes"].as_u64().unwrap()
+    };
+
+    let sparse_dst = tmp.path().join("sparse.img");
+    assert!(sparse_bytes("--sparse", &sparse_dst) > len / 2);
+
+    let dense_dst = tmp.path().join("dense.img");
+    assert_eq!(sparse_bytes("--no-sparse", &dense_dst), 0);
+    let meta = fs::metadata(&dense_dst).unwrap();
+    assert!(
+        meta.blocks() * 512 >= len,
+        "--no-sparse must allocate every block"
+    );
+}
@@ -0,0 +1,102 @@
+//! `--address`: the control connection and the data plane originate
+//! from the requested local address. Needs a second loopback address
+//! (127.0.0.2, present on Linux); skipped where it cannot be bound.
+
+use std::fs;
+use std::net::{IpAddr, TcpListener};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+mod common;
+use common::{cli_bin, run_with_timeout, TestContext};
+
+const SOURCE: &str = "127.0.0.2";
+
+fn second_loopback() -> Option<IpAddr> {
+    let ip: IpAddr = SOURCE.parse().unwrap();
+    TcpListener::bind((ip, 0)).ok().map(|_| ip)
+}
+
+#[test]
+fn control_connection_originates_from_address() {
+    let Some(source) = second_loopback() else {
+        eprintln!("{SOURCE} is not a local address here; skipping");
+        return;
+    };
+    // A bare listener standing in for the daemon: only the peer address
+    // of the connection matters.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let config = tempfile::tempdir().unwrap();
+    let mut cmd = Command::new(cli_bin());
+    cmd.arg("--config-dir")
+        .arg(config.path())
+        .arg("--address")
+        .arg(SOURCE)
+        .arg("--contimeout")
+        .arg("5s")
+        .arg("ls")
+        .arg(format!("127.0.0.1:{port}:/test/"));
+    let mut cli = cmd
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let (conn, peer) = listener.accept().unwrap();
+    drop(conn);
+    let _ = cli.kill();
+    let _ = cli.wait();
+    assert_eq!(peer.ip(), source);
+}
+
+#[test]
+fn push_with_address_completes_over_both_planes() {
+    if second_loopback().is_none() {
+        eprintln!("{SOURCE} is not a local address here; skipping");
+        return;
+    }
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("a.bin"), vec![5u8; 256 * 1024]).unwrap();
+
+    let mut cmd = Command::new(&ctx.cli_bin);
+    cmd.arg("--config-dir")
+        .arg(&ctx.config_dir)
+        .arg("--address")
+        .arg(SOURCE)
+        .arg("copy")
+        .arg("--yes")
+        .arg(format!("{}/", src.display()))
+        .arg(format!("127.0.0.1:{}:/test/", ctx.daemon_port));
+    let output = run_with_timeout(cmd, Duration::from_secs(60));
+    assert!(
+        output.status.success(),
+        "push with --address failed\nstdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        fs::read(ctx.module_dir.join("a.bin")).unwrap(),
+        vec![5u8; 256 * 1024]
+    );
+}
+
+#[test]
+fn address_that_is_not_local_fails_the_connect() {
+    // TEST-NET-1: never assigned to a local interface.
+    let port = common::pick_unused_port();
+    let config = tempfile::tempdir().unwrap();
+    let mut cmd = Command::new(cli_bin());
+    cmd.arg("--config-dir")
+        .arg(config.path())
+        .arg("--address")
+        .arg("192.0.2.1")
+        .arg("--contimeout")
+        .arg("5s")
+        .arg("ls")
+        .arg(format!("127.0.0.1:{port}:/test/"));
+    let output = run_with_timeout(cmd, Duration::from_secs(20));
+    assert!(!output.status.success());
+}
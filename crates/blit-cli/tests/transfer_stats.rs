@@ -0,0 +1,68 @@
+//! `--stats` / `--human-readable`: the statistics block a local copy
+//! prints after its summary carries the run's real totals.
+
+use std::fs;
+
+mod common;
+use common::copy;
+
+/// The value printed after `label:` in the stats block.
+fn stat<'a>(stdout: &'a str, label: &str) -> &'a str {
+    let prefix = format!("{label}:");
+    stdout
+        .lines()
+        .map(str::trim_start)
+        .find_map(|line| line.strip_prefix(prefix.as_str()))
+        .map(str::trim)
+        .unwrap_or_else(|| panic!("no {label:?} line in:\n{stdout}"))
+}
+
+#[test]
+fn stats_block_reports_totals_for_a_full_then_incremental_copy() {
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(src.join("sub")).unwrap();
+    fs::create_dir_all(&dst).unwrap();
+    fs::write(src.join("a.bin"), vec![1u8; 1000]).unwrap();
+    fs::write(src.join("b.bin"), vec![2u8; 2000]).unwrap();
+    fs::write(src.join("sub/c.bin"), vec![3u8; 3000]).unwrap();
+
+    let output = copy(&["--stats"], &src, &dst);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Transfer statistics:"), "{stdout}");
+    assert_eq!(stat(&stdout, "Files examined"), "3");
+    assert_eq!(stat(&stdout, "Files transferred"), "3");
+    assert_eq!(stat(&stdout, "Total file size"), "6000 bytes");
+    assert_eq!(stat(&stdout, "Transferred size"), "6000 bytes");
+    assert_eq!(stat(&stdout, "Unchanged size"), "0 bytes");
+    assert_eq!(stat(&stdout, "Speedup"), "1.00");
+    assert!(stat(&stdout, "Throughput").ends_with("bytes/s"), "{stdout}");
+
+    // Only the resized file moves on the second run; the block splits
+    // the source size into transferred and unchanged.
+    fs::write(src.join("b.bin"), vec![4u8; 500]).unwrap();
+    let output = copy(&["--stats", "--human-readable"], &src, &dst);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stat(&stdout, "Files examined"), "3");
+    assert_eq!(stat(&stdout, "Files transferred"), "1");
+    assert_eq!(stat(&stdout, "Total file size"), "4.39 KiB");
+    assert_eq!(stat(&stdout, "Transferred size"), "500 B");
+    assert_eq!(stat(&stdout, "Unchanged size"), "3.91 KiB");
+    assert_eq!(stat(&stdout, "Speedup"), "9.00");
+    assert!(stat(&stdout, "Throughput").ends_with("/s"), "{stdout}");
+}
+
+#[test]
+fn stats_is_off_by_default() {
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(&src).unwrap();
+    fs::create_dir_all(&dst).unwrap();
+    fs::write(src.join("a.bin"), b"hello").unwrap();
+
+    let output = copy(&[], &src, &dst);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("Transfer statistics:"), "{stdout}");
+}
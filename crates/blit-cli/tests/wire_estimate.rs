@@ -0,0 +1,91 @@
+//! `--dry-run`'s on-the-wire estimate: the planned payloads' framed
+//! size (record headers, tar member headers and padding, content)
+//! predicts what a push of the same tree sends over the data plane.
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+mod common;
+use common::{assert_success, blit, write_fixture, TestContext};
+
+/// Many small files — tar-shard material, where framing is a visible
+/// share of the bytes — one of them under a 100+ byte path, and one
+/// file large enough to travel on its own.
+fn write_shard_fixture(src: &Path) -> u64 {
+    write_fixture(src);
+    // The shared fixture's `top.txt` and `sub/nested.txt`.
+    let mut total = 9;
+    let deep = src.join(format!("deep/{}", "d".repeat(90)));
+    fs::create_dir_all(&deep).unwrap();
+    fs::create_dir_all(src.join("small")).unwrap();
+    for i in 0..40u64 {
+        let len = 100 + i * 37;
+        fs::write(
+            src.join(format!("small/f{i:02}.txt")),
+            vec![b'a' + (i % 26) as u8; len as usize],
+        )
+        .unwrap();
+        total += len;
+    }
+    fs::write(deep.join("long-named.txt"), b"long path").unwrap();
+    total += 9;
+    let large = vec![0x5au8; 2 * 1024 * 1024];
+    fs::write(src.join("large.bin"), &large).unwrap();
+    total + large.len() as u64
+}
+
+fn blit_json(ctx: &TestContext, args: &[&str]) -> Value {
+    let output = blit(ctx, args);
+    assert_success(&output);
+    serde_json::from_slice(&output.stdout).expect("json summary")
+}
+
+#[test]
+fn dry_run_wire_estimate_predicts_the_bytes_a_push_sends() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    let file_bytes = write_shard_fixture(&src);
+    let src_arg = format!("{}/", src.display());
+
+    let planned = blit_json(
+        &ctx,
+        &[
+            "copy",
+            "--dry-run",
+            "--json",
+            &src_arg,
+            &format!("{}/", ctx.workspace.join("dst").display()),
+        ],
+    );
+    let estimate = planned["wire_bytes_estimate"].as_u64().expect("estimate");
+    assert!(
+        estimate > file_bytes,
+        "estimate {estimate} leaves out framing over {file_bytes} file bytes"
+    );
+
+    let pushed = blit_json(
+        &ctx,
+        &[
+            "copy",
+            "--json",
+            &src_arg,
+            &format!("127.0.0.1:{}:/test/pushed/", ctx.daemon_port),
+        ],
+    );
+    let sent = pushed["stream_bytes"].as_u64().expect("stream bytes");
+    assert!(
+        sent > file_bytes,
+        "push sent {sent} for {file_bytes} file bytes"
+    );
+
+    // The streams count payload and tar bytes but not the few record
+    // header bytes the estimate adds, and the push may group shards
+    // differently; both stay well inside 2%.
+    let diff = estimate.abs_diff(sent);
+    assert!(
+        diff * 50 <= sent,
+        "estimate {estimate} vs {sent} bytes sent ({diff} apart)"
+    );
+}
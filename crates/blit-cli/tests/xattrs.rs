@@ -0,0 +1,164 @@
+//! `--xattrs`: extended attributes carried across, filtered by
+//! namespace. Only `user.*` crosses by default; `--xattrs-all` and
+//! `--xattrs-include` admit more, `--xattrs-exclude` holds one back, and
+//! Blit's own `user.blit.*` never transfers. Checked locally and through
+//! a push, where the daemon destination applies them. A filesystem
+//! without user xattrs skips the test.
+#![cfg(target_os = "linux")]
+
+use std::fs;
+use std::path::Path;
+
+mod common;
+use common::{assert_success, blit, write_fixture, TestContext};
+
+/// Writes the fixture and tags it, or `false` (with a note) when the
+/// filesystem refuses user xattrs.
+fn write_tagged_fixture(src: &Path) -> bool {
+    write_fixture(src);
+    if let Err(err) = xattr::set(src.join("top.txt"), "user.origin", b"camera") {
+        eprintln!("skipping: filesystem refuses user xattrs: {err}");
+        return false;
+    }
+    xattr::set(src.join("top.txt"), "user.private", b"secret").unwrap();
+    xattr::set(src.join("top.txt"), "user.blit.stat", b"0 0 644").unwrap();
+    xattr::set(src.join("sub"), "user.origin", b"folder").unwrap();
+    true
+}
+
+fn get(path: &Path, name: &str) -> Option<Vec<u8>> {
+    xattr::get(path, name).unwrap()
+}
+
+fn dir(path: &Path) -> String {
+    format!("{}/", path.display())
+}
+
+fn copy(ctx: &TestContext, flags: &[&str], src: &str, dst: &str) {
+    let mut args = vec!["copy", "--yes"];
+    args.extend_from_slice(flags);
+    args.extend_from_slice(&[src, dst]);
+    assert_success(&blit(ctx, &args));
+}
+
+#[test]
+fn local_copy_without_xattrs_leaves_them_behind() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    let dst = ctx.workspace.join("dst");
+    if !write_tagged_fixture(&src) {
+        return;
+    }
+
+    copy(&ctx, &[], &dir(&src), &dir(&dst));
+
+    assert_eq!(fs::read(dst.join("top.txt")).unwrap(), b"top");
+    assert_eq!(get(&dst.join("top.txt"), "user.origin"), None);
+}
+
+#[test]
+fn local_copy_carries_user_xattrs_less_exclusions() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    let dst = ctx.workspace.join("dst");
+    if !write_tagged_fixture(&src) {
+        return;
+    }
+
+    copy(
+        &ctx,
+        &["--xattrs-exclude", "user.private"],
+        &dir(&src),
+        &dir(&dst),
+    );
+
+    let top = dst.join("top.txt");
+    assert_eq!(get(&top, "user.origin").as_deref(), Some(&b"camera"[..]));
+    assert_eq!(get(&top, "user.private"), None);
+    assert_eq!(get(&top, "user.blit.stat"), None);
+    assert_eq!(
+        get(&dst.join("sub"), "user.origin").as_deref(),
+        Some(&b"folder"[..])
+    );
+}
+
+#[test]
+fn push_carries_xattrs_to_the_daemon() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    if !write_tagged_fixture(&src) {
+        return;
+    }
+
+    copy(
+        &ctx,
+        &["-X"],
+        &dir(&src),
+        &format!("127.0.0.1:{}:/test/pushed/", ctx.daemon_port),
+    );
+
+    let pushed = ctx.module_dir.join("pushed");
+    assert_eq!(
+        get(&pushed.join("top.txt"), "user.origin").as_deref(),
+        Some(&b"camera"[..])
+    );
+    assert_eq!(
+        get(&pushed.join("top.txt"), "user.private").as_deref(),
+        Some(&b"secret"[..])
+    );
+    assert_eq!(get(&pushed.join("top.txt"), "user.blit.stat"), None);
+    assert_eq!(
+        get(&pushed.join("sub"), "user.origin").as_deref(),
+        Some(&b"folder"[..])
+    );
+}
+
+#[test]
+fn pull_carries_xattrs_from_the_daemon() {
+    let ctx = TestContext::new();
+    if !write_tagged_fixture(&ctx.module_dir.join("tagged")) {
+        return;
+    }
+    let dst = ctx.workspace.join("pulled");
+
+    copy(
+        &ctx,
+        &["--xattrs"],
+        &format!("127.0.0.1:{}:/test/tagged/", ctx.daemon_port),
+        &dir(&dst),
+    );
+
+    assert_eq!(
+        get(&dst.join("top.txt"), "user.origin").as_deref(),
+        Some(&b"camera"[..])
+    );
+}
+
+#[test]
+fn malformed_namespace_is_rejected_before_copying() {
+    let ctx = TestContext::new();
+    let src = ctx.workspace.join("src");
+    let dst = ctx.workspace.join("dst");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("top.txt"), b"top").unwrap();
+
+    let output = blit(
+        &ctx,
+        &[
+            "copy",
+            "--yes",
+            "--xattrs-include",
+            "user,trusted",
+            &dir(&src),
+            &dir(&dst),
+        ],
+    );
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("invalid xattr namespace"),
+        "stderr:\n{stderr}"
+    );
+    assert!(!dst.join("top.txt").exists());
+}
@@ -20,6 +20,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     tonic_prost_build::configure()
         .build_server(true)
         .build_client(true)
+        // `SessionOpen` dwarfs every other `TransferFrame` variant; it is
+        // sent once per session, so it rides boxed.
+        .boxed(".blit.v2.TransferFrame.frame.open")
         .compile_protos(&[proto_file.as_path()], &[proto_dir.as_path()])?;
     Ok(())
 }
@@ -22,6 +22,24 @@ const MB: usize = 1024 * KB;
 /// throughput collapses regardless of memory pressure.
 pub const DATA_PLANE_BUFFER_FLOOR: usize = 64 * KB;
 
+/// Smallest `--block-size` override accepted.
+pub const MIN_BLOCK_SIZE_OVERRIDE: usize = 4 * KB;
+
+/// Largest `--block-size` override accepted — the resume copy's own
+/// block ceiling (`copy::file_copy::resume::MAX_BLOCK_SIZE`).
+pub const MAX_BLOCK_SIZE_OVERRIDE: usize = 64 * MB;
+
+/// Check a user-supplied block size against
+/// [`MIN_BLOCK_SIZE_OVERRIDE`]..=[`MAX_BLOCK_SIZE_OVERRIDE`].
+pub fn validate_block_size(bytes: u64) -> eyre::Result<usize> {
+    match usize::try_from(bytes) {
+        Ok(size) if (MIN_BLOCK_SIZE_OVERRIDE..=MAX_BLOCK_SIZE_OVERRIDE).contains(&size) => Ok(size),
+        _ => eyre::bail!(
+            "block size {bytes} is outside {MIN_BLOCK_SIZE_OVERRIDE}..={MAX_BLOCK_SIZE_OVERRIDE} bytes"
+        ),
+    }
+}
+
 /// Available system memory in bytes via sysinfo. `System::new()` +
 /// `refresh_memory()` reads only the memory counters (`new_all()`
 /// would walk the whole process table for the same answer).
@@ -50,6 +68,8 @@ fn sanitize_available_memory(raw: u64) -> u64 {
 pub struct BufferSizer {
     max_buffer_size: usize,
     min_buffer_size: usize,
+    /// `--block-size`: replaces the derived size outright.
+    fixed_size: Option<usize>,
     cached_available_memory: OnceCell<u64>,
 }
 
@@ -58,12 +78,26 @@ impl BufferSizer {
         BufferSizer {
             max_buffer_size: 16 * MB, // 16MB max
             min_buffer_size: MB,      // 1MB min
+            fixed_size: None,
             cached_available_memory: OnceCell::new(),
         }
     }
 
+    /// A sizer that always answers `size` (`--block-size`). The caller
+    /// validates it with [`validate_block_size`].
+    pub fn with_block_size(size: usize) -> Self {
+        BufferSizer {
+            fixed_size: Some(size),
+            ..Self::new()
+        }
+    }
+
     /// Calculate optimal buffer size based on file size and available memory
     pub fn calculate_buffer_size(&self, file_size: u64, is_network: bool) -> usize {
+        if let Some(size) = self.fixed_size {
+            return size;
+        }
+
         // Get or cache available memory
         let available_memory = *self
             .cached_available_memory
@@ -142,6 +176,24 @@ mod tests {
         assert!(large_buf >= 8 * MB);
         assert!(large_buf <= sizer.max_buffer_size);
     }
+
+    #[test]
+    fn block_size_override_replaces_the_derived_size() {
+        let sizer = BufferSizer::with_block_size(32 * KB);
+        assert_eq!(sizer.calculate_buffer_size(MB as u64, false), 32 * KB);
+        assert_eq!(
+            sizer.calculate_buffer_size(2 * 1024 * MB as u64, true),
+            32 * KB
+        );
+    }
+
+    #[test]
+    fn block_size_validation_bounds() {
+        assert_eq!(validate_block_size(4096).unwrap(), 4 * KB);
+        assert_eq!(validate_block_size(64 * MB as u64).unwrap(), 64 * MB);
+        assert!(validate_block_size(4095).is_err());
+        assert!(validate_block_size(64 * MB as u64 + 1).is_err());
+    }
 }
 
 // ============================================================================
@@ -303,7 +355,10 @@ impl BufferPool {
     /// budget below `2 × streams` buffers could deadlock a stream
     /// against its own first buffer. The liveness floor
     /// `budget ≥ buffer_size × streams × 2` therefore always wins over
-    /// the cap.
+    /// the cap. The read-ahead sender (`send_file_read_ahead`) queues
+    /// more than two per stream, but only its reader task acquires and
+    /// its writer only releases, so a deeper queue waits on the budget
+    /// without deadlocking.
     pub fn for_data_plane(chunk_bytes: usize, streams: usize) -> Self {
         let (buffer_size, pool_size, budget) =
             data_plane_pool_params(chunk_bytes, streams, available_memory_bytes());
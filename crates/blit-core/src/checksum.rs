@@ -13,6 +13,7 @@ pub enum ChecksumType {
     Blake3,
     XxHash3,
     Md5, // For compatibility
+    Sha256,
 }
 
 /// CHAR_OFFSET constant from rsync (for compatibility)
@@ -136,13 +137,17 @@ pub fn strong_checksum(
             warn!("MD5 is cryptographically broken; prefer Blake3 (default) or SHA-256");
             Ok(md5::compute(data).to_vec())
         }
+        ChecksumType::Sha256 => {
+            use sha2::Digest;
+            Ok(sha2::Sha256::digest(data).to_vec())
+        }
     }
 }
 
 /// Buffer size for streaming hashing — the one place this decision
 /// lives (w7-4: it was restated at five sites, including a 256 KiB
 /// stack array in the daemon's `build_file_header`).
-const HASH_READ_BUF_BYTES: usize = 256 * 1024;
+pub const HASH_READ_BUF_BYTES: usize = 256 * 1024;
 
 /// Hash everything `reader` yields with the given algorithm, owning
 /// the read loop and buffer size. `hash_file`, `partial_hash_first_last`'s
@@ -185,10 +190,27 @@ pub fn hash_reader(reader: &mut dyn Read, ty: ChecksumType) -> Result<Vec<u8>> {
             }
             Ok(ctx.finalize().to_vec())
         }
+        ChecksumType::Sha256 => {
+            use sha2::Digest;
+            let mut hasher = sha2::Sha256::new();
+            loop {
+                let n = reader.read(&mut buf)?;
+                if n == 0 {
+                    break;
+                }
+                hasher.update(&buf[..n]);
+            }
+            Ok(hasher.finalize().to_vec())
+        }
     }
 }
 
 /// Hash a whole file with the given algorithm.
+///
+/// The digest depends on the file's bytes alone: every algorithm runs
+/// unkeyed and unseeded, and multi-byte results are big-endian, so the
+/// same content hashes identically on any machine, run, or platform.
+/// `scan --manifest` relies on this for cross-machine diffs.
 pub fn hash_file(path: &Path, ty: ChecksumType) -> Result<Vec<u8>> {
     let mut f = File::open(path).with_context(|| format!("open {}", path.display()))?;
     hash_reader(&mut f, ty)
@@ -199,25 +221,49 @@ pub fn hash_file(path: &Path, ty: ChecksumType) -> Result<Vec<u8>> {
 pub fn partial_hash_first_last(path: &Path, bytes: usize) -> Result<Vec<u8>> {
     let mut f = File::open(path).with_context(|| format!("open {}", path.display()))?;
     let len = f.metadata()?.len();
-    if len as usize <= bytes * 2 {
+    partial_hash_reader(&mut f, len, bytes)
+}
+
+/// [`partial_hash_first_last`] over an open reader of `len` bytes.
+/// Both windows stream through one `HASH_READ_BUF_BYTES` buffer, so
+/// memory stays bounded whatever `bytes` is.
+pub fn partial_hash_reader<R: Read + Seek>(
+    reader: &mut R,
+    len: u64,
+    bytes: usize,
+) -> Result<Vec<u8>> {
+    if len <= bytes as u64 * 2 {
         // Whole-file Blake3 — identical to hash_reader's Blake3 arm.
-        return hash_reader(&mut f, ChecksumType::Blake3);
+        return hash_reader(reader, ChecksumType::Blake3);
     }
+    let mut buf = vec![0u8; HASH_READ_BUF_BYTES.min(bytes)];
     let mut hasher = blake3::Hasher::new();
-    let mut first = vec![0u8; bytes];
-    f.read_exact(&mut first)?;
     hasher.update(b"FIRST");
-    hasher.update(&first);
+    hash_exact(reader, bytes, &mut buf, &mut hasher)?;
 
-    f.seek(SeekFrom::End(-(bytes as i64)))?;
-    let mut last = vec![0u8; bytes];
-    f.read_exact(&mut last)?;
+    reader.seek(SeekFrom::End(-(bytes as i64)))?;
     hasher.update(b"LAST");
-    hasher.update(&last);
+    hash_exact(reader, bytes, &mut buf, &mut hasher)?;
     hasher.update(&len.to_le_bytes());
     Ok(hasher.finalize().as_bytes().to_vec())
 }
 
+/// Feed exactly `count` bytes of `reader` to `hasher`, `buf` at a time.
+fn hash_exact(
+    reader: &mut dyn Read,
+    mut count: usize,
+    buf: &mut [u8],
+    hasher: &mut blake3::Hasher,
+) -> Result<()> {
+    while count > 0 {
+        let n = count.min(buf.len());
+        reader.read_exact(&mut buf[..n])?;
+        hasher.update(&buf[..n]);
+        count -= n;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -247,6 +293,31 @@ mod tests {
         }
     }
 
+    /// Digests are compared across machines (`scan --manifest`), so
+    /// pin them to the published test vectors for "abc".
+    #[test]
+    fn whole_file_digests_are_stable() {
+        let tmp = tempfile::NamedTempFile::new().expect("tmp file");
+        std::fs::write(tmp.path(), b"abc").expect("write");
+        let hex = |ty| {
+            hash_file(tmp.path(), ty)
+                .expect("hash_file")
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect::<String>()
+        };
+        assert_eq!(
+            hex(ChecksumType::Blake3),
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+        assert_eq!(
+            hex(ChecksumType::Sha256),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(hex(ChecksumType::Md5), "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(hex(ChecksumType::XxHash3), "78af5f94892f3950");
+    }
+
     #[test]
     fn test_rolling_checksum_basic() {
         let data = b"Hello, World!";
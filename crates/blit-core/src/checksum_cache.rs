@@ -0,0 +1,358 @@
+//! Persistent Blake3 cache for checksum-mode compares (`--checksum-cache`).
+//!
+//! A checksum transfer hashes every same-size file on both ends, every
+//! run, even when nothing changed since the last one. The cache keeps
+//! each file's whole-file Blake3 keyed by (absolute path, size, mtime)
+//! in `checksum_cache.json` under the config dir; a later run that finds
+//! the same key reuses the digest instead of reading the file. A key
+//! whose size or mtime moved is dropped on lookup, and the fresh digest
+//! replaces it.
+//!
+//! Like rsync's quick check, the key trusts mtime: an edit that keeps
+//! both size and mtime (a tool restoring timestamps) is invisible to it.
+//! A file modified within [`RACY_WINDOW`] of being hashed is not cached
+//! at all, so a write landing in the same mtime tick as the hash cannot
+//! pin a stale digest.
+//!
+//! The cache is process-wide: the CLI [`install`]s it for a transfer
+//! that asked for it and [`flush`]es it afterwards. Consumers — the
+//! compare predicates, the session's destination diff, the checksumming
+//! source — consult [`active`] and hash directly when it is `None`.
+
+use std::collections::HashMap;
+use std::fs::{self, File, Metadata};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use eyre::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::checksum::{self, ChecksumType};
+
+/// File name under the config dir.
+pub const CACHE_FILENAME: &str = "checksum_cache.json";
+
+/// Bump when the entry shape or digest meaning changes; a cache file
+/// with another version loads empty.
+const CACHE_VERSION: u32 = 1;
+
+/// Files modified this recently are hashed but not cached (see the
+/// module docs).
+pub const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CacheEntry {
+    size: u64,
+    mtime_seconds: i64,
+    mtime_nanos: u32,
+    /// Lowercase hex Blake3 of the whole file.
+    blake3: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheState {
+    version: u32,
+    entries: HashMap<String, CacheEntry>,
+}
+
+/// Lookup counters, for `--verbose` reporting and tests.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub invalidated: u64,
+}
+
+/// The loaded cache. Lookups and inserts lock one map briefly; hashing
+/// itself happens outside the lock.
+#[derive(Debug)]
+pub struct ChecksumCache {
+    path: PathBuf,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+    dirty: AtomicBool,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    invalidated: AtomicU64,
+}
+
+impl ChecksumCache {
+    /// Load the cache at `path`. A missing file is an empty cache; an
+    /// unreadable or foreign-version one is discarded with a warning,
+    /// since every entry can be recomputed.
+    pub fn load(path: PathBuf) -> Self {
+        let entries = match fs::read_to_string(&path) {
+            Ok(text) => match serde_json::from_str::<CacheState>(&text) {
+                Ok(state) if state.version == CACHE_VERSION => state.entries,
+                Ok(_) => HashMap::new(),
+                Err(err) => {
+                    log::warn!(
+                        "checksum cache {} is unreadable, starting empty: {err}",
+                        path.display()
+                    );
+                    HashMap::new()
+                }
+            },
+            Err(_) => HashMap::new(),
+        };
+        Self {
+            path,
+            entries: Mutex::new(entries),
+            dirty: AtomicBool::new(false),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            invalidated: AtomicU64::new(0),
+        }
+    }
+
+    /// The file this cache loads from and saves to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            invalidated: self.invalidated.load(Ordering::Relaxed),
+        }
+    }
+
+    /// The cached digest for `path` if `meta` still matches the key it
+    /// was stored under. A stale entry is dropped.
+    pub fn lookup(&self, path: &Path, meta: &Metadata) -> Option<Vec<u8>> {
+        let key = cache_key(path)?;
+        let (size, mtime_seconds, mtime_nanos) = file_key(meta)?;
+        let mut entries = self.lock();
+        let Some(entry) = entries.get(&key) else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        if entry.size == size
+            && entry.mtime_seconds == mtime_seconds
+            && entry.mtime_nanos == mtime_nanos
+        {
+            if let Ok(hash) = blake3::Hash::from_hex(&entry.blake3) {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Some(hash.as_bytes().to_vec());
+            }
+        }
+        entries.remove(&key);
+        self.dirty.store(true, Ordering::Relaxed);
+        self.invalidated.fetch_add(1, Ordering::Relaxed);
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        None
+    }
+
+    /// Record `digest` for `path` under the key `meta` describes.
+    /// `meta` must be the metadata read BEFORE hashing, so a write that
+    /// races the hash leaves a key the next lookup rejects.
+    pub fn store(&self, path: &Path, meta: &Metadata, digest: &[u8]) {
+        let Some(key) = cache_key(path) else {
+            return;
+        };
+        let Some((size, mtime_seconds, mtime_nanos)) = file_key(meta) else {
+            return;
+        };
+        let Ok(bytes) = <[u8; blake3::OUT_LEN]>::try_from(digest) else {
+            return;
+        };
+        let racy = meta
+            .modified()
+            .ok()
+            .and_then(|mtime| SystemTime::now().duration_since(mtime).ok())
+            .is_none_or(|age| age < RACY_WINDOW);
+        if racy {
+            return;
+        }
+        let entry = CacheEntry {
+            size,
+            mtime_seconds,
+            mtime_nanos,
+            blake3: blake3::Hash::from(bytes).to_hex().to_string(),
+        };
+        if self.lock().insert(key, entry.clone()).as_ref() != Some(&entry) {
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Write the cache back if anything changed since it was loaded.
+    /// Written to a sibling temp file and renamed over the original, so
+    /// an interrupted save never leaves a truncated cache.
+    pub fn save(&self) -> Result<()> {
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+        let state = CacheState {
+            version: CACHE_VERSION,
+            entries: self.lock().clone(),
+        };
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        let mut file = File::create(&tmp)
+            .with_context(|| format!("create checksum cache {}", tmp.display()))?;
+        file.write_all(serde_json::to_string(&state)?.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&tmp, &self.path)
+            .with_context(|| format!("replace checksum cache {}", self.path.display()))?;
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, CacheEntry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Absolute path as the cache key; `None` for a non-UTF-8 path, which
+/// is simply never cached.
+fn cache_key(path: &Path) -> Option<String> {
+    std::path::absolute(path).ok()?.to_str().map(str::to_string)
+}
+
+fn file_key(meta: &Metadata) -> Option<(u64, i64, u32)> {
+    if !meta.is_file() {
+        return None;
+    }
+    let mtime = meta.modified().ok()?;
+    let (seconds, nanos) = match mtime.duration_since(UNIX_EPOCH) {
+        Ok(after) => (after.as_secs() as i64, after.subsec_nanos()),
+        Err(before) => {
+            let before = before.duration();
+            (-(before.as_secs() as i64), before.subsec_nanos())
+        }
+    };
+    Some((meta.len(), seconds, nanos))
+}
+
+static ACTIVE: OnceLock<ChecksumCache> = OnceLock::new();
+
+/// Install the process-wide cache. A second call is silently ignored
+/// (same contract as [`crate::remote::connect::set_connect_timeout`]).
+pub fn install(cache: ChecksumCache) {
+    let _ = ACTIVE.set(cache);
+}
+
+/// The installed cache, if any.
+pub fn active() -> Option<&'static ChecksumCache> {
+    ACTIVE.get()
+}
+
+/// Save the installed cache, if any.
+pub fn flush() -> Result<()> {
+    match active() {
+        Some(cache) => cache.save(),
+        None => Ok(()),
+    }
+}
+
+/// Whole-file Blake3 of `path` through `cache`: a hit reads nothing, a
+/// miss hashes and records the digest.
+pub fn hash_file_with(cache: Option<&ChecksumCache>, path: &Path) -> Result<Vec<u8>> {
+    let Some(cache) = cache else {
+        return checksum::hash_file(path, ChecksumType::Blake3);
+    };
+    let meta = fs::metadata(path).with_context(|| format!("stat {}", path.display()))?;
+    if let Some(digest) = cache.lookup(path, &meta) {
+        return Ok(digest);
+    }
+    let digest = checksum::hash_file(path, ChecksumType::Blake3)?;
+    cache.store(path, &meta, &digest);
+    Ok(digest)
+}
+
+/// Hash `path` and record the digest in `cache` without a lookup — for
+/// a caller that already looked up and missed.
+pub fn hash_and_store(cache: Option<&ChecksumCache>, path: &Path) -> Result<Vec<u8>> {
+    let meta = fs::metadata(path).with_context(|| format!("stat {}", path.display()))?;
+    let digest = checksum::hash_file(path, ChecksumType::Blake3)?;
+    if let Some(cache) = cache {
+        cache.store(path, &meta, &digest);
+    }
+    Ok(digest)
+}
+
+/// [`hash_file_with`] through the installed cache.
+pub fn hash_file(path: &Path) -> Result<Vec<u8>> {
+    hash_file_with(active(), path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filetime::{set_file_mtime, FileTime};
+
+    /// A file old enough to be cached.
+    fn settled(path: &Path, body: &[u8], mtime: i64) {
+        fs::write(path, body).unwrap();
+        set_file_mtime(path, FileTime::from_unix_time(mtime, 0)).unwrap();
+    }
+
+    #[test]
+    fn populated_then_reused_then_invalidated() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("data.bin");
+        settled(&file, b"first contents", 1_700_000_000);
+        let cache_path = tmp.path().join(CACHE_FILENAME);
+
+        let cache = ChecksumCache::load(cache_path.clone());
+        let digest = hash_file_with(Some(&cache), &file).unwrap();
+        assert_eq!(digest, blake3::hash(b"first contents").as_bytes().to_vec());
+        assert_eq!(cache.len(), 1);
+        cache.save().unwrap();
+
+        // A fresh process reuses the stored digest without reading.
+        let reloaded = ChecksumCache::load(cache_path.clone());
+        assert_eq!(hash_file_with(Some(&reloaded), &file).unwrap(), digest);
+        assert_eq!(
+            reloaded.stats(),
+            CacheStats {
+                hits: 1,
+                misses: 0,
+                invalidated: 0
+            }
+        );
+
+        // Same size, new mtime: the entry is dropped and recomputed.
+        settled(&file, b"other contents", 1_700_000_100);
+        let fresh = hash_file_with(Some(&reloaded), &file).unwrap();
+        assert_eq!(fresh, blake3::hash(b"other contents").as_bytes().to_vec());
+        assert_eq!(reloaded.stats().invalidated, 1);
+        reloaded.save().unwrap();
+        let again = ChecksumCache::load(cache_path);
+        assert_eq!(hash_file_with(Some(&again), &file).unwrap(), fresh);
+        assert_eq!(again.stats().hits, 1);
+    }
+
+    #[test]
+    fn recently_modified_files_are_not_cached() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("hot.bin");
+        fs::write(&file, b"just written").unwrap();
+        let cache = ChecksumCache::load(tmp.path().join(CACHE_FILENAME));
+        hash_file_with(Some(&cache), &file).unwrap();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn foreign_version_or_garbage_loads_empty() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join(CACHE_FILENAME);
+        fs::write(&path, r#"{"version":999,"entries":{}}"#).unwrap();
+        assert!(ChecksumCache::load(path.clone()).is_empty());
+        fs::write(&path, "not json").unwrap();
+        assert!(ChecksumCache::load(path).is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+//! `--chmod <spec>` permission overrides.
+//!
+//! The spec is rsync's: comma-separated clauses, each optionally
+//! prefixed `D` (directories only) or `F` (files only), and either an
+//! octal mode (`644`, `2775`) that replaces the permission bits or a
+//! symbolic `chmod(1)` clause (`g+r`, `u=rwX,o-w`, `a+t`). Clauses apply
+//! in order.
+//!
+//! The SOURCE rewrites each manifest header's mode before it leaves the
+//! scan (see `remote::transfer::source::ChmodSource`), and the spec
+//! rides `SessionOpen.chmod` (contract v20) so a pull's daemon source
+//! applies it too. An entry with no Unix mode (`permissions == 0`, a
+//! Windows source) is left alone: there is nothing to adjust.
+
+use eyre::{bail, Result};
+
+/// Permission bits a spec may touch; everything above (the file-type
+/// bits `st_mode` carries) passes through untouched.
+const MODE_BITS: u32 = 0o7777;
+
+/// A parsed `--chmod` spec. Parse with [`ChmodSpec::parse`], apply
+/// with [`ChmodSpec::apply`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChmodSpec {
+    clauses: Vec<Clause>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Applies {
+    All,
+    Dirs,
+    Files,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Clause {
+    applies: Applies,
+    action: Action,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Action {
+    /// Octal: replace every permission bit.
+    Set(u32),
+    /// Symbolic: the `who` mask, then each `(op, perms)` in order.
+    Symbolic { who: u32, ops: Vec<(char, String)> },
+}
+
+impl ChmodSpec {
+    /// Parse a comma-separated spec. Fails on an empty spec or any
+    /// clause that is neither a valid octal nor a valid symbolic mode.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut clauses = Vec::new();
+        for raw in spec.split(',') {
+            let clause = raw.trim();
+            if clause.is_empty() {
+                bail!("invalid --chmod '{spec}': empty clause");
+            }
+            clauses.push(
+                parse_clause(clause).map_err(|e| eyre::eyre!("invalid --chmod '{spec}': {e}"))?,
+            );
+        }
+        Ok(Self { clauses })
+    }
+
+    /// `mode` with every clause applied. `is_dir` picks the `D`/`F`
+    /// clauses that apply and decides `X`. Zero (no known mode) stays
+    /// zero.
+    pub fn apply(&self, mode: u32, is_dir: bool) -> u32 {
+        if mode == 0 {
+            return 0;
+        }
+        let mut bits = mode & MODE_BITS;
+        for clause in &self.clauses {
+            match clause.applies {
+                Applies::Dirs if !is_dir => continue,
+                Applies::Files if is_dir => continue,
+                _ => {}
+            }
+            match &clause.action {
+                Action::Set(octal) => bits = *octal,
+                Action::Symbolic { who, ops } => {
+                    for (op, perms) in ops {
+                        let wanted = perm_bits(perms, bits, is_dir) & who;
+                        bits = match op {
+                            '+' => bits | wanted,
+                            '-' => bits & !wanted,
+                            _ => (bits & !who) | wanted,
+                        };
+                    }
+                }
+            }
+        }
+        (mode & !MODE_BITS) | bits
+    }
+}
+
+fn parse_clause(clause: &str) -> Result<Clause> {
+    let (applies, rest) = match clause.as_bytes()[0] {
+        b'D' => (Applies::Dirs, &clause[1..]),
+        b'F' => (Applies::Files, &clause[1..]),
+        _ => (Applies::All, clause),
+    };
+    if rest.is_empty() {
+        bail!("'{clause}' has no mode");
+    }
+    if rest.bytes().all(|b| b.is_ascii_digit()) {
+        let octal = u32::from_str_radix(rest, 8)
+            .ok()
+            .filter(|mode| rest.len() <= 4 && *mode <= MODE_BITS)
+            .ok_or_else(|| eyre::eyre!("'{rest}' is not an octal mode"))?;
+        return Ok(Clause {
+            applies,
+            action: Action::Set(octal),
+        });
+    }
+
+    let op_at = rest
+        .find(['+', '-', '='])
+        .ok_or_else(|| eyre::eyre!("'{rest}' has no +, - or = operator"))?;
+    let mut who = 0;
+    for c in rest[..op_at].chars() {
+        who |= match c {
+            'u' => 0o4700,
+            'g' => 0o2070,
+            'o' => 0o1007,
+            'a' => MODE_BITS,
+            other => bail!("unknown class '{other}' in '{rest}'"),
+        };
+    }
+    if who == 0 {
+        who = MODE_BITS;
+    }
+    let mut ops: Vec<(char, String)> = Vec::new();
+    for c in rest[op_at..].chars() {
+        match c {
+            '+' | '-' | '=' => ops.push((c, String::new())),
+            'r' | 'w' | 'x' | 'X' | 's' | 't' => {
+                ops.last_mut().expect("starts at an operator").1.push(c)
+            }
+            other => bail!("unknown permission '{other}' in '{rest}'"),
+        }
+    }
+    Ok(Clause {
+        applies,
+        action: Action::Symbolic { who, ops },
+    })
+}
+
+/// Every bit `perms` names, across all classes; the caller masks it to
+/// the clause's classes. `X` is execute only for a directory or an
+/// entry some class can already execute.
+fn perm_bits(perms: &str, current: u32, is_dir: bool) -> u32 {
+    perms.chars().fold(0, |bits, c| {
+        bits | match c {
+            'r' => 0o444,
+            'w' => 0o222,
+            'x' => 0o111,
+            'X' if is_dir || current & 0o111 != 0 => 0o111,
+            's' => 0o6000,
+            't' => 0o1000,
+            _ => 0,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(spec: &str, mode: u32, is_dir: bool) -> u32 {
+        ChmodSpec::parse(spec).unwrap().apply(mode, is_dir)
+    }
+
+    #[test]
+    fn octal_replaces_permission_bits_and_keeps_the_file_type() {
+        assert_eq!(apply("640", 0o100755, false), 0o100640);
+        assert_eq!(apply("2775", 0o40700, true), 0o42775);
+    }
+
+    #[test]
+    fn symbolic_clauses_adjust_the_named_classes() {
+        assert_eq!(apply("g+r", 0o600, false), 0o640);
+        assert_eq!(apply("go-rwx", 0o755, false), 0o700);
+        assert_eq!(apply("u=rw,o=", 0o777, false), 0o670);
+        assert_eq!(apply("+x", 0o644, false), 0o755);
+        assert_eq!(apply("a+rX", 0o600, false), 0o644);
+        assert_eq!(apply("a+rX", 0o700, false), 0o755);
+        assert_eq!(apply("u+s,o+t", 0o755, false), 0o5755);
+        assert_eq!(apply("u+r-w", 0o644, false), 0o444);
+    }
+
+    #[test]
+    fn d_and_f_prefixes_pick_the_entry_kind() {
+        let spec = "D755,F644";
+        assert_eq!(apply(spec, 0o700, true), 0o755);
+        assert_eq!(apply(spec, 0o600, false), 0o644);
+        assert_eq!(apply("Dg+rX", 0o700, true), 0o750);
+        assert_eq!(apply("Dg+rX", 0o600, false), 0o600);
+    }
+
+    #[test]
+    fn an_unknown_mode_is_left_alone() {
+        assert_eq!(apply("644", 0, false), 0);
+    }
+
+    #[test]
+    fn malformed_specs_are_rejected() {
+        for spec in ["", "g+r,", "D", "9", "17777", "q+r", "g+z", "rw"] {
+            assert!(ChmodSpec::parse(spec).is_err(), "{spec:?} should not parse");
+        }
+    }
+}
@@ -1,9 +1,72 @@
 use crate::checksum::{self, ChecksumType};
+use crate::checksum_cache;
 use crate::generated::ComparisonMode;
 use eyre::{Context, Result};
+use std::fs::File;
+use std::io::{Read, Seek};
 use std::path::Path;
 use std::time::SystemTime;
 
+/// Size of each first/last window the partial hash reads.
+const PARTIAL_HASH_BYTES: usize = 1024 * 1024;
+
+/// Whether two files of the same `len` differ in content. Streams, and
+/// reads as little as it can: differing first/last windows answer
+/// without touching the middle, and a file no larger than both windows
+/// is already hashed whole by the partial pass. With a
+/// `--checksum-cache` installed, two cached digests answer without any
+/// read, and full hashes computed here are recorded.
+fn same_size_contents_differ(src: &Path, dst: &Path, len: u64) -> Result<bool> {
+    let cache = checksum_cache::active();
+    let cached = |path: &Path| {
+        let cache = cache?;
+        cache.lookup(path, &path.metadata().ok()?)
+    };
+    let (src_cached, dst_cached) = (cached(src), cached(dst));
+    if let (Some(a), Some(b)) = (&src_cached, &dst_cached) {
+        return Ok(a != b);
+    }
+    let open = |path: &Path| File::open(path).with_context(|| format!("open {}", path.display()));
+    let full = |path: &Path, known: Option<Vec<u8>>| match known {
+        Some(digest) => Ok(digest),
+        None => checksum_cache::hash_and_store(cache, path),
+    };
+    same_size_readers_differ(
+        &mut open(src)?,
+        &mut open(dst)?,
+        len,
+        |_| full(src, src_cached),
+        |_| full(dst, dst_cached),
+    )
+    .with_context(|| format!("comparing {} with {}", src.display(), dst.display()))
+}
+
+/// [`same_size_contents_differ`] over two open readers of `len` bytes
+/// each, positioned at their start. `src_full` / `dst_full` produce a
+/// side's whole-content Blake3 and run only when the first/last windows
+/// match on a file larger than both of them.
+pub(crate) fn same_size_readers_differ<S: Read + Seek, D: Read + Seek>(
+    src: &mut S,
+    dst: &mut D,
+    len: u64,
+    src_full: impl FnOnce(&mut S) -> Result<Vec<u8>>,
+    dst_full: impl FnOnce(&mut D) -> Result<Vec<u8>>,
+) -> Result<bool> {
+    let src_ph =
+        checksum::partial_hash_reader(src, len, PARTIAL_HASH_BYTES).context("hashing source")?;
+    let dst_ph =
+        checksum::partial_hash_reader(dst, len, PARTIAL_HASH_BYTES).context("hashing dest")?;
+    if src_ph != dst_ph {
+        return Ok(true);
+    }
+    if len <= 2 * PARTIAL_HASH_BYTES as u64 {
+        return Ok(false);
+    }
+    let src_hash = src_full(src).context("hashing source")?;
+    let dst_hash = dst_full(dst).context("hashing dest")?;
+    Ok(src_hash != dst_hash)
+}
+
 /// Check if a file needs to be copied (for mirror mode)
 pub fn file_needs_copy(src: &Path, dst: &Path, use_checksum: bool) -> Result<bool> {
     if !dst.exists() {
@@ -48,26 +111,19 @@ pub fn file_needs_copy_with_checksum_type(
         return Ok(true);
     }
 
-    let ph_bytes = 1024 * 1024; // 1 MiB
-    let src_ph = checksum::partial_hash_first_last(src, ph_bytes)?;
-    let dst_ph = checksum::partial_hash_first_last(dst, ph_bytes)?;
+    if checksum == Some(ChecksumType::Blake3) {
+        return same_size_contents_differ(src, dst, src_meta.len());
+    }
+    let src_ph = checksum::partial_hash_first_last(src, PARTIAL_HASH_BYTES)?;
+    let dst_ph = checksum::partial_hash_first_last(dst, PARTIAL_HASH_BYTES)?;
     if src_ph != dst_ph {
         return Ok(true);
     }
-    match checksum {
-        Some(ChecksumType::Blake3) => {
-            let a = checksum::hash_file(src, ChecksumType::Blake3)?;
-            let b = checksum::hash_file(dst, ChecksumType::Blake3)?;
-            Ok(a != b)
-        }
-        Some(_) | None => {
-            let src_time = src_meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
-            let dst_time = dst_meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
-            Ok(src_time
-                .duration_since(dst_time)
-                .is_ok_and(|diff| diff.as_secs() > 2))
-        }
-    }
+    let src_time = src_meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    let dst_time = dst_meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    Ok(src_time
+        .duration_since(dst_time)
+        .is_ok_and(|diff| diff.as_secs() > 2))
 }
 
 /// Per-mode comparison predicate. Returns `true` when the source file
@@ -86,7 +142,8 @@ pub fn file_needs_copy_with_checksum_type(
 ///     tolerance matches `file_needs_copy_with_checksum_type` and
 ///     FAT/exFAT mtime granularity.
 ///   - `Checksum`: copy when missing, sizes differ, or Blake3 hashes
-///     differ. mtime is not consulted.
+///     differ (first/last windows first; the full hash only when they
+///     match). mtime is not consulted.
 ///   - `SizeOnly`: copy when missing or sizes differ; mtime ignored.
 ///   - `IgnoreTimes` / `Force`: always copy.
 ///
@@ -112,11 +169,7 @@ pub fn file_needs_copy_with_mode(src: &Path, dst: &Path, mode: ComparisonMode) -
             if src_meta.len() != dst_meta.len() {
                 return Ok(true);
             }
-            let src_hash = checksum::hash_file(src, ChecksumType::Blake3)
-                .with_context(|| format!("hashing source {}", src.display()))?;
-            let dst_hash = checksum::hash_file(dst, ChecksumType::Blake3)
-                .with_context(|| format!("hashing dest {}", dst.display()))?;
-            Ok(src_hash != dst_hash)
+            same_size_contents_differ(src, dst, src_meta.len())
         }
         // Unspecified folds to the historical default.
         ComparisonMode::Unspecified | ComparisonMode::SizeMtime => {
@@ -124,3 +177,67 @@ pub fn file_needs_copy_with_mode(src: &Path, dst: &Path, mode: ComparisonMode) -
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, SeekFrom};
+
+    /// Read + Seek wrapper that counts the bytes read through it.
+    struct CountingReader {
+        inner: Cursor<Vec<u8>>,
+        bytes_read: u64,
+    }
+
+    impl Read for CountingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.bytes_read += n as u64;
+            Ok(n)
+        }
+    }
+
+    impl Seek for CountingReader {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    /// Three windows' worth of zeros with `head` as the first byte and
+    /// `middle` at the centre, so the middle is outside both windows.
+    fn reader(head: u8, middle: u8) -> CountingReader {
+        let mut data = vec![0u8; 3 * PARTIAL_HASH_BYTES];
+        data[0] = head;
+        data[3 * PARTIAL_HASH_BYTES / 2] = middle;
+        CountingReader {
+            inner: Cursor::new(data),
+            bytes_read: 0,
+        }
+    }
+
+    fn full_hash(reader: &mut CountingReader) -> Result<Vec<u8>> {
+        reader.rewind()?;
+        checksum::hash_reader(reader, ChecksumType::Blake3)
+    }
+
+    #[test]
+    fn a_partial_mismatch_answers_without_the_full_hash() {
+        let (mut src, mut dst) = (reader(1, 0), reader(9, 0));
+        let len = src.inner.get_ref().len() as u64;
+        let no_full = |_: &mut CountingReader| -> Result<Vec<u8>> { panic!("full hash ran") };
+        assert!(same_size_readers_differ(&mut src, &mut dst, len, no_full, no_full).unwrap());
+        // Each side is read for its two partial-hash windows only.
+        assert_eq!(src.bytes_read, 2 * PARTIAL_HASH_BYTES as u64);
+        assert_eq!(dst.bytes_read, 2 * PARTIAL_HASH_BYTES as u64);
+    }
+
+    #[test]
+    fn matching_windows_fall_through_to_the_full_hash() {
+        let (mut src, mut dst) = (reader(1, 0), reader(1, 7));
+        let len = src.inner.get_ref().len() as u64;
+        assert!(same_size_readers_differ(&mut src, &mut dst, len, full_hash, full_hash).unwrap());
+
+        let (mut src, mut dst) = (reader(1, 7), reader(1, 7));
+        assert!(!same_size_readers_differ(&mut src, &mut dst, len, full_hash, full_hash).unwrap());
+    }
+}
@@ -1,7 +1,6 @@
 use eyre::Result;
 #[cfg(all(unix, not(target_os = "macos")))]
 use eyre::WrapErr;
-#[cfg(not(target_os = "macos"))]
 use std::fs::File;
 #[cfg(target_os = "macos")]
 use std::path::Path;
@@ -36,13 +35,14 @@ pub(crate) fn attempt_copy_file_range_linux(
     src: &File,
     dst: &File,
     file_size: u64,
+    on_bytes: &dyn Fn(u64),
 ) -> Result<bool> {
     use std::os::unix::io::AsRawFd;
     let sfd = src.as_raw_fd();
     let dfd = dst.as_raw_fd();
     let mut copied: u64 = 0;
     while copied < file_size {
-        let to_copy = (file_size - copied) as usize;
+        let to_copy = (file_size - copied).min(KERNEL_COPY_SLICE) as usize;
         let res = unsafe {
             libc::copy_file_range(
                 sfd,
@@ -55,6 +55,7 @@ pub(crate) fn attempt_copy_file_range_linux(
         };
         if res > 0 {
             copied += res as u64;
+            on_bytes(res as u64);
             continue;
         }
         if res == 0 {
@@ -75,16 +76,22 @@ pub(crate) fn attempt_copy_file_range_linux(
 }
 
 #[cfg(all(unix, not(target_os = "macos")))]
-pub(crate) fn attempt_sendfile_linux(src: &File, dst: &File, file_size: u64) -> Result<bool> {
+pub(crate) fn attempt_sendfile_linux(
+    src: &File,
+    dst: &File,
+    file_size: u64,
+    on_bytes: &dyn Fn(u64),
+) -> Result<bool> {
     use std::os::unix::io::AsRawFd;
     let sfd = src.as_raw_fd();
     let dfd = dst.as_raw_fd();
     let mut copied: u64 = 0;
     while copied < file_size {
-        let to_copy = (file_size - copied) as usize;
+        let to_copy = (file_size - copied).min(KERNEL_COPY_SLICE) as usize;
         let res = unsafe { libc::sendfile(dfd, sfd, std::ptr::null_mut(), to_copy) };
         if res > 0 {
             copied += res as u64;
+            on_bytes(res as u64);
             continue;
         }
         if res == 0 {
@@ -104,38 +111,80 @@ pub(crate) fn attempt_sendfile_linux(src: &File, dst: &File, file_size: u64) ->
     Ok(copied == file_size)
 }
 
+/// Most one `copy_file_range`/`sendfile` call moves, so a large file
+/// reports progress between calls rather than in one 2 GiB step.
+#[cfg(all(unix, not(target_os = "macos")))]
+const KERNEL_COPY_SLICE: u64 = 16 * 1024 * 1024;
+
+/// Granularity at which `SparseMode::Always` looks for all-zero runs to
+/// leave as holes.
+#[cfg(unix)]
+const ZERO_BLOCK: usize = 64 * 1024;
+
+/// Copy `[start, end)` of `src` to the same offsets in the pre-sized
+/// `dst`. With `skip_zeros`, all-zero blocks are not written and stay
+/// holes. Returns the bytes actually written.
+#[cfg(unix)]
+fn copy_segment_at(
+    src: &File,
+    dst: &File,
+    start: u64,
+    end: u64,
+    buf: &mut [u8],
+    skip_zeros: bool,
+    on_bytes: &dyn Fn(u64),
+) -> Result<u64> {
+    use std::os::unix::fs::FileExt as _;
+
+    let mut offset = start;
+    let mut written = 0u64;
+    while offset < end {
+        let to_read = (end - offset).min(buf.len() as u64) as usize;
+        src.read_exact_at(&mut buf[..to_read], offset)?;
+        let block_len = if skip_zeros { ZERO_BLOCK } else { to_read };
+        let mut at = offset;
+        for block in buf[..to_read].chunks(block_len) {
+            if !(skip_zeros && block.iter().all(|&b| b == 0)) {
+                dst.write_all_at(block, at)?;
+                written += block.len() as u64;
+            }
+            at += block.len() as u64;
+        }
+        offset += to_read as u64;
+        on_bytes(to_read as u64);
+    }
+    Ok(written)
+}
+
+/// Sparse copy driven by `SEEK_DATA`/`SEEK_HOLE`: the source's holes are
+/// never read, and the pre-sized destination keeps them as holes. With
+/// `skip_zeros` (`--sparse`), all-zero blocks inside data segments are
+/// left as holes too. Returns the bytes written, or `None` when the
+/// source reports no data segment (no `SEEK_DATA` support, or nothing
+/// but holes) and the caller should pick another strategy.
 #[cfg(all(unix, not(target_os = "macos")))]
 pub(crate) fn attempt_sparse_copy_unix(
     src: &File,
     dst: &File,
     file_size: u64,
+    skip_zeros: bool,
+    on_bytes: &dyn Fn(u64),
 ) -> Result<Option<u64>> {
-    use std::io::{Read, Seek, SeekFrom};
-    use std::os::unix::fs::FileExt as _;
     use std::os::unix::io::AsRawFd;
 
     let sfd = src.as_raw_fd();
-    let dfd = dst;
 
     let mut pos: i64 = 0;
     let mut any = false;
-    let mut copied: u64 = 0;
+    let mut written: u64 = 0;
     dst.set_len(file_size)
         .with_context(|| "pre-size destination for sparse copy")?;
-    let buf_sz = 1 << 20;
-    let mut buf = vec![0u8; buf_sz];
+    let mut buf = vec![0u8; 1 << 20];
     while (pos as u64) < file_size {
         let data_off = unsafe { libc::lseek(sfd, pos, libc::SEEK_DATA) };
         if data_off < 0 {
-            let err = std::io::Error::last_os_error();
-            if let Some(code) = err.raw_os_error() {
-                if code == libc::ENXIO || code == libc::EINVAL {
-                    if !any {
-                        return Ok(None);
-                    }
-                    break;
-                }
-            }
+            // ENXIO: no data past `pos` (trailing hole). Anything else
+            // before the first segment means SEEK_DATA is unusable here.
             if !any {
                 return Ok(None);
             }
@@ -143,48 +192,63 @@ pub(crate) fn attempt_sparse_copy_unix(
         }
         any = true;
         let hole_off = unsafe { libc::lseek(sfd, data_off, libc::SEEK_HOLE) };
-        if hole_off < 0 {
-            let start = data_off as u64;
-            let end = file_size;
-            if start >= end {
-                break;
-            }
-            let mut so = src.try_clone()?;
-            so.seek(SeekFrom::Start(start))?;
-            let mut remaining = end - start;
-            let mut offset = start;
-            while remaining > 0 {
-                let to_read = remaining.min(buf.len() as u64) as usize;
-                so.read_exact(&mut buf[..to_read])?;
-                dfd.write_all_at(&buf[..to_read], offset)?;
-                remaining -= to_read as u64;
-                offset += to_read as u64;
-            }
-            copied = end;
-            break;
-        }
         let start = data_off as u64;
-        let end = hole_off as u64;
-        if start >= end {
-            pos = hole_off;
-            continue;
+        let end = if hole_off < 0 {
+            file_size
+        } else {
+            hole_off as u64
+        };
+        if start < end {
+            written += copy_segment_at(src, dst, start, end, &mut buf, skip_zeros, on_bytes)?;
         }
-        let mut so = src.try_clone()?;
-        so.seek(SeekFrom::Start(start))?;
-        let mut remaining = end - start;
-        let mut offset = start;
-        while remaining > 0 {
-            let to_read = remaining.min(buf.len() as u64) as usize;
-            so.read_exact(&mut buf[..to_read])?;
-            dfd.write_all_at(&buf[..to_read], offset)?;
-            remaining -= to_read as u64;
-            offset += to_read as u64;
+        if hole_off < 0 {
+            break;
         }
-        copied = end;
         pos = hole_off;
     }
 
-    Ok(if any { Some(copied) } else { None })
+    Ok(if any { Some(written) } else { None })
+}
+
+/// `--sparse` without `SEEK_DATA`: read the whole source and leave every
+/// all-zero block as a hole in the pre-sized destination. Returns the
+/// bytes written.
+#[cfg(unix)]
+pub(crate) fn zero_skip_copy_unix(
+    src: &File,
+    dst: &File,
+    file_size: u64,
+    on_bytes: &dyn Fn(u64),
+) -> Result<u64> {
+    dst.set_len(file_size)?;
+    let mut buf = vec![0u8; 1 << 20];
+    copy_segment_at(src, dst, 0, file_size, &mut buf, true, on_bytes)
+}
+
+/// `--no-sparse`: a plain read/write loop that writes every byte, zeros
+/// included. Deliberately not `io::copy`, which std specializes into
+/// `copy_file_range` on Linux and can carry the source's holes across.
+pub(crate) fn dense_copy(
+    src: &mut impl std::io::Read,
+    dst: &mut impl std::io::Write,
+    buffer_size: usize,
+    on_bytes: &dyn Fn(u64),
+) -> Result<u64> {
+    let mut buf = vec![0u8; buffer_size.max(1)];
+    let mut written = 0u64;
+    loop {
+        let n = match src.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err.into()),
+        };
+        dst.write_all(&buf[..n])?;
+        written += n as u64;
+        on_bytes(n as u64);
+    }
+    dst.flush()?;
+    Ok(written)
 }
 
 #[cfg(windows)]
@@ -20,10 +20,29 @@ const FILE_FLAG_SEQUENTIAL_SCAN: u32 = 0x0800_0000;
 #[cfg(windows)]
 use crate::copy::windows;
 
+/// How [`copy_file`] treats runs of zeros in the source.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SparseMode {
+    /// Clone and zero-copy fast paths first; the source's holes survive
+    /// whenever the chain reaches the sparse-aware fallback.
+    #[default]
+    Auto,
+    /// `--sparse`: skip the fast paths and leave every hole, and every
+    /// all-zero block, unallocated at the destination.
+    Always,
+    /// `--no-sparse`: write every byte, zeros included, so the
+    /// destination is fully allocated.
+    Never,
+}
+
 /// Copy a single file with optimal buffer size
 pub struct FileCopyOutcome {
     pub bytes_copied: u64,
     pub clone_succeeded: bool,
+    /// Bytes of the destination left as holes instead of written. Zero
+    /// when a clone or zero-copy primitive handled the file (those keep
+    /// or drop holes on their own, without reporting either way).
+    pub sparse_bytes: u64,
 }
 
 pub fn copy_file(
@@ -31,9 +50,31 @@ pub fn copy_file(
     dst: &Path,
     buffer_sizer: &BufferSizer,
     is_network: bool,
+    sparse: SparseMode,
+) -> Result<FileCopyOutcome> {
+    copy_file_with_progress(src, dst, buffer_sizer, is_network, sparse, false, &|_| {})
+}
+
+/// [`copy_file`] that calls `on_bytes` with each chunk the streaming
+/// paths copy, so one large file can report progress before it is done.
+/// Clone primitives finish in one step and report nothing; nor does the
+/// buffered fallback, which hands the whole file to `io::copy`.
+///
+/// `preallocate` (`--preallocate`) reserves the file's full size on the
+/// destination before anything streams into it. It is skipped under
+/// [`SparseMode::Always`], whose holes it would fill.
+pub fn copy_file_with_progress(
+    src: &Path,
+    dst: &Path,
+    buffer_sizer: &BufferSizer,
+    is_network: bool,
+    sparse: SparseMode,
+    preallocate: bool,
+    on_bytes: &dyn Fn(u64),
 ) -> Result<FileCopyOutcome> {
+    let preallocate = preallocate && sparse != SparseMode::Always;
     #[cfg(windows)]
-    if !is_network {
+    if !is_network && sparse == SparseMode::Auto {
         match windows::windows_copyfile(src, dst) {
             Ok(bytes) => {
                 let clone_succeeded = windows::take_last_block_clone_success();
@@ -48,6 +89,7 @@ pub fn copy_file(
                 return Ok(FileCopyOutcome {
                     bytes_copied: bytes,
                     clone_succeeded,
+                    sparse_bytes: 0,
                 });
             }
             Err(err) => {
@@ -107,12 +149,18 @@ pub fn copy_file(
         let dst_file: Option<File> = None;
         #[cfg(all(unix, not(target_os = "macos")))]
         let dst_file = File::create(dst)?;
+        #[cfg(all(unix, not(target_os = "macos")))]
+        if preallocate {
+            crate::fs_capability::preallocate(&dst_file, file_size)?;
+        }
 
-        let (total_bytes, clone_succeeded) = {
+        let (total_bytes, clone_succeeded, sparse_bytes) = {
             #[cfg(windows)]
             {
                 let mut clone_success = false;
-                if crate::fs_capability::supports_block_clone_same_volume(src, dst)? {
+                if sparse == SparseMode::Auto
+                    && crate::fs_capability::supports_block_clone_same_volume(src, dst)?
+                {
                     match windows::try_block_clone_with_handles(&src_file, &dst_file, file_size)? {
                         windows::BlockCloneOutcome::Cloned => {
                             clone_success = true;
@@ -139,8 +187,15 @@ pub fn copy_file(
                         }
                     }
                 }
+                if !clone_success && preallocate {
+                    crate::fs_capability::preallocate(&dst_file, file_size)?;
+                }
                 if clone_success {
-                    (file_size, true)
+                    (file_size, true, 0)
+                } else if sparse == SparseMode::Never {
+                    let copied =
+                        clone::dense_copy(&mut &src_file, &mut dst_file, buffer_size, on_bytes)?;
+                    (copied, false, 0)
                 } else {
                     let copied = clone::sparse_copy_windows(
                         src_file,
@@ -148,7 +203,7 @@ pub fn copy_file(
                         buffer_size,
                         file_size,
                     )?;
-                    (copied, false)
+                    (copied, false, file_size.saturating_sub(copied))
                 }
             }
             #[cfg(target_os = "macos")]
@@ -159,38 +214,90 @@ pub fn copy_file(
                                   // copy if neither clone succeeded. The streaming
                                   // path creates the destination itself when it
                                   // opens its writer.
-                let cloned = clone::attempt_clonefile_macos(src, dst).unwrap_or(false)
-                    || clone::attempt_fcopyfile_macos(src, dst).unwrap_or(false);
+                let cloned = sparse == SparseMode::Auto
+                    && (clone::attempt_clonefile_macos(src, dst).unwrap_or(false)
+                        || clone::attempt_fcopyfile_macos(src, dst).unwrap_or(false));
                 if cloned {
-                    (file_size, true)
+                    (file_size, true, 0)
+                } else if sparse == SparseMode::Always {
+                    let dst_for_stream = File::create(dst)?;
+                    let written = clone::zero_skip_copy_unix(
+                        &src_file,
+                        &dst_for_stream,
+                        file_size,
+                        on_bytes,
+                    )?;
+                    (file_size, false, file_size - written)
+                } else if sparse == SparseMode::Never {
+                    let mut dst_for_stream = File::create(dst)?;
+                    if preallocate {
+                        crate::fs_capability::preallocate(&dst_for_stream, file_size)?;
+                    }
+                    let n = clone::dense_copy(
+                        &mut &src_file,
+                        &mut dst_for_stream,
+                        buffer_size,
+                        on_bytes,
+                    )?;
+                    (n, false, 0)
                 } else {
                     let dst_for_stream = File::create(dst)?;
+                    if preallocate {
+                        crate::fs_capability::preallocate(&dst_for_stream, file_size)?;
+                    }
                     let mut reader = BufReader::with_capacity(buffer_size, src_file);
                     let mut writer = BufWriter::with_capacity(buffer_size, dst_for_stream);
                     let n = io::copy(&mut reader, &mut writer)?;
                     writer.flush()?;
-                    (n, false)
+                    (n, false, 0)
                 }
             }
             #[cfg(all(unix, not(target_os = "macos")))]
             {
-                let fast_linux =
-                    clone::attempt_copy_file_range_linux(&src_file, &dst_file, file_size)
+                match sparse {
+                    SparseMode::Never => {
+                        let n = clone::dense_copy(
+                            &mut &src_file,
+                            &mut &dst_file,
+                            buffer_size,
+                            on_bytes,
+                        )?;
+                        (n, false, 0)
+                    }
+                    SparseMode::Always => {
+                        let written = match clone::attempt_sparse_copy_unix(
+                            &src_file, &dst_file, file_size, true, on_bytes,
+                        )? {
+                            Some(written) => written,
+                            None => clone::zero_skip_copy_unix(
+                                &src_file, &dst_file, file_size, on_bytes,
+                            )?,
+                        };
+                        (file_size, false, file_size - written)
+                    }
+                    SparseMode::Auto => {
+                        let fast_linux = clone::attempt_copy_file_range_linux(
+                            &src_file, &dst_file, file_size, on_bytes,
+                        )
                         .unwrap_or(false)
-                        || clone::attempt_sendfile_linux(&src_file, &dst_file, file_size)
+                            || clone::attempt_sendfile_linux(
+                                &src_file, &dst_file, file_size, on_bytes,
+                            )
                             .unwrap_or(false);
-                if fast_linux {
-                    (file_size, true)
-                } else if let Some(n) =
-                    clone::attempt_sparse_copy_unix(&src_file, &dst_file, file_size)?
-                {
-                    (n, false)
-                } else {
-                    let mut reader = BufReader::with_capacity(buffer_size, src_file);
-                    let mut writer = BufWriter::with_capacity(buffer_size, dst_file);
-                    let n = io::copy(&mut reader, &mut writer)?;
-                    writer.flush()?;
-                    (n, false)
+                        if fast_linux {
+                            (file_size, true, 0)
+                        } else if let Some(written) = clone::attempt_sparse_copy_unix(
+                            &src_file, &dst_file, file_size, false, on_bytes,
+                        )? {
+                            (file_size, false, file_size - written)
+                        } else {
+                            let mut reader = BufReader::with_capacity(buffer_size, src_file);
+                            let mut writer = BufWriter::with_capacity(buffer_size, dst_file);
+                            let n = io::copy(&mut reader, &mut writer)?;
+                            writer.flush()?;
+                            (n, false, 0)
+                        }
+                    }
                 }
             }
         };
@@ -201,6 +308,7 @@ pub fn copy_file(
         Ok(FileCopyOutcome {
             bytes_copied: total_bytes,
             clone_succeeded,
+            sparse_bytes,
         })
     })();
 
@@ -229,11 +337,44 @@ mod fallback_tests {
         let data: Vec<u8> = (0u8..=255).cycle().take(100_000).collect();
         std::fs::write(&src, &data).unwrap();
 
-        let outcome = copy_file(&src, &dst, &BufferSizer::default(), false).unwrap();
+        let outcome =
+            copy_file(&src, &dst, &BufferSizer::default(), false, SparseMode::Auto).unwrap();
         assert_eq!(outcome.bytes_copied, data.len() as u64);
         assert_eq!(std::fs::read(&dst).unwrap(), data);
     }
 
+    /// `--block-size`: the streaming loop reads in exactly the override's
+    /// chunks, not the derived size.
+    #[test]
+    fn block_size_override_sets_streaming_read_size() {
+        struct CountingReader {
+            data: std::io::Cursor<Vec<u8>>,
+            requested: std::cell::RefCell<Vec<usize>>,
+        }
+        impl std::io::Read for CountingReader {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                self.requested.borrow_mut().push(buf.len());
+                self.data.read(buf)
+            }
+        }
+
+        let data: Vec<u8> = (0u8..=255).cycle().take(100_000).collect();
+        let mut reader = CountingReader {
+            data: std::io::Cursor::new(data.clone()),
+            requested: Default::default(),
+        };
+        let sizer = BufferSizer::with_block_size(8192);
+        let buffer_size = sizer.calculate_buffer_size(data.len() as u64, false);
+        let mut out = Vec::new();
+        let copied = clone::dense_copy(&mut reader, &mut out, buffer_size, &|_| {}).unwrap();
+
+        assert_eq!(copied, data.len() as u64);
+        assert_eq!(out, data);
+        let requested = reader.requested.into_inner();
+        assert_eq!(requested.len(), data.len().div_ceil(8192) + 1);
+        assert!(requested.iter().all(|&len| len == 8192), "{requested:?}");
+    }
+
     /// macOS: `clonefile(2)` returns `EEXIST` when the destination already
     /// exists, so a pre-existing dst deterministically forces the FIRST
     /// fast-path hop (clonefile) to fail. `fcopyfile` (opened with
@@ -256,7 +397,8 @@ mod fallback_tests {
         // Pre-create dst so clonefile hits EEXIST and the chain advances.
         std::fs::write(&dst, b"stale pre-existing contents").unwrap();
 
-        let outcome = copy_file(&src, &dst, &BufferSizer::default(), false).unwrap();
+        let outcome =
+            copy_file(&src, &dst, &BufferSizer::default(), false, SparseMode::Auto).unwrap();
         assert_eq!(outcome.bytes_copied, data.len() as u64);
         assert_eq!(
             std::fs::read(&dst).unwrap(),
@@ -275,4 +417,123 @@ mod fallback_tests {
              not the buffered tail"
         );
     }
+
+    /// A 4 MiB file with data only in its first and last 4 KiB: holes
+    /// in between wherever the filesystem supports them, zeros either way.
+    #[cfg(unix)]
+    fn write_sparse_source(path: &Path) -> Vec<u8> {
+        use std::os::unix::fs::FileExt as _;
+        let len = 4 * 1024 * 1024u64;
+        let file = std::fs::File::create(path).unwrap();
+        file.set_len(len).unwrap();
+        file.write_all_at(&[0xAB; 4096], 0).unwrap();
+        file.write_all_at(&[0xCD; 4096], len - 4096).unwrap();
+        drop(file);
+        std::fs::read(path).unwrap()
+    }
+
+    /// `--no-sparse`: every byte is written, so the destination's
+    /// allocated blocks cover its whole length even though the source
+    /// is mostly holes.
+    #[cfg(unix)]
+    #[test]
+    fn no_sparse_copy_is_fully_allocated() {
+        use std::os::unix::fs::MetadataExt as _;
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src.bin");
+        let dst = dir.path().join("dst.bin");
+        let data = write_sparse_source(&src);
+
+        let outcome = copy_file(
+            &src,
+            &dst,
+            &BufferSizer::default(),
+            false,
+            SparseMode::Never,
+        )
+        .unwrap();
+        assert_eq!(outcome.sparse_bytes, 0);
+        assert_eq!(std::fs::read(&dst).unwrap(), data);
+        let meta = std::fs::metadata(&dst).unwrap();
+        assert!(
+            meta.blocks() * 512 >= meta.len(),
+            "--no-sparse destination must be fully allocated: {} bytes allocated for {} bytes",
+            meta.blocks() * 512,
+            meta.len()
+        );
+    }
+
+    /// `--preallocate`: the reservation alone allocates blocks for the
+    /// file's full size, and a preallocated copy of a sparse source is
+    /// still byte-identical. Linux only, and skipped on a filesystem
+    /// without `fallocate`.
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn preallocate_reserves_blocks_for_the_full_size() {
+        use crate::fs_capability::FilesystemCapability as _;
+        use std::os::unix::fs::MetadataExt as _;
+        let dir = tempfile::tempdir().unwrap();
+        let reserved = dir.path().join("reserved.bin");
+        let len = 4 * 1024 * 1024u64;
+        let file = File::create(&reserved).unwrap();
+        if !crate::fs_capability::get_platform_capability()
+            .preallocate(&file, len)
+            .unwrap()
+        {
+            eprintln!("skipping: fallocate is not supported here");
+            return;
+        }
+        drop(file);
+        let meta = std::fs::metadata(&reserved).unwrap();
+        assert!(
+            meta.blocks() * 512 >= len,
+            "preallocated file must own {len} bytes of blocks, has {}",
+            meta.blocks() * 512
+        );
+
+        let src = dir.path().join("src.bin");
+        let dst = dir.path().join("dst.bin");
+        let data = write_sparse_source(&src);
+        copy_file_with_progress(
+            &src,
+            &dst,
+            &BufferSizer::default(),
+            false,
+            SparseMode::Never,
+            true,
+            &|_| {},
+        )
+        .unwrap();
+        assert_eq!(std::fs::read(&dst).unwrap(), data);
+        let meta = std::fs::metadata(&dst).unwrap();
+        assert!(meta.blocks() * 512 >= meta.len());
+    }
+
+    /// `--sparse`: the zero middle is left as holes and the outcome
+    /// counts it, whether or not the source itself was sparse.
+    #[cfg(unix)]
+    #[test]
+    fn sparse_copy_counts_bytes_left_as_holes() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src.bin");
+        let dst = dir.path().join("dst.bin");
+        let data = write_sparse_source(&src);
+
+        let outcome = copy_file(
+            &src,
+            &dst,
+            &BufferSizer::default(),
+            false,
+            SparseMode::Always,
+        )
+        .unwrap();
+        assert_eq!(std::fs::read(&dst).unwrap(), data);
+        assert!(!outcome.clone_succeeded);
+        // Only the two data blocks' 64 KiB zero-scan windows are written.
+        assert!(
+            outcome.sparse_bytes >= data.len() as u64 - 2 * 64 * 1024,
+            "holes saved: {}",
+            outcome.sparse_bytes
+        );
+    }
 }
@@ -5,6 +5,11 @@ mod windows;
 
 pub use compare::{file_needs_copy, file_needs_copy_with_checksum_type, file_needs_copy_with_mode};
 pub use file_copy::resume::{DEFAULT_BLOCK_SIZE, MAX_BLOCK_SIZE};
-pub use file_copy::{copy_file, mmap_copy_file, resume_copy_file, ResumeCopyOutcome};
+pub use file_copy::{
+    copy_file, copy_file_with_progress, mmap_copy_file, resume_copy_file, FileCopyOutcome,
+    ResumeCopyOutcome, SparseMode,
+};
+#[cfg(windows)]
+pub(crate) use windows::try_block_clone_same_volume;
 #[cfg(windows)]
 pub use windows::windows_copyfile;
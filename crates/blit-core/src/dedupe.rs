@@ -0,0 +1,297 @@
+//! `--dedupe`: after a local transfer, replace destination files with
+//! identical content by hard links to one copy.
+//!
+//! Files are grouped by size, mode, mtime and owner first (a hard link
+//! shares one inode, and with it that metadata, so files that differ
+//! there are never candidates), and only groups of two or more distinct
+//! inodes are hashed (BLAKE3). Within a hash group the first path with
+//! a given set of extended attributes keeps its inode and every later
+//! path carrying the same set is re-pointed at it.
+//!
+//! Paths are re-pointed with [`link_over`], which the daemon's
+//! `dedupe_store` pass shares; its docs carry why sharing an inode
+//! between destination paths is safe. That safety is Unix only, and so
+//! is this pass.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use eyre::{Context, Result};
+
+use crate::checksum::{hash_file, ChecksumType};
+
+/// What one [`dedupe_tree`] pass did.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DedupeStats {
+    /// Files replaced by a link to an identical file.
+    pub files_linked: u64,
+    /// Bytes no longer stored twice thanks to `files_linked`.
+    pub bytes_saved: u64,
+}
+
+/// Hard-link identical regular files under `root` together. Best effort
+/// per file: one that cannot be hashed or linked is logged and left as
+/// it is, and the rest of the pass continues. Errors only when `root`
+/// itself cannot be walked.
+#[cfg(unix)]
+pub fn dedupe_tree(root: &Path) -> Result<DedupeStats> {
+    use std::os::unix::fs::MetadataExt;
+
+    type MetaKey = (u64, u64, u32, u32, u32, i64, i64);
+    let mut by_meta: HashMap<MetaKey, Vec<(PathBuf, fs::Metadata)>> = HashMap::new();
+    for entry in walkdir::WalkDir::new(root).follow_links(false) {
+        let entry = entry.with_context(|| format!("walking {}", root.display()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let meta = entry
+            .metadata()
+            .with_context(|| format!("stat {}", entry.path().display()))?;
+        if meta.len() == 0 {
+            continue;
+        }
+        let key = (
+            meta.dev(),
+            meta.len(),
+            meta.mode(),
+            meta.uid(),
+            meta.gid(),
+            meta.mtime(),
+            meta.mtime_nsec(),
+        );
+        by_meta
+            .entry(key)
+            .or_default()
+            .push((entry.into_path(), meta));
+    }
+
+    let mut stats = DedupeStats::default();
+    for mut group in by_meta.into_values() {
+        group.sort_by(|a, b| a.0.cmp(&b.0));
+        // Paths already sharing an inode need nothing; keep one of each.
+        let mut seen = std::collections::HashSet::new();
+        group.retain(|(_, meta)| seen.insert(meta.ino()));
+        if group.len() < 2 {
+            continue;
+        }
+        let mut keepers: HashMap<Vec<u8>, Vec<&(PathBuf, fs::Metadata)>> = HashMap::new();
+        for file in &group {
+            let digest = match hash_file(&file.0, ChecksumType::Blake3) {
+                Ok(digest) => digest,
+                Err(err) => {
+                    log::warn!("dedupe: leaving {} unshared: {err:#}", file.0.display());
+                    continue;
+                }
+            };
+            // The key already matches everything but extended
+            // attributes; a file whose set no keeper shares starts its
+            // own.
+            let candidates = keepers.entry(digest).or_default();
+            let Some(keeper) = candidates
+                .iter()
+                .find(|keeper| link_compatible(&keeper.0, &keeper.1, &file.0, &file.1))
+            else {
+                candidates.push(file);
+                continue;
+            };
+            match link_over(&keeper.0, &file.0, &file.1) {
+                Ok(true) => {
+                    stats.files_linked += 1;
+                    stats.bytes_saved += file.1.len();
+                }
+                Ok(false) => {}
+                Err(err) => {
+                    log::warn!("dedupe: leaving {} unshared: {err:#}", file.0.display());
+                }
+            }
+        }
+    }
+    Ok(stats)
+}
+
+#[cfg(not(unix))]
+pub fn dedupe_tree(_root: &Path) -> Result<DedupeStats> {
+    eyre::bail!("--dedupe is only supported on Unix destinations")
+}
+
+/// Point `path` at `target`'s inode: link beside it, then rename over
+/// it, so the path always names a complete copy of its content. `meta`
+/// is what `path` looked like when it was hashed. `Ok(false)`, leaving
+/// it alone, when the two are not [`link_compatible`] or `path` has
+/// changed since (a concurrent write replaced it).
+///
+/// Safe only because destination writes never rewrite a shared inode:
+/// the sink unlinks or copies apart a file with other links before
+/// touching it ([`crate::fs_capability::detach_hard_link`]), so a later
+/// write to one path leaves every other path on the inode alone. That
+/// guard is Unix only.
+pub fn link_over(target: &Path, path: &Path, meta: &fs::Metadata) -> Result<bool> {
+    let target_meta =
+        fs::symlink_metadata(target).with_context(|| format!("stat {}", target.display()))?;
+    if !link_compatible(target, &target_meta, path, meta) {
+        return Ok(false);
+    }
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let staged = path.with_file_name(format!(".{name}.blit-dedupe"));
+    let _ = fs::remove_file(&staged);
+    fs::hard_link(target, &staged)
+        .with_context(|| format!("linking {} to {}", path.display(), target.display()))?;
+    let unchanged = fs::symlink_metadata(path).is_ok_and(|now| {
+        same_inode(&now, meta) && link_compatible(target, &target_meta, path, &now)
+    });
+    if !unchanged {
+        let _ = fs::remove_file(&staged);
+        return Ok(false);
+    }
+    if let Err(err) = fs::rename(&staged, path) {
+        let _ = fs::remove_file(&staged);
+        return Err(err).with_context(|| format!("replacing {}", path.display()));
+    }
+    Ok(true)
+}
+
+/// Whether `a` and `b` can share one inode without either losing
+/// metadata: a hard link shares size, permissions, mtime, owner and
+/// every extended attribute — Blit's own `user.blit.*` records (the
+/// fake-super stat) included. Attributes that cannot be read never
+/// match.
+pub fn link_compatible(a: &Path, a_meta: &fs::Metadata, b: &Path, b_meta: &fs::Metadata) -> bool {
+    a_meta.len() == b_meta.len()
+        && a_meta.permissions() == b_meta.permissions()
+        && a_meta.modified().ok() == b_meta.modified().ok()
+        && same_owner(a_meta, b_meta)
+        && matches!(
+            (crate::xattrs::read_all(a), crate::xattrs::read_all(b)),
+            (Ok(a), Ok(b)) if a == b
+        )
+}
+
+#[cfg(unix)]
+fn same_owner(a: &fs::Metadata, b: &fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.uid() == b.uid() && a.gid() == b.gid()
+}
+
+#[cfg(not(unix))]
+fn same_owner(_a: &fs::Metadata, _b: &fs::Metadata) -> bool {
+    true
+}
+
+#[cfg(unix)]
+fn same_inode(a: &fs::Metadata, b: &fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.dev() == b.dev() && a.ino() == b.ino()
+}
+
+#[cfg(not(unix))]
+fn same_inode(_a: &fs::Metadata, _b: &fs::Metadata) -> bool {
+    false
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::MetadataExt;
+
+    fn ino(path: &Path) -> u64 {
+        fs::metadata(path).unwrap().ino()
+    }
+
+    #[test]
+    fn identical_files_share_one_inode_and_others_are_left_alone() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir(root.join("sub")).unwrap();
+        let mtime = filetime::FileTime::from_unix_time(1_700_000_000, 0);
+        for (name, body) in [
+            ("a", &b"same content"[..]),
+            ("sub/b", b"same content"),
+            ("c", b"diff content"),
+        ] {
+            fs::write(root.join(name), body).unwrap();
+            filetime::set_file_mtime(root.join(name), mtime).unwrap();
+        }
+
+        let stats = dedupe_tree(root).unwrap();
+
+        assert_eq!(
+            stats,
+            DedupeStats {
+                files_linked: 1,
+                bytes_saved: 12
+            }
+        );
+        assert_eq!(ino(&root.join("a")), ino(&root.join("sub/b")));
+        assert_ne!(ino(&root.join("a")), ino(&root.join("c")));
+        assert_eq!(fs::read(root.join("sub/b")).unwrap(), b"same content");
+        // A rerun finds nothing left to share.
+        assert_eq!(dedupe_tree(root).unwrap(), DedupeStats::default());
+    }
+
+    #[test]
+    fn same_content_with_different_mtime_keeps_its_own_copy() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::write(root.join("a"), b"same content").unwrap();
+        fs::write(root.join("b"), b"same content").unwrap();
+        filetime::set_file_mtime(root.join("a"), filetime::FileTime::from_unix_time(1, 0)).unwrap();
+        filetime::set_file_mtime(root.join("b"), filetime::FileTime::from_unix_time(2, 0)).unwrap();
+
+        assert_eq!(dedupe_tree(root).unwrap(), DedupeStats::default());
+        assert_ne!(ino(&root.join("a")), ino(&root.join("b")));
+    }
+
+    /// Same content and times as the keeper, but another owner (a root
+    /// `-a` copy or `--chown`): linking would hand it the keeper's
+    /// owner. Skipped where the runner cannot chown.
+    #[test]
+    fn same_content_with_a_different_owner_keeps_its_own_copy() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let mtime = filetime::FileTime::from_unix_time(1_700_000_000, 0);
+        for name in ["a", "b"] {
+            fs::write(root.join(name), b"same content").unwrap();
+            filetime::set_file_mtime(root.join(name), mtime).unwrap();
+        }
+        if std::os::unix::fs::chown(root.join("b"), Some(4242), Some(4343)).is_err() {
+            return;
+        }
+
+        assert_eq!(dedupe_tree(root).unwrap(), DedupeStats::default());
+        assert_ne!(ino(&root.join("a")), ino(&root.join("b")));
+        assert_eq!(fs::metadata(root.join("b")).unwrap().uid(), 4242);
+    }
+
+    /// Extended attributes (`--xattrs`, or a fake-super record) are
+    /// per inode too: a file is only linked to a keeper carrying the
+    /// same set, so `c` joins `b` and `a` stays alone. Skipped where
+    /// the filesystem refuses `user.*` attributes.
+    #[test]
+    fn same_content_links_only_within_one_xattr_set() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let mtime = filetime::FileTime::from_unix_time(1_700_000_000, 0);
+        for name in ["a", "b", "c"] {
+            fs::write(root.join(name), b"same content").unwrap();
+        }
+        for name in ["b", "c"] {
+            if xattr::set(root.join(name), "user.origin", b"tagged").is_err() {
+                return;
+            }
+        }
+        for name in ["a", "b", "c"] {
+            filetime::set_file_mtime(root.join(name), mtime).unwrap();
+        }
+
+        let stats = dedupe_tree(root).unwrap();
+
+        assert_eq!(stats.files_linked, 1);
+        assert_eq!(ino(&root.join("b")), ino(&root.join("c")));
+        assert_ne!(ino(&root.join("a")), ino(&root.join("b")));
+        assert_eq!(xattr::get(root.join("a"), "user.origin").unwrap(), None);
+    }
+}
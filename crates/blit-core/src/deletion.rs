@@ -82,6 +82,10 @@ pub struct DeletionOptions<'a> {
     pub abort: Option<&'a AtomicBool>,
     pub execute: bool,
     pub directory_mode: DirectoryMode,
+    /// Called with each target once it is removed (or, without
+    /// `execute`, counted). Entries already gone and non-empty
+    /// directories a filtered mirror leaves alone are not reported.
+    pub on_deleted: Option<&'a dyn Fn(&Path)>,
 }
 
 /// Resolve and classify explicit purge targets without following symlinks.
@@ -167,17 +171,27 @@ pub fn execute_deletion_plan(
             source,
         };
 
+    let report = |target: &Path| {
+        if let Some(on_deleted) = options.on_deleted {
+            on_deleted(target);
+        }
+    };
+
     let mut stats = DeletionStats::default();
     for file in files {
         prepare(file)?;
         if !options.execute {
             stats.files += 1;
+            report(file);
             continue;
         }
         #[cfg(windows)]
         crate::win_fs::clear_readonly_recursive(file);
         match std::fs::remove_file(file) {
-            Ok(()) => stats.files += 1,
+            Ok(()) => {
+                stats.files += 1;
+                report(file);
+            }
             Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
             Err(error)
                 if error.kind() == std::io::ErrorKind::IsADirectory
@@ -186,7 +200,10 @@ pub fn execute_deletion_plan(
                 #[cfg(windows)]
                 crate::win_fs::clear_readonly_recursive(file);
                 match std::fs::remove_dir_all(file) {
-                    Ok(()) => stats.dirs += 1,
+                    Ok(()) => {
+                        stats.dirs += 1;
+                        report(file);
+                    }
                     Err(inner) if inner.kind() == std::io::ErrorKind::NotFound => {}
                     Err(inner) => {
                         return Err(filesystem_error("remove directory tree", file, inner))
@@ -201,6 +218,7 @@ pub fn execute_deletion_plan(
         prepare(dir)?;
         if !options.execute {
             stats.dirs += 1;
+            report(dir);
             continue;
         }
         #[cfg(windows)]
@@ -210,7 +228,10 @@ pub fn execute_deletion_plan(
             DirectoryMode::Recursive => std::fs::remove_dir_all(dir),
         };
         match result {
-            Ok(()) => stats.dirs += 1,
+            Ok(()) => {
+                stats.dirs += 1;
+                report(dir);
+            }
             Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
             Err(error)
                 if matches!(
@@ -256,6 +277,7 @@ mod tests {
                 directory_mode: DirectoryMode::EmptyOnly {
                     tolerate_nonempty: false,
                 },
+                on_deleted: None,
             },
         )
         .expect("delete plan");
@@ -280,6 +302,7 @@ mod tests {
                 abort: None,
                 execute: true,
                 directory_mode: DirectoryMode::Recursive,
+                on_deleted: None,
             },
         )
         .expect("recursive purge");
@@ -312,6 +335,7 @@ mod tests {
                 abort: None,
                 execute: true,
                 directory_mode: DirectoryMode::Recursive,
+                on_deleted: None,
             },
         )
         .expect("readonly recursive purge");
@@ -337,6 +361,7 @@ mod tests {
                 directory_mode: DirectoryMode::EmptyOnly {
                     tolerate_nonempty: false,
                 },
+                on_deleted: None,
             },
         )
         .expect_err("abort must stop deletion");
@@ -361,6 +386,7 @@ mod tests {
                 directory_mode: DirectoryMode::EmptyOnly {
                     tolerate_nonempty: false,
                 },
+                on_deleted: None,
             },
         )
         .expect_err("containment must stop deletion");
@@ -21,6 +21,12 @@
 //!   production telemetry, always within the profile-clamped safety
 //!   limit.
 //!
+//! Operator pins (`--streams` / `--chunk-size`) arrive as a wire
+//! [`DialRequest`] and are folded in at construction
+//! ([`TransferDial::with_request`]): a pinned axis starts at the
+//! requested value, clamped to the same receiver-bounded ceilings, and
+//! the tuner never moves it.
+//!
 //! This replaces the size-keyed `determine_remote_tuning` static
 //! ladder: byte and stream concurrency both start conservatively and
 //! ramp on evidence instead of guessing from workload shape.
@@ -29,7 +35,7 @@ use std::collections::HashMap;
 use std::sync::atomic::{AtomicI32, AtomicU32, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
-use crate::generated::CapacityProfile;
+use crate::generated::{CapacityProfile, DialRequest};
 pub use crate::remote::transfer::progress::SharedStreamProbes;
 use crate::remote::transfer::progress::{StreamId, StreamProbe, StreamProbeRegistry};
 
@@ -275,6 +281,23 @@ pub fn receiver_initial_streams(profile: Option<&CapacityProfile>) -> usize {
     DIAL_FLOOR_INITIAL_STREAMS.min(receiver_stream_ceiling(profile))
 }
 
+/// The operator's requested stream count, if any (`0` = unset).
+pub fn requested_streams(request: Option<&DialRequest>) -> Option<usize> {
+    request.and_then(|request| (request.streams > 0).then_some(request.streams as usize))
+}
+
+/// Epoch-0 stream count for a session: the operator's `--streams` pin
+/// clamped to the receiver's ceiling, else the conservative
+/// [`receiver_initial_streams`] floor. The responder sizes its grant
+/// and the SOURCE validates that grant with this one function, so the
+/// two ends cannot disagree about the socket count.
+pub fn epoch0_streams(profile: Option<&CapacityProfile>, request: Option<&DialRequest>) -> usize {
+    match requested_streams(request) {
+        Some(streams) => streams.clamp(1, receiver_stream_ceiling(profile)),
+        None => receiver_initial_streams(profile),
+    }
+}
+
 /// Serialized wire-epoch state. Resize proposals are rare (at most one per
 /// control-lane round trip), so one short critical section is preferable to
 /// a split-atomic check/CAS sequence that can reopen a refused transfer or
@@ -418,6 +441,10 @@ pub struct TransferDial {
     ceiling_prefetch: usize,
     ceiling_max_streams: usize,
     ceiling_tcp_buffer_bytes: usize,
+    /// `--streams`: membership is fixed at epoch 0; no resize proposals.
+    streams_pinned: bool,
+    /// `--chunk-size`: the cheap-dial steps leave `chunk_bytes` alone.
+    chunk_pinned: bool,
     observer: Option<DialObserver>,
 }
 
@@ -491,10 +518,37 @@ impl TransferDial {
             ceiling_prefetch,
             ceiling_max_streams: ceiling_streams,
             ceiling_tcp_buffer_bytes: ceiling_tcp,
+            streams_pinned: false,
+            chunk_pinned: false,
             observer: None,
         }
     }
 
+    /// Fold the operator's [`DialRequest`] pins into a freshly built
+    /// dial. Each pin is clamped to the bounds the receiver profile
+    /// already set (streams to `1..=ceiling_max_streams`, chunk to
+    /// [`crate::buffer::DATA_PLANE_BUFFER_FLOOR`]`..=` the chunk
+    /// ceiling) — a pin can choose a point inside the safety envelope,
+    /// never widen it. Unset (`0`) axes stay adaptive.
+    pub fn with_request(mut self, request: Option<&DialRequest>) -> Self {
+        if let Some(streams) = requested_streams(request) {
+            let streams = streams.clamp(1, self.ceiling_max_streams.max(1));
+            self.initial_streams = AtomicUsize::new(streams);
+            self.live_streams = AtomicUsize::new(streams);
+            self.peak_streams = AtomicUsize::new(streams);
+            self.streams_pinned = true;
+        }
+        if let Some(chunk) = request.map(|r| r.chunk_bytes).filter(|&c| c > 0) {
+            let chunk = usize::try_from(chunk).unwrap_or(usize::MAX).clamp(
+                crate::buffer::DATA_PLANE_BUFFER_FLOOR.min(self.ceiling_chunk_bytes),
+                self.ceiling_chunk_bytes,
+            );
+            self.chunk_bytes = AtomicUsize::new(chunk);
+            self.chunk_pinned = true;
+        }
+        self
+    }
+
     /// Attach the optional aggregate observer before sharing this dial.
     /// A disabled trace leaves this `None`, so the hot path performs no
     /// event allocation or role-dependent work.
@@ -578,14 +632,17 @@ impl TransferDial {
             .refused
     }
 
+    /// A pinned chunk counts as "at its bound" in both directions, so a
+    /// `--chunk-size` pin alone never blocks stream escalation.
     fn cheap_dials_maxed(&self) -> bool {
-        self.chunk_bytes.load(Ordering::Relaxed) >= self.ceiling_chunk_bytes
+        (self.chunk_pinned || self.chunk_bytes.load(Ordering::Relaxed) >= self.ceiling_chunk_bytes)
             && self.prefetch_count.load(Ordering::Relaxed) >= self.ceiling_prefetch
     }
 
     fn cheap_dials_floored(&self) -> bool {
-        self.chunk_bytes.load(Ordering::Relaxed)
-            <= DIAL_FLOOR_CHUNK_BYTES.min(self.ceiling_chunk_bytes)
+        (self.chunk_pinned
+            || self.chunk_bytes.load(Ordering::Relaxed)
+                <= DIAL_FLOOR_CHUNK_BYTES.min(self.ceiling_chunk_bytes))
             && self.prefetch_count.load(Ordering::Relaxed)
                 <= DIAL_FLOOR_PREFETCH.min(self.ceiling_prefetch).max(1)
     }
@@ -622,6 +679,10 @@ impl TransferDial {
         if state.refused || state.pending.is_some() {
             return None;
         }
+        if self.streams_pinned {
+            // `--streams`: the operator fixed membership; epoch 0 is final.
+            return Some((None, DialDecisionReason::Bound));
+        }
         #[cfg(test)]
         let test_hook = self
             .resize_tick_test_hook
@@ -845,7 +906,7 @@ impl TransferDial {
         let mut moved = false;
         let chunk = self.chunk_bytes.load(Ordering::Relaxed);
         let next = (chunk.saturating_mul(2)).min(self.ceiling_chunk_bytes);
-        if next > chunk {
+        if !self.chunk_pinned && next > chunk {
             self.chunk_bytes.store(next, Ordering::Relaxed);
             moved = true;
         }
@@ -870,7 +931,7 @@ impl TransferDial {
         let mut moved = false;
         let chunk = self.chunk_bytes.load(Ordering::Relaxed);
         let next = (chunk / 2).max(DIAL_FLOOR_CHUNK_BYTES.min(self.ceiling_chunk_bytes));
-        if next < chunk {
+        if !self.chunk_pinned && next < chunk {
             self.chunk_bytes.store(next, Ordering::Relaxed);
             moved = true;
         }
@@ -1181,6 +1242,69 @@ mod tests {
         assert_eq!(bounded.live_streams(), 2);
     }
 
+    #[test]
+    fn stream_pin_replaces_the_floor_within_the_receiver_ceiling() {
+        let pin = |streams| DialRequest {
+            streams,
+            chunk_bytes: 0,
+        };
+        assert_eq!(epoch0_streams(None, Some(&pin(6))), 6);
+        assert_eq!(epoch0_streams(None, Some(&pin(0))), 4, "0 = unset");
+        assert_eq!(epoch0_streams(None, None), 4);
+        assert_eq!(
+            epoch0_streams(Some(&profile(3, 0, 0)), Some(&pin(8))),
+            3,
+            "clamped to the receiver ceiling"
+        );
+        assert_eq!(
+            epoch0_streams(None, Some(&pin(999))),
+            DIAL_DEFAULT_STREAM_LIMIT
+        );
+
+        let dial =
+            TransferDial::conservative_within(Some(&profile(3, 0, 0))).with_request(Some(&pin(8)));
+        assert_eq!(dial.initial_streams(), 3);
+        assert_eq!(dial.live_streams(), 3);
+    }
+
+    #[test]
+    fn pinned_streams_never_propose_a_resize() {
+        let dial = TransferDial::conservative().with_request(Some(&DialRequest {
+            streams: 4,
+            chunk_bytes: 0,
+        }));
+        while dial.step_up_cheap_dials() {}
+        for _ in 0..(RESIZE_COOLDOWN_TICKS * 4) {
+            assert_eq!(dial.resize_tick(1 << 20, 0.0), None);
+        }
+        assert_eq!(dial.live_streams(), 4);
+    }
+
+    #[test]
+    fn chunk_pin_is_clamped_and_survives_tuner_steps() {
+        let dial = TransferDial::conservative().with_request(Some(&DialRequest {
+            streams: 0,
+            chunk_bytes: 4 * MIB as u64,
+        }));
+        assert_eq!(dial.chunk_bytes(), 4 * MIB);
+        while dial.step_up_cheap_dials() {}
+        assert_eq!(dial.chunk_bytes(), 4 * MIB, "step-up leaves the pin");
+        assert_eq!(dial.prefetch_count(), DIAL_CEILING_PREFETCH);
+        dial.step_down_cheap_dials();
+        assert_eq!(dial.chunk_bytes(), 4 * MIB, "step-down leaves the pin");
+
+        let huge = TransferDial::conservative().with_request(Some(&DialRequest {
+            streams: 0,
+            chunk_bytes: u64::MAX,
+        }));
+        assert_eq!(huge.chunk_bytes(), DIAL_CEILING_CHUNK_BYTES);
+        let tiny = TransferDial::conservative().with_request(Some(&DialRequest {
+            streams: 0,
+            chunk_bytes: 1,
+        }));
+        assert_eq!(tiny.chunk_bytes(), crate::buffer::DATA_PLANE_BUFFER_FLOOR);
+    }
+
     #[test]
     fn unknown_profile_fields_keep_default_ceilings() {
         let dial = TransferDial::conservative_within(Some(&profile(0, 0, 0)));
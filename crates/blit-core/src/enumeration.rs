@@ -5,6 +5,7 @@ use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 use crate::fs_enum::{FileEntry, FileFilter};
+use crate::manifest_checkpoint::ManifestCheckpoint;
 
 /// Describes the kind of entry returned by the enumerator.
 #[derive(Debug, Clone)]
@@ -40,6 +41,42 @@ pub struct SuppressedScanError {
 #[derive(Debug, Default, Clone)]
 pub struct EnumerationOutcome {
     pub suppressed_errors: Vec<SuppressedScanError>,
+    /// [`FileEnumerator::enumerate_local_checkpointed`] only:
+    /// directories read from disk, and directories whose listing came
+    /// from the checkpoint instead.
+    pub dirs_listed: u64,
+    pub dirs_from_checkpoint: u64,
+}
+
+/// Which symlinks the walk dereferences (`--copy-links` /
+/// `--copy-dirlinks`). A dereferenced link is reported as what it points
+/// at — a followed directory link is descended into as a real directory,
+/// a followed file link is a regular file — under the link's own path.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SymlinkDeref {
+    /// Symlinks are not followed (the default).
+    #[default]
+    None,
+    /// Follow symlinks that point at directories (`--copy-dirlinks`).
+    Dirs,
+    /// Follow every symlink (`--copy-links`).
+    All,
+    /// Follow only symlinks whose referent resolves outside the walk
+    /// root (`--copy-unsafe-links`); links within the tree are left
+    /// alone.
+    Unsafe,
+}
+
+/// The order the walk visits a directory's entries in (`--sort-order`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Whatever order the filesystem lists them in (the default, and
+    /// the fastest).
+    #[default]
+    None,
+    /// Byte-wise by file name, so identical trees enumerate — and shard
+    /// — identically whatever their on-disk listing order.
+    Name,
 }
 
 /// Result of filesystem enumeration. `absolute_path` is the full path on disk,
@@ -62,6 +99,9 @@ pub struct FileEnumerator {
     filter: FileFilter,
     follow_symlinks: bool,
     include_symlinks: bool,
+    deref: SymlinkDeref,
+    safe_links: bool,
+    sort: SortOrder,
 }
 
 impl FileEnumerator {
@@ -70,6 +110,9 @@ impl FileEnumerator {
             filter,
             follow_symlinks: false,
             include_symlinks: false,
+            deref: SymlinkDeref::None,
+            safe_links: false,
+            sort: SortOrder::None,
         }
     }
 
@@ -86,6 +129,30 @@ impl FileEnumerator {
         self
     }
 
+    /// Configure which symlinks are dereferenced. Unlike
+    /// [`follow_symlinks`](Self::follow_symlinks), a link whose target
+    /// directory already encloses the walk position (a symlink loop) is
+    /// skipped instead of ending that subtree's scan with an error.
+    pub fn deref_symlinks(mut self, deref: SymlinkDeref) -> Self {
+        self.deref = deref;
+        self
+    }
+
+    /// Drop symlinks whose referent resolves outside the walk root
+    /// (`--safe-links`) instead of dereferencing them, whatever the
+    /// [`SymlinkDeref`] policy would otherwise do with them.
+    pub fn safe_links(mut self, safe: bool) -> Self {
+        self.safe_links = safe;
+        self
+    }
+
+    /// Visit each directory's entries in `sort` order (`--sort-order`).
+    /// Parents still precede their children either way.
+    pub fn sort_order(mut self, sort: SortOrder) -> Self {
+        self.sort = sort;
+        self
+    }
+
     /// Enumerate local filesystem entries beneath `root`, applying the
     /// configured filters.
     ///
@@ -125,8 +192,9 @@ impl FileEnumerator {
     /// Enumerate entries and invoke `visit` for each discovered item.
     ///
     /// **Suppressed errors**: non-root WalkDir errors (e.g.
-    /// permission-denied on a child directory) are silently
-    /// skipped to keep the scan resilient. Callers that need to
+    /// permission-denied on a child directory, or a child that cannot
+    /// be stat'ed) are silently skipped to keep the scan resilient; an
+    /// unreadable root still fails the walk. Callers that need to
     /// know about these — most importantly anything driving
     /// mirror-deletion, where "not seen during scan" must NOT mean
     /// "delete from destination" — must use
@@ -168,16 +236,220 @@ impl FileEnumerator {
 
         let filter = self.filter.clone_without_cache();
         let mut outcome = EnumerationOutcome::default();
+        let mut followed = Vec::new();
+        // Resolved once per walk for the dereference policies' in-tree
+        // test, not once per symlink.
+        let canonical_root = if self.deref == SymlinkDeref::None {
+            None
+        } else {
+            Some(
+                fs::canonicalize(root)
+                    .with_context(|| format!("resolve root {}", root.display()))?,
+            )
+        };
+        self.walk(
+            root,
+            root,
+            canonical_root.as_deref(),
+            &filter,
+            &mut outcome,
+            &mut followed,
+            &mut visit,
+        )?;
+        Ok(outcome)
+    }
+
+    /// [`enumerate_local_streaming_capturing`], taking each unchanged
+    /// directory's listing from `checkpoint` and recording the ones it
+    /// has to read (see [`crate::manifest_checkpoint`]). Entries, order
+    /// and filtering match the plain walk. Symlink following and
+    /// dereferencing are not supported here; with either configured, or
+    /// a single-file root, this is the plain walk.
+    ///
+    /// [`enumerate_local_streaming_capturing`]: Self::enumerate_local_streaming_capturing
+    pub fn enumerate_local_checkpointed<F>(
+        &self,
+        root: &Path,
+        checkpoint: &mut ManifestCheckpoint,
+        mut visit: F,
+    ) -> Result<EnumerationOutcome>
+    where
+        F: FnMut(EnumeratedEntry) -> Result<()>,
+    {
+        if self.follow_symlinks || self.deref != SymlinkDeref::None || !root.is_dir() {
+            return self.enumerate_local_streaming_capturing(root, visit);
+        }
+        let filter = self.filter.clone_without_cache();
+        let mut outcome = EnumerationOutcome::default();
+        let metadata =
+            fs::metadata(root).with_context(|| format!("stat directory {}", root.display()))?;
+        let names = self
+            .directory_names(root, "", &metadata, checkpoint, &mut outcome)
+            .with_context(|| format!("reading directory {}", root.display()))?;
+        self.walk_listed(
+            root,
+            root,
+            names,
+            &filter,
+            checkpoint,
+            &mut outcome,
+            &mut visit,
+        )?;
+        Ok(outcome)
+    }
+
+    /// The names in directory `dir` (`rel` from the root): from the
+    /// checkpoint when unchanged, else read and recorded.
+    fn directory_names(
+        &self,
+        dir: &Path,
+        rel: &str,
+        metadata: &Metadata,
+        checkpoint: &mut ManifestCheckpoint,
+        outcome: &mut EnumerationOutcome,
+    ) -> std::io::Result<Vec<std::ffi::OsString>> {
+        if let Some(names) = checkpoint.listing(rel, metadata) {
+            outcome.dirs_from_checkpoint += 1;
+            return Ok(names.iter().map(Into::into).collect());
+        }
+        let names = fs::read_dir(dir)?
+            .map(|entry| entry.map(|entry| entry.file_name()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        outcome.dirs_listed += 1;
+        // A name that is not UTF-8 cannot be recorded; its directory is
+        // simply read again next time.
+        let utf8: Option<Vec<String>> = names
+            .iter()
+            .map(|n| n.to_str().map(str::to_string))
+            .collect();
+        if let Some(utf8) = utf8 {
+            if let Err(err) = checkpoint.record(rel, metadata, &utf8) {
+                log::warn!("manifest checkpoint: {err:#}");
+            }
+        }
+        Ok(names)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn walk_listed<F>(
+        &self,
+        root: &Path,
+        dir: &Path,
+        mut names: Vec<std::ffi::OsString>,
+        filter: &FileFilter,
+        checkpoint: &mut ManifestCheckpoint,
+        outcome: &mut EnumerationOutcome,
+        visit: &mut F,
+    ) -> Result<()>
+    where
+        F: FnMut(EnumeratedEntry) -> Result<()>,
+    {
+        if self.sort == SortOrder::Name {
+            names.sort();
+        }
+        for name in names {
+            let path = dir.join(&name);
+            let metadata = match fs::symlink_metadata(&path) {
+                Ok(metadata) => metadata,
+                // Gone since the listing was taken.
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    outcome.suppressed_errors.push(SuppressedScanError {
+                        path: path.display().to_string(),
+                        kind: Some(err.kind()),
+                        message: err.to_string(),
+                    });
+                    continue;
+                }
+            };
+            let rel = relative_path(root, &path);
+
+            if metadata.is_dir() {
+                if !filter.allows_dir(&path) || !filter.allows_attributes(&metadata) {
+                    continue;
+                }
+                let rel_posix = crate::path_posix::relative_path_to_posix(&rel);
+                let children =
+                    self.directory_names(&path, &rel_posix, &metadata, checkpoint, outcome);
+                visit(EnumeratedEntry {
+                    absolute_path: path.clone(),
+                    relative_path: rel,
+                    metadata,
+                    kind: EntryKind::Directory,
+                })?;
+                match children {
+                    Ok(children) => {
+                        self.walk_listed(root, &path, children, filter, checkpoint, outcome, visit)?
+                    }
+                    Err(err) => outcome.suppressed_errors.push(SuppressedScanError {
+                        path: path.display().to_string(),
+                        kind: Some(err.kind()),
+                        message: err.to_string(),
+                    }),
+                }
+            } else if metadata.is_file() {
+                let size = metadata.len();
+                if !filter.allows_entry(Some(&rel), &path, size, metadata.modified().ok())
+                    || !filter.allows_attributes(&metadata)
+                {
+                    continue;
+                }
+                visit(EnumeratedEntry {
+                    absolute_path: path,
+                    relative_path: rel,
+                    metadata,
+                    kind: EntryKind::File { size },
+                })?;
+            } else if metadata.file_type().is_symlink() && self.include_symlinks {
+                if !filter.allows_entry(Some(&rel), &path, 0, metadata.modified().ok())
+                    || !filter.allows_attributes(&metadata)
+                {
+                    continue;
+                }
+                let target = fs::read_link(&path).ok();
+                visit(EnumeratedEntry {
+                    absolute_path: path,
+                    relative_path: rel,
+                    metadata,
+                    kind: EntryKind::Symlink { target },
+                })?;
+            }
+        }
+        Ok(())
+    }
 
-        let mut walker = WalkDir::new(root)
-            .follow_links(self.follow_symlinks)
-            .into_iter();
+    /// Walk `walk_root` — `root` itself, or a dereferenced directory link
+    /// beneath it — reporting paths relative to `root`. `canonical_root`
+    /// is `root` resolved, present whenever a [`SymlinkDeref`] policy is
+    /// set. `followed` holds the canonical parent directory of every
+    /// directory link being descended through, which is what the loop
+    /// check tests against.
+    fn walk<F>(
+        &self,
+        root: &Path,
+        walk_root: &Path,
+        canonical_root: Option<&Path>,
+        filter: &FileFilter,
+        outcome: &mut EnumerationOutcome,
+        followed: &mut Vec<PathBuf>,
+        visit: &mut F,
+    ) -> Result<()>
+    where
+        F: FnMut(EnumeratedEntry) -> Result<()>,
+    {
+        let mut walker = WalkDir::new(walk_root).follow_links(self.follow_symlinks);
+        if self.sort == SortOrder::Name {
+            walker = walker.sort_by_file_name();
+        }
+        let mut walker = walker.into_iter();
 
         while let Some(next) = walker.next() {
             let entry = match next {
                 Ok(e) => e,
                 Err(err) => {
-                    if err.depth() == 0 {
+                    // A followed directory link's own root is a child of
+                    // the scan, so only the real root's failure is fatal.
+                    if err.depth() == 0 && walk_root == root {
                         return Err(err.into());
                     }
                     // Non-root walkdir error: capture it so callers
@@ -216,7 +488,9 @@ impl FileEnumerator {
                     let size = metadata.len();
                     let mtime = metadata.modified().ok();
                     let rel = PathBuf::new();
-                    if filter.allows_entry(Some(&rel), path, size, mtime) {
+                    if filter.allows_entry(Some(&rel), path, size, mtime)
+                        && filter.allows_attributes(&metadata)
+                    {
                         visit(EnumeratedEntry {
                             absolute_path: path.to_path_buf(),
                             // Empty relative path so src_root.join(rel) === src_root
@@ -236,9 +510,14 @@ impl FileEnumerator {
                     continue;
                 }
 
-                let metadata = entry
-                    .metadata()
-                    .with_context(|| format!("stat directory {}", path.display()))?;
+                let Some(metadata) = stat_child(&entry, outcome) else {
+                    walker.skip_current_dir();
+                    continue;
+                };
+                if !filter.allows_attributes(&metadata) {
+                    walker.skip_current_dir();
+                    continue;
+                }
 
                 visit(EnumeratedEntry {
                     absolute_path: path.to_path_buf(),
@@ -247,14 +526,16 @@ impl FileEnumerator {
                     kind: EntryKind::Directory,
                 })?;
             } else if entry.file_type().is_file() {
-                let metadata = entry
-                    .metadata()
-                    .with_context(|| format!("stat file {}", path.display()))?;
+                let Some(metadata) = stat_child(&entry, outcome) else {
+                    continue;
+                };
                 let size = metadata.len();
                 let mtime = metadata.modified().ok();
                 let rel = relative_path(root, path);
 
-                if !filter.allows_entry(Some(&rel), path, size, mtime) {
+                if !filter.allows_entry(Some(&rel), path, size, mtime)
+                    || !filter.allows_attributes(&metadata)
+                {
                     continue;
                 }
 
@@ -264,17 +545,34 @@ impl FileEnumerator {
                     metadata,
                     kind: EntryKind::File { size },
                 })?;
+            } else if entry.file_type().is_symlink()
+                && !self.follow_symlinks
+                && self.deref != SymlinkDeref::None
+                && self.visit_dereferenced(
+                    root,
+                    canonical_root,
+                    path,
+                    filter,
+                    outcome,
+                    followed,
+                    visit,
+                )?
+            {
+                // Reported (or deliberately skipped) as what it points at.
             } else if entry.file_type().is_symlink() && self.include_symlinks {
                 if self.follow_symlinks {
                     continue;
                 }
 
-                let metadata = fs::symlink_metadata(path)
-                    .with_context(|| format!("symlink metadata {}", path.display()))?;
+                let Some(metadata) = stat_child(&entry, outcome) else {
+                    continue;
+                };
                 let mtime = metadata.modified().ok();
                 let rel = relative_path(root, path);
 
-                if !filter.allows_entry(Some(&rel), path, 0, mtime) {
+                if !filter.allows_entry(Some(&rel), path, 0, mtime)
+                    || !filter.allows_attributes(&metadata)
+                {
                     continue;
                 }
 
@@ -289,7 +587,130 @@ impl FileEnumerator {
             }
         }
 
-        Ok(outcome)
+        Ok(())
+    }
+
+    /// Apply the [`SymlinkDeref`] policy to the symlink at `path`. Returns
+    /// false when the policy leaves this link alone (a file link under
+    /// `Dirs`, an in-tree link under `Unsafe`), so the caller falls back
+    /// to its plain symlink handling.
+    fn visit_dereferenced<F>(
+        &self,
+        root: &Path,
+        canonical_root: Option<&Path>,
+        path: &Path,
+        filter: &FileFilter,
+        outcome: &mut EnumerationOutcome,
+        followed: &mut Vec<PathBuf>,
+        visit: &mut F,
+    ) -> Result<bool>
+    where
+        F: FnMut(EnumeratedEntry) -> Result<()>,
+    {
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            // A dangling link points at no directory, so `Dirs` has
+            // nothing to follow; `All` was asked to copy its referent
+            // and could not, which is a gap in the scan.
+            Err(err) if self.deref == SymlinkDeref::All => {
+                outcome.suppressed_errors.push(SuppressedScanError {
+                    path: path.display().to_string(),
+                    kind: Some(err.kind()),
+                    message: format!("symlink has no referent: {err}"),
+                });
+                return Ok(true);
+            }
+            Err(_) => return Ok(false),
+        };
+        let rel = relative_path(root, path);
+        let target = fs::canonicalize(path)
+            .with_context(|| format!("resolve symlink {}", path.display()))?;
+        // The walk resolves the root whenever a policy is set, and this
+        // runs only under one.
+        let inside = canonical_root.is_some_and(|root| target.starts_with(root));
+        if self.deref == SymlinkDeref::Unsafe && inside {
+            return Ok(false);
+        }
+        if self.safe_links && !inside {
+            log::warn!(
+                "skipping unsafe symlink {} -> {}",
+                path.display(),
+                target.display()
+            );
+            return Ok(true);
+        }
+
+        if metadata.is_dir() {
+            if !filter.allows_dir(path) || !filter.allows_attributes(&metadata) {
+                return Ok(true);
+            }
+            let parent = path
+                .parent()
+                .map(fs::canonicalize)
+                .transpose()
+                .with_context(|| format!("resolve parent of {}", path.display()))?
+                .unwrap_or_default();
+            // Descending into a directory that encloses where the walk
+            // already is would reach this link again, forever.
+            if followed
+                .iter()
+                .chain(std::iter::once(&parent))
+                .any(|dir| dir.starts_with(&target))
+            {
+                log::warn!(
+                    "skipping symlink loop {} -> {}",
+                    path.display(),
+                    target.display()
+                );
+                return Ok(true);
+            }
+            visit(EnumeratedEntry {
+                absolute_path: path.to_path_buf(),
+                relative_path: rel,
+                metadata,
+                kind: EntryKind::Directory,
+            })?;
+            followed.push(parent);
+            let walked = self.walk(root, path, canonical_root, filter, outcome, followed, visit);
+            followed.pop();
+            walked?;
+            return Ok(true);
+        }
+
+        if self.deref == SymlinkDeref::Dirs || !metadata.is_file() {
+            return Ok(false);
+        }
+        let size = metadata.len();
+        if filter.allows_entry(Some(&rel), path, size, metadata.modified().ok())
+            && filter.allows_attributes(&metadata)
+        {
+            visit(EnumeratedEntry {
+                absolute_path: path.to_path_buf(),
+                relative_path: rel,
+                metadata,
+                kind: EntryKind::File { size },
+            })?;
+        }
+        Ok(true)
+    }
+}
+
+/// Stat a non-root entry the walk listed. An entry that cannot be
+/// stat'ed — EACCES under a directory that is readable but not
+/// searchable, say — is recorded as suppressed rather than failing the
+/// whole scan; one that vanished since the listing is simply gone.
+fn stat_child(entry: &walkdir::DirEntry, outcome: &mut EnumerationOutcome) -> Option<Metadata> {
+    match entry.metadata() {
+        Ok(metadata) => Some(metadata),
+        Err(err) if err.io_error().map(|e| e.kind()) == Some(std::io::ErrorKind::NotFound) => None,
+        Err(err) => {
+            outcome.suppressed_errors.push(SuppressedScanError {
+                path: entry.path().display().to_string(),
+                kind: err.io_error().map(|e| e.kind()),
+                message: err.to_string(),
+            });
+            None
+        }
     }
 }
 
@@ -390,4 +811,131 @@ mod tests {
             "excluded file at root should be skipped"
         );
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn unreadable_subdirectory_is_recorded_and_siblings_still_enumerate() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = tempdir().unwrap();
+        let root = tmp.path().join("src");
+        for dir in ["locked", "opaque", "z"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in ["a.txt", "locked/inner.txt", "opaque/hidden.txt", "z/ok.txt"] {
+            std::fs::write(root.join(file), file).unwrap();
+        }
+        let chmod = |rel: &str, mode: u32| {
+            std::fs::set_permissions(root.join(rel), std::fs::Permissions::from_mode(mode)).unwrap()
+        };
+        // `locked` cannot be listed; `opaque` lists but nothing in it
+        // can be stat'ed.
+        chmod("locked", 0o000);
+        chmod("opaque", 0o444);
+
+        let enumerator = FileEnumerator::new(FileFilter::default());
+        let walked = enumerator.enumerate_local_capturing(&root);
+        let unlisted = std::fs::read_dir(root.join("locked")).is_err();
+        chmod("locked", 0o755);
+        chmod("opaque", 0o755);
+        if !unlisted {
+            eprintln!("skipping: permissions are not enforced for this user");
+            return;
+        }
+
+        let (entries, outcome) = walked.expect("a subdirectory must not fail the walk");
+        let paths: Vec<_> = entries.iter().map(|e| e.relative_path.clone()).collect();
+        for seen in ["a.txt", "locked", "opaque", "z", "z/ok.txt"] {
+            assert!(paths.contains(&PathBuf::from(seen)), "{seen}: {paths:?}");
+        }
+        assert!(!paths.contains(&PathBuf::from("opaque/hidden.txt")));
+        let suppressed: Vec<_> = outcome.suppressed_errors.iter().map(|e| &e.path).collect();
+        assert_eq!(suppressed.len(), 2, "{suppressed:?}");
+        assert!(outcome
+            .suppressed_errors
+            .iter()
+            .all(|e| e.kind == Some(std::io::ErrorKind::PermissionDenied)));
+        assert!(suppressed.iter().any(|p| p.ends_with("locked")));
+        assert!(suppressed.iter().any(|p| p.ends_with("hidden.txt")));
+
+        // The root itself is not a subtree to skip: it fails the scan.
+        chmod("", 0o000);
+        let err = enumerator.enumerate_local_capturing(&root);
+        chmod("", 0o755);
+        assert!(err.is_err(), "an unreadable root must fail the walk");
+    }
+
+    #[test]
+    fn checkpointed_walk_resumes_from_listings_recorded_before_an_interruption() {
+        use crate::manifest_checkpoint::ManifestCheckpoint;
+        use std::collections::BTreeSet;
+
+        let tmp = tempdir().unwrap();
+        let root = tmp.path().join("src");
+        for dir in ["a/b", "c", "d"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in [
+            "top.txt",
+            "a/one.txt",
+            "a/b/two.txt",
+            "c/three.txt",
+            "d/four.txt",
+        ] {
+            std::fs::write(root.join(file), file).unwrap();
+        }
+        // Settle every directory past the racy window so it is recorded.
+        let past = filetime::FileTime::from_unix_time(1_600_000_000, 0);
+        for dir in ["", "a", "a/b", "c", "d"] {
+            filetime::set_file_mtime(root.join(dir), past).unwrap();
+        }
+        let ckpt = tmp.path().join("walk.ckpt");
+        let enumerator = FileEnumerator::new(FileFilter::default());
+        let paths = |entries: Vec<EnumeratedEntry>| -> BTreeSet<PathBuf> {
+            entries.into_iter().map(|e| e.relative_path).collect()
+        };
+
+        // Interrupted after a few entries, as a dead push would be.
+        let mut checkpoint = ManifestCheckpoint::open(&ckpt, &root).unwrap();
+        let mut seen = 0;
+        let err = enumerator.enumerate_local_checkpointed(&root, &mut checkpoint, |_| {
+            seen += 1;
+            if seen == 3 {
+                eyre::bail!("interrupted");
+            }
+            Ok(())
+        });
+        assert!(err.is_err());
+        drop(checkpoint);
+
+        // The re-run reads fewer directories and still sees every entry.
+        let mut checkpoint = ManifestCheckpoint::open(&ckpt, &root).unwrap();
+        let mut entries = Vec::new();
+        let outcome = enumerator
+            .enumerate_local_checkpointed(&root, &mut checkpoint, |e| {
+                entries.push(e);
+                Ok(())
+            })
+            .unwrap();
+        assert!(outcome.dirs_from_checkpoint > 0, "{outcome:?}");
+        assert!(outcome.dirs_listed < 5, "{outcome:?}");
+        assert_eq!(outcome.dirs_from_checkpoint + outcome.dirs_listed, 5);
+        assert_eq!(
+            paths(entries),
+            paths(enumerator.enumerate_local(&root).unwrap())
+        );
+
+        // A change to one directory re-reads just that one.
+        std::fs::write(root.join("c/new.txt"), b"new").unwrap();
+        let mut checkpoint = ManifestCheckpoint::open(&ckpt, &root).unwrap();
+        let mut entries = Vec::new();
+        let outcome = enumerator
+            .enumerate_local_checkpointed(&root, &mut checkpoint, |e| {
+                entries.push(e);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!((outcome.dirs_listed, outcome.dirs_from_checkpoint), (1, 4));
+        assert!(paths(entries).contains(Path::new("c/new.txt")));
+    }
 }
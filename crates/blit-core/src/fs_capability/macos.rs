@@ -1,8 +1,9 @@
 //! macOS filesystem capability implementation
 
 use super::{Capabilities, FastCopyResult, FilesystemCapability, MetadataPreserved};
-use eyre::Result;
+use eyre::{Context, Result};
 use std::path::Path;
+use std::time::SystemTime;
 
 pub struct MacOSCapability {
     capabilities: Capabilities,
@@ -27,6 +28,7 @@ impl MacOSCapability {
                 block_clone_same_volume: false,
                 filesystem_type: None,
                 reflink: false,
+                creation_time: true,
             },
         }
     }
@@ -101,6 +103,64 @@ impl FilesystemCapability for MacOSCapability {
 
         Ok(FastCopyResult::Fallback)
     }
+
+    fn set_creation_time(&self, path: &Path, created: SystemTime) -> Result<bool> {
+        use std::os::macos::fs::FileTimesExt;
+
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("open {} to set creation time", path.display()))?;
+        file.set_times(std::fs::FileTimes::new().set_created(created))
+            .with_context(|| format!("set creation time on {}", path.display()))?;
+        Ok(true)
+    }
+
+    fn preallocate(&self, file: &std::fs::File, len: u64) -> Result<bool> {
+        use std::os::unix::io::AsRawFd;
+
+        // Contiguous first, then any layout — F_PREALLOCATE's own
+        // documented fallback order.
+        for flags in [libc::F_ALLOCATECONTIG, libc::F_ALLOCATEALL] {
+            let mut store = libc::fstore_t {
+                fst_flags: flags,
+                fst_posmode: libc::F_PEOFPOSMODE,
+                fst_offset: 0,
+                fst_length: len as libc::off_t,
+                fst_bytesalloc: 0,
+            };
+            let rc = unsafe { libc::fcntl(file.as_raw_fd(), libc::F_PREALLOCATE, &mut store) };
+            if rc != -1 {
+                return Ok(true);
+            }
+        }
+        let err = std::io::Error::last_os_error();
+        match err.raw_os_error() {
+            Some(libc::ENOTSUP) | Some(libc::EINVAL) => Ok(false),
+            _ => Err(err).with_context(|| format!("F_PREALLOCATE {len} bytes")),
+        }
+    }
+
+    fn reflink(&self, src: &Path, dst: &Path) -> Result<bool> {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        // clonefile(2) refuses to replace an existing destination.
+        match std::fs::remove_file(dst) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err).with_context(|| format!("remove {}", dst.display())),
+        }
+        let src_c = CString::new(src.as_os_str().as_bytes())?;
+        let dst_c = CString::new(dst.as_os_str().as_bytes())?;
+        let rc = unsafe { libc::clonefile(src_c.as_ptr(), dst_c.as_ptr(), 0) };
+        if rc == 0 {
+            return Ok(true);
+        }
+        let err = std::io::Error::last_os_error();
+        match err.raw_os_error() {
+            Some(libc::ENOTSUP) | Some(libc::EXDEV) => Ok(false),
+            _ => Err(err).with_context(|| format!("clonefile {}", dst.display())),
+        }
+    }
 }
 
 fn attempt_clonefile(src: &Path, dst: &Path) -> Result<bool> {
@@ -13,6 +13,7 @@ use std::collections::HashMap;
 use std::path::Path;
 #[cfg(unix)]
 use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
 
 #[cfg(target_os = "macos")]
 mod macos;
@@ -44,6 +45,32 @@ pub trait FilesystemCapability {
 
     /// Fast copy using OS-specific primitives
     fn fast_copy(&self, src: &Path, dst: &Path) -> Result<FastCopyResult>;
+
+    /// Set the creation (birth) time of the file at `path`. Returns
+    /// `Ok(false)` where the platform cannot set one (Linux records a
+    /// birth time but offers no call to change it).
+    fn set_creation_time(&self, path: &Path, created: SystemTime) -> Result<bool> {
+        let _ = (path, created);
+        Ok(false)
+    }
+
+    /// Reserve `len` bytes of storage for `file` before it is written.
+    /// Returns `Ok(false)` where the platform or filesystem has no way
+    /// to; an `Err` means the space could not be reserved (out of
+    /// space, quota).
+    fn preallocate(&self, file: &std::fs::File, len: u64) -> Result<bool> {
+        let _ = (file, len);
+        Ok(false)
+    }
+
+    /// Make `dst` a copy-on-write clone of `src`, sharing its extents
+    /// until either side is written. Returns `Ok(false)` where the
+    /// platform or filesystem cannot clone (or `src` and `dst` sit on
+    /// different volumes); `dst` may then have been created empty.
+    fn reflink(&self, src: &Path, dst: &Path) -> Result<bool> {
+        let _ = (src, dst);
+        Ok(false)
+    }
 }
 
 /// What metadata was actually preserved
@@ -70,6 +97,8 @@ pub struct Capabilities {
     pub filesystem_type: Option<String>,
     /// Whether the filesystem supports reflink/clone (CoW copy).
     pub reflink: bool,
+    /// Whether a file's creation time can be set (`--crtimes`).
+    pub creation_time: bool,
 }
 
 /// Result of fast copy attempt
@@ -86,6 +115,96 @@ pub fn get_platform_capability() -> PlatformCapability {
     PlatformCapability::new()
 }
 
+/// Best-effort `--crtimes` apply: stamp the wire's `crtime_seconds` as
+/// the creation time of `path`. A platform that cannot set one skips
+/// silently; a refused set is logged like a refused mtime.
+pub fn apply_creation_time(path: &Path, crtime_seconds: Option<i64>) {
+    let Some(seconds) = crtime_seconds else {
+        return;
+    };
+    let capability = get_platform_capability();
+    if !capability.capabilities().creation_time {
+        return;
+    }
+    let created = crate::wire_metadata::unix_seconds_to_system_time(seconds);
+    if let Err(e) = capability.set_creation_time(path, created) {
+        log::warn!("set creation time on {}: {}", path.display(), e);
+    }
+}
+
+/// `--preallocate`: reserve `len` bytes for a freshly created `file`
+/// so a large copy lays out contiguously and runs out of space before
+/// any byte is streamed rather than midway. Unsupported platforms and
+/// filesystems skip silently.
+pub fn preallocate(file: &std::fs::File, len: u64) -> Result<()> {
+    if len == 0 {
+        return Ok(());
+    }
+    if !get_platform_capability().preallocate(file, len)? {
+        log::debug!("preallocation unsupported; streaming without it");
+    }
+    Ok(())
+}
+
+/// `--reflink-dest`: clone `src` to `dst` copy-on-write, so `dst` holds
+/// the same bytes without writing them and stays independent of `src`
+/// afterwards. `Ok(false)` means no clone was made and the caller must
+/// copy instead.
+pub fn reflink(src: &Path, dst: &Path) -> Result<bool> {
+    get_platform_capability().reflink(src, dst)
+}
+
+/// Before a truncating write to `path`: if it is a regular file that
+/// other names hard-link to, unlink this name so the write lands in a
+/// fresh inode and the other names keep their content — the
+/// replace-not-rewrite semantics of rsync. A missing path or a file with
+/// a single link is left alone. No-op off Unix.
+pub fn detach_hard_link(path: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    if shared_inode(path)? {
+        std::fs::remove_file(path)?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+/// [`detach_hard_link`] for writes that patch the existing file rather
+/// than replace it (resume blocks, metadata repair): a shared file is
+/// first copied to a private inode, mode and mtime included, which is
+/// renamed over this name before anything is touched.
+pub fn unshare_hard_link(path: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    if shared_inode(path)? {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let staged = path.with_file_name(format!(".{name}.blit-unshare"));
+        let mtime = filetime::FileTime::from_last_modification_time(&std::fs::metadata(path)?);
+        let copied = std::fs::copy(path, &staged)
+            .and_then(|_| filetime::set_file_mtime(&staged, mtime))
+            .and_then(|_| std::fs::rename(&staged, path));
+        if copied.is_err() {
+            let _ = std::fs::remove_file(&staged);
+        }
+        copied?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn shared_inode(path: &Path) -> std::io::Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) => Ok(meta.is_file() && meta.nlink() > 1),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
 /// Global cache of probed capabilities keyed by device ID. Only the
 /// unix arm of `cached_probe` can key by device, so the cache itself
 /// is unix-only.
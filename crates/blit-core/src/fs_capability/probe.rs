@@ -37,6 +37,7 @@ fn capabilities_for_filesystem(fs_type: Option<&str>) -> Capabilities {
             block_clone_same_volume: false,
             filesystem_type: Some("apfs".into()),
             reflink: true, // APFS supports clonefile
+            creation_time: true,
         },
         Some("hfs") | Some("hfs+") => Capabilities {
             sparse_files: true,
@@ -48,6 +49,7 @@ fn capabilities_for_filesystem(fs_type: Option<&str>) -> Capabilities {
             block_clone_same_volume: false,
             filesystem_type: Some("hfs+".into()),
             reflink: false,
+            creation_time: true,
         },
         // Linux filesystems
         Some("btrfs") => Capabilities {
@@ -60,6 +62,7 @@ fn capabilities_for_filesystem(fs_type: Option<&str>) -> Capabilities {
             block_clone_same_volume: false,
             filesystem_type: Some("btrfs".into()),
             reflink: true, // btrfs supports FICLONE
+            creation_time: false,
         },
         Some("xfs") => Capabilities {
             sparse_files: true,
@@ -71,6 +74,7 @@ fn capabilities_for_filesystem(fs_type: Option<&str>) -> Capabilities {
             block_clone_same_volume: false,
             filesystem_type: Some("xfs".into()),
             reflink: true, // XFS v5 with reflink enabled
+            creation_time: false,
         },
         Some("ext4") | Some("ext3") | Some("ext2") => Capabilities {
             sparse_files: true,
@@ -82,6 +86,7 @@ fn capabilities_for_filesystem(fs_type: Option<&str>) -> Capabilities {
             block_clone_same_volume: false,
             filesystem_type: fs_type.map(Into::into),
             reflink: false,
+            creation_time: false,
         },
         Some("zfs") => Capabilities {
             sparse_files: true,
@@ -93,6 +98,7 @@ fn capabilities_for_filesystem(fs_type: Option<&str>) -> Capabilities {
             block_clone_same_volume: false,
             filesystem_type: Some("zfs".into()),
             reflink: false, // ZFS block cloning exists but not via standard interfaces
+            creation_time: false,
         },
         Some("tmpfs") => Capabilities {
             sparse_files: true,
@@ -104,6 +110,7 @@ fn capabilities_for_filesystem(fs_type: Option<&str>) -> Capabilities {
             block_clone_same_volume: false,
             filesystem_type: Some("tmpfs".into()),
             reflink: false,
+            creation_time: false,
         },
         Some("nfs") | Some("nfs4") | Some("cifs") | Some("smbfs") => Capabilities {
             sparse_files: false,
@@ -115,6 +122,7 @@ fn capabilities_for_filesystem(fs_type: Option<&str>) -> Capabilities {
             block_clone_same_volume: false,
             filesystem_type: fs_type.map(Into::into),
             reflink: false,
+            creation_time: false,
         },
         // Windows filesystems (detected via volume queries, not statfs)
         Some("ntfs") => Capabilities {
@@ -127,6 +135,7 @@ fn capabilities_for_filesystem(fs_type: Option<&str>) -> Capabilities {
             block_clone_same_volume: false,
             filesystem_type: Some("ntfs".into()),
             reflink: false,
+            creation_time: true,
         },
         Some("refs") => Capabilities {
             sparse_files: true,
@@ -138,6 +147,7 @@ fn capabilities_for_filesystem(fs_type: Option<&str>) -> Capabilities {
             block_clone_same_volume: true,
             filesystem_type: Some("refs".into()),
             reflink: true,
+            creation_time: true,
         },
         // Unknown or unsupported — conservative defaults
         _ => {
@@ -162,6 +172,7 @@ fn platform_defaults() -> Capabilities {
             block_clone_same_volume: false,
             filesystem_type: None,
             reflink: false,
+            creation_time: true,
         }
     }
     #[cfg(all(unix, not(target_os = "macos")))]
@@ -176,6 +187,7 @@ fn platform_defaults() -> Capabilities {
             block_clone_same_volume: false,
             filesystem_type: None,
             reflink: false,
+            creation_time: false,
         }
     }
     #[cfg(windows)]
@@ -190,6 +202,7 @@ fn platform_defaults() -> Capabilities {
             block_clone_same_volume: false,
             filesystem_type: None,
             reflink: false,
+            creation_time: true,
         }
     }
 }
@@ -301,6 +314,10 @@ mod tests {
 
         let ext4 = capabilities_for_filesystem(Some("ext4"));
         assert!(!ext4.reflink, "ext4 does not support reflink");
+        assert!(!ext4.creation_time, "Linux cannot set a birth time on ext4");
+
+        let apfs = capabilities_for_filesystem(Some("apfs"));
+        assert!(apfs.creation_time, "apfs creation time is settable");
 
         let nfs = capabilities_for_filesystem(Some("nfs"));
         assert!(!nfs.sparse_files, "nfs does not reliably support sparse");
@@ -21,6 +21,7 @@ impl UnixCapability {
                 block_clone_same_volume: false,
                 filesystem_type: None,
                 reflink: false,
+                creation_time: false,
             },
         }
     }
@@ -105,6 +106,49 @@ impl FilesystemCapability for UnixCapability {
 
         Ok(FastCopyResult::Fallback)
     }
+
+    #[cfg(target_os = "linux")]
+    fn preallocate(&self, file: &std::fs::File, len: u64) -> Result<bool> {
+        use std::os::unix::io::AsRawFd;
+
+        let rc = unsafe { libc::fallocate(file.as_raw_fd(), 0, 0, len as libc::off_t) };
+        if rc == 0 {
+            return Ok(true);
+        }
+        let err = std::io::Error::last_os_error();
+        match err.raw_os_error() {
+            Some(libc::EOPNOTSUPP) | Some(libc::ENOSYS) => Ok(false),
+            _ => Err(err).with_context(|| format!("fallocate {len} bytes")),
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn reflink(&self, src: &Path, dst: &Path) -> Result<bool> {
+        use std::os::unix::io::AsRawFd;
+
+        let src_file =
+            std::fs::File::open(src).with_context(|| format!("open {}", src.display()))?;
+        let dst_file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(dst)
+            .with_context(|| format!("create {}", dst.display()))?;
+        // FICLONE: btrfs, XFS with reflink=1, bcachefs, OCFS2.
+        let rc = unsafe { libc::ioctl(dst_file.as_raw_fd(), libc::FICLONE, src_file.as_raw_fd()) };
+        if rc == 0 {
+            return Ok(true);
+        }
+        let err = std::io::Error::last_os_error();
+        match err.raw_os_error() {
+            Some(libc::EOPNOTSUPP)
+            | Some(libc::EXDEV)
+            | Some(libc::EINVAL)
+            | Some(libc::ENOTTY)
+            | Some(libc::ENOSYS) => Ok(false),
+            _ => Err(err).with_context(|| format!("FICLONE {}", dst.display())),
+        }
+    }
 }
 
 #[cfg(target_os = "linux")]
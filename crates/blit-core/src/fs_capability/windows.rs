@@ -9,6 +9,7 @@ use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::os::windows::ffi::OsStrExt;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use windows::core::PCWSTR;
 use windows::Win32::Storage::FileSystem::{GetVolumeInformationW, GetVolumePathNameW};
 
@@ -50,6 +51,7 @@ impl WindowsCapability {
                 block_clone_same_volume: false,
                 filesystem_type: None,
                 reflink: false,
+                creation_time: true,
             },
         }
     }
@@ -124,6 +126,38 @@ impl FilesystemCapability for WindowsCapability {
 
         Ok(FastCopyResult::Fallback)
     }
+
+    fn set_creation_time(&self, path: &Path, created: SystemTime) -> Result<bool> {
+        use std::os::windows::fs::{FileTimesExt, OpenOptionsExt};
+        // FILE_WRITE_ATTRIBUTES is all SetFileTime needs, and unlike a
+        // write open it is granted on a read-only file.
+        const FILE_WRITE_ATTRIBUTES: u32 = 0x0100;
+
+        let file = std::fs::OpenOptions::new()
+            .access_mode(FILE_WRITE_ATTRIBUTES)
+            .open(ensure_long_path(path))
+            .with_context(|| format!("open {} to set creation time", path.display()))?;
+        file.set_times(std::fs::FileTimes::new().set_created(created))
+            .with_context(|| format!("set creation time on {}", path.display()))?;
+        Ok(true)
+    }
+
+    fn preallocate(&self, file: &std::fs::File, len: u64) -> Result<bool> {
+        // SetEndOfFile: NTFS allocates the clusters for the new end of
+        // file. SetFileValidData would also skip zeroing them, but needs
+        // SE_MANAGE_VOLUME_NAME, which a copy should not demand.
+        file.set_len(len)
+            .with_context(|| format!("SetEndOfFile to {len} bytes"))?;
+        Ok(true)
+    }
+
+    fn reflink(&self, src: &Path, dst: &Path) -> Result<bool> {
+        // FSCTL_DUPLICATE_EXTENTS_TO_FILE (ReFS, Dev Drive), same volume.
+        let len = std::fs::metadata(src)
+            .with_context(|| format!("stat {}", src.display()))?
+            .len();
+        Ok(crate::copy::try_block_clone_same_volume(src, dst, len)?.is_some())
+    }
 }
 
 /// Public helper for other modules to check block-clone fast-path eligibility.
@@ -1,6 +1,7 @@
 use eyre::{Context, Result};
 use once_cell::sync::OnceCell;
 use std::collections::HashSet;
+use std::fs::Metadata;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
@@ -34,6 +35,9 @@ pub struct CopyJob {
 ///   (set by the orchestrator at filter-build time, not by the leaf code).
 /// - `files_from`: when present, only listed relative paths pass; all
 ///   other rules above are bypassed for the inclusion test.
+/// - `exclude_attributes`: Windows `FILE_ATTRIBUTE_*` bits; an entry
+///   carrying any of them is skipped (a directory with its subtree).
+///   Inert on other platforms, which have no such attributes.
 #[derive(Debug)]
 pub struct FileFilter {
     pub include_files: Vec<String>,
@@ -46,6 +50,7 @@ pub struct FileFilter {
     /// Set by orchestrator when building the filter (calculated, not hardcoded).
     pub reference_time: Option<SystemTime>,
     pub files_from: Option<HashSet<PathBuf>>,
+    pub exclude_attributes: u32,
     compiled_includes: OnceCell<globset::GlobSet>,
     compiled_files: OnceCell<globset::GlobSet>,
     compiled_dirs: OnceCell<globset::GlobSet>,
@@ -66,6 +71,7 @@ impl FileFilter {
             max_age: self.max_age,
             reference_time: self.reference_time,
             files_from: self.files_from.clone(),
+            exclude_attributes: self.exclude_attributes,
             compiled_includes: OnceCell::new(),
             compiled_files: OnceCell::new(),
             compiled_dirs: OnceCell::new(),
@@ -82,6 +88,7 @@ impl FileFilter {
             && self.min_age.is_none()
             && self.max_age.is_none()
             && self.files_from.is_none()
+            && self.exclude_attributes == 0
     }
 
     /// Load a `--files-from` list (one relative path per line, blank lines
@@ -102,6 +109,26 @@ impl FileFilter {
         Ok(set)
     }
 
+    /// Load an `--exclude-from` / `--include-from` pattern list: one
+    /// glob per line, blank lines and `#` comments skipped, file order
+    /// kept. The patterns join the inline `--exclude` / `--include`
+    /// lists unchanged, so a file and the flags it stands in for filter
+    /// identically.
+    pub fn load_patterns_from(path: &Path) -> Result<Vec<String>> {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("opening pattern list {}", path.display()))?;
+        let mut patterns = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.with_context(|| format!("reading pattern list {}", path.display()))?;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            patterns.push(trimmed.to_string());
+        }
+        Ok(patterns)
+    }
+
     fn build_globset(patterns: &[String]) -> globset::GlobSet {
         let mut builder = globset::GlobSetBuilder::new();
         for pat in patterns {
@@ -169,6 +196,14 @@ impl FileFilter {
         self.should_include_dir(path)
     }
 
+    /// Filter check for a manifest directory entry (contract v12). A
+    /// directory is kept unless `--exclude-dir` matches one of its
+    /// components; under `files_from` only the listed files travel, so
+    /// no directory entry does.
+    pub fn allows_directory_entry(&self, rel_path: &Path) -> bool {
+        self.files_from.is_none() && self.should_include_dir(rel_path)
+    }
+
     /// Full filter check. `rel_path` enables `files_from` matching;
     /// `mtime` enables age filtering. Both default to permissive when
     /// `None` (so back-compat callers via `allows_file` still work).
@@ -251,6 +286,24 @@ impl FileFilter {
         true
     }
 
+    /// Attribute check, kept apart from [`Self::allows_entry`] because
+    /// it needs the entry's own metadata rather than its path. Applies
+    /// under `files_from` too: a listed hidden file is still hidden.
+    pub fn allows_attributes(&self, metadata: &Metadata) -> bool {
+        if self.exclude_attributes == 0 {
+            return true;
+        }
+        #[cfg(windows)]
+        {
+            crate::win_fs::file_attributes(metadata) & self.exclude_attributes == 0
+        }
+        #[cfg(not(windows))]
+        {
+            let _ = metadata;
+            true
+        }
+    }
+
     /// Check if a directory should be included
     fn should_include_dir(&self, path: &Path) -> bool {
         // files_from doesn't restrict dir traversal — we still need to
@@ -288,6 +341,7 @@ impl Default for FileFilter {
             max_age: None,
             reference_time: None,
             files_from: None,
+            exclude_attributes: 0,
             compiled_includes: OnceCell::new(),
             compiled_files: OnceCell::new(),
             compiled_dirs: OnceCell::new(),
@@ -301,6 +355,40 @@ impl Clone for FileFilter {
     }
 }
 
+/// Windows file-attribute names accepted by `--exclude-attributes`,
+/// with their `FILE_ATTRIBUTE_*` bits.
+pub const FILE_ATTRIBUTE_NAMES: &[(&str, u32)] = &[
+    ("readonly", 0x0000_0001),
+    ("hidden", 0x0000_0002),
+    ("system", 0x0000_0004),
+    ("archive", 0x0000_0020),
+    ("temporary", 0x0000_0100),
+    ("offline", 0x0000_1000),
+];
+
+/// Parse a comma-separated attribute list like "hidden,system" into a
+/// `FILE_ATTRIBUTE_*` mask. Names are case-insensitive.
+pub fn parse_attributes(s: &str) -> Result<u32> {
+    let mut mask = 0;
+    for name in s.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        let Some((_, bit)) = FILE_ATTRIBUTE_NAMES
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(name))
+        else {
+            let known: Vec<&str> = FILE_ATTRIBUTE_NAMES.iter().map(|(n, _)| *n).collect();
+            eyre::bail!(
+                "unknown file attribute '{name}' (expected {})",
+                known.join(", ")
+            );
+        };
+        mask |= bit;
+    }
+    if mask == 0 {
+        eyre::bail!("empty attribute list");
+    }
+    Ok(mask)
+}
+
 /// Parse a human-readable size like "100K", "10M", "1G", "1.5Mi" into bytes.
 /// SI suffixes (K=1000) and binary suffixes (Ki=1024) both supported.
 pub fn parse_size(s: &str) -> Result<u64> {
@@ -1,27 +1,38 @@
 pub mod buffer;
 pub mod checksum;
+pub mod checksum_cache;
+pub mod chmod;
 pub mod config;
 pub mod copy;
+pub mod dedupe;
 pub mod deletion;
 pub mod dial;
 pub mod enumeration;
 pub mod fs_capability;
 pub mod fs_enum;
 pub mod manifest;
+pub mod manifest_checkpoint;
 pub mod mdns;
 pub mod mirror_planner;
+pub mod ownership;
 pub mod path_posix;
+pub mod path_rename;
 pub mod path_safety;
 pub mod perf_history;
 pub mod perf_predictor;
 pub mod remote;
+pub mod remote_options;
+pub mod source_snapshot;
+pub mod staging;
 pub mod stderr_log;
+pub mod transfer_facade;
 pub mod transfer_plan;
 pub mod transfer_session;
 #[cfg(windows)]
 pub mod win_fs;
 pub(crate) mod windows_metadata;
 pub mod wire_metadata;
+pub mod xattrs;
 pub mod zero_copy;
 
 #[cfg(test)]
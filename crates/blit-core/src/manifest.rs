@@ -48,6 +48,8 @@ pub enum FileStatus {
     New,
     /// File exists on target and should be skipped (ignore_existing mode).
     SkippedExisting,
+    /// File is absent on target and should be skipped (existing_only mode).
+    SkippedMissing,
 }
 
 /// Options for the per-entry manifest comparison.
@@ -61,6 +63,8 @@ pub struct CompareOptions {
     pub mode: CompareMode,
     /// If true, skip files that already exist on target (regardless of differences).
     pub ignore_existing: bool,
+    /// If true, skip files the target does not have: only update, never create.
+    pub existing_only: bool,
 }
 
 /// Status of one source header against the target's view of the same
@@ -76,6 +80,7 @@ pub fn header_transfer_status(
     options: &CompareOptions,
 ) -> FileStatus {
     match target {
+        None if options.existing_only => FileStatus::SkippedMissing,
         None => FileStatus::New,
         Some((target_size, target_mtime, target_checksum)) => {
             // File exists on target
@@ -181,6 +186,10 @@ mod tests {
             permissions: 0o644,
             checksum,
             windows_metadata: None,
+            crtime_seconds: None,
+            directory: false,
+            owner: None,
+            xattrs: Vec::new(),
         }
     }
 
@@ -300,6 +309,25 @@ mod tests {
         assert_eq!(status(&new_src, None, &opts), FileStatus::New);
     }
 
+    /// `--existing`: absent targets skip; existing ones are compared
+    /// as usual.
+    #[test]
+    fn existing_only_skips_new_but_compares_existing() {
+        let opts = CompareOptions {
+            existing_only: true,
+            ..Default::default()
+        };
+        let new_src = header("new.txt", 100, 1000);
+        assert_eq!(status(&new_src, None, &opts), FileStatus::SkippedMissing);
+        let modified_src = header("exists.txt", 200, 2000);
+        let dst = header("exists.txt", 100, 1000);
+        assert_eq!(
+            status(&modified_src, Some(&dst), &opts),
+            FileStatus::Modified
+        );
+        assert_eq!(status(&dst, Some(&dst), &opts), FileStatus::Unchanged);
+    }
+
     /// Checksum mode: content-equal skips even with a different mtime
     /// (the cell `--checksum` exists for).
     #[test]
@@ -342,6 +370,7 @@ mod tests {
         let opts = CompareOptions {
             mode: CompareMode::Checksum,
             ignore_existing: true,
+            ..Default::default()
         };
         let src = header_with_checksum("a.txt", 100, 1000, vec![1, 2]);
         let dst = header_with_checksum("a.txt", 100, 1000, vec![3, 4]);
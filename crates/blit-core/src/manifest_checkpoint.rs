@@ -0,0 +1,229 @@
+//! On-disk checkpoint of a source walk (`--manifest-checkpoint`).
+//!
+//! Walking tens of millions of files takes long enough that losing it
+//! to a dead push hurts: the re-run starts the walk from scratch. A
+//! checkpoint records each directory's listing (its child names) as the
+//! walk reads it, one JSON line per directory, appended and flushed as
+//! it goes, so whatever was listed before the failure survives it.
+//!
+//! A re-run against the same root takes a directory's names from the
+//! checkpoint instead of reading the directory, as long as the
+//! directory's mtime and inode are unchanged — adding, removing or
+//! renaming an entry changes its directory's mtime. Only the names are
+//! reused: every entry is still stat'ed, so sizes, mtimes and modes are
+//! current and a file rewritten in place since the checkpoint is sent
+//! as it is now. A directory modified within [`RACY_WINDOW`] of being
+//! read is not recorded, since a same-tick change could leave its mtime
+//! unchanged (coarse-timestamp filesystems).
+//!
+//! The caller deletes the checkpoint once the transfer it fed succeeds.
+
+use std::collections::HashMap;
+use std::fs::{self, File, Metadata, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use eyre::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// How recently a directory may have changed and still be recorded.
+pub const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+#[derive(Serialize, Deserialize)]
+struct CheckpointHeader {
+    root: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DirRecord {
+    dir: String,
+    mtime_ns: i128,
+    ino: u64,
+    names: Vec<String>,
+}
+
+/// An open checkpoint for one source root: the listings recorded so
+/// far, and the file new ones are appended to.
+pub struct ManifestCheckpoint {
+    path: PathBuf,
+    file: File,
+    listings: HashMap<String, DirRecord>,
+}
+
+impl ManifestCheckpoint {
+    /// Open (or create) the checkpoint at `path` for a walk of `root`.
+    /// A checkpoint written for another root is discarded, as is a torn
+    /// last line left by a process that died mid-write.
+    pub fn open(path: &Path, root: &Path) -> Result<Self> {
+        let root_key = root.display().to_string();
+        let existing = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("reading manifest checkpoint {}", path.display()))
+            }
+        };
+
+        let mut listings = HashMap::new();
+        let mut valid_len = 0usize;
+        let mut lines = existing.split_inclusive('\n');
+        let same_root = lines
+            .next()
+            .filter(|line| line.ends_with('\n'))
+            .and_then(|line| serde_json::from_str::<CheckpointHeader>(line).ok())
+            .is_some_and(|header| header.root == root_key);
+        if same_root {
+            valid_len = existing.find('\n').map_or(0, |i| i + 1);
+            for line in lines {
+                let Some(record) = line
+                    .strip_suffix('\n')
+                    .and_then(|line| serde_json::from_str::<DirRecord>(line).ok())
+                else {
+                    break;
+                };
+                valid_len += line.len();
+                listings.insert(record.dir.clone(), record);
+            }
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(path)
+            .with_context(|| format!("opening manifest checkpoint {}", path.display()))?;
+        file.set_len(valid_len as u64)?;
+        let mut checkpoint = Self {
+            path: path.to_path_buf(),
+            file,
+            listings,
+        };
+        if valid_len == 0 {
+            checkpoint.append(&CheckpointHeader { root: root_key })?;
+        } else {
+            use std::io::Seek;
+            checkpoint.file.seek(io::SeekFrom::End(0))?;
+        }
+        Ok(checkpoint)
+    }
+
+    /// Directories the checkpoint holds a listing for.
+    pub fn len(&self) -> usize {
+        self.listings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.listings.is_empty()
+    }
+
+    /// The recorded names of directory `rel` (`/`-separated, empty for
+    /// the root), if it has not changed since they were recorded.
+    pub fn listing(&self, rel: &str, metadata: &Metadata) -> Option<&[String]> {
+        let record = self.listings.get(rel)?;
+        (record.mtime_ns == mtime_ns(metadata) && record.ino == inode(metadata))
+            .then_some(record.names.as_slice())
+    }
+
+    /// Record the freshly read names of directory `rel`. A directory
+    /// still inside the racy window is skipped (see the module docs).
+    pub fn record(&mut self, rel: &str, metadata: &Metadata, names: &[String]) -> Result<()> {
+        let settled = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age >= RACY_WINDOW);
+        if !settled {
+            return Ok(());
+        }
+        let record = DirRecord {
+            dir: rel.to_string(),
+            mtime_ns: mtime_ns(metadata),
+            ino: inode(metadata),
+            names: names.to_vec(),
+        };
+        self.append(&record)?;
+        self.listings.insert(record.dir.clone(), record);
+        Ok(())
+    }
+
+    fn append<T: Serialize>(&mut self, value: &T) -> Result<()> {
+        let mut line = serde_json::to_vec(value)?;
+        line.push(b'\n');
+        self.file
+            .write_all(&line)
+            .with_context(|| format!("writing manifest checkpoint {}", self.path.display()))
+    }
+}
+
+fn mtime_ns(metadata: &Metadata) -> i128 {
+    let mtime = filetime::FileTime::from_last_modification_time(metadata);
+    i128::from(mtime.unix_seconds()) * 1_000_000_000 + i128::from(mtime.nanoseconds())
+}
+
+#[cfg(unix)]
+fn inode(metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.ino()
+}
+
+#[cfg(not(unix))]
+fn inode(_metadata: &Metadata) -> u64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn age(path: &Path) {
+        let past = filetime::FileTime::from_unix_time(1_600_000_000, 0);
+        filetime::set_file_mtime(path, past).unwrap();
+    }
+
+    #[test]
+    fn listings_survive_reopen_and_go_stale_when_the_directory_changes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("src");
+        fs::create_dir_all(&root).unwrap();
+        age(&root);
+        let path = tmp.path().join("walk.ckpt");
+        let names = vec!["a.txt".to_string(), "sub".to_string()];
+
+        let mut checkpoint = ManifestCheckpoint::open(&path, &root).unwrap();
+        checkpoint
+            .record("", &fs::metadata(&root).unwrap(), &names)
+            .unwrap();
+        drop(checkpoint);
+        // A torn trailing line from a crash is dropped, not fatal.
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"dir\":\"half")
+            .unwrap();
+
+        let checkpoint = ManifestCheckpoint::open(&path, &root).unwrap();
+        let meta = fs::metadata(&root).unwrap();
+        assert_eq!(checkpoint.listing("", &meta), Some(names.as_slice()));
+
+        fs::write(root.join("new.txt"), b"x").unwrap();
+        assert_eq!(checkpoint.listing("", &fs::metadata(&root).unwrap()), None);
+
+        // Another root starts over.
+        let other = ManifestCheckpoint::open(&path, tmp.path()).unwrap();
+        assert!(other.is_empty());
+    }
+
+    #[test]
+    fn recently_changed_directories_are_not_recorded() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("walk.ckpt");
+        let mut checkpoint = ManifestCheckpoint::open(&path, tmp.path()).unwrap();
+        checkpoint
+            .record("", &fs::metadata(tmp.path()).unwrap(), &[])
+            .unwrap();
+        assert!(checkpoint.is_empty());
+    }
+}
@@ -0,0 +1,334 @@
+//! Ownership preservation (`--super` / `--fake-super`, contract v14).
+//!
+//! A session that asks for ownership carries each entry's uid, gid and
+//! logical mode in `FileHeader.owner`, and the destination applies them
+//! in one pass once every payload has landed: `lchown` to the carried
+//! owner, then the carried mode (a chown clears set-id bits).
+//!
+//! A daemon that cannot chown — it runs unprivileged — fakes it instead
+//! (`fake_super` on the module, rsync's `--fake-super`): the owner and
+//! mode are recorded in the [`FAKE_SUPER_XATTR`] extended attribute, and
+//! the file keeps the daemon's ownership with owner read/write added to
+//! its mode so the daemon can still serve it back. A fake-super SOURCE
+//! reads the attribute back into the header, so a pull from such a
+//! module restores what a push recorded there.
+//!
+//! `--chown USER:GROUP` (contract v21) forces one owner instead: the
+//! destination resolves the names against its own user database
+//! ([`ChownOverride::resolve`]) and chowns every entry to them after the
+//! transfer, overriding carried owners too. Without privilege to chown
+//! it warns and leaves ownership alone.
+//!
+//! The attribute value is `<octal mode> <uid>:<gid>`. Symlinks are
+//! chowned but never faked: Linux refuses `user.*` attributes on them.
+//! All of this is Unix-only; elsewhere nothing is read and applying is a
+//! no-op.
+
+use std::io;
+use std::path::Path;
+
+use crate::generated::FileOwner;
+
+/// Extended attribute a fake-super end records ownership in.
+pub const FAKE_SUPER_XATTR: &str = "user.blit.stat";
+
+/// The bits [`FileOwner::mode`] carries.
+const MODE_BITS: u32 = 0o7777;
+
+/// The ownership to carry for `path`: its fake-super record when
+/// `fake_super` is set and one exists, else the inode's own.
+pub fn read_owner(
+    path: &Path,
+    metadata: &std::fs::Metadata,
+    fake_super: bool,
+) -> Option<FileOwner> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        if fake_super {
+            if let Some(owner) = read_fake_super(path) {
+                return Some(owner);
+            }
+        }
+        Some(FileOwner {
+            uid: metadata.uid(),
+            gid: metadata.gid(),
+            mode: metadata.mode() & MODE_BITS,
+        })
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (path, metadata, fake_super);
+        None
+    }
+}
+
+/// Give `path` the carried `owner`: chown and chmod it, or with
+/// `fake_super` record it in [`FAKE_SUPER_XATTR`] and only make sure the
+/// file stays readable and writable by this process.
+pub fn apply_owner(path: &Path, owner: &FileOwner, fake_super: bool) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::fs::Permissions;
+        use std::os::unix::fs::PermissionsExt;
+        let meta = std::fs::symlink_metadata(path)?;
+        let symlink = meta.file_type().is_symlink();
+        if fake_super {
+            if symlink {
+                return Ok(());
+            }
+            xattr::set(path, FAKE_SUPER_XATTR, format_record(owner).as_bytes())?;
+            let access = if meta.is_dir() { 0o700 } else { 0o600 };
+            let mode = meta.permissions().mode() & MODE_BITS;
+            if mode & access != access {
+                std::fs::set_permissions(path, Permissions::from_mode(mode | access))?;
+            }
+            return Ok(());
+        }
+        std::os::unix::fs::lchown(path, Some(owner.uid), Some(owner.gid))?;
+        if !symlink {
+            std::fs::set_permissions(path, Permissions::from_mode(owner.mode & MODE_BITS))?;
+        }
+        Ok(())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (path, owner, fake_super);
+        Ok(())
+    }
+}
+
+/// A `--chown USER:GROUP` override, resolved to ids on the host that
+/// applies it. Either half may be absent (`USER`, `USER:`, `:GROUP`),
+/// which leaves that id as it is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChownOverride {
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+}
+
+impl ChownOverride {
+    /// Parse `spec` and resolve its names through this host's user and
+    /// group databases; numeric ids are taken as they are. Fails on an
+    /// empty spec or an unknown name.
+    pub fn resolve(spec: &str) -> eyre::Result<Self> {
+        let (user, group) = spec.split_once(':').unwrap_or((spec, ""));
+        if user.is_empty() && group.is_empty() {
+            eyre::bail!("invalid --chown '{spec}': expected USER:GROUP, USER or :GROUP");
+        }
+        let uid = (!user.is_empty())
+            .then(|| resolve_id(user, IdKind::User))
+            .transpose()?;
+        let gid = (!group.is_empty())
+            .then(|| resolve_id(group, IdKind::Group))
+            .transpose()?;
+        Ok(Self { uid, gid })
+    }
+
+    /// Whether this process may give files away. Only then is the
+    /// override applied; otherwise the destination warns and skips it.
+    pub fn can_apply() -> bool {
+        #[cfg(unix)]
+        {
+            // SAFETY: geteuid has no preconditions and cannot fail.
+            unsafe { libc::geteuid() == 0 }
+        }
+        #[cfg(not(unix))]
+        {
+            false
+        }
+    }
+
+    /// `lchown` `path` to the override, keeping any set-id bits the
+    /// kernel clears on a change of owner.
+    pub fn apply(&self, path: &Path) -> io::Result<()> {
+        #[cfg(unix)]
+        {
+            use std::fs::Permissions;
+            use std::os::unix::fs::{MetadataExt, PermissionsExt};
+            let meta = std::fs::symlink_metadata(path)?;
+            if self.uid.is_none_or(|uid| uid == meta.uid())
+                && self.gid.is_none_or(|gid| gid == meta.gid())
+            {
+                return Ok(());
+            }
+            std::os::unix::fs::lchown(path, self.uid, self.gid)?;
+            let mode = meta.mode() & MODE_BITS;
+            if !meta.file_type().is_symlink() && mode & 0o6000 != 0 {
+                std::fs::set_permissions(path, Permissions::from_mode(mode))?;
+            }
+            Ok(())
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = path;
+            Ok(())
+        }
+    }
+
+    /// Replace the ids of a carried owner with the override's.
+    pub fn override_owner(&self, owner: &mut FileOwner) {
+        if let Some(uid) = self.uid {
+            owner.uid = uid;
+        }
+        if let Some(gid) = self.gid {
+            owner.gid = gid;
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum IdKind {
+    User,
+    Group,
+}
+
+fn resolve_id(name: &str, kind: IdKind) -> eyre::Result<u32> {
+    if let Ok(id) = name.parse::<u32>() {
+        return Ok(id);
+    }
+    let what = match kind {
+        IdKind::User => "user",
+        IdKind::Group => "group",
+    };
+    lookup_id(name, kind)?.ok_or_else(|| eyre::eyre!("invalid --chown: unknown {what} '{name}'"))
+}
+
+/// Look `name` up with the reentrant `getpwnam_r` / `getgrnam_r`,
+/// growing the buffer while the entry does not fit.
+#[cfg(unix)]
+fn lookup_id(name: &str, kind: IdKind) -> eyre::Result<Option<u32>> {
+    let cname = std::ffi::CString::new(name)
+        .map_err(|_| eyre::eyre!("invalid --chown: '{name}' contains a NUL byte"))?;
+    let mut buf = vec![0 as libc::c_char; 1024];
+    loop {
+        // SAFETY: every pointer is valid for the call: `cname` is a
+        // NUL-terminated string, `buf` is writable for `buf.len()`
+        // bytes, and the entry and result slots are live locals.
+        let (rc, found) = unsafe {
+            match kind {
+                IdKind::User => {
+                    let mut entry: libc::passwd = std::mem::zeroed();
+                    let mut result: *mut libc::passwd = std::ptr::null_mut();
+                    let rc = libc::getpwnam_r(
+                        cname.as_ptr(),
+                        &mut entry,
+                        buf.as_mut_ptr(),
+                        buf.len(),
+                        &mut result,
+                    );
+                    (rc, (!result.is_null()).then_some(entry.pw_uid))
+                }
+                IdKind::Group => {
+                    let mut entry: libc::group = std::mem::zeroed();
+                    let mut result: *mut libc::group = std::ptr::null_mut();
+                    let rc = libc::getgrnam_r(
+                        cname.as_ptr(),
+                        &mut entry,
+                        buf.as_mut_ptr(),
+                        buf.len(),
+                        &mut result,
+                    );
+                    (rc, (!result.is_null()).then_some(entry.gr_gid))
+                }
+            }
+        };
+        match rc {
+            0 => return Ok(found),
+            libc::ERANGE if buf.len() < 1 << 20 => buf.resize(buf.len() * 2, 0),
+            rc => {
+                return Err(io::Error::from_raw_os_error(rc))
+                    .map_err(|e| eyre::eyre!("looking up '{name}' for --chown: {e}"))
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn lookup_id(name: &str, _kind: IdKind) -> eyre::Result<Option<u32>> {
+    eyre::bail!("--chown names cannot be resolved on this platform (use numeric ids): '{name}'")
+}
+
+#[cfg(unix)]
+fn read_fake_super(path: &Path) -> Option<FileOwner> {
+    let value = xattr::get(path, FAKE_SUPER_XATTR).ok()??;
+    parse_record(std::str::from_utf8(&value).ok()?)
+}
+
+fn format_record(owner: &FileOwner) -> String {
+    format!("{:o} {}:{}", owner.mode & MODE_BITS, owner.uid, owner.gid)
+}
+
+/// Parse a [`format_record`] value; anything malformed reads as no
+/// record, so the inode's own ownership is carried instead.
+fn parse_record(value: &str) -> Option<FileOwner> {
+    let (mode, ids) = value.trim().split_once(' ')?;
+    let (uid, gid) = ids.split_once(':')?;
+    Some(FileOwner {
+        uid: uid.parse().ok()?,
+        gid: gid.parse().ok()?,
+        mode: u32::from_str_radix(mode, 8).ok()? & MODE_BITS,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_round_trip_and_reject_garbage() {
+        let owner = FileOwner {
+            uid: 1234,
+            gid: 5678,
+            mode: 0o4750,
+        };
+        assert_eq!(format_record(&owner), "4750 1234:5678");
+        assert_eq!(parse_record("4750 1234:5678"), Some(owner));
+        assert_eq!(parse_record("rw-r--r-- 0:0"), None);
+        assert_eq!(parse_record("644 root:root"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn chown_specs_resolve_names_and_ids() {
+        assert_eq!(
+            ChownOverride::resolve("root:0").unwrap(),
+            ChownOverride {
+                uid: Some(0),
+                gid: Some(0)
+            }
+        );
+        assert_eq!(
+            ChownOverride::resolve("1234").unwrap(),
+            ChownOverride {
+                uid: Some(1234),
+                gid: None
+            }
+        );
+        assert_eq!(ChownOverride::resolve(":55").unwrap().uid, None);
+        assert!(ChownOverride::resolve(":").is_err());
+        assert!(ChownOverride::resolve("no-such-user-blit:0").is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn fake_super_records_ownership_and_keeps_the_file_accessible() {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("f");
+        std::fs::write(&path, b"x").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o400)).unwrap();
+        let owner = FileOwner {
+            uid: 4242,
+            gid: 4343,
+            mode: 0o400,
+        };
+
+        apply_owner(&path, &owner, true).unwrap();
+        let meta = std::fs::metadata(&path).unwrap();
+        assert_eq!(meta.mode() & MODE_BITS, 0o600);
+        assert_ne!(meta.uid(), 4242, "fake-super never chowns");
+        assert_eq!(read_owner(&path, &meta, true), Some(owner));
+        assert_eq!(read_owner(&path, &meta, false).unwrap().mode, 0o600);
+    }
+}
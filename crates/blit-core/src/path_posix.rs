@@ -88,11 +88,65 @@ pub fn relative_str_to_posix(s: &str) -> String {
     }
 }
 
+/// Collapse `items` — each naming a `/`-separated path via `path` — to
+/// the top-most ones. An item `is_root` accepts starts a group unless
+/// another group already encloses it; every later item under a group's
+/// path (or equal to it) is handed to `absorb` with that group instead
+/// of being kept. Items that neither start nor join a group are dropped.
+/// Returned in path-component order.
+pub fn collapse_to_topmost<T>(
+    items: impl IntoIterator<Item = T>,
+    path: impl Fn(&T) -> &str,
+    mut is_root: impl FnMut(&T) -> bool,
+    mut absorb: impl FnMut(&mut T, T),
+) -> Vec<T> {
+    let mut sorted: Vec<T> = items.into_iter().collect();
+    sorted.sort_by(|a, b| path(a).split('/').cmp(path(b).split('/')));
+    let mut roots: Vec<T> = Vec::new();
+    for item in sorted {
+        // Component order puts a directory directly before its
+        // descendants, so the last root is the only one that can
+        // enclose this item.
+        if let Some(root) = roots.last_mut() {
+            if path(&item)
+                .strip_prefix(path(root))
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            {
+                absorb(root, item);
+                continue;
+            }
+        }
+        if is_root(&item) {
+            roots.push(item);
+        }
+    }
+    roots
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn collapse_keeps_top_most_and_folds_descendants_in() {
+        let items = ["a/b", "a", "ab", "c/d/e", "c/d", "a/b/c", "a"].map(|p| (p.to_string(), 1));
+        let collapsed = collapse_to_topmost(
+            items,
+            |(p, _)| p,
+            |(p, _)| p != "c/d",
+            |(_, n), (_, m)| *n += m,
+        );
+        assert_eq!(
+            collapsed,
+            [
+                ("a".to_string(), 4),
+                ("ab".to_string(), 1),
+                ("c/d/e".to_string(), 1)
+            ]
+        );
+    }
+
     #[test]
     fn empty_and_dot_produce_empty_string() {
         assert_eq!(relative_path_to_posix(Path::new("")), "");
@@ -0,0 +1,150 @@
+//! `--rename <from-regex>=<to>` path rewriting.
+//!
+//! Rules are applied by the SOURCE to every manifest relative path
+//! before the header leaves the scan (see
+//! `remote::transfer::source::RenamingSource`), so the destination only
+//! ever sees — diffs, writes, and mirror-scopes — the rewritten names.
+//! The rules ride `SessionOpen.rename` (contract v6) because on a pull
+//! the SOURCE is the remote daemon, not the client.
+//!
+//! A rewritten path is untrusted input exactly like a wire path: it is
+//! run through [`crate::path_safety::validate_wire_path`] (the core twin
+//! of the daemon's `resolve_relative_path`), so a rule cannot produce
+//! `..`, an absolute path, or a Windows prefix. The empty relative path
+//! (single-file source: "the root itself") is never rewritten.
+
+use eyre::{bail, eyre, Context, Result};
+use regex::Regex;
+
+use crate::generated::RenameRule;
+use crate::path_posix::relative_path_to_posix;
+
+/// Parse one CLI `<from-regex>=<to>` spec. The split is at the FIRST
+/// `=`, so the replacement may contain `=` but the pattern may not
+/// (use `\x3d` in the regex instead). The pattern is compiled here so
+/// a malformed rule fails before any connection is made.
+pub fn parse_rule(spec: &str) -> Result<RenameRule> {
+    let (pattern, replacement) = spec
+        .split_once('=')
+        .ok_or_else(|| eyre!("invalid --rename '{spec}': expected <from-regex>=<to>"))?;
+    if pattern.is_empty() {
+        bail!("invalid --rename '{spec}': empty pattern");
+    }
+    Regex::new(pattern).with_context(|| format!("invalid --rename pattern '{pattern}'"))?;
+    Ok(RenameRule {
+        pattern: pattern.to_string(),
+        replacement: replacement.to_string(),
+    })
+}
+
+/// Parse every `--rename` spec, preserving order (rules chain).
+pub fn parse_rules(specs: &[String]) -> Result<Vec<RenameRule>> {
+    specs.iter().map(|spec| parse_rule(spec)).collect()
+}
+
+/// Compiled, ordered rule chain. Each rule sees the previous rule's
+/// output; only a rule's first match is replaced.
+#[derive(Debug, Clone, Default)]
+pub struct PathRenamer {
+    rules: Vec<(Regex, String)>,
+}
+
+impl PathRenamer {
+    /// Compile wire rules. Called at OPEN validation on a serving
+    /// responder, so a malformed pattern from a peer is refused before
+    /// the scan starts.
+    pub fn from_rules(rules: &[RenameRule]) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                Regex::new(&rule.pattern)
+                    .with_context(|| format!("invalid rename pattern '{}'", rule.pattern))
+                    .map(|re| (re, rule.replacement.clone()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Rewrite one POSIX relative path. The result is validated and
+    /// re-normalized (stray `.` components and doubled separators a
+    /// replacement introduced are collapsed); a result that is empty or
+    /// fails wire-path validation is an error naming both paths.
+    pub fn rename(&self, relative_path: &str) -> Result<String> {
+        if relative_path.is_empty() || self.rules.is_empty() {
+            return Ok(relative_path.to_string());
+        }
+        let mut current = relative_path.to_string();
+        for (re, replacement) in &self.rules {
+            current = re.replace(&current, replacement.as_str()).into_owned();
+        }
+        let validated = crate::path_safety::validate_wire_path(&current).with_context(|| {
+            format!("--rename rewrote '{relative_path}' to unsafe path '{current}'")
+        })?;
+        if validated.as_os_str().is_empty() {
+            bail!("--rename rewrote '{relative_path}' to an empty path");
+        }
+        Ok(relative_path_to_posix(&validated))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn renamer(specs: &[&str]) -> PathRenamer {
+        let specs: Vec<String> = specs.iter().map(|s| s.to_string()).collect();
+        PathRenamer::from_rules(&parse_rules(&specs).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn prefix_strip_rewrites_matching_paths_only() {
+        let r = renamer(&["^build/out/="]);
+        assert_eq!(r.rename("build/out/app.bin").unwrap(), "app.bin");
+        assert_eq!(r.rename("build/out/lib/a.so").unwrap(), "lib/a.so");
+        assert_eq!(r.rename("src/main.rs").unwrap(), "src/main.rs");
+    }
+
+    #[test]
+    fn rules_chain_in_order_with_captures() {
+        let r = renamer(&[r"^(\w+)\.txt$=$1.md", "^=docs/"]);
+        assert_eq!(r.rename("notes.txt").unwrap(), "docs/notes.md");
+    }
+
+    #[test]
+    fn rewrite_escaping_the_root_is_rejected() {
+        for spec in ["^=../", "^=/", "^a/=a/../../"] {
+            let r = renamer(&[spec]);
+            let err = r.rename("a/file").unwrap_err();
+            assert!(
+                format!("{err:#}").contains("unsafe path"),
+                "{spec}: {err:#}"
+            );
+        }
+    }
+
+    #[test]
+    fn rewrite_to_empty_is_rejected() {
+        let r = renamer(&["^.*$="]);
+        assert!(r.rename("a/file").is_err());
+    }
+
+    #[test]
+    fn empty_relative_path_is_never_rewritten() {
+        let r = renamer(&["^=prefix/"]);
+        assert_eq!(r.rename("").unwrap(), "");
+    }
+
+    #[test]
+    fn malformed_specs_are_rejected_at_parse() {
+        assert!(parse_rule("no-separator").is_err());
+        assert!(parse_rule("=to").is_err());
+        assert!(parse_rule("([=to").is_err());
+        let rule = parse_rule("a=b=c").unwrap();
+        assert_eq!(rule.pattern, "a");
+        assert_eq!(rule.replacement, "b=c");
+    }
+}
@@ -3,19 +3,38 @@
 //! Records summarized run information to a capped JSONL file under the user's
 //! config directory. The data stays on-device and can be toggled via the CLI
 //! (`blit diagnostics perf --enable/--disable`).
+//!
+//! A run's record is appended once, when it ends. So that a crashed or
+//! killed run still teaches something, a running transfer also keeps a
+//! [`HistoryCheckpoint`]: its in-progress record, replaced atomically
+//! every [`CHECKPOINT_INTERVAL`] under `perf_inflight/`. A run that ends
+//! normally removes it; one that unwinds with an error appends it at
+//! once, and one that died leaves it for the next run to append
+//! ([`recover_interrupted_checkpoints`]). Either way the history gains
+//! a record flagged `interrupted`, counting the work done up to the
+//! last checkpoint.
 
 use crate::config;
 use std::collections::VecDeque;
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use eyre::{Context, Result};
 use serde::{Deserialize, Serialize};
 
 const DEFAULT_MAX_BYTES: u64 = 1_000_000; // ~1 MiB cap per design docs
 const SETTINGS_FILE: &str = "settings.json";
+const CHECKPOINT_DIR: &str = "perf_inflight";
+
+/// How often a running transfer replaces its [`HistoryCheckpoint`].
+pub const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A checkpoint untouched this long belongs to a run that died: a live
+/// one rewrites it every [`CHECKPOINT_INTERVAL`].
+const CHECKPOINT_STALE_AFTER: Duration = Duration::from_secs(60);
 
 /// Current schema version for PerformanceRecord.
 ///
@@ -30,7 +49,11 @@ const SETTINGS_FILE: &str = "settings.json";
 ///       lane implicitly in `options.dry_run` and
 ///       `fast_path == Some("null_sink")`; migration derives `run_kind`
 ///       from those without touching `mode`. R56-F1.
-pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+///   3 - added `interrupted`: the record was recovered from the
+///       in-progress checkpoint of a run that never finished. Older
+///       records were all written at run end, so the default (false)
+///       is their truth and no migration is needed.
+pub const CURRENT_SCHEMA_VERSION: u32 = 3;
 
 /// High-level category of a transfer run (intent-side).
 ///
@@ -170,6 +193,11 @@ pub struct PerformanceRecord {
     pub large_tasks: u32,
     #[serde(default)]
     pub large_bytes: u64,
+    /// The run never finished: this is its last [`HistoryCheckpoint`],
+    /// so the counts and `transfer_duration_ms` cover only the work
+    /// done up to it.
+    #[serde(default)]
+    pub interrupted: bool,
 }
 
 impl PerformanceRecord {
@@ -226,6 +254,7 @@ impl PerformanceRecord {
             raw_bundle_bytes: 0,
             large_tasks: 0,
             large_bytes: 0,
+            interrupted: false,
         }
     }
 }
@@ -240,7 +269,10 @@ pub fn append_local_record(record: &PerformanceRecord) -> Result<()> {
         return Ok(());
     }
 
-    let path = history_path()?;
+    append_record_to_path(&history_path()?, record)
+}
+
+fn append_record_to_path(path: &Path, record: &PerformanceRecord) -> Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).with_context(|| {
             format!(
@@ -252,18 +284,225 @@ pub fn append_local_record(record: &PerformanceRecord) -> Result<()> {
 
     let mut file = OpenOptions::new()
         .create(true)
+        .read(true)
         .append(true)
-        .open(&path)
+        .open(path)
         .with_context(|| format!("failed to open performance history file {}", path.display()))?;
 
-    let line = serde_json::to_string(record).context("serialize performance record")?;
-    writeln!(file, "{line}").context("write performance record")?;
+    // A writer that died mid-line left a torn record with no newline;
+    // start on a fresh line so this one does not fuse onto it (the
+    // reader skips the torn line).
+    let mut line = String::new();
+    if !ends_with_newline(&mut file)? {
+        line.push('\n');
+    }
+    line.push_str(&serde_json::to_string(record).context("serialize performance record")?);
+    line.push('\n');
+    file.write_all(line.as_bytes())
+        .context("write performance record")?;
     drop(file);
 
-    enforce_size_cap(&path, DEFAULT_MAX_BYTES)?;
+    enforce_size_cap(path, DEFAULT_MAX_BYTES)?;
     Ok(())
 }
 
+/// True for an empty file or one whose last byte is `\n`.
+fn ends_with_newline(file: &mut File) -> Result<bool> {
+    use std::io::{Read, Seek, SeekFrom};
+    let len = file.metadata()?.len();
+    if len == 0 {
+        return Ok(true);
+    }
+    let mut last = [0u8; 1];
+    file.seek(SeekFrom::Start(len - 1))?;
+    file.read_exact(&mut last)?;
+    Ok(last[0] == b'\n')
+}
+
+/// Replace `path` with `contents` atomically: a sibling temp file is
+/// written and synced, then renamed over it, so a crash leaves either
+/// the old file or the new one, never a torn mix.
+fn replace_atomically(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    let mut file =
+        File::create(&tmp).with_context(|| format!("failed to create {}", tmp.display()))?;
+    file.write_all(contents)
+        .and_then(|()| file.sync_all())
+        .with_context(|| format!("failed to write {}", tmp.display()))?;
+    drop(file);
+    fs::rename(&tmp, path).with_context(|| format!("failed to replace {}", path.display()))
+}
+
+/// The in-progress record of a running transfer. See the module docs.
+///
+/// [`write`](Self::write) replaces the checkpoint file atomically, so a
+/// crash leaves the previous checkpoint or the new one, never a torn
+/// record. [`complete`](Self::complete) removes it once the run's final
+/// record is appended. Dropping the last handle without completing
+/// appends the last checkpoint as an `interrupted` record. Handles are
+/// cheap clones: a periodic writer task holds one while the run holds
+/// another, and a write racing completion is discarded, never
+/// resurrecting the file.
+#[derive(Clone)]
+pub struct HistoryCheckpoint {
+    inner: Arc<CheckpointInner>,
+}
+
+struct CheckpointInner {
+    path: PathBuf,
+    history: PathBuf,
+    /// False once completed; held across each write so one cannot land
+    /// after the file was removed.
+    open: Mutex<bool>,
+}
+
+impl HistoryCheckpoint {
+    /// Begin checkpointing a run, first appending what earlier runs that
+    /// died left behind. `None` when performance history is disabled.
+    pub fn start() -> Result<Option<Self>> {
+        if !perf_history_enabled()? {
+            return Ok(None);
+        }
+        let dir = config_dir()?;
+        let checkpoints = dir.join(CHECKPOINT_DIR);
+        let history = history_path()?;
+        recover_checkpoints_in(&checkpoints, &history, CHECKPOINT_STALE_AFTER)?;
+        Self::start_in(&checkpoints, history).map(Some)
+    }
+
+    fn start_in(checkpoints: &Path, history: PathBuf) -> Result<Self> {
+        fs::create_dir_all(checkpoints).with_context(|| {
+            format!(
+                "failed to create performance checkpoint directory {}",
+                checkpoints.display()
+            )
+        })?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let name = format!("{}-{}.json", std::process::id(), now.as_nanos());
+        Ok(Self {
+            inner: Arc::new(CheckpointInner {
+                path: checkpoints.join(name),
+                history,
+                open: Mutex::new(true),
+            }),
+        })
+    }
+
+    /// Replace the checkpoint with `record`. A no-op once completed.
+    pub fn write(&self, record: &PerformanceRecord) -> Result<()> {
+        let open = self.inner.lock_open();
+        if !*open {
+            return Ok(());
+        }
+        let json = serde_json::to_vec(record).context("serialize performance checkpoint")?;
+        replace_atomically(&self.inner.path, &json)
+    }
+
+    /// The run ended and its final record is appended: remove the
+    /// checkpoint so it is never recovered as an interrupted run.
+    pub fn complete(&self) -> Result<()> {
+        let mut open = self.inner.lock_open();
+        *open = false;
+        remove_if_present(&self.inner.path)
+    }
+}
+
+impl CheckpointInner {
+    fn lock_open(&self) -> std::sync::MutexGuard<'_, bool> {
+        self.open
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for CheckpointInner {
+    fn drop(&mut self) {
+        if *self.lock_open() {
+            let _ = recover_checkpoint(&self.path, &self.history);
+        }
+    }
+}
+
+/// Append, as `interrupted` records, the checkpoints of runs that died
+/// without completing (untouched for a minute), removing each. A
+/// checkpoint that does not parse is removed unrecorded. Returns how
+/// many records were appended. [`HistoryCheckpoint::start`] runs this,
+/// so the next transfer picks up after a crash.
+pub fn recover_interrupted_checkpoints() -> Result<usize> {
+    if !perf_history_enabled()? {
+        return Ok(0);
+    }
+    recover_checkpoints_in(
+        &config_dir()?.join(CHECKPOINT_DIR),
+        &history_path()?,
+        CHECKPOINT_STALE_AFTER,
+    )
+}
+
+fn recover_checkpoints_in(
+    checkpoints: &Path,
+    history: &Path,
+    stale_after: Duration,
+) -> Result<usize> {
+    let entries = match fs::read_dir(checkpoints) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(err).with_context(|| format!("reading {}", checkpoints.display())),
+    };
+    let mut recovered = 0;
+    for entry in entries {
+        let path = entry?.path();
+        let is_checkpoint = path.extension().is_some_and(|ext| ext == "json");
+        let is_orphan_tmp = path.extension().is_some_and(|ext| ext == "tmp");
+        if !is_checkpoint && !is_orphan_tmp {
+            continue;
+        }
+        let age = fs::metadata(&path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .unwrap_or_default();
+        if age < stale_after {
+            continue;
+        }
+        if is_orphan_tmp {
+            remove_if_present(&path)?;
+        } else if recover_checkpoint(&path, history)? {
+            recovered += 1;
+        }
+    }
+    Ok(recovered)
+}
+
+/// Append `path`'s checkpoint to `history` as an interrupted record and
+/// remove it. Returns whether a record was appended.
+fn recover_checkpoint(path: &Path, history: &Path) -> Result<bool> {
+    let record = match fs::read(path) {
+        Ok(bytes) => serde_json::from_slice::<PerformanceRecord>(&bytes).ok(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(err).with_context(|| format!("reading {}", path.display())),
+    };
+    let recovered = record.is_some();
+    if let Some(mut record) = record {
+        record.interrupted = true;
+        append_record_to_path(history, &record)?;
+    }
+    remove_if_present(path)?;
+    Ok(recovered)
+}
+
+fn remove_if_present(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err).with_context(|| format!("removing {}", path.display())),
+    }
+}
+
 /// Migrate a record from an older schema version to the current version.
 ///
 /// Returns the record with `schema_version` set to `CURRENT_SCHEMA_VERSION`.
@@ -343,12 +582,13 @@ pub fn migrate_history_file() -> Result<usize> {
     let records = read_records_from_path(&path, 0)?;
     let count = records.len();
 
-    let mut file = File::create(&path)
-        .with_context(|| format!("rewriting history file {}", path.display()))?;
+    let mut contents = Vec::new();
     for record in &records {
-        let line = serde_json::to_string(record).context("serialize migrated record")?;
-        writeln!(file, "{line}")?;
+        serde_json::to_writer(&mut contents, record).context("serialize migrated record")?;
+        contents.push(b'\n');
     }
+    replace_atomically(&path, &contents)
+        .with_context(|| format!("rewriting history file {}", path.display()))?;
 
     Ok(count)
 }
@@ -483,11 +723,12 @@ fn enforce_size_cap(path: &Path, max_bytes: u64) -> Result<()> {
         return Ok(());
     }
 
-    let mut file = File::create(path)?;
+    let mut contents = String::with_capacity(total_size);
     for line in lines {
-        writeln!(file, "{line}")?;
+        contents.push_str(&line);
+        contents.push('\n');
     }
-    Ok(())
+    replace_atomically(path, contents.as_bytes())
 }
 
 #[cfg(test)]
@@ -766,6 +1007,126 @@ mod tests {
         assert!(record.run_kind.is_real_transfer());
     }
 
+    // ── Checkpoints: interrupted runs still land ──────────────────────
+
+    fn in_progress_record(files: usize, bytes: u64) -> PerformanceRecord {
+        let options = OptionSnapshot {
+            dry_run: false,
+            preserve_symlinks: true,
+            include_symlinks: true,
+            skip_unchanged: true,
+            checksum: false,
+            compare_mode: CompareModeSnapshot::SizeMtime,
+            workers: 4,
+        };
+        let mut record = PerformanceRecord::new(
+            TransferMode::Copy,
+            None,
+            None,
+            files,
+            bytes,
+            options,
+            Some("session".to_string()),
+            0,
+            1_500,
+            0,
+            0,
+        );
+        record.tar_shard_tasks = 3;
+        record.large_tasks = 1;
+        record
+    }
+
+    /// A run that dies (no drop, no completion — `forget` stands in for
+    /// the kill) leaves its last checkpoint as a valid record, which the
+    /// next run's recovery appends as an interrupted record.
+    #[test]
+    fn a_killed_run_leaves_a_partial_but_valid_record() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let checkpoints = dir.path().join(CHECKPOINT_DIR);
+        let history = dir.path().join("perf_local.jsonl");
+
+        let checkpoint = HistoryCheckpoint::start_in(&checkpoints, history.clone()).expect("start");
+        checkpoint
+            .write(&in_progress_record(10, 1_000))
+            .expect("write");
+        checkpoint
+            .write(&in_progress_record(25, 4_096))
+            .expect("rewrite");
+        let path = checkpoint.inner.path.clone();
+        std::mem::forget(checkpoint);
+
+        let on_disk: PerformanceRecord =
+            serde_json::from_slice(&fs::read(&path).expect("checkpoint")).expect("valid json");
+        assert_eq!(on_disk.file_count, 25);
+        assert!(!on_disk.interrupted);
+
+        // A live run's checkpoint is not taken from it.
+        assert_eq!(
+            recover_checkpoints_in(&checkpoints, &history, CHECKPOINT_STALE_AFTER).unwrap(),
+            0
+        );
+        assert!(path.exists());
+
+        assert_eq!(
+            recover_checkpoints_in(&checkpoints, &history, Duration::ZERO).unwrap(),
+            1
+        );
+        assert!(!path.exists());
+        let records = read_records_from_path(&history, 0).expect("read");
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert!(record.interrupted);
+        assert_eq!(record.file_count, 25);
+        assert_eq!(record.total_bytes, 4_096);
+        assert_eq!(record.tar_shard_tasks, 3);
+        assert_eq!(record.large_tasks, 1);
+        assert_eq!(record.transfer_duration_ms, 1_500);
+    }
+
+    #[test]
+    fn an_abandoned_checkpoint_lands_on_drop_and_a_completed_one_never() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let checkpoints = dir.path().join(CHECKPOINT_DIR);
+        let history = dir.path().join("perf_local.jsonl");
+
+        let completed = HistoryCheckpoint::start_in(&checkpoints, history.clone()).unwrap();
+        completed.write(&in_progress_record(1, 1)).unwrap();
+        let writer = completed.clone();
+        completed.complete().unwrap();
+        // A tick racing completion must not resurrect the file.
+        writer.write(&in_progress_record(2, 2)).unwrap();
+        drop((completed, writer));
+
+        let abandoned = HistoryCheckpoint::start_in(&checkpoints, history.clone()).unwrap();
+        abandoned.write(&in_progress_record(7, 70)).unwrap();
+        drop(abandoned);
+
+        let records = read_records_from_path(&history, 0).unwrap();
+        assert_eq!(records.len(), 1);
+        assert!(records[0].interrupted);
+        assert_eq!(records[0].file_count, 7);
+        assert_eq!(fs::read_dir(&checkpoints).unwrap().count(), 0);
+    }
+
+    /// A writer killed mid-append leaves a torn last line; the next
+    /// append starts a fresh line instead of fusing onto it.
+    #[test]
+    fn append_after_a_torn_line_keeps_the_new_record_readable() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let history = dir.path().join("perf_local.jsonl");
+        let mut file = File::create(&history).unwrap();
+        writeln!(file, "{}", sample_v1_json()).unwrap();
+        write!(file, "{{\"schema_version\":3,\"timest").unwrap();
+        drop(file);
+
+        append_record_to_path(&history, &in_progress_record(4, 40)).unwrap();
+
+        let records = read_records_from_path(&history, 0).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].file_count, 4);
+    }
+
     /// The eligibility helper is the actual chokepoint other modules
     /// gate on; pin it explicitly so changes to RunKind variants
     /// can't accidentally shift the contract.
@@ -665,6 +665,7 @@ mod tests {
             raw_bundle_bytes: 0,
             large_tasks: 0,
             large_bytes: 0,
+            interrupted: false,
         }
     }
 
@@ -0,0 +1,182 @@
+//! Connect-time `Capabilities` probe.
+//!
+//! The transfer session itself never negotiates: `SessionHello` is an
+//! exact build match and same build implies same wire features
+//! (docs/TRANSFER_SESSION.md §Invariants 2). What DOES vary between two
+//! daemons of one build is operator policy — `--force-grpc-data` grants
+//! no TCP data plane, `--no-server-checksums` refuses content compares,
+//! delegation is off by default. The probe surfaces both before any
+//! session opens: a stale end fails with the same BUILD_MISMATCH fault
+//! the HELLO would raise, and the client drops optional behaviour the
+//! daemon turned off instead of discovering it mid-handshake.
+//!
+//! A daemon that predates the RPC answers `UNIMPLEMENTED`; [`probe`]
+//! maps that to `None` and callers proceed exactly as before the probe
+//! existed (the session HELLO still guards the build).
+
+use std::collections::BTreeSet;
+
+use eyre::{Context, Result};
+use tonic::transport::Channel;
+
+use crate::generated::blit_client::BlitClient;
+use crate::generated::{CapabilitiesRequest, CapabilitiesResponse};
+use crate::remote::transfer::operation_spec::SUPPORTED_SPEC_VERSION;
+use crate::transfer_session::{session_build_id, HelloConfig, SessionFault, CONTRACT_VERSION};
+
+/// The daemon grants a TCP data plane (absent under `--force-grpc-data`).
+pub const FEATURE_TCP_DATA_PLANE: &str = "tcp_data_plane";
+/// `--streams` / `--chunk-size` pins take effect (contract v7). Only
+/// meaningful with a data plane, so advertised only alongside it.
+pub const FEATURE_DIAL_PINS: &str = "dial_pins";
+/// Checksum-compare sessions are served (absent under
+/// `--no-server-checksums`).
+pub const FEATURE_SERVER_CHECKSUMS: &str = "server_checksums";
+/// The daemon accepts inbound `DelegatedPull` requests.
+pub const FEATURE_DELEGATED_PULL: &str = "delegated_pull";
+/// Block-wise `--resume` of half-present files.
+pub const FEATURE_RESUME: &str = "resume";
+/// Source-side `--rename` path rewrites (contract v6).
+pub const FEATURE_RENAME: &str = "rename";
+
+/// The operator switches a daemon's advertised feature set depends on.
+#[derive(Debug, Clone, Copy)]
+pub struct DaemonPolicy {
+    pub force_grpc_data: bool,
+    pub server_checksums_enabled: bool,
+    pub delegation_enabled: bool,
+}
+
+/// The daemon side: this build's identity plus the features `policy`
+/// leaves on. `motd` is the operator's `[daemon] motd`.
+pub fn advertise(policy: DaemonPolicy, motd: Option<&str>) -> CapabilitiesResponse {
+    let mut features = vec![FEATURE_RESUME, FEATURE_RENAME];
+    if !policy.force_grpc_data {
+        features.extend([FEATURE_TCP_DATA_PLANE, FEATURE_DIAL_PINS]);
+    }
+    if policy.server_checksums_enabled {
+        features.push(FEATURE_SERVER_CHECKSUMS);
+    }
+    if policy.delegation_enabled {
+        features.push(FEATURE_DELEGATED_PULL);
+    }
+    CapabilitiesResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        build_id: session_build_id().to_string(),
+        contract_version: CONTRACT_VERSION,
+        spec_version: SUPPORTED_SPEC_VERSION,
+        features: features.into_iter().map(str::to_string).collect(),
+        motd: motd.unwrap_or_default().to_string(),
+    }
+}
+
+/// What a daemon reported about itself, decoupled from the prost type.
+#[derive(Debug, Clone)]
+pub struct DaemonCapabilities {
+    pub version: String,
+    pub build_id: String,
+    pub contract_version: u32,
+    pub spec_version: u32,
+    pub features: BTreeSet<String>,
+    pub motd: Option<String>,
+}
+
+impl From<CapabilitiesResponse> for DaemonCapabilities {
+    fn from(resp: CapabilitiesResponse) -> Self {
+        Self {
+            version: resp.version,
+            build_id: resp.build_id,
+            contract_version: resp.contract_version,
+            spec_version: resp.spec_version,
+            features: resp.features.into_iter().collect(),
+            motd: (!resp.motd.is_empty()).then_some(resp.motd),
+        }
+    }
+}
+
+impl DaemonCapabilities {
+    pub fn supports(&self, feature: &str) -> bool {
+        self.features.contains(feature)
+    }
+
+    /// The HELLO's exact-match check, run before the session opens.
+    /// Same fault, same message, so a stale end reads identically
+    /// whichever surface caught it.
+    pub fn ensure_same_build(&self) -> std::result::Result<(), SessionFault> {
+        let local = HelloConfig::default();
+        if self.build_id == local.build_id && self.contract_version == local.contract_version {
+            Ok(())
+        } else {
+            Err(SessionFault::build_mismatch(
+                &local,
+                &self.build_id,
+                self.contract_version,
+            ))
+        }
+    }
+}
+
+/// Ask the daemon behind `client` for its capabilities. `Ok(None)` when
+/// the daemon predates the RPC (`UNIMPLEMENTED`).
+pub async fn probe(client: &mut BlitClient<Channel>) -> Result<Option<DaemonCapabilities>> {
+    match client.capabilities(CapabilitiesRequest {}).await {
+        Ok(resp) => Ok(Some(resp.into_inner().into())),
+        Err(status) if status.code() == tonic::Code::Unimplemented => Ok(None),
+        Err(status) => Err(eyre::Report::new(status)).context("querying daemon capabilities"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> DaemonPolicy {
+        DaemonPolicy {
+            force_grpc_data: false,
+            server_checksums_enabled: true,
+            delegation_enabled: false,
+        }
+    }
+
+    #[test]
+    fn advertised_features_follow_operator_policy() {
+        let caps = DaemonCapabilities::from(advertise(policy(), Some("hi")));
+        assert!(caps.supports(FEATURE_TCP_DATA_PLANE));
+        assert!(caps.supports(FEATURE_DIAL_PINS));
+        assert!(caps.supports(FEATURE_SERVER_CHECKSUMS));
+        assert!(!caps.supports(FEATURE_DELEGATED_PULL));
+        assert_eq!(caps.motd.as_deref(), Some("hi"));
+
+        let caps = DaemonCapabilities::from(advertise(
+            DaemonPolicy {
+                force_grpc_data: true,
+                server_checksums_enabled: false,
+                delegation_enabled: true,
+            },
+            None,
+        ));
+        assert!(!caps.supports(FEATURE_TCP_DATA_PLANE));
+        assert!(!caps.supports(FEATURE_DIAL_PINS));
+        assert!(!caps.supports(FEATURE_SERVER_CHECKSUMS));
+        assert!(caps.supports(FEATURE_DELEGATED_PULL));
+        assert!(caps.supports(FEATURE_RESUME));
+        assert_eq!(caps.motd, None);
+    }
+
+    #[test]
+    fn stale_daemon_build_is_a_build_mismatch_fault() {
+        let mut caps = DaemonCapabilities::from(advertise(policy(), None));
+        assert!(caps.ensure_same_build().is_ok());
+
+        caps.contract_version = CONTRACT_VERSION - 1;
+        let fault = caps.ensure_same_build().unwrap_err();
+        assert_eq!(
+            fault.code,
+            crate::generated::session_error::Code::BuildMismatch
+        );
+        assert_eq!(fault.peer_build_id, caps.build_id);
+        assert!(fault
+            .message
+            .contains(&format!("v{}", CONTRACT_VERSION - 1)));
+    }
+}
@@ -0,0 +1,192 @@
+//! Control-plane connect: the one bounded `Endpoint::connect` every
+//! `BlitClient` goes through, and the wording of its failures.
+//!
+//! Before this module the transfer session client and the app layer's
+//! admin helper each hardcoded a 30 s deadline and surfaced tonic's
+//! transport error verbatim — `transport error` over a chain that only
+//! sometimes named the cause. [`connect_channel`] bounds the whole
+//! connect (DNS + TCP) by the operator's `--contimeout` and
+//! [`describe_connect_failure`] turns the common causes (nothing
+//! listening, a name that does not resolve, an `https://` endpoint)
+//! into a message naming the host and port it tried.
+
+use std::error::Error as StdError;
+use std::io;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use eyre::Result;
+use tonic::transport::{Channel, Endpoint};
+
+use super::error_category::{ErrorCategory, RemoteError};
+
+/// Connect deadline when `--contimeout` is not given (audit-2's 30 s
+/// policy). Bounds both the outer `tokio::time::timeout` over the whole
+/// `connect()` future and the inner `Endpoint::connect_timeout` over
+/// the post-resolution TCP phase — the inner bound alone does NOT cover
+/// slow DNS, which hyper-util resolves before applying it.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+static CONNECT_TIMEOUT: OnceLock<Duration> = OnceLock::new();
+
+/// Install the process-wide control-plane connect deadline. Called by
+/// the CLI from `main` when `--contimeout` is supplied; a second call
+/// is silently ignored (same contract as
+/// `instrumentation::set_counter_path`).
+pub fn set_connect_timeout(timeout: Duration) {
+    let _ = CONNECT_TIMEOUT.set(timeout);
+}
+
+/// The installed connect deadline, or [`DEFAULT_CONNECT_TIMEOUT`].
+pub fn connect_timeout() -> Duration {
+    CONNECT_TIMEOUT
+        .get()
+        .copied()
+        .unwrap_or(DEFAULT_CONNECT_TIMEOUT)
+}
+
+/// Open a control-plane channel to `endpoint` within
+/// [`connect_timeout`]. Failures are [`RemoteError`]s in the
+/// `Connection` category, worded by [`describe_connect_failure`].
+pub async fn connect_channel(endpoint: Endpoint) -> Result<Channel> {
+    let timeout = connect_timeout();
+    let target = authority(&endpoint);
+    let endpoint = match super::transfer::socket::local_address() {
+        Some(local) => endpoint.local_address(Some(local)),
+        None => endpoint,
+    };
+    match tokio::time::timeout(timeout, endpoint.connect_timeout(timeout).connect()).await {
+        Ok(Ok(channel)) => Ok(channel),
+        Ok(Err(err)) => Err(describe_connect_failure(&target, &err).into()),
+        Err(_) => Err(RemoteError {
+            category: ErrorCategory::Connection,
+            message: format!(
+                "connecting to {target} timed out after {timeout:?} — the host is \
+                 unreachable or not answering; raise --contimeout if the network is slow"
+            ),
+        }
+        .into()),
+    }
+}
+
+/// Word a failed connect to `target` (`host:port`) by its cause. The
+/// transport error's own chain is kept after the lead sentence so
+/// nothing tonic reported is lost.
+pub fn describe_connect_failure(target: &str, err: &(dyn StdError + 'static)) -> RemoteError {
+    let detail = chain_text(err);
+    let lead = if io_kind(err) == Some(io::ErrorKind::ConnectionRefused) {
+        format!(
+            "connection refused by {target} — is blit-daemon running and listening on that port?"
+        )
+    } else if causes(err).any(|cause| {
+        let text = cause.to_string();
+        text == "dns error" || text.contains("failed to lookup address")
+    }) {
+        let host = target.rsplit_once(':').map_or(target, |(host, _)| host);
+        format!("could not resolve host '{host}' (connecting to {target}) — check the name")
+    } else if detail.to_ascii_lowercase().contains("tls") {
+        format!(
+            "TLS connect to {target} failed — blit-daemon serves plaintext gRPC; \
+             address it as host:port, not https://"
+        )
+    } else if matches!(
+        io_kind(err),
+        Some(io::ErrorKind::HostUnreachable | io::ErrorKind::NetworkUnreachable)
+    ) {
+        format!("{target} is unreachable from this host — check the address and routing")
+    } else {
+        format!("connecting to {target} failed")
+    };
+    RemoteError {
+        category: ErrorCategory::Connection,
+        message: format!("{lead}: {detail}"),
+    }
+}
+
+fn authority(endpoint: &Endpoint) -> String {
+    endpoint
+        .uri()
+        .authority()
+        .map(|authority| authority.to_string())
+        .unwrap_or_else(|| endpoint.uri().to_string())
+}
+
+fn causes<'a>(
+    err: &'a (dyn StdError + 'static),
+) -> impl Iterator<Item = &'a (dyn StdError + 'static)> {
+    std::iter::successors(Some(err), |&cause| cause.source())
+}
+
+fn io_kind(err: &(dyn StdError + 'static)) -> Option<io::ErrorKind> {
+    causes(err).find_map(|cause| cause.downcast_ref::<io::Error>().map(io::Error::kind))
+}
+
+fn chain_text(err: &(dyn StdError + 'static)) -> String {
+    causes(err)
+        .map(|cause| cause.to_string())
+        .collect::<Vec<_>>()
+        .join(": ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn closed_port_fails_fast_with_a_refused_message() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let started = Instant::now();
+        let err =
+            connect_channel(Endpoint::from_shared(format!("http://127.0.0.1:{port}")).unwrap())
+                .await
+                .expect_err("nothing listens on a dropped listener's port");
+        assert!(
+            started.elapsed() < Duration::from_secs(5),
+            "refused connect took {:?}",
+            started.elapsed()
+        );
+        let msg = err.to_string();
+        assert!(
+            msg.contains(&format!("connection refused by 127.0.0.1:{port}")),
+            "unexpected error: {msg}"
+        );
+        assert_eq!(
+            crate::remote::error_category::categorize(&err),
+            ErrorCategory::Connection
+        );
+    }
+
+    #[test]
+    fn unresolvable_names_and_tls_failures_get_their_own_wording() {
+        #[derive(Debug)]
+        struct Wrapped(&'static str, io::Error);
+        impl std::fmt::Display for Wrapped {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.0)
+            }
+        }
+        impl StdError for Wrapped {
+            fn source(&self) -> Option<&(dyn StdError + 'static)> {
+                Some(&self.1)
+            }
+        }
+
+        let dns = Wrapped(
+            "dns error",
+            io::Error::other("failed to lookup address information"),
+        );
+        let msg = describe_connect_failure("nosuch.invalid:9031", &dns).message;
+        assert!(
+            msg.starts_with("could not resolve host 'nosuch.invalid'"),
+            "{msg}"
+        );
+
+        let tls = io::Error::other("Connecting to HTTPS without TLS enabled");
+        let msg = describe_connect_failure("host:9031", &tls).message;
+        assert!(msg.starts_with("TLS connect to host:9031 failed"), "{msg}");
+    }
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 
 use eyre::{bail, eyre, Result};
@@ -48,54 +49,49 @@ impl RemoteEndpoint {
             LocalPathCheck::NotLocal => {}
         }
 
-        if let Some(idx) = trimmed.find("://") {
-            // Root export (server://path)
-            let host_port = &trimmed[..idx];
-            let remainder = &trimmed[idx + 3..];
-            let (host, port) = parse_host_port(host_port)?;
-            let rel = normalize_relative_path_buf(remainder);
+        // The authority is split off first and everything after it is
+        // path: a `:` or `://` inside the module path (`a:b`, `x://y`)
+        // must not be mistaken for the host/path separator.
+        let (host, port, spec) = split_authority(trimmed)?;
+        let Some(spec) = spec else {
+            // Discovery (server or server:port)
             return Ok(Self {
                 host,
                 port,
-                path: RemotePath::Root { rel_path: rel },
+                path: RemotePath::Discovery,
             });
-        }
-
-        if let Some(idx) = trimmed.find(":/") {
-            // Module export (server:/module/...)
-            let host_port = &trimmed[..idx];
-            let remainder = &trimmed[idx + 2..];
-            let (host, port) = parse_host_port(host_port)?;
-
-            let slash_idx = remainder.find('/').ok_or_else(|| {
-                eyre!(
-                    "module path must end with '/' (e.g., server:/module/ or server:/module/path)"
-                )
-            })?;
-
-            let module = &remainder[..slash_idx];
-            if module.is_empty() {
-                bail!("module name cannot be empty; expected server:/module/...");
-            }
-            let rest = &remainder[slash_idx + 1..];
-            let rel = normalize_relative_path_buf(rest);
+        };
 
+        if let Some(remainder) = spec.strip_prefix("//") {
+            // Root export (server://path)
+            let rel = normalize_relative_path_buf(&decode_path(remainder)?);
             return Ok(Self {
                 host,
                 port,
-                path: RemotePath::Module {
-                    module: module.to_string(),
-                    rel_path: rel,
-                },
+                path: RemotePath::Root { rel_path: rel },
             });
         }
 
-        // Discovery (server or server:port)
-        let (host, port) = parse_host_port(trimmed)?;
+        // Module export (server:/module/...)
+        let remainder = &spec[1..];
+        let slash_idx = remainder.find('/').ok_or_else(|| {
+            eyre!("module path must end with '/' (e.g., server:/module/ or server:/module/path)")
+        })?;
+
+        let module = &remainder[..slash_idx];
+        if module.is_empty() {
+            bail!("module name cannot be empty; expected server:/module/...");
+        }
+        let rest = &remainder[slash_idx + 1..];
+        let rel = normalize_relative_path_buf(&decode_path(rest)?);
+
         Ok(Self {
             host,
             port,
-            path: RemotePath::Discovery,
+            path: RemotePath::Module {
+                module: module.to_string(),
+                rel_path: rel,
+            },
         })
     }
 
@@ -106,12 +102,10 @@ impl RemoteEndpoint {
         // A colon-containing host can only be IPv6 in our schema —
         // hostnames and IPv4 addresses never contain colons. Bare
         // `2001:db8::1:9031` is parsed by HTTP libraries as host
-        // `2001` with garbage trailing, which is the bug.
-        if self.host.contains(':') {
-            format!("http://[{}]:{}", self.host, self.port)
-        } else {
-            format!("http://{}:{}", self.host, self.port)
-        }
+        // `2001` with garbage trailing, which is the bug. A zone stays
+        // in its raw `%eth0` form: the connector hands the bracket-less
+        // host to the system resolver, which understands that spelling.
+        format!("http://{}", format_host_port(&self.host, self.port))
     }
 
     /// Render just the `host[:port]` portion of this endpoint
@@ -162,38 +156,92 @@ impl RemoteEndpoint {
     }
 }
 
-fn parse_host_port(authority: &str) -> Result<(String, u16)> {
-    if authority.is_empty() {
-        bail!("remote location missing host");
-    }
-
-    if let Some(stripped) = authority.strip_prefix('[') {
-        // IPv6 literal
+/// Split `raw` into host, port, and the path spec that follows the
+/// authority (`/module/...` or `//path`; `None` for the discovery
+/// form). A bracketed IPv6 literal runs to its `]` and may carry a zone
+/// (`[fe80::1%eth0]`); any other host runs to the first `:` — hostnames
+/// and IPv4 addresses never contain one.
+/// A `:` followed by digits and then `:` or the end is the port;
+/// anything else after the host must be `:/` or `://`.
+fn split_authority(raw: &str) -> Result<(String, u16, Option<&str>)> {
+    let (host, after_host) = if let Some(stripped) = raw.strip_prefix('[') {
         let closing = stripped
             .find(']')
-            .ok_or_else(|| eyre!("unterminated IPv6 address: {}", authority))?;
-        let host = &stripped[..closing];
-        let remainder = &stripped[closing + 1..];
-        let port = if let Some(port_str) = remainder.strip_prefix(':') {
-            parse_port(port_str)?
-        } else if remainder.is_empty() {
-            RemoteEndpoint::DEFAULT_PORT
+            .ok_or_else(|| eyre!("unterminated IPv6 address: {}", raw))?;
+        let after = &stripped[closing + 1..];
+        if !after.is_empty() && !after.starts_with(':') {
+            bail!("invalid host specification: {}", raw);
+        }
+        let literal = &stripped[..closing];
+        if literal.contains('%') {
+            (Cow::Owned(normalize_scoped_ipv6(literal)?), after)
         } else {
-            bail!("invalid host specification: {}", authority);
-        };
-        return Ok((host.to_string(), port));
+            (Cow::Borrowed(literal), after)
+        }
+    } else {
+        let end = raw.find(':').unwrap_or(raw.len());
+        (Cow::Borrowed(&raw[..end]), &raw[end..])
+    };
+    let host = host.as_ref();
+    if host.is_empty() {
+        bail!("remote location missing host");
+    }
+
+    let Some(after_colon) = after_host.strip_prefix(':') else {
+        return Ok((host.to_string(), RemoteEndpoint::DEFAULT_PORT, None));
+    };
+    if after_colon.starts_with('/') {
+        return Ok((
+            host.to_string(),
+            RemoteEndpoint::DEFAULT_PORT,
+            Some(after_colon),
+        ));
     }
 
-    if let Some((host, port)) = authority.rsplit_once(':') {
-        if host.is_empty() {
-            bail!("remote location missing host before ':'");
+    let (port_str, spec) = match after_colon.split_once(':') {
+        Some((port_str, spec)) => (port_str, Some(spec)),
+        None => (after_colon, None),
+    };
+    let port = parse_port(port_str).map_err(|err| {
+        // `2001:db8::1` splits at its first colon; say why.
+        if after_colon.contains(':') && host.bytes().all(|b| b.is_ascii_hexdigit()) {
+            eyre!("{err}; IPv6 addresses must be bracketed, e.g. [::1]:9031:/module/")
+        } else {
+            err
         }
-        Ok((host.to_string(), parse_port(port)?))
-    } else {
-        Ok((authority.to_string(), RemoteEndpoint::DEFAULT_PORT))
+    })?;
+    match spec {
+        Some(spec) if spec.starts_with('/') => Ok((host.to_string(), port, Some(spec))),
+        Some(_) => bail!(
+            "expected ':/module/' or '://path' after {}; got: {}",
+            format_host_port(host, port),
+            raw
+        ),
+        None => Ok((host.to_string(), port, None)),
     }
 }
 
+/// Normalize a bracketed IPv6 literal carrying a zone (`fe80::1%eth0`,
+/// or RFC 6874's URI form `fe80::1%25eth0`) to the bare `addr%zone`
+/// form the host field stores. The address must be IPv6 and the zone
+/// non-empty; it is kept verbatim — the data-plane dial maps a named
+/// zone to its interface index.
+fn normalize_scoped_ipv6(literal: &str) -> Result<String> {
+    let (addr, zone) = literal
+        .split_once('%')
+        .expect("caller checked for a zone separator");
+    let zone = zone
+        .strip_prefix("25")
+        .filter(|z| !z.is_empty())
+        .unwrap_or(zone);
+    if zone.is_empty() || zone.contains(['%', '/', ':']) {
+        bail!("invalid IPv6 zone identifier in [{}]", literal);
+    }
+    addr.parse::<std::net::Ipv6Addr>()
+        .map_err(|_| eyre!("a zone identifier needs an IPv6 address: [{}]", literal))?;
+    Ok(format!("{addr}%{zone}"))
+}
+
 fn parse_port(raw: &str) -> Result<u16> {
     if raw.is_empty() {
         return Ok(RemoteEndpoint::DEFAULT_PORT);
@@ -202,6 +250,44 @@ fn parse_port(raw: &str) -> Result<u16> {
         .map_err(|_| eyre!("invalid port '{}'", raw))
 }
 
+/// Percent-decode the relative path portion of a remote location, so
+/// names the shell or a URL-minded caller cannot pass verbatim can be
+/// written as `%XX` (`my%20file`, `100%25`). A `%` not followed by two
+/// hex digits is kept literally; a decode that is not UTF-8 is refused.
+fn decode_path(raw: &str) -> Result<String> {
+    if !raw.contains('%') {
+        return Ok(raw.to_string());
+    }
+    let bytes = raw.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|pair| std::str::from_utf8(pair).ok())
+            .filter(|pair| pair.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|pair| u8::from_str_radix(pair, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(out).map_err(|_| eyre!("percent-encoded path is not valid UTF-8: {}", raw))
+}
+
+/// Render `host:port` for a socket address or URI authority, bracketing
+/// an IPv6 literal (`[::1]:9031`). `host` is stored bracket-less, as
+/// [`RemoteEndpoint::parse`] leaves it.
+pub fn format_host_port(host: &str, port: impl std::fmt::Display) -> String {
+    format!("{}:{}", display_host(host), port)
+}
+
 fn normalize_relative_path_buf(raw: &str) -> PathBuf {
     if raw.is_empty() {
         PathBuf::new()
@@ -417,4 +503,78 @@ mod tests {
         let ep = RemoteEndpoint::parse("[::1]:9444:/m/path").expect("parse");
         assert_eq!(ep.host_port_display(), "[::1]:9444");
     }
+
+    fn module_parts(ep: &RemoteEndpoint) -> (&str, String) {
+        match &ep.path {
+            RemotePath::Module { module, rel_path } => (module, rel_path_to_string(rel_path)),
+            other => panic!("expected module path, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn paths_with_spaces_parse_verbatim_or_percent_encoded() {
+        let ep = RemoteEndpoint::parse("server:/media/My Photos/day one.jpg").unwrap();
+        assert_eq!(module_parts(&ep), ("media", "My Photos/day one.jpg".into()));
+
+        let ep = RemoteEndpoint::parse("server:/media/My%20Photos/100%25.jpg").unwrap();
+        assert_eq!(module_parts(&ep), ("media", "My Photos/100%.jpg".into()));
+
+        // A `%` that is not an escape stays literal.
+        let ep = RemoteEndpoint::parse("server:/media/50%off").unwrap();
+        assert_eq!(module_parts(&ep), ("media", "50%off".into()));
+    }
+
+    #[test]
+    fn ipv6_literal_with_port_and_root_path() {
+        let ep = RemoteEndpoint::parse("[::1]:9031://backups/a").unwrap();
+        assert_eq!(ep.host, "::1");
+        assert_eq!(ep.port, 9031);
+        assert!(matches!(ep.path, RemotePath::Root { .. }));
+        assert_eq!(ep.control_plane_uri(), "http://[::1]:9031");
+
+        let ep = RemoteEndpoint::parse("[fe80::2]:9444").unwrap();
+        assert_eq!((ep.host.as_str(), ep.port), ("fe80::2", 9444));
+        assert!(matches!(ep.path, RemotePath::Discovery));
+        assert_eq!(format_host_port(&ep.host, ep.port), "[fe80::2]:9444");
+    }
+
+    #[test]
+    fn scoped_ipv6_literal_keeps_its_zone() {
+        let ep = RemoteEndpoint::parse("[fe80::1%eth0]:9444:/mod/dir").unwrap();
+        assert_eq!((ep.host.as_str(), ep.port), ("fe80::1%eth0", 9444));
+        assert_eq!(module_parts(&ep), ("mod", "dir".into()));
+        assert_eq!(ep.display(), "[fe80::1%eth0]:9444:/mod/dir");
+        assert_eq!(format_host_port(&ep.host, ep.port), "[fe80::1%eth0]:9444");
+        assert_eq!(ep.control_plane_uri(), "http://[fe80::1%eth0]:9444");
+        tonic::transport::Endpoint::from_shared(ep.control_plane_uri())
+            .expect("the control-plane URI stays parseable");
+
+        // RFC 6874's URI spelling normalizes to the same host.
+        let ep = RemoteEndpoint::parse("[fe80::1%25eth0]:/mod/").unwrap();
+        assert_eq!(ep.host, "fe80::1%eth0");
+
+        for bad in ["[fe80::1%]:/mod/", "[host%eth0]:/mod/", "[::1%a/b]:/mod/"] {
+            assert!(RemoteEndpoint::parse(bad).is_err(), "{bad} must be refused");
+        }
+    }
+
+    #[test]
+    fn colons_after_the_authority_belong_to_the_path() {
+        let ep = RemoteEndpoint::parse("server:9000:/data/C:/notes:v2.txt").unwrap();
+        assert_eq!(ep.port, 9000);
+        assert_eq!(module_parts(&ep), ("data", "C:/notes:v2.txt".into()));
+
+        // `://` inside a module path is not the root-export separator
+        // (the doubled slash collapses like any path component).
+        let ep = RemoteEndpoint::parse("server:/data/http://mirror").unwrap();
+        assert_eq!(module_parts(&ep), ("data", "http:/mirror".into()));
+    }
+
+    #[test]
+    fn unbracketed_ipv6_and_bad_ports_are_explained() {
+        let err = RemoteEndpoint::parse("2001:db8::1:/m/").unwrap_err();
+        assert!(err.to_string().contains("must be bracketed"), "{err}");
+        let err = RemoteEndpoint::parse("server:port:/m/").unwrap_err();
+        assert!(err.to_string().contains("invalid port 'port'"), "{err}");
+    }
 }
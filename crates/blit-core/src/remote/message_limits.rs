@@ -0,0 +1,182 @@
+//! gRPC message-size policy for the control plane.
+//!
+//! tonic caps every decoded message at 4 MiB unless told otherwise, and
+//! a frame over the cap fails the whole RPC with an opaque
+//! `OutOfRange`/`ResourceExhausted` status. Before this module nothing
+//! in the workspace set a limit: safety rested on the in-stream payload
+//! chunk ([`CONTROL_PLANE_CHUNK_SIZE`]) and the per-frame resume and
+//! tar-header ceilings staying well under that unstated 4 MiB.
+//!
+//! [`MessageLimits`] owns both knobs. Every `BlitClient` is built
+//! through [`blit_client`] and every `BlitServer` through
+//! [`blit_server`], so the decode and encode ceilings are set in one
+//! place; the in-stream carrier chunks payload at
+//! [`MessageLimits::control_chunk_bytes`].
+//!
+//! What rides one frame: the manifest is one `FileHeader` per frame, a
+//! `NeedBatch` names at most one diff batch of paths (default 128,
+//! capped under 4 MiB by
+//! [`MAX_DIFF_BATCH_BYTES`](crate::transfer_session::diff_batch::MAX_DIFF_BATCH_BYTES)),
+//! and payload is
+//! split at the control chunk size — so with the defaults only a
+//! pathological `List` response or an oversized chunk comes near the
+//! ceiling. The raised default ceiling keeps those working against a
+//! peer built with this module; a peer without it still decodes at
+//! 4 MiB, which is why the chunk default stays at 1 MiB.
+
+use std::sync::OnceLock;
+
+use eyre::Context as _;
+use tonic::transport::Channel;
+
+use crate::generated::blit_client::BlitClient;
+use crate::generated::blit_server::{Blit, BlitServer};
+pub use crate::remote::transfer::CONTROL_PLANE_CHUNK_SIZE;
+
+/// tonic's built-in decode ceiling — the limit every peer ran with
+/// before [`MessageLimits`] existed.
+pub const TONIC_DEFAULT_MAX_MESSAGE_BYTES: usize = 4 * 1024 * 1024;
+
+/// Default encode/decode ceiling for every control-plane message.
+/// Sixteen times tonic's default: room for large listings and for a
+/// raised control chunk without letting one frame pin unbounded memory.
+pub const DEFAULT_MAX_MESSAGE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Headroom a payload frame needs over its chunk for the protobuf
+/// envelope (frame tag, length prefixes). The chunk must fit the
+/// ceiling with this to spare.
+pub const CHUNK_ENVELOPE_HEADROOM: usize = 64 * 1024;
+
+/// Smallest accepted control chunk; below this the per-frame overhead
+/// dominates the payload.
+pub const MIN_CONTROL_CHUNK_BYTES: usize = 4 * 1024;
+
+/// Control-plane message limits, installed once per process: by the
+/// `blit` CLI from the global `--max-message-size`/`--control-chunk-size`
+/// flags and by the daemon from the `[daemon]`
+/// `max_message_size`/`control_chunk_size` config keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageLimits {
+    /// Encode and decode ceiling for one gRPC message.
+    pub max_message_bytes: usize,
+    /// Payload bytes per `FileData`/`TarShardChunk` frame on the
+    /// in-stream carrier.
+    pub control_chunk_bytes: usize,
+}
+
+impl Default for MessageLimits {
+    fn default() -> Self {
+        Self {
+            max_message_bytes: DEFAULT_MAX_MESSAGE_BYTES,
+            control_chunk_bytes: CONTROL_PLANE_CHUNK_SIZE,
+        }
+    }
+}
+
+impl MessageLimits {
+    /// Parse the operator-facing sizes shared by the CLI flags and the
+    /// daemon config keys (`64Mi`, `1048576`; see
+    /// [`crate::fs_enum::parse_size`]). Unset values keep the default.
+    /// The ceiling may not drop below tonic's 4 MiB — every fixed
+    /// per-frame bound in the session assumes at least that — and the
+    /// chunk plus [`CHUNK_ENVELOPE_HEADROOM`] must fit under it.
+    pub fn parse(
+        max_message_size: Option<&str>,
+        control_chunk_size: Option<&str>,
+    ) -> eyre::Result<Self> {
+        let defaults = Self::default();
+        let max_message_bytes = max_message_size
+            .map(|raw| parse_bytes(raw, "max message size"))
+            .transpose()?
+            .unwrap_or(defaults.max_message_bytes);
+        if max_message_bytes < TONIC_DEFAULT_MAX_MESSAGE_BYTES {
+            eyre::bail!(
+                "max message size {max_message_bytes} is below the 4 MiB every peer accepts"
+            );
+        }
+        let control_chunk_bytes = control_chunk_size
+            .map(|raw| parse_bytes(raw, "control chunk size"))
+            .transpose()?
+            .unwrap_or(defaults.control_chunk_bytes);
+        if control_chunk_bytes < MIN_CONTROL_CHUNK_BYTES {
+            eyre::bail!(
+                "control chunk size {control_chunk_bytes} is below the {MIN_CONTROL_CHUNK_BYTES}-byte minimum"
+            );
+        }
+        if control_chunk_bytes.saturating_add(CHUNK_ENVELOPE_HEADROOM) > max_message_bytes {
+            eyre::bail!(
+                "control chunk size {control_chunk_bytes} does not fit the max message size \
+                 {max_message_bytes} (a frame needs {CHUNK_ENVELOPE_HEADROOM} bytes of envelope)"
+            );
+        }
+        Ok(Self {
+            max_message_bytes,
+            control_chunk_bytes,
+        })
+    }
+}
+
+fn parse_bytes(raw: &str, what: &str) -> eyre::Result<usize> {
+    let bytes =
+        crate::fs_enum::parse_size(raw).with_context(|| format!("invalid {what} '{raw}'"))?;
+    usize::try_from(bytes).with_context(|| format!("{what} '{raw}' is too large"))
+}
+
+static MESSAGE_LIMITS: OnceLock<MessageLimits> = OnceLock::new();
+
+/// Install the process-wide limits. A second call is silently ignored
+/// (same contract as
+/// [`set_data_socket_overrides`](crate::remote::transfer::socket::set_data_socket_overrides)).
+pub fn set_message_limits(limits: MessageLimits) {
+    let _ = MESSAGE_LIMITS.set(limits);
+}
+
+/// The installed limits, or [`MessageLimits::default`].
+pub fn message_limits() -> MessageLimits {
+    MESSAGE_LIMITS.get().copied().unwrap_or_default()
+}
+
+/// A `BlitClient` over `channel` with the installed ceilings.
+pub fn blit_client(channel: Channel) -> BlitClient<Channel> {
+    let limits = message_limits();
+    BlitClient::new(channel)
+        .max_decoding_message_size(limits.max_message_bytes)
+        .max_encoding_message_size(limits.max_message_bytes)
+}
+
+/// A `BlitServer` for `service` with the installed ceilings — the
+/// daemon and in-process test servers alike.
+pub fn blit_server<T: Blit>(service: T) -> BlitServer<T> {
+    let limits = message_limits();
+    BlitServer::new(service)
+        .max_decoding_message_size(limits.max_message_bytes)
+        .max_encoding_message_size(limits.max_message_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_clear_the_tonic_ceiling() {
+        let limits = MessageLimits::parse(None, None).unwrap();
+        assert_eq!(limits, MessageLimits::default());
+        assert!(limits.max_message_bytes > TONIC_DEFAULT_MAX_MESSAGE_BYTES);
+        assert!(
+            limits.control_chunk_bytes + CHUNK_ENVELOPE_HEADROOM <= TONIC_DEFAULT_MAX_MESSAGE_BYTES,
+            "the default chunk must still fit a peer decoding at 4 MiB"
+        );
+    }
+
+    #[test]
+    fn parse_accepts_sizes_and_refuses_chunks_that_do_not_fit() {
+        let limits = MessageLimits::parse(Some("128Mi"), Some("8Mi")).unwrap();
+        assert_eq!(limits.max_message_bytes, 128 << 20);
+        assert_eq!(limits.control_chunk_bytes, 8 << 20);
+
+        assert!(MessageLimits::parse(Some("1Mi"), None).is_err());
+        assert!(MessageLimits::parse(None, Some("1Ki")).is_err());
+        assert!(MessageLimits::parse(Some("8Mi"), Some("8Mi")).is_err());
+        assert!(MessageLimits::parse(Some("lots"), None).is_err());
+    }
+}
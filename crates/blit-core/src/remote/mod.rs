@@ -1,7 +1,16 @@
+pub mod capabilities;
+pub mod connect;
 pub mod endpoint;
+pub mod error_category;
 pub mod grpc_server;
 pub mod instrumentation;
+pub mod message_limits;
 pub mod retry;
 pub mod transfer;
 
 pub use endpoint::{RemoteEndpoint, RemotePath};
+
+/// Request metadata key carrying the operator's admin token. A
+/// `CancelJob` bearing the daemon's `[daemon] admin_token` may cancel
+/// any active transfer, not only one started from the caller's host.
+pub const ADMIN_TOKEN_METADATA: &str = "x-blit-admin-token";
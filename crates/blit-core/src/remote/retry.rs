@@ -134,6 +134,7 @@ mod tests {
                 peer_notified: false,
                 relative_path: None,
                 io_kind,
+                data_plane_unreachable: false,
             })
             .wrap_err("pushing to host:/mod/")
         };
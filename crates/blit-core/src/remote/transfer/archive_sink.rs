@@ -0,0 +1,361 @@
+//! A tar archive as the receive destination (`blit copy remote:/x FIFO`).
+//!
+//! [`ArchiveSink`] is the write-side twin of
+//! [`ArchiveTransferSource`](super::archive_source::ArchiveTransferSource):
+//! instead of landing each received file under a destination root it
+//! appends it, as it arrives, to one tar stream written to any
+//! [`Write`] — a named pipe or a pipe on stdout feeding `tar x`. Every
+//! carrier lands here: streamed `FILE` records and small-file runs are
+//! appended as they are read off the wire, and tar shards and tar
+//! streams are walked with the same `tar_safety` checks the filesystem
+//! sink runs, their members re-emitted in order. Members carry the
+//! header every Blit archive member gets ([`tar_member_header`]), so
+//! mode and mtime survive; directories are implicit in their files'
+//! paths, as on the source side.
+//!
+//! The pipe is one sequential stream, so members are appended whole
+//! under a lock: parallel receive streams interleave by member, never
+//! inside one. Nothing is seekable — a member whose bytes run short of
+//! its header size fails the transfer, leaving a truncated archive the
+//! consumer rejects. [`ArchiveSink::close`] writes the end-of-archive
+//! blocks once the whole session succeeded — not
+//! [`TransferSink::finish`], which every receive worker calls as its
+//! socket drains — and a transfer that fails never reaches it, for the
+//! same reason.
+//!
+//! [`tar_member_header`]: super::payload::tar_member_header
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use eyre::{bail, eyre, Context, Result};
+use tar::Builder;
+
+use crate::generated::FileHeader;
+use crate::path_safety::validate_wire_path;
+use crate::remote::transfer::payload::{tar_member_header, PreparedPayload};
+use crate::remote::transfer::sink::{pump_chunks, ChunkChannelReader, SinkOutcome, TransferSink};
+use crate::remote::transfer::tar_safety::{
+    safe_extract_tar_shard, safe_extract_tar_stream, ExtractedFile, TarShardExtractOptions,
+};
+
+type Archive = Builder<Box<dyn Write + Send>>;
+
+/// Appends every received file to one tar stream. See the module docs.
+pub struct ArchiveSink {
+    /// `None` once [`ArchiveSink::close`] ran.
+    archive: Arc<Mutex<Option<Archive>>>,
+    /// The session's (empty, scratch) destination root; never written.
+    root: PathBuf,
+    /// Member name for a single-file source, whose manifest entry has
+    /// an empty relative path.
+    root_name: String,
+}
+
+impl ArchiveSink {
+    /// Write the archive to `out`. `root` is the scratch root the
+    /// session diffs against; `root_name` names the member when the
+    /// source is one file rather than a tree.
+    pub fn new(out: Box<dyn Write + Send>, root: PathBuf, root_name: String) -> Self {
+        Self {
+            archive: Arc::new(Mutex::new(Some(Builder::new(out)))),
+            root,
+            root_name,
+        }
+    }
+
+    /// Close the archive: the end-of-archive blocks, then a flush. Call
+    /// once, after the session succeeded; a later append fails.
+    pub async fn close(&self) -> Result<()> {
+        let archive = Arc::clone(&self.archive);
+        tokio::task::spawn_blocking(move || {
+            let taken = archive
+                .lock()
+                .map_err(|_| eyre!("archive sink lock poisoned"))?
+                .take();
+            if let Some(archive) = taken {
+                let mut out = archive.into_inner().context("finishing the archive")?;
+                out.flush().context("flushing the archive")?;
+            }
+            Ok(())
+        })
+        .await
+        .context("archive sink worker panicked")?
+    }
+
+    fn appender(&self) -> Appender {
+        Appender {
+            archive: Arc::clone(&self.archive),
+            root_name: self.root_name.clone(),
+        }
+    }
+
+    /// Run `write` on a blocking thread with the appender, reading any
+    /// `reader` bytes through a channel while it runs.
+    async fn with_streamed<T: Send + 'static>(
+        &self,
+        reader: &mut (dyn tokio::io::AsyncRead + Unpin + Send),
+        what: &str,
+        write: impl FnOnce(&Appender, ChunkChannelReader) -> Result<T> + Send + 'static,
+    ) -> Result<T> {
+        let (tx, rx) = tokio::sync::mpsc::channel::<Vec<u8>>(4);
+        let appender = self.appender();
+        let worker =
+            tokio::task::spawn_blocking(move || write(&appender, ChunkChannelReader::new(rx)));
+        let pumped = pump_chunks(reader, tx, what).await;
+        let written = worker.await.context("archive sink worker panicked")?;
+        pumped?;
+        written
+    }
+}
+
+/// The blocking half: appends whole members under the archive lock.
+struct Appender {
+    archive: Arc<Mutex<Option<Archive>>>,
+    root_name: String,
+}
+
+impl Appender {
+    /// Append `header`'s member, its bytes read from `data`. Exactly
+    /// `header.size` bytes are taken; fewer fails the member.
+    fn append(&self, header: &FileHeader, data: impl Read) -> Result<u64> {
+        let path = if header.relative_path.is_empty() {
+            PathBuf::from(&self.root_name)
+        } else {
+            validate_wire_path(&header.relative_path)?
+        };
+        let mut guard = self
+            .archive
+            .lock()
+            .map_err(|_| eyre!("archive sink lock poisoned"))?;
+        let archive = guard
+            .as_mut()
+            .ok_or_else(|| eyre!("archive already closed; cannot add {}", path.display()))?;
+        let mut limited = data.take(header.size);
+        archive
+            .append_data(&mut tar_member_header(header), &path, &mut limited)
+            .with_context(|| format!("adding {} to the archive", path.display()))?;
+        if limited.limit() > 0 {
+            bail!(
+                "{} ended {} byte(s) short of its {}-byte header; the archive is truncated",
+                path.display(),
+                limited.limit(),
+                header.size
+            );
+        }
+        Ok(header.size)
+    }
+
+    fn append_extracted(&self, file: &ExtractedFile) -> Result<u64> {
+        let header = FileHeader {
+            relative_path: file.rel.clone(),
+            size: file.size,
+            mtime_seconds: file.mtime.map_or(0, |mtime| mtime.unix_seconds()),
+            permissions: file.permissions.unwrap_or(0),
+            ..FileHeader::default()
+        };
+        self.append(&header, file.contents.as_slice())
+    }
+}
+
+fn outcome(files_written: usize, bytes_written: u64) -> SinkOutcome {
+    SinkOutcome {
+        files_written,
+        bytes_written,
+    }
+}
+
+#[async_trait]
+impl TransferSink for ArchiveSink {
+    async fn write_payload(&self, payload: PreparedPayload) -> Result<SinkOutcome> {
+        let PreparedPayload::TarShard { headers, data } = payload else {
+            bail!("an archive destination receives wire records only, not local copies or resume blocks");
+        };
+        let appender = self.appender();
+        let root = self.root.clone();
+        tokio::task::spawn_blocking(move || {
+            let members =
+                safe_extract_tar_shard(&data, headers, &root, &TarShardExtractOptions::default())?;
+            let mut bytes = 0;
+            for member in &members {
+                bytes += appender.append_extracted(member)?;
+            }
+            Ok(outcome(members.len(), bytes))
+        })
+        .await
+        .context("archive sink worker panicked")?
+    }
+
+    async fn write_file_stream(
+        &self,
+        header: &FileHeader,
+        reader: &mut (dyn tokio::io::AsyncRead + Unpin + Send),
+    ) -> Result<SinkOutcome> {
+        let header = header.clone();
+        self.with_streamed(reader, "file stream", move |appender, data| {
+            Ok(outcome(1, appender.append(&header, data)?))
+        })
+        .await
+    }
+
+    async fn write_tar_stream(
+        &self,
+        headers: Vec<FileHeader>,
+        reader: &mut (dyn tokio::io::AsyncRead + Unpin + Send),
+    ) -> Result<SinkOutcome> {
+        let root = self.root.clone();
+        self.with_streamed(reader, "tar stream", move |appender, data| {
+            let (mut files, mut bytes) = (0, 0);
+            safe_extract_tar_stream(
+                data,
+                headers,
+                &root,
+                &TarShardExtractOptions::default(),
+                |member| {
+                    bytes += appender.append_extracted(&member)?;
+                    files += 1;
+                    Ok(())
+                },
+            )?;
+            Ok(outcome(files, bytes))
+        })
+        .await
+    }
+
+    /// One blocking hop for the run, as on the filesystem sink.
+    async fn write_small_files(&self, files: Vec<(FileHeader, Vec<u8>)>) -> Result<SinkOutcome> {
+        let appender = self.appender();
+        tokio::task::spawn_blocking(move || {
+            let mut bytes = 0;
+            for (header, contents) in &files {
+                bytes += appender.append(header, contents.as_slice())?;
+            }
+            Ok(outcome(files.len(), bytes))
+        })
+        .await
+        .context("archive sink worker panicked")?
+    }
+
+    fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `Write` the test can read back after the sink drops its half.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn header(rel: &str, size: u64) -> FileHeader {
+        FileHeader {
+            relative_path: rel.to_string(),
+            size,
+            mtime_seconds: 1_700_000_000,
+            permissions: 0o640,
+            ..FileHeader::default()
+        }
+    }
+
+    fn members(bytes: &[u8]) -> Vec<(String, Vec<u8>, u32)> {
+        let mut archive = tar::Archive::new(bytes);
+        archive
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let mut entry = entry.unwrap();
+                let path = entry.path().unwrap().to_string_lossy().into_owned();
+                let mode = entry.header().mode().unwrap();
+                let mut contents = Vec::new();
+                entry.read_to_end(&mut contents).unwrap();
+                (path, contents, mode)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn every_carrier_lands_as_one_archive() {
+        let out = Shared::default();
+        let sink = ArchiveSink::new(
+            Box::new(out.clone()),
+            PathBuf::from("/nonexistent/scratch"),
+            "single".into(),
+        );
+
+        let streamed = header("dir/streamed.txt", 6);
+        sink.write_file_stream(&streamed, &mut &b"stream"[..])
+            .await
+            .unwrap();
+        sink.write_small_files(vec![(header("small.txt", 5), b"small".to_vec())])
+            .await
+            .unwrap();
+        let shard_headers = vec![header("shard/a.txt", 1), header("shard/b.txt", 2)];
+        let shard = crate::remote::transfer::build_tar_shard_from(&shard_headers, |h| {
+            Ok(std::io::Cursor::new(vec![b'x'; h.size as usize]))
+        })
+        .unwrap();
+        let landed = sink
+            .write_payload(PreparedPayload::TarShard {
+                headers: shard_headers,
+                data: shard,
+            })
+            .await
+            .unwrap();
+        assert_eq!(landed.files_written, 2);
+        sink.write_file_stream(&header("", 3), &mut &b"one"[..])
+            .await
+            .unwrap();
+        sink.close().await.unwrap();
+
+        let bytes = out.0.lock().unwrap().clone();
+        let names: Vec<(String, Vec<u8>)> = members(&bytes)
+            .into_iter()
+            .map(|(path, contents, mode)| {
+                assert_eq!(mode, 0o640, "{path}");
+                (path, contents)
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("dir/streamed.txt".to_string(), b"stream".to_vec()),
+                ("small.txt".to_string(), b"small".to_vec()),
+                ("shard/a.txt".to_string(), b"x".to_vec()),
+                ("shard/b.txt".to_string(), b"xx".to_vec()),
+                ("single".to_string(), b"one".to_vec()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn a_short_member_fails_and_escaping_paths_are_refused() {
+        let sink = ArchiveSink::new(
+            Box::new(Shared::default()),
+            PathBuf::from("/nonexistent/scratch"),
+            "single".into(),
+        );
+        let err = sink
+            .write_file_stream(&header("short.txt", 10), &mut &b"abc"[..])
+            .await
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("short"), "{err:#}");
+        assert!(sink
+            .write_file_stream(&header("../escape.txt", 1), &mut &b"x"[..])
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,421 @@
+//! A tar archive presented as a source tree (`blit copy --unpack`).
+//!
+//! [`ArchiveTransferSource`] indexes a tarball's members once — relative
+//! path, size, mtime, mode and the byte offset of each member's data —
+//! and then serves the manifest and every file read from that index, so
+//! `blit copy --unpack release.tar.gz server:/module/dir/` lands the
+//! archive's tree at the destination without extracting it locally.
+//! The archive is the source root: the session, planner and sinks see an
+//! ordinary `TransferSource`, and every carrier (in-stream, data plane,
+//! tar shards, `--resume` block diffs) reads member bytes through
+//! [`TransferSource::open_file`] / [`TransferSource::prepare_payload`].
+//!
+//! Member selection follows the local walk's posture: regular files are
+//! the manifest, directories are implicit in their files' paths, and
+//! symlinks / devices / FIFOs are skipped the way `FileEnumerator` skips
+//! symlinks. A hard link is served from its target's data. A path that
+//! fails `path_safety::validate_wire_path` (absolute, `..`) refuses the
+//! whole archive rather than being silently dropped — the receiver would
+//! reject it anyway, and a tarball carrying one is not a tree.
+//!
+//! Reads seek straight to a member's offset, which plain tar allows. A
+//! gzip stream (detected by magic, not extension) is not seekable, so it
+//! is inflated once into a temp-dir spool file, deleted when the source
+//! drops, and indexed from there.
+
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use eyre::{bail, eyre, Context, Result};
+use tar::{Archive, EntryType};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio::sync::mpsc;
+
+use crate::fs_enum::FileFilter;
+use crate::generated::FileHeader;
+use crate::path_posix::relative_path_to_posix;
+use crate::path_safety::validate_wire_path;
+use crate::remote::transfer::payload::{build_tar_shard_from, PreparedPayload, TransferPayload};
+use crate::remote::transfer::source::{SourceScan, TransferSource};
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Where one member's bytes live inside the (uncompressed) tar stream.
+#[derive(Debug, Clone, Copy)]
+struct MemberData {
+    offset: u64,
+    size: u64,
+}
+
+struct ArchiveIndex {
+    /// The seekable tar stream: the archive itself, or the gzip spool.
+    tar_path: PathBuf,
+    headers: Vec<FileHeader>,
+    members: HashMap<String, MemberData>,
+    /// Held so the inflated spool lives exactly as long as the source.
+    _spool: Option<tempfile::NamedTempFile>,
+}
+
+impl ArchiveIndex {
+    fn member(&self, header: &FileHeader) -> Result<MemberData> {
+        self.members
+            .get(&header.relative_path)
+            .copied()
+            .ok_or_else(|| eyre!("{} is not a member of the archive", header.relative_path))
+    }
+
+    fn read_member(&self, header: &FileHeader) -> Result<std::io::Take<std::fs::File>> {
+        let member = self.member(header)?;
+        let mut file = std::fs::File::open(&self.tar_path)
+            .with_context(|| format!("opening archive {}", self.tar_path.display()))?;
+        file.seek(SeekFrom::Start(member.offset))
+            .with_context(|| format!("seeking to archive member {}", header.relative_path))?;
+        Ok(file.take(member.size))
+    }
+}
+
+pub struct ArchiveTransferSource {
+    archive: PathBuf,
+    index: Arc<ArchiveIndex>,
+}
+
+impl ArchiveTransferSource {
+    /// Index `archive` (plain or gzip-compressed tar). Blocking: reads
+    /// every member header, and inflates a gzip stream in full — call it
+    /// from `spawn_blocking` on an async path.
+    pub fn open(archive: &Path) -> Result<Self> {
+        let mut file = std::fs::File::open(archive)
+            .with_context(|| format!("opening archive {}", archive.display()))?;
+        let mut magic = [0u8; 2];
+        let is_gzip = matches!(file.read_exact(&mut magic), Ok(()) if magic == GZIP_MAGIC);
+
+        let (tar_path, spool) = if is_gzip {
+            file.seek(SeekFrom::Start(0))?;
+            let mut spool =
+                tempfile::NamedTempFile::new().context("creating archive inflate spool")?;
+            std::io::copy(&mut flate2::read::GzDecoder::new(file), spool.as_file_mut())
+                .with_context(|| format!("decompressing {}", archive.display()))?;
+            (spool.path().to_path_buf(), Some(spool))
+        } else {
+            (archive.to_path_buf(), None)
+        };
+
+        let (headers, members) = index_members(&tar_path)
+            .with_context(|| format!("reading archive {}", archive.display()))?;
+        Ok(Self {
+            archive: archive.to_path_buf(),
+            index: Arc::new(ArchiveIndex {
+                tar_path,
+                headers,
+                members,
+                _spool: spool,
+            }),
+        })
+    }
+
+    /// The archive's regular-file members, in archive order.
+    pub fn headers(&self) -> &[FileHeader] {
+        &self.index.headers
+    }
+}
+
+fn index_members(tar_path: &Path) -> Result<(Vec<FileHeader>, HashMap<String, MemberData>)> {
+    let mut archive = Archive::new(std::fs::File::open(tar_path)?);
+    let mut headers: Vec<FileHeader> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut members: HashMap<String, MemberData> = HashMap::new();
+
+    for entry in archive.entries_with_seek()? {
+        let entry = entry.context("reading archive entry")?;
+        let entry_type = entry.header().entry_type();
+        let data = match entry_type {
+            EntryType::Regular | EntryType::Continuous => MemberData {
+                offset: entry.raw_file_position(),
+                size: entry.size(),
+            },
+            EntryType::Link => {
+                let target = entry
+                    .link_name()?
+                    .ok_or_else(|| eyre!("hard link entry without a target"))?;
+                let target = member_path(&target)?;
+                *members.get(&target).ok_or_else(|| {
+                    eyre!("hard link to {target}, which precedes no regular member")
+                })?
+            }
+            // Directories are implicit in their files' paths; links and
+            // special files are skipped like the local walk skips them.
+            _ => continue,
+        };
+        let rel = member_path(&entry.path()?)?;
+        if rel.is_empty() {
+            continue;
+        }
+        let header = FileHeader {
+            relative_path: rel.clone(),
+            size: data.size,
+            mtime_seconds: entry.header().mtime().unwrap_or(0) as i64,
+            permissions: entry.header().mode().unwrap_or(0) & 0o7777,
+            checksum: Vec::new(),
+            windows_metadata: None,
+            crtime_seconds: None,
+            directory: false,
+            owner: None,
+            xattrs: Vec::new(),
+        };
+        // Later members replace earlier ones of the same path, which is
+        // what extracting the archive in order would leave behind.
+        match positions.get(&rel) {
+            Some(&at) => headers[at] = header,
+            None => {
+                positions.insert(rel.clone(), headers.len());
+                headers.push(header);
+            }
+        }
+        members.insert(rel, data);
+    }
+    Ok((headers, members))
+}
+
+fn member_path(path: &Path) -> Result<String> {
+    let Some(raw) = path.to_str() else {
+        bail!("archive member path is not UTF-8: {}", path.display());
+    };
+    let validated =
+        validate_wire_path(raw).with_context(|| format!("unsafe archive member path {raw:?}"))?;
+    Ok(relative_path_to_posix(&validated))
+}
+
+#[async_trait]
+impl TransferSource for ArchiveTransferSource {
+    fn scan(
+        &self,
+        filter: Option<FileFilter>,
+        _unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+        let (tx, rx) = mpsc::channel::<FileHeader>(64);
+        let index = Arc::clone(&self.index);
+        let filter = filter.unwrap_or_default();
+        let task = tokio::spawn(async move {
+            let mut emitted: u64 = 0;
+            for header in &index.headers {
+                let rel = Path::new(&header.relative_path);
+                let mtime = (header.mtime_seconds > 0)
+                    .then(|| {
+                        UNIX_EPOCH.checked_add(Duration::from_secs(header.mtime_seconds as u64))
+                    })
+                    .flatten();
+                if !filter.allows_entry(Some(rel), rel, header.size, mtime) {
+                    continue;
+                }
+                tx.send(header.clone())
+                    .await
+                    .map_err(|_| eyre!("failed to queue manifest entry"))?;
+                emitted += 1;
+            }
+            Ok(emitted)
+        });
+        (rx, SourceScan::new(task))
+    }
+
+    async fn prepare_payload(&self, payload: TransferPayload) -> Result<PreparedPayload> {
+        match payload {
+            TransferPayload::File(header) => Ok(PreparedPayload::File(header)),
+            TransferPayload::TarShard { headers } => {
+                let index = Arc::clone(&self.index);
+                tokio::task::spawn_blocking(move || {
+                    let data = build_tar_shard_from(&headers, |header| index.read_member(header))?;
+                    Ok(PreparedPayload::TarShard { headers, data })
+                })
+                .await
+                .map_err(|err| eyre!("tar shard worker failed: {err}"))?
+            }
+            TransferPayload::TarStream { headers } => Ok(PreparedPayload::TarStream { headers }),
+            TransferPayload::ResumeFile {
+                header,
+                block_size,
+                dest_hashes,
+                hash_key,
+            } => Ok(PreparedPayload::ResumeFile {
+                header,
+                block_size,
+                dest_hashes,
+                hash_key,
+            }),
+            TransferPayload::FileBlock { .. } | TransferPayload::FileBlockComplete { .. } => {
+                bail!("FileBlock payloads cannot be prepared from an archive source")
+            }
+        }
+    }
+
+    async fn check_availability(
+        &self,
+        headers: Vec<FileHeader>,
+        _unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> Result<Vec<FileHeader>> {
+        // Every indexed member is readable for as long as the index lives.
+        Ok(headers)
+    }
+
+    async fn open_file(
+        &self,
+        header: &FileHeader,
+    ) -> Result<Box<dyn tokio::io::AsyncRead + Unpin + Send>> {
+        let member = self.index.member(header)?;
+        let mut file = tokio::fs::File::open(&self.index.tar_path).await?;
+        file.seek(SeekFrom::Start(member.offset)).await?;
+        Ok(Box::new(file.take(member.size)))
+    }
+
+    fn root(&self) -> &Path {
+        &self.archive
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn append(builder: &mut tar::Builder<impl Write>, path: &str, body: &[u8], mode: u32) {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(body.len() as u64);
+        header.set_mode(mode);
+        header.set_mtime(1_700_000_000);
+        header.set_entry_type(EntryType::Regular);
+        header.set_cksum();
+        builder.append_data(&mut header, path, body).unwrap();
+    }
+
+    fn write_fixture<W: Write>(out: W) -> W {
+        let mut builder = tar::Builder::new(out);
+        let mut dir = tar::Header::new_gnu();
+        dir.set_entry_type(EntryType::Directory);
+        dir.set_mode(0o755);
+        dir.set_size(0);
+        dir.set_cksum();
+        builder.append_data(&mut dir, "docs/", &[][..]).unwrap();
+        append(&mut builder, "./docs/readme.txt", b"read me", 0o640);
+        append(&mut builder, "bin/tool", b"#!/bin/sh\n", 0o755);
+        let mut link = tar::Header::new_gnu();
+        link.set_entry_type(EntryType::Symlink);
+        link.set_size(0);
+        link.set_cksum();
+        builder
+            .append_link(&mut link, "docs/latest", "readme.txt")
+            .unwrap();
+        append(&mut builder, "bin/tool", b"#!/bin/sh\nexit 0\n", 0o755);
+        builder.into_inner().unwrap()
+    }
+
+    async fn scan_all(source: &ArchiveTransferSource, filter: Option<FileFilter>) -> Vec<String> {
+        let (mut rx, mut scan) = source.scan(filter, Arc::default());
+        let mut paths = Vec::new();
+        while let Some(header) = rx.recv().await {
+            paths.push(header.relative_path);
+        }
+        assert_eq!(scan.finish().await.unwrap(), paths.len() as u64);
+        paths
+    }
+
+    async fn read(source: &ArchiveTransferSource, rel: &str) -> Vec<u8> {
+        let header = source
+            .headers()
+            .iter()
+            .find(|h| h.relative_path == rel)
+            .unwrap()
+            .clone();
+        let mut body = Vec::new();
+        source
+            .open_file(&header)
+            .await
+            .unwrap()
+            .read_to_end(&mut body)
+            .await
+            .unwrap();
+        body
+    }
+
+    #[tokio::test]
+    async fn enumerates_and_reads_plain_and_gzip_archives() {
+        let tmp = tempfile::tempdir().unwrap();
+        let plain = tmp.path().join("tree.tar");
+        write_fixture(std::fs::File::create(&plain).unwrap());
+        let gz = tmp.path().join("tree.tgz");
+        write_fixture(flate2::write::GzEncoder::new(
+            std::fs::File::create(&gz).unwrap(),
+            flate2::Compression::fast(),
+        ))
+        .finish()
+        .unwrap();
+
+        for path in [&plain, &gz] {
+            let source = ArchiveTransferSource::open(path).unwrap();
+            assert_eq!(source.root(), path.as_path());
+            // Directory and symlink members are not files; the repeated
+            // member keeps its first position but its last contents.
+            assert_eq!(
+                scan_all(&source, None).await,
+                vec!["docs/readme.txt", "bin/tool"]
+            );
+            let tool = &source.headers()[1];
+            assert_eq!(tool.permissions, 0o755);
+            assert_eq!(tool.mtime_seconds, 1_700_000_000);
+            assert_eq!(read(&source, "bin/tool").await, b"#!/bin/sh\nexit 0\n");
+            assert_eq!(read(&source, "docs/readme.txt").await, b"read me");
+
+            let mut filter = FileFilter::default();
+            filter.exclude_files = vec!["*.txt".into()];
+            assert_eq!(scan_all(&source, Some(filter)).await, vec!["bin/tool"]);
+
+            let shard = source
+                .prepare_payload(TransferPayload::TarShard {
+                    headers: source.headers().to_vec(),
+                })
+                .await
+                .unwrap();
+            let PreparedPayload::TarShard { data, .. } = shard else {
+                panic!("expected a tar shard");
+            };
+            let mut rebuilt = Archive::new(std::io::Cursor::new(data));
+            let mut members = Vec::new();
+            for entry in rebuilt.entries().unwrap() {
+                let mut entry = entry.unwrap();
+                let mut body = String::new();
+                entry.read_to_string(&mut body).unwrap();
+                members.push((entry.path().unwrap().display().to_string(), body));
+            }
+            assert_eq!(
+                members,
+                vec![
+                    ("docs/readme.txt".to_string(), "read me".to_string()),
+                    ("bin/tool".to_string(), "#!/bin/sh\nexit 0\n".to_string()),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn escaping_member_refuses_the_archive() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("evil.tar");
+        let mut builder = tar::Builder::new(std::fs::File::create(&path).unwrap());
+        let mut header = tar::Header::new_gnu();
+        header.set_size(1);
+        header.set_entry_type(EntryType::Regular);
+        // `set_path` refuses `..`; write the raw name the way a hostile
+        // archiver would.
+        header.as_old_mut().name[..9].copy_from_slice(b"../escape");
+        header.set_cksum();
+        builder.append(&header, &b"x"[..]).unwrap();
+        builder.into_inner().unwrap();
+
+        let err = ArchiveTransferSource::open(&path)
+            .err()
+            .expect("a `..` member must refuse the archive");
+        assert!(format!("{err:#}").contains("unsafe archive member path"));
+    }
+}
@@ -4,12 +4,14 @@ use prost::Message;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
 
-use crate::buffer::BufferPool;
+use crate::buffer::{BufferPool, PoolBuffer};
 use crate::generated::{FileHeader, WindowsFileMetadata};
 
+use super::abort_on_drop::AbortOnDrop;
 use super::payload::{prepared_payload_stream, PreparedPayload, TransferPayload};
 use super::progress::{LiveProbe, NoProbe, Probe, StreamProbe};
 use super::stall_guard::{StallGuardWriter, TRANSFER_STALL_TIMEOUT};
+use crate::remote::endpoint::format_host_port;
 use crate::remote::transfer::source::TransferSource;
 use std::sync::Arc;
 
@@ -18,6 +20,9 @@ pub const DATA_PLANE_RECORD_FILE: u8 = 0;
 pub const DATA_PLANE_RECORD_TAR_SHARD: u8 = 1;
 pub const DATA_PLANE_RECORD_BLOCK: u8 = 2;
 pub const DATA_PLANE_RECORD_BLOCK_COMPLETE: u8 = 3;
+/// Contract v10: many tiny files as one streamed tar archive.
+/// Format: [type:1][member headers][len:4][bytes]...[0:4]
+pub const DATA_PLANE_RECORD_TAR_STREAM: u8 = 4;
 pub const DATA_PLANE_RECORD_END: u8 = 0xFF;
 const WINDOWS_METADATA_PRESENT: u8 = 1;
 const WINDOWS_METADATA_ABSENT: u8 = 0;
@@ -29,6 +34,13 @@ pub(crate) const MAX_WINDOWS_METADATA_WIRE_BYTES: usize =
 /// before the tar-body length is even read.
 pub(crate) const MAX_TAR_SHARD_HEADER_WIRE_BYTES: usize =
     crate::remote::transfer::tar_safety::MAX_TAR_SHARD_BYTES as usize;
+/// Largest single frame of a `TAR_STREAM` record's archive bytes. Frames
+/// bound the receiver's per-read allocation; the archive itself is
+/// unbounded because nothing buffers it whole.
+pub(crate) const MAX_TAR_STREAM_FRAME_BYTES: usize = 16 * 1024 * 1024;
+/// Tar-stream members whose source opens overlap on the send side.
+/// Members still enter the archive in header order.
+const TAR_STREAM_READ_AHEAD: usize = 16;
 
 fn windows_metadata_wire_len(metadata: Option<&WindowsFileMetadata>) -> usize {
     match metadata {
@@ -131,19 +143,22 @@ pub(crate) async fn read_windows_metadata_with_budget<R: tokio::io::AsyncRead +
     }
 }
 
+/// `[path_len:4][path][size:8][mtime:8][perms:4][windows metadata]`:
+/// one member's header in a tar record, and a `FILE` record's header.
+fn member_header_wire_bytes(header: &FileHeader) -> usize {
+    (std::mem::size_of::<u32>()
+        + header.relative_path.len()
+        + std::mem::size_of::<u64>()
+        + std::mem::size_of::<i64>()
+        + std::mem::size_of::<u32>())
+    .saturating_add(windows_metadata_wire_len(header.windows_metadata.as_ref()))
+}
+
 fn tar_shard_header_wire_bytes(headers: &[FileHeader]) -> Result<usize> {
     let mut total = std::mem::size_of::<u32>();
     for header in headers {
-        let fixed = std::mem::size_of::<u32>()
-            + header.relative_path.len()
-            + std::mem::size_of::<u64>()
-            + std::mem::size_of::<i64>()
-            + std::mem::size_of::<u32>();
         total = total
-            .checked_add(fixed)
-            .and_then(|value| {
-                value.checked_add(windows_metadata_wire_len(header.windows_metadata.as_ref()))
-            })
+            .checked_add(member_header_wire_bytes(header))
             .ok_or_else(|| eyre::eyre!("tar-shard header byte count overflow"))?;
         if total > MAX_TAR_SHARD_HEADER_WIRE_BYTES {
             bail!(
@@ -156,6 +171,69 @@ fn tar_shard_header_wire_bytes(headers: &[FileHeader]) -> Result<usize> {
     Ok(total)
 }
 
+/// Size of the tar archive Blit builds for `headers`: per member a
+/// 512-byte header (plus a GNU long-name entry for a path over 100
+/// bytes) and the content padded to 512, then the two zero blocks
+/// that end the archive.
+fn tar_archive_bytes(headers: &[FileHeader]) -> u64 {
+    const BLOCK: u64 = 512;
+    let padded = |len: u64| len.div_ceil(BLOCK) * BLOCK;
+    headers
+        .iter()
+        .map(|header| {
+            let path_len = header.relative_path.len() as u64;
+            let long_name = if path_len > 100 {
+                BLOCK + padded(path_len + 1)
+            } else {
+                0
+            };
+            BLOCK + long_name + padded(header.size)
+        })
+        .sum::<u64>()
+        + 2 * BLOCK
+}
+
+/// Estimated bytes `payload` puts on the TCP data plane: its record
+/// framing, the tar overhead of shard and stream records, and the file
+/// content. Computed from the headers alone, so a dry run can predict
+/// what a push sends. Uncompressed — no carrier compresses payloads.
+pub fn payload_wire_bytes(payload: &TransferPayload) -> u64 {
+    let tag = 1u64;
+    let member_headers = |headers: &[FileHeader]| -> u64 {
+        std::mem::size_of::<u32>() as u64
+            + headers
+                .iter()
+                .map(|header| member_header_wire_bytes(header) as u64)
+                .sum::<u64>()
+    };
+    match payload {
+        TransferPayload::File(header) | TransferPayload::ResumeFile { header, .. } => {
+            tag + member_header_wire_bytes(header) as u64
+                + header.size
+                + crate::windows_metadata::payload_bytes(header)
+        }
+        TransferPayload::TarShard { headers } => {
+            // `[len:8][digest]` ahead of the archive.
+            tag + member_headers(headers)
+                + std::mem::size_of::<u64>() as u64
+                + super::tar_safety::TAR_SHARD_DIGEST_LEN as u64
+                + tar_archive_bytes(headers)
+        }
+        TransferPayload::TarStream { headers } => {
+            // `[len:4]` per frame, the zero-length terminator, then the
+            // digest.
+            let archive = tar_archive_bytes(headers);
+            let frames = archive.div_ceil(MAX_TAR_STREAM_FRAME_BYTES as u64);
+            tag + member_headers(headers)
+                + archive
+                + 4 * (frames + 1)
+                + super::tar_safety::TAR_SHARD_DIGEST_LEN as u64
+        }
+        TransferPayload::FileBlock { size, .. } => *size,
+        TransferPayload::FileBlockComplete { .. } => 0,
+    }
+}
+
 /// ue-r2-2: length of the per-epoch resize credential a data socket
 /// echoes after the one-time token when resize was negotiated
 /// (`DataPlaneGrant.epoch0_sub_token` for the initial
@@ -288,7 +366,7 @@ impl<P: Probe> DataPlaneSession<P> {
         pool: Arc<BufferPool>,
         probe: P,
     ) -> Result<Self> {
-        let addr = format!("{}:{}", host, port);
+        let addr = format_host_port(host, port);
         if trace {
             eprintln!("[data-plane-client] connecting to {}", addr);
         }
@@ -435,6 +513,18 @@ impl<P: Probe> DataPlaneSession<P> {
                         }
                     }
                 }
+                PreparedPayload::TarStream { headers } => {
+                    self.send_tar_stream(source.clone(), &headers)
+                        .await
+                        .map_err(|err| err.wrap_err("sending tar stream"))?;
+                    for header in &headers {
+                        self.bytes_sent = self.bytes_sent.saturating_add(header.size);
+                        if let Some(progress) = progress {
+                            progress.report_payload(0, header.size);
+                            progress.report_file_complete(header.relative_path.clone());
+                        }
+                    }
+                }
                 PreparedPayload::FileBlock { .. }
                 | PreparedPayload::FileBlockComplete { .. }
                 | PreparedPayload::ResumeFile { .. } => {
@@ -471,7 +561,14 @@ impl<P: Probe> DataPlaneSession<P> {
             .open_file(header)
             .await
             .with_context(|| format!("opening {}", rel))?;
-        self.send_file_from_reader(header, &mut file).await
+        if self.payload_prefetch < 2 {
+            return self.send_file_from_reader(header, &mut file).await;
+        }
+        trace_client!(self, "sending file '{}' ({} bytes)", rel, header.size);
+        self.write_file_record_header(header).await?;
+        self.send_file_read_ahead(file, header, rel).await?;
+        trace_client!(self, "file '{}' sent ({} bytes)", rel, header.size);
+        Ok(())
     }
 
     /// Send a file payload whose bytes come from an arbitrary async
@@ -487,7 +584,20 @@ impl<P: Probe> DataPlaneSession<P> {
     ) -> Result<()> {
         let rel = &header.relative_path;
         trace_client!(self, "sending file '{}' ({} bytes)", rel, header.size);
+        self.write_file_record_header(header).await?;
+
+        // Double-buffered I/O: overlaps source reads with network writes
+        self.send_file_double_buffered(reader, header, rel).await?;
+
+        trace_client!(self, "file '{}' sent ({} bytes)", rel, header.size);
 
+        Ok(())
+    }
+
+    /// The FILE record's framing ahead of its bytes: tag, path, size,
+    /// mtime, permissions and Windows metadata.
+    async fn write_file_record_header(&mut self, header: &FileHeader) -> Result<()> {
+        let rel = &header.relative_path;
         let path_bytes = rel.as_bytes();
         if path_bytes.len() > u32::MAX as usize {
             bail!("relative path too long for transfer: {}", rel);
@@ -526,13 +636,61 @@ impl<P: Probe> DataPlaneSession<P> {
             .write_all(&header.permissions.to_be_bytes())
             .await
             .context("writing permissions")?;
-        write_windows_metadata(&mut self.stream, header.windows_metadata.as_ref()).await?;
-
-        // Double-buffered I/O: overlaps source reads with network writes
-        self.send_file_double_buffered(reader, header, rel).await?;
-
-        trace_client!(self, "file '{}' sent ({} bytes)", rel, header.size);
+        write_windows_metadata(&mut self.stream, header.windows_metadata.as_ref()).await
+    }
 
+    /// Read-ahead file sending, for a file this session opened itself
+    /// (every SOURCE data plane, so pushes and daemon-served pulls
+    /// alike). A reader task fills pool buffers up to `payload_prefetch`
+    /// ahead of the socket: a disk stall drains the queue instead of
+    /// stalling the write, and a socket stall lets the reads run on.
+    /// [`Self::send_file_double_buffered`] is the depth-one case.
+    async fn send_file_read_ahead(
+        &mut self,
+        file: Box<dyn tokio::io::AsyncRead + Unpin + Send>,
+        header: &FileHeader,
+        rel: &str,
+    ) -> Result<()> {
+        if header.size == 0 {
+            return Ok(());
+        }
+        let (tx, mut rx) = tokio::sync::mpsc::channel(self.payload_prefetch);
+        let reader = AbortOnDrop::new(tokio::spawn(read_ahead(
+            file,
+            header.size,
+            self.pool.clone(),
+            tx,
+            rel.to_string(),
+        )));
+
+        let mut sent = 0u64;
+        while let Some(chunk) = rx.recv().await {
+            let (buf, len): (PoolBuffer, usize) = chunk?;
+            // Only the socket write is timed, as in the double-buffered
+            // loop: the reads run on the reader task.
+            let started = P::ACTIVE.then(std::time::Instant::now);
+            self.stream
+                .write_all(&buf.as_slice()[..len])
+                .await
+                .with_context(|| format!("sending {}", rel))?;
+            if let Some(started) = started {
+                self.probe
+                    .note_write_blocked(started.elapsed().as_nanos() as u64);
+            }
+            self.probe.record_bytes(len as u64);
+            crate::remote::instrumentation::record_cli_data_plane_outbound_bytes(len as u64);
+            super::progress::report_in_flight(len as u64);
+            sent += len as u64;
+        }
+        drop(reader);
+        if sent != header.size {
+            bail!(
+                "reading {}: read-ahead stopped after {} of {} bytes",
+                rel,
+                sent,
+                header.size
+            );
+        }
         Ok(())
     }
 
@@ -612,6 +770,7 @@ impl<P: Probe> DataPlaneSession<P> {
             }
             self.probe.record_bytes(bytes_a as u64);
             crate::remote::instrumentation::record_cli_data_plane_outbound_bytes(bytes_a as u64);
+            super::progress::report_in_flight(bytes_a as u64);
 
             let bytes_b = read_result.with_context(|| format!("reading {}", rel))?;
 
@@ -653,6 +812,7 @@ impl<P: Probe> DataPlaneSession<P> {
             }
             self.probe.record_bytes(bytes_a as u64);
             crate::remote::instrumentation::record_cli_data_plane_outbound_bytes(bytes_a as u64);
+            super::progress::report_in_flight(bytes_a as u64);
         }
 
         // Buffers return to pool automatically on drop
@@ -688,6 +848,190 @@ impl<P: Probe> DataPlaneSession<P> {
         if let Some(trace) = phase_write {
             trace.socket_first_write(self.phase_epoch, self.phase_socket);
         }
+        self.write_member_headers(&headers).await?;
+
+        self.stream
+            .write_all(&(data.len() as u64).to_be_bytes())
+            .await
+            .context("writing tar shard length")?;
+        self.stream
+            .write_all(&super::tar_safety::tar_shard_digest(data))
+            .await
+            .context("writing tar shard digest")?;
+        for chunk in data.chunks(self.chunk_bytes.max(1)) {
+            // codex ue-r2-1e F3: shard writes carry the small-file
+            // workloads — without a blocked signal here the tuner sees
+            // a saturated link as a clean one. Same P::ACTIVE gating as
+            // the file loop: NoProbe reads no clock.
+            let started = if P::ACTIVE {
+                Some(std::time::Instant::now())
+            } else {
+                None
+            };
+            self.stream
+                .write_all(chunk)
+                .await
+                .context("writing tar shard payload")?;
+            if let Some(t) = started {
+                self.probe.note_write_blocked(t.elapsed().as_nanos() as u64);
+            }
+            self.probe.record_bytes(chunk.len() as u64);
+            crate::remote::instrumentation::record_cli_data_plane_outbound_bytes(chunk.len() as u64);
+        }
+        trace_client!(
+            self,
+            "tar shard payload sent ({} file(s), {} bytes)",
+            shard_len,
+            data.len()
+        );
+
+        Ok(())
+    }
+
+    /// Send a tar-stream record: the member header block, then one tar
+    /// archive of every member, built member by member from `source`
+    /// and written in frames as it grows — the sender never holds more
+    /// than a frame of it — and the archive's Blake3, hashed frame by
+    /// frame. A member that cannot be read fails the record naming its
+    /// file.
+    pub async fn send_tar_stream(
+        &mut self,
+        source: Arc<dyn TransferSource>,
+        headers: &[FileHeader],
+    ) -> Result<()> {
+        tar_shard_header_wire_bytes(headers)?;
+        if headers.len() > super::payload::TAR_STREAM_MAX_MEMBERS {
+            bail!(
+                "tar stream of {} file(s) exceeds the {}-member record cap",
+                headers.len(),
+                super::payload::TAR_STREAM_MAX_MEMBERS
+            );
+        }
+        trace_client!(self, "sending tar stream with {} file(s)", headers.len());
+        self.begin_tar_stream(headers).await?;
+
+        let mut hasher = blake3::Hasher::new();
+        let frame_bytes = self.chunk_bytes.clamp(1, MAX_TAR_STREAM_FRAME_BYTES);
+        let mut builder = tar::Builder::new(Vec::with_capacity(frame_bytes));
+        let mut members = futures::stream::iter(headers.iter().cloned())
+            .map(|header| {
+                let source = source.clone();
+                async move {
+                    let contents = read_tar_stream_member(&source, &header).await?;
+                    Ok::<_, eyre::Report>((header, contents))
+                }
+            })
+            .buffered(TAR_STREAM_READ_AHEAD);
+        while let Some(member) = members.next().await {
+            let (header, contents) = member?;
+            let mut tar_header = super::payload::tar_member_header(&header);
+            builder
+                .append_data(
+                    &mut tar_header,
+                    std::path::Path::new(&header.relative_path),
+                    contents.as_slice(),
+                )
+                .with_context(|| format!("adding {} to tar stream", header.relative_path))?;
+            if builder.get_ref().len() >= frame_bytes {
+                let frame = std::mem::take(builder.get_mut());
+                self.write_tar_stream_frames(&frame, &mut hasher).await?;
+            }
+        }
+        let tail = builder.into_inner().context("finishing tar stream")?;
+        self.write_tar_stream_frames(&tail, &mut hasher).await?;
+        self.end_tar_stream(&hasher).await?;
+        trace_client!(self, "tar stream sent ({} file(s))", headers.len());
+        Ok(())
+    }
+
+    /// Relay case: forward a tar-stream record whose archive bytes arrive
+    /// on `reader` (an inbound `TAR_STREAM`, checked against its own
+    /// digest as it is read) to the next hop, re-framed and re-hashed.
+    pub async fn send_tar_stream_from_reader(
+        &mut self,
+        headers: &[FileHeader],
+        reader: &mut (dyn tokio::io::AsyncRead + Unpin + Send),
+    ) -> Result<()> {
+        tar_shard_header_wire_bytes(headers)?;
+        self.begin_tar_stream(headers).await?;
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = vec![0u8; self.chunk_bytes.clamp(1, MAX_TAR_STREAM_FRAME_BYTES)];
+        loop {
+            let n = reader
+                .read(&mut buf)
+                .await
+                .context("reading relayed tar stream")?;
+            if n == 0 {
+                break;
+            }
+            self.write_tar_stream_frames(&buf[..n], &mut hasher).await?;
+        }
+        self.end_tar_stream(&hasher).await
+    }
+
+    async fn begin_tar_stream(&mut self, headers: &[FileHeader]) -> Result<()> {
+        let phase_write = self.take_first_payload_write_trace();
+        self.stream
+            .write_all(&[DATA_PLANE_RECORD_TAR_STREAM])
+            .await
+            .context("writing tar stream record tag")?;
+        if let Some(trace) = phase_write {
+            trace.socket_first_write(self.phase_epoch, self.phase_socket);
+        }
+        self.write_member_headers(headers).await
+    }
+
+    /// Write archive bytes as `[len:4][bytes]` frames, folding them into
+    /// the record's digest. Empty input writes nothing — a zero-length
+    /// frame is the record's terminator.
+    async fn write_tar_stream_frames(
+        &mut self,
+        bytes: &[u8],
+        hasher: &mut blake3::Hasher,
+    ) -> Result<()> {
+        hasher.update(bytes);
+        for chunk in bytes.chunks(MAX_TAR_STREAM_FRAME_BYTES) {
+            // Same blocked-write signal as the tar-shard chunk loop, so the
+            // tuner reads tiny-file load on this carrier too.
+            let started = if P::ACTIVE {
+                Some(std::time::Instant::now())
+            } else {
+                None
+            };
+            self.stream
+                .write_all(&(chunk.len() as u32).to_be_bytes())
+                .await
+                .context("writing tar stream frame length")?;
+            self.stream
+                .write_all(chunk)
+                .await
+                .context("writing tar stream frame")?;
+            if let Some(t) = started {
+                self.probe.note_write_blocked(t.elapsed().as_nanos() as u64);
+            }
+            self.probe.record_bytes(chunk.len() as u64);
+            crate::remote::instrumentation::record_cli_data_plane_outbound_bytes(chunk.len() as u64);
+        }
+        Ok(())
+    }
+
+    /// The zero-length terminator, then the Blake3 of every archive
+    /// byte the record framed (contract v24).
+    async fn end_tar_stream(&mut self, hasher: &blake3::Hasher) -> Result<()> {
+        self.stream
+            .write_all(&0u32.to_be_bytes())
+            .await
+            .context("writing tar stream terminator")?;
+        self.stream
+            .write_all(hasher.finalize().as_bytes())
+            .await
+            .context("writing tar stream digest")
+    }
+
+    /// The member header block shared by `TAR_SHARD` and `TAR_STREAM`
+    /// records: `[count:4]` then, per member,
+    /// `[path_len:4][path][size:8][mtime:8][perms:4][windows metadata]`.
+    async fn write_member_headers(&mut self, headers: &[FileHeader]) -> Result<()> {
         self.stream
             .write_all(&(headers.len() as u32).to_be_bytes())
             .await
@@ -723,38 +1067,6 @@ impl<P: Probe> DataPlaneSession<P> {
                 .context("writing shard permissions")?;
             write_windows_metadata(&mut self.stream, header.windows_metadata.as_ref()).await?;
         }
-
-        self.stream
-            .write_all(&(data.len() as u64).to_be_bytes())
-            .await
-            .context("writing tar shard length")?;
-        for chunk in data.chunks(self.chunk_bytes.max(1)) {
-            // codex ue-r2-1e F3: shard writes carry the small-file
-            // workloads — without a blocked signal here the tuner sees
-            // a saturated link as a clean one. Same P::ACTIVE gating as
-            // the file loop: NoProbe reads no clock.
-            let started = if P::ACTIVE {
-                Some(std::time::Instant::now())
-            } else {
-                None
-            };
-            self.stream
-                .write_all(chunk)
-                .await
-                .context("writing tar shard payload")?;
-            if let Some(t) = started {
-                self.probe.note_write_blocked(t.elapsed().as_nanos() as u64);
-            }
-            self.probe.record_bytes(chunk.len() as u64);
-            crate::remote::instrumentation::record_cli_data_plane_outbound_bytes(chunk.len() as u64);
-        }
-        trace_client!(
-            self,
-            "tar shard payload sent ({} file(s), {} bytes)",
-            shard_len,
-            data.len()
-        );
-
         Ok(())
     }
 
@@ -913,6 +1225,189 @@ impl<P: Probe> DataPlaneSession<P> {
 /// pipeline.
 pub const RECEIVE_CHUNK_SIZE: usize = 1024 * 1024;
 
+/// Read one tar-stream member whole (members are tiny by selection).
+/// Any failure names the member, so the session fault reports the file.
+async fn read_tar_stream_member(
+    source: &Arc<dyn TransferSource>,
+    header: &FileHeader,
+) -> Result<Vec<u8>> {
+    let rel = &header.relative_path;
+    let read = async {
+        let reader = source
+            .open_file(header)
+            .await
+            .with_context(|| format!("opening {rel}"))?;
+        let mut contents = Vec::with_capacity(header.size as usize);
+        reader
+            .take(header.size)
+            .read_to_end(&mut contents)
+            .await
+            .with_context(|| format!("reading {rel}"))?;
+        if contents.len() as u64 != header.size {
+            bail!(
+                "{rel} shrank to {} of its {} manifest byte(s) while streaming",
+                contents.len(),
+                header.size
+            );
+        }
+        Ok(contents)
+    }
+    .await;
+    read.map_err(|e| e.wrap_err(super::faulted_path::FaultedPath(rel.clone())))
+}
+
+/// The archive bytes of an inbound `TAR_STREAM` record as a plain
+/// reader: strips the `[len:4]` framing, hashes the bytes as they pass,
+/// and at the zero-length terminator reads the record's digest. EOF
+/// means the archive matched it, leaving `inner` at the next record tag;
+/// a mismatch ends the read with a [`TarStreamDigestMismatch`] instead.
+pub(crate) struct TarStreamFrames<'a, R: ?Sized> {
+    inner: &'a mut R,
+    len_buf: [u8; 4],
+    len_filled: usize,
+    remaining: usize,
+    hasher: blake3::Hasher,
+    received: u64,
+    trailer: Option<([u8; super::tar_safety::TAR_SHARD_DIGEST_LEN], usize)>,
+    done: bool,
+}
+
+/// The error a [`TarStreamFrames`] read ends with when the archive
+/// bytes do not hash to the digest after the record's terminator. It
+/// travels inside a [`std::io::Error`]; [`is_tar_stream_digest_mismatch`]
+/// finds it in a report's chain.
+#[derive(Debug)]
+pub(crate) struct TarStreamDigestMismatch {
+    pub(crate) received: u64,
+}
+
+impl std::fmt::Display for TarStreamDigestMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "tar stream failed its Blake3 check: {} bytes received, digest does not match the sender's",
+            self.received
+        )
+    }
+}
+
+impl std::error::Error for TarStreamDigestMismatch {}
+
+/// Whether `err` is (or wraps) a tar stream's failed digest check.
+pub(crate) fn is_tar_stream_digest_mismatch(err: &eyre::Report) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<std::io::Error>()
+            .and_then(|io| io.get_ref())
+            .is_some_and(|inner| inner.is::<TarStreamDigestMismatch>())
+    })
+}
+
+impl<'a, R: tokio::io::AsyncRead + Unpin + ?Sized> TarStreamFrames<'a, R> {
+    pub(crate) fn new(inner: &'a mut R) -> Self {
+        Self {
+            inner,
+            len_buf: [0; 4],
+            len_filled: 0,
+            remaining: 0,
+            hasher: blake3::Hasher::new(),
+            received: 0,
+            trailer: None,
+            done: false,
+        }
+    }
+
+    /// Discard whatever the sink left unread (the archive's trailing
+    /// padding) through the terminator and check the digest.
+    pub(crate) async fn drain(&mut self) -> Result<u64> {
+        tokio::io::copy(self, &mut tokio::io::sink())
+            .await
+            .context("draining tar stream record")
+    }
+}
+
+impl<R: tokio::io::AsyncRead + Unpin + ?Sized> tokio::io::AsyncRead for TarStreamFrames<'_, R> {
+    fn poll_read(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        use std::task::{ready, Poll};
+        let this = self.get_mut();
+        let eof = || {
+            std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "tar stream ended before its terminator",
+            )
+        };
+        loop {
+            if this.done || buf.remaining() == 0 {
+                return Poll::Ready(Ok(()));
+            }
+            if let Some((digest, filled)) = &mut this.trailer {
+                while *filled < digest.len() {
+                    let mut digest_buf = tokio::io::ReadBuf::new(&mut digest[*filled..]);
+                    ready!(std::pin::Pin::new(&mut *this.inner).poll_read(cx, &mut digest_buf))?;
+                    let n = digest_buf.filled().len();
+                    if n == 0 {
+                        return Poll::Ready(Err(eof()));
+                    }
+                    *filled += n;
+                }
+                this.done = true;
+                if this.hasher.finalize() != *digest {
+                    return Poll::Ready(Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        TarStreamDigestMismatch {
+                            received: this.received,
+                        },
+                    )));
+                }
+                return Poll::Ready(Ok(()));
+            }
+            if this.remaining == 0 {
+                while this.len_filled < this.len_buf.len() {
+                    let mut len_buf = tokio::io::ReadBuf::new(&mut this.len_buf[this.len_filled..]);
+                    ready!(std::pin::Pin::new(&mut *this.inner).poll_read(cx, &mut len_buf))?;
+                    let n = len_buf.filled().len();
+                    if n == 0 {
+                        return Poll::Ready(Err(eof()));
+                    }
+                    this.len_filled += n;
+                }
+                this.len_filled = 0;
+                let len = u32::from_be_bytes(this.len_buf) as usize;
+                if len == 0 {
+                    this.trailer = Some(([0; super::tar_safety::TAR_SHARD_DIGEST_LEN], 0));
+                    continue;
+                }
+                if len > MAX_TAR_STREAM_FRAME_BYTES {
+                    return Poll::Ready(Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!(
+                            "tar stream frame of {len} bytes exceeds max {MAX_TAR_STREAM_FRAME_BYTES}"
+                        ),
+                    )));
+                }
+                this.remaining = len;
+                continue;
+            }
+            let limit = this.remaining.min(buf.remaining());
+            let mut frame = tokio::io::ReadBuf::new(buf.initialize_unfilled_to(limit));
+            ready!(std::pin::Pin::new(&mut *this.inner).poll_read(cx, &mut frame))?;
+            let n = frame.filled().len();
+            if n == 0 {
+                return Poll::Ready(Err(eof()));
+            }
+            this.hasher.update(frame.filled());
+            this.received += n as u64;
+            buf.advance(n);
+            this.remaining -= n;
+            return Poll::Ready(Ok(()));
+        }
+    }
+}
+
 /// Stream `expected` bytes from an async source into an async sink with
 /// double-buffered I/O — while one buffer drains to disk, the other is
 /// being filled from the wire. Symmetric counterpart of
@@ -975,6 +1470,7 @@ where
         if let Some(progress) = byte_progress {
             progress.report(bytes_a as u64);
         }
+        super::progress::report_in_flight(bytes_a as u64);
         let bytes_b = read_res?;
         if bytes_b == 0 && total + bytes_a as u64 != expected {
             bail!(
@@ -995,6 +1491,7 @@ where
         if let Some(progress) = byte_progress {
             progress.report(bytes_a as u64);
         }
+        super::progress::report_in_flight(bytes_a as u64);
     }
 
     Ok(total)
@@ -1017,6 +1514,41 @@ where
     Ok(n)
 }
 
+/// The reader half of [`DataPlaneSession::send_file_read_ahead`]: fill
+/// pool buffers from `file` until `size` bytes are queued, clamping an
+/// over-returning reader the way the double-buffered loop does. The
+/// writer only ever releases buffers, so a pool budget spent by other
+/// streams' queues makes this wait, never deadlock.
+async fn read_ahead(
+    mut file: Box<dyn tokio::io::AsyncRead + Unpin + Send>,
+    size: u64,
+    pool: Arc<BufferPool>,
+    tx: tokio::sync::mpsc::Sender<Result<(PoolBuffer, usize)>>,
+    rel: String,
+) {
+    let mut remaining = size;
+    while remaining > 0 {
+        let mut buf = pool.acquire().await;
+        let chunk = match file.read(buf.as_mut_slice()).await {
+            Ok(0) => Err(eyre::eyre!(
+                "unexpected EOF while reading {} ({} bytes remaining)",
+                rel,
+                remaining
+            )),
+            Ok(read) => {
+                let read = (read as u64).min(remaining) as usize;
+                remaining -= read as u64;
+                Ok((buf, read))
+            }
+            Err(err) => Err(eyre::Report::new(err).wrap_err(format!("reading {}", rel))),
+        };
+        let failed = chunk.is_err();
+        if tx.send(chunk).await.is_err() || failed {
+            return;
+        }
+    }
+}
+
 #[cfg(test)]
 mod block_telemetry_tests {
     use super::*;
@@ -1237,6 +1769,161 @@ mod underflow_tests {
     }
 }
 
+#[cfg(test)]
+mod read_ahead_tests {
+    use super::*;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context as TaskContext, Poll};
+    use std::time::{Duration, Instant};
+    use tokio::net::TcpSocket;
+
+    const CHUNK: usize = 64 * 1024;
+
+    /// A loopback data plane whose drain takes `pace` per `CHUNK`
+    /// received; returns everything it received.
+    async fn paced_pair(pace: Duration) -> (TcpStream, tokio::task::JoinHandle<Vec<u8>>) {
+        // Small socket buffers on both ends, so the socket pushes back
+        // on the writer instead of absorbing the whole file.
+        let listen = TcpSocket::new_v4().unwrap();
+        listen.set_recv_buffer_size(CHUNK as u32).unwrap();
+        listen.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let listener = listen.listen(1).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let drain = tokio::spawn(async move {
+            let (mut sock, _) = listener.accept().await.unwrap();
+            let mut received = Vec::new();
+            let mut buf = vec![0u8; CHUNK];
+            let started = tokio::time::Instant::now();
+            loop {
+                let n = sock.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    return received;
+                }
+                received.extend_from_slice(&buf[..n]);
+                let chunks = received.len() as f64 / CHUNK as f64;
+                tokio::time::sleep_until(started + pace.mul_f64(chunks)).await;
+            }
+        });
+        let socket = TcpSocket::new_v4().unwrap();
+        socket.set_send_buffer_size(CHUNK as u32).unwrap();
+        (socket.connect(addr).await.unwrap(), drain)
+    }
+
+    #[tokio::test]
+    async fn read_ahead_sends_the_same_record_as_the_double_buffered_loop() {
+        let tmp = tempfile::tempdir().unwrap();
+        let content: Vec<u8> = (0..3 * 1024 * 1024 + 4321)
+            .map(|i: u32| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+            .collect();
+        std::fs::write(tmp.path().join("big.bin"), &content).unwrap();
+        let source: Arc<dyn TransferSource> = Arc::new(
+            crate::remote::transfer::source::FsTransferSource::new(tmp.path().to_path_buf()),
+        );
+        let header = FileHeader {
+            relative_path: "big.bin".into(),
+            size: content.len() as u64,
+            ..Default::default()
+        };
+
+        let mut records = Vec::new();
+        for prefetch in [1, 4] {
+            let (client, drain) = paced_pair(Duration::ZERO).await;
+            // A budget of three buffers: the reader must wait on the
+            // writer's releases rather than run the whole file ahead.
+            let pool = Arc::new(BufferPool::new(CHUNK, 4, Some(3 * CHUNK)));
+            let mut session =
+                DataPlaneSession::from_stream(client, false, CHUNK, prefetch, pool).await;
+            session.send_file(source.clone(), &header).await.unwrap();
+            drop(session);
+            records.push(drain.await.unwrap());
+        }
+        assert_eq!(records[0], records[1]);
+        assert!(records[1].ends_with(&content));
+    }
+
+    /// Reads `CHUNK` bytes at a time; every `every`th read first stalls
+    /// for `stall`, the way a disk seek or a cold cache does.
+    struct BurstyReader {
+        remaining: usize,
+        reads: usize,
+        every: usize,
+        stall: Duration,
+        sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+    }
+
+    impl tokio::io::AsyncRead for BurstyReader {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut TaskContext<'_>,
+            buf: &mut tokio::io::ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            if self.sleep.is_none() && self.reads.is_multiple_of(self.every) && self.remaining > 0 {
+                self.sleep = Some(Box::pin(tokio::time::sleep(self.stall)));
+            }
+            if let Some(sleep) = self.sleep.as_mut() {
+                std::task::ready!(sleep.as_mut().poll(cx));
+            }
+            self.sleep = None;
+            self.reads += 1;
+            let n = self.remaining.min(buf.remaining()).min(CHUNK);
+            buf.put_slice(&vec![0x3C; n]);
+            self.remaining -= n;
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    /// Benchmark-style: with reads that stall in bursts and a socket
+    /// that drains at a steady pace, the double-buffered loop pays
+    /// for every stall on top of the writes, while a queue deep enough
+    /// to cover one stall rides it out on the socket's own time.
+    #[tokio::test]
+    async fn read_ahead_overlaps_bursty_reads_with_socket_writes() {
+        const SIZE: usize = 144 * CHUNK;
+        let header = FileHeader {
+            size: SIZE as u64,
+            ..Default::default()
+        };
+        let reader = || BurstyReader {
+            remaining: SIZE,
+            reads: 1,
+            every: 48,
+            stall: Duration::from_millis(240),
+            sleep: None,
+        };
+        let pace = Duration::from_millis(5);
+
+        let (client, drain) = paced_pair(pace).await;
+        let pool = Arc::new(BufferPool::new(CHUNK, 8, None));
+        let mut session = DataPlaneSession::from_stream(client, false, CHUNK, 1, pool).await;
+        let started = Instant::now();
+        session
+            .send_file_double_buffered(&mut reader(), &header, "bursty.bin")
+            .await
+            .unwrap();
+        drop(session);
+        assert_eq!(drain.await.unwrap().len(), SIZE);
+        let double_buffered = started.elapsed();
+
+        let (client, drain) = paced_pair(pace).await;
+        let pool = Arc::new(BufferPool::new(CHUNK, 56, None));
+        let mut session = DataPlaneSession::from_stream(client, false, CHUNK, 52, pool).await;
+        let started = Instant::now();
+        session
+            .send_file_read_ahead(Box::new(reader()), &header, "bursty.bin")
+            .await
+            .unwrap();
+        drop(session);
+        assert_eq!(drain.await.unwrap().len(), SIZE);
+        let read_ahead = started.elapsed();
+
+        assert!(
+            read_ahead.as_secs_f64() < double_buffered.as_secs_f64() * 0.85,
+            "read-ahead {read_ahead:?} vs double-buffered {double_buffered:?}"
+        );
+    }
+}
+
 #[cfg(test)]
 mod windows_metadata_codec_tests {
     use super::*;
@@ -1269,3 +1956,57 @@ mod windows_metadata_codec_tests {
         assert_eq!(absent, None);
     }
 }
+
+#[cfg(test)]
+mod wire_estimate_tests {
+    use super::*;
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// The estimate of a tar-shard record is exact: it matches the
+    /// bytes the session writes, member headers, digest, padding and a
+    /// long-name entry included.
+    #[tokio::test]
+    async fn tar_shard_estimate_matches_the_bytes_written() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let drain = tokio::spawn(async move {
+            let (mut sock, _) = listener.accept().await.unwrap();
+            let mut sink = Vec::new();
+            sock.read_to_end(&mut sink).await.unwrap();
+            sink.len() as u64
+        });
+        let client = TcpStream::connect(addr).await.unwrap();
+        let pool = Arc::new(BufferPool::new(64 * 1024, 4, None));
+        let mut session = DataPlaneSession::from_stream(client, false, 64 * 1024, 1, pool).await;
+
+        let headers: Vec<FileHeader> = [
+            ("a.txt".to_string(), 0u64),
+            ("dir/b.bin".to_string(), 513),
+            (format!("deep/{}.dat", "n".repeat(120)), 4096),
+        ]
+        .into_iter()
+        .map(|(relative_path, size)| FileHeader {
+            relative_path,
+            size,
+            mtime_seconds: 1_700_000_000,
+            permissions: 0o644,
+            ..Default::default()
+        })
+        .collect();
+        let data = super::super::payload::build_tar_shard_from(&headers, |header| {
+            Ok(std::io::Cursor::new(vec![7u8; header.size as usize]))
+        })
+        .unwrap();
+        assert_eq!(data.len() as u64, tar_archive_bytes(&headers));
+
+        let estimate = payload_wire_bytes(&TransferPayload::TarShard {
+            headers: headers.clone(),
+        });
+        session
+            .send_prepared_tar_shard(headers, &data)
+            .await
+            .unwrap();
+        drop(session);
+        assert_eq!(drain.await.unwrap(), estimate);
+    }
+}
@@ -16,7 +16,9 @@ use std::path::Path;
 use eyre::{Context, Result};
 
 use crate::generated::FileHeader;
-use crate::remote::transfer::payload::{plan_transfer_payloads, TransferPayload};
+use crate::remote::transfer::payload::{
+    plan_data_plane_payloads, plan_transfer_payloads, TransferPayload,
+};
 use crate::transfer_plan::PlanOptions;
 
 /// Push origins outsource the diff to the daemon: the client sends its
@@ -40,6 +42,17 @@ pub fn plan_push_payloads(
     plan_transfer_payloads(headers, source_root, plan_options).context("planning push payloads")
 }
 
+/// [`plan_push_payloads`] for a batch bound for the TCP data plane, where
+/// a batch dominated by tiny files travels as tar streams
+/// ([`plan_data_plane_payloads`]).
+pub fn plan_data_plane_push_payloads(
+    headers: Vec<FileHeader>,
+    source_root: &Path,
+    plan_options: PlanOptions,
+) -> Result<Vec<TransferPayload>> {
+    plan_data_plane_payloads(headers, source_root, plan_options).context("planning push payloads")
+}
+
 // (`LocalDiffInputs` / `plan_local_mirror` / `filter_unchanged` died at
 // otp-11b with their last caller, the engine's streaming plan — the
 // local route diffs through the session's `destination_needs` and
@@ -89,6 +102,10 @@ mod tests {
             permissions: 0,
             checksum: vec![],
             windows_metadata: None,
+            crtime_seconds: None,
+            directory: false,
+            owner: None,
+            xattrs: Vec::new(),
         }
     }
 
@@ -286,4 +303,112 @@ mod tests {
             .any(|p| matches!(p, TransferPayload::TarShard { .. }));
         assert!(has_tar, "force_tar must produce a TarShard payload");
     }
+
+    fn tiny_headers(dir: &std::path::Path, count: usize) -> Vec<FileHeader> {
+        (0..count)
+            .map(|i| {
+                let name = format!("f{i:03}.txt");
+                std::fs::write(dir.join(&name), b"tiny").unwrap();
+                header(&name, 4)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn planner_shard_max_files_yields_more_smaller_shards() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        let shard_members = |options: PlanOptions| -> Vec<usize> {
+            plan_transfer_payloads(tiny_headers(&src, 200), &src, options)
+                .unwrap()
+                .iter()
+                .map(|p| match p {
+                    TransferPayload::TarShard { headers } => headers.len(),
+                    other => panic!("expected only tar shards, got {other:?}"),
+                })
+                .collect()
+        };
+        let derived = shard_members(PlanOptions::default());
+        let capped = shard_members(PlanOptions {
+            shard_max_files: Some(25),
+            ..PlanOptions::default()
+        });
+        assert_eq!(derived, vec![200]);
+        assert_eq!(capped, vec![25; 8]);
+    }
+
+    #[test]
+    fn planner_no_shard_emits_only_file_payloads() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        let plan_options = PlanOptions {
+            no_shard: true,
+            ..PlanOptions::default()
+        };
+        let planned = plan_transfer_payloads(tiny_headers(&src, 200), &src, plan_options).unwrap();
+        assert_eq!(planned.len(), 200);
+        assert!(planned
+            .iter()
+            .all(|p| matches!(p, TransferPayload::File(_))));
+    }
+
+    #[test]
+    fn data_plane_planner_streams_a_tiny_file_dominated_batch() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        let mut headers = tiny_headers(&src, 600);
+        std::fs::write(src.join("big.bin"), vec![0u8; 64 * 1024]).unwrap();
+        headers.push(header("big.bin", 64 * 1024));
+
+        let planned = plan_data_plane_push_payloads(headers, &src, PlanOptions::default()).unwrap();
+        let streamed: Vec<usize> = planned
+            .iter()
+            .filter_map(|p| match p {
+                TransferPayload::TarStream { headers } => Some(headers.len()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(streamed, vec![600], "every tiny file rides one tar stream");
+        assert!(planned
+            .iter()
+            .any(|p| matches!(p, TransferPayload::File(h) if h.relative_path == "big.bin")));
+    }
+
+    #[test]
+    fn data_plane_planner_keeps_shards_below_the_tar_stream_shape() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        let is_stream = |p: &TransferPayload| matches!(p, TransferPayload::TarStream { .. });
+
+        // Too few tiny files to be worth one serialized connection.
+        let few =
+            plan_data_plane_push_payloads(tiny_headers(&src, 200), &src, PlanOptions::default())
+                .unwrap();
+        assert!(!few.iter().any(is_stream));
+
+        // Tiny files present but not dominant (600 of 1,200).
+        let mut mixed = tiny_headers(&src, 600);
+        for i in 0..600 {
+            let name = format!("m{i:03}.bin");
+            std::fs::write(src.join(&name), vec![1u8; 8192]).unwrap();
+            mixed.push(header(&name, 8192));
+        }
+        let mixed = plan_data_plane_push_payloads(mixed, &src, PlanOptions::default()).unwrap();
+        assert!(!mixed.iter().any(is_stream));
+
+        // --no-shard opts out of streams as well as shards.
+        let no_shard = PlanOptions {
+            no_shard: true,
+            ..PlanOptions::default()
+        };
+        let planned =
+            plan_data_plane_push_payloads(tiny_headers(&src, 600), &src, no_shard).unwrap();
+        assert!(planned
+            .iter()
+            .all(|p| matches!(p, TransferPayload::File(_))));
+    }
 }
@@ -74,7 +74,8 @@ pub fn outcome_for_report(report: &eyre::Report) -> TransferLifecycleOutcome {
             | Code::ReadOnly
             | Code::DelegationRefused
             | Code::ScanIncomplete
-            | Code::ChecksumDisabled,
+            | Code::ChecksumDisabled
+            | Code::FileLimit,
         ) => TransferLifecycleOutcome::Refused,
         _ => TransferLifecycleOutcome::Error,
     }
@@ -1,4 +1,6 @@
 pub mod abort_on_drop;
+pub mod archive_sink;
+pub mod archive_source;
 pub mod data_plane;
 pub mod diff_planner;
 pub mod faulted_path;
@@ -19,6 +21,7 @@ pub mod tar_safety;
 pub mod tcp_info;
 
 pub use abort_on_drop::AbortOnDrop;
+pub use archive_sink::ArchiveSink;
 pub use data_plane::{
     generate_sub_token, receive_stream_double_buffered, DataPlaneSession, CONTROL_PLANE_CHUNK_SIZE,
     DATA_PLANE_RECORD_BLOCK, DATA_PLANE_RECORD_BLOCK_COMPLETE, DATA_PLANE_RECORD_END,
@@ -30,8 +33,9 @@ pub use lifecycle_trace::{
     TransferLifecycleTrace,
 };
 pub use payload::{
-    build_tar_shard, payload_file_count, plan_transfer_payloads, prepare_payload,
-    prepared_payload_stream, PreparedPayload, TransferPayload, DEFAULT_PAYLOAD_PREFETCH,
+    build_tar_shard, build_tar_shard_from, payload_file_count, plan_transfer_payloads,
+    prepare_payload, prepared_payload_stream, PreparedPayload, TransferPayload,
+    DEFAULT_PAYLOAD_PREFETCH,
 };
 pub use pipeline::{
     execute_sink_pipeline, execute_sink_pipeline_elastic, execute_sink_pipeline_streaming,
@@ -41,9 +45,9 @@ pub use pipeline::{
 pub use resume_diff::ResumeBlockDiff;
 
 pub use progress::{
-    ByteProgressSink, LiveProbe, NoProbe, Probe, ProgressEvent, ProgressTotals,
-    RemoteTransferProgress, SharedStreamProbes, StreamId, StreamProbe, StreamProbeRegistry,
-    StreamState, StreamTelemetry, StreamTelemetrySnapshot,
+    ByteProgressSink, InFlightBytes, LiveProbe, NoProbe, Probe, ProgressEvent, ProgressTotals,
+    RemoteTransferProgress, SharedStreamBytes, SharedStreamProbes, StreamBytes, StreamId,
+    StreamProbe, StreamProbeRegistry, StreamState, StreamTelemetry, StreamTelemetrySnapshot,
 };
 pub use session_phase::{SessionPhaseEvent, SessionPhaseRole, SessionPhaseTrace};
 pub use sink::{DataPlaneSink, FsSinkConfig, FsTransferSink, NullSink, SinkOutcome, TransferSink};
@@ -24,7 +24,8 @@ use eyre::{bail, Context, Result};
 use crate::fs_enum::FileFilter;
 
 pub use crate::generated::{
-    ComparisonMode, FilterSpec, MirrorMode, ResumeSettings, TransferOperationSpec,
+    ComparisonMode, DialRequest, FilterSpec, MirrorMode, RenameRule, ResumeSettings,
+    TransferOperationSpec,
 };
 
 // Aliases for proto-side types (raw wire shape) so the from_spec()
@@ -46,7 +47,9 @@ use crate::generated::{
 ///     Bumping forces v1 daemons to fail closed (R51-F3).
 ///   - 3: added `drop_windows_metadata`. Exact versioning prevents a
 ///     delegated peer from silently ignoring the explicit lossy policy.
-pub const SUPPORTED_SPEC_VERSION: u32 = 3;
+///   - 4: added `rename` (`--rename` path rewrite rules).
+///   - 5: added `dial_request` (`--streams` / `--chunk-size` pins).
+pub const SUPPORTED_SPEC_VERSION: u32 = 5;
 
 /// Normalized, internal-friendly view of a transfer operation. Folds
 /// proto-`Unspecified` into concrete defaults, converts `FilterSpec`
@@ -92,6 +95,12 @@ pub struct NormalizedTransferOperation {
     /// Explicitly discard Windows attributes and named data streams at the
     /// SOURCE. False preserves strictly.
     pub drop_windows_metadata: bool,
+    /// `--rename` rules, validated (every pattern compiles). Empty
+    /// leaves names unchanged.
+    pub rename: Vec<RenameRule>,
+    /// `--streams` / `--chunk-size` pins for the delegated session's
+    /// dial. `None` leaves it fully adaptive.
+    pub dial_request: Option<DialRequest>,
 }
 
 impl NormalizedTransferOperation {
@@ -129,6 +138,8 @@ impl NormalizedTransferOperation {
             .transpose()
             .context("converting FilterSpec to FileFilter")?
             .filter(|f| !f.is_empty());
+        crate::path_rename::PathRenamer::from_rules(&spec.rename)
+            .context("validating rename rules")?;
 
         Ok(Self {
             module: spec.module,
@@ -141,6 +152,8 @@ impl NormalizedTransferOperation {
             ignore_existing: spec.ignore_existing,
             require_complete_scan: spec.require_complete_scan,
             drop_windows_metadata: spec.drop_windows_metadata,
+            rename: spec.rename,
+            dial_request: spec.dial_request,
         })
     }
 
@@ -255,6 +268,10 @@ pub struct DelegatedSpecOptions {
     /// Explicitly discard Windows attributes and named data streams at the
     /// source daemon before it emits the manifest.
     pub drop_windows_metadata: bool,
+    /// `--rename` rules the source daemon applies to its manifest.
+    pub rename: Vec<RenameRule>,
+    /// `--streams` / `--chunk-size` pins.
+    pub dial_request: Option<DialRequest>,
 }
 
 /// Build the delegated trigger's wire [`TransferOperationSpec`] from a
@@ -320,6 +337,8 @@ pub fn delegated_spec_from_options(
         ignore_existing: options.ignore_existing,
         require_complete_scan: options.require_complete_scan,
         drop_windows_metadata: options.drop_windows_metadata,
+        rename: options.rename.clone(),
+        dial_request: options.dial_request,
     })
 }
 
@@ -499,9 +518,21 @@ mod tests {
             ignore_existing: false,
             require_complete_scan: false,
             drop_windows_metadata: false,
+            rename: Vec::new(),
+            dial_request: None,
         }
     }
 
+    #[test]
+    fn malformed_rename_pattern_is_rejected() {
+        let mut spec = empty_spec();
+        spec.rename = vec![RenameRule {
+            pattern: "([".into(),
+            replacement: String::new(),
+        }];
+        assert!(NormalizedTransferOperation::from_spec(spec).is_err());
+    }
+
     #[test]
     fn unspecified_compare_mode_folds_to_size_mtime() {
         let normalized = NormalizedTransferOperation::from_spec(empty_spec()).unwrap();
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
 use eyre::{bail, eyre, Context, Result};
@@ -19,6 +20,14 @@ pub enum TransferPayload {
     TarShard {
         headers: Vec<FileHeader>,
     },
+    /// Many tiny files as ONE tar archive streamed over a single
+    /// data-plane connection (`TAR_STREAM` record): members are read and
+    /// framed as the archive is built, and the receiver unpacks while the
+    /// bytes arrive — nothing buffers the whole archive on either end.
+    /// Only the TCP planner ([`plan_data_plane_payloads`]) emits it.
+    TarStream {
+        headers: Vec<FileHeader>,
+    },
     /// Resume protocol: overwrite a block of an existing file.
     FileBlock {
         relative_path: String,
@@ -38,10 +47,13 @@ pub enum TransferPayload {
     /// worker ⇒ one socket, which is what keeps the record strictly
     /// serialized (every `BLOCK` before its `BLOCK_COMPLETE`, no
     /// cross-socket reorder hazard against the truncate+stamp).
+    /// `hash_key` is the session's `--checksum-seed` key the destination
+    /// hashed under (`None` = unseeded).
     ResumeFile {
         header: FileHeader,
         block_size: u32,
         dest_hashes: Vec<Vec<u8>>,
+        hash_key: Option<[u8; 32]>,
     },
 }
 
@@ -73,6 +85,9 @@ pub async fn prepare_payload(
         })
         .await
         .map_err(|err| eyre!("tar shard worker failed: {err}"))?,
+        // Nothing to prepare: the archive is built member by member
+        // inside the sink write (DataPlaneSink), as it streams.
+        TransferPayload::TarStream { headers } => Ok(PreparedPayload::TarStream { headers }),
         // Resume payloads can only originate on the receive side (parsed
         // off the wire by DataPlaneSource); the file-system source never
         // produces them.
@@ -86,12 +101,14 @@ pub async fn prepare_payload(
             mut header,
             block_size,
             dest_hashes,
+            hash_key,
         } => {
             if header.windows_metadata.is_none() {
                 return Ok(PreparedPayload::ResumeFile {
                     header,
                     block_size,
                     dest_hashes,
+                    hash_key,
                 });
             }
             task::spawn_blocking(move || {
@@ -101,6 +118,7 @@ pub async fn prepare_payload(
                     header,
                     block_size,
                     dest_hashes,
+                    hash_key,
                 })
             })
             .await
@@ -138,6 +156,10 @@ pub enum PreparedPayload {
         headers: Vec<FileHeader>,
         data: Vec<u8>,
     },
+    /// Send-side tar stream (see [`TransferPayload::TarStream`]). The
+    /// receive pipeline never builds one — the archive bytes reach the
+    /// sink through `TransferSink::write_tar_stream` as they arrive.
+    TarStream { headers: Vec<FileHeader> },
     /// Resume: write `bytes` at `offset` into the existing file at
     /// `dst_root.join(relative_path)`.
     FileBlock {
@@ -167,6 +189,7 @@ pub enum PreparedPayload {
         header: FileHeader,
         block_size: u32,
         dest_hashes: Vec<Vec<u8>>,
+        hash_key: Option<[u8; 32]>,
     },
 }
 
@@ -177,6 +200,8 @@ pub fn plan_transfer_payloads(
     source_root: &Path,
     options: PlanOptions,
 ) -> Result<Vec<TransferPayload>> {
+    // Directory manifest entries (contract v12) carry no bytes.
+    let headers: Vec<FileHeader> = headers.into_iter().filter(|h| !h.directory).collect();
     if headers.is_empty() {
         return Ok(Vec::new());
     }
@@ -248,7 +273,7 @@ pub fn plan_transfer_payloads(
     // Resume variants (FileBlock / FileBlockComplete) are receive-only and
     // never appear here — plan_transfer_payloads is the outbound planner.
     payloads.sort_by_key(|p| match p {
-        TransferPayload::TarShard { .. } => (0, 0),
+        TransferPayload::TarShard { .. } | TransferPayload::TarStream { .. } => (0, 0),
         TransferPayload::File(h) => (1, h.size),
         TransferPayload::ResumeFile { header, .. } => (1, header.size),
         TransferPayload::FileBlock { size, .. } => (2, *size),
@@ -258,12 +283,57 @@ pub fn plan_transfer_payloads(
     Ok(payloads)
 }
 
+/// Members of a tar stream are files strictly below this size — the
+/// "many tiny files" shape where per-record and per-file overheads
+/// dominate the bytes.
+pub const TAR_STREAM_MEMBER_MAX_BYTES: u64 = 4096;
+/// A batch needs at least this many tiny files before a tar stream is
+/// worth its single-connection serialization.
+pub const TAR_STREAM_MIN_MEMBERS: usize = 512;
+/// Most members one `TAR_STREAM` record carries; a larger tiny-file set
+/// is split across records. Matches the receiver's wire cap.
+pub const TAR_STREAM_MAX_MEMBERS: usize = 65_536;
+
+/// Plan a need batch for the TCP data plane. When the batch is dominated
+/// by sub-4 KiB files (at least [`TAR_STREAM_MIN_MEMBERS`] of them, and
+/// at least 90% of the batch), the tiny files travel as
+/// [`TransferPayload::TarStream`] records and the rest plans through
+/// [`plan_transfer_payloads`]; otherwise the whole batch does.
+/// `no_shard` opts out, and files carrying Windows metadata always take
+/// the ordinary records (their named streams are hydrated at prepare).
+pub fn plan_data_plane_payloads(
+    headers: Vec<FileHeader>,
+    source_root: &Path,
+    options: PlanOptions,
+) -> Result<Vec<TransferPayload>> {
+    let is_member =
+        |h: &FileHeader| h.size < TAR_STREAM_MEMBER_MAX_BYTES && h.windows_metadata.is_none();
+    let members = headers.iter().filter(|h| is_member(h)).count();
+    if options.no_shard
+        || members < TAR_STREAM_MIN_MEMBERS
+        || members.saturating_mul(10) < headers.len().saturating_mul(9)
+    {
+        return plan_transfer_payloads(headers, source_root, options);
+    }
+    let (tiny, rest): (Vec<FileHeader>, Vec<FileHeader>) = headers.into_iter().partition(is_member);
+    let mut payloads: Vec<TransferPayload> = tiny
+        .chunks(TAR_STREAM_MAX_MEMBERS)
+        .map(|chunk| TransferPayload::TarStream {
+            headers: chunk.to_vec(),
+        })
+        .collect();
+    payloads.extend(plan_transfer_payloads(rest, source_root, options)?);
+    Ok(payloads)
+}
+
 pub fn payload_file_count(payloads: &[TransferPayload]) -> usize {
     payloads
         .iter()
         .map(|payload| match payload {
             TransferPayload::File(_) => 1,
-            TransferPayload::TarShard { headers } => headers.len(),
+            TransferPayload::TarShard { headers } | TransferPayload::TarStream { headers } => {
+                headers.len()
+            }
             // Resume payloads patch existing files in-place — they
             // don't add to the "files transferred" count.
             TransferPayload::FileBlock { .. } | TransferPayload::FileBlockComplete { .. } => 0,
@@ -294,42 +364,55 @@ pub fn prepared_payload_stream(
 }
 
 pub fn build_tar_shard(source_root: &Path, headers: &[FileHeader]) -> Result<Vec<u8>> {
-    let mut builder = Builder::new(Vec::new());
-
-    for header in headers {
-        let rel = Path::new(&header.relative_path);
+    build_tar_shard_from(headers, |header| {
         // Empty relative_path = "root is itself the file" (single-file
         // source). See FsTransferSource::open_file for context — join("")
         // can preserve a trailing separator that File::open rejects.
-        let full_path = if header.relative_path.is_empty() {
-            source_root.to_path_buf()
-        } else {
-            source_root.join(rel)
-        };
-        let mut file = std::fs::File::open(&full_path)
-            .with_context(|| format!("opening {}", full_path.display()))?;
+        let full_path = source_path_for_header(source_root, header);
+        std::fs::File::open(&full_path).with_context(|| format!("opening {}", full_path.display()))
+    })
+}
 
-        let mut tar_header = Header::new_gnu();
-        tar_header.set_entry_type(EntryType::Regular);
-        let mode = if header.permissions == 0 {
-            0o644
-        } else {
-            header.permissions
-        };
-        tar_header.set_mode(mode);
-        tar_header.set_size(header.size);
-        let mtime = if header.mtime_seconds >= 0 {
-            header.mtime_seconds as u64
-        } else {
-            0
-        };
-        tar_header.set_mtime(mtime);
-        tar_header.set_cksum();
+/// Build a tar shard whose member bytes come from `open` rather than the
+/// filesystem — sources whose files are not plain paths (an archive
+/// member, say) share the shard layout [`build_tar_shard`] emits.
+pub fn build_tar_shard_from<R: Read>(
+    headers: &[FileHeader],
+    mut open: impl FnMut(&FileHeader) -> Result<R>,
+) -> Result<Vec<u8>> {
+    let mut builder = Builder::new(Vec::new());
 
+    for header in headers {
+        let rel = Path::new(&header.relative_path);
+        let mut reader = open(header)?;
+
+        let mut tar_header = tar_member_header(header);
         builder
-            .append_data(&mut tar_header, rel, &mut file)
-            .with_context(|| format!("adding {} to tar shard", full_path.display()))?;
+            .append_data(&mut tar_header, rel, &mut reader)
+            .with_context(|| format!("adding {} to tar shard", header.relative_path))?;
     }
 
     builder.into_inner().context("finalizing tar shard")
 }
+
+/// The tar header every Blit archive member gets: a regular GNU entry
+/// carrying the manifest's size, mode (0644 when absent) and mtime.
+pub(crate) fn tar_member_header(header: &FileHeader) -> Header {
+    let mut tar_header = Header::new_gnu();
+    tar_header.set_entry_type(EntryType::Regular);
+    let mode = if header.permissions == 0 {
+        0o644
+    } else {
+        header.permissions
+    };
+    tar_header.set_mode(mode);
+    tar_header.set_size(header.size);
+    let mtime = if header.mtime_seconds >= 0 {
+        header.mtime_seconds as u64
+    } else {
+        0
+    };
+    tar_header.set_mtime(mtime);
+    tar_header.set_cksum();
+    tar_header
+}
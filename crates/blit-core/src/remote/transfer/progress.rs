@@ -10,7 +10,9 @@ use tokio::sync::mpsc::UnboundedSender;
 ///
 /// - **Bytes ride `Payload` only.** Transferred bytes are reported
 ///   exclusively as `Payload { bytes, .. }` deltas (chunk- or
-///   file-granular, producer's choice). `FileComplete` carries no byte
+///   file-granular, producer's choice; a large file on the per-file
+///   lane may report several deltas through [`InFlightBytes`] before
+///   its `FileComplete`). `FileComplete` carries no byte
 ///   field at all, so no fold can double-count a file's bytes — the
 ///   class of bug filed as design-1.
 /// - **Files are counted exactly once**, through exactly one of two
@@ -46,6 +48,11 @@ pub enum ProgressEvent {
     /// finished. Deliberately carries no byte count — bytes ride
     /// [`ProgressEvent::Payload`] only.
     FileComplete { path: String },
+    /// The destination entry at the wire `path` (POSIX separators,
+    /// relative to the destination root) was removed by the mirror
+    /// delete pass — or, in a local dry run, would be. Reported by the
+    /// deleting end only; never part of transferred totals.
+    Deleted { path: String },
 }
 
 /// Running totals folded from a [`ProgressEvent`] stream under the
@@ -83,6 +90,7 @@ impl ProgressTotals {
             ProgressEvent::FileComplete { .. } => {
                 self.files = self.files.saturating_add(1);
             }
+            ProgressEvent::Deleted { .. } => {}
         }
     }
 
@@ -266,6 +274,60 @@ mod progress_totals_tests {
     }
 }
 
+#[cfg(test)]
+mod held_progress_tests {
+    use super::*;
+
+    fn drain(rx: &mut tokio::sync::mpsc::UnboundedReceiver<ProgressEvent>) -> ProgressTotals {
+        let mut totals = ProgressTotals::default();
+        while let Ok(event) = rx.try_recv() {
+            totals.apply(&event);
+        }
+        totals
+    }
+
+    /// A discarded attempt reports nothing — not what it held, not what
+    /// a straggling clone sends later — so its rerun counts once.
+    #[test]
+    fn a_discarded_attempt_reports_nothing() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let progress = RemoteTransferProgress::new(tx);
+        let (held, hold) = progress.held();
+        held.report_manifest_batch(3, 30);
+        hold.discard();
+        held.report_manifest_batch(3, 30);
+        assert_eq!(drain(&mut rx), ProgressTotals::default());
+
+        progress.report_manifest_batch(3, 30);
+        assert_eq!(drain(&mut rx).manifest_files, 3);
+    }
+
+    /// The first moved byte opens the hold, in order, and a released
+    /// hold sends whatever it still had.
+    #[test]
+    fn a_kept_attempt_reports_everything_in_order() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let (held, hold) = RemoteTransferProgress::new(tx).held();
+        held.report_manifest_batch(2, 20);
+        assert!(rx.try_recv().is_err(), "held before any payload");
+        held.report_payload(0, 10);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(ProgressEvent::ManifestBatch { files: 2, .. })
+        ));
+        held.report_file_complete("a".into());
+        hold.release();
+        let totals = drain(&mut rx);
+        assert_eq!((totals.files, totals.bytes), (1, 10));
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let (held, hold) = RemoteTransferProgress::new(tx).held();
+        held.report_manifest_batch(2, 20);
+        hold.release();
+        assert_eq!(drain(&mut rx).manifest_files, 2);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -342,6 +404,19 @@ impl<T> std::ops::Deref for CachePadded<T> {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct StreamId(pub u32);
 
+/// Payload bytes one data-plane stream carried over a whole session,
+/// retired streams included. Collected at data-plane close for the
+/// caller's end-of-transfer report (`--stats`, `--json`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StreamBytes {
+    pub stream: StreamId,
+    pub bytes: u64,
+}
+
+/// Caller-owned accumulator the SOURCE data plane fills with one
+/// [`StreamBytes`] per stream, ordered by stream id, when it closes.
+pub type SharedStreamBytes = Arc<Mutex<Vec<StreamBytes>>>;
+
 /// Coarse lifecycle state of a single stream, stored as a `u8` atomic so
 /// the sampler can read it lock-free. The controller uses it to exclude
 /// draining/closed streams from marginal-gain math.
@@ -595,37 +670,226 @@ impl Probe for LiveProbe {
     }
 }
 
+/// The producer handle for a [`ProgressEvent`] stream. This is the
+/// library-level progress API: an embedder builds one over its own
+/// channel, passes it as `PushSessionOptions::progress` /
+/// `PullSessionOptions::progress`, and folds the receiver through
+/// [`ProgressTotals`] (or reads the events directly). Nothing here
+/// formats; the CLI progress line and the TUI footers are consumers
+/// like any other. Sends never block and a dropped receiver is ignored.
 #[derive(Clone)]
 pub struct RemoteTransferProgress {
     sender: UnboundedSender<ProgressEvent>,
+    /// Set on a [`held`](Self::held) handle; `None` sends straight through.
+    hold: Option<Arc<Mutex<HoldState>>>,
+}
+
+/// Where a [`RemoteTransferProgress::held`] handle's events go.
+enum HoldState {
+    /// Kept back, in order, until the first transferred byte or file.
+    Holding(Vec<ProgressEvent>),
+    /// Sent as they arrive.
+    Open,
+    /// Dropped: the attempt was abandoned and its rerun reports afresh.
+    Discarded,
 }
 
 impl RemoteTransferProgress {
     pub fn new(sender: UnboundedSender<ProgressEvent>) -> Self {
-        Self { sender }
+        Self { sender, hold: None }
+    }
+
+    /// A handle for one session attempt the caller may abandon and rerun
+    /// (the in-stream fallback after an unreachable data plane). It keeps
+    /// its events back until the first `Payload` or `FileComplete` —
+    /// by then the attempt's data plane is up, so it can no longer be
+    /// abandoned — and [`ProgressHold`] releases or discards whatever is
+    /// still held once the attempt ends. A discarded attempt reports
+    /// nothing, so the rerun's events are not counted twice.
+    pub(crate) fn held(&self) -> (Self, ProgressHold) {
+        let state = Arc::new(Mutex::new(HoldState::Holding(Vec::new())));
+        let held = Self {
+            sender: self.sender.clone(),
+            hold: Some(Arc::clone(&state)),
+        };
+        let hold = ProgressHold {
+            sender: self.sender.clone(),
+            state,
+        };
+        (held, hold)
+    }
+
+    fn send(&self, event: ProgressEvent) {
+        let Some(hold) = &self.hold else {
+            let _ = self.sender.send(event);
+            return;
+        };
+        let mut state = hold.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match &mut *state {
+            HoldState::Holding(held) => {
+                held.push(event);
+                let moved = matches!(
+                    held.last(),
+                    Some(ProgressEvent::Payload { .. } | ProgressEvent::FileComplete { .. })
+                );
+                if moved {
+                    for event in std::mem::take(held) {
+                        let _ = self.sender.send(event);
+                    }
+                    *state = HoldState::Open;
+                }
+            }
+            HoldState::Open => {
+                let _ = self.sender.send(event);
+            }
+            HoldState::Discarded => {}
+        }
     }
 
     /// Announce `files` more expected files (the denominator). Never
     /// adds to transferred totals.
     pub fn report_manifest_batch(&self, files: usize, bytes: u64) {
-        let _ = self
-            .sender
-            .send(ProgressEvent::ManifestBatch { files, bytes });
+        self.send(ProgressEvent::ManifestBatch { files, bytes });
     }
 
     /// Report a transfer delta. `bytes` is the only byte channel in
     /// the contract; `files` is nonzero only on the aggregate lane
     /// (producers with no per-file visibility — see the enum docs).
     pub fn report_payload(&self, files: usize, bytes: u64) {
-        let _ = self.sender.send(ProgressEvent::Payload { files, bytes });
+        self.send(ProgressEvent::Payload { files, bytes });
     }
 
     /// Report one finished file on the per-file lane. `path` is the
     /// source-relative wire path. Carries no bytes by construction —
     /// report those via [`report_payload`](Self::report_payload).
     pub fn report_file_complete(&self, path: String) {
-        let _ = self.sender.send(ProgressEvent::FileComplete { path });
+        self.send(ProgressEvent::FileComplete { path });
+    }
+
+    /// Report one entry the mirror delete pass removed. `path` is the
+    /// destination-relative wire path.
+    pub fn report_deleted(&self, path: String) {
+        self.send(ProgressEvent::Deleted { path });
+    }
+}
+
+/// The caller's side of a [`RemoteTransferProgress::held`] handle.
+pub(crate) struct ProgressHold {
+    sender: UnboundedSender<ProgressEvent>,
+    state: Arc<Mutex<HoldState>>,
+}
+
+impl ProgressHold {
+    /// The attempt stands: send what is still held, and let later
+    /// events through.
+    pub(crate) fn release(self) {
+        let mut state = self.state.lock().unwrap_or_else(|p| p.into_inner());
+        if let HoldState::Holding(held) = &mut *state {
+            for event in std::mem::take(held) {
+                let _ = self.sender.send(event);
+            }
+        }
+        *state = HoldState::Open;
     }
+
+    /// The attempt is abandoned: drop what is held and anything a
+    /// straggling clone of its handle sends later.
+    pub(crate) fn discard(self) {
+        *self.state.lock().unwrap_or_else(|p| p.into_inner()) = HoldState::Discarded;
+    }
+}
+
+/// Mid-file reports are coalesced to at least this many bytes, so a
+/// file under one step adds no events and a 10 GiB file adds ~2 500.
+pub const IN_FLIGHT_STEP_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Byte progress for the one file a pipeline worker is moving, so a
+/// single large file advances the bar while it copies instead of
+/// jumping at the end. The copy and send loops [`report`](Self::report)
+/// chunks as they land; the worker then calls [`finish`](Self::finish)
+/// with the file's final byte count, which reports whatever the chunks
+/// did not — the file's `Payload` deltas sum to that count exactly.
+///
+/// Loops reach the current file's counter through [`report_in_flight`]
+/// (a task-local installed by [`with_in_flight`]), or through
+/// [`in_flight`] captured before a blocking hop.
+#[derive(Clone)]
+pub struct InFlightBytes {
+    progress: RemoteTransferProgress,
+    expected: u64,
+    state: Arc<InFlightState>,
+}
+
+#[derive(Default)]
+struct InFlightState {
+    reported: AtomicU64,
+    pending: AtomicU64,
+}
+
+impl InFlightBytes {
+    /// Counter for a file expected to move `expected` bytes. Mid-file
+    /// reports never exceed it, whatever the loops observe (a source
+    /// that grew, a fast path retried after a partial attempt).
+    pub fn new(progress: RemoteTransferProgress, expected: u64) -> Self {
+        Self {
+            progress,
+            expected,
+            state: Arc::default(),
+        }
+    }
+
+    /// `delta` more bytes of the file landed.
+    pub fn report(&self, delta: u64) {
+        let pending = self.state.pending.fetch_add(delta, Ordering::Relaxed) + delta;
+        if pending < IN_FLIGHT_STEP_BYTES {
+            return;
+        }
+        let flushed = self.state.pending.swap(0, Ordering::Relaxed);
+        let reported = self.state.reported.load(Ordering::Relaxed);
+        let flushed = flushed.min(self.expected.saturating_sub(reported));
+        if flushed > 0 {
+            self.state.reported.fetch_add(flushed, Ordering::Relaxed);
+            self.progress.report_payload(0, flushed);
+        }
+    }
+
+    /// The file is done and moved `total` bytes: report the part the
+    /// mid-file deltas did not cover. A file with no mid-file delta
+    /// always reports here, zero bytes included — exactly the one
+    /// `Payload` per file it sent before mid-file reporting existed.
+    pub fn finish(&self, total: u64) {
+        let reported = self.state.reported.load(Ordering::Relaxed);
+        let rest = total.saturating_sub(reported);
+        if rest > 0 || reported == 0 {
+            self.progress.report_payload(0, rest);
+        }
+    }
+}
+
+tokio::task_local! {
+    static IN_FLIGHT: InFlightBytes;
+}
+
+/// Run `fut` with `bytes` as the task's in-flight file counter (or
+/// without one, for `None`).
+pub async fn with_in_flight<F: std::future::Future>(
+    bytes: Option<InFlightBytes>,
+    fut: F,
+) -> F::Output {
+    match bytes {
+        Some(bytes) => IN_FLIGHT.scope(bytes, fut).await,
+        None => fut.await,
+    }
+}
+
+/// The task's in-flight file counter, for handing to blocking work.
+pub fn in_flight() -> Option<InFlightBytes> {
+    IN_FLIGHT.try_with(Clone::clone).ok()
+}
+
+/// Report `delta` bytes against the task's in-flight file, if any.
+pub fn report_in_flight(delta: u64) {
+    let _ = IN_FLIGHT.try_with(|bytes| bytes.report(delta));
 }
 
 #[cfg(test)]
@@ -20,6 +20,26 @@ use crate::generated::FileHeader;
 use super::faulted_path::FaultedPath;
 use super::source::TransferSource;
 
+/// `--checksum-seed` (contract v19): the key both ends hash resume
+/// blocks under. Seed 0 is unseeded — plain blake3, the same hashes a
+/// pre-v19 session exchanged. Any other seed derives a blake3 key, so
+/// block contents cannot be precomputed to collide with hashes the
+/// peer will accept without knowing the session's seed. Blit has no
+/// weak rolling checksum; the block hash is the only one the seed keys.
+pub fn block_hash_key(seed: u32) -> Option<[u8; 32]> {
+    (seed != 0).then(|| blake3::derive_key("blit resume block hash seed", &seed.to_le_bytes()))
+}
+
+/// One resume block's hash under `key` (see [`block_hash_key`]). The
+/// DESTINATION hashes its partial file with this and the SOURCE diffs
+/// against it, so both sides must hold the same key.
+pub fn block_hash(key: Option<&[u8; 32]>, block: &[u8]) -> blake3::Hash {
+    match key {
+        Some(key) => blake3::keyed_hash(key, block),
+        None => blake3::hash(block),
+    }
+}
+
 /// One step of the resume block diff.
 #[derive(Debug)]
 pub enum ResumeDiffEvent<'a> {
@@ -52,6 +72,7 @@ pub struct ResumeBlockDiff {
     size: u64,
     block_size: usize,
     dest_hashes: Vec<Vec<u8>>,
+    hash_key: Option<[u8; 32]>,
     buf: Vec<u8>,
     offset: u64,
     index: usize,
@@ -81,6 +102,7 @@ impl ResumeBlockDiff {
             size: header.size,
             block_size,
             dest_hashes,
+            hash_key: None,
             buf: vec![0u8; block_size],
             offset: 0,
             index: 0,
@@ -89,6 +111,14 @@ impl ResumeBlockDiff {
         })
     }
 
+    /// Hash source blocks under the session's `--checksum-seed` key —
+    /// the one the DESTINATION hashed `dest_hashes` under. `None` (the
+    /// default) is plain blake3.
+    pub fn with_hash_key(mut self, key: Option<[u8; 32]>) -> Self {
+        self.hash_key = key;
+        self
+    }
+
     /// Arm keepalive ticks: a [`ResumeDiffEvent::KeepAlive`] is emitted
     /// whenever `interval` passes without a stale block being yielded.
     pub fn with_keepalive(mut self, interval: Duration) -> Self {
@@ -121,7 +151,10 @@ impl ResumeBlockDiff {
                 filled += got;
             }
             let stale = match self.dest_hashes.get(self.index) {
-                Some(expected) => blake3::hash(&self.buf[..this]).as_bytes()[..] != expected[..],
+                Some(expected) => {
+                    block_hash(self.hash_key.as_ref(), &self.buf[..this]).as_bytes()[..]
+                        != expected[..]
+                }
                 None => true,
             };
             let block_offset = self.offset;
@@ -200,4 +233,48 @@ mod tests {
             "unarmed diff emits no keepalives"
         );
     }
+
+    /// `--checksum-seed`: two sessions with different seeds hash the
+    /// same block differently, seed 0 is plain blake3, and a diff only
+    /// trusts hashes made under its own key.
+    #[tokio::test]
+    async fn checksum_seed_keys_block_hashes_per_session() {
+        let block = vec![0x42u8; 4096];
+        let a = block_hash_key(7);
+        let b = block_hash_key(8);
+        assert_ne!(
+            block_hash(a.as_ref(), &block),
+            block_hash(b.as_ref(), &block),
+            "different seeds must not share block hashes"
+        );
+        assert_eq!(
+            block_hash(a.as_ref(), &block),
+            block_hash(block_hash_key(7).as_ref(), &block),
+            "one seed hashes deterministically on both ends"
+        );
+        assert_eq!(block_hash_key(0), None);
+        assert_eq!(block_hash(None, &block), blake3::hash(&block));
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("f.bin"), &block).unwrap();
+        let header = FileHeader {
+            relative_path: "f.bin".to_string(),
+            size: block.len() as u64,
+            ..Default::default()
+        };
+        let hashes = vec![block_hash(a.as_ref(), &block).as_bytes().to_vec()];
+        let source: Arc<dyn TransferSource> =
+            Arc::new(FsTransferSource::new(dir.path().to_path_buf()));
+        for (key, stale) in [(a, false), (b, true), (None, true)] {
+            let mut diff = ResumeBlockDiff::open(&source, &header, block.len(), hashes.clone())
+                .await
+                .unwrap()
+                .with_hash_key(key);
+            assert_eq!(
+                diff.next_event().await.unwrap().is_some(),
+                stale,
+                "key {key:?}"
+            );
+        }
+    }
 }
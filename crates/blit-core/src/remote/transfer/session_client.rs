@@ -18,6 +18,7 @@
 //! and pull (otp-5b) default to the TCP data plane; the in-stream
 //! carrier is the requested fallback either direction.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
@@ -25,37 +26,88 @@ use std::time::Duration;
 use eyre::{eyre, Result};
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::sync::CancellationToken;
 use tonic::transport::{Channel, Endpoint};
 
 use crate::generated::blit_client::BlitClient;
 use crate::generated::{
-    ComparisonMode, FilterSpec, MirrorMode, ResumeSettings, SessionOpen, TransferRole,
-    TransferSummary,
+    session_error, ComparisonMode, DialRequest, FilterSpec, MirrorMode, RenameRule, ResumeSettings,
+    SessionOpen, TransferFrame, TransferRole, TransferSummary,
 };
+use crate::remote::capabilities::{self, DaemonCapabilities};
 use crate::remote::endpoint::{RemoteEndpoint, RemotePath};
-use crate::remote::transfer::source::TransferSource;
+use crate::remote::transfer::progress::ProgressHold;
+use crate::remote::transfer::source::{FileCountWarning, TransferSource};
 use crate::remote::transfer::{
-    ByteProgressSink, RemoteTransferProgress, SessionPhaseRole, TransferLifecycleOutcome,
-    TransferLifecycleTrace,
+    ByteProgressSink, RemoteTransferProgress, SessionPhaseRole, SharedStreamBytes,
+    TransferLifecycleOutcome, TransferLifecycleTrace, TransferSink,
 };
 use crate::transfer_plan::PlanOptions;
-use crate::transfer_session::transport::{grpc_client_transport, GRPC_CHANNEL_FRAMES};
+use crate::transfer_session::lane::{self, LaneHandle, Redial};
+use crate::transfer_session::transport::{
+    grpc_client_transport, FrameTransport, GRPC_CHANNEL_FRAMES,
+};
 use crate::transfer_session::{
-    run_destination, run_source, DestinationInstruments, DestinationOutcome,
-    DestinationSessionConfig, DestinationTarget, HelloConfig, SessionEndpoint, SourceInstruments,
-    SourceSessionConfig,
+    run_destination, run_source, session_error_frame, DestinationInstruments, DestinationOutcome,
+    DestinationSessionConfig, DestinationTarget, HelloConfig, SessionEndpoint, SessionFault,
+    SourceInstruments, SourceSessionConfig,
 };
 
 /// The push-shaped session options. The full verb surface rides here
 /// since otp-10a (mirror, filters, progress, trace); the SOURCE owns
 /// the planner knobs, the DESTINATION owns the compare decision.
+#[derive(Clone)]
 pub struct PushSessionOptions {
     pub compare_mode: ComparisonMode,
     pub ignore_existing: bool,
+    /// `--existing`, riding `SessionOpen.existing_only` (contract v16):
+    /// the daemon DESTINATION requests only files it already has.
+    pub existing_only: bool,
+    /// `--max-files`, riding `SessionOpen.max_files` (contract v18):
+    /// this SOURCE refuses a manifest of more files before sending any
+    /// of it. 0 = no cap.
+    pub max_files: u64,
+    /// `--checksum-seed`, riding `SessionOpen.checksum_seed` (contract
+    /// v19): both ends key their resume block hashes with it. 0 =
+    /// unkeyed.
+    pub checksum_seed: u32,
+    /// `--chmod`, riding `SessionOpen.chmod` (contract v20): this
+    /// SOURCE rewrites every manifest mode and the destination applies
+    /// them. Empty = none.
+    pub chmod: String,
+    /// `--chown`, riding `SessionOpen.chown` (contract v21): the
+    /// daemon destination resolves and applies it. Empty = none.
+    pub chown: String,
+    /// `--xattrs` filter spec, riding `SessionOpen.xattrs` (contract
+    /// v22). As with `preserve_owner`, the caller builds its source
+    /// `with_xattrs` from the same spec. Empty = none.
+    pub xattrs: String,
+    /// `--remote-option`s, riding `SessionOpen.remote_options`
+    /// (contract v23): the daemon DESTINATION applies the keys it knows
+    /// and ignores the rest ([`crate::remote_options`]). Empty = none.
+    pub remote_options: HashMap<String, String>,
+    /// `--warn-files`: this SOURCE's scan asks before going past the
+    /// threshold. `None` never asks.
+    pub file_count_warning: Option<FileCountWarning>,
+    /// `--metadata-only`, riding `SessionOpen.metadata_only` (contract
+    /// v9): the daemon DESTINATION requests no bytes and only repairs
+    /// mtime/mode on content-equal files. Requires `Checksum` compare.
+    pub metadata_only: bool,
     pub require_complete_scan: bool,
     /// Explicitly discard Windows attributes and named data streams at the
     /// SOURCE. False preserves strictly and may reject a non-Windows target.
     pub drop_windows_metadata: bool,
+    /// `--crtimes`, riding `SessionOpen.preserve_crtimes` (contract v11).
+    /// The push SOURCE is this end's own `FsTransferSource`, so the caller
+    /// must also build it `with_crtimes`.
+    pub preserve_crtimes: bool,
+    /// `--super` / `--fake-super`, riding `SessionOpen.preserve_owner`
+    /// (contract v14). As with `preserve_crtimes`, the caller builds its
+    /// source `with_owner`.
+    pub preserve_owner: bool,
+    /// `--prune-empty-dirs`, riding `SessionOpen.prune_empty_dirs`
+    /// (contract v13).
+    pub prune_empty_dirs: bool,
     pub plan_options: PlanOptions,
     /// Force the in-stream byte carrier instead of the TCP data plane
     /// (otp-4b). Default `false` = the responder grants a data plane and
@@ -77,6 +129,14 @@ pub struct PushSessionOptions {
     /// `FilteredSource` chokepoint; the DESTINATION uses it to scope
     /// mirror deletions. `None` scans everything.
     pub filter: Option<FilterSpec>,
+    /// `--rename` rules, riding `SessionOpen.rename` (contract v6). This
+    /// SOURCE rewrites its own manifest paths with them; empty leaves
+    /// names unchanged.
+    pub rename: Vec<RenameRule>,
+    /// `--streams` / `--chunk-size` pins, riding `SessionOpen.dial_request`
+    /// (contract v7). This SOURCE pins its own dial with them; `None`
+    /// stays fully adaptive.
+    pub dial_request: Option<DialRequest>,
     /// otp-10a: mirror on the session (otp-6b's one delete rule — the
     /// daemon DESTINATION diffs the complete source manifest against
     /// its tree at SourceDone and deletes extraneous entries locally).
@@ -92,8 +152,22 @@ pub struct PushSessionOptions {
     /// otp-10a: emit `[data-plane-client]` connect traces on the data
     /// plane sockets this SOURCE dials (`--trace-data-plane`).
     pub trace_data_plane: bool,
+    /// Filled with the payload bytes each TCP data-plane stream carried
+    /// once the session's data plane closes; left empty on the
+    /// in-stream carrier. Feeds the push verb's per-stream report.
+    pub stream_bytes: Option<SharedStreamBytes>,
     /// Explicit process-local lifecycle context. Disabled by default.
     pub lifecycle_trace: TransferLifecycleTrace,
+    /// `--reconnect`: carry the control stream on a resumable lane
+    /// (contract v17). A drop mid-transfer is redialed and the session
+    /// resumes where it was, for up to this long; `None` fails the
+    /// session on the first drop, as before.
+    pub reconnect: Option<Duration>,
+    /// Cancel the session from outside. Firing it sends the daemon a
+    /// `CANCELLED` error frame, drops the data-plane sockets, and
+    /// returns a `Cancelled` [`SessionFault`]; the daemon ends its half
+    /// as it would for any peer error. `None` runs to completion.
+    pub cancel: Option<CancellationToken>,
 }
 
 impl Default for PushSessionOptions {
@@ -101,18 +175,35 @@ impl Default for PushSessionOptions {
         Self {
             compare_mode: ComparisonMode::SizeMtime,
             ignore_existing: false,
+            existing_only: false,
+            max_files: 0,
+            checksum_seed: 0,
+            chmod: String::new(),
+            chown: String::new(),
+            xattrs: String::new(),
+            remote_options: HashMap::new(),
+            file_count_warning: None,
+            metadata_only: false,
             require_complete_scan: false,
             drop_windows_metadata: false,
+            preserve_crtimes: false,
+            preserve_owner: false,
+            prune_empty_dirs: false,
             plan_options: PlanOptions::default(),
             in_stream_bytes: false,
             resume: false,
             resume_block_size: 0,
             filter: None,
+            rename: Vec::new(),
+            dial_request: None,
             mirror_enabled: false,
             mirror_kind: MirrorMode::Off,
             progress: None,
             trace_data_plane: false,
+            stream_bytes: None,
             lifecycle_trace: TransferLifecycleTrace::disabled(),
+            reconnect: None,
+            cancel: None,
         }
     }
 }
@@ -121,7 +212,51 @@ impl Default for PushSessionOptions {
 /// session pushing `source`'s tree into the endpoint's module/path.
 /// Returns the destination-computed [`TransferSummary`] (contract:
 /// DESTINATION is the scorer).
+///
+/// If the daemon grants a data plane this end cannot reach (see
+/// [`SessionFault::data_plane_unreachable`]), the session is rerun once
+/// on the in-stream carrier instead of failing; the summary's
+/// `in_stream_carrier_used` reports the switch, and `progress` /
+/// `stream_bytes` carry only the rerun's reports.
 pub async fn run_push_session(
+    endpoint: &RemoteEndpoint,
+    source: Arc<dyn TransferSource>,
+    mut options: PushSessionOptions,
+) -> Result<TransferSummary> {
+    options
+        .lifecycle_trace
+        .attach_initiator_role(SessionPhaseRole::Source);
+    let mut client = connect_transfer_client_with_trace(endpoint, &options.lifecycle_trace).await?;
+    if let Some(caps) = capabilities::probe(&mut client).await? {
+        caps.ensure_same_build()?;
+        fit_to_daemon(
+            &caps,
+            endpoint,
+            &mut options.in_stream_bytes,
+            &mut options.dial_request,
+        );
+    }
+    let fallback = (!options.in_stream_bytes).then(|| (client.clone(), options.clone()));
+    let attempt = AttemptProgress::begin(&mut options.progress, options.stream_bytes.as_ref());
+    match (
+        push_session_once(client, endpoint, Arc::clone(&source), options).await,
+        fallback,
+    ) {
+        (Err(err), Some((client, mut options))) if data_plane_unreachable(&err) => {
+            attempt.roll_back();
+            warn_in_stream_fallback(endpoint, &err);
+            options.in_stream_bytes = true;
+            push_session_once(client, endpoint, source, options).await
+        }
+        (result, _) => {
+            attempt.keep();
+            result
+        }
+    }
+}
+
+async fn push_session_once(
+    mut client: BlitClient<Channel>,
     endpoint: &RemoteEndpoint,
     source: Arc<dyn TransferSource>,
     options: PushSessionOptions,
@@ -133,16 +268,25 @@ pub async fn run_push_session(
     // path never crosses the wire (contract §SessionOpen).
     let (module, path) = endpoint_module_path(endpoint)?;
 
-    let mut client = connect_transfer_client_with_trace(endpoint, &lifecycle_trace).await?;
-
     let open = SessionOpen {
         initiator_role: TransferRole::Source as i32,
         module,
         path,
         compare_mode: options.compare_mode as i32,
         ignore_existing: options.ignore_existing,
+        existing_only: options.existing_only,
+        max_files: options.max_files,
+        checksum_seed: options.checksum_seed,
+        chmod: options.chmod,
+        chown: options.chown,
+        xattrs: options.xattrs,
+        remote_options: options.remote_options,
+        metadata_only: options.metadata_only,
         require_complete_scan: options.require_complete_scan,
         drop_windows_metadata: options.drop_windows_metadata,
+        preserve_crtimes: options.preserve_crtimes,
+        preserve_owner: options.preserve_owner,
+        prune_empty_dirs: options.prune_empty_dirs,
         // otp-4b: default to the TCP data plane; the responder grants it
         // in SessionAccept unless this asks for the in-stream fallback.
         in_stream_bytes: options.in_stream_bytes,
@@ -156,31 +300,15 @@ pub async fn run_push_session(
         // support; this is the client wiring, symmetric with pull's
         // otp-9a).
         filter: options.filter,
+        rename: options.rename,
+        dial_request: options.dial_request,
         mirror_enabled: options.mirror_enabled,
         mirror_kind: options.mirror_kind as i32,
         ..Default::default()
     };
 
-    // Open the bidi RPC: the request stream is fed by `out_tx`, the
-    // response stream is the inbound half. The handler returns its
-    // response stream immediately (it spawns the session), so this
-    // await resolves before any frame flows — no deadlock.
-    let (out_tx, out_rx) = mpsc::channel(GRPC_CHANNEL_FRAMES);
-    lifecycle_trace.record("transfer_rpc_open_begin", None);
-    let inbound = match client.transfer(ReceiverStream::new(out_rx)).await {
-        Ok(response) => {
-            lifecycle_trace.record(
-                "transfer_rpc_open_end",
-                Some(TransferLifecycleOutcome::Success),
-            );
-            response.into_inner()
-        }
-        Err(status) => {
-            lifecycle_trace.record("transfer_rpc_open_end", Some(rpc_status_outcome(&status)));
-            return Err(eyre::Report::new(transfer_open_refusal(status)));
-        }
-    };
-    let transport = grpc_client_transport(out_tx, inbound);
+    let (transport, terminator, lane) =
+        open_session_transport(&mut client, options.reconnect, &lifecycle_trace).await?;
 
     // otp-10a: own the unreadable-scan accumulator so a partial source
     // scan fails the push after the session completes — the old push
@@ -199,10 +327,12 @@ pub async fn run_push_session(
         instruments: SourceInstruments {
             progress: options.progress,
             unreadable: Some(Arc::clone(&unreadable)),
+            stream_bytes: options.stream_bytes,
             trace_data_plane: options.trace_data_plane,
             session_phase_trace: Default::default(),
             lifecycle_trace,
             small_file_probe: Default::default(),
+            file_count_warning: options.file_count_warning,
             #[cfg(test)]
             dial_test_samples: None,
             #[cfg(test)]
@@ -213,7 +343,13 @@ pub async fn run_push_session(
             dial_membership_test_gate: None,
         },
     };
-    let summary = run_source(cfg, transport, source).await?;
+    let summary = until_cancelled(
+        options.cancel.as_ref(),
+        terminator,
+        run_source(cfg, transport, source),
+    )
+    .await?;
+    finish_lane(lane).await;
 
     let unreadable = unreadable
         .lock()
@@ -237,13 +373,53 @@ pub async fn run_push_session(
 /// Mirror and filters ride the open since otp-9a (the session honors
 /// them since otp-6). The DESTINATION owns the compare decision; the
 /// SOURCE owns the planner knobs (none cross the wire).
+#[derive(Clone)]
 pub struct PullSessionOptions {
     pub compare_mode: ComparisonMode,
     pub ignore_existing: bool,
+    /// `--existing` (contract v16) — this DESTINATION requests only
+    /// files it already has. Symmetric with
+    /// [`PushSessionOptions::existing_only`].
+    pub existing_only: bool,
+    /// `--max-files` (contract v18) — the daemon SOURCE refuses a
+    /// manifest of more files. Symmetric with
+    /// [`PushSessionOptions::max_files`].
+    pub max_files: u64,
+    /// `--checksum-seed` (contract v19). Symmetric with
+    /// [`PushSessionOptions::checksum_seed`].
+    pub checksum_seed: u32,
+    /// `--chmod` (contract v20); the daemon SOURCE rewrites the modes.
+    /// Symmetric with [`PushSessionOptions::chmod`].
+    pub chmod: String,
+    /// `--chown` (contract v21); this DESTINATION applies it.
+    /// Symmetric with [`PushSessionOptions::chown`].
+    pub chown: String,
+    /// `--xattrs` (contract v22): the daemon SOURCE reads what the
+    /// filter admits and this DESTINATION sets it. Empty = none.
+    pub xattrs: String,
+    /// `--remote-option`s (contract v23), interpreted by the daemon
+    /// SOURCE, which knows none yet. Symmetric with
+    /// [`PushSessionOptions::remote_options`].
+    pub remote_options: HashMap<String, String>,
+    /// `--metadata-only` (contract v9) — this DESTINATION requests no
+    /// bytes. Symmetric with [`PushSessionOptions::metadata_only`].
+    pub metadata_only: bool,
     pub require_complete_scan: bool,
     /// Explicitly discard Windows attributes and named data streams at the
     /// SOURCE. False preserves strictly and may reject a non-Windows target.
     pub drop_windows_metadata: bool,
+    /// `--crtimes` (contract v11): the SOURCE responder stamps each
+    /// header with its creation time. Symmetric with
+    /// [`PushSessionOptions::preserve_crtimes`].
+    pub preserve_crtimes: bool,
+    /// `--super` / `--fake-super` (contract v14): the SOURCE responder
+    /// stamps each header with its owner, and this DESTINATION applies
+    /// it — recorded in the fake-super xattr when `fake_super` is set.
+    pub preserve_owner: bool,
+    pub fake_super: bool,
+    /// `--prune-empty-dirs` (contract v13), applied by the SOURCE
+    /// responder's scan.
+    pub prune_empty_dirs: bool,
     /// Force the in-stream byte carrier instead of the TCP data plane
     /// (otp-5b). Default `false` = the SOURCE responder grants a data
     /// plane and this DESTINATION initiator dials + receives over TCP
@@ -261,6 +437,14 @@ pub struct PullSessionOptions {
     /// (the session honors it since otp-6a — this is the client
     /// wiring). `None` scans everything.
     pub filter: Option<FilterSpec>,
+    /// `--rename` rules, riding `SessionOpen.rename` (contract v6) —
+    /// the daemon SOURCE rewrites its manifest paths, so this
+    /// DESTINATION receives and writes the rewritten names.
+    pub rename: Vec<RenameRule>,
+    /// `--streams` / `--chunk-size` pins, riding `SessionOpen.dial_request`
+    /// (contract v7) — the daemon SOURCE pins its dial and grants the
+    /// requested epoch-0 socket count, which this end dials.
+    pub dial_request: Option<DialRequest>,
     /// otp-9a: mirror on the session (otp-6b's one delete rule — this
     /// DESTINATION diffs the complete source manifest against its tree
     /// at SourceDone and deletes extraneous entries locally). Explicit
@@ -282,6 +466,17 @@ pub struct PullSessionOptions {
     pub trace_data_plane: bool,
     /// Explicit process-local lifecycle context. Disabled by default.
     pub lifecycle_trace: TransferLifecycleTrace,
+    /// `--reconnect` (contract v17). Symmetric with
+    /// [`PushSessionOptions::reconnect`].
+    pub reconnect: Option<Duration>,
+    /// Cancel the session from outside. Symmetric with
+    /// [`PushSessionOptions::cancel`].
+    pub cancel: Option<CancellationToken>,
+    /// Land received files here instead of under `dest_root` — a pull
+    /// into a pipe ([`ArchiveSink`](crate::remote::transfer::ArchiveSink)),
+    /// with `dest_root` an empty scratch directory. The caller closes
+    /// the archive once the session succeeds. `None` = the filesystem.
+    pub receive_sink: Option<Arc<dyn TransferSink>>,
 }
 
 impl Default for PullSessionOptions {
@@ -289,18 +484,35 @@ impl Default for PullSessionOptions {
         Self {
             compare_mode: ComparisonMode::SizeMtime,
             ignore_existing: false,
+            existing_only: false,
+            max_files: 0,
+            checksum_seed: 0,
+            chmod: String::new(),
+            chown: String::new(),
+            xattrs: String::new(),
+            remote_options: HashMap::new(),
+            metadata_only: false,
             require_complete_scan: false,
             drop_windows_metadata: false,
+            preserve_crtimes: false,
+            preserve_owner: false,
+            fake_super: false,
+            prune_empty_dirs: false,
             in_stream_bytes: false,
             resume: false,
             resume_block_size: 0,
             filter: None,
+            rename: Vec::new(),
+            dial_request: None,
             mirror_enabled: false,
             mirror_kind: MirrorMode::Off,
             byte_progress: None,
             progress: None,
             trace_data_plane: false,
             lifecycle_trace: TransferLifecycleTrace::disabled(),
+            reconnect: None,
+            cancel: None,
+            receive_sink: None,
         }
     }
 }
@@ -332,8 +544,132 @@ pub async fn run_pull_session(
 /// [`run_pull_session`] over an already-connected client (otp-9b). The
 /// delegated dst daemon connects separately so a connect failure keeps
 /// its own error phase (`ConnectSource`) structurally, without string
-/// matching on the session error.
+/// matching on the session error. An unreachable data-plane grant
+/// reruns the session once on the in-stream carrier, as
+/// [`run_push_session`] does.
 pub async fn run_pull_session_with_client(
+    mut client: BlitClient<Channel>,
+    endpoint: &RemoteEndpoint,
+    dest_root: PathBuf,
+    mut options: PullSessionOptions,
+) -> Result<DestinationOutcome> {
+    if let Some(caps) = capabilities::probe(&mut client).await? {
+        caps.ensure_same_build()?;
+        fit_to_daemon(
+            &caps,
+            endpoint,
+            &mut options.in_stream_bytes,
+            &mut options.dial_request,
+        );
+    }
+    let fallback = (!options.in_stream_bytes).then(|| (client.clone(), options.clone()));
+    let attempt = AttemptProgress::begin(&mut options.progress, None);
+    match (
+        pull_session_once(client, endpoint, dest_root.clone(), options).await,
+        fallback,
+    ) {
+        (Err(err), Some((client, mut options))) if data_plane_unreachable(&err) => {
+            attempt.roll_back();
+            warn_in_stream_fallback(endpoint, &err);
+            options.in_stream_bytes = true;
+            pull_session_once(client, endpoint, dest_root, options).await
+        }
+        (result, _) => {
+            attempt.keep();
+            result
+        }
+    }
+}
+
+/// Drop the optional carrier behaviour `caps` says this daemon's
+/// operator turned off, before the open is built. A daemon without a
+/// TCP data plane (`--force-grpc-data`) never grants one, so the open
+/// asks for the in-stream carrier up front, and any `--streams` /
+/// `--chunk-size` pins — meaningless without sockets — are dropped with
+/// a warning rather than riding an open that would ignore them.
+fn fit_to_daemon(
+    caps: &DaemonCapabilities,
+    endpoint: &RemoteEndpoint,
+    in_stream_bytes: &mut bool,
+    dial_request: &mut Option<DialRequest>,
+) {
+    if !caps.supports(capabilities::FEATURE_TCP_DATA_PLANE) {
+        *in_stream_bytes = true;
+    }
+    if dial_request.is_some() && !caps.supports(capabilities::FEATURE_DIAL_PINS) {
+        log::warn!(
+            "daemon at {} serves no TCP data plane; ignoring --streams/--chunk-size",
+            endpoint.host
+        );
+        *dial_request = None;
+    }
+}
+
+/// What one session attempt reported, held so an attempt abandoned for
+/// the in-stream rerun can be taken back: its progress events (see
+/// [`RemoteTransferProgress::held`]) and any per-stream byte entries it
+/// appended. A pull's `byte_progress` needs no rollback — it counts
+/// payload bytes, and an unreachable data plane fails before any move.
+struct AttemptProgress {
+    hold: Option<ProgressHold>,
+    stream_bytes: Option<(SharedStreamBytes, usize)>,
+}
+
+impl AttemptProgress {
+    /// Swap `progress` for a held handle and note where `stream_bytes`
+    /// stands. The caller's own handle stays on its rerun options.
+    fn begin(
+        progress: &mut Option<RemoteTransferProgress>,
+        stream_bytes: Option<&SharedStreamBytes>,
+    ) -> Self {
+        let hold = progress.as_mut().map(|sink| {
+            let (held, hold) = sink.held();
+            *sink = held;
+            hold
+        });
+        let stream_bytes = stream_bytes.map(|shared| {
+            let len = shared.lock().unwrap_or_else(|p| p.into_inner()).len();
+            (Arc::clone(shared), len)
+        });
+        Self { hold, stream_bytes }
+    }
+
+    fn keep(self) {
+        if let Some(hold) = self.hold {
+            hold.release();
+        }
+    }
+
+    fn roll_back(self) {
+        if let Some(hold) = self.hold {
+            hold.discard();
+        }
+        if let Some((shared, len)) = self.stream_bytes {
+            shared
+                .lock()
+                .unwrap_or_else(|p| p.into_inner())
+                .truncate(len);
+        }
+    }
+}
+
+/// Whether `err` is this initiator's failed epoch-0 data-plane dial —
+/// the one failure a rerun on the in-stream carrier can fix, because
+/// the sockets are dialed before any manifest or payload moves.
+fn data_plane_unreachable(err: &eyre::Report) -> bool {
+    err.chain()
+        .filter_map(|cause| cause.downcast_ref::<SessionFault>())
+        .any(|fault| fault.data_plane_unreachable)
+}
+
+fn warn_in_stream_fallback(endpoint: &RemoteEndpoint, err: &eyre::Report) {
+    log::warn!(
+        "data plane to {} unreachable, retrying over the gRPC carrier: {err:#}",
+        endpoint.host
+    );
+}
+
+async fn pull_session_once(
     mut client: BlitClient<Channel>,
     endpoint: &RemoteEndpoint,
     dest_root: PathBuf,
@@ -349,8 +685,19 @@ pub async fn run_pull_session_with_client(
         path,
         compare_mode: options.compare_mode as i32,
         ignore_existing: options.ignore_existing,
+        existing_only: options.existing_only,
+        max_files: options.max_files,
+        checksum_seed: options.checksum_seed,
+        chmod: options.chmod,
+        chown: options.chown,
+        xattrs: options.xattrs,
+        remote_options: options.remote_options,
+        metadata_only: options.metadata_only,
         require_complete_scan: options.require_complete_scan,
         drop_windows_metadata: options.drop_windows_metadata,
+        preserve_crtimes: options.preserve_crtimes,
+        preserve_owner: options.preserve_owner,
+        prune_empty_dirs: options.prune_empty_dirs,
         // otp-5b: default to the TCP data plane; the SOURCE responder
         // grants it in SessionAccept unless this asks for the in-stream
         // fallback.
@@ -363,27 +710,15 @@ pub async fn run_pull_session_with_client(
         // otp-9a: filter + mirror ride the open (otp-6a/6b session
         // support; this is the client wiring).
         filter: options.filter,
+        rename: options.rename,
+        dial_request: options.dial_request,
         mirror_enabled: options.mirror_enabled,
         mirror_kind: options.mirror_kind as i32,
         ..Default::default()
     };
 
-    let (out_tx, out_rx) = mpsc::channel(GRPC_CHANNEL_FRAMES);
-    lifecycle_trace.record("transfer_rpc_open_begin", None);
-    let inbound = match client.transfer(ReceiverStream::new(out_rx)).await {
-        Ok(response) => {
-            lifecycle_trace.record(
-                "transfer_rpc_open_end",
-                Some(TransferLifecycleOutcome::Success),
-            );
-            response.into_inner()
-        }
-        Err(status) => {
-            lifecycle_trace.record("transfer_rpc_open_end", Some(rpc_status_outcome(&status)));
-            return Err(eyre::Report::new(transfer_open_refusal(status)));
-        }
-    };
-    let transport = grpc_client_transport(out_tx, inbound);
+    let (transport, terminator, lane) =
+        open_session_transport(&mut client, options.reconnect, &lifecycle_trace).await?;
 
     let cfg = DestinationSessionConfig {
         hello: HelloConfig::default(),
@@ -391,6 +726,7 @@ pub async fn run_pull_session_with_client(
         // The initiator dials the data plane on the same host it reached
         // the control plane on (contract §Transport: initiator dials).
         data_plane_host: Some(endpoint.host.clone()),
+        diff_batch: None,
         receiver_capacity: None,
         instruments: DestinationInstruments {
             progress: options.progress,
@@ -401,8 +737,127 @@ pub async fn run_pull_session_with_client(
             small_file_probe: Default::default(),
         },
         local_apply: None,
+        fake_super: options.fake_super,
+        receive_sink: options.receive_sink,
     };
-    run_destination(cfg, transport, DestinationTarget::Fixed(dest_root)).await
+    let outcome = until_cancelled(
+        options.cancel.as_ref(),
+        terminator,
+        run_destination(cfg, transport, DestinationTarget::Fixed(dest_root)),
+    )
+    .await?;
+    finish_lane(lane).await;
+    Ok(outcome)
+}
+
+/// Open the `Transfer` RPC and return the transport the session runs
+/// over, the sender [`until_cancelled`] frames a cancel on, and — with
+/// `reconnect` set — the resumable lane carrying both. The handler
+/// returns its response stream immediately (it spawns the session), so
+/// the open resolves before any frame flows — no deadlock.
+async fn open_session_transport(
+    client: &mut BlitClient<Channel>,
+    reconnect: Option<Duration>,
+    lifecycle_trace: &TransferLifecycleTrace,
+) -> Result<(
+    FrameTransport,
+    mpsc::Sender<TransferFrame>,
+    Option<LaneHandle>,
+)> {
+    let mark = reconnect.map(|_| lane::LANE_ATTACH);
+    lifecycle_trace.record("transfer_rpc_open_begin", None);
+    let (physical, out_tx) = match open_transfer_rpc(client, mark).await {
+        Ok(opened) => {
+            lifecycle_trace.record(
+                "transfer_rpc_open_end",
+                Some(TransferLifecycleOutcome::Success),
+            );
+            opened
+        }
+        Err(status) => {
+            lifecycle_trace.record("transfer_rpc_open_end", Some(rpc_status_outcome(&status)));
+            return Err(eyre::Report::new(transfer_open_refusal(status)));
+        }
+    };
+    let Some(window) = reconnect else {
+        return Ok((physical, out_tx, None));
+    };
+    drop(out_tx);
+    let redial_client = client.clone();
+    let redial: Box<Redial> = Box::new(move || {
+        let mut client = redial_client.clone();
+        Box::pin(async move {
+            let (physical, _) = open_transfer_rpc(&mut client, Some(lane::LANE_RESUME))
+                .await
+                .map_err(|status| eyre!("reopening Transfer RPC: {}", status.message()))?;
+            Ok(physical)
+        })
+    });
+    let (transport, lane) = lane::initiate(physical, window, redial).await?;
+    let terminator = lane
+        .terminator()
+        .ok_or_else(|| eyre!("control lane closed before the session started"))?;
+    Ok((transport, terminator, Some(lane)))
+}
+
+/// Open one bidi `Transfer` RPC: the request stream is fed by the
+/// returned sender, the response stream is the transport's inbound
+/// half. `lane` marks a lane RPC for the daemon's dispatcher.
+async fn open_transfer_rpc(
+    client: &mut BlitClient<Channel>,
+    lane: Option<&'static str>,
+) -> std::result::Result<(FrameTransport, mpsc::Sender<TransferFrame>), tonic::Status> {
+    let (out_tx, out_rx) = mpsc::channel(GRPC_CHANNEL_FRAMES);
+    let mut request = tonic::Request::new(ReceiverStream::new(out_rx));
+    if let Some(mark) = lane {
+        request.metadata_mut().insert(
+            lane::LANE_METADATA_KEY,
+            tonic::metadata::MetadataValue::from_static(mark),
+        );
+    }
+    let inbound = client.transfer(request).await?.into_inner();
+    Ok((grpc_client_transport(out_tx.clone(), inbound), out_tx))
+}
+
+/// Let a session's lane close out after the session succeeded. The
+/// outcome is already in hand, so a lane that cannot close cleanly is
+/// only worth a warning.
+async fn finish_lane(lane: Option<LaneHandle>) {
+    if let Some(lane) = lane {
+        if let Err(err) = lane.finish().await {
+            log::warn!("control stream did not close cleanly: {err:#}");
+        }
+    }
+}
+
+/// Drive `session` unless `cancel` fires first. On cancel the session
+/// future is dropped — closing its data-plane sockets mid-record — and
+/// a `CANCELLED` error frame goes out on `terminator` (a clone of the
+/// request stream's sender, or the lane's session sender under
+/// `--reconnect`) so the daemon ends its half on a framed
+/// reason rather than a bare hangup. The daemon keeps whatever partial
+/// files it wrote; a later `--resume` run patches them.
+async fn until_cancelled<T>(
+    cancel: Option<&CancellationToken>,
+    terminator: mpsc::Sender<TransferFrame>,
+    session: impl std::future::Future<Output = Result<T>>,
+) -> Result<T> {
+    let Some(cancel) = cancel else {
+        drop(terminator);
+        return session.await;
+    };
+    tokio::select! {
+        biased;
+        result = session => result,
+        () = cancel.cancelled() => {
+            const REASON: &str = "transfer cancelled by the client";
+            // Best effort: a daemon that already hung up needs no reason.
+            let _ = terminator
+                .send(session_error_frame(session_error::Code::Cancelled, REASON))
+                .await;
+            Err(SessionFault::refusal(session_error::Code::Cancelled, REASON).into())
+        }
+    }
 }
 
 /// Derive the wire `(module, path)` from a resolved endpoint. Empty
@@ -462,6 +917,35 @@ mod endpoint_module_path_tests {
         let (_, path) = endpoint_module_path(&endpoint(PathBuf::new())).expect("resolves");
         assert_eq!(path, "");
     }
+
+    /// A daemon that advertises no TCP data plane gets an in-stream
+    /// open with the dial pins dropped; one that does keeps both as
+    /// the caller asked.
+    #[test]
+    fn missing_data_plane_feature_downgrades_the_open() {
+        use crate::remote::capabilities::{advertise, DaemonPolicy};
+        let pins = Some(DialRequest {
+            streams: 4,
+            chunk_bytes: 0,
+        });
+        let policy = |force_grpc_data| DaemonPolicy {
+            force_grpc_data,
+            server_checksums_enabled: true,
+            delegation_enabled: false,
+        };
+
+        let caps = DaemonCapabilities::from(advertise(policy(true), None));
+        let (mut in_stream, mut dial) = (false, pins);
+        fit_to_daemon(&caps, &endpoint(PathBuf::new()), &mut in_stream, &mut dial);
+        assert!(in_stream);
+        assert_eq!(dial, None);
+
+        let caps = DaemonCapabilities::from(advertise(policy(false), None));
+        let (mut in_stream, mut dial) = (false, pins);
+        fit_to_daemon(&caps, &endpoint(PathBuf::new()), &mut in_stream, &mut dial);
+        assert!(!in_stream);
+        assert_eq!(dial, pins);
+    }
 }
 
 /// The `Transfer` RPC failed at OPEN — before any session frame flowed.
@@ -512,8 +996,8 @@ fn rpc_status_outcome(status: &tonic::Status) -> TransferLifecycleOutcome {
 }
 
 /// Build a `BlitClient` over `endpoint`'s control-plane URI with a
-/// bounded connect (audit-2's 30 s policy, inherited from the old
-/// drivers' connect path).
+/// bounded connect (`--contimeout`, default audit-2's 30 s; see
+/// [`crate::remote::connect`]).
 /// `pub` since otp-9b: the delegated dst daemon connects separately
 /// from running the session so connect failures keep their own phase.
 pub async fn connect_transfer_client(endpoint: &RemoteEndpoint) -> Result<BlitClient<Channel>> {
@@ -529,26 +1013,22 @@ pub async fn connect_transfer_client_with_trace(
     let uri = endpoint.control_plane_uri();
     lifecycle_trace.record("control_connect_begin", None);
     let conn = match Endpoint::from_shared(uri.clone()) {
-        Ok(endpoint) => endpoint.connect_timeout(Duration::from_secs(30)),
+        Ok(endpoint) => endpoint,
         Err(err) => {
             lifecycle_trace.record("control_connect_end", Some(TransferLifecycleOutcome::Error));
             return Err(eyre!("invalid endpoint uri {uri}: {err}"));
         }
     };
-    let channel = match tokio::time::timeout(Duration::from_secs(30), conn.connect()).await {
-        Ok(Ok(channel)) => channel,
-        Ok(Err(err)) => {
-            lifecycle_trace.record("control_connect_end", Some(TransferLifecycleOutcome::Error));
-            return Err(eyre!("connecting to {uri}: {err}"));
-        }
-        Err(_) => {
+    let channel = match crate::remote::connect::connect_channel(conn).await {
+        Ok(channel) => channel,
+        Err(err) => {
             lifecycle_trace.record("control_connect_end", Some(TransferLifecycleOutcome::Error));
-            return Err(eyre!("timed out connecting to {uri}"));
+            return Err(err);
         }
     };
     lifecycle_trace.record(
         "control_connect_end",
         Some(TransferLifecycleOutcome::Success),
     );
-    Ok(BlitClient::new(channel))
+    Ok(crate::remote::message_limits::blit_client(channel))
 }
@@ -282,7 +282,7 @@ pub(super) fn trace_env_run_id(mut read: impl FnMut(&str) -> Option<String>) ->
     enabled.then(|| read(RUN_ID_ENV).unwrap_or_else(|| format!("pid-{}", std::process::id())))
 }
 
-pub(super) fn session_trace_id(session_token: &[u8]) -> Arc<str> {
+pub(crate) fn session_trace_id(session_token: &[u8]) -> Arc<str> {
     let digest = blake3::hash(session_token).to_hex();
     Arc::from(&digest.as_str()[..16])
 }
@@ -4,6 +4,7 @@
 //! Implementations handle the actual write: local filesystem, TCP data plane, etc.
 
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -12,12 +13,12 @@ use filetime::FileTime;
 
 use crate::buffer::BufferSizer;
 use crate::checksum::ChecksumType;
-use crate::copy::{copy_file, resume_copy_file};
+use crate::copy::{copy_file_with_progress, resume_copy_file, SparseMode};
 use crate::generated::{ComparisonMode, FileHeader};
 use crate::remote::transfer::payload::PreparedPayload;
-use crate::remote::transfer::progress::{ByteProgressSink, NoProbe, Probe};
+use crate::remote::transfer::progress::{ByteProgressSink, InFlightBytes, NoProbe, Probe};
 use crate::remote::transfer::small_file_probe::{BoundSmallFileProbe, MemberTimingReport};
-use crate::remote::transfer::source::TransferSource;
+use crate::remote::transfer::source::{original_relative_path, RenamedPaths, TransferSource};
 
 // Re-export for consumers.
 pub use super::data_plane::DataPlaneSession;
@@ -63,6 +64,45 @@ pub trait TransferSink: Send + Sync {
         )
     }
 
+    /// Unpack a tar-stream record: one tar archive of `headers`' files,
+    /// read from `reader` as it arrives off the wire (which yields the
+    /// archive bytes and then EOF). Outbound-only sinks inherit the
+    /// default error implementation.
+    async fn write_tar_stream(
+        &self,
+        headers: Vec<FileHeader>,
+        _reader: &mut (dyn tokio::io::AsyncRead + Unpin + Send),
+    ) -> Result<SinkOutcome> {
+        eyre::bail!(
+            "{} does not support write_tar_stream ({} file(s))",
+            std::any::type_name::<Self>(),
+            headers.len()
+        )
+    }
+
+    /// Write a run of small whole files the receive pipeline coalesced
+    /// from consecutive `FILE` records, their bytes already read off the
+    /// wire. The default writes them one at a time through
+    /// [`TransferSink::write_file_stream`]; either way a failure names
+    /// its file ([`FaultedPath`]).
+    ///
+    /// [`FaultedPath`]: crate::remote::transfer::faulted_path::FaultedPath
+    async fn write_small_files(&self, files: Vec<(FileHeader, Vec<u8>)>) -> Result<SinkOutcome> {
+        let mut total = SinkOutcome::default();
+        for (header, bytes) in files {
+            let outcome = self
+                .write_file_stream(&header, &mut bytes.as_slice())
+                .await
+                .map_err(|e| {
+                    e.wrap_err(crate::remote::transfer::faulted_path::FaultedPath(
+                        header.relative_path.clone(),
+                    ))
+                })?;
+            total.merge(&outcome);
+        }
+        Ok(total)
+    }
+
     /// Signal that all payloads have been sent. Flushes buffers, sends terminators, etc.
     /// Default implementation is a no-op.
     async fn finish(&self) -> Result<()> {
@@ -94,6 +134,27 @@ pub struct FsSinkConfig {
     /// dropped at the sink layer. The default `SizeMtime` keeps
     /// pre-fix behavior for callers that haven't migrated.
     pub compare_mode: ComparisonMode,
+    /// `--sparse` / `--no-sparse` for File payloads copied through the
+    /// zero-copy cascade. `Auto` keeps the cascade's opportunistic holes.
+    pub sparse: SparseMode,
+    /// `--block-size`: the streaming copy buffer and resume block size
+    /// in bytes, already validated. `0` keeps the derived sizes.
+    pub block_size: usize,
+    /// `--preallocate`: reserve each copied File payload's full size
+    /// before streaming it (skipped under `SparseMode::Always`).
+    pub preallocate: bool,
+    /// `--reflink-dest`: a reference tree checked before each File
+    /// payload is copied. When it holds the same relative path with the
+    /// source's size and mtime (content, under `checksum`), the
+    /// destination is cloned copy-on-write from it instead of written
+    /// from the source.
+    pub reflink_dest: Option<PathBuf>,
+    /// `--remote-option fsync=true`: `sync_all` every file a wire
+    /// receive lands (streamed files, tar shard and tar stream members,
+    /// small-file runs) before it counts as written. Resume blocks
+    /// always sync. Off by default for the reason `write_file_stream`
+    /// gives.
+    pub fsync: bool,
 }
 
 impl Default for FsSinkConfig {
@@ -104,6 +165,11 @@ impl Default for FsSinkConfig {
             checksum: None,
             resume: false,
             compare_mode: ComparisonMode::SizeMtime,
+            sparse: SparseMode::Auto,
+            block_size: 0,
+            preallocate: false,
+            reflink_dest: None,
+            fsync: false,
         }
     }
 }
@@ -135,6 +201,15 @@ pub struct FsTransferSink {
     /// Separate otp-12 high-volume observer. `None` is the exact normal
     /// sink path: no clocks, per-member timing, or output.
     small_file_probe: Option<BoundSmallFileProbe>,
+    /// `--rename` on the local route: payload headers carry rewritten
+    /// paths, but File copies and tar mtime restamps read
+    /// `src_root` directly, so they resolve the original name through
+    /// the scan's rename map. `None` = source and destination names
+    /// are the same.
+    renamed_sources: Option<RenamedPaths>,
+    /// Bytes File copies left as holes instead of writing (see
+    /// [`FsTransferSink::with_sparse_counter`]). `None` = not tallied.
+    sparse_bytes: Option<Arc<AtomicU64>>,
 }
 
 impl FsTransferSink {
@@ -155,9 +230,25 @@ impl FsTransferSink {
             config,
             byte_progress: None,
             small_file_probe: None,
+            renamed_sources: None,
+            sparse_bytes: None,
         }
     }
 
+    /// Tally the bytes every File copy leaves as holes into `counter`.
+    /// The local route reads it back for `LocalMirrorSummary::sparse_bytes`.
+    pub fn with_sparse_counter(mut self, counter: Arc<AtomicU64>) -> Self {
+        self.sparse_bytes = Some(counter);
+        self
+    }
+
+    /// Resolve source reads through a `RenamingSource`'s rename map
+    /// (local `--rename`).
+    pub fn with_renamed_sources(mut self, renamed: RenamedPaths) -> Self {
+        self.renamed_sources = Some(renamed);
+        self
+    }
+
     /// Attach a byte-level progress sink. When set,
     /// `write_file_stream` reports every chunk the data plane
     /// writes against this sink. Used by the daemon side of
@@ -245,11 +336,21 @@ impl TransferSink for FsTransferSink {
             PreparedPayload::ResumeFile { .. } => {
                 eyre::bail!("FsTransferSink does not consume composite ResumeFile payloads")
             }
+            // Send-side shape; inbound archive bytes arrive through
+            // `write_tar_stream` instead.
+            PreparedPayload::TarStream { .. } => {
+                eyre::bail!("FsTransferSink does not consume send-side TarStream payloads")
+            }
             PreparedPayload::File(_) | PreparedPayload::TarShard { .. } => {
                 let src_root = self.src_root.clone();
                 let dst_root = self.dst_root.clone();
                 let canonical_dst_root = self.canonical_dst_root.clone();
+                let renamed_sources = self.renamed_sources.clone();
                 let config = self.config.clone();
+                let sparse_bytes = self.sparse_bytes.clone();
+                // The blocking copy runs off this task; carry the file's
+                // progress counter across by value.
+                let in_flight = crate::remote::transfer::progress::in_flight();
                 let tar_probe = self
                     .small_file_probe
                     .as_ref()
@@ -265,8 +366,11 @@ impl TransferSink for FsTransferSink {
                         &src_root,
                         &dst_root,
                         canonical_dst_root.as_deref(),
+                        renamed_sources.as_ref(),
                         &header,
                         &config,
+                        sparse_bytes.as_deref(),
+                        in_flight.as_ref(),
                     ),
                     PreparedPayload::TarShard { headers, data } => {
                         let worker_started = tar_probe.as_ref().map(|_| std::time::Instant::now());
@@ -277,6 +381,7 @@ impl TransferSink for FsTransferSink {
                             &src_root,
                             &dst_root,
                             canonical_dst_root.as_deref(),
+                            renamed_sources.as_ref(),
                             &headers,
                             &data,
                             &config,
@@ -311,6 +416,82 @@ impl TransferSink for FsTransferSink {
         Ok(outcome)
     }
 
+    /// Unpack a tar stream while it arrives: an async pump feeds the
+    /// archive bytes to a blocking worker running the tar walk, through a
+    /// short queue, so neither side ever holds the whole archive. The
+    /// digest is only known once the archive is through, so members are
+    /// staged beside their destinations and renamed into place only once
+    /// it verifies; a record that fails it has its staged members
+    /// removed, every destination untouched, and fails `DATA_LOSS`.
+    async fn write_tar_stream(
+        &self,
+        headers: Vec<FileHeader>,
+        reader: &mut (dyn tokio::io::AsyncRead + Unpin + Send),
+    ) -> Result<SinkOutcome> {
+        if self.canonical_dst_root.is_none() {
+            log::warn!(
+                "write_tar_stream at '{}' has no canonical root; tar-stream receive \
+                 falls back to lexical-only path checks",
+                self.dst_root.display()
+            );
+        }
+        let (tx, rx) = tokio::sync::mpsc::channel::<Vec<u8>>(4);
+        let dst_root = self.dst_root.clone();
+        let canonical_dst_root = self.canonical_dst_root.clone();
+        let config = self.config.clone();
+        let members = headers.len();
+        let first = headers
+            .first()
+            .map(|h| h.relative_path.clone())
+            .unwrap_or_default();
+        let worker = tokio::task::spawn_blocking(move || {
+            let reader = ChunkChannelReader::new(rx);
+            let mut staged = Vec::new();
+            let unpacked = unpack_tar_stream(
+                reader,
+                headers,
+                &dst_root,
+                canonical_dst_root.as_deref(),
+                &config,
+                &mut staged,
+            );
+            (unpacked, staged)
+        });
+        let mut pumped = pump_chunks(reader, tx, "tar stream").await;
+        let (unpacked, staged) = worker.await.context("tar stream worker panicked")?;
+        if pumped.is_ok() && unpacked.is_err() {
+            // The worker quit early, leaving the rest of the record unread:
+            // read it through so a corrupted archive reports as such.
+            pumped = tokio::io::copy(reader, &mut tokio::io::sink())
+                .await
+                .map(|_| ())
+                .context("draining tar stream");
+        }
+        if let Err(err) = &pumped {
+            // Unverified bytes never reach a destination path.
+            tokio::task::spawn_blocking(move || discard_staged_members(&staged))
+                .await
+                .context("tar stream cleanup panicked")?;
+            if crate::remote::transfer::data_plane::is_tar_stream_digest_mismatch(err) {
+                return Err(super::tar_safety::tar_stream_digest_fault(
+                    members, &first, err,
+                ));
+            }
+        } else {
+            tokio::task::spawn_blocking(move || commit_staged_members(&staged))
+                .await
+                .context("tar stream commit panicked")??;
+        }
+        // A broken wire surfaces as its own error, not as the truncated
+        // archive the worker saw because of it.
+        pumped?;
+        let outcome = unpacked?;
+        if let Some(bp) = &self.byte_progress {
+            bp.report(outcome.bytes_written);
+        }
+        Ok(outcome)
+    }
+
     /// Stream file bytes from the wire to the destination filesystem
     /// using the same double-buffered helper the send side uses. This
     /// is what makes push and pull receive symmetric on the FsTransferSink.
@@ -370,6 +551,8 @@ impl TransferSink for FsTransferSink {
         crate::windows_metadata::validate_payload(header.windows_metadata.as_ref())
             .with_context(|| format!("validating Windows metadata for {}", header.relative_path))?;
         crate::windows_metadata::prepare_destination(&dst, header.windows_metadata.as_ref())?;
+        crate::fs_capability::detach_hard_link(&dst)
+            .with_context(|| format!("detaching {}", dst.display()))?;
 
         {
             use tokio::io::AsyncWriteExt as _;
@@ -398,6 +581,12 @@ impl TransferSink for FsTransferSink {
             file.flush()
                 .await
                 .with_context(|| format!("flushing {}", dst.display()))?;
+            if self.config.fsync {
+                file.sync_all()
+                    .await
+                    .with_context(|| format!("syncing {}", dst.display()))?;
+                log::debug!("synced {}", dst.display());
+            }
         }
         // Handle dropped → kernel close() complete → no further
         // metadata churn from this file. Now safe to set mtime by path.
@@ -406,7 +595,8 @@ impl TransferSink for FsTransferSink {
         // multi-second on spinning rust and crater throughput
         // (9.3 → 3.3 Gbps observed). The transfer's durability signal
         // is its END marker plus the OS's own flush; matches rsync's
-        // default behavior. Add a config flag if a caller needs sync.
+        // default behavior. A caller that needs it asks for
+        // `FsSinkConfig::fsync` (`--remote-option fsync=true`).
 
         let windows_bytes =
             crate::windows_metadata::replace_streams(&dst, header.windows_metadata.as_ref())?;
@@ -421,6 +611,7 @@ impl TransferSink for FsTransferSink {
                 log::warn!("set mtime on {}: {}", dst.display(), e);
             }
         }
+        crate::fs_capability::apply_creation_time(&dst, header.crtime_seconds);
 
         // Permissions arrive on the wire (Unix mode bits). Apply best-
         // effort; ignore failures (cross-fs, root-owned dst, etc.).
@@ -443,18 +634,110 @@ impl TransferSink for FsTransferSink {
         })
     }
 
+    /// One blocking hop for the whole run instead of a tokio file-op
+    /// round trip per syscall per file: each distinct parent directory is
+    /// created once, the files are written in parallel, and a failed file
+    /// gets one retry on its own (like a tar shard member). Files are
+    /// written in place, exactly as `write_file_stream` writes them.
+    async fn write_small_files(&self, files: Vec<(FileHeader, Vec<u8>)>) -> Result<SinkOutcome> {
+        use super::tar_safety::ExtractedFile;
+
+        let mut members = Vec::with_capacity(files.len());
+        let mut refused = Vec::new();
+        for (header, contents) in files {
+            let dest_path = match self
+                .resolve_destination(&header.relative_path)
+                .with_context(|| format!("validating receive path {:?}", header.relative_path))
+            {
+                Ok(dest_path) => dest_path,
+                Err(err) => {
+                    refused.push((header.relative_path, err));
+                    continue;
+                }
+            };
+            members.push(ExtractedFile {
+                mtime: (self.config.preserve_times && header.mtime_seconds > 0)
+                    .then(|| FileTime::from_unix_time(header.mtime_seconds, 0)),
+                crtime_seconds: header.crtime_seconds,
+                permissions: (header.permissions != 0).then_some(header.permissions),
+                size: contents.len() as u64,
+                rel: header.relative_path,
+                dest_path,
+                contents,
+                windows_metadata: header.windows_metadata,
+            });
+        }
+        // R58-F4: the wire was already drained; a dry run stops here.
+        if self.config.dry_run {
+            let (files_written, bytes_written) =
+                fail_on_member_failures("small-file run", members.len(), 0, refused)?;
+            return Ok(SinkOutcome {
+                files_written,
+                bytes_written,
+            });
+        }
+        let fsync = self.config.fsync;
+        let outcome =
+            tokio::task::spawn_blocking(move || write_small_file_run(&members, refused, fsync))
+                .await
+                .context("sink worker panicked")??;
+        if let Some(bp) = &self.byte_progress {
+            bp.report(outcome.bytes_written);
+        }
+        Ok(outcome)
+    }
+
     fn root(&self) -> &Path {
         &self.dst_root
     }
 }
 
+/// Write a coalesced run of small files (see
+/// [`FsTransferSink::write_small_files`]). Runs usually share a handful
+/// of directories, so the parent check is paid once per directory, not
+/// once per file; a directory that still fails to appear surfaces on the
+/// member's own retry, which creates its parents itself. Members
+/// `refused` before writing fail the run like a shard's do.
+fn write_small_file_run(
+    members: &[super::tar_safety::ExtractedFile],
+    refused: Vec<(String, eyre::Report)>,
+    fsync: bool,
+) -> Result<SinkOutcome> {
+    use rayon::prelude::*;
+
+    let mut ensured: std::collections::HashSet<&Path> = std::collections::HashSet::new();
+    for member in members {
+        if let Some(parent) = member.dest_path.parent() {
+            if ensured.insert(parent) {
+                if let Err(err) = std::fs::create_dir_all(parent) {
+                    log::debug!("create dir {}: {err}", parent.display());
+                }
+            }
+        }
+    }
+    let results: Vec<Result<u64>> = members.par_iter().map(|f| write_member(f, false)).collect();
+    let mut failed = refused;
+    let (files_written, bytes_written) =
+        retry_failed_members("small-file run", members, results, fsync, &mut failed);
+    let (files_written, bytes_written) =
+        fail_on_member_failures("small-file run", files_written, bytes_written, failed)?;
+    Ok(SinkOutcome {
+        files_written,
+        bytes_written,
+    })
+}
+
 /// Copy a single file using the zero-copy cascade in `copy::file_copy`.
+#[allow(clippy::too_many_arguments)]
 fn write_file_payload(
     src_root: &Path,
     dst_root: &Path,
     canonical_dst_root: Option<&Path>,
+    renamed_sources: Option<&RenamedPaths>,
     header: &FileHeader,
     config: &FsSinkConfig,
+    sparse_bytes: Option<&AtomicU64>,
+    in_flight: Option<&InFlightBytes>,
 ) -> Result<SinkOutcome> {
     // An empty relative_path means "the root itself" — the enumeration
     // root was a single file (same rule as FsTransferSource::open_file):
@@ -463,9 +746,12 @@ fn write_file_payload(
     // local session route (otp-11) is the first caller to send a
     // file-root File payload through here.
     if header.relative_path.is_empty() {
-        return copy_root_file_payload(src_root, dst_root, header, config);
+        return copy_root_file_payload(src_root, dst_root, header, config, sparse_bytes, in_flight);
     }
-    let src = src_root.join(&header.relative_path);
+    let src = match renamed_sources {
+        Some(renamed) => src_root.join(original_relative_path(renamed, &header.relative_path)?),
+        None => src_root.join(&header.relative_path),
+    };
     // R47-F1: the FsTransferSink::write_payload arm for
     // PreparedPayload::File hit this helper, which previously
     // joined dst_root + header.relative_path lexically. A peer-
@@ -493,7 +779,7 @@ fn write_file_payload(
         }
     };
 
-    copy_resolved_file_payload(&src, &dst, header, config)
+    copy_resolved_file_payload(&src, &dst, header, config, sparse_bytes, in_flight)
 }
 
 /// The file-root identity case of [`write_file_payload`]: `src_root`
@@ -505,8 +791,10 @@ fn copy_root_file_payload(
     dst_root: &Path,
     header: &FileHeader,
     config: &FsSinkConfig,
+    sparse_bytes: Option<&AtomicU64>,
+    in_flight: Option<&InFlightBytes>,
 ) -> Result<SinkOutcome> {
-    copy_resolved_file_payload(src_root, dst_root, header, config)
+    copy_resolved_file_payload(src_root, dst_root, header, config, sparse_bytes, in_flight)
 }
 
 /// Shared tail of the File-payload write: dry-run gate, parent mkdir,
@@ -516,6 +804,8 @@ fn copy_resolved_file_payload(
     dst: &Path,
     header: &FileHeader,
     config: &FsSinkConfig,
+    sparse_bytes: Option<&AtomicU64>,
+    in_flight: Option<&InFlightBytes>,
 ) -> Result<SinkOutcome> {
     // R58-F4: dry-run must be side-effect-free. Bail before the
     // parent-mkdir so a dry-run doesn't create destination
@@ -538,13 +828,51 @@ fn copy_resolved_file_payload(
 
     let mut did_copy = false;
     if config.resume {
-        let outcome = resume_copy_file(src, dst, 0)
+        crate::fs_capability::unshare_hard_link(dst)
+            .with_context(|| format!("unsharing {}", dst.display()))?;
+        let outcome = resume_copy_file(src, dst, config.block_size)
             .with_context(|| format!("resume copy {}", header.relative_path))?;
         did_copy = outcome.bytes_transferred > 0;
     } else if crate::copy::file_needs_copy_with_mode(src, dst, config.compare_mode)? {
-        let sizer = BufferSizer::default();
-        copy_file(src, dst, &sizer, false)
-            .with_context(|| format!("copy {}", header.relative_path))?;
+        let sizer = match config.block_size {
+            0 => BufferSizer::default(),
+            size => BufferSizer::with_block_size(size),
+        };
+        let on_bytes = |delta| {
+            if let Some(bytes) = in_flight {
+                bytes.report(delta);
+            }
+        };
+        let reflinked = match config.reflink_dest.as_deref() {
+            Some(reference) => reflink_from_reference(reference, src, dst, header, config)?,
+            None => false,
+        };
+        let outcome = match config.checksum {
+            _ if reflinked => crate::copy::FileCopyOutcome {
+                bytes_copied: header.size,
+                clone_succeeded: true,
+                sparse_bytes: 0,
+            },
+            Some(checksum) => copy_verified(src, dst, &sizer, config, &on_bytes, checksum)
+                .with_context(|| format!("copy {}", header.relative_path))?,
+            None => {
+                crate::fs_capability::detach_hard_link(dst)
+                    .with_context(|| format!("detaching {}", dst.display()))?;
+                copy_file_with_progress(
+                    src,
+                    dst,
+                    &sizer,
+                    false,
+                    config.sparse,
+                    config.preallocate,
+                    &on_bytes,
+                )
+                .with_context(|| format!("copy {}", header.relative_path))?
+            }
+        };
+        if let Some(counter) = sparse_bytes {
+            counter.fetch_add(outcome.sparse_bytes, Ordering::Relaxed);
+        }
         did_copy = true;
     }
 
@@ -568,6 +896,7 @@ fn copy_resolved_file_payload(
             }
         }
     }
+    crate::fs_capability::apply_creation_time(dst, header.crtime_seconds);
     crate::windows_metadata::apply_attributes(dst, header.windows_metadata.as_ref())?;
 
     Ok(SinkOutcome {
@@ -576,6 +905,110 @@ fn copy_resolved_file_payload(
     })
 }
 
+/// `--reflink-dest`: clone `dst` from `reference/<relative_path>` when
+/// that file is unchanged from `src` — same size and mtime, or the same
+/// content under `--checksum`. Returns false (copy from the source as
+/// usual) when there is no such file or the filesystem cannot clone it.
+fn reflink_from_reference(
+    reference: &Path,
+    src: &Path,
+    dst: &Path,
+    header: &FileHeader,
+    config: &FsSinkConfig,
+) -> Result<bool> {
+    let candidate = crate::path_safety::safe_join(reference, &header.relative_path)?;
+    let unchanged = match config.checksum {
+        Some(checksum) => {
+            std::fs::symlink_metadata(&candidate)
+                .is_ok_and(|meta| meta.is_file() && meta.len() == header.size)
+                && crate::checksum::hash_file(&candidate, checksum)?
+                    == crate::checksum::hash_file(src, checksum)?
+        }
+        None => {
+            !crate::copy::file_needs_copy_with_mode(src, &candidate, ComparisonMode::SizeMtime)?
+                && std::fs::symlink_metadata(&candidate).is_ok_and(|meta| meta.is_file())
+        }
+    };
+    if !unchanged {
+        return Ok(false);
+    }
+    crate::fs_capability::detach_hard_link(dst)
+        .with_context(|| format!("detaching {}", dst.display()))?;
+    let cloned = crate::fs_capability::reflink(&candidate, dst)
+        .with_context(|| format!("reflink {} from {}", dst.display(), candidate.display()))?;
+    if !cloned {
+        log::debug!(
+            "reflink unsupported for {}; copying from the source",
+            dst.display()
+        );
+    }
+    Ok(cloned)
+}
+
+/// `--checksum` on a local copy: write to a hidden sibling of `dst`,
+/// hash it against the source, and only then rename it over `dst`. A
+/// mismatch (silent storage corruption) removes the staged file and
+/// fails the copy, leaving any existing `dst` untouched. The rename
+/// also replaces a hard link instead of writing through it.
+fn copy_verified(
+    src: &Path,
+    dst: &Path,
+    sizer: &BufferSizer,
+    config: &FsSinkConfig,
+    on_bytes: &dyn Fn(u64),
+    checksum: ChecksumType,
+) -> Result<crate::copy::FileCopyOutcome> {
+    let name = dst
+        .file_name()
+        .ok_or_else(|| eyre::eyre!("destination has no file name: {}", dst.display()))?;
+    let mut staged_name = std::ffi::OsString::from(".");
+    staged_name.push(name);
+    staged_name.push(".blit-verify");
+    let staged = dst.with_file_name(staged_name);
+    let _ = std::fs::remove_file(&staged);
+
+    let verified = copy_file_with_progress(
+        src,
+        &staged,
+        sizer,
+        false,
+        config.sparse,
+        config.preallocate,
+        on_bytes,
+    )
+    .and_then(|outcome| {
+        #[cfg(test)]
+        if CORRUPT_VERIFY_STAGE.with(|hook| hook.replace(false)) {
+            let mut bytes = std::fs::read(&staged)?;
+            if let Some(first) = bytes.first_mut() {
+                *first ^= 0xff;
+            }
+            std::fs::write(&staged, bytes)?;
+        }
+        let expected = crate::checksum::hash_file(src, checksum)?;
+        if crate::checksum::hash_file(&staged, checksum)? != expected {
+            eyre::bail!(
+                "post-copy verification failed: {} does not match {}",
+                dst.display(),
+                src.display()
+            );
+        }
+        std::fs::rename(&staged, dst).with_context(|| format!("committing {}", dst.display()))?;
+        Ok(outcome)
+    });
+    if verified.is_err() {
+        let _ = std::fs::remove_file(&staged);
+    }
+    verified
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Corrupts the next [`copy_verified`] stage on this thread before
+    /// it is hashed, so tests can drive the mismatch path.
+    static CORRUPT_VERIFY_STAGE: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
 /// Read the local source timestamp at apply time so local copies retain the
 /// precision that the second-granularity wire header cannot represent. The
 /// header value remains a fallback when the source timestamp cannot be read.
@@ -590,13 +1023,24 @@ fn source_file_mtime(source: &Path, fallback: Option<FileTime>) -> Option<FileTi
 /// Replace wire-derived tar timestamps with local source timestamps. An empty
 /// source root is the transfer-session convention for a wire receive, which
 /// must keep using the timestamp carried by its header.
-fn restamp_local_tar_mtimes(src_root: &Path, files: &mut [super::tar_safety::ExtractedFile]) {
+fn restamp_local_tar_mtimes(
+    src_root: &Path,
+    renamed_sources: Option<&RenamedPaths>,
+    files: &mut [super::tar_safety::ExtractedFile],
+) {
     if src_root.as_os_str().is_empty() {
         return;
     }
     for file in files {
         let source = if file.rel.is_empty() {
             src_root.to_path_buf()
+        } else if let Some(renamed) = renamed_sources {
+            // An unmapped member keeps the tar's own mtime rather than
+            // stamping from whatever happens to live at the new name.
+            match original_relative_path(renamed, &file.rel) {
+                Ok(original) => src_root.join(original),
+                Err(_) => continue,
+            }
         } else {
             src_root.join(&file.rel)
         };
@@ -609,6 +1053,7 @@ fn write_tar_shard_payload(
     src_root: &Path,
     dst_root: &Path,
     canonical_dst_root: Option<&Path>,
+    renamed_sources: Option<&RenamedPaths>,
     headers: &[FileHeader],
     data: &[u8],
     config: &FsSinkConfig,
@@ -645,6 +1090,8 @@ fn write_tar_shard_payload(
     let parse_started = probe.map(|_| std::time::Instant::now());
     let opts = TarShardExtractOptions::default();
     let mut extracted = safe_extract_tar_shard(data, headers.to_vec(), dst_root, &opts)?;
+    // Members refused before any write; the rest of the shard still lands.
+    let mut refused: Vec<(String, eyre::Report)> = Vec::new();
 
     // R47-F1: tar shards arriving on FsTransferSink::write_payload
     // (push-receive on the daemon flows through here too) only had
@@ -653,12 +1100,21 @@ fn write_tar_shard_payload(
     // entry path like `link/victim` write through the symlink.
     // Verify each extracted entry's destination against the
     // canonical root before writing.
+    //
+    // An escaping member is refused on its own: it is never written (nor
+    // retried), but it no longer costs the shard's other members.
     if let Some(canonical) = canonical_dst_root {
-        for f in &extracted {
-            crate::path_safety::verify_contained(canonical, &f.dest_path).with_context(|| {
+        extracted.retain(|f| {
+            match crate::path_safety::verify_contained(canonical, &f.dest_path).with_context(|| {
                 format!("tar shard entry {:?} escapes destination root", f.dest_path)
-            })?;
-        }
+            }) {
+                Ok(()) => true,
+                Err(err) => {
+                    refused.push((f.rel.clone(), err));
+                    false
+                }
+            }
+        });
     } else {
         log::warn!(
             "write_tar_shard_payload at '{}' has no canonical root; \
@@ -672,7 +1128,7 @@ fn write_tar_shard_payload(
     // the helper would otherwise apply. Permissions are best-effort
     // either way (matches the historical FsTransferSink policy).
     if config.preserve_times {
-        restamp_local_tar_mtimes(src_root, &mut extracted);
+        restamp_local_tar_mtimes(src_root, renamed_sources, &mut extracted);
     } else {
         for f in &mut extracted {
             f.mtime = None;
@@ -686,51 +1142,9 @@ fn write_tar_shard_payload(
     // policy as `tar_safety::write_extracted_file` but inlined so we
     // can return per-file byte counts for the SinkOutcome.
     if probe.is_none() {
-        let results: Vec<Result<u64>> = extracted
-            .into_par_iter()
-            .map(|f: ExtractedFile| -> Result<u64> {
-                if let Some(parent) = f.dest_path.parent() {
-                    std::fs::create_dir_all(parent)
-                        .with_context(|| format!("create dir {}", parent.display()))?;
-                }
-                crate::windows_metadata::prepare_destination(
-                    &f.dest_path,
-                    f.windows_metadata.as_ref(),
-                )?;
-                std::fs::write(&f.dest_path, &f.contents)
-                    .with_context(|| format!("write {}", f.dest_path.display()))?;
-                let windows_bytes = crate::windows_metadata::replace_streams(
-                    &f.dest_path,
-                    f.windows_metadata.as_ref(),
-                )?;
-                if let Some(ft) = f.mtime {
-                    if let Err(e) = filetime::set_file_mtime(&f.dest_path, ft) {
-                        log::warn!("set mtime on {}: {}", f.dest_path.display(), e);
-                    }
-                }
-                #[cfg(unix)]
-                if let Some(perms) = f.permissions {
-                    use std::os::unix::fs::PermissionsExt;
-                    if let Err(e) = std::fs::set_permissions(
-                        &f.dest_path,
-                        std::fs::Permissions::from_mode(perms),
-                    ) {
-                        log::warn!("set permissions on {}: {}", f.dest_path.display(), e);
-                    }
-                }
-                crate::windows_metadata::apply_attributes(
-                    &f.dest_path,
-                    f.windows_metadata.as_ref(),
-                )?;
-                Ok(f.size.saturating_add(windows_bytes))
-            })
-            .collect();
-        let mut files_written = 0usize;
-        let mut bytes_written = 0u64;
-        for result in results {
-            bytes_written += result?;
-            files_written += 1;
-        }
+        let results: Vec<Result<u64>> = extracted.par_iter().map(write_shard_member).collect();
+        let (files_written, bytes_written) =
+            settle_shard_members(&extracted, results, refused, config.fsync)?;
         return Ok(SinkOutcome {
             files_written,
             bytes_written,
@@ -747,8 +1161,8 @@ fn write_tar_shard_payload(
     );
     let members_started = probe.map(|_| std::time::Instant::now());
     let results: Vec<Result<(u64, Option<MemberSample>)>> = extracted
-        .into_par_iter()
-        .map(|f: ExtractedFile| -> Result<(u64, Option<MemberSample>)> {
+        .par_iter()
+        .map(|f: &ExtractedFile| -> Result<(u64, Option<MemberSample>)> {
             use std::io::Write as _;
 
             let total_started = std::time::Instant::now();
@@ -763,6 +1177,8 @@ fn write_tar_shard_payload(
                 &f.dest_path,
                 f.windows_metadata.as_ref(),
             )?;
+            crate::fs_capability::detach_hard_link(&f.dest_path)
+                .with_context(|| format!("detach {}", f.dest_path.display()))?;
 
             let open_started = std::time::Instant::now();
             let mut file = std::fs::File::create(&f.dest_path)
@@ -786,6 +1202,7 @@ fn write_tar_shard_payload(
                     log::warn!("set mtime on {}: {}", f.dest_path.display(), e);
                 }
             }
+            crate::fs_capability::apply_creation_time(&f.dest_path, f.crtime_seconds);
             #[cfg(unix)]
             if let Some(perms) = f.permissions {
                 use std::os::unix::fs::PermissionsExt;
@@ -805,17 +1222,20 @@ fn write_tar_shard_payload(
         .collect();
     let member_parallel_wall = members_started.map(|started| started.elapsed());
 
-    let mut files_written = 0usize;
-    let mut bytes_written = 0u64;
     let mut member_timings = MemberTimingReport::default();
-    for r in results {
-        let (bytes, sample) = r?;
-        bytes_written += bytes;
-        files_written += 1;
-        if let Some((mkdir, open, write, close, metadata, total)) = sample {
-            member_timings.record(mkdir, open, write, close, metadata, total);
-        }
-    }
+    let results: Vec<Result<u64>> = results
+        .into_iter()
+        .map(|r| {
+            r.map(|(bytes, sample)| {
+                if let Some((mkdir, open, write, close, metadata, total)) = sample {
+                    member_timings.record(mkdir, open, write, close, metadata, total);
+                }
+                bytes
+            })
+        })
+        .collect();
+    let (files_written, bytes_written) =
+        settle_shard_members(&extracted, results, refused, config.fsync)?;
 
     if let Some((probe, shard_id, started, blocking_pool_wait)) = probe {
         probe.note_shard_sink(
@@ -838,6 +1258,379 @@ fn write_tar_shard_payload(
     })
 }
 
+/// Write one extracted tar-shard member: parent dirs, bytes, Windows
+/// streams, then best-effort mtime / permissions (same policy as
+/// `tar_safety::write_extracted_file`, returning the byte count for the
+/// `SinkOutcome`).
+fn write_shard_member(f: &super::tar_safety::ExtractedFile) -> Result<u64> {
+    write_member(f, true)
+}
+
+/// [`write_shard_member`], with the parent-directory creation left to
+/// a caller that already ensured it (`ensure_parent: false`).
+fn write_member(f: &super::tar_safety::ExtractedFile, ensure_parent: bool) -> Result<u64> {
+    if let Some(parent) = f.dest_path.parent().filter(|_| ensure_parent) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("create dir {}", parent.display()))?;
+    }
+    crate::windows_metadata::prepare_destination(&f.dest_path, f.windows_metadata.as_ref())?;
+    crate::fs_capability::detach_hard_link(&f.dest_path)
+        .with_context(|| format!("detach {}", f.dest_path.display()))?;
+    std::fs::write(&f.dest_path, &f.contents)
+        .with_context(|| format!("write {}", f.dest_path.display()))?;
+    let windows_bytes =
+        crate::windows_metadata::replace_streams(&f.dest_path, f.windows_metadata.as_ref())?;
+    if let Some(ft) = f.mtime {
+        if let Err(e) = filetime::set_file_mtime(&f.dest_path, ft) {
+            log::warn!("set mtime on {}: {}", f.dest_path.display(), e);
+        }
+    }
+    crate::fs_capability::apply_creation_time(&f.dest_path, f.crtime_seconds);
+    #[cfg(unix)]
+    if let Some(perms) = f.permissions {
+        use std::os::unix::fs::PermissionsExt;
+        if let Err(e) =
+            std::fs::set_permissions(&f.dest_path, std::fs::Permissions::from_mode(perms))
+        {
+            log::warn!("set permissions on {}: {}", f.dest_path.display(), e);
+        }
+    }
+    crate::windows_metadata::apply_attributes(&f.dest_path, f.windows_metadata.as_ref())?;
+    Ok(f.size.saturating_add(windows_bytes))
+}
+
+/// Settle a shard's parallel member writes. Every member has already
+/// been attempted, so one bad member never costs the others; each
+/// failed member is retried once on its own (a single-file write, as if
+/// it had travelled as a `File` record). Members that still fail — and
+/// members `refused` before writing — fail the shard, the first one
+/// named structurally ([`FaultedPath`]) so the session error reports
+/// the file rather than "tar shard". Returns `(files, bytes)` written.
+///
+/// [`FaultedPath`]: crate::remote::transfer::faulted_path::FaultedPath
+fn settle_shard_members(
+    extracted: &[super::tar_safety::ExtractedFile],
+    results: Vec<Result<u64>>,
+    refused: Vec<(String, eyre::Report)>,
+    fsync: bool,
+) -> Result<(usize, u64)> {
+    let mut failed = refused;
+    let (files_written, bytes_written) =
+        retry_failed_members("tar shard", extracted, results, fsync, &mut failed);
+    fail_on_member_failures("tar shard", files_written, bytes_written, failed)
+}
+
+/// The retry half of [`settle_shard_members`]: count the members that
+/// landed, retrying each failed one once on its own, and push the ones
+/// that still fail onto `failed`. Under `fsync` a member counts only
+/// once it is synced; a failed sync fails the member without a retry.
+fn retry_failed_members(
+    what: &str,
+    extracted: &[super::tar_safety::ExtractedFile],
+    results: Vec<Result<u64>>,
+    fsync: bool,
+    failed: &mut Vec<(String, eyre::Report)>,
+) -> (usize, u64) {
+    let mut files_written = 0usize;
+    let mut bytes_written = 0u64;
+    for (f, result) in extracted.iter().zip(results) {
+        let bytes = match result {
+            Ok(bytes) => bytes,
+            Err(first) => match write_shard_member(f) {
+                Ok(bytes) => {
+                    log::warn!(
+                        "{what} member {} failed ({first:#}); landed on its own retry",
+                        f.rel
+                    );
+                    bytes
+                }
+                Err(err) => {
+                    failed.push((f.rel.clone(), err));
+                    continue;
+                }
+            },
+        };
+        if fsync {
+            if let Err(err) = sync_landed_file(&f.dest_path) {
+                failed.push((f.rel.clone(), err));
+                continue;
+            }
+        }
+        bytes_written += bytes;
+        files_written += 1;
+    }
+    (files_written, bytes_written)
+}
+
+/// `sync_all` a file already written and closed. Unix syncs through a
+/// read handle (the member's mode may already forbid writing); Windows
+/// needs a write handle to flush.
+fn sync_landed_file(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    let file = std::fs::File::open(path);
+    #[cfg(not(unix))]
+    let file = std::fs::OpenOptions::new().write(true).open(path);
+    file.and_then(|file| file.sync_all())
+        .with_context(|| format!("syncing {}", path.display()))?;
+    log::debug!("synced {}", path.display());
+    Ok(())
+}
+
+/// The naming half of [`settle_shard_members`]: the first failed member
+/// fails the record under its own path; the rest are logged.
+fn fail_on_member_failures(
+    what: &str,
+    files_written: usize,
+    bytes_written: u64,
+    failed: Vec<(String, eyre::Report)>,
+) -> Result<(usize, u64)> {
+    let mut failed = failed.into_iter();
+    let Some((rel, err)) = failed.next() else {
+        return Ok((files_written, bytes_written));
+    };
+    for (other, other_err) in failed {
+        log::warn!("{what} member {other} failed: {other_err:#}");
+    }
+    Err(err
+        .wrap_err(format!(
+            "{what} member {rel} failed ({files_written} other member(s) landed)"
+        ))
+        .wrap_err(crate::remote::transfer::faulted_path::FaultedPath(rel)))
+}
+
+/// Members a tar-stream worker buffers before writing them in parallel.
+/// Bounds the receiver's memory to a batch of tiny files however long
+/// the archive runs.
+const TAR_STREAM_WRITE_BATCH: usize = 256;
+
+/// Unpack a `TAR_STREAM` archive as it arrives: members are validated by
+/// the shared tar-safety walk, written in parallel batches, and settled
+/// like a shard's (one retry each; the first that still fails names the
+/// record's fault). Reads `reader` through the record's terminator so
+/// the wire stays aligned for the next record. Each member is written to
+/// a staged sibling ([`staged_member_path`]) and recorded in `staged`;
+/// the caller commits or discards them once the record's digest is
+/// known.
+fn unpack_tar_stream(
+    mut reader: impl std::io::Read,
+    headers: Vec<FileHeader>,
+    dst_root: &Path,
+    canonical_dst_root: Option<&Path>,
+    config: &FsSinkConfig,
+    staged: &mut Vec<StagedMember>,
+) -> Result<SinkOutcome> {
+    use super::tar_safety::{safe_extract_tar_stream, ExtractedFile, TarShardExtractOptions};
+
+    fn write_batch(
+        batch: &mut Vec<ExtractedFile>,
+        targets: &mut Vec<PathBuf>,
+        fsync: bool,
+        landed: &mut (usize, u64),
+        staged: &mut Vec<StagedMember>,
+        failed: &mut Vec<(String, eyre::Report)>,
+    ) {
+        use rayon::prelude::*;
+        use std::collections::HashSet;
+        let results: Vec<Result<u64>> = batch.par_iter().map(write_shard_member).collect();
+        let failed_before = failed.len();
+        let (files, bytes) = retry_failed_members("tar stream", batch, results, fsync, failed);
+        let failed_now: HashSet<&str> = failed[failed_before..]
+            .iter()
+            .map(|(rel, _)| rel.as_str())
+            .collect();
+        for (f, target) in batch.iter().zip(targets.drain(..)) {
+            if failed_now.contains(f.rel.as_str()) {
+                let _ = std::fs::remove_file(&f.dest_path);
+            } else {
+                staged.push(StagedMember {
+                    path: f.dest_path.clone(),
+                    dest: target,
+                    windows_metadata: f.windows_metadata.clone(),
+                });
+            }
+        }
+        landed.0 += files;
+        landed.1 += bytes;
+        batch.clear();
+    }
+
+    let mut landed = (0usize, 0u64);
+    let mut failed: Vec<(String, eyre::Report)> = Vec::new();
+    let mut batch: Vec<ExtractedFile> = Vec::with_capacity(TAR_STREAM_WRITE_BATCH);
+    let mut targets: Vec<PathBuf> = Vec::with_capacity(TAR_STREAM_WRITE_BATCH);
+    safe_extract_tar_stream(
+        &mut reader,
+        headers,
+        dst_root,
+        &TarShardExtractOptions::default(),
+        |mut f| {
+            if config.dry_run {
+                landed.0 += 1;
+                return Ok(());
+            }
+            // R47-F1, per member (as for shards).
+            if let Some(canonical) = canonical_dst_root {
+                if let Err(err) = crate::path_safety::verify_contained(canonical, &f.dest_path)
+                    .with_context(|| {
+                        format!(
+                            "tar stream entry {:?} escapes destination root",
+                            f.dest_path
+                        )
+                    })
+                {
+                    failed.push((f.rel.clone(), err));
+                    return Ok(());
+                }
+            }
+            if !config.preserve_times {
+                f.mtime = None;
+            }
+            let staged_path = staged_member_path(&f.dest_path);
+            targets.push(std::mem::replace(&mut f.dest_path, staged_path));
+            batch.push(f);
+            if batch.len() >= TAR_STREAM_WRITE_BATCH {
+                write_batch(
+                    &mut batch,
+                    &mut targets,
+                    config.fsync,
+                    &mut landed,
+                    staged,
+                    &mut failed,
+                );
+            }
+            Ok(())
+        },
+    )?;
+    write_batch(
+        &mut batch,
+        &mut targets,
+        config.fsync,
+        &mut landed,
+        staged,
+        &mut failed,
+    );
+    std::io::copy(&mut reader, &mut std::io::sink()).context("draining tar stream")?;
+    let (files_written, bytes_written) =
+        fail_on_member_failures("tar stream", landed.0, landed.1, failed)?;
+    Ok(SinkOutcome {
+        files_written,
+        bytes_written,
+    })
+}
+
+/// A tar-stream member written to its staged sibling, waiting on the
+/// record's digest.
+struct StagedMember {
+    path: PathBuf,
+    dest: PathBuf,
+    windows_metadata: Option<crate::generated::WindowsFileMetadata>,
+}
+
+/// Where a tar-stream member waits for its record's digest: a hidden
+/// `.NAME.blit-stream` sibling of `dest`, so the final rename stays on
+/// one filesystem.
+fn staged_member_path(dest: &Path) -> PathBuf {
+    let name = dest
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    dest.with_file_name(format!(".{name}.blit-stream"))
+}
+
+/// Rename every staged tar-stream member over its destination. The first
+/// rename that fails fails the record; the members not yet renamed are
+/// removed with it.
+fn commit_staged_members(staged: &[StagedMember]) -> Result<()> {
+    for (i, member) in staged.iter().enumerate() {
+        crate::windows_metadata::prepare_destination(
+            &member.dest,
+            member.windows_metadata.as_ref(),
+        )
+        .and_then(|()| {
+            std::fs::rename(&member.path, &member.dest)
+                .with_context(|| format!("renaming into {}", member.dest.display()))
+        })
+        .inspect_err(|_| discard_staged_members(&staged[i..]))?;
+    }
+    Ok(())
+}
+
+/// Remove staged tar-stream members whose record failed.
+fn discard_staged_members(staged: &[StagedMember]) {
+    for member in staged {
+        if let Err(e) = std::fs::remove_file(&member.path) {
+            log::warn!(
+                "removing {} after a failed tar stream: {e}",
+                member.path.display()
+            );
+        }
+    }
+}
+
+/// Blocking [`std::io::Read`] over archive chunks an async task pumps
+/// off the wire, so `tar::Archive` can run on a blocking thread while
+/// the bytes are still arriving.
+pub(super) struct ChunkChannelReader {
+    rx: tokio::sync::mpsc::Receiver<Vec<u8>>,
+    chunk: Vec<u8>,
+    pos: usize,
+}
+
+impl ChunkChannelReader {
+    pub(super) fn new(rx: tokio::sync::mpsc::Receiver<Vec<u8>>) -> Self {
+        Self {
+            rx,
+            chunk: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+/// Feed `reader` into a [`ChunkChannelReader`]'s channel until EOF. A
+/// blocking side that hung up early stops the pump quietly: its own
+/// error is the story.
+pub(super) async fn pump_chunks(
+    reader: &mut (dyn tokio::io::AsyncRead + Unpin + Send),
+    tx: tokio::sync::mpsc::Sender<Vec<u8>>,
+    what: &str,
+) -> Result<()> {
+    use crate::remote::transfer::data_plane::RECEIVE_CHUNK_SIZE;
+    use tokio::io::AsyncReadExt as _;
+
+    loop {
+        let mut chunk = vec![0u8; RECEIVE_CHUNK_SIZE];
+        let n = reader
+            .read(&mut chunk)
+            .await
+            .with_context(|| format!("reading {what}"))?;
+        if n == 0 {
+            return Ok(());
+        }
+        chunk.truncate(n);
+        if tx.send(chunk).await.is_err() {
+            return Ok(());
+        }
+    }
+}
+
+impl std::io::Read for ChunkChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.pos == self.chunk.len() {
+            match self.rx.blocking_recv() {
+                Some(chunk) => {
+                    self.chunk = chunk;
+                    self.pos = 0;
+                }
+                None => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.chunk.len() - self.pos);
+        buf[..n].copy_from_slice(&self.chunk[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
 /// Resume protocol: overwrite a block of an existing file at the given offset.
 async fn write_file_block_payload(
     dst_root: &Path,
@@ -860,7 +1653,10 @@ async fn write_file_block_payload(
     let bytes_len = bytes.len() as u64;
     // Resume blocks patch existing files at offset; we want to create
     // if missing but never truncate (subsequent block records share
-    // the file).
+    // the file). A file sharing its inode with other names is copied
+    // apart first, so the patch cannot reach them.
+    crate::fs_capability::unshare_hard_link(&dst)
+        .with_context(|| format!("unsharing {}", dst.display()))?;
     let mut file = tokio::fs::OpenOptions::new()
         .write(true)
         .create(true)
@@ -915,6 +1711,10 @@ async fn write_file_block_complete(
             .with_context(|| format!("validating block-complete path {:?}", relative_path))?,
     };
     crate::windows_metadata::prepare_destination(&dst, windows_metadata.as_ref())?;
+    // Zero blocks may have been sent (content already equal), so the
+    // truncate and stamps below can be the first touch of a shared file.
+    crate::fs_capability::unshare_hard_link(&dst)
+        .with_context(|| format!("unsharing {}", dst.display()))?;
     {
         let file = tokio::fs::OpenOptions::new()
             .write(true)
@@ -1026,6 +1826,17 @@ impl<P: Probe> TransferSink for DataPlaneSink<P> {
                     bytes_written: bytes,
                 })
             }
+            PreparedPayload::TarStream { headers } => {
+                let bytes: u64 = headers.iter().map(|header| header.size).sum();
+                session
+                    .send_tar_stream(self.source.clone(), &headers)
+                    .await
+                    .context("sending tar stream")?;
+                Ok(SinkOutcome {
+                    files_written: headers.len(),
+                    bytes_written: bytes,
+                })
+            }
             // Resume payloads can't be relayed without a reverse-resume
             // protocol on the next hop. Reject explicitly.
             PreparedPayload::FileBlock { .. } | PreparedPayload::FileBlockComplete { .. } => {
@@ -1042,6 +1853,7 @@ impl<P: Probe> TransferSink for DataPlaneSink<P> {
                 header,
                 block_size,
                 dest_hashes,
+                hash_key,
             } => {
                 use crate::remote::transfer::resume_diff::{ResumeBlockDiff, ResumeDiffEvent};
                 let path = header.relative_path.clone();
@@ -1058,6 +1870,7 @@ impl<P: Probe> TransferSink for DataPlaneSink<P> {
                         dest_hashes,
                     )
                     .await?
+                    .with_hash_key(hash_key)
                     .with_keepalive(
                         crate::remote::transfer::stall_guard::TRANSFER_STALL_TIMEOUT / 3,
                     );
@@ -1124,6 +1937,24 @@ impl<P: Probe> TransferSink for DataPlaneSink<P> {
         })
     }
 
+    /// Relay case: an inbound tar stream forwards to the next hop as one.
+    async fn write_tar_stream(
+        &self,
+        headers: Vec<FileHeader>,
+        reader: &mut (dyn tokio::io::AsyncRead + Unpin + Send),
+    ) -> Result<SinkOutcome> {
+        let bytes: u64 = headers.iter().map(|header| header.size).sum();
+        let mut session = self.session.lock().await;
+        session
+            .send_tar_stream_from_reader(&headers, reader)
+            .await
+            .context("relaying tar stream")?;
+        Ok(SinkOutcome {
+            files_written: headers.len(),
+            bytes_written: bytes,
+        })
+    }
+
     async fn finish(&self) -> Result<()> {
         let mut session = self.session.lock().await;
         session.finish().await
@@ -1184,6 +2015,10 @@ impl TransferSink for NullSink {
                 files_written: 0,
                 bytes_written: bytes.len() as u64,
             }),
+            PreparedPayload::TarStream { headers } => Ok(SinkOutcome {
+                files_written: headers.len(),
+                bytes_written: headers.iter().map(|header| header.size).sum(),
+            }),
             PreparedPayload::FileBlockComplete { .. } => Ok(SinkOutcome::default()),
             // Send-side composite (otp-7b); the receive path this sink
             // benchmarks never produces it.
@@ -1224,6 +2059,21 @@ impl TransferSink for NullSink {
         })
     }
 
+    async fn write_tar_stream(
+        &self,
+        headers: Vec<FileHeader>,
+        reader: &mut (dyn tokio::io::AsyncRead + Unpin + Send),
+    ) -> Result<SinkOutcome> {
+        // Same --null drain policy as write_file_stream: no progress.
+        let n = tokio::io::copy(reader, &mut tokio::io::sink())
+            .await
+            .context("draining tar stream (null sink)")?;
+        Ok(SinkOutcome {
+            files_written: headers.len(),
+            bytes_written: n,
+        })
+    }
+
     fn root(&self) -> &Path {
         &self.label
     }
@@ -1242,6 +2092,10 @@ mod tests {
             permissions: 0o644,
             checksum: Vec::new(),
             windows_metadata: None,
+            crtime_seconds: None,
+            directory: false,
+            owner: None,
+            xattrs: Vec::new(),
         }
     }
 
@@ -1257,12 +2111,66 @@ mod tests {
         let dst = tmp.path().join("dst.bin");
         std::fs::write(&src, b"root payload").unwrap();
         let header = make_file_header("", b"root payload".len() as u64);
-        let outcome =
-            write_file_payload(&src, &dst, None, &header, &FsSinkConfig::default()).unwrap();
+        let outcome = write_file_payload(
+            &src,
+            &dst,
+            None,
+            None,
+            &header,
+            &FsSinkConfig::default(),
+            None,
+            None,
+        )
+        .unwrap();
         assert_eq!(outcome.files_written, 1);
         assert_eq!(std::fs::read(&dst).unwrap(), b"root payload");
     }
 
+    #[test]
+    fn checksum_copy_commits_only_verified_bytes() {
+        let tmp = tempdir().unwrap();
+        let (src, dst) = (tmp.path().join("src"), tmp.path().join("dst"));
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::create_dir_all(&dst).unwrap();
+        std::fs::write(src.join("good.bin"), b"verified bytes").unwrap();
+        std::fs::write(src.join("bad.bin"), b"corrupted bytes").unwrap();
+        let config = FsSinkConfig {
+            checksum: Some(ChecksumType::Blake3),
+            ..FsSinkConfig::default()
+        };
+        let copy = |rel: &str, size: usize| {
+            write_file_payload(
+                &src,
+                &dst,
+                None,
+                None,
+                &make_file_header(rel, size as u64),
+                &config,
+                None,
+                None,
+            )
+        };
+
+        copy("good.bin", 14).unwrap();
+        assert_eq!(
+            std::fs::read(dst.join("good.bin")).unwrap(),
+            b"verified bytes"
+        );
+
+        CORRUPT_VERIFY_STAGE.with(|hook| hook.set(true));
+        let err = copy("bad.bin", 15).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("post-copy verification failed"),
+            "{err:#}"
+        );
+        assert!(!dst.join("bad.bin").exists(), "mismatch must not commit");
+        let leftovers: Vec<_> = std::fs::read_dir(&dst)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(leftovers, vec![std::ffi::OsString::from("good.bin")]);
+    }
+
     #[test]
     fn source_mtime_keeps_subsecond_precision_over_wire_fallback() {
         let tmp = tempdir().unwrap();
@@ -1302,6 +2210,11 @@ mod tests {
                 checksum: None,
                 resume: false,
                 compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
             },
         );
 
@@ -1316,6 +2229,46 @@ mod tests {
         assert_eq!(std::fs::read(dst.join("file.txt")).unwrap(), content);
     }
 
+    /// `fsync` syncs what each wire receive path lands, including a
+    /// small-file member whose mode already forbids writing.
+    #[tokio::test]
+    async fn fs_sink_fsync_receives_land_synced() {
+        let tmp = tempdir().unwrap();
+        let dst = tmp.path().join("dst");
+        std::fs::create_dir_all(&dst).unwrap();
+        let sink = FsTransferSink::new(
+            PathBuf::new(),
+            dst.clone(),
+            FsSinkConfig {
+                fsync: true,
+                ..FsSinkConfig::default()
+            },
+        );
+
+        let header = make_file_header("streamed.txt", 6);
+        let mut reader: &[u8] = b"stream";
+        let outcome = sink.write_file_stream(&header, &mut reader).await.unwrap();
+        assert_eq!(outcome.files_written, 1);
+
+        let mut readonly = make_file_header("sub/readonly.txt", 4);
+        readonly.permissions = 0o444;
+        let outcome = sink
+            .write_small_files(vec![
+                (readonly, b"ro!!".to_vec()),
+                (make_file_header("sub/plain.txt", 5), b"plain".to_vec()),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(outcome.files_written, 2);
+
+        assert_eq!(std::fs::read(dst.join("streamed.txt")).unwrap(), b"stream");
+        assert_eq!(
+            std::fs::read(dst.join("sub/readonly.txt")).unwrap(),
+            b"ro!!"
+        );
+        assert_eq!(std::fs::read(dst.join("sub/plain.txt")).unwrap(), b"plain");
+    }
+
     #[tokio::test]
     async fn fs_sink_dry_run_does_not_write() {
         let tmp = tempdir().unwrap();
@@ -1335,6 +2288,11 @@ mod tests {
                 checksum: None,
                 resume: false,
                 compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
             },
         );
 
@@ -1372,6 +2330,11 @@ mod tests {
                 checksum: None,
                 resume: false,
                 compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
             },
         );
 
@@ -1409,6 +2372,11 @@ mod tests {
                 checksum: None,
                 resume: false,
                 compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
             },
         );
 
@@ -1445,6 +2413,11 @@ mod tests {
                 checksum: None,
                 resume: false,
                 compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
             },
         );
 
@@ -1502,6 +2475,11 @@ mod tests {
                 checksum: None,
                 resume: false,
                 compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
             },
         );
 
@@ -1587,6 +2565,11 @@ mod tests {
                 checksum: None,
                 resume: false,
                 compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
             },
         );
 
@@ -1660,6 +2643,11 @@ mod tests {
                 checksum: None,
                 resume: false,
                 compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
             },
         );
         let header = make_file_header(rel, 4);
@@ -1734,6 +2722,11 @@ mod tests {
                 checksum: None,
                 resume: false,
                 compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
             },
         );
 
@@ -1770,6 +2763,11 @@ mod tests {
                 checksum: None,
                 resume: false,
                 compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
             },
         );
 
@@ -1818,6 +2816,11 @@ mod tests {
                 checksum: None,
                 resume: false,
                 compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
             },
         );
 
@@ -1878,6 +2881,11 @@ mod tests {
                 checksum: None,
                 resume: false,
                 compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
             },
         );
 
@@ -1949,6 +2957,11 @@ mod tests {
                 checksum: None,
                 resume: false,
                 compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
             },
         );
 
@@ -1971,6 +2984,120 @@ mod tests {
         );
     }
 
+    /// Tar of regular-file members, for the shard-settle pins.
+    fn tar_of(members: &[(&str, &[u8])]) -> Vec<u8> {
+        use tar::{Builder, EntryType, Header as TarHeader};
+
+        let mut tar_buf: Vec<u8> = Vec::new();
+        {
+            let mut builder = Builder::new(&mut tar_buf);
+            for (path, content) in members {
+                let mut hdr = TarHeader::new_gnu();
+                hdr.set_entry_type(EntryType::Regular);
+                hdr.set_size(content.len() as u64);
+                hdr.set_mode(0o644);
+                hdr.set_path(path).unwrap();
+                hdr.set_cksum();
+                builder.append(&hdr, *content).unwrap();
+            }
+            builder.finish().unwrap();
+        }
+        tar_buf
+    }
+
+    fn shard_test_sink(src_root: PathBuf, dst: PathBuf) -> FsTransferSink {
+        FsTransferSink::new(
+            src_root,
+            dst,
+            FsSinkConfig {
+                preserve_times: false,
+                dry_run: false,
+                checksum: None,
+                resume: false,
+                compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
+            },
+        )
+    }
+
+    /// One member that cannot be written (a non-empty directory sits at
+    /// its path, so its solo retry fails too) fails the shard naming
+    /// that member structurally, while every other member still lands.
+    #[tokio::test]
+    async fn tar_shard_member_failure_lands_the_rest_and_names_the_member() {
+        let tmp = tempdir().unwrap();
+        let dst = tmp.path().join("dst");
+        std::fs::create_dir_all(dst.join("bad.txt/occupied")).unwrap();
+        let members: [(&str, &[u8]); 3] = [
+            ("ok-1.txt", b"first"),
+            ("bad.txt", b"blocked"),
+            ("nested/ok-2.txt", b"second"),
+        ];
+        let headers = members
+            .iter()
+            .map(|(path, content)| make_file_header(path, content.len() as u64))
+            .collect();
+        let sink = shard_test_sink(PathBuf::new(), dst.clone());
+
+        let err = sink
+            .write_payload(PreparedPayload::TarShard {
+                headers,
+                data: tar_of(&members),
+            })
+            .await
+            .expect_err("the blocked member fails the shard");
+        let faulted = err
+            .downcast_ref::<crate::remote::transfer::FaultedPath>()
+            .expect("failure names the member");
+        assert_eq!(faulted.0, "bad.txt");
+        assert_eq!(std::fs::read(dst.join("ok-1.txt")).unwrap(), b"first");
+        assert_eq!(
+            std::fs::read(dst.join("nested/ok-2.txt")).unwrap(),
+            b"second"
+        );
+        assert!(dst.join("bad.txt/occupied").is_dir());
+    }
+
+    /// A member refused for escaping through a destination symlink is
+    /// never written, and no longer takes its shard-mates with it.
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn tar_shard_escaping_member_is_refused_alone() {
+        use std::os::unix::fs::symlink;
+
+        let tmp = tempdir().unwrap();
+        let dst = tmp.path().join("dst");
+        let outside = tmp.path().join("outside");
+        std::fs::create_dir_all(&dst).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        symlink(&outside, dst.join("link")).unwrap();
+        let members: [(&str, &[u8]); 2] = [("link/victim.txt", b"escape"), ("fine.txt", b"ok")];
+        let headers = members
+            .iter()
+            .map(|(path, content)| make_file_header(path, content.len() as u64))
+            .collect();
+        let sink = shard_test_sink(tmp.path().join("src"), dst.clone());
+
+        let err = sink
+            .write_payload(PreparedPayload::TarShard {
+                headers,
+                data: tar_of(&members),
+            })
+            .await
+            .expect_err("the escaping member fails the shard");
+        assert!(format!("{err:#}").contains("escapes destination root"));
+        let faulted = err
+            .downcast_ref::<crate::remote::transfer::FaultedPath>()
+            .expect("failure names the member");
+        assert_eq!(faulted.0, "link/victim.txt");
+        assert!(!outside.join("victim.txt").exists());
+        assert_eq!(std::fs::read(dst.join("fine.txt")).unwrap(), b"ok");
+    }
+
     /// c-1b round 2 regression: tar shards land via `write_payload`,
     /// not `write_file_stream`, so the chunk-granular byte hook
     /// inside `receive_stream_double_buffered` never fires for them.
@@ -2023,6 +3150,11 @@ mod tests {
                 checksum: None,
                 resume: false,
                 compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
             },
         )
         .with_byte_progress(sink_progress);
@@ -2070,6 +3202,11 @@ mod tests {
                 checksum: None,
                 resume: false,
                 compare_mode: ComparisonMode::SizeMtime,
+                sparse: SparseMode::Auto,
+                block_size: 0,
+                preallocate: false,
+                reflink_dest: None,
+                fsync: false,
             },
         )
         .with_byte_progress(sink_progress);
@@ -2093,4 +3230,39 @@ mod tests {
             "FileBlock byte progress must equal outcome.bytes_written"
         );
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn writes_replace_a_hard_linked_destination_instead_of_rewriting_it() {
+        let tmp = tempdir().unwrap();
+        let src = tmp.path().join("src");
+        let dst = tmp.path().join("dst");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::create_dir_all(&dst).unwrap();
+        std::fs::write(src.join("file.txt"), b"new content").unwrap();
+        std::fs::write(dst.join("file.txt"), b"old").unwrap();
+        std::fs::write(dst.join("resume.bin"), vec![0u8; 64]).unwrap();
+        // Other names for both destination files, e.g. a dedupe store.
+        let other = tmp.path().join("other.txt");
+        let other_resume = tmp.path().join("other.bin");
+        std::fs::hard_link(dst.join("file.txt"), &other).unwrap();
+        std::fs::hard_link(dst.join("resume.bin"), &other_resume).unwrap();
+
+        let sink = shard_test_sink(src, dst.clone());
+        sink.write_payload(PreparedPayload::File(make_file_header("file.txt", 11)))
+            .await
+            .unwrap();
+        sink.write_payload(PreparedPayload::FileBlock {
+            relative_path: "resume.bin".to_string(),
+            offset: 16,
+            bytes: vec![0xAB; 32],
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(std::fs::read(dst.join("file.txt")).unwrap(), b"new content");
+        assert_eq!(std::fs::read(&other).unwrap(), b"old");
+        assert_eq!(std::fs::read(dst.join("resume.bin")).unwrap()[16], 0xAB);
+        assert_eq!(std::fs::read(&other_resume).unwrap(), vec![0u8; 64]);
+    }
 }
@@ -13,15 +13,20 @@
 //! design-3 added [`dial_data_plane`]: the client-side dial (bounded
 //! connect + policy + bounded handshake write) lives here too, so
 //! both data-plane connect sites share one owner and neither can
-//! regress to an unbounded `TcpStream::connect`.
+//! regress to an unbounded `TcpStream::connect`. The dial also owns
+//! scoped IPv6 (`[fe80::1%eth0]:port` keeps its zone), the optional
+//! outgoing interface ([`set_data_plane_interface`]) and the optional
+//! source address ([`set_local_address`]).
 
 use std::io;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr, SocketAddrV6};
+use std::sync::OnceLock;
 use std::time::Duration;
 
 use eyre::Context as _;
 use socket2::{SockRef, TcpKeepalive};
 use tokio::io::{AsyncWrite, AsyncWriteExt};
-use tokio::net::TcpStream;
+use tokio::net::{TcpSocket, TcpStream};
 
 /// Bounded wait for a data-plane accept (w1-4: one shared pair — this
 /// and [`DATA_PLANE_TOKEN_TIMEOUT`] — replacing three per-file
@@ -55,6 +60,274 @@ pub const TCP_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(10);
 /// Unanswered probes before the connection is declared dead.
 pub const TCP_KEEPALIVE_RETRIES: u32 = 5;
 
+/// Operator overrides for the data-plane socket policy, installed
+/// once per process: by the `blit` CLI from the global
+/// `--tcp-buffer-size`/`--tcp-keepalive` flags and by the daemon from
+/// the `[daemon]` `tcp_buffer_size`/`tcp_keepalive` config keys.
+/// `None` fields keep the built-in policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DataSocketOverrides {
+    /// Fixed SO_SNDBUF/SO_RCVBUF for every data-plane socket. Wins
+    /// over the dial's ramped size and applies on paths that hold no
+    /// dial (accepts, the pull client), which otherwise run kernel
+    /// defaults.
+    pub tcp_buffer_size: Option<usize>,
+    /// Idle time before the first keepalive probe, replacing
+    /// [`TCP_KEEPALIVE_IDLE`]. For middleboxes that drop idle flows
+    /// sooner than 60 s.
+    pub keepalive_idle: Option<Duration>,
+}
+
+impl DataSocketOverrides {
+    /// Parse the operator-facing spellings shared by the CLI flags and
+    /// the daemon config keys: a size (`4Mi`, `262144`; see
+    /// [`crate::fs_enum::parse_size`]) and a duration (`20s`, `1m`; see
+    /// [`crate::fs_enum::parse_duration`]). Zero values are refused —
+    /// a zero buffer or a zero idle is never what the operator meant.
+    pub fn parse(
+        tcp_buffer_size: Option<&str>,
+        keepalive_idle: Option<&str>,
+    ) -> eyre::Result<Self> {
+        let tcp_buffer_size = tcp_buffer_size
+            .map(|raw| {
+                let bytes = crate::fs_enum::parse_size(raw)
+                    .with_context(|| format!("invalid TCP buffer size '{raw}'"))?;
+                if bytes == 0 {
+                    eyre::bail!("invalid TCP buffer size '{raw}': must be greater than zero");
+                }
+                usize::try_from(bytes)
+                    .with_context(|| format!("TCP buffer size '{raw}' is too large"))
+            })
+            .transpose()?;
+        let keepalive_idle = keepalive_idle
+            .map(|raw| {
+                let idle = crate::fs_enum::parse_duration(raw)
+                    .with_context(|| format!("invalid TCP keepalive '{raw}'"))?;
+                if idle < Duration::from_secs(1) {
+                    eyre::bail!("invalid TCP keepalive '{raw}': must be at least 1s");
+                }
+                Ok(idle)
+            })
+            .transpose()?;
+        Ok(Self {
+            tcp_buffer_size,
+            keepalive_idle,
+        })
+    }
+}
+
+static SOCKET_OVERRIDES: OnceLock<DataSocketOverrides> = OnceLock::new();
+
+/// Install the process-wide socket overrides. Called once at startup,
+/// before any data-plane socket exists; a second call is silently
+/// ignored (same contract as `instrumentation::set_counter_path`).
+pub fn set_data_socket_overrides(overrides: DataSocketOverrides) {
+    let _ = SOCKET_OVERRIDES.set(overrides);
+}
+
+/// The installed overrides, or the all-`None` default.
+pub fn data_socket_overrides() -> DataSocketOverrides {
+    SOCKET_OVERRIDES.get().copied().unwrap_or_default()
+}
+
+static DATA_PLANE_INTERFACE: OnceLock<String> = OnceLock::new();
+
+/// Install the process-wide outgoing interface for data-plane dials
+/// (the `blit` CLI's `--data-plane-interface`). Every dial binds its
+/// socket to the interface, and a link-local IPv6 target written
+/// without a zone (`fe80::1`) takes the interface as its scope. A
+/// second call is silently ignored (same contract as
+/// [`set_data_socket_overrides`]).
+pub fn set_data_plane_interface(name: impl Into<String>) {
+    let _ = DATA_PLANE_INTERFACE.set(name.into());
+}
+
+/// The installed data-plane interface, if any.
+pub fn data_plane_interface() -> Option<&'static str> {
+    DATA_PLANE_INTERFACE.get().map(String::as_str)
+}
+
+static LOCAL_ADDRESS: OnceLock<IpAddr> = OnceLock::new();
+
+/// Install the process-wide source address for outgoing connections
+/// (the `blit` CLI's `--address`). Data-plane dials bind their socket
+/// to it, and [`crate::remote::connect::connect_channel`] binds the
+/// control connection to it. A second call is silently ignored (same
+/// contract as [`set_data_socket_overrides`]).
+pub fn set_local_address(addr: IpAddr) {
+    let _ = LOCAL_ADDRESS.set(addr);
+}
+
+/// The installed source address, if any.
+pub fn local_address() -> Option<IpAddr> {
+    LOCAL_ADDRESS.get().copied()
+}
+
+/// Parse a bracketed, zoned IPv6 dial target (`[fe80::1%eth0]:9031`,
+/// the form [`format_host_port`](crate::remote::endpoint::format_host_port)
+/// renders for a scoped host) keeping its zone as the scope id. std's
+/// parser takes only numeric zones, and the resolver fallback never
+/// sees past the brackets, so a named zone is mapped to its interface
+/// index here. `None` = not a zoned literal; resolve normally.
+pub fn parse_scoped_socket_addr(addr: &str) -> Option<io::Result<SocketAddrV6>> {
+    let (literal, port) = addr.strip_prefix('[')?.split_once("]:")?;
+    let (ip, zone) = literal.split_once('%')?;
+    Some((|| {
+        let ip = ip.parse::<Ipv6Addr>().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid IPv6 address in {addr}"),
+            )
+        })?;
+        let port = port.parse::<u16>().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid port in {addr}"),
+            )
+        })?;
+        Ok(SocketAddrV6::new(ip, port, 0, interface_index(zone)?))
+    })())
+}
+
+/// Interface index for a zone or interface name: numeric zones pass
+/// through; names resolve through `if_nametoindex` where the platform
+/// has it (Windows zones are numeric).
+fn interface_index(name: &str) -> io::Result<u32> {
+    if let Ok(index) = name.parse::<u32>() {
+        return Ok(index);
+    }
+    #[cfg(unix)]
+    {
+        let c_name = std::ffi::CString::new(name).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidInput, "interface name contains NUL")
+        })?;
+        // SAFETY: `c_name` is a valid NUL-terminated string for the
+        // duration of the call; `if_nametoindex` only reads it.
+        let index = unsafe { libc::if_nametoindex(c_name.as_ptr()) };
+        if index == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no network interface named '{name}'"),
+            ));
+        }
+        Ok(index)
+    }
+    #[cfg(not(unix))]
+    {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("interface '{name}': use the numeric interface index on this platform"),
+        ))
+    }
+}
+
+/// Link-local IPv6 (`fe80::/10`) — the one family where a missing
+/// scope id makes the address ambiguous.
+fn is_unicast_link_local(ip: &Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xffc0) == 0xfe80
+}
+
+/// Resolve a data-plane dial target: a zoned literal keeps its scope
+/// ([`parse_scoped_socket_addr`]); anything else goes through the
+/// resolver. With an `interface`, a link-local target that carries no
+/// scope takes the interface's index.
+async fn resolve_dial_target(addr: &str, interface: Option<&str>) -> io::Result<Vec<SocketAddr>> {
+    let mut targets: Vec<SocketAddr> = match parse_scoped_socket_addr(addr) {
+        Some(scoped) => vec![SocketAddr::V6(scoped?)],
+        None => tokio::net::lookup_host(addr).await?.collect(),
+    };
+    if let Some(interface) = interface {
+        for target in &mut targets {
+            if let SocketAddr::V6(v6) = target {
+                if v6.scope_id() == 0 && is_unicast_link_local(v6.ip()) {
+                    v6.set_scope_id(interface_index(interface)?);
+                }
+            }
+        }
+    }
+    Ok(targets)
+}
+
+/// Connect to the first reachable resolved target, binding the socket
+/// to `interface` and to the source address `local` first when set. A
+/// source address only dials targets of its own family.
+async fn connect_dial_target(
+    addr: &str,
+    interface: Option<&str>,
+    local: Option<IpAddr>,
+) -> io::Result<TcpStream> {
+    let mut last_err = None;
+    for target in resolve_dial_target(addr, interface).await? {
+        if local.is_some_and(|local| local.is_ipv4() != target.is_ipv4()) {
+            last_err.get_or_insert_with(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{addr} has no address of the --address family"),
+                )
+            });
+            continue;
+        }
+        let connected = match (interface, local) {
+            (None, None) => TcpStream::connect(target).await,
+            (interface, local) => {
+                let socket = if target.is_ipv4() {
+                    TcpSocket::new_v4()?
+                } else {
+                    TcpSocket::new_v6()?
+                };
+                if let Some(interface) = interface {
+                    bind_socket_to_interface(&socket, interface, target.is_ipv4())?;
+                }
+                if let Some(local) = local {
+                    socket.bind(SocketAddr::new(local, 0))?;
+                }
+                socket.connect(target).await
+            }
+        };
+        match connected {
+            Ok(stream) => return Ok(stream),
+            Err(err) => last_err = Some(err),
+        }
+    }
+    Err(last_err.unwrap_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{addr} resolved to no addresses"),
+        )
+    }))
+}
+
+/// Pin an unconnected socket's egress to `interface`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn bind_socket_to_interface(socket: &TcpSocket, interface: &str, _ipv4: bool) -> io::Result<()> {
+    SockRef::from(socket).bind_device(Some(interface.as_bytes()))
+}
+
+/// Pin an unconnected socket's egress to `interface`.
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn bind_socket_to_interface(socket: &TcpSocket, interface: &str, ipv4: bool) -> io::Result<()> {
+    let index = std::num::NonZeroU32::new(interface_index(interface)?);
+    if ipv4 {
+        SockRef::from(socket).bind_device_by_index_v4(index)
+    } else {
+        SockRef::from(socket).bind_device_by_index_v6(index)
+    }
+}
+
+/// Pin an unconnected socket's egress to `interface`.
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios"
+)))]
+fn bind_socket_to_interface(_socket: &TcpSocket, interface: &str, _ipv4: bool) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("binding data-plane sockets to interface '{interface}' is not supported on this platform"),
+    ))
+}
+
 /// Apply the data-plane socket policy to a connected or accepted
 /// stream, in place (no `into_std`/`from_std` round trip):
 ///
@@ -79,21 +352,35 @@ pub const TCP_KEEPALIVE_RETRIES: u32 = 5;
 ///   where none is (the pull client and the daemon push receiver hold
 ///   no dial).
 ///
+/// Installed [`DataSocketOverrides`] take precedence over both the
+/// keepalive idle constant and `tcp_buffer_size`.
+///
 /// Errors only if `TCP_NODELAY` cannot be set (or the fd/socket
 /// handle is unusable, which the same call surfaces).
 pub fn configure_data_socket(stream: &TcpStream, tcp_buffer_size: Option<usize>) -> io::Result<()> {
+    configure_data_socket_with(stream, tcp_buffer_size, &data_socket_overrides())
+}
+
+/// Override-parameterized core of [`configure_data_socket`], so tests
+/// can pin the override precedence without touching the process-wide
+/// `OnceLock`.
+fn configure_data_socket_with(
+    stream: &TcpStream,
+    tcp_buffer_size: Option<usize>,
+    overrides: &DataSocketOverrides,
+) -> io::Result<()> {
     let socket = SockRef::from(stream);
     socket.set_tcp_nodelay(true)?;
     // `set_tcp_keepalive` also flips SO_KEEPALIVE on, so this is the
     // whole keepalive story in one call.
     let keepalive = TcpKeepalive::new()
-        .with_time(TCP_KEEPALIVE_IDLE)
+        .with_time(overrides.keepalive_idle.unwrap_or(TCP_KEEPALIVE_IDLE))
         .with_interval(TCP_KEEPALIVE_INTERVAL)
         .with_retries(TCP_KEEPALIVE_RETRIES);
     if let Err(e) = socket.set_tcp_keepalive(&keepalive) {
         log::warn!("set TCP keepalive on data-plane socket: {}", e);
     }
-    if let Some(size) = tcp_buffer_size {
+    if let Some(size) = overrides.tcp_buffer_size.or(tcp_buffer_size) {
         if let Err(e) = socket.set_send_buffer_size(size) {
             log::warn!("set TCP send buffer to {} bytes: {}", size, e);
         }
@@ -144,7 +431,8 @@ async fn dial_data_plane_with_timeouts(
     connect_timeout: Duration,
     token_timeout: Duration,
 ) -> eyre::Result<TcpStream> {
-    let mut stream = match tokio::time::timeout(connect_timeout, TcpStream::connect(addr)).await {
+    let connect = connect_dial_target(addr, data_plane_interface(), local_address());
+    let mut stream = match tokio::time::timeout(connect_timeout, connect).await {
         Ok(connected) => connected.with_context(|| format!("connecting data plane {addr}"))?,
         Err(_) => {
             return Err(eyre::Report::new(io::Error::new(
@@ -271,6 +559,55 @@ mod tests {
         );
     }
 
+    /// Operator overrides land on the socket: the override buffer
+    /// beats the dial's size (and applies where the caller passed
+    /// `None`, as the accept paths do), and the keepalive idle is the
+    /// override, not the built-in constant.
+    #[tokio::test]
+    async fn overrides_take_precedence_over_policy_defaults() {
+        let (client, server) = loopback_pair().await;
+        let overrides = DataSocketOverrides {
+            tcp_buffer_size: Some(512 * 1024),
+            keepalive_idle: Some(Duration::from_secs(17)),
+        };
+        configure_data_socket_with(&client, Some(64 * 1024), &overrides).expect("configure");
+        configure_data_socket_with(&server, None, &overrides).expect("configure");
+
+        for stream in [&client, &server] {
+            let sock = SockRef::from(stream);
+            assert!(sock.tcp_nodelay().expect("read nodelay"));
+            assert!(sock.keepalive().expect("read keepalive"));
+            assert!(
+                sock.send_buffer_size().expect("read sndbuf") >= 512 * 1024,
+                "override send buffer must win"
+            );
+            assert!(
+                sock.recv_buffer_size().expect("read rcvbuf") >= 512 * 1024,
+                "override recv buffer must win"
+            );
+            #[cfg(unix)]
+            assert_eq!(
+                sock.tcp_keepalive_time().expect("read keepalive time"),
+                Duration::from_secs(17),
+                "keepalive idle must be the override"
+            );
+        }
+    }
+
+    #[test]
+    fn override_spellings_parse_and_zero_is_refused() {
+        let parsed = DataSocketOverrides::parse(Some("4Mi"), Some("20s")).expect("parse");
+        assert_eq!(parsed.tcp_buffer_size, Some(4 << 20));
+        assert_eq!(parsed.keepalive_idle, Some(Duration::from_secs(20)));
+        assert_eq!(
+            DataSocketOverrides::parse(None, None).expect("parse"),
+            DataSocketOverrides::default()
+        );
+        assert!(DataSocketOverrides::parse(Some("0"), None).is_err());
+        assert!(DataSocketOverrides::parse(None, Some("0s")).is_err());
+        assert!(DataSocketOverrides::parse(Some("lots"), None).is_err());
+    }
+
     // ── design-3: bounded dial ────────────────────────────────────
 
     fn chain_has_timed_out(err: &eyre::Report) -> bool {
@@ -403,4 +740,112 @@ mod tests {
             "None must not touch the recv buffer"
         );
     }
+
+    /// A zoned IPv6 dial target keeps its zone as the scope id: numeric
+    /// zones pass through, unzoned and bracket-less targets fall back to
+    /// the resolver, and a malformed literal is an error, not a lookup.
+    #[test]
+    fn scoped_ipv6_dial_targets_keep_their_zone() {
+        let scoped = parse_scoped_socket_addr("[fe80::1%3]:9031")
+            .expect("zoned literal")
+            .expect("parses");
+        assert_eq!(*scoped.ip(), "fe80::1".parse::<Ipv6Addr>().unwrap());
+        assert_eq!((scoped.port(), scoped.scope_id()), (9031, 3));
+
+        assert!(parse_scoped_socket_addr("[fe80::1]:9031").is_none());
+        assert!(parse_scoped_socket_addr("127.0.0.1:9031").is_none());
+        assert!(parse_scoped_socket_addr("[fe80::1%3]:port")
+            .unwrap()
+            .is_err());
+        assert!(parse_scoped_socket_addr("[nothex%3]:9031")
+            .unwrap()
+            .is_err());
+    }
+
+    /// Named zones map through `if_nametoindex`; an unknown interface
+    /// is refused rather than dialed unscoped.
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn named_zone_resolves_to_the_interface_index() {
+        let lo = interface_index("lo").expect("loopback interface");
+        let scoped = parse_scoped_socket_addr("[fe80::1%lo]:9031")
+            .unwrap()
+            .expect("parses");
+        assert_eq!(scoped.scope_id(), lo);
+        assert!(parse_scoped_socket_addr("[fe80::1%no-such-if0]:9031")
+            .unwrap()
+            .is_err());
+    }
+
+    /// With an interface set, a link-local target without a zone takes
+    /// the interface's index; other targets are left alone.
+    #[tokio::test]
+    async fn interface_scopes_unzoned_link_local_targets() {
+        let targets = resolve_dial_target("[fe80::1]:9031", Some("7"))
+            .await
+            .expect("resolve");
+        assert!(matches!(targets[..], [SocketAddr::V6(v6)] if v6.scope_id() == 7));
+
+        let targets = resolve_dial_target("[::1]:9031", Some("7"))
+            .await
+            .expect("resolve");
+        assert!(matches!(targets[..], [SocketAddr::V6(v6)] if v6.scope_id() == 0));
+    }
+
+    /// End to end through the dial: a zoned loopback literal, rendered
+    /// by the endpoint formatter exactly as a session would, connects
+    /// and delivers the handshake. Skipped where the sandbox has no
+    /// IPv6 loopback.
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn dial_connects_through_a_zoned_literal() {
+        use tokio::io::AsyncReadExt;
+
+        let Ok(listener) = TcpListener::bind("[::1]:0").await else {
+            return;
+        };
+        let port = listener.local_addr().expect("addr").port();
+        let addr = crate::remote::endpoint::format_host_port("::1%lo", port);
+        assert_eq!(addr, format!("[::1%lo]:{port}"));
+
+        let (dialed, accepted) = tokio::join!(dial_data_plane(&addr, b"tok", None), async {
+            let (mut server, _) = listener.accept().await.expect("accept");
+            let mut token = [0u8; 3];
+            server.read_exact(&mut token).await.expect("read token");
+            token
+        });
+        dialed.expect("dial");
+        assert_eq!(&accepted, b"tok");
+    }
+
+    /// `--address`: the dial's socket is bound to the requested source
+    /// address before connecting, so the peer sees it as the origin.
+    /// Skipped where 127.0.0.2 is not a usable loopback address.
+    #[tokio::test]
+    async fn dial_originates_from_the_requested_local_address() {
+        let local: IpAddr = "127.0.0.2".parse().unwrap();
+        if std::net::TcpListener::bind((local, 0)).is_err() {
+            return;
+        }
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr").to_string();
+
+        let (dialed, accepted) = tokio::join!(
+            connect_dial_target(&addr, None, Some(local)),
+            listener.accept()
+        );
+        let dialed = dialed.expect("dial");
+        let (_, peer) = accepted.expect("accept");
+        assert_eq!(dialed.local_addr().expect("local").ip(), local);
+        assert_eq!(peer.ip(), local);
+    }
+
+    /// A source address never dials a target of the other family.
+    #[tokio::test]
+    async fn dial_with_a_local_address_skips_other_family_targets() {
+        let err = connect_dial_target("[::1]:9", None, Some("127.0.0.1".parse().unwrap()))
+            .await
+            .expect_err("v4 source cannot reach a v6 target");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
 }
@@ -1,3 +1,5 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
@@ -6,8 +8,10 @@ use eyre::Result;
 use tokio::fs;
 use tokio::sync::mpsc;
 
+use crate::enumeration::{SortOrder, SymlinkDeref};
 use crate::fs_enum::FileFilter;
 use crate::generated::FileHeader;
+use crate::path_rename::PathRenamer;
 use crate::remote::transfer::abort_on_drop::AbortOnDrop;
 use crate::remote::transfer::payload::{PreparedPayload, TransferPayload};
 
@@ -188,15 +192,171 @@ pub trait TransferSource: Send + Sync {
 
     /// Returns the root path of the source (if applicable).
     fn root(&self) -> &Path;
+
+    /// The on-disk file `open_file(header)` reads, for sources that read
+    /// local files directly. `--checksum-cache` keys digests on it;
+    /// `None` (the default) means the source is never cached.
+    fn local_path(&self, _header: &FileHeader) -> Option<PathBuf> {
+        None
+    }
 }
 
 pub struct FsTransferSource {
     root: PathBuf,
+    deref: SymlinkDeref,
+    /// `--safe-links`: never dereference a symlink that resolves
+    /// outside the root.
+    safe_links: bool,
+    /// `--sort-order`: the order the scan visits each directory in.
+    sort: SortOrder,
+    /// `--skip-locked`: a file another process holds locked is recorded
+    /// unreadable and skipped at the availability check instead of
+    /// failing the transfer.
+    skip_locked: bool,
+    /// `--crtimes`: stamp each header with the file's creation time.
+    crtimes: bool,
+    /// `--super` / `--fake-super`: stamp each header with its owner
+    /// ([`crate::ownership`]), read from the fake-super xattr when
+    /// `fake_super` is set.
+    owner: bool,
+    fake_super: bool,
+    /// `--xattrs`: stamp each header with the extended attributes this
+    /// filter admits ([`crate::xattrs`]).
+    xattrs: Option<crate::xattrs::XattrFilter>,
+    /// `--manifest-checkpoint`: where the scan records and reuses
+    /// directory listings ([`crate::manifest_checkpoint`]).
+    manifest_checkpoint: Option<PathBuf>,
 }
 
 impl FsTransferSource {
     pub fn new(root: PathBuf) -> Self {
-        Self { root }
+        Self {
+            root,
+            deref: SymlinkDeref::None,
+            safe_links: false,
+            sort: SortOrder::None,
+            skip_locked: false,
+            crtimes: false,
+            owner: false,
+            fake_super: false,
+            xattrs: None,
+            manifest_checkpoint: None,
+        }
+    }
+
+    /// Dereference symlinks during the scan (`--copy-links` /
+    /// `--copy-dirlinks`). Reads need nothing extra: a followed entry's
+    /// path runs through the link, so opening it reads the referent.
+    pub fn with_symlink_deref(mut self, deref: SymlinkDeref) -> Self {
+        self.deref = deref;
+        self
+    }
+
+    /// Drop symlinks that resolve outside the root (`--safe-links`)
+    /// rather than following them under `--copy-links` /
+    /// `--copy-dirlinks`.
+    pub fn with_safe_links(mut self, safe_links: bool) -> Self {
+        self.safe_links = safe_links;
+        self
+    }
+
+    /// Scan each directory in `sort` order (`--sort-order name`), so an
+    /// unchanged tree always produces the same manifest.
+    pub fn with_sort_order(mut self, sort: SortOrder) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Skip files another process holds locked (`--skip-locked`):
+    /// [`TransferSource::check_availability`] records a Windows sharing
+    /// or lock violation like an unreadable entry and drops it. A file
+    /// locked after the check still fails its copy.
+    pub fn with_skip_locked(mut self, skip_locked: bool) -> Self {
+        self.skip_locked = skip_locked;
+        self
+    }
+
+    /// Carry each file's creation time in `FileHeader.crtime_seconds`
+    /// (`--crtimes`) where the platform reports one. Off by default: the
+    /// destination applies whatever arrives, so the flag lives here.
+    pub fn with_crtimes(mut self, crtimes: bool) -> Self {
+        self.crtimes = crtimes;
+        self
+    }
+
+    /// Carry each entry's owner and mode in `FileHeader.owner`
+    /// (`--super`). With `fake_super`, an entry's
+    /// [`crate::ownership::FAKE_SUPER_XATTR`] record is carried in place
+    /// of its inode's ownership wherever one exists.
+    pub fn with_owner(mut self, owner: bool, fake_super: bool) -> Self {
+        self.owner = owner;
+        self.fake_super = fake_super;
+        self
+    }
+
+    /// Carry each entry's extended attributes that `filter` admits in
+    /// `FileHeader.xattrs` (`--xattrs`). `None` (the default) reads none.
+    pub fn with_xattrs(mut self, filter: Option<crate::xattrs::XattrFilter>) -> Self {
+        self.xattrs = filter;
+        self
+    }
+
+    /// Checkpoint the scan's directory listings at `path`
+    /// (`--manifest-checkpoint`), resuming from the listings a previous,
+    /// interrupted scan of the same root left there. The caller removes
+    /// the file once the transfer succeeds.
+    pub fn with_manifest_checkpoint(mut self, path: Option<PathBuf>) -> Self {
+        self.manifest_checkpoint = path;
+        self
+    }
+}
+
+impl FsTransferSource {
+    fn stamps(&self) -> HeaderStamps {
+        HeaderStamps {
+            crtimes: self.crtimes,
+            owner: self.owner,
+            fake_super: self.fake_super,
+            xattrs: self.xattrs.clone(),
+        }
+    }
+}
+
+/// Optional metadata the scan stamps on each header.
+#[derive(Clone)]
+struct HeaderStamps {
+    crtimes: bool,
+    owner: bool,
+    fake_super: bool,
+    xattrs: Option<crate::xattrs::XattrFilter>,
+}
+
+impl HeaderStamps {
+    fn owner(
+        &self,
+        path: &Path,
+        metadata: &std::fs::Metadata,
+    ) -> Option<crate::generated::FileOwner> {
+        self.owner
+            .then(|| crate::ownership::read_owner(path, metadata, self.fake_super))
+            .flatten()
+    }
+
+    /// The admitted attributes of `path`. A filesystem without xattr
+    /// support has none; any other failure to read them is logged and
+    /// the entry crosses without them.
+    fn xattrs(&self, path: &Path) -> Vec<crate::generated::Xattr> {
+        let Some(filter) = &self.xattrs else {
+            return Vec::new();
+        };
+        match filter.read(path) {
+            Ok(xattrs) => xattrs,
+            Err(err) if err.kind() == std::io::ErrorKind::Unsupported => Vec::new(),
+            Err(err) => {
+                log::warn!("reading xattrs of {}: {err}", path.display());
+                Vec::new()
+            }
+        }
     }
 }
 
@@ -210,8 +370,13 @@ impl TransferSource for FsTransferSource {
         let (headers, task) = spawn_manifest_task(
             self.root.clone(),
             filter.unwrap_or_default(),
+            self.deref,
+            self.safe_links,
+            self.sort,
             unreadable_paths,
             true,
+            self.stamps(),
+            self.manifest_checkpoint.clone(),
         );
         (headers, SourceScan::new(task))
     }
@@ -224,8 +389,13 @@ impl TransferSource for FsTransferSource {
         let (headers, task) = spawn_manifest_task(
             self.root.clone(),
             filter.unwrap_or_default(),
+            self.deref,
+            self.safe_links,
+            self.sort,
             unreadable_paths,
             false,
+            self.stamps(),
+            self.manifest_checkpoint.clone(),
         );
         (headers, SourceScan::new(task))
     }
@@ -240,7 +410,7 @@ impl TransferSource for FsTransferSource {
         headers: Vec<FileHeader>,
         unreadable_paths: Arc<Mutex<Vec<String>>>,
     ) -> Result<Vec<FileHeader>> {
-        filter_readable_headers(&self.root, headers, &unreadable_paths).await
+        filter_readable_headers(&self.root, headers, &unreadable_paths, self.skip_locked).await
     }
 
     async fn open_file(
@@ -264,6 +434,14 @@ impl TransferSource for FsTransferSource {
     fn root(&self) -> &Path {
         &self.root
     }
+
+    fn local_path(&self, header: &FileHeader) -> Option<PathBuf> {
+        Some(if header.relative_path.is_empty() {
+            self.root.clone()
+        } else {
+            self.root.join(&header.relative_path)
+        })
+    }
 }
 
 /// Stream a manifest scan of `root` as `FileHeader`s (otp-10c-2:
@@ -273,11 +451,17 @@ impl TransferSource for FsTransferSource {
 /// R46-F2: suppressed walk errors and unreadable files land in
 /// `unreadable` so a downstream mirror-deletion gate sees "scan was
 /// incomplete" via a single check.
+#[allow(clippy::too_many_arguments)]
 fn spawn_manifest_task(
     root: PathBuf,
     filter: FileFilter,
+    deref: SymlinkDeref,
+    safe_links: bool,
+    sort: SortOrder,
     unreadable: Arc<Mutex<Vec<String>>>,
     preserve_windows_metadata: bool,
+    stamps: HeaderStamps,
+    checkpoint: Option<PathBuf>,
 ) -> (
     mpsc::Receiver<FileHeader>,
     tokio::task::JoinHandle<Result<u64>>,
@@ -289,12 +473,43 @@ fn spawn_manifest_task(
 
     let (manifest_tx, manifest_rx) = mpsc::channel::<FileHeader>(64);
     let handle = tokio::task::spawn_blocking(move || -> Result<u64> {
-        let enumerator = FileEnumerator::new(filter);
+        let enumerator = FileEnumerator::new(filter)
+            .deref_symlinks(deref)
+            .safe_links(safe_links)
+            .sort_order(sort);
         let start = Instant::now();
         let mut last_log = start;
         let mut enumerated: u64 = 0;
         let unreadable = unreadable;
-        let scan_outcome = enumerator.enumerate_local_streaming_capturing(&root, |entry| {
+        let mut checkpoint = checkpoint
+            .map(|path| crate::manifest_checkpoint::ManifestCheckpoint::open(&path, &root))
+            .transpose()?;
+        let visit = |entry: crate::enumeration::EnumeratedEntry| {
+            // Contract v12: every directory rides the manifest too, so
+            // an empty one is recreated rather than only implied by the
+            // files under it. The walk yields a directory before its
+            // contents, so the destination sees parents first.
+            if matches!(entry.kind, EntryKind::Directory) {
+                let rel = crate::path_posix::relative_path_to_posix(&entry.relative_path);
+                if rel.is_empty() {
+                    return Ok(());
+                }
+                let header = FileHeader {
+                    relative_path: rel,
+                    mtime_seconds: crate::wire_metadata::mtime_seconds(&entry.metadata)
+                        .unwrap_or(0),
+                    permissions: crate::wire_metadata::permissions_mode(&entry.metadata),
+                    directory: true,
+                    owner: stamps.owner(&entry.absolute_path, &entry.metadata),
+                    xattrs: stamps.xattrs(&entry.absolute_path),
+                    ..Default::default()
+                };
+                manifest_tx
+                    .blocking_send(header)
+                    .map_err(|_| eyre!("failed to queue manifest entry"))?;
+                enumerated += 1;
+                return Ok(());
+            }
             if let EntryKind::File { size } = entry.kind {
                 let rel = crate::path_posix::relative_path_to_posix(&entry.relative_path);
                 let absolute = entry.absolute_path.clone();
@@ -321,7 +536,7 @@ fn spawn_manifest_task(
 
                 let mtime = crate::wire_metadata::mtime_seconds(&entry.metadata).unwrap_or(0);
                 let permissions = crate::wire_metadata::permissions_mode(&entry.metadata);
-                let Some(header) = file_header_with_windows_metadata_policy(
+                let Some(mut header) = file_header_with_windows_metadata_policy(
                     rel,
                     size,
                     mtime,
@@ -333,6 +548,11 @@ fn spawn_manifest_task(
                 ) else {
                     return Ok(());
                 };
+                if stamps.crtimes {
+                    header.crtime_seconds = crate::wire_metadata::crtime_seconds(&entry.metadata);
+                }
+                header.owner = stamps.owner(&absolute, &entry.metadata);
+                header.xattrs = stamps.xattrs(&absolute);
                 manifest_tx
                     .blocking_send(header)
                     .map_err(|_| eyre!("failed to queue manifest entry"))?;
@@ -345,7 +565,18 @@ fn spawn_manifest_task(
                 }
             }
             Ok(())
-        })?;
+        };
+        let scan_outcome = match checkpoint.as_mut() {
+            Some(checkpoint) => {
+                let outcome = enumerator.enumerate_local_checkpointed(&root, checkpoint, visit)?;
+                eprintln!(
+                    "Manifest checkpoint: {} directories reused, {} read",
+                    outcome.dirs_from_checkpoint, outcome.dirs_listed
+                );
+                outcome
+            }
+            None => enumerator.enumerate_local_streaming_capturing(&root, visit)?,
+        };
         for suppressed in &scan_outcome.suppressed_errors {
             record_unreadable_entry(
                 &unreadable,
@@ -383,6 +614,10 @@ fn file_header_with_windows_metadata_policy(
             permissions,
             checksum: Vec::new(),
             windows_metadata: None,
+            crtime_seconds: None,
+            directory: false,
+            owner: None,
+            xattrs: Vec::new(),
         });
     }
     file_header_with_windows_metadata(
@@ -423,6 +658,10 @@ fn file_header_with_windows_metadata(
         permissions,
         checksum: vec![],
         windows_metadata,
+        crtime_seconds: None,
+        directory: false,
+        owner: None,
+        xattrs: Vec::new(),
     })
 }
 
@@ -436,11 +675,13 @@ fn record_unreadable_entry(list: &Arc<Mutex<Vec<String>>>, rel: &str, reason: &s
 /// Filter `headers` down to the ones whose files are still readable
 /// under `source_root`, recording the rest in `unreadable`
 /// (otp-10c-2: relocated verbatim from the deleted push driver's
-/// `client::helpers`).
+/// `client::helpers`). With `skip_locked`, a file held locked by
+/// another process is recorded and skipped too.
 async fn filter_readable_headers(
     source_root: &Path,
     headers: Vec<FileHeader>,
     unreadable: &Arc<Mutex<Vec<String>>>,
+    skip_locked: bool,
 ) -> Result<Vec<FileHeader>> {
     use eyre::eyre;
     use std::io::ErrorKind;
@@ -468,6 +709,10 @@ async fn filter_readable_headers(
                     record_unreadable_entry(unreadable, &rel, "not found");
                     continue;
                 }
+                _ if skip_locked && is_lock_violation(&err) => {
+                    record_unreadable_entry(unreadable, &rel, "locked by another process");
+                    continue;
+                }
                 _ => {
                     return Err(eyre!(format!(
                         "opening {} during payload planning: {}",
@@ -482,6 +727,26 @@ async fn filter_readable_headers(
     Ok(filtered)
 }
 
+/// A Windows sharing violation (another process opened the file
+/// without read sharing) or lock violation (a byte-range lock). std
+/// maps neither onto an `ErrorKind`, so match the raw codes.
+fn is_lock_violation(err: &std::io::Error) -> bool {
+    #[cfg(windows)]
+    {
+        const ERROR_SHARING_VIOLATION: i32 = 32;
+        const ERROR_LOCK_VIOLATION: i32 = 33;
+        matches!(
+            err.raw_os_error(),
+            Some(ERROR_SHARING_VIOLATION | ERROR_LOCK_VIOLATION)
+        )
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = err;
+        false
+    }
+}
+
 /// Decorator that applies a `FileFilter` uniformly to any inner
 /// `TransferSource`'s scan output. This is the SINGLE filter chokepoint
 /// for every src/dst combination (local→local, push, pull, remote→remote);
@@ -544,6 +809,91 @@ impl TransferSource for WindowsMetadataDroppingSource {
     fn root(&self) -> &Path {
         self.inner.root()
     }
+
+    fn local_path(&self, header: &FileHeader) -> Option<PathBuf> {
+        self.inner.local_path(header)
+    }
+}
+
+/// Records the relative path of every file whose bytes this source
+/// prepared for sending — whole, in a tar shard or stream, or through
+/// the resume block phase. Files the destination did not need are
+/// never prepared, so they are never recorded. `--remove-source-files`
+/// deletes exactly the recorded files once the session succeeds.
+pub struct SentFilesSource {
+    inner: Arc<dyn TransferSource>,
+    sent: Arc<Mutex<Vec<String>>>,
+}
+
+impl SentFilesSource {
+    pub fn new(inner: Arc<dyn TransferSource>, sent: Arc<Mutex<Vec<String>>>) -> Self {
+        Self { inner, sent }
+    }
+}
+
+#[async_trait]
+impl TransferSource for SentFilesSource {
+    fn scan(
+        &self,
+        filter: Option<FileFilter>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+        self.inner.scan(filter, unreadable_paths)
+    }
+
+    fn scan_without_windows_metadata(
+        &self,
+        filter: Option<FileFilter>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+        self.inner
+            .scan_without_windows_metadata(filter, unreadable_paths)
+    }
+
+    async fn prepare_payload(&self, payload: TransferPayload) -> Result<PreparedPayload> {
+        let paths: Vec<String> = match &payload {
+            TransferPayload::File(header) | TransferPayload::ResumeFile { header, .. } => {
+                vec![header.relative_path.clone()]
+            }
+            TransferPayload::TarShard { headers } | TransferPayload::TarStream { headers } => {
+                headers.iter().map(|h| h.relative_path.clone()).collect()
+            }
+            // Blocks of a file whose `ResumeFile` is recorded.
+            TransferPayload::FileBlock { .. } | TransferPayload::FileBlockComplete { .. } => {
+                Vec::new()
+            }
+        };
+        let prepared = self.inner.prepare_payload(payload).await?;
+        if let Ok(mut sent) = self.sent.lock() {
+            sent.extend(paths);
+        }
+        Ok(prepared)
+    }
+
+    async fn check_availability(
+        &self,
+        headers: Vec<FileHeader>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> Result<Vec<FileHeader>> {
+        self.inner
+            .check_availability(headers, unreadable_paths)
+            .await
+    }
+
+    async fn open_file(
+        &self,
+        header: &FileHeader,
+    ) -> Result<Box<dyn tokio::io::AsyncRead + Unpin + Send>> {
+        self.inner.open_file(header).await
+    }
+
+    fn root(&self) -> &Path {
+        self.inner.root()
+    }
+
+    fn local_path(&self, header: &FileHeader) -> Option<PathBuf> {
+        self.inner.local_path(header)
+    }
 }
 
 impl FilteredSource {
@@ -596,6 +946,10 @@ impl TransferSource for FilteredSource {
     fn root(&self) -> &Path {
         self.inner.root()
     }
+
+    fn local_path(&self, header: &FileHeader) -> Option<PathBuf> {
+        self.inner.local_path(header)
+    }
 }
 
 impl FilteredSource {
@@ -696,6 +1050,10 @@ impl TransferSource for ChecksummingSource {
     fn root(&self) -> &Path {
         self.inner.root()
     }
+
+    fn local_path(&self, header: &FileHeader) -> Option<PathBuf> {
+        self.inner.local_path(header)
+    }
 }
 
 impl ChecksummingSource {
@@ -721,6 +1079,12 @@ impl ChecksummingSource {
         let task = tokio::spawn(async move {
             let stop = move || stop_probe.is_closed();
             while let Some(mut header) = header_rx.recv().await {
+                if header.directory {
+                    if tx.send(header).await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
                 match hash_header_content(inner.as_ref(), &header, &stop).await {
                     Ok(Some(checksum)) => header.checksum = checksum,
                     // Receiver gone mid-hash — the session ended; stop.
@@ -744,6 +1108,670 @@ impl ChecksummingSource {
     }
 }
 
+/// `--prune-empty-dirs` decorator: holds each directory entry back until
+/// a file under it is manifested and drops it otherwise, so a directory
+/// the filter emptied (or that was empty to begin with) is not created.
+/// Wrap OUTSIDE the filter — pruning judges the filtered manifest.
+///
+/// The filesystem scan is depth-first with each directory ahead of its
+/// contents, so the held entries always form the ancestor chain of the
+/// current entry: a held directory that is not an ancestor of the next
+/// entry has been walked completely without a file and is dropped
+/// there, keeping the buffer at one path's depth.
+pub struct PruneEmptyDirsSource {
+    inner: Arc<dyn TransferSource>,
+}
+
+impl PruneEmptyDirsSource {
+    pub fn new(inner: Arc<dyn TransferSource>) -> Self {
+        Self { inner }
+    }
+
+    fn scan_with_metadata_policy(
+        &self,
+        filter: Option<FileFilter>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+        preserve_windows_metadata: bool,
+    ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+        let (mut inner_rx, mut scan) = if preserve_windows_metadata {
+            self.inner.scan(filter, unreadable_paths)
+        } else {
+            self.inner
+                .scan_without_windows_metadata(filter, unreadable_paths)
+        };
+        let (tx, rx) = mpsc::channel(64);
+        let handle = tokio::spawn(async move {
+            let mut held: Vec<FileHeader> = Vec::new();
+            let mut forwarded = 0u64;
+            while let Some(header) = inner_rx.recv().await {
+                while held
+                    .last()
+                    .is_some_and(|dir| !is_ancestor(&dir.relative_path, &header.relative_path))
+                {
+                    held.pop();
+                }
+                if header.directory {
+                    held.push(header);
+                    continue;
+                }
+                for dir in held.drain(..).chain(std::iter::once(header)) {
+                    forwarded += 1;
+                    if tx.send(dir).await.is_err() {
+                        return Ok(forwarded);
+                    }
+                }
+            }
+            Ok(forwarded)
+        });
+        scan.replace_primary(handle);
+        (rx, scan)
+    }
+}
+
+/// Is POSIX relative path `dir` a strict ancestor of `path`?
+fn is_ancestor(dir: &str, path: &str) -> bool {
+    path.len() > dir.len() && path.starts_with(dir) && path.as_bytes()[dir.len()] == b'/'
+}
+
+#[async_trait]
+impl TransferSource for PruneEmptyDirsSource {
+    fn scan(
+        &self,
+        filter: Option<FileFilter>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+        self.scan_with_metadata_policy(filter, unreadable_paths, true)
+    }
+
+    fn scan_without_windows_metadata(
+        &self,
+        filter: Option<FileFilter>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+        self.scan_with_metadata_policy(filter, unreadable_paths, false)
+    }
+
+    async fn prepare_payload(&self, payload: TransferPayload) -> Result<PreparedPayload> {
+        self.inner.prepare_payload(payload).await
+    }
+
+    async fn check_availability(
+        &self,
+        headers: Vec<FileHeader>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> Result<Vec<FileHeader>> {
+        self.inner
+            .check_availability(headers, unreadable_paths)
+            .await
+    }
+
+    async fn open_file(
+        &self,
+        header: &FileHeader,
+    ) -> Result<Box<dyn tokio::io::AsyncRead + Unpin + Send>> {
+        self.inner.open_file(header).await
+    }
+
+    fn root(&self) -> &Path {
+        self.inner.root()
+    }
+
+    fn local_path(&self, header: &FileHeader) -> Option<PathBuf> {
+        self.inner.local_path(header)
+    }
+}
+
+/// `--chmod` decorator: rewrites every manifest header's mode (and the
+/// carried owner's, under `--super`) through a [`crate::chmod::ChmodSpec`]
+/// before it leaves the scan. Payloads are prepared from those headers,
+/// so a file's bytes arrive with the mode the manifest promised.
+pub struct ChmodSource {
+    inner: Arc<dyn TransferSource>,
+    spec: Arc<crate::chmod::ChmodSpec>,
+}
+
+impl ChmodSource {
+    pub fn new(inner: Arc<dyn TransferSource>, spec: crate::chmod::ChmodSpec) -> Self {
+        Self {
+            inner,
+            spec: Arc::new(spec),
+        }
+    }
+
+    fn scan_with_metadata_policy(
+        &self,
+        filter: Option<FileFilter>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+        preserve_windows_metadata: bool,
+    ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+        let (mut inner_rx, mut scan) = if preserve_windows_metadata {
+            self.inner.scan(filter, unreadable_paths)
+        } else {
+            self.inner
+                .scan_without_windows_metadata(filter, unreadable_paths)
+        };
+        let spec = Arc::clone(&self.spec);
+        let (tx, rx) = mpsc::channel(64);
+        let task = tokio::spawn(async move {
+            while let Some(mut header) = inner_rx.recv().await {
+                header.permissions = spec.apply(header.permissions, header.directory);
+                if let Some(owner) = header.owner.as_mut() {
+                    owner.mode = spec.apply(owner.mode, header.directory);
+                }
+                if tx.send(header).await.is_err() {
+                    break;
+                }
+            }
+        });
+        scan.add_auxiliary(task);
+        (rx, scan)
+    }
+}
+
+#[async_trait]
+impl TransferSource for ChmodSource {
+    fn scan(
+        &self,
+        filter: Option<FileFilter>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+        self.scan_with_metadata_policy(filter, unreadable_paths, true)
+    }
+
+    fn scan_without_windows_metadata(
+        &self,
+        filter: Option<FileFilter>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+        self.scan_with_metadata_policy(filter, unreadable_paths, false)
+    }
+
+    async fn prepare_payload(&self, payload: TransferPayload) -> Result<PreparedPayload> {
+        self.inner.prepare_payload(payload).await
+    }
+
+    async fn check_availability(
+        &self,
+        headers: Vec<FileHeader>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> Result<Vec<FileHeader>> {
+        self.inner
+            .check_availability(headers, unreadable_paths)
+            .await
+    }
+
+    async fn open_file(
+        &self,
+        header: &FileHeader,
+    ) -> Result<Box<dyn tokio::io::AsyncRead + Unpin + Send>> {
+        self.inner.open_file(header).await
+    }
+
+    fn root(&self) -> &Path {
+        self.inner.root()
+    }
+
+    fn local_path(&self, header: &FileHeader) -> Option<PathBuf> {
+        self.inner.local_path(header)
+    }
+}
+
+/// The soft `--warn-files` threshold: once the manifest holds more than
+/// `threshold` files, the scan pauses and asks `confirm` (a blocking
+/// call — it may prompt on a terminal) whether to go on. `false` aborts
+/// the scan as CANCELLED.
+#[derive(Clone)]
+pub struct FileCountWarning {
+    pub threshold: u64,
+    pub confirm: Arc<dyn Fn(u64) -> bool + Send + Sync>,
+}
+
+impl std::fmt::Debug for FileCountWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileCountWarning")
+            .field("threshold", &self.threshold)
+            .finish_non_exhaustive()
+    }
+}
+
+/// `--max-files` / `--warn-files` decorator: counts the files (not
+/// directories) entering the manifest. Past `max_files` the scan fails
+/// with FILE_LIMIT; with a cap set, every entry is held back until the
+/// scan finished within it, so the destination sees nothing of a
+/// refused tree. The warning only pauses the scan for its answer —
+/// entries already sent keep moving meanwhile. Wrap outermost, so the
+/// count is the manifest the destination would receive.
+pub struct FileCountGuardSource {
+    inner: Arc<dyn TransferSource>,
+    max_files: Option<u64>,
+    warning: Option<FileCountWarning>,
+}
+
+impl FileCountGuardSource {
+    pub fn new(
+        inner: Arc<dyn TransferSource>,
+        max_files: Option<u64>,
+        warning: Option<FileCountWarning>,
+    ) -> Self {
+        Self {
+            inner,
+            max_files,
+            warning,
+        }
+    }
+
+    fn scan_with_metadata_policy(
+        &self,
+        filter: Option<FileFilter>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+        preserve_windows_metadata: bool,
+    ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+        use crate::generated::session_error::Code;
+        use crate::transfer_session::SessionFault;
+
+        let (mut inner_rx, mut scan) = if preserve_windows_metadata {
+            self.inner.scan(filter, unreadable_paths)
+        } else {
+            self.inner
+                .scan_without_windows_metadata(filter, unreadable_paths)
+        };
+        let (tx, rx) = mpsc::channel(64);
+        let max_files = self.max_files;
+        let mut warning = self.warning.clone();
+        let handle = tokio::spawn(async move {
+            let mut held: Vec<FileHeader> = Vec::new();
+            let mut files = 0u64;
+            let mut forwarded = 0u64;
+            while let Some(header) = inner_rx.recv().await {
+                if !header.directory {
+                    files += 1;
+                    if let Some(max) = max_files.filter(|&max| files > max) {
+                        return Err(eyre::Report::new(SessionFault::refusal(
+                            Code::FileLimit,
+                            format!("the source has more than {max} files (--max-files {max})"),
+                        )));
+                    }
+                    if let Some(warning) = warning.take_if(|w| files > w.threshold) {
+                        let threshold = warning.threshold;
+                        let confirmed =
+                            tokio::task::spawn_blocking(move || (warning.confirm)(threshold))
+                                .await
+                                .map_err(|err| eyre::eyre!("file-count prompt panicked: {err}"))?;
+                        if !confirmed {
+                            return Err(eyre::Report::new(SessionFault::refusal(
+                                Code::Cancelled,
+                                format!(
+                                    "not confirmed: the source has more than {threshold} \
+                                     files (--warn-files {threshold})"
+                                ),
+                            )));
+                        }
+                    }
+                }
+                if max_files.is_some() {
+                    held.push(header);
+                    continue;
+                }
+                forwarded += 1;
+                if tx.send(header).await.is_err() {
+                    return Ok(forwarded);
+                }
+            }
+            for header in held {
+                forwarded += 1;
+                if tx.send(header).await.is_err() {
+                    break;
+                }
+            }
+            Ok(forwarded)
+        });
+        scan.replace_primary(handle);
+        (rx, scan)
+    }
+}
+
+#[async_trait]
+impl TransferSource for FileCountGuardSource {
+    fn scan(
+        &self,
+        filter: Option<FileFilter>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+        self.scan_with_metadata_policy(filter, unreadable_paths, true)
+    }
+
+    fn scan_without_windows_metadata(
+        &self,
+        filter: Option<FileFilter>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+        self.scan_with_metadata_policy(filter, unreadable_paths, false)
+    }
+
+    async fn prepare_payload(&self, payload: TransferPayload) -> Result<PreparedPayload> {
+        self.inner.prepare_payload(payload).await
+    }
+
+    async fn check_availability(
+        &self,
+        headers: Vec<FileHeader>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> Result<Vec<FileHeader>> {
+        self.inner
+            .check_availability(headers, unreadable_paths)
+            .await
+    }
+
+    async fn open_file(
+        &self,
+        header: &FileHeader,
+    ) -> Result<Box<dyn tokio::io::AsyncRead + Unpin + Send>> {
+        self.inner.open_file(header).await
+    }
+
+    fn root(&self) -> &Path {
+        self.inner.root()
+    }
+
+    fn local_path(&self, header: &FileHeader) -> Option<PathBuf> {
+        self.inner.local_path(header)
+    }
+}
+
+/// Renamed → original relative path map, filled by a
+/// [`RenamingSource`] scan. Shared with every reader that receives a
+/// renamed header but must open the original file: the decorator's own
+/// payload methods and, on the local route, `FsTransferSink`.
+pub type RenamedPaths = Arc<Mutex<HashMap<String, String>>>;
+
+/// Decorator that rewrites each scanned header's `relative_path` through
+/// the `--rename` rules ([`PathRenamer`]) before it enters the manifest.
+/// Every downstream consumer — the destination diff, mirror scoping,
+/// payload records — sees only the rewritten name; this wrapper maps
+/// back to the original on the read side (`open_file`, availability
+/// checks, payload preparation), so the inner source never sees a
+/// renamed path.
+///
+/// Wrap OUTSIDE the filter (filters match the names the user sees on the
+/// source). A rewrite that fails wire-path validation, or two source
+/// entries that rewrite to the same path, fail the scan — a collision
+/// would otherwise silently overwrite one file with another.
+pub struct RenamingSource {
+    inner: Arc<dyn TransferSource>,
+    renamer: Arc<PathRenamer>,
+    originals: RenamedPaths,
+}
+
+impl RenamingSource {
+    pub fn new(inner: Arc<dyn TransferSource>, renamer: PathRenamer) -> Self {
+        Self {
+            inner,
+            renamer: Arc::new(renamer),
+            originals: RenamedPaths::default(),
+        }
+    }
+
+    /// A second view over `other`'s rename map for a different inner
+    /// source — the local route prepares payloads through the unfiltered
+    /// fs source while the filtered chain owns the scan.
+    pub fn sharing(inner: Arc<dyn TransferSource>, other: &RenamingSource) -> Self {
+        Self {
+            inner,
+            renamer: Arc::clone(&other.renamer),
+            originals: Arc::clone(&other.originals),
+        }
+    }
+
+    /// The renamed → original map this source's scan fills.
+    pub fn renamed_paths(&self) -> RenamedPaths {
+        Arc::clone(&self.originals)
+    }
+
+    fn original_header(&self, header: &FileHeader) -> Result<FileHeader> {
+        let mut original = header.clone();
+        original.relative_path = original_relative_path(&self.originals, &header.relative_path)?;
+        Ok(original)
+    }
+
+    fn scan_with_metadata_policy(
+        &self,
+        filter: Option<FileFilter>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+        preserve_windows_metadata: bool,
+    ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+        let (mut inner_rx, mut scan) = if preserve_windows_metadata {
+            self.inner.scan(filter, unreadable_paths)
+        } else {
+            self.inner
+                .scan_without_windows_metadata(filter, unreadable_paths)
+        };
+        let (tx, rx) = mpsc::channel(64);
+        let renamer = Arc::clone(&self.renamer);
+        let originals = Arc::clone(&self.originals);
+        let handle = tokio::spawn(async move {
+            originals
+                .lock()
+                .map_err(|err| eyre::eyre!("rename map poisoned: {err}"))?
+                .clear();
+            let mut forwarded = 0u64;
+            while let Some(mut header) = inner_rx.recv().await {
+                // The rules rewrite file paths; a directory's own
+                // rewrite need not match its files', so directory
+                // entries stay out of a renamed manifest and only the
+                // renamed files' parents are created.
+                if header.directory {
+                    continue;
+                }
+                let renamed = renamer.rename(&header.relative_path)?;
+                {
+                    let mut map = originals
+                        .lock()
+                        .map_err(|err| eyre::eyre!("rename map poisoned: {err}"))?;
+                    match map.entry(renamed.clone()) {
+                        Entry::Occupied(existing) => eyre::bail!(
+                            "--rename collision: '{}' and '{}' both rewrite to '{}'",
+                            existing.get(),
+                            header.relative_path,
+                            renamed
+                        ),
+                        Entry::Vacant(slot) => {
+                            slot.insert(header.relative_path.clone());
+                        }
+                    }
+                }
+                header.relative_path = renamed;
+                forwarded += 1;
+                if tx.send(header).await.is_err() {
+                    break;
+                }
+            }
+            Ok(forwarded)
+        });
+        scan.replace_primary(handle);
+        (rx, scan)
+    }
+}
+
+/// Resolve a renamed manifest path back to the source's own name. A
+/// path the scan never emitted is an error, not a pass-through: the
+/// caller would otherwise read a different file than the manifest
+/// promised.
+pub fn original_relative_path(originals: &RenamedPaths, renamed: &str) -> Result<String> {
+    originals
+        .lock()
+        .map_err(|err| eyre::eyre!("rename map poisoned: {err}"))?
+        .get(renamed)
+        .cloned()
+        .ok_or_else(|| eyre::eyre!("'{renamed}' is not a renamed source entry"))
+}
+
+#[async_trait]
+impl TransferSource for RenamingSource {
+    fn scan(
+        &self,
+        filter: Option<FileFilter>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+        self.scan_with_metadata_policy(filter, unreadable_paths, true)
+    }
+
+    fn scan_without_windows_metadata(
+        &self,
+        filter: Option<FileFilter>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+        self.scan_with_metadata_policy(filter, unreadable_paths, false)
+    }
+
+    async fn prepare_payload(&self, payload: TransferPayload) -> Result<PreparedPayload> {
+        match payload {
+            TransferPayload::File(header) => {
+                let renamed = header.relative_path.clone();
+                match self
+                    .inner
+                    .prepare_payload(TransferPayload::File(self.original_header(&header)?))
+                    .await?
+                {
+                    PreparedPayload::File(mut prepared) => {
+                        prepared.relative_path = renamed;
+                        Ok(PreparedPayload::File(prepared))
+                    }
+                    _ => eyre::bail!("inner source changed the shape of a File payload"),
+                }
+            }
+            TransferPayload::TarShard { headers } => {
+                let renamed: Vec<String> =
+                    headers.iter().map(|h| h.relative_path.clone()).collect();
+                let originals = headers
+                    .iter()
+                    .map(|h| self.original_header(h))
+                    .collect::<Result<Vec<_>>>()?;
+                match self
+                    .inner
+                    .prepare_payload(TransferPayload::TarShard { headers: originals })
+                    .await?
+                {
+                    PreparedPayload::TarShard { mut headers, data } => {
+                        for (header, name) in headers.iter_mut().zip(&renamed) {
+                            header.relative_path = name.clone();
+                        }
+                        let data = tokio::task::spawn_blocking(move || {
+                            rename_tar_members(&data, &renamed)
+                        })
+                        .await
+                        .map_err(|err| eyre::eyre!("tar rename worker failed: {err}"))??;
+                        Ok(PreparedPayload::TarShard { headers, data })
+                    }
+                    _ => eyre::bail!("inner source changed the shape of a TarShard payload"),
+                }
+            }
+            TransferPayload::ResumeFile {
+                header,
+                block_size,
+                dest_hashes,
+                hash_key,
+            } => {
+                let renamed = header.relative_path.clone();
+                match self
+                    .inner
+                    .prepare_payload(TransferPayload::ResumeFile {
+                        header: self.original_header(&header)?,
+                        block_size,
+                        dest_hashes,
+                        hash_key,
+                    })
+                    .await?
+                {
+                    PreparedPayload::ResumeFile {
+                        mut header,
+                        block_size,
+                        dest_hashes,
+                        hash_key,
+                    } => {
+                        header.relative_path = renamed;
+                        Ok(PreparedPayload::ResumeFile {
+                            header,
+                            block_size,
+                            dest_hashes,
+                            hash_key,
+                        })
+                    }
+                    _ => eyre::bail!("inner source changed the shape of a ResumeFile payload"),
+                }
+            }
+            other => self.inner.prepare_payload(other).await,
+        }
+    }
+
+    async fn check_availability(
+        &self,
+        headers: Vec<FileHeader>,
+        unreadable_paths: Arc<Mutex<Vec<String>>>,
+    ) -> Result<Vec<FileHeader>> {
+        let mut renamed_by_original: HashMap<String, String> = HashMap::new();
+        let mut originals = Vec::with_capacity(headers.len());
+        for header in &headers {
+            let original = self.original_header(header)?;
+            renamed_by_original
+                .insert(original.relative_path.clone(), header.relative_path.clone());
+            originals.push(original);
+        }
+        let available = self
+            .inner
+            .check_availability(originals, unreadable_paths)
+            .await?;
+        Ok(available
+            .into_iter()
+            .map(|mut header| {
+                if let Some(renamed) = renamed_by_original.remove(&header.relative_path) {
+                    header.relative_path = renamed;
+                }
+                header
+            })
+            .collect())
+    }
+
+    async fn open_file(
+        &self,
+        header: &FileHeader,
+    ) -> Result<Box<dyn tokio::io::AsyncRead + Unpin + Send>> {
+        self.inner.open_file(&self.original_header(header)?).await
+    }
+
+    fn root(&self) -> &Path {
+        self.inner.root()
+    }
+
+    fn local_path(&self, header: &FileHeader) -> Option<PathBuf> {
+        self.inner.local_path(&self.original_header(header).ok()?)
+    }
+}
+
+/// Re-emit a prepared tar shard with each member renamed, in order.
+/// Tar members are named by `relative_path` and the receive side
+/// (`tar_safety`) matches them against the manifest headers, so the
+/// member names must carry the rewritten paths too.
+fn rename_tar_members(data: &[u8], renamed: &[String]) -> Result<Vec<u8>> {
+    use eyre::Context;
+    let mut archive = tar::Archive::new(data);
+    let mut builder = tar::Builder::new(Vec::with_capacity(data.len()));
+    let mut names = renamed.iter();
+    for entry in archive.entries().context("reading tar shard for rename")? {
+        let mut entry = entry.context("reading tar shard entry for rename")?;
+        let name = names
+            .next()
+            .ok_or_else(|| eyre::eyre!("tar shard has more members than headers"))?;
+        let mut header = entry.header().clone();
+        builder
+            .append_data(&mut header, name, &mut entry)
+            .with_context(|| format!("renaming tar member to {name}"))?;
+    }
+    if names.next().is_some() {
+        eyre::bail!("tar shard has fewer members than headers");
+    }
+    builder.into_inner().context("finalizing renamed tar shard")
+}
+
 fn strip_windows_metadata_from_scan(
     mut header_rx: mpsc::Receiver<FileHeader>,
     mut scan: SourceScan,
@@ -772,6 +1800,20 @@ async fn hash_header_content(
     stop: &(dyn Fn() -> bool + Send + Sync),
 ) -> Result<Option<Vec<u8>>> {
     use tokio::io::AsyncReadExt;
+    // `--checksum-cache`: key on the local file and its metadata as it
+    // stood before the read.
+    let cached = match (crate::checksum_cache::active(), source.local_path(header)) {
+        (Some(cache), Some(path)) => match tokio::fs::metadata(&path).await {
+            Ok(meta) => {
+                if let Some(digest) = cache.lookup(&path, &meta) {
+                    return Ok(Some(digest));
+                }
+                Some((cache, path, meta))
+            }
+            Err(_) => None,
+        },
+        _ => None,
+    };
     let mut reader = source.open_file(header).await?;
     let mut hasher = blake3::Hasher::new();
     let mut buf = vec![0u8; 64 * 1024];
@@ -785,7 +1827,11 @@ async fn hash_header_content(
         }
         hasher.update(&buf[..got]);
     }
-    Ok(Some(hasher.finalize().as_bytes().to_vec()))
+    let digest = hasher.finalize().as_bytes().to_vec();
+    if let Some((cache, path, meta)) = cached {
+        cache.store(&path, &meta, &digest);
+    }
+    Ok(Some(digest))
 }
 
 async fn filter_headers(
@@ -797,6 +1843,12 @@ async fn filter_headers(
     use std::time::{Duration, UNIX_EPOCH};
     while let Some(header) = rx.recv().await {
         let rel = PathBuf::from(&header.relative_path);
+        if header.directory {
+            if filter.allows_directory_entry(&rel) && tx.send(header).await.is_err() {
+                break;
+            }
+            continue;
+        }
         let mtime = if header.mtime_seconds > 0 {
             UNIX_EPOCH.checked_add(Duration::from_secs(header.mtime_seconds as u64))
         } else {
@@ -1048,6 +2100,10 @@ mod filtered_source_tests {
             permissions: 0,
             checksum: vec![],
             windows_metadata: None,
+            crtime_seconds: None,
+            directory: false,
+            owner: None,
+            xattrs: Vec::new(),
         }
     }
 
@@ -1059,6 +2115,34 @@ mod filtered_source_tests {
         out
     }
 
+    #[tokio::test]
+    async fn prune_empty_dirs_keeps_only_directories_holding_a_file() {
+        let dir = |path: &str| FileHeader {
+            relative_path: path.into(),
+            directory: true,
+            ..Default::default()
+        };
+        // Scan order: each directory ahead of its contents.
+        let inner: Arc<dyn TransferSource> = Arc::new(StubSource::new(vec![
+            dir("a"),
+            dir("a/empty"),
+            dir("a/b"),
+            header("a/b/file.txt", 1),
+            dir("a-sibling"),
+            dir("c"),
+            dir("c/d"),
+            header("top.txt", 1),
+        ]));
+        let source = PruneEmptyDirsSource::new(inner);
+        let (rx, mut scan) = source.scan(None, Arc::new(Mutex::new(Vec::new())));
+        assert_eq!(
+            collect(rx).await,
+            vec!["a", "a/b", "a/b/file.txt", "top.txt"],
+            "directories with no file under them are dropped"
+        );
+        assert_eq!(scan.finish().await.unwrap(), 4);
+    }
+
     #[tokio::test]
     async fn explicit_lossy_source_strips_windows_metadata_before_manifest() {
         let mut metadata_header = header("metadata.bin", 10);
@@ -1287,6 +2371,10 @@ mod checksumming_source_tests {
             permissions: 0,
             checksum: vec![],
             windows_metadata: None,
+            crtime_seconds: None,
+            directory: false,
+            owner: None,
+            xattrs: Vec::new(),
         }
     }
 
@@ -43,6 +43,48 @@ use tar::{Archive, EntryType};
 
 use crate::generated::{FileHeader, WindowsFileMetadata};
 use crate::path_safety;
+use crate::transfer_session::SessionFault;
+
+/// Length of the Blake3 digest every tar shard carries on the wire,
+/// TCP record and in-stream `TarShardHeader.blake3` alike (contract v8).
+pub const TAR_SHARD_DIGEST_LEN: usize = blake3::OUT_LEN;
+
+/// Digest of a tar shard's archive bytes, computed by the sending end
+/// over exactly the buffer it puts on the wire.
+pub fn tar_shard_digest(data: &[u8]) -> [u8; TAR_SHARD_DIGEST_LEN] {
+    *blake3::hash(data).as_bytes()
+}
+
+/// Check a received shard against the digest its record carried. Runs
+/// before [`safe_extract_tar_shard`] sees the buffer, so a corrupted shard
+/// fails with a `DATA_LOSS` fault and writes nothing.
+pub fn verify_tar_shard_digest(headers: &[FileHeader], data: &[u8], expected: &[u8]) -> Result<()> {
+    if blake3::hash(data) == *expected {
+        return Ok(());
+    }
+    let first = headers
+        .first()
+        .map(|h| h.relative_path.as_str())
+        .unwrap_or("<empty>");
+    Err(SessionFault::data_loss(format!(
+        "tar shard of {} file(s) (first '{first}') failed its Blake3 check: {} bytes received, \
+         digest does not match the sender's",
+        headers.len(),
+        data.len()
+    ))
+    .into())
+}
+
+/// The `DATA_LOSS` fault for a tar stream whose archive did not match
+/// the digest after its terminator (contract v24) — `cause` is the read
+/// error that reported it. Unlike a shard's, the check comes after the
+/// members were unpacked; the receiving sink removes what they wrote.
+pub fn tar_stream_digest_fault(members: usize, first: &str, cause: &eyre::Report) -> eyre::Report {
+    SessionFault::data_loss(format!(
+        "tar stream of {members} file(s) (first '{first}'): {cause:#}"
+    ))
+    .into()
+}
 
 /// Default per-entry / per-shard byte cap. Tar shards target 4–64 MiB;
 /// 256 MiB is comfortable headroom while bounding pathological
@@ -90,6 +132,8 @@ pub struct ExtractedFile {
     /// mtime to apply, derived from `FileHeader.mtime_seconds`.
     /// `None` when the header carried no mtime (`mtime_seconds == 0`).
     pub mtime: Option<FileTime>,
+    /// Creation time from `FileHeader.crtime_seconds` (`--crtimes`).
+    pub crtime_seconds: Option<i64>,
     /// Unix permissions from `FileHeader.permissions`. `None` when
     /// the header carried no perms (`permissions == 0`).
     pub permissions: Option<u32>,
@@ -109,14 +153,37 @@ pub fn safe_extract_tar_shard(
     dst_root: &Path,
     options: &TarShardExtractOptions,
 ) -> Result<Vec<ExtractedFile>> {
+    let mut out: Vec<ExtractedFile> = Vec::with_capacity(expected_headers.len());
+    safe_extract_tar_stream(
+        Cursor::new(buffer),
+        expected_headers,
+        dst_root,
+        options,
+        |file| {
+            out.push(file);
+            Ok(())
+        },
+    )?;
+    Ok(out)
+}
+
+/// [`safe_extract_tar_shard`] over a sequential reader: each validated
+/// member is handed to `each` as soon as its bytes are read, so an
+/// archive arriving off a socket (a `TAR_STREAM` record) is never held
+/// whole. Same per-entry policy; `each` failing stops the walk.
+pub fn safe_extract_tar_stream<R: std::io::Read>(
+    reader: R,
+    expected_headers: Vec<FileHeader>,
+    dst_root: &Path,
+    options: &TarShardExtractOptions,
+    mut each: impl FnMut(ExtractedFile) -> Result<()>,
+) -> Result<()> {
     let mut expected: HashMap<String, FileHeader> = expected_headers
         .into_iter()
         .map(|h| (h.relative_path.clone(), h))
         .collect();
 
-    let mut out: Vec<ExtractedFile> = Vec::with_capacity(expected.len());
-
-    let mut archive = Archive::new(Cursor::new(buffer));
+    let mut archive = Archive::new(reader);
     let entries = archive.entries().context("reading tar shard entries")?;
 
     for entry_result in entries {
@@ -204,17 +271,19 @@ pub fn safe_extract_tar_shard(
             None
         };
         let size = header.size;
+        let crtime_seconds = header.crtime_seconds;
         let windows_metadata = header.windows_metadata;
 
-        out.push(ExtractedFile {
+        each(ExtractedFile {
             rel: rel_string,
             dest_path,
             contents,
             mtime,
+            crtime_seconds,
             permissions,
             size,
             windows_metadata,
-        });
+        })?;
     }
 
     if options.require_exact_headers && !expected.is_empty() {
@@ -222,7 +291,7 @@ pub fn safe_extract_tar_shard(
         bail!("tar shard missing expected entries: {missing:?}");
     }
 
-    Ok(out)
+    Ok(())
 }
 
 /// Write one `ExtractedFile` to disk, applying mtime and Unix
@@ -250,6 +319,7 @@ pub fn write_extracted_file(file: &ExtractedFile) -> Result<()> {
             log::warn!("set mtime on {}: {}", file.dest_path.display(), e);
         }
     }
+    crate::fs_capability::apply_creation_time(&file.dest_path, file.crtime_seconds);
     #[cfg(unix)]
     if let Some(perms) = file.permissions {
         use std::os::unix::fs::PermissionsExt;
@@ -278,6 +348,10 @@ mod tests {
             permissions: 0o644,
             checksum: vec![],
             windows_metadata: None,
+            crtime_seconds: None,
+            directory: false,
+            owner: None,
+            xattrs: Vec::new(),
         }
     }
 
@@ -409,6 +483,7 @@ mod tests {
             dest_path: dest.clone(),
             contents: b"payload".to_vec(),
             mtime: Some(FileTime::from_unix_time(1_577_836_800, 0)),
+            crtime_seconds: None,
             permissions: Some(0o600),
             size: 7,
             windows_metadata: None,
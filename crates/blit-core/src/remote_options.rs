@@ -0,0 +1,135 @@
+//! `--remote-option KEY=VALUE` (contract v23): behavior toggles a client
+//! asks the daemon end of a session to apply, without a new
+//! `SessionOpen` field per toggle.
+//!
+//! The options ride `SessionOpen.remote_options`. Only the RESPONDER
+//! interprets them, against the keys its role knows
+//! ([`RemoteOptions::resolve`]): a known key with a malformed value is
+//! refused at OPEN, and a key this role does not know is ignored with a
+//! warning and named back in `SessionAccept.ignored_remote_options`, so
+//! the client warns too. Both ends are the same build — a different one
+//! is refused with `BUILD_MISMATCH` at HELLO, before any `SessionOpen`
+//! — so "ignored" only ever means a key the responder's ROLE does not
+//! take, never a key its build predates.
+//!
+//! Known keys:
+//!
+//! | key     | role        | values          | effect |
+//! |---------|-------------|-----------------|--------|
+//! | `fsync` | DESTINATION | `true`, `false` | sync every received file before it counts as written ([`FsSinkConfig::fsync`]) |
+//!
+//! [`FsSinkConfig::fsync`]: crate::remote::transfer::sink::FsSinkConfig::fsync
+
+use std::collections::HashMap;
+
+use eyre::{bail, Result};
+
+use crate::generated::TransferRole;
+
+/// The remote options a responder honors, resolved for its role.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RemoteOptions {
+    /// `fsync=true` (DESTINATION).
+    pub fsync: bool,
+}
+
+impl RemoteOptions {
+    /// Resolve `options` for an end serving `role`. Returns the
+    /// options to apply and the keys this role ignores, sorted. A known
+    /// key with a value it cannot take is an error.
+    pub fn resolve(
+        options: &HashMap<String, String>,
+        role: TransferRole,
+    ) -> Result<(Self, Vec<String>)> {
+        let mut resolved = Self::default();
+        let mut ignored = Vec::new();
+        for (key, value) in options {
+            match (key.as_str(), role) {
+                ("fsync", TransferRole::Destination) => resolved.fsync = parse_bool(key, value)?,
+                _ => ignored.push(key.clone()),
+            }
+        }
+        ignored.sort();
+        Ok((resolved, ignored))
+    }
+}
+
+fn parse_bool(key: &str, value: &str) -> Result<bool> {
+    match value {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        other => bail!("remote option {key}: expected true or false, got {other:?}"),
+    }
+}
+
+/// Parse repeated `--remote-option KEY=VALUE` arguments into the map
+/// `SessionOpen.remote_options` carries. Keys are not checked against
+/// the known set here — that is the daemon's call — but each must be
+/// non-empty and given once.
+pub fn parse_remote_options(args: &[String]) -> Result<HashMap<String, String>> {
+    let mut options = HashMap::with_capacity(args.len());
+    for arg in args {
+        let Some((key, value)) = arg.split_once('=') else {
+            bail!("--remote-option {arg:?}: expected KEY=VALUE");
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            bail!("--remote-option {arg:?}: empty key");
+        }
+        if options.insert(key.to_string(), value.to_string()).is_some() {
+            bail!("--remote-option {key} given more than once");
+        }
+    }
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn destination_honors_fsync_and_ignores_unknown_keys() {
+        let (resolved, ignored) = RemoteOptions::resolve(
+            &options(&[("fsync", "true"), ("zeta", "1"), ("alpha", "x")]),
+            TransferRole::Destination,
+        )
+        .unwrap();
+        assert!(resolved.fsync);
+        assert_eq!(ignored, ["alpha", "zeta"]);
+    }
+
+    #[test]
+    fn a_source_ignores_destination_keys() {
+        let (resolved, ignored) =
+            RemoteOptions::resolve(&options(&[("fsync", "true")]), TransferRole::Source).unwrap();
+        assert_eq!(resolved, RemoteOptions::default());
+        assert_eq!(ignored, ["fsync"]);
+    }
+
+    #[test]
+    fn a_malformed_known_value_is_refused() {
+        let err = RemoteOptions::resolve(
+            &options(&[("fsync", "sometimes")]),
+            TransferRole::Destination,
+        )
+        .unwrap_err();
+        assert!(format!("{err}").contains("fsync"), "{err}");
+    }
+
+    #[test]
+    fn cli_arguments_parse_into_the_wire_map() {
+        let parsed =
+            parse_remote_options(&["fsync=true".to_string(), "note=a=b".to_string()]).unwrap();
+        assert_eq!(parsed, options(&[("fsync", "true"), ("note", "a=b")]));
+        assert!(parse_remote_options(&["fsync".to_string()]).is_err());
+        assert!(parse_remote_options(&["=true".to_string()]).is_err());
+        assert!(parse_remote_options(&["a=1".to_string(), "a=2".to_string()]).is_err());
+    }
+}
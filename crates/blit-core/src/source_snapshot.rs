@@ -0,0 +1,462 @@
+//! Point-in-time source snapshots: read a consistent, frozen view of
+//! the source instead of the live tree.
+//!
+//! [`SourceSnapshot`] owns one temporary snapshot for the length of a
+//! transfer. [`SourceSnapshot::read_root`] is the source root mapped
+//! into the snapshot — the transfer enumerates and reads through it
+//! exactly as it would the live root — and dropping the guard deletes
+//! the snapshot.
+//!
+//! - `--vss` (Windows): a Volume Shadow Copy of the source's volume,
+//!   created and deleted through the `Win32_ShadowCopy` CIM class, read
+//!   through its `\\?\GLOBALROOT\Device\HarddiskVolumeShadowCopyN`
+//!   device path. Needs an elevated process.
+//! - `--snapshot` (Unix): a read-only filesystem snapshot where the
+//!   source's filesystem supports one ([`detect_filesystem_type`]):
+//!   `btrfs subvolume snapshot -r` of the enclosing subvolume (Linux),
+//!   or an APFS local snapshot mounted read-only (macOS). Any other
+//!   filesystem warns and reads the live tree.
+
+use std::path::{Path, PathBuf};
+
+use eyre::{eyre, Result};
+
+use crate::fs_capability::detect_filesystem_type;
+
+/// A temporary snapshot of the volume holding a transfer's source.
+/// Deleted on drop (a failed delete is logged, never fatal: the
+/// transfer's outcome already stands).
+pub struct SourceSnapshot {
+    read_root: PathBuf,
+    /// What the snapshot is, for the cleanup-failure warning.
+    label: String,
+    /// Deletes the snapshot; taken and run once, on drop.
+    delete: Option<Box<dyn FnOnce() -> Result<()> + Send>>,
+}
+
+impl SourceSnapshot {
+    /// Shadow-copy the volume holding `source` (`--vss`) and map
+    /// `source` into the shadow copy.
+    pub fn create_vss(source: &Path) -> Result<Self> {
+        #[cfg(windows)]
+        {
+            vss::create(source)
+        }
+        #[cfg(not(windows))]
+        {
+            let _ = source;
+            Err(eyre!("--vss is only available on Windows"))
+        }
+    }
+
+    /// Snapshot the filesystem holding `source` (`--snapshot`) and map
+    /// `source` into the snapshot. `Ok(None)` — after a warning — when
+    /// the filesystem has no snapshot support blit can drive: the
+    /// caller reads the live tree.
+    pub fn create_fs(source: &Path) -> Result<Option<Self>> {
+        let fs_type = detect_filesystem_type(source);
+        match fs_type.as_deref() {
+            #[cfg(target_os = "linux")]
+            Some("btrfs") => btrfs::create(source).map(Some),
+            #[cfg(target_os = "macos")]
+            Some("apfs") => apfs::create(source).map(Some),
+            other => {
+                log::warn!(
+                    "--snapshot: {} is on {}, which blit cannot snapshot; reading the live tree",
+                    source.display(),
+                    other.unwrap_or("an undetected filesystem")
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// The source root inside the snapshot: enumerate and read here.
+    pub fn read_root(&self) -> &Path {
+        &self.read_root
+    }
+}
+
+impl Drop for SourceSnapshot {
+    fn drop(&mut self) {
+        if let Some(delete) = self.delete.take() {
+            if let Err(err) = delete() {
+                log::warn!("failed to delete {}: {err:#}", self.label);
+            }
+        }
+    }
+}
+
+/// Map `live` — a path on the volume mounted at `volume_root` — to the
+/// same path inside a snapshot of that volume rooted at
+/// `snapshot_root`.
+#[cfg_attr(
+    not(any(windows, target_os = "linux", target_os = "macos")),
+    allow(dead_code)
+)]
+fn map_into_snapshot(live: &Path, volume_root: &Path, snapshot_root: &Path) -> Result<PathBuf> {
+    let relative = live.strip_prefix(volume_root).map_err(|_| {
+        eyre!(
+            "{} is not under its volume root {}",
+            live.display(),
+            volume_root.display()
+        )
+    })?;
+    Ok(if relative.as_os_str().is_empty() {
+        snapshot_root.to_path_buf()
+    } else {
+        snapshot_root.join(relative)
+    })
+}
+
+#[cfg(windows)]
+mod vss {
+    use std::path::{Component, Path, PathBuf, Prefix};
+    use std::process::Command;
+
+    use eyre::{bail, eyre, Context, Result};
+
+    use super::{map_into_snapshot, SourceSnapshot};
+
+    pub(super) fn create(source: &Path) -> Result<SourceSnapshot> {
+        let live = std::fs::canonicalize(source)
+            .with_context(|| format!("resolving {} for --vss", source.display()))?;
+        let (volume, volume_root) = volume_of(&live)?;
+        let output = powershell(&format!(
+            "$r = Invoke-CimMethod -ClassName Win32_ShadowCopy -MethodName Create \
+             -Arguments @{{Volume='{volume}'; Context='ClientAccessible'}}; \
+             if ($r.ReturnValue -ne 0) {{ throw \"Win32_ShadowCopy.Create returned $($r.ReturnValue)\" }}; \
+             $s = Get-CimInstance Win32_ShadowCopy -Filter \"ID='$($r.ShadowID)'\"; \
+             Write-Output $r.ShadowID; Write-Output $s.DeviceObject"
+        ))
+        .with_context(|| format!("creating a shadow copy of {volume} (needs an elevated shell)"))?;
+        let mut lines = output.lines().map(str::trim).filter(|l| !l.is_empty());
+        let (Some(shadow_id), Some(device)) = (lines.next(), lines.next()) else {
+            bail!("shadow copy of {volume} reported no id/device: {output:?}");
+        };
+        // Build the guard first so a mapping failure still deletes the
+        // shadow copy.
+        let id = shadow_id.to_string();
+        let mut snapshot = SourceSnapshot {
+            read_root: PathBuf::new(),
+            label: format!("shadow copy {shadow_id}"),
+            delete: Some(Box::new(move || delete(&id))),
+        };
+        let snapshot_root = PathBuf::from(format!("{device}\\"));
+        snapshot.read_root = map_into_snapshot(&live, &volume_root, &snapshot_root)?;
+        log::info!(
+            "reading {} through shadow copy {shadow_id} ({})",
+            source.display(),
+            snapshot.read_root.display()
+        );
+        Ok(snapshot)
+    }
+
+    fn delete(shadow_id: &str) -> Result<()> {
+        powershell(&format!(
+            "Get-CimInstance Win32_ShadowCopy -Filter \"ID='{shadow_id}'\" | Remove-CimInstance"
+        ))
+        .map(drop)
+    }
+
+    /// The drive a canonical (`\\?\C:\...`) path lives on: the
+    /// `C:\` volume name `Win32_ShadowCopy.Create` takes, and the
+    /// verbatim root to strip from the path.
+    fn volume_of(live: &Path) -> Result<(String, PathBuf)> {
+        match live.components().next() {
+            Some(Component::Prefix(prefix)) => match prefix.kind() {
+                Prefix::VerbatimDisk(letter) | Prefix::Disk(letter) => {
+                    let letter = char::from(letter).to_ascii_uppercase();
+                    let mut root = PathBuf::from(prefix.as_os_str());
+                    root.push("\\");
+                    Ok((format!("{letter}:\\"), root))
+                }
+                _ => Err(eyre!(
+                    "--vss needs a source on a local drive letter, not {}",
+                    live.display()
+                )),
+            },
+            _ => Err(eyre!("cannot determine the volume of {}", live.display())),
+        }
+    }
+
+    fn powershell(script: &str) -> Result<String> {
+        let output = Command::new("powershell.exe")
+            .args(["-NoProfile", "-NonInteractive", "-Command", script])
+            .output()
+            .context("running powershell.exe")?;
+        if !output.status.success() {
+            bail!(
+                "powershell exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// A name for a temporary snapshot unique to this process and moment.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn unique_name() -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    format!(".blit-snapshot-{}-{nanos}", std::process::id())
+}
+
+/// Run `program args…`, returning stdout; a non-zero exit is an error
+/// carrying stderr.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn run_tool(program: &str, args: &[&std::ffi::OsStr]) -> Result<String> {
+    use eyre::Context;
+
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .with_context(|| format!("running {program}"))?;
+    if !output.status.success() {
+        return Err(eyre!(
+            "{program} exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(target_os = "linux")]
+mod btrfs {
+    use std::os::unix::fs::MetadataExt;
+    use std::path::{Path, PathBuf};
+
+    use eyre::{Context, Result};
+
+    use super::{map_into_snapshot, run_tool, unique_name, SourceSnapshot};
+
+    /// Inode number of every btrfs subvolume root
+    /// (`BTRFS_FIRST_FREE_OBJECTID`).
+    const SUBVOLUME_ROOT_INO: u64 = 256;
+
+    pub(super) fn create(source: &Path) -> Result<SourceSnapshot> {
+        let live = std::fs::canonicalize(source)
+            .with_context(|| format!("resolving {} for --snapshot", source.display()))?;
+        let subvolume = subvolume_of(&live)?;
+        // Inside the subvolume it snapshots: same filesystem, and a path
+        // that does not exist yet is not part of the snapshot.
+        let snapshot_root = subvolume.join(unique_name());
+        run_tool(
+            "btrfs",
+            &[
+                "subvolume".as_ref(),
+                "snapshot".as_ref(),
+                "-r".as_ref(),
+                subvolume.as_os_str(),
+                snapshot_root.as_os_str(),
+            ],
+        )
+        .with_context(|| format!("snapshotting btrfs subvolume {}", subvolume.display()))?;
+        let path = snapshot_root.clone();
+        let mut snapshot = SourceSnapshot {
+            read_root: PathBuf::new(),
+            label: format!("btrfs snapshot {}", snapshot_root.display()),
+            delete: Some(Box::new(move || {
+                run_tool(
+                    "btrfs",
+                    &["subvolume".as_ref(), "delete".as_ref(), path.as_os_str()],
+                )
+                .map(drop)
+            })),
+        };
+        snapshot.read_root = map_into_snapshot(&live, &subvolume, &snapshot_root)?;
+        log::info!(
+            "reading {} through btrfs snapshot {}",
+            source.display(),
+            snapshot.read_root.display()
+        );
+        Ok(snapshot)
+    }
+
+    /// The root of the subvolume holding `live`: every subvolume has
+    /// its own device number, so walk up while the parent shares it.
+    fn subvolume_of(live: &Path) -> Result<PathBuf> {
+        let dev = std::fs::metadata(live)?.dev();
+        let mut root = live;
+        while let Some(parent) = root.parent() {
+            match std::fs::metadata(parent) {
+                Ok(meta) if meta.dev() == dev => root = parent,
+                _ => break,
+            }
+        }
+        let ino = std::fs::metadata(root)?.ino();
+        if ino != SUBVOLUME_ROOT_INO {
+            eyre::bail!(
+                "{} does not look like a btrfs subvolume root (inode {ino})",
+                root.display()
+            );
+        }
+        Ok(root.to_path_buf())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod apfs {
+    use std::ffi::{CStr, CString};
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::{Path, PathBuf};
+
+    use eyre::{eyre, Context, Result};
+
+    use super::{map_into_snapshot, run_tool, unique_name, SourceSnapshot};
+
+    /// The writable data volume behind the read-only system volume's
+    /// firmlinks (`/Users`, `/Applications`, …).
+    const DATA_VOLUME: &str = "/System/Volumes/Data";
+
+    pub(super) fn create(source: &Path) -> Result<SourceSnapshot> {
+        let live = std::fs::canonicalize(source)
+            .with_context(|| format!("resolving {} for --snapshot", source.display()))?;
+        let volume = mount_point_of(&live)?;
+        let output = run_tool("tmutil", &["localsnapshot".as_ref()])
+            .context("creating an APFS local snapshot (needs root)")?;
+        // "Created local snapshot with date: 2026-10-15-101500"
+        let date = output
+            .split(':')
+            .nth(1)
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .ok_or_else(|| eyre!("tmutil reported no snapshot date: {output:?}"))?
+            .to_string();
+        let mount = std::env::temp_dir().join(unique_name());
+        let label = format!("APFS snapshot {date}");
+        let delete_date = date.clone();
+        let delete_mount = mount.clone();
+        let mut snapshot = SourceSnapshot {
+            read_root: PathBuf::new(),
+            label,
+            delete: Some(Box::new(move || {
+                let _ = run_tool("umount", &[delete_mount.as_os_str()]);
+                let _ = std::fs::remove_dir(&delete_mount);
+                run_tool(
+                    "tmutil",
+                    &["deletelocalsnapshots".as_ref(), delete_date.as_ref()],
+                )
+                .map(drop)
+            })),
+        };
+        std::fs::create_dir(&mount)
+            .with_context(|| format!("creating snapshot mount point {}", mount.display()))?;
+        let name = format!("com.apple.TimeMachine.{date}.local");
+        run_tool(
+            "mount_apfs",
+            &[
+                "-o".as_ref(),
+                "rdonly".as_ref(),
+                "-s".as_ref(),
+                name.as_ref(),
+                volume.as_os_str(),
+                mount.as_os_str(),
+            ],
+        )
+        .with_context(|| format!("mounting {name} of {}", volume.display()))?;
+        // Firmlinked paths (`/Users/…`) live on the data volume without
+        // its `/System/Volumes/Data` prefix.
+        snapshot.read_root = if volume == Path::new(DATA_VOLUME) && !live.starts_with(&volume) {
+            map_into_snapshot(&live, Path::new("/"), &mount)?
+        } else {
+            map_into_snapshot(&live, &volume, &mount)?
+        };
+        log::info!(
+            "reading {} through APFS snapshot {date} ({})",
+            source.display(),
+            snapshot.read_root.display()
+        );
+        Ok(snapshot)
+    }
+
+    /// Where the volume holding `live` is mounted (`statfs` `f_mntonname`).
+    fn mount_point_of(live: &Path) -> Result<PathBuf> {
+        let c_path = CString::new(live.as_os_str().as_bytes())?;
+        let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+        if unsafe { libc::statfs(c_path.as_ptr(), &mut stat) } != 0 {
+            return Err(std::io::Error::last_os_error())
+                .with_context(|| format!("statfs {}", live.display()));
+        }
+        let name = unsafe { CStr::from_ptr(stat.f_mntonname.as_ptr()) };
+        Ok(PathBuf::from(std::ffi::OsStr::from_bytes(name.to_bytes())))
+    }
+}
+
+/// Test constructor: a "snapshot" already materialized at `read_root`
+/// whose drop runs `cleanup`.
+#[cfg(test)]
+pub(crate) fn fake_snapshot(
+    read_root: PathBuf,
+    cleanup: impl FnOnce() + Send + 'static,
+) -> SourceSnapshot {
+    SourceSnapshot {
+        read_root,
+        label: "test snapshot".to_string(),
+        delete: Some(Box::new(move || {
+            cleanup();
+            Ok(())
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn live_paths_map_under_the_snapshot_root() {
+        let volume = Path::new("/vol");
+        let snap = Path::new("/snaps/one");
+        assert_eq!(
+            map_into_snapshot(Path::new("/vol/data/set"), volume, snap).unwrap(),
+            Path::new("/snaps/one/data/set")
+        );
+        assert_eq!(
+            map_into_snapshot(Path::new("/vol"), volume, snap).unwrap(),
+            snap
+        );
+        assert!(map_into_snapshot(Path::new("/other/data"), volume, snap).is_err());
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn vss_is_refused_off_windows() {
+        let err = SourceSnapshot::create_vss(Path::new(".")).err().unwrap();
+        assert!(err.to_string().contains("only available on Windows"));
+    }
+
+    /// A filesystem blit cannot snapshot falls back to the live tree
+    /// rather than failing the transfer.
+    #[test]
+    fn unsupported_filesystem_reads_live() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let fs_type = detect_filesystem_type(tmp.path());
+        if matches!(fs_type.as_deref(), Some("btrfs") | Some("apfs")) {
+            return;
+        }
+        assert!(SourceSnapshot::create_fs(tmp.path())
+            .expect("fallback")
+            .is_none());
+    }
+
+    #[test]
+    fn dropping_the_guard_cleans_up() {
+        let cleaned = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&cleaned);
+        let snapshot = fake_snapshot(PathBuf::from("/snaps/one"), move || {
+            flag.store(true, Ordering::SeqCst)
+        });
+        assert_eq!(snapshot.read_root(), Path::new("/snaps/one"));
+        assert!(!cleaned.load(Ordering::SeqCst));
+        drop(snapshot);
+        assert!(cleaned.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,110 @@
+//! Blit's staging leftovers, and which of them are stale.
+//!
+//! Several write paths stage their output beside (or inside) the
+//! destination and rename it into place once it is complete: a
+//! `--checksum` local copy (`.NAME.blit-verify`), `--delay-updates`
+//! (a `.blit-delay-updates` directory, or `.NAME.blit-delay` for a
+//! single-file root), `--dedupe` (`.NAME.blit-dedupe`), hard-link
+//! unsharing (`.NAME.blit-unshare`) and tar-stream members awaiting
+//! their record's digest (`.NAME.blit-stream`). A crashed or killed
+//! transfer leaves them behind. `blit clean` finds the ones nothing has
+//! touched for a while with [`stale_entries`] and removes them.
+
+use crate::path_posix::collapse_to_topmost;
+use crate::transfer_session::DELAY_UPDATES_DIR;
+
+/// Suffixes of the hidden per-file staging siblings (`.NAME` + suffix).
+pub const STAGED_SUFFIXES: &[&str] = &[
+    ".blit-verify",
+    ".blit-delay",
+    ".blit-dedupe",
+    ".blit-unshare",
+    ".blit-stream",
+];
+
+/// Whether `name` (one path component) is something Blit stages writes
+/// under: a hidden per-file sibling, or a `--delay-updates` staging
+/// directory (also in its `--temp-dir` form, `.blit-delay-updates-KEY`).
+pub fn is_staging_name(name: &str) -> bool {
+    if let Some(rest) = name.strip_prefix(DELAY_UPDATES_DIR) {
+        return rest.is_empty() || rest.starts_with('-');
+    }
+    name.len() > 1
+        && name.starts_with('.')
+        && STAGED_SUFFIXES
+            .iter()
+            .any(|suffix| name.len() > suffix.len() + 1 && name.ends_with(suffix))
+}
+
+/// One entry of a listed tree: `/`-separated and relative to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListedEntry {
+    pub path: String,
+    pub mtime_seconds: i64,
+}
+
+/// The staging entries in `entries` that are stale: named by
+/// [`is_staging_name`], not inside another one, and with nothing in
+/// them modified at or after `cutoff_seconds` (Unix time). A staging
+/// directory is judged by its newest descendant, so one a live transfer
+/// is still filling is never picked. Returned sorted; each directory
+/// stands for everything under it.
+pub fn stale_entries(entries: &[ListedEntry], cutoff_seconds: i64) -> Vec<String> {
+    collapse_to_topmost(
+        entries
+            .iter()
+            .map(|entry| (entry.path.as_str(), entry.mtime_seconds)),
+        |(path, _)| path,
+        |(path, _)| is_staging_name(path.rsplit('/').next().unwrap_or(path)),
+        |(_, newest), (_, mtime)| *newest = (*newest).max(mtime),
+    )
+    .into_iter()
+    .filter(|(_, newest)| *newest < cutoff_seconds)
+    .map(|(path, _)| path.to_string())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, mtime_seconds: i64) -> ListedEntry {
+        ListedEntry {
+            path: path.to_string(),
+            mtime_seconds,
+        }
+    }
+
+    #[test]
+    fn staging_names_are_recognised() {
+        assert!(is_staging_name(".report.pdf.blit-verify"));
+        assert!(is_staging_name(".out.bin.blit-delay"));
+        assert!(is_staging_name(".blit-delay-updates"));
+        assert!(is_staging_name(".blit-delay-updates-0123456789abcdef"));
+        assert!(is_staging_name(".a.blit-unshare"));
+        assert!(!is_staging_name("report.pdf.blit-verify"));
+        assert!(!is_staging_name(".blit-verify"));
+        assert!(!is_staging_name(".blit-delay-updatesx"));
+        assert!(!is_staging_name("notes.txt"));
+    }
+
+    #[test]
+    fn only_untouched_staging_roots_are_stale() {
+        let entries = [
+            entry("keep.txt", 10),
+            entry(".keep.txt.blit-verify", 10),
+            entry("sub", 10),
+            entry("sub/.fresh.blit-dedupe", 500),
+            entry(".blit-delay-updates", 10),
+            entry(".blit-delay-updates/sub", 10),
+            entry(".blit-delay-updates/sub/.x.blit-verify", 10),
+            entry("busy", 10),
+            entry("busy/.blit-delay-updates", 10),
+            entry("busy/.blit-delay-updates/live.bin", 500),
+        ];
+        assert_eq!(
+            stale_entries(&entries, 100),
+            vec![".blit-delay-updates", ".keep.txt.blit-verify"]
+        );
+    }
+}
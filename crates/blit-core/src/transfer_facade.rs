@@ -0,0 +1,112 @@
+//! Library entry point for embedding local transfers.
+//!
+//! [`TransferFacade::copy_local`] runs one local copy or mirror on the
+//! same session the CLI and TUI ride
+//! ([`crate::transfer_session::run_local_session`]) — an embedder gets
+//! the identical diff, mirror delete rule, and summary without
+//! assembling session configs or reaching into `blit-cli`.
+//! [`LocalMirrorOptions`] stays the knob surface; the facade only adds
+//! a progress callback, fed the w6-1 [`ProgressEvent`] stream the CLI
+//! progress monitor folds.
+//!
+//! ```no_run
+//! # async fn run() -> eyre::Result<()> {
+//! use blit_core::transfer_facade::TransferFacade;
+//! use blit_core::transfer_session::LocalMirrorOptions;
+//!
+//! let summary = TransferFacade::new()
+//!     .on_progress(|event| eprintln!("{event:?}"))
+//!     .copy_local(
+//!         "/data/src".as_ref(),
+//!         "/backup/src".as_ref(),
+//!         LocalMirrorOptions::default(),
+//!         false,
+//!     )
+//!     .await?;
+//! println!("copied {} files", summary.copied_files);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::Path;
+use std::sync::Arc;
+
+use eyre::{Context, Result};
+use tokio::sync::mpsc;
+
+use crate::remote::transfer::{ProgressEvent, RemoteTransferProgress};
+use crate::transfer_session::local::run_local_session_with_progress;
+use crate::transfer_session::{LocalMirrorOptions, LocalMirrorSummary};
+
+type ProgressCallback = Arc<dyn Fn(ProgressEvent) + Send + Sync>;
+
+/// Programmatic front door for local transfers. Cheap to build and
+/// reusable across runs; holds only the optional progress callback.
+#[derive(Clone, Default)]
+pub struct TransferFacade {
+    progress: Option<ProgressCallback>,
+}
+
+impl std::fmt::Debug for TransferFacade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransferFacade")
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+impl TransferFacade {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `callback` with every progress event of each run. Events
+    /// are delivered on the task awaiting [`Self::copy_local`], in
+    /// order, and all of them before it returns.
+    pub fn on_progress(mut self, callback: impl Fn(ProgressEvent) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Copy `src` to `dst` (`mirror = true` also deletes destination
+    /// entries absent from the source, scoped by
+    /// `options.delete_scope`). `mirror` wins over `options.mirror`.
+    pub async fn copy_local(
+        &self,
+        src: &Path,
+        dst: &Path,
+        mut options: LocalMirrorOptions,
+        mirror: bool,
+    ) -> Result<LocalMirrorSummary> {
+        options.mirror = mirror;
+        let verb = if mirror { "mirror" } else { "copy" };
+        let context = || format!("failed to {verb} {} to {}", src.display(), dst.display());
+
+        let Some(callback) = &self.progress else {
+            return run_local_session_with_progress(src, dst, options, None)
+                .await
+                .with_context(context);
+        };
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let session = run_local_session_with_progress(
+            src,
+            dst,
+            options,
+            Some(RemoteTransferProgress::new(tx)),
+        );
+        tokio::pin!(session);
+        let result = loop {
+            tokio::select! {
+                result = &mut session => break result,
+                Some(event) = rx.recv() => callback(event),
+            }
+        };
+        // The session has returned, so nothing sends any more; hand
+        // over whatever it queued after the last select turn.
+        while let Ok(event) = rx.try_recv() {
+            callback(event);
+        }
+        result.with_context(context)
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use crate::enumeration::SortOrder;
+
 /// Adaptive transfer task classification shared across push, pull, and local engines.
 #[derive(Clone, Debug)]
 pub enum TransferTask {
@@ -20,6 +22,20 @@ pub struct PlanOptions {
     pub small_target: Option<u64>,
     pub small_count_target: Option<usize>,
     pub medium_target: Option<u64>,
+    /// `--shard-max-bytes`: hard ceiling on one tar shard's payload
+    /// bytes. Unlike `small_target` (a floor the size ladder may raise),
+    /// this replaces the derived target outright.
+    pub shard_max_bytes: Option<u64>,
+    /// `--shard-max-files`: hard ceiling on one tar shard's member count,
+    /// replacing the derived count target (no 128..4096 clamp).
+    pub shard_max_files: Option<usize>,
+    /// `--no-shard`: never bundle small files into tar shards; every
+    /// file travels as its own record. Wins over `force_tar`.
+    pub no_shard: bool,
+    /// `--sort-order name`: plan files in path order rather than
+    /// arrival order, so the same set of files always yields the same
+    /// shards. The scan takes its order from here too.
+    pub sort_order: SortOrder,
 }
 
 impl PlanOptions {
@@ -29,6 +45,10 @@ impl PlanOptions {
             small_target: None,
             small_count_target: None,
             medium_target: None,
+            shard_max_bytes: None,
+            shard_max_files: None,
+            no_shard: false,
+            sort_order: SortOrder::None,
         }
     }
 }
@@ -56,7 +76,7 @@ pub fn build_plan(
     let mut small: Vec<PathBuf> = Vec::new();
     let mut medium: Vec<(PathBuf, u64)> = Vec::new();
     let mut total_medium_bytes: u64 = 0;
-    let mut large_files: Vec<TransferTask> = Vec::new();
+    let mut large: Vec<PathBuf> = Vec::new();
     let mut total_bytes: u128 = 0;
     for e in files {
         if e.is_directory {
@@ -78,11 +98,21 @@ pub fn build_plan(
             total_medium_bytes = total_medium_bytes.saturating_add(e.size);
         } else {
             // Large: schedule as single large-file task; range/delta decided when sending
-            large_files.push(TransferTask::Large { path: rel.clone() });
+            large.push(rel);
         }
     }
     // Shard small files into larger tars for multi-GB workloads
-    small.sort_by_key(|p| p.as_os_str().len());
+    if options.sort_order == SortOrder::Name {
+        small.sort();
+        medium.sort();
+        large.sort();
+    } else {
+        small.sort_by_key(|p| p.as_os_str().len());
+    }
+    let large_files: Vec<TransferTask> = large
+        .into_iter()
+        .map(|path| TransferTask::Large { path })
+        .collect();
 
     let mut small_tasks: Vec<TransferTask> = Vec::new();
     let small_count = small.len();
@@ -98,7 +128,9 @@ pub fn build_plan(
     // Tar shards only make sense for 2+ files (batching) — a single file
     // gains nothing from tar wrapping and breaks the empty-path case
     // produced by enumerating a file root directly.
-    let use_tar = if options.force_tar {
+    let use_tar = if options.no_shard {
+        false
+    } else if options.force_tar {
         small_count >= 1
     } else if small_count < 2 {
         false
@@ -125,6 +157,13 @@ pub fn build_plan(
                 256
             });
         count_target = count_target.clamp(128, 4096);
+        // Operator ceilings replace the derived targets outright.
+        if let Some(max) = options.shard_max_bytes {
+            target_shard = max;
+        }
+        if let Some(max) = options.shard_max_files {
+            count_target = max.max(1);
+        }
 
         let mut cur: Vec<PathBuf> = Vec::new();
         let mut cur_bytes: u64 = 0;
@@ -287,4 +326,96 @@ mod tests {
             .collect();
         assert_eq!(shard_sizes, vec![128, 128, 44]);
     }
+
+    fn shard_sizes(tasks: &[TransferTask]) -> Vec<usize> {
+        tasks
+            .iter()
+            .map(|t| match t {
+                TransferTask::TarShard(paths) => paths.len(),
+                other => panic!("expected only tar shards, got {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shard_max_files_caps_members_below_the_clamp() {
+        let files: Vec<FileEntry> = (0..300).map(|i| entry(&format!("f{i:03}"), 1024)).collect();
+        let derived = build_plan(&files, Path::new("/src"), PlanOptions::default());
+        let options = PlanOptions {
+            shard_max_files: Some(50),
+            ..PlanOptions::default()
+        };
+        let capped = build_plan(&files, Path::new("/src"), options);
+        assert_eq!(shard_sizes(&derived), vec![256, 44]);
+        assert_eq!(shard_sizes(&capped), vec![50; 6]);
+    }
+
+    #[test]
+    fn shard_max_bytes_replaces_the_derived_target() {
+        // 40 × 64 KiB = 2.5 MiB fits one derived (>= 4 MiB) shard; a
+        // 256 KiB ceiling holds 4 members per shard.
+        let files: Vec<FileEntry> = (0..40)
+            .map(|i| entry(&format!("f{i:02}"), 64 * 1024))
+            .collect();
+        let options = PlanOptions {
+            shard_max_bytes: Some(256 * 1024),
+            ..PlanOptions::default()
+        };
+        assert_eq!(
+            shard_sizes(&build_plan(
+                &files,
+                Path::new("/src"),
+                PlanOptions::default()
+            )),
+            vec![40]
+        );
+        assert_eq!(
+            shard_sizes(&build_plan(&files, Path::new("/src"), options)),
+            vec![4; 10]
+        );
+    }
+
+    #[test]
+    fn name_order_plans_identically_whatever_the_arrival_order() {
+        let files: Vec<FileEntry> = (0..300)
+            .map(|i| entry(&format!("d{}/f{i:03}", i % 7), 1024 + i))
+            .collect();
+        let reversed: Vec<FileEntry> = files.iter().rev().cloned().collect();
+        let options = PlanOptions {
+            sort_order: SortOrder::Name,
+            shard_max_files: Some(64),
+            ..PlanOptions::default()
+        };
+        let shards = |files: &[FileEntry]| -> Vec<Vec<PathBuf>> {
+            build_plan(files, Path::new("/src"), options)
+                .into_iter()
+                .map(|t| match t {
+                    TransferTask::TarShard(paths) => paths,
+                    other => panic!("expected only tar shards, got {other:?}"),
+                })
+                .collect()
+        };
+        let forward = shards(&files);
+        assert_eq!(forward, shards(&reversed));
+        let flat: Vec<&PathBuf> = forward.iter().flatten().collect();
+        assert!(
+            flat.windows(2).all(|w| w[0] < w[1]),
+            "members in path order"
+        );
+    }
+
+    #[test]
+    fn no_shard_plans_every_small_file_on_its_own() {
+        let files: Vec<FileEntry> = (0..300).map(|i| entry(&format!("f{i:03}"), 1024)).collect();
+        let options = PlanOptions {
+            no_shard: true,
+            force_tar: true,
+            ..PlanOptions::default()
+        };
+        let tasks = build_plan(&files, Path::new("/src"), options);
+        assert_eq!(tasks.len(), 300);
+        assert!(tasks
+            .iter()
+            .all(|t| matches!(t, TransferTask::RawBundle(paths) if paths.len() == 1)));
+    }
 }
@@ -43,12 +43,14 @@ use crate::buffer::BufferPool;
 #[cfg(test)]
 use crate::dial::{blocked_ratio, DialSampleInput};
 use crate::dial::{
-    receiver_initial_streams, receiver_stream_ceiling, spawn_dial_tuner_with_resize,
-    DialObservationEvent, DialObserver, ResizeProposal, TransferDial,
+    epoch0_streams, receiver_stream_ceiling, spawn_dial_tuner_with_resize, DialObservationEvent,
+    DialObserver, ResizeProposal, TransferDial,
 };
 use crate::generated::{
-    session_error::Code, CapacityProfile, DataPlaneGrant, DataPlaneResizeOp, FileHeader,
+    session_error::Code, CapacityProfile, DataPlaneGrant, DataPlaneResizeOp, DialRequest,
+    FileHeader,
 };
+use crate::remote::endpoint::format_host_port;
 use crate::remote::transfer::payload::{PreparedPayload, TransferPayload};
 use crate::remote::transfer::pipeline::execute_receive_pipeline_with_phase;
 use crate::remote::transfer::session_phase::{BoundSessionPhaseTrace, SessionPhaseFields};
@@ -62,8 +64,8 @@ use crate::remote::transfer::stall_guard::{StallGuard, TRANSFER_STALL_TIMEOUT};
 use crate::remote::transfer::{
     execute_sink_pipeline_elastic, generate_sub_token, AbortOnDrop, DataPlaneSession,
     ElasticPipelineControl, ElasticPipelineOutcome, FaultedPath, LiveProbe, MembershipOutcome,
-    RemoteTransferProgress, SharedStreamProbes, SinkMember, StreamId, StreamProbe,
-    StreamProbeRegistry, SUB_TOKEN_LEN,
+    RemoteTransferProgress, SharedStreamBytes, SharedStreamProbes, SinkMember, StreamBytes,
+    StreamId, StreamProbe, StreamProbeRegistry, SUB_TOKEN_LEN,
 };
 
 use super::{SessionFault, SourceInstruments};
@@ -115,15 +117,34 @@ fn dp_fault_io(err: &eyre::Report, msg: impl Into<String>) -> eyre::Report {
     eyre::Report::new(fault)
 }
 
+/// [`dp_fault_io`] for an INITIATOR's failed epoch-0 dial: the granted
+/// port could not be reached, before any payload moved. Flagged
+/// [`SessionFault::data_plane_unreachable`] so the session client can
+/// rerun on the in-stream carrier, and worded so an operator reading it
+/// (delegated jobs, `--retry` exhaustion) knows the remedy.
+fn dp_unreachable(err: &eyre::Report, what: &str) -> eyre::Report {
+    let mut fault = SessionFault::refusal(
+        Code::DataPlaneFailed,
+        format!(
+            "{what}: {err:#} (data port unreachable from this end; --force-grpc carries \
+             payload over the control connection instead)"
+        ),
+    )
+    .with_io_kind_from(err);
+    fault.data_plane_unreachable = true;
+    eyre::Report::new(fault)
+}
+
 pub(super) fn validate_epoch0_streams(
     granted: u32,
     receiver_capacity: Option<&CapacityProfile>,
+    dial_request: Option<&DialRequest>,
 ) -> Result<usize> {
-    let expected = receiver_initial_streams(receiver_capacity);
+    let expected = epoch0_streams(receiver_capacity, dial_request);
     if granted as usize != expected {
         return Err(eyre::Report::new(SessionFault::protocol_violation(
             format!(
-                "data-plane grant initial_streams {granted}, expected receiver-bounded floor {expected}"
+                "data-plane grant initial_streams {granted}, expected receiver-bounded epoch 0 {expected}"
             ),
         )));
     }
@@ -153,6 +174,7 @@ pub(super) struct ResponderDataPlane {
 /// cannot bind grants no data plane).
 pub(super) async fn prepare_responder_data_plane(
     receiver_capacity: Option<&CapacityProfile>,
+    dial_request: Option<&DialRequest>,
 ) -> Option<ResponderDataPlane> {
     let listener = match TcpListener::bind(("0.0.0.0", 0)).await {
         Ok(listener) => listener,
@@ -186,10 +208,11 @@ pub(super) async fn prepare_responder_data_plane(
             return None;
         }
     };
-    // Epoch 0 is the one conservative floor, bounded by the actual byte
-    // receiver's advertised limit. It never depends on manifest shape.
+    // Epoch 0 is the one conservative floor (or the initiator's
+    // `--streams` pin), bounded by the actual byte receiver's advertised
+    // limit. It never depends on manifest shape.
     let ceiling = receiver_stream_ceiling(receiver_capacity);
-    let initial_streams = receiver_initial_streams(receiver_capacity) as u32;
+    let initial_streams = epoch0_streams(receiver_capacity, dial_request) as u32;
     Some(ResponderDataPlane {
         listener,
         session_token,
@@ -735,13 +758,14 @@ pub(super) async fn dial_destination_data_plane(
     host: &str,
     grant: &DataPlaneGrant,
     receiver_capacity: Option<&CapacityProfile>,
+    dial_request: Option<&DialRequest>,
     sink: Arc<dyn TransferSink>,
     progress: Option<RemoteTransferProgress>,
     trace: bool,
     phase_trace: Option<BoundSessionPhaseTrace>,
     small_file_probe: Option<BoundSmallFileProbe>,
 ) -> Result<InitiatorReceivePlaneRun> {
-    let initial = validate_epoch0_streams(grant.initial_streams, receiver_capacity)?;
+    let initial = validate_epoch0_streams(grant.initial_streams, receiver_capacity, dial_request)?;
     // Epoch-0 handshake: session_token ‖ epoch0_sub_token.
     let mut handshake = grant.session_token.clone();
     handshake.extend_from_slice(&grant.epoch0_sub_token);
@@ -770,10 +794,7 @@ pub(super) async fn dial_destination_data_plane(
             Ok(socket) => socket,
             Err(err) => {
                 abort_receive_workers(&mut receives).await;
-                return Err(dp_fault_io(
-                    &err,
-                    format!("dialing session data plane (receive): {err:#}"),
-                ));
+                return Err(dp_unreachable(&err, "dialing session data plane (receive)"));
             }
         };
         if let Some(phase) = &phase_trace {
@@ -822,7 +843,7 @@ impl InitiatorReceivePlaneRun {
     pub(super) async fn add_dialed_stream(&mut self, epoch: u32, sub_token: &[u8]) -> Result<()> {
         let mut handshake = self.session_token.clone();
         handshake.extend_from_slice(sub_token);
-        let addr = format!("{}:{}", self.host, self.tcp_port);
+        let addr = format_host_port(&self.host, self.tcp_port);
         if self.trace {
             eprintln!("[data-plane-client] connecting to {addr} (receive resize)");
         }
@@ -989,6 +1010,19 @@ fn terminal_probe_totals(probes: &[StreamProbe]) -> TerminalProbeTotals {
     }
 }
 
+/// One [`StreamBytes`] per retained probe, in stream-id order.
+fn stream_bytes_of(probes: &[StreamProbe]) -> Vec<StreamBytes> {
+    let mut streams: Vec<StreamBytes> = probes
+        .iter()
+        .map(|probe| StreamBytes {
+            stream: probe.id(),
+            bytes: probe.snapshot().bytes_sent,
+        })
+        .collect();
+    streams.sort_by_key(|stream| stream.stream.0);
+    streams
+}
+
 fn retain_terminal_probe(
     terminal_probes: &Option<StdMutex<Vec<StreamProbe>>>,
     probe: &StreamProbe,
@@ -1109,10 +1143,13 @@ pub(super) struct SourceDataPlane {
     /// identical — only this transport action flips (otp-5b-2).
     sockets: SourceSockets,
     phase_trace: Option<BoundSessionPhaseTrace>,
-    /// Diagnostic-only clones of every initial and accepted ADD probe.
-    /// Unlike the live registry, retirement never removes these handles.
-    /// The collection exists only when session-phase tracing is active.
+    /// Clones of every initial and accepted ADD probe. Unlike the live
+    /// registry, retirement never removes these handles. The collection
+    /// exists only when session-phase tracing is active or the caller
+    /// asked for the per-stream byte breakdown.
     terminal_probes: Option<StdMutex<Vec<StreamProbe>>>,
+    /// The caller's per-stream byte accumulator, filled at [`Self::finish`].
+    stream_bytes: Option<SharedStreamBytes>,
     small_file_probe: Option<BoundSmallFileProbe>,
     queue_trace_armed: AtomicBool,
 }
@@ -1235,14 +1272,16 @@ async fn start_source_data_plane(
     epoch0_sub_token: Vec<u8>,
     granted_initial: u32,
     receiver_capacity: Option<&CapacityProfile>,
+    dial_request: Option<&DialRequest>,
     source: Arc<dyn TransferSource>,
     instruments: &SourceInstruments,
     phase_trace: Option<BoundSessionPhaseTrace>,
     small_file_probe: Option<BoundSmallFileProbe>,
 ) -> Result<SourceDataPlane> {
-    let initial = validate_epoch0_streams(granted_initial, receiver_capacity)?;
+    let initial = validate_epoch0_streams(granted_initial, receiver_capacity, dial_request)?;
     let observer = phase_trace.as_ref().map(phase_dial_observer);
     let dial = TransferDial::conservative_within(receiver_capacity)
+        .with_request(dial_request)
         .with_observer(observer)
         .shared();
     dial.set_negotiated_streams(initial);
@@ -1255,9 +1294,9 @@ async fn start_source_data_plane(
     ));
     let trace = instruments.trace_data_plane;
     let probes: SharedStreamProbes = Arc::new(StdMutex::new(StreamProbeRegistry::default()));
-    let terminal_probes = phase_trace
-        .as_ref()
-        .map(|_| StdMutex::new(Vec::with_capacity(initial)));
+    let stream_bytes = instruments.stream_bytes.clone();
+    let terminal_probes = (phase_trace.is_some() || stream_bytes.is_some())
+        .then(|| StdMutex::new(Vec::with_capacity(initial)));
     let mut sinks = Vec::with_capacity(initial);
 
     for socket_id in 0..initial {
@@ -1295,7 +1334,7 @@ async fn start_source_data_plane(
                 LiveProbe(probe.clone()),
             )
             .await
-            .map_err(|err| dp_fault_io(&err, format!("dialing session data plane: {err:#}")))?,
+            .map_err(|err| dp_unreachable(&err, "dialing session data plane"))?,
             SourceSockets::Accept { listener } => {
                 let socket = accept_authenticated(listener, &epoch0_handshake).await?;
                 configure_data_socket(&socket, dial.tcp_buffer_bytes()).map_err(|err| {
@@ -1382,6 +1421,7 @@ async fn start_source_data_plane(
         sockets,
         phase_trace,
         terminal_probes,
+        stream_bytes,
         small_file_probe,
         queue_trace_armed: AtomicBool::new(queue_trace_armed),
     })
@@ -1398,6 +1438,7 @@ pub(super) async fn dial_source_data_plane(
     host: &str,
     grant: &DataPlaneGrant,
     receiver_capacity: Option<&CapacityProfile>,
+    dial_request: Option<&DialRequest>,
     source: Arc<dyn TransferSource>,
     instruments: &SourceInstruments,
     phase_trace: Option<BoundSessionPhaseTrace>,
@@ -1412,6 +1453,7 @@ pub(super) async fn dial_source_data_plane(
         grant.epoch0_sub_token.clone(),
         grant.initial_streams,
         receiver_capacity,
+        dial_request,
         source,
         instruments,
         phase_trace,
@@ -1436,6 +1478,7 @@ pub(super) async fn dial_source_data_plane(
 pub(super) async fn accept_source_data_plane(
     bound: ResponderDataPlane,
     receiver_capacity: Option<&CapacityProfile>,
+    dial_request: Option<&DialRequest>,
     source: Arc<dyn TransferSource>,
     instruments: &SourceInstruments,
     phase_trace: Option<BoundSessionPhaseTrace>,
@@ -1449,6 +1492,7 @@ pub(super) async fn accept_source_data_plane(
         bound.epoch0_sub_token,
         bound.initial_streams,
         receiver_capacity,
+        dial_request,
         source,
         instruments,
         phase_trace,
@@ -1869,6 +1913,16 @@ impl SourceDataPlane {
                 },
             );
         }
+        if let (Some(accumulator), Some(probes)) = (&self.stream_bytes, &self.terminal_probes) {
+            let probes = probes
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            let mut streams = stream_bytes_of(&probes);
+            accumulator
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .append(&mut streams);
+        }
         Ok(elastic.outcome)
     }
 
@@ -1958,7 +2012,10 @@ impl NeedListSink {
         }
     }
 
-    fn validate_and_claim_header(&self, payload: &FileHeader) -> Result<()> {
+    /// Data-plane records carry no creation time, so a validated header
+    /// adopts the manifest's `crtime_seconds` (`--crtimes`) before the
+    /// inner sink writes it.
+    fn validate_and_claim_header(&self, payload: &mut FileHeader) -> Result<()> {
         let manifest = self
             .granted_headers
             .lock()
@@ -1999,6 +2056,7 @@ impl NeedListSink {
                 .with_path(payload.relative_path.as_str()),
             ));
         }
+        payload.crtime_seconds = manifest.crtime_seconds;
         self.granted_headers
             .lock()
             .expect("granted-headers lock poisoned")
@@ -2006,13 +2064,13 @@ impl NeedListSink {
         self.claim(&payload.relative_path)
     }
 
-    fn validate_and_claim_shard_headers(&self, payloads: &[FileHeader]) -> Result<()> {
+    fn validate_and_claim_shard_headers(&self, payloads: &mut [FileHeader]) -> Result<()> {
         {
             let manifests = self
                 .granted_headers
                 .lock()
                 .expect("granted-headers lock poisoned");
-            for payload in payloads {
+            for payload in payloads.iter_mut() {
                 let manifest = manifests.get(&payload.relative_path).ok_or_else(|| {
                     eyre::Report::new(
                         SessionFault::protocol_violation(format!(
@@ -2047,6 +2105,7 @@ impl NeedListSink {
                         .with_path(payload.relative_path.as_str()),
                     ));
                 }
+                payload.crtime_seconds = manifest.crtime_seconds;
             }
         }
         {
@@ -2054,7 +2113,7 @@ impl NeedListSink {
                 .granted_headers
                 .lock()
                 .expect("granted-headers lock poisoned");
-            for payload in payloads {
+            for payload in payloads.iter() {
                 manifests.remove(&payload.relative_path);
             }
         }
@@ -2306,14 +2365,14 @@ impl NeedListSink {
 
 #[async_trait]
 impl TransferSink for NeedListSink {
-    async fn write_payload(&self, payload: PreparedPayload) -> Result<SinkOutcome> {
-        match &payload {
+    async fn write_payload(&self, mut payload: PreparedPayload) -> Result<SinkOutcome> {
+        match &mut payload {
             PreparedPayload::File(header) => {
                 self.reject_resume_flagged(&header.relative_path)?;
                 self.validate_and_claim_header(header)?;
             }
             PreparedPayload::TarShard { headers, .. } => {
-                for header in headers {
+                for header in headers.iter() {
                     self.reject_resume_flagged(&header.relative_path)?;
                 }
                 self.validate_and_claim_shard_headers(headers)?;
@@ -2368,6 +2427,13 @@ impl TransferSink for NeedListSink {
                     "composite ResumeFile payload on the data-plane receive",
                 )));
             }
+            // Send-side shape; the receive pipeline hands tar-stream
+            // records to `write_tar_stream`.
+            PreparedPayload::TarStream { .. } => {
+                return Err(eyre::Report::new(SessionFault::protocol_violation(
+                    "send-side TarStream payload on the data-plane receive",
+                )));
+            }
         }
         // Tag the inner write's failure with the file it concerned
         // (otp-7b-2) where the payload names exactly one file.
@@ -2392,13 +2458,39 @@ impl TransferSink for NeedListSink {
         reader: &mut (dyn tokio::io::AsyncRead + Unpin + Send),
     ) -> Result<SinkOutcome> {
         self.reject_resume_flagged(&header.relative_path)?;
-        self.validate_and_claim_header(header)?;
+        let mut header = header.clone();
+        self.validate_and_claim_header(&mut header)?;
         self.inner
-            .write_file_stream(header, reader)
+            .write_file_stream(&header, reader)
             .await
             .map_err(|e| super::tag_path(e, &header.relative_path))
     }
 
+    /// Every coalesced file is checked against the need list before the
+    /// inner sink writes any of them; the inner sink names a failed write.
+    async fn write_small_files(
+        &self,
+        mut files: Vec<(FileHeader, Vec<u8>)>,
+    ) -> Result<SinkOutcome> {
+        for (header, _) in &mut files {
+            self.reject_resume_flagged(&header.relative_path)?;
+            self.validate_and_claim_header(header)?;
+        }
+        self.inner.write_small_files(files).await
+    }
+
+    async fn write_tar_stream(
+        &self,
+        mut headers: Vec<FileHeader>,
+        reader: &mut (dyn tokio::io::AsyncRead + Unpin + Send),
+    ) -> Result<SinkOutcome> {
+        for header in &headers {
+            self.reject_resume_flagged(&header.relative_path)?;
+        }
+        self.validate_and_claim_shard_headers(&mut headers)?;
+        self.inner.write_tar_stream(headers, reader).await
+    }
+
     async fn finish(&self) -> Result<()> {
         self.inner.finish().await
     }
@@ -2442,17 +2534,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn stream_bytes_cover_every_retained_probe_in_id_order() {
+        let added = StreamProbe::new(StreamId(2));
+        added.record_bytes(30);
+        let first = StreamProbe::new(StreamId(0));
+        first.record_bytes(10);
+        let retired = StreamProbe::new(StreamId(1));
+        retired.record_bytes(20);
+
+        assert_eq!(
+            stream_bytes_of(&[added, first, retired]),
+            vec![
+                StreamBytes {
+                    stream: StreamId(0),
+                    bytes: 10,
+                },
+                StreamBytes {
+                    stream: StreamId(1),
+                    bytes: 20,
+                },
+                StreamBytes {
+                    stream: StreamId(2),
+                    bytes: 30,
+                },
+            ]
+        );
+    }
+
     /// The grant starts at the canonical receiver-bounded floor without
     /// consulting manifest shape and carries two independent credentials.
     #[tokio::test]
     async fn responder_grant_uses_receiver_bounded_floor_with_16_byte_tokens() {
-        let rdp = prepare_responder_data_plane(None)
+        let rdp = prepare_responder_data_plane(None, None)
             .await
             .expect("bind loopback data plane");
         let grant = rdp.grant();
         assert_eq!(
             grant.initial_streams as usize,
-            receiver_initial_streams(None),
+            crate::dial::receiver_initial_streams(None),
             "epoch 0 is the conservative default floor"
         );
         assert_eq!(grant.session_token.len(), SUB_TOKEN_LEN);
@@ -2538,6 +2658,7 @@ mod tests {
             },
             phase_trace: None,
             terminal_probes: None,
+            stream_bytes: None,
             small_file_probe: None,
             queue_trace_armed: AtomicBool::new(false),
         };
@@ -2591,6 +2712,7 @@ mod tests {
             },
             phase_trace: None,
             terminal_probes: None,
+            stream_bytes: None,
             small_file_probe: None,
             queue_trace_armed: AtomicBool::new(false),
         };
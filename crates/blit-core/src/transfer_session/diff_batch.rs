@@ -0,0 +1,221 @@
+//! How the DESTINATION batches the incoming manifest for its diff.
+//!
+//! Manifest entries are buffered until a batch is full, then diffed on
+//! the blocking pool and answered with one `NeedBatch`. A batch is full
+//! at [`DiffBatchLimits::max_entries`] entries or once its encoded
+//! headers reach [`DiffBatchLimits::max_bytes`], whichever comes first,
+//! so a tree of very long paths cannot grow one batch past the byte
+//! budget.
+//!
+//! Flow control: the diff runs inline in the receive loop, so no
+//! further manifest frame is read while a batch is being diffed or its
+//! `NeedBatch` sent. The bounded transport then backs up into the
+//! SOURCE's scan, and the destination holds at most one batch plus the
+//! transport's frame buffer, however wide the tree.
+
+use std::sync::OnceLock;
+
+use eyre::Context as _;
+use prost::Message as _;
+
+use crate::generated::FileHeader;
+use crate::remote::message_limits::{CHUNK_ENVELOPE_HEADROOM, TONIC_DEFAULT_MAX_MESSAGE_BYTES};
+
+/// Default entries per diff batch. The per-entry check is 2+ blocking
+/// syscalls, so it runs chunked on the blocking pool instead of inline
+/// per entry (the daemon push handler's `MANIFEST_CHECK_CHUNK`, w4-4).
+pub const DEFAULT_DIFF_BATCH_ENTRIES: usize = 128;
+
+/// Default encoded-manifest bytes per diff batch.
+pub const DEFAULT_DIFF_BATCH_BYTES: usize = 1024 * 1024;
+
+/// Smallest accepted byte budget; below this a batch holds a handful
+/// of entries and the per-batch round trip dominates.
+pub const MIN_DIFF_BATCH_BYTES: usize = 4 * 1024;
+
+/// Largest accepted byte budget. A batch's `NeedBatch` names at most
+/// every path in it, so this keeps the reply under the 4 MiB every
+/// peer decodes.
+pub const MAX_DIFF_BATCH_BYTES: usize = TONIC_DEFAULT_MAX_MESSAGE_BYTES - CHUNK_ENVELOPE_HEADROOM;
+
+/// Diff batch limits, installed once per process: by the `blit` CLI
+/// from the global `--manifest-batch-entries`/`--manifest-batch-size`
+/// flags and by the daemon from the `[daemon]`
+/// `manifest_batch_entries`/`manifest_batch_size` config keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffBatchLimits {
+    /// Entries that fill a batch.
+    pub max_entries: usize,
+    /// Encoded manifest bytes that fill a batch. One header larger than
+    /// this still forms a batch of its own.
+    pub max_bytes: usize,
+}
+
+impl Default for DiffBatchLimits {
+    fn default() -> Self {
+        Self {
+            max_entries: DEFAULT_DIFF_BATCH_ENTRIES,
+            max_bytes: DEFAULT_DIFF_BATCH_BYTES,
+        }
+    }
+}
+
+impl DiffBatchLimits {
+    /// Parse the operator-facing values shared by the CLI flags and the
+    /// daemon config keys: an entry count and a size (`1Mi`, `65536`;
+    /// see [`crate::fs_enum::parse_size`]). Unset values keep the
+    /// default.
+    pub fn parse(max_entries: Option<&str>, max_bytes: Option<&str>) -> eyre::Result<Self> {
+        let defaults = Self::default();
+        let max_entries = match max_entries {
+            Some(raw) => raw
+                .trim()
+                .parse::<usize>()
+                .with_context(|| format!("invalid manifest batch entries '{raw}'"))?,
+            None => defaults.max_entries,
+        };
+        if max_entries == 0 {
+            eyre::bail!("manifest batch entries must be at least 1");
+        }
+        let max_bytes = match max_bytes {
+            Some(raw) => {
+                let bytes = crate::fs_enum::parse_size(raw)
+                    .with_context(|| format!("invalid manifest batch size '{raw}'"))?;
+                usize::try_from(bytes)
+                    .with_context(|| format!("manifest batch size '{raw}' is too large"))?
+            }
+            None => defaults.max_bytes,
+        };
+        if !(MIN_DIFF_BATCH_BYTES..=MAX_DIFF_BATCH_BYTES).contains(&max_bytes) {
+            eyre::bail!(
+                "manifest batch size {max_bytes} is outside \
+                 {MIN_DIFF_BATCH_BYTES}..={MAX_DIFF_BATCH_BYTES} bytes"
+            );
+        }
+        Ok(Self {
+            max_entries,
+            max_bytes,
+        })
+    }
+}
+
+static DIFF_BATCH_LIMITS: OnceLock<DiffBatchLimits> = OnceLock::new();
+
+/// Install the process-wide limits. A second call is silently ignored
+/// (same contract as
+/// [`set_message_limits`](crate::remote::message_limits::set_message_limits)).
+pub fn set_diff_batch_limits(limits: DiffBatchLimits) {
+    let _ = DIFF_BATCH_LIMITS.set(limits);
+}
+
+/// The installed limits, or [`DiffBatchLimits::default`].
+pub fn diff_batch_limits() -> DiffBatchLimits {
+    DIFF_BATCH_LIMITS.get().copied().unwrap_or_default()
+}
+
+/// The manifest entries buffered for the next diff.
+pub(super) struct DiffBatch {
+    limits: DiffBatchLimits,
+    headers: Vec<FileHeader>,
+    bytes: usize,
+}
+
+impl DiffBatch {
+    pub(super) fn new(limits: DiffBatchLimits) -> Self {
+        Self {
+            limits,
+            headers: Vec::new(),
+            bytes: 0,
+        }
+    }
+
+    /// Buffer `header`; returns the whole batch once it is full.
+    pub(super) fn push(&mut self, header: FileHeader) -> Option<Vec<FileHeader>> {
+        self.bytes += header.encoded_len();
+        self.headers.push(header);
+        if self.headers.len() >= self.limits.max_entries || self.bytes >= self.limits.max_bytes {
+            Some(self.take())
+        } else {
+            None
+        }
+    }
+
+    /// Everything buffered so far (the tail at `ManifestComplete`).
+    pub(super) fn take(&mut self) -> Vec<FileHeader> {
+        self.bytes = 0;
+        std::mem::take(&mut self.headers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(path: String) -> FileHeader {
+        FileHeader {
+            relative_path: path,
+            size: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_accepts_limits_and_refuses_out_of_range_values() {
+        assert_eq!(
+            DiffBatchLimits::parse(None, None).unwrap(),
+            DiffBatchLimits::default()
+        );
+        let limits = DiffBatchLimits::parse(Some("4096"), Some("2Mi")).unwrap();
+        assert_eq!(limits.max_entries, 4096);
+        assert_eq!(limits.max_bytes, 2 << 20);
+
+        assert!(DiffBatchLimits::parse(Some("0"), None).is_err());
+        assert!(DiffBatchLimits::parse(Some("many"), None).is_err());
+        assert!(DiffBatchLimits::parse(None, Some("1Ki")).is_err());
+        assert!(DiffBatchLimits::parse(None, Some("8Mi")).is_err());
+    }
+
+    /// A very wide directory never buffers more than one batch: every
+    /// batch stops at the entry count or the byte budget, and nothing
+    /// is lost across the boundaries.
+    #[test]
+    fn wide_directory_batches_stay_within_both_limits() {
+        let limits = DiffBatchLimits {
+            max_entries: 500,
+            max_bytes: 16 * 1024,
+        };
+        let mut batch = DiffBatch::new(limits);
+        let mut batches = Vec::new();
+        let mut high_water = 0usize;
+        for i in 0..100_000 {
+            // Every tenth name is long, so some batches fill on bytes.
+            let name = if i % 10 == 0 {
+                format!("wide/{}-{i}", "x".repeat(200))
+            } else {
+                format!("wide/f{i}")
+            };
+            if let Some(full) = batch.push(header(name)) {
+                batches.push(full);
+            }
+            high_water = high_water.max(batch.headers.len());
+        }
+        batches.push(batch.take());
+
+        assert!(high_water < limits.max_entries);
+        assert_eq!(batches.iter().map(Vec::len).sum::<usize>(), 100_000);
+        for full in &batches {
+            assert!(full.len() <= limits.max_entries);
+            let bytes: usize = full.iter().map(|h| h.encoded_len()).sum();
+            let last = full.last().map_or(0, |h| h.encoded_len());
+            assert!(
+                bytes - last < limits.max_bytes,
+                "batch crossed the byte budget before its last entry"
+            );
+        }
+        let full_batches = &batches[..batches.len() - 1];
+        assert!(
+            full_batches.iter().any(|b| b.len() < limits.max_entries),
+            "the byte budget never cut a batch short"
+        );
+    }
+}
@@ -0,0 +1,824 @@
+//! Resumable control lane (`--reconnect`, contract v17).
+//!
+//! Without a lane, the session's frames ride one `Transfer` RPC and a
+//! control-stream drop ends the session, however healthy the TCP data
+//! plane is. A lane sits between the session drivers and that RPC: the
+//! drivers get an ordinary [`FrameTransport`] and never see a lane
+//! frame, while a pump task underneath numbers every session frame,
+//! keeps the ones the peer has not acknowledged, and — when the RPC
+//! breaks — re-attaches over a fresh one and replays what the peer
+//! missed. The session state itself (phase, negotiated grant, need
+//! lists, open data-plane sockets) never moves: both ends keep running
+//! their drivers through the outage, so resynchronizing is nothing more
+//! than the two `received` counts exchanged in `LaneAttach`.
+//!
+//! The initiator picks the lane id and redials; the responder (the
+//! daemon) holds the dropped lane in its [`LaneRegistry`] for the
+//! reconnect window, keyed by that id, and hands the re-attach RPC to
+//! it. Frame grammar: `proto/blit.proto` §Resumable control lane and
+//! `docs/TRANSFER_SESSION.md` §Resumable control lane.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use eyre::{bail, eyre, Result};
+use prost::Message;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use super::transport::{FrameRx, FrameTransport, FrameTx, GRPC_CHANNEL_FRAMES};
+use crate::generated::transfer_frame::Frame;
+use crate::generated::{session_error, LaneAck, LaneAttach, LaneClose, TransferFrame};
+
+/// gRPC metadata key marking a `Transfer` RPC that carries a lane, so
+/// the daemon's dispatcher can route it before reading a frame: value
+/// [`LANE_ATTACH`] on the RPC that opens the lane, [`LANE_RESUME`] on a
+/// re-attach (which serves no session of its own).
+pub const LANE_METADATA_KEY: &str = "blit-lane";
+pub const LANE_ATTACH: &str = "attach";
+pub const LANE_RESUME: &str = "resume";
+
+/// Length of the random lane id an initiator picks.
+pub const LANE_ID_LEN: usize = 16;
+
+/// Longest reconnect window a responder holds a dropped lane for,
+/// whatever the initiator asked.
+pub const MAX_RECONNECT_WINDOW: Duration = Duration::from_secs(600);
+
+/// An end acknowledges after this many session frames or bytes,
+/// whichever comes first. The byte bound must stay under
+/// [`MAX_REPLAY_BYTES`] or a sender waiting on room in its replay
+/// buffer could wait on an ack that never comes.
+const ACK_EVERY_FRAMES: u64 = 16;
+const ACK_EVERY_BYTES: usize = 8 * 1024 * 1024;
+
+/// Unacknowledged session bytes an end holds for replay before it stops
+/// taking frames from its session (the session then blocks on send, as
+/// it would under transport backpressure).
+const MAX_REPLAY_BYTES: usize = 64 * 1024 * 1024;
+
+/// How long a re-attach RPC may take to present its `LaneAttach`.
+const ATTACH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// First and longest pause between an initiator's redial attempts.
+const REDIAL_BACKOFF_START: Duration = Duration::from_millis(250);
+const REDIAL_BACKOFF_MAX: Duration = Duration::from_secs(4);
+
+/// How long a finished lane waits for the peer to close its half of the
+/// last RPC before it lets go.
+const CLOSE_GRACE: Duration = Duration::from_secs(5);
+
+/// Opens a fresh physical transport to the responder for a re-attach.
+/// The initiator's lane calls it after every drop; the transport it
+/// returns must not carry any frame yet.
+pub type Redial =
+    dyn Fn() -> Pin<Box<dyn Future<Output = Result<FrameTransport>> + Send>> + Send + Sync;
+
+/// The caller's side of a running lane.
+pub struct LaneHandle {
+    pump: JoinHandle<Result<()>>,
+    // Weak, so the handle alone never keeps the session direction open:
+    // the lane closes when the session drops its transport.
+    terminator: mpsc::WeakSender<TransferFrame>,
+}
+
+impl LaneHandle {
+    /// A sender into the lane's session direction, for the one frame an
+    /// outside party sends on the session's behalf: the `CANCELLED`
+    /// error when the session future is dropped on cancel. `None` once
+    /// the session has closed its sending half.
+    pub fn terminator(&self) -> Option<mpsc::Sender<TransferFrame>> {
+        self.terminator.upgrade()
+    }
+
+    /// [`terminator`](Self::terminator) for a party that outlives this
+    /// handle — the daemon's dispatcher, which cancels a served session
+    /// by dropping the future that owns it. Upgrade it before dropping
+    /// the session: the lane forwards the frame to whichever physical
+    /// transport is current, not the RPC that opened the lane.
+    pub fn weak_terminator(&self) -> mpsc::WeakSender<TransferFrame> {
+        self.terminator.clone()
+    }
+
+    /// Wait for the lane to finish once the session is done with its
+    /// transport: everything sent is acknowledged and both ends closed.
+    /// Errs when a drop could not be re-attached within the window.
+    pub async fn finish(self) -> Result<()> {
+        self.pump
+            .await
+            .map_err(|err| eyre!("control lane task failed: {err}"))?
+    }
+}
+
+/// Start a lane as the initiator over `physical`, a freshly opened
+/// transport: announce it with `LaneAttach{resume=false}` and return the
+/// transport the session drivers run over. After a drop the lane calls
+/// `redial` until it re-attaches or `window` runs out.
+pub async fn initiate(
+    mut physical: FrameTransport,
+    window: Duration,
+    redial: Box<Redial>,
+) -> Result<(FrameTransport, LaneHandle)> {
+    let lane_id = generate_lane_id()?;
+    physical
+        .send(lane_frame(Frame::LaneAttach(LaneAttach {
+            lane_id: lane_id.clone(),
+            resume: false,
+            received: 0,
+            reconnect_window_ms: window.as_millis().min(u32::MAX as u128) as u32,
+        })))
+        .await?;
+    Ok(spawn_lane(
+        physical,
+        window,
+        LaneEnd::Initiator { lane_id, redial },
+    ))
+}
+
+/// Generate a lane id. A missing system RNG is an error, never a
+/// guessable id: the id is the credential that re-attaches the lane.
+fn generate_lane_id() -> Result<Vec<u8>> {
+    use rand::{rngs::SysRng, TryRng};
+    let mut buf = vec![0u8; LANE_ID_LEN];
+    SysRng
+        .try_fill_bytes(&mut buf)
+        .map_err(|err| eyre!("system RNG unavailable: {err}"))?;
+    Ok(buf)
+}
+
+/// A re-attach RPC handed to the dropped lane it names.
+struct Reattach {
+    physical: FrameTransport,
+    peer_received: u64,
+}
+
+/// The responder's table of live lanes, keyed by lane id. The daemon
+/// holds one for its lifetime; a lane leaves it when its pump ends.
+#[derive(Clone, Default)]
+pub struct LaneRegistry {
+    lanes: Arc<StdMutex<HashMap<Vec<u8>, mpsc::Sender<Reattach>>>>,
+}
+
+impl LaneRegistry {
+    /// Accept a new lane over `physical`, whose first frame must be the
+    /// initiator's `LaneAttach{resume=false}`, and return the transport
+    /// the session drivers run over.
+    pub async fn respond(
+        &self,
+        mut physical: FrameTransport,
+    ) -> Result<(FrameTransport, LaneHandle)> {
+        let attach = match first_attach(&mut physical).await? {
+            attach if !attach.resume && attach.lane_id.len() == LANE_ID_LEN => attach,
+            _ => {
+                return Err(refuse(&mut physical, "expected a new control lane").await);
+            }
+        };
+        let (reattach_tx, reattach_rx) = mpsc::channel(1);
+        let registered = {
+            let mut lanes = self.lanes.lock().expect("lane registry poisoned");
+            match lanes.entry(attach.lane_id.clone()) {
+                Entry::Occupied(_) => false,
+                Entry::Vacant(slot) => {
+                    slot.insert(reattach_tx);
+                    true
+                }
+            }
+        };
+        if !registered {
+            return Err(refuse(&mut physical, "control lane id already in use").await);
+        }
+        let window =
+            Duration::from_millis(attach.reconnect_window_ms.into()).min(MAX_RECONNECT_WINDOW);
+        Ok(spawn_lane(
+            physical,
+            window,
+            LaneEnd::Responder {
+                reattach: reattach_rx,
+                _entry: RegistryEntry {
+                    lanes: Arc::clone(&self.lanes),
+                    lane_id: attach.lane_id,
+                },
+            },
+        ))
+    }
+
+    /// Hand a re-attach RPC to the lane it names. Its first frame must be
+    /// `LaneAttach{resume=true}`; an id this registry no longer holds —
+    /// never opened, finished, or expired — is refused with a
+    /// `SessionError` so the initiator stops redialing.
+    pub async fn reattach(&self, mut physical: FrameTransport) -> Result<()> {
+        let attach = first_attach(&mut physical).await?;
+        if !attach.resume {
+            return Err(refuse(&mut physical, "expected a control lane re-attach").await);
+        }
+        let lane = self
+            .lanes
+            .lock()
+            .expect("lane registry poisoned")
+            .get(&attach.lane_id)
+            .cloned();
+        let Some(lane) = lane else {
+            return Err(refuse(&mut physical, "unknown or expired control lane").await);
+        };
+        lane.send(Reattach {
+            physical,
+            peer_received: attach.received,
+        })
+        .await
+        .map_err(|_| eyre!("control lane ended before the re-attach arrived"))
+    }
+
+    /// Lanes currently held, live or waiting for a re-attach.
+    pub fn len(&self) -> usize {
+        self.lanes.lock().expect("lane registry poisoned").len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Removes a responder lane from its registry when the pump ends.
+struct RegistryEntry {
+    lanes: Arc<StdMutex<HashMap<Vec<u8>, mpsc::Sender<Reattach>>>>,
+    lane_id: Vec<u8>,
+}
+
+impl Drop for RegistryEntry {
+    fn drop(&mut self) {
+        if let Ok(mut lanes) = self.lanes.lock() {
+            lanes.remove(&self.lane_id);
+        }
+    }
+}
+
+async fn first_attach(physical: &mut FrameTransport) -> Result<LaneAttach> {
+    match tokio::time::timeout(ATTACH_TIMEOUT, physical.recv()).await {
+        Ok(Ok(Some(TransferFrame {
+            frame: Some(Frame::LaneAttach(attach)),
+        }))) => Ok(attach),
+        Ok(Ok(Some(_))) => Err(refuse(physical, "expected LaneAttach as the first frame").await),
+        Ok(Ok(None)) => bail!("control lane closed before LaneAttach"),
+        Ok(Err(err)) => Err(err),
+        Err(_) => bail!("no LaneAttach within {ATTACH_TIMEOUT:?}"),
+    }
+}
+
+/// Tell the peer why this lane RPC is refused and return the error.
+async fn refuse(physical: &mut FrameTransport, message: &str) -> eyre::Report {
+    let _ = physical
+        .send(super::session_error_frame(
+            session_error::Code::ProtocolViolation,
+            message,
+        ))
+        .await;
+    eyre!("{message}")
+}
+
+fn lane_frame(f: Frame) -> TransferFrame {
+    TransferFrame { frame: Some(f) }
+}
+
+enum LaneEnd {
+    Initiator {
+        lane_id: Vec<u8>,
+        redial: Box<Redial>,
+    },
+    Responder {
+        reattach: mpsc::Receiver<Reattach>,
+        _entry: RegistryEntry,
+    },
+}
+
+fn spawn_lane(
+    physical: FrameTransport,
+    window: Duration,
+    end: LaneEnd,
+) -> (FrameTransport, LaneHandle) {
+    let (session_tx, session_out) = mpsc::channel(GRPC_CHANNEL_FRAMES);
+    let (session_in, session_rx) = mpsc::channel(GRPC_CHANNEL_FRAMES);
+    let (tx, rx) = physical.split();
+    let pump = LanePump {
+        end,
+        window,
+        tx,
+        rx,
+        session_out,
+        session_in: Some(session_in),
+        replay: VecDeque::new(),
+        replay_bytes: 0,
+        acked: 0,
+        received: 0,
+        unacked_frames: 0,
+        unacked_bytes: 0,
+        local_closed: false,
+        peer_closed: false,
+    };
+    let handle = LaneHandle {
+        pump: tokio::spawn(pump.run()),
+        terminator: session_tx.downgrade(),
+    };
+    let transport = FrameTransport::new(
+        Box::new(LaneFrameTx { tx: session_tx }),
+        Box::new(LaneFrameRx { rx: session_rx }),
+    );
+    (transport, handle)
+}
+
+struct LaneFrameTx {
+    tx: mpsc::Sender<TransferFrame>,
+}
+
+#[async_trait]
+impl FrameTx for LaneFrameTx {
+    async fn send(&mut self, frame: TransferFrame) -> Result<()> {
+        self.tx
+            .send(frame)
+            .await
+            .map_err(|_| eyre!("control lane closed"))
+    }
+}
+
+/// The session's inbound half. The pump forwards a lane failure as one
+/// `Err` so the drivers fail with the reason rather than a bare close.
+struct LaneFrameRx {
+    rx: mpsc::Receiver<Result<TransferFrame>>,
+}
+
+#[async_trait]
+impl FrameRx for LaneFrameRx {
+    async fn recv(&mut self) -> Result<Option<TransferFrame>> {
+        self.rx.recv().await.transpose()
+    }
+}
+
+/// One lane's state, owned by its pump task. Session frames are
+/// counted from zero in each direction; `acked` is how many of ours the
+/// peer confirmed, so `replay[0]` is frame number `acked`.
+struct LanePump {
+    end: LaneEnd,
+    window: Duration,
+    tx: Box<dyn FrameTx>,
+    rx: Box<dyn FrameRx>,
+    session_out: mpsc::Receiver<TransferFrame>,
+    session_in: Option<mpsc::Sender<Result<TransferFrame>>>,
+    replay: VecDeque<TransferFrame>,
+    replay_bytes: usize,
+    acked: u64,
+    received: u64,
+    unacked_frames: u64,
+    unacked_bytes: usize,
+    local_closed: bool,
+    peer_closed: bool,
+}
+
+enum LaneEvent {
+    Outbound(Option<TransferFrame>),
+    Inbound(Result<Option<TransferFrame>>),
+    Reattach(Reattach),
+}
+
+async fn next_reattach(end: &mut LaneEnd) -> Reattach {
+    match end {
+        LaneEnd::Responder { reattach, .. } => match reattach.recv().await {
+            Some(reattach) => reattach,
+            None => std::future::pending().await,
+        },
+        LaneEnd::Initiator { .. } => std::future::pending().await,
+    }
+}
+
+impl LanePump {
+    async fn run(mut self) -> Result<()> {
+        let result = self.pump().await;
+        if let Err(err) = &result {
+            if let Some(session_in) = self.session_in.take() {
+                let _ = session_in.send(Err(eyre!("{err:#}"))).await;
+            }
+        }
+        result
+    }
+
+    async fn pump(&mut self) -> Result<()> {
+        loop {
+            if self.local_closed && self.peer_closed && self.replay.is_empty() {
+                self.linger().await;
+                return Ok(());
+            }
+            let take_outbound = !self.local_closed && self.replay_bytes < MAX_REPLAY_BYTES;
+            let event = tokio::select! {
+                frame = self.session_out.recv(), if take_outbound => LaneEvent::Outbound(frame),
+                inbound = self.rx.recv() => LaneEvent::Inbound(inbound),
+                reattach = next_reattach(&mut self.end) => LaneEvent::Reattach(reattach),
+            };
+            let delivered = match event {
+                LaneEvent::Outbound(Some(frame)) => {
+                    self.replay_bytes += frame.encoded_len();
+                    self.replay.push_back(frame.clone());
+                    self.tx.send(frame).await.is_ok()
+                }
+                LaneEvent::Outbound(None) => {
+                    self.local_closed = true;
+                    self.tx
+                        .send(lane_frame(Frame::LaneClose(LaneClose {})))
+                        .await
+                        .is_ok()
+                }
+                LaneEvent::Inbound(Ok(Some(frame))) => self.on_inbound(frame).await?,
+                LaneEvent::Inbound(Ok(None)) | LaneEvent::Inbound(Err(_)) => false,
+                LaneEvent::Reattach(reattach) => self.install(reattach).await?,
+            };
+            if !delivered {
+                self.recover().await?;
+            }
+        }
+    }
+
+    /// Handle one frame from the peer. `Ok(false)` means the physical
+    /// transport failed while answering it.
+    async fn on_inbound(&mut self, frame: TransferFrame) -> Result<bool> {
+        match frame.frame {
+            Some(Frame::LaneAck(ack)) => {
+                self.trim(ack.received)?;
+                Ok(true)
+            }
+            Some(Frame::LaneClose(_)) => {
+                // Drop the session's inbound sender: its next recv reads
+                // as the peer's clean close. A repeat after a re-attach
+                // is expected and only re-acknowledged.
+                self.peer_closed = true;
+                self.session_in = None;
+                Ok(self.send_ack().await)
+            }
+            Some(Frame::LaneAttach(_)) => {
+                bail!("control lane protocol violation: LaneAttach mid-lane")
+            }
+            frame => {
+                if self.peer_closed {
+                    bail!("control lane protocol violation: session frame after LaneClose");
+                }
+                let frame = TransferFrame { frame };
+                self.received += 1;
+                self.unacked_frames += 1;
+                self.unacked_bytes += frame.encoded_len();
+                if let Some(session_in) = &self.session_in {
+                    // A session that already hung up its receive half
+                    // has no use for the frame; it is still counted.
+                    if session_in.send(Ok(frame)).await.is_err() {
+                        self.session_in = None;
+                    }
+                }
+                if self.unacked_frames >= ACK_EVERY_FRAMES || self.unacked_bytes >= ACK_EVERY_BYTES
+                {
+                    return Ok(self.send_ack().await);
+                }
+                Ok(true)
+            }
+        }
+    }
+
+    async fn send_ack(&mut self) -> bool {
+        self.unacked_frames = 0;
+        self.unacked_bytes = 0;
+        self.tx
+            .send(lane_frame(Frame::LaneAck(LaneAck {
+                received: self.received,
+            })))
+            .await
+            .is_ok()
+    }
+
+    /// Drop every buffered frame the peer reports received.
+    fn trim(&mut self, peer_received: u64) -> Result<()> {
+        let sent = self.acked + self.replay.len() as u64;
+        if peer_received < self.acked || peer_received > sent {
+            bail!(
+                "control lane protocol violation: peer reports {peer_received} frames received, \
+                 {} acknowledged and {sent} sent",
+                self.acked
+            );
+        }
+        while self.acked < peer_received {
+            if let Some(frame) = self.replay.pop_front() {
+                self.replay_bytes -= frame.encoded_len();
+            }
+            self.acked += 1;
+        }
+        Ok(())
+    }
+
+    /// Re-establish the physical transport after a drop, within the
+    /// window, and resynchronize both directions over it.
+    async fn recover(&mut self) -> Result<()> {
+        loop {
+            log::warn!(
+                "control stream dropped; re-attaching within {:?}",
+                self.window
+            );
+            let reattach = self.reestablish().await?;
+            if self.install(reattach).await? {
+                log::info!("control stream re-attached");
+                return Ok(());
+            }
+        }
+    }
+
+    async fn reestablish(&mut self) -> Result<Reattach> {
+        let deadline = Instant::now() + self.window;
+        let window = self.window;
+        let lost = || eyre!("control stream lost and not re-established within {window:?}");
+        match &mut self.end {
+            LaneEnd::Responder { reattach, .. } => {
+                match tokio::time::timeout_at(deadline, reattach.recv()).await {
+                    Ok(Some(reattach)) => Ok(reattach),
+                    Ok(None) | Err(_) => Err(lost()),
+                }
+            }
+            LaneEnd::Initiator { lane_id, redial } => {
+                let mut backoff = REDIAL_BACKOFF_START;
+                loop {
+                    let attempt = redial_once(redial.as_ref(), lane_id, self.received);
+                    match tokio::time::timeout_at(deadline, attempt).await {
+                        Err(_) => return Err(lost()),
+                        Ok(Ok(reattach)) => return Ok(reattach),
+                        Ok(Err(RedialError::Refused(err))) => return Err(err),
+                        Ok(Err(RedialError::Retry(err))) => {
+                            log::debug!("control stream redial failed: {err:#}");
+                        }
+                    }
+                    if Instant::now() + backoff >= deadline {
+                        return Err(lost());
+                    }
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(REDIAL_BACKOFF_MAX);
+                }
+            }
+        }
+    }
+
+    /// Switch to a re-attached transport and replay what the peer
+    /// missed. A responder answers the initiator's `LaneAttach` first
+    /// (the initiator's was already read). `Ok(false)` means the new
+    /// transport failed too.
+    async fn install(&mut self, reattach: Reattach) -> Result<bool> {
+        let Reattach {
+            physical,
+            peer_received,
+        } = reattach;
+        let (mut tx, rx) = physical.split();
+        if matches!(self.end, LaneEnd::Responder { .. }) {
+            let answer = lane_frame(Frame::LaneAttach(LaneAttach {
+                lane_id: Vec::new(),
+                resume: true,
+                received: self.received,
+                reconnect_window_ms: 0,
+            }));
+            if tx.send(answer).await.is_err() {
+                return Ok(false);
+            }
+        }
+        self.tx = tx;
+        self.rx = rx;
+        self.unacked_frames = 0;
+        self.unacked_bytes = 0;
+        self.trim(peer_received)?;
+        for frame in self.replay.iter().cloned() {
+            if self.tx.send(frame).await.is_err() {
+                return Ok(false);
+            }
+        }
+        if self.local_closed
+            && self
+                .tx
+                .send(lane_frame(Frame::LaneClose(LaneClose {})))
+                .await
+                .is_err()
+        {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Both ends are done: close this end's half of the RPC and give the
+    /// peer a moment to close its own, so neither side sees the last
+    /// RPC end as a drop.
+    async fn linger(&mut self) {
+        self.tx = Box::new(ClosedFrameTx);
+        let rx = &mut self.rx;
+        let _ = tokio::time::timeout(CLOSE_GRACE, async {
+            while let Ok(Some(_)) = rx.recv().await {}
+        })
+        .await;
+    }
+}
+
+/// Stands in for a released physical sender.
+struct ClosedFrameTx;
+
+#[async_trait]
+impl FrameTx for ClosedFrameTx {
+    async fn send(&mut self, _frame: TransferFrame) -> Result<()> {
+        bail!("control lane closed")
+    }
+}
+
+enum RedialError {
+    /// The responder no longer holds the lane; redialing cannot help.
+    Refused(eyre::Report),
+    Retry(eyre::Report),
+}
+
+async fn redial_once(
+    redial: &Redial,
+    lane_id: &[u8],
+    received: u64,
+) -> std::result::Result<Reattach, RedialError> {
+    let mut physical = redial().await.map_err(RedialError::Retry)?;
+    physical
+        .send(lane_frame(Frame::LaneAttach(LaneAttach {
+            lane_id: lane_id.to_vec(),
+            resume: true,
+            received,
+            reconnect_window_ms: 0,
+        })))
+        .await
+        .map_err(RedialError::Retry)?;
+    match physical.recv().await {
+        Ok(Some(TransferFrame {
+            frame: Some(Frame::LaneAttach(answer)),
+        })) if answer.resume => Ok(Reattach {
+            physical,
+            peer_received: answer.received,
+        }),
+        Ok(Some(TransferFrame {
+            frame: Some(Frame::Error(err)),
+        })) => Err(RedialError::Refused(eyre!(
+            "control stream re-attach refused: {}",
+            err.message
+        ))),
+        Ok(Some(other)) => Err(RedialError::Refused(eyre!(
+            "control lane protocol violation: expected LaneAttach, got {}",
+            super::frame_name(&other.frame)
+        ))),
+        Ok(None) => Err(RedialError::Retry(eyre!("re-attach RPC closed"))),
+        Err(err) => Err(RedialError::Retry(err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generated::SourceDone;
+    use crate::transfer_session::transport::in_process_pair;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    fn data_frame() -> TransferFrame {
+        lane_frame(Frame::SourceDone(SourceDone {}))
+    }
+
+    /// A physical transport whose sends fail once `cut` is set, and
+    /// whose receives then read as a dropped stream.
+    fn cuttable(transport: FrameTransport, cut: Arc<AtomicBool>) -> FrameTransport {
+        struct Tx(Box<dyn FrameTx>, Arc<AtomicBool>);
+        struct Rx(Box<dyn FrameRx>, Arc<AtomicBool>);
+        #[async_trait]
+        impl FrameTx for Tx {
+            async fn send(&mut self, frame: TransferFrame) -> Result<()> {
+                if self.1.load(Ordering::SeqCst) {
+                    bail!("cut");
+                }
+                self.0.send(frame).await
+            }
+        }
+        #[async_trait]
+        impl FrameRx for Rx {
+            async fn recv(&mut self) -> Result<Option<TransferFrame>> {
+                if self.1.load(Ordering::SeqCst) {
+                    bail!("cut");
+                }
+                let frame = self.0.recv().await;
+                if self.1.load(Ordering::SeqCst) {
+                    bail!("cut");
+                }
+                frame
+            }
+        }
+        let (tx, rx) = transport.split();
+        FrameTransport::new(Box::new(Tx(tx, Arc::clone(&cut))), Box::new(Rx(rx, cut)))
+    }
+
+    /// A redial that connects straight to `registry`'s re-attach path.
+    fn redial_into(registry: &LaneRegistry) -> Box<Redial> {
+        let registry = registry.clone();
+        Box::new(move || {
+            let registry = registry.clone();
+            Box::pin(async move {
+                let (client, server) = in_process_pair();
+                tokio::spawn(async move {
+                    let _ = registry.reattach(server).await;
+                });
+                Ok(client)
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn frames_sent_across_a_drop_arrive_once_and_in_order() {
+        let registry = LaneRegistry::default();
+        let cut = Arc::new(AtomicBool::new(false));
+        let (client, server) = in_process_pair();
+        let client = cuttable(client, Arc::clone(&cut));
+        let (responder, initiator) = tokio::join!(
+            registry.respond(server),
+            initiate(client, Duration::from_secs(10), redial_into(&registry)),
+        );
+        let (mut initiator, initiator_lane) = initiator.unwrap();
+        let (mut responder, responder_lane) = responder.unwrap();
+
+        for _ in 0..5 {
+            initiator.send(data_frame()).await.unwrap();
+        }
+        for _ in 0..5 {
+            responder.recv().await.unwrap().unwrap();
+        }
+        cut.store(true, Ordering::SeqCst);
+        for _ in 0..40 {
+            initiator.send(data_frame()).await.unwrap();
+            responder.send(data_frame()).await.unwrap();
+        }
+        for _ in 0..40 {
+            assert!(responder.recv().await.unwrap().is_some());
+            assert!(initiator.recv().await.unwrap().is_some());
+        }
+        drop((initiator, responder));
+        initiator_lane.finish().await.unwrap();
+        responder_lane.finish().await.unwrap();
+        assert!(registry.is_empty(), "a finished lane leaves the registry");
+    }
+
+    #[tokio::test]
+    async fn peer_close_reads_as_a_clean_close() {
+        let registry = LaneRegistry::default();
+        let (client, server) = in_process_pair();
+        let (responder, initiator) = tokio::join!(
+            registry.respond(server),
+            initiate(client, Duration::from_secs(10), redial_into(&registry)),
+        );
+        let (initiator, initiator_lane) = initiator.unwrap();
+        let (mut responder, responder_lane) = responder.unwrap();
+        drop(initiator);
+        assert!(responder.recv().await.unwrap().is_none());
+        drop(responder);
+        initiator_lane.finish().await.unwrap();
+        responder_lane.finish().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn unknown_lane_is_refused_and_the_drop_surfaces() {
+        let registry = LaneRegistry::default();
+        let cut = Arc::new(AtomicBool::new(false));
+        let (client, server) = in_process_pair();
+        let client = cuttable(client, Arc::clone(&cut));
+        // The redial reaches a registry that never saw this lane.
+        let stranger = LaneRegistry::default();
+        let (responder, initiator) = tokio::join!(
+            registry.respond(server),
+            initiate(client, Duration::from_secs(10), redial_into(&stranger)),
+        );
+        let (mut initiator, initiator_lane) = initiator.unwrap();
+        let (_responder, _responder_lane) = responder.unwrap();
+        cut.store(true, Ordering::SeqCst);
+        let _ = initiator.send(data_frame()).await;
+        let err = initiator.recv().await.unwrap_err();
+        assert!(
+            format!("{err:#}").contains("unknown or expired control lane"),
+            "{err:#}"
+        );
+        assert!(initiator_lane.finish().await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn responder_gives_up_after_the_window() {
+        let registry = LaneRegistry::default();
+        let (client, server) = in_process_pair();
+        let (responder, initiator) = tokio::join!(
+            registry.respond(server),
+            initiate(client, Duration::from_secs(3), redial_into(&registry)),
+        );
+        let (initiator, initiator_lane) = initiator.unwrap();
+        let (mut responder, responder_lane) = responder.unwrap();
+        // The initiator vanishes without closing its lane.
+        initiator_lane.pump.abort();
+        drop(initiator);
+        let err = responder.recv().await.unwrap_err();
+        assert!(format!("{err:#}").contains("not re-established"), "{err:#}");
+        assert!(responder_lane.finish().await.is_err());
+        assert!(registry.is_empty());
+    }
+}
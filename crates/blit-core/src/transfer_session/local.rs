@@ -23,18 +23,24 @@ use async_trait::async_trait;
 use eyre::{eyre, Context, Result};
 use tokio::sync::mpsc;
 
+use crate::copy::SparseMode;
+use crate::dedupe::DedupeStats;
+use crate::enumeration::SymlinkDeref;
 use crate::fs_enum::FileFilter;
-use crate::generated::{FileHeader, MirrorMode, SessionOpen, TransferRole};
+use crate::generated::{FileHeader, MirrorMode, RenameRule, SessionOpen, TransferRole};
 use crate::path_posix::relative_path_to_posix;
+use crate::path_rename::PathRenamer;
+use crate::perf_history::HistoryCheckpoint;
 use crate::remote::transfer::payload::{TransferPayload, DEFAULT_PAYLOAD_PREFETCH};
 use crate::remote::transfer::pipeline::execute_sink_pipeline_streaming;
 use crate::remote::transfer::sink::{
     FsSinkConfig, FsTransferSink, NullSink, SinkOutcome, TransferSink,
 };
 use crate::remote::transfer::source::{
-    FilteredSource, FsTransferSource, SourceScan, TransferSource,
+    FileCountWarning, FilteredSource, FsTransferSource, RenamingSource, SourceScan, TransferSource,
 };
-use crate::remote::transfer::{RemoteTransferProgress, SmallFileProbe};
+use crate::remote::transfer::{AbortOnDrop, RemoteTransferProgress, SmallFileProbe};
+use crate::source_snapshot::SourceSnapshot;
 use crate::transfer_plan::PlanOptions;
 
 use super::transport::in_process_pair;
@@ -150,9 +156,42 @@ pub struct LocalMirrorOptions {
     /// comparison mode. Orthogonal to `checksum`; matches the wire
     /// `ignore_existing` for full route parity.
     pub ignore_existing: bool,
+    /// `--existing` (`SessionOpen.existing_only`): skip any file the
+    /// destination does not already have, and create no directories.
+    pub existing_only: bool,
+    /// `--max-files` (`SessionOpen.max_files`): refuse a source of more
+    /// files before anything is copied. 0 = no cap.
+    pub max_files: u64,
+    /// `--warn-files`: ask before the scan goes past the threshold.
+    pub file_count_warning: Option<FileCountWarning>,
+    /// `--chmod` (`SessionOpen.chmod`): permission clauses applied to
+    /// every copied entry's mode. Empty = none.
+    pub chmod: String,
+    /// `--chown` (`SessionOpen.chown`): owner override for every copied
+    /// entry, applied when running as root. Empty = none.
+    pub chown: String,
+    /// `--xattrs` (`SessionOpen.xattrs`): the namespace filter spec of
+    /// the extended attributes carried across
+    /// ([`crate::xattrs::XattrFilter::spec`]). Empty = none.
+    pub xattrs: String,
+    /// `--metadata-only` (`SessionOpen.metadata_only`): copy no bytes,
+    /// only repair mtime/mode on content-equal files. Needs `checksum`.
+    pub metadata_only: bool,
     /// Explicitly discard Windows attributes and named data streams at the
     /// SOURCE. False preserves strictly.
     pub drop_windows_metadata: bool,
+    /// `--crtimes`: carry each file's creation time and set it on the
+    /// destination where the platform allows (macOS, Windows).
+    pub crtimes: bool,
+    /// `--super`: carry each entry's owner and mode and chown the copy
+    /// to them ([`crate::ownership`]).
+    pub owner: bool,
+    /// `--fake-super`: read carried owners from, and record them in, the
+    /// `user.blit.stat` xattr on both trees instead of chowning.
+    pub fake_super: bool,
+    /// `--prune-empty-dirs`: drop directories with no file under them
+    /// from the filtered manifest (`SessionOpen.prune_empty_dirs`).
+    pub prune_empty_dirs: bool,
     pub checksum: bool,
     /// R58-F7: comparison policy — `--size-only` / `--ignore-times` /
     /// `--force` honored on local copy/mirror the same way the remote
@@ -172,6 +211,56 @@ pub struct LocalMirrorOptions {
     /// Discard writes (NullSink). Measures source read + pipeline
     /// throughput.
     pub null_sink: bool,
+    /// Ordered `--rename` rules applied to each relative path before it
+    /// is written at the destination (the process-local twin of
+    /// `SessionOpen.rename`). Empty = names unchanged.
+    pub rename: Vec<RenameRule>,
+    /// Which source symlinks the scan dereferences (`--copy-links` /
+    /// `--copy-dirlinks` / `--copy-unsafe-links`).
+    pub symlink_deref: SymlinkDeref,
+    /// `--safe-links`: drop source symlinks that resolve outside the
+    /// source tree instead of dereferencing them.
+    pub safe_links: bool,
+    /// `--sparse` / `--no-sparse`: how file copies treat runs of zeros.
+    pub sparse: SparseMode,
+    /// `--preallocate`: reserve each streamed file's full size before
+    /// copying it. Ignored under `SparseMode::Always`.
+    pub preallocate: bool,
+    /// `--block-size`: fixed streaming-copy buffer and resume block
+    /// size in bytes. `0` keeps the derived sizes.
+    pub block_size: usize,
+    /// `--skip-locked`: a source file another process holds locked is
+    /// recorded in `unreadable_paths` and skipped instead of failing
+    /// the transfer (Windows sharing / lock violations).
+    pub skip_locked: bool,
+    /// `--vss` (Windows): enumerate and read the source through a Volume
+    /// Shadow Copy of its volume, deleted once the transfer ends.
+    pub vss: bool,
+    /// `--snapshot` (Unix): enumerate and read the source through a
+    /// temporary read-only btrfs / APFS snapshot; other filesystems warn
+    /// and read live. Ignored when `vss` is set.
+    pub snapshot: bool,
+    /// `--shard-max-bytes` / `--shard-max-files` / `--no-shard`: tar-shard
+    /// ceilings for the payload planner. Default keeps the derived tuning.
+    pub plan_options: PlanOptions,
+    /// `--delay-updates`: write every file under a staging directory and
+    /// rename the batch into place only once the whole apply succeeded
+    /// (and, for mirror, before the delete pass). Not valid with `resume`.
+    pub delay_updates: bool,
+    /// `--temp-dir`: stage `delay_updates` writes under this directory
+    /// instead of inside the destination. One on another filesystem
+    /// falls back to the default staging place (with a warning), since
+    /// the commit must stay a rename.
+    pub temp_dir: Option<PathBuf>,
+    /// `--dedupe`: once the transfer succeeds, hard-link identical files
+    /// under the destination together ([`crate::dedupe`]). Skipped on
+    /// dry runs and under `null_sink`.
+    pub dedupe: bool,
+    /// `--reflink-dest`: clone each needed file copy-on-write from this
+    /// reference tree when it holds an unchanged copy (see
+    /// [`FsSinkConfig::reflink_dest`]). Disables tar sharding, so every
+    /// file reaches the sink as its own payload.
+    pub reflink_dest: Option<PathBuf>,
 }
 
 impl Default for LocalMirrorOptions {
@@ -184,7 +273,18 @@ impl Default for LocalMirrorOptions {
             verbose: false,
             perf_history: true,
             ignore_existing: false,
+            existing_only: false,
+            max_files: 0,
+            chmod: String::new(),
+            chown: String::new(),
+            xattrs: String::new(),
+            file_count_warning: None,
+            metadata_only: false,
             drop_windows_metadata: false,
+            crtimes: false,
+            owner: false,
+            fake_super: false,
+            prune_empty_dirs: false,
             checksum: false,
             compare_mode: LocalCompareMode::default(),
             delete_scope: LocalMirrorDeleteScope::default(),
@@ -193,6 +293,20 @@ impl Default for LocalMirrorOptions {
             debug_mode: false,
             resume: false,
             null_sink: false,
+            rename: Vec::new(),
+            symlink_deref: SymlinkDeref::None,
+            safe_links: false,
+            sparse: SparseMode::Auto,
+            preallocate: false,
+            block_size: 0,
+            skip_locked: false,
+            vss: false,
+            snapshot: false,
+            plan_options: PlanOptions::default(),
+            delay_updates: false,
+            temp_dir: None,
+            dedupe: false,
+            reflink_dest: None,
         }
     }
 }
@@ -238,6 +352,10 @@ pub struct LocalMirrorSummary {
     pub raw_bundle_bytes: u64,
     pub large_tasks: usize,
     pub large_bytes: u64,
+    /// Estimated TCP data-plane bytes of the planned payloads — what a
+    /// push of the same changes would send, framing and tar overhead
+    /// included (see `payload_wire_bytes`).
+    pub wire_bytes: u64,
     /// Classifier for the CLI summary line.
     pub outcome: TransferOutcome,
     /// R47-F4: source-side paths that couldn't be scanned or read.
@@ -245,6 +363,11 @@ pub struct LocalMirrorSummary {
     /// `blit move`'s source-side delete — MUST inspect this and
     /// refuse when non-empty.
     pub unreadable_paths: Vec<String>,
+    /// Bytes file copies left as holes instead of writing (`--sparse`,
+    /// or the opportunistic sparse fallback). Zero under `--no-sparse`.
+    pub sparse_bytes: u64,
+    /// `--dedupe`: what the post-transfer pass linked together.
+    pub dedupe: DedupeStats,
 }
 
 /// Process-local destination extension: apply needed files in-process
@@ -284,6 +407,191 @@ pub struct LocalApply {
     pub(super) unreadable: Arc<StdMutex<Vec<String>>>,
     /// Counters the entry folds into [`LocalMirrorSummary`] afterward.
     pub(super) stats: Arc<LocalApplyStats>,
+    /// `--delay-updates`: the sink writes under this staging root and
+    /// the run's `finish()` commits it. `None` = writes land in place.
+    pub(super) delayed: Option<Arc<DelayedUpdates>>,
+}
+
+/// Name of the `--delay-updates` staging directory inside a directory
+/// destination. A stale one (an interrupted earlier run) is discarded
+/// before the next delayed run stages into it.
+pub const DELAY_UPDATES_DIR: &str = ".blit-delay-updates";
+
+/// The `--delay-updates` staging pair: the sink writes every payload
+/// under `stage_root` instead of `dst_root`; [`DelayedUpdates::commit`]
+/// renames the staged tree into place as one batch.
+///
+/// A directory destination stages under [`DELAY_UPDATES_DIR`] inside
+/// itself (same filesystem, so every commit is a plain rename). A
+/// file-root transfer (the source IS one file) stages at a hidden
+/// sibling of the target instead — the target path names a file, so
+/// there is no directory to stage inside. `--temp-dir` moves either
+/// staging place into that directory, named per destination so two
+/// runs sharing it don't discard each other's stage, as long as it is
+/// on the destination's filesystem.
+pub(super) struct DelayedUpdates {
+    stage_root: PathBuf,
+    dst_root: PathBuf,
+}
+
+impl DelayedUpdates {
+    fn new(src_root: &Path, dst_root: &Path, temp_dir: Option<&Path>) -> Result<Self> {
+        let file_root = std::fs::metadata(src_root)
+            .map(|meta| meta.is_file())
+            .unwrap_or(false);
+        if let Some(dir) = temp_dir {
+            if !dir.is_dir() {
+                return Err(eyre!("--temp-dir {} is not a directory", dir.display()));
+            }
+        }
+        let temp_dir = temp_dir.filter(|dir| {
+            let same = same_filesystem(dir, dst_root);
+            if !same {
+                log::warn!(
+                    "--temp-dir {} is not on the destination's filesystem; staging \
+                     beside the destination so the commit stays a rename",
+                    dir.display()
+                );
+            }
+            same
+        });
+        let stage_root = if let Some(dir) = temp_dir {
+            let key = blake3::hash(dst_root.as_os_str().as_encoded_bytes()).to_hex();
+            dir.join(format!("{DELAY_UPDATES_DIR}-{}", &key[..16]))
+        } else if file_root {
+            let name = dst_root
+                .file_name()
+                .ok_or_else(|| eyre!("destination has no file name: {}", dst_root.display()))?;
+            let mut staged = std::ffi::OsString::from(".");
+            staged.push(name);
+            staged.push(".blit-delay");
+            dst_root.with_file_name(staged)
+        } else {
+            dst_root.join(DELAY_UPDATES_DIR)
+        };
+        let delayed = Self {
+            stage_root,
+            dst_root: dst_root.to_path_buf(),
+        };
+        delayed.discard();
+        if !file_root {
+            // Staged elsewhere, the destination root would otherwise
+            // only appear once something was committed into it.
+            std::fs::create_dir_all(&delayed.dst_root).with_context(|| {
+                format!(
+                    "failed to create destination {}",
+                    delayed.dst_root.display()
+                )
+            })?;
+            std::fs::create_dir_all(&delayed.stage_root).with_context(|| {
+                format!(
+                    "failed to create staging directory {}",
+                    delayed.stage_root.display()
+                )
+            })?;
+        }
+        Ok(delayed)
+    }
+
+    /// Rename every staged entry to its final name, then drop the
+    /// staging root. Targets resolve through the same canonical
+    /// containment check as a direct write, so a destination-side
+    /// symlink can't redirect the batch outside the root.
+    fn commit(&self) -> Result<()> {
+        let meta = match std::fs::symlink_metadata(&self.stage_root) {
+            Ok(meta) => meta,
+            // Nothing was staged (every file up to date).
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => {
+                return Err(err).with_context(|| {
+                    format!("failed to stat staging root {}", self.stage_root.display())
+                })
+            }
+        };
+        if !meta.is_dir() {
+            return std::fs::rename(&self.stage_root, &self.dst_root).with_context(|| {
+                format!(
+                    "failed to commit delayed update to {}",
+                    self.dst_root.display()
+                )
+            });
+        }
+        let canonical = crate::path_safety::canonical_dest_root(&self.dst_root)?;
+        for entry in walkdir::WalkDir::new(&self.stage_root)
+            .min_depth(1)
+            .sort_by_file_name()
+        {
+            let entry = entry.with_context(|| {
+                format!("failed to walk staging root {}", self.stage_root.display())
+            })?;
+            if entry.file_type().is_dir() {
+                continue;
+            }
+            let rel = entry
+                .path()
+                .strip_prefix(&self.stage_root)
+                .expect("walkdir yields paths under its root");
+            let target = crate::path_safety::safe_join_contained(
+                &canonical,
+                &self.dst_root,
+                &relative_path_to_posix(rel),
+            )?;
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("failed to create {}", parent.display()))?;
+            }
+            std::fs::rename(entry.path(), &target).with_context(|| {
+                format!("failed to commit delayed update to {}", target.display())
+            })?;
+        }
+        std::fs::remove_dir_all(&self.stage_root).with_context(|| {
+            format!(
+                "failed to remove staging directory {}",
+                self.stage_root.display()
+            )
+        })
+    }
+
+    /// Best-effort removal of the staging root: a failed run leaves the
+    /// destination exactly as it was before the transfer started.
+    fn discard(&self) {
+        let result = match std::fs::symlink_metadata(&self.stage_root) {
+            Ok(meta) if meta.is_dir() => std::fs::remove_dir_all(&self.stage_root),
+            Ok(_) => std::fs::remove_file(&self.stage_root),
+            Err(_) => return,
+        };
+        if let Err(err) = result {
+            log::warn!(
+                "failed to remove staging area {}: {err}",
+                self.stage_root.display()
+            );
+        }
+    }
+}
+
+/// Whether a rename from `dir` to `target` stays on one filesystem.
+/// `target` may not exist yet, so its nearest existing ancestor stands
+/// in for it. Unknown (a missing `dir`, no ancestor) counts as not.
+fn same_filesystem(dir: &Path, target: &Path) -> bool {
+    let Some(existing) = target.ancestors().find(|p| p.exists()) else {
+        return false;
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        match (std::fs::metadata(dir), std::fs::metadata(existing)) {
+            (Ok(a), Ok(b)) => a.dev() == b.dev(),
+            _ => false,
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        // One volume shares one path prefix (drive letter or UNC share).
+        match (std::fs::canonicalize(dir), std::fs::canonicalize(existing)) {
+            (Ok(a), Ok(b)) => a.components().next() == b.components().next(),
+            _ => false,
+        }
+    }
 }
 
 /// Destination-side counters for the local summary. Atomics because
@@ -298,10 +606,29 @@ pub struct LocalApplyStats {
     pub(super) tar_shard_bytes: AtomicU64,
     pub(super) large_tasks: AtomicU64,
     pub(super) large_bytes: AtomicU64,
+    pub(super) wire_bytes: AtomicU64,
     pub(super) deleted_files: AtomicU64,
     pub(super) deleted_dirs: AtomicU64,
 }
 
+impl LocalApplyStats {
+    /// The counters so far, shaped as the summary a perf-history
+    /// checkpoint is built from.
+    fn progress_summary(&self, started: Instant) -> LocalMirrorSummary {
+        LocalMirrorSummary {
+            scanned_files: self.scanned_files.load(Ordering::Relaxed) as usize,
+            scanned_bytes: self.scanned_bytes.load(Ordering::Relaxed),
+            duration: started.elapsed(),
+            tar_shard_tasks: self.tar_shard_tasks.load(Ordering::Relaxed) as usize,
+            tar_shard_files: self.tar_shard_files.load(Ordering::Relaxed) as usize,
+            tar_shard_bytes: self.tar_shard_bytes.load(Ordering::Relaxed),
+            large_tasks: self.large_tasks.load(Ordering::Relaxed) as usize,
+            large_bytes: self.large_bytes.load(Ordering::Relaxed),
+            ..LocalMirrorSummary::default()
+        }
+    }
+}
+
 /// A running local-apply pipeline: the destination diff queues
 /// payloads, `finish()` closes the queue and joins the pipeline for
 /// the write totals (the same join discipline as the data-plane
@@ -313,6 +640,8 @@ pub struct LocalApplyStats {
 pub(super) struct LocalApplyRun {
     payload_tx: Option<mpsc::Sender<TransferPayload>>,
     pipeline: Option<tokio::task::JoinHandle<Result<SinkOutcome>>>,
+    /// Uncommitted `--delay-updates` staging; discarded on drop.
+    delayed: Option<Arc<DelayedUpdates>>,
 }
 
 impl Drop for LocalApplyRun {
@@ -320,6 +649,9 @@ impl Drop for LocalApplyRun {
         if let Some(handle) = &self.pipeline {
             handle.abort();
         }
+        if let Some(delayed) = self.delayed.take() {
+            delayed.discard();
+        }
     }
 }
 
@@ -351,6 +683,7 @@ impl LocalApply {
         LocalApplyRun {
             payload_tx: Some(payload_tx),
             pipeline: Some(pipeline),
+            delayed: self.delayed.clone(),
         }
     }
 
@@ -373,6 +706,10 @@ impl LocalApply {
             self.plan_options,
         )?;
         for payload in &payloads {
+            self.stats.wire_bytes.fetch_add(
+                crate::remote::transfer::data_plane::payload_wire_bytes(payload),
+                Ordering::Relaxed,
+            );
             match payload {
                 TransferPayload::TarShard { headers } => {
                     self.stats.tar_shard_tasks.fetch_add(1, Ordering::Relaxed);
@@ -414,15 +751,29 @@ impl LocalApplyRun {
 
     /// Close the queue and join the pipeline. Returns the write
     /// totals; surfaces the pipeline's own error as the root cause.
+    /// Under `--delay-updates` a clean join commits the staged batch
+    /// here — before the caller's mirror delete pass — and a failed one
+    /// discards it (via drop).
     pub(super) async fn finish(mut self) -> Result<SinkOutcome> {
         self.payload_tx.take();
         let pipeline = self
             .pipeline
             .take()
             .expect("local apply pipeline joined twice");
-        pipeline
+        let totals = pipeline
             .await
-            .map_err(|err| eyre!("local apply pipeline panicked: {err}"))?
+            .map_err(|err| eyre!("local apply pipeline panicked: {err}"))??;
+        if let Some(delayed) = self.delayed.take() {
+            let committing = Arc::clone(&delayed);
+            let committed = tokio::task::spawn_blocking(move || committing.commit())
+                .await
+                .map_err(|err| eyre!("delayed-update commit panicked: {err}"))?;
+            if let Err(err) = committed {
+                delayed.discard();
+                return Err(err);
+            }
+        }
+        Ok(totals)
     }
 }
 
@@ -483,6 +834,10 @@ impl TransferSource for DestSubtreeExcludedSource {
     fn root(&self) -> &Path {
         self.inner.root()
     }
+
+    fn local_path(&self, header: &FileHeader) -> Option<PathBuf> {
+        self.inner.local_path(header)
+    }
 }
 
 impl DestSubtreeExcludedSource {
@@ -538,11 +893,58 @@ pub async fn run_local_session(
     dst_root: &Path,
     options: LocalMirrorOptions,
 ) -> Result<LocalMirrorSummary> {
-    let started = Instant::now();
+    run_local_session_with_progress(src_root, dst_root, options, None).await
+}
 
+/// [`run_local_session`] reporting w6-1 progress events from the apply
+/// side — the destination instruments' `progress` hook, the same one a
+/// remote receive feeds. [`crate::transfer_facade`] is the embedder
+/// entry over it; `blit_app::transfers::local::run_with_progress` is
+/// the CLI's.
+pub async fn run_local_session_with_progress(
+    src_root: &Path,
+    dst_root: &Path,
+    options: LocalMirrorOptions,
+    progress: Option<RemoteTransferProgress>,
+) -> Result<LocalMirrorSummary> {
     if !src_root.exists() {
         return Err(eyre!("source path does not exist: {}", src_root.display()));
     }
+    let snapshot = if options.vss || options.snapshot {
+        let live = src_root.to_path_buf();
+        let vss = options.vss;
+        tokio::task::spawn_blocking(move || {
+            if vss {
+                SourceSnapshot::create_vss(&live).map(Some)
+            } else {
+                SourceSnapshot::create_fs(&live)
+            }
+        })
+        .await
+        .map_err(|err| eyre!("source snapshot worker panicked: {err}"))??
+    } else {
+        None
+    };
+    run_local_session_from(src_root, snapshot, dst_root, options, progress).await
+}
+
+/// The session body over an optional [`SourceSnapshot`]: enumeration
+/// and reads go through the snapshot's read root, while destination
+/// nesting is judged against the live `src_root` (the snapshot holds a
+/// frozen copy of a nested destination too). The snapshot is dropped —
+/// deleted — once the session has joined.
+pub(crate) async fn run_local_session_from(
+    src_root: &Path,
+    snapshot: Option<SourceSnapshot>,
+    dst_root: &Path,
+    options: LocalMirrorOptions,
+    progress: Option<RemoteTransferProgress>,
+) -> Result<LocalMirrorSummary> {
+    let started = Instant::now();
+    let live_root = src_root;
+    let src_root = snapshot
+        .as_ref()
+        .map_or(live_root, |snapshot| snapshot.read_root());
     if !options.dry_run {
         if let Some(parent) = dst_root.parent() {
             std::fs::create_dir_all(parent).with_context(|| {
@@ -566,7 +968,16 @@ pub async fn run_local_session(
         initiator_role: TransferRole::Source as i32,
         compare_mode: compare_mode as i32,
         ignore_existing: options.ignore_existing,
+        existing_only: options.existing_only,
+        max_files: options.max_files,
+        chmod: options.chmod.clone(),
+        chown: options.chown.clone(),
+        xattrs: options.xattrs.clone(),
+        metadata_only: options.metadata_only,
         drop_windows_metadata: options.drop_windows_metadata,
+        preserve_crtimes: options.crtimes,
+        preserve_owner: options.owner,
+        prune_empty_dirs: options.prune_empty_dirs,
         // The local carrier moves no bytes on any lane; in-stream keeps
         // the responder from binding a TCP data plane.
         in_stream_bytes: true,
@@ -582,27 +993,76 @@ pub async fn run_local_session(
     // Source chain: fs source → user filter (the universal
     // FilteredSource chokepoint, same as push/pull) → dest-subtree
     // exclusion when dst nests inside src.
-    let fs_source: Arc<dyn TransferSource> =
-        Arc::new(FsTransferSource::new(src_root.to_path_buf()));
+    let fs_source: Arc<dyn TransferSource> = Arc::new(
+        FsTransferSource::new(src_root.to_path_buf())
+            .with_symlink_deref(options.symlink_deref)
+            .with_safe_links(options.safe_links)
+            .with_sort_order(options.plan_options.sort_order)
+            .with_skip_locked(options.skip_locked)
+            .with_crtimes(options.crtimes)
+            .with_owner(options.owner, options.fake_super)
+            .with_xattrs(crate::xattrs::XattrFilter::from_spec(&options.xattrs)?),
+    );
     let filtered: Arc<dyn TransferSource> = Arc::new(FilteredSource::new(
         Arc::clone(&fs_source),
         options.filter.clone_without_cache(),
     ));
-    let scan_source: Arc<dyn TransferSource> = match dest_subtree_rel(src_root, dst_root) {
+    let scan_source: Arc<dyn TransferSource> = match dest_subtree_rel(live_root, dst_root) {
         Some(exclude_rel) => Arc::new(DestSubtreeExcludedSource {
             inner: filtered,
             exclude_rel,
         }),
         None => filtered,
     };
+    // `--rename` caps the chain here rather than riding the open (the
+    // filter's posture): the apply side needs the concrete decorator's
+    // rename map to prepare and copy each renamed entry from its
+    // original path.
+    let renaming = if options.rename.is_empty() {
+        None
+    } else {
+        Some(Arc::new(RenamingSource::new(
+            scan_source.clone(),
+            PathRenamer::from_rules(&options.rename)?,
+        )))
+    };
+    let (scan_source, prepare_source): (Arc<dyn TransferSource>, Arc<dyn TransferSource>) =
+        match renaming.as_ref() {
+            Some(renaming) => (
+                renaming.clone(),
+                Arc::new(RenamingSource::sharing(Arc::clone(&fs_source), renaming)),
+            ),
+            None => (scan_source, Arc::clone(&fs_source)),
+        };
+
+    // `--delay-updates` stages real writes only: a dry run or the null
+    // sink writes nothing to hold back.
+    if options.delay_updates && options.resume {
+        return Err(eyre!(
+            "--delay-updates cannot be combined with --resume: a resumed \
+             file continues in place, which is exactly what delaying forbids"
+        ));
+    }
+    let delayed = if options.delay_updates && !options.dry_run && !options.null_sink {
+        Some(Arc::new(DelayedUpdates::new(
+            src_root,
+            dst_root,
+            options.temp_dir.as_deref(),
+        )?))
+    } else {
+        None
+    };
 
     // Local write backend — the old orchestrator's exact construction.
+    let sparse_bytes = Arc::new(AtomicU64::new(0));
     let sink: Arc<dyn TransferSink> = if options.null_sink {
         Arc::new(NullSink::new())
     } else {
-        Arc::new(FsTransferSink::new(
+        let sink = FsTransferSink::new(
             src_root.to_path_buf(),
-            dst_root.to_path_buf(),
+            delayed
+                .as_ref()
+                .map_or_else(|| dst_root.to_path_buf(), |d| d.stage_root.clone()),
             FsSinkConfig {
                 preserve_times: options.preserve_times,
                 dry_run: options.dry_run,
@@ -613,16 +1073,31 @@ pub async fn run_local_session(
                 },
                 resume: options.resume,
                 compare_mode,
+                sparse: options.sparse,
+                block_size: options.block_size,
+                preallocate: options.preallocate,
+                reflink_dest: options.reflink_dest.clone(),
+                fsync: false,
             },
-        ))
+        )
+        .with_sparse_counter(Arc::clone(&sparse_bytes));
+        Arc::new(match renaming.as_ref() {
+            Some(renaming) => sink.with_renamed_sources(renaming.renamed_paths()),
+            None => sink,
+        })
     };
 
+    let mut plan_options = options.plan_options;
+    if options.reflink_dest.is_some() {
+        plan_options.no_shard = true;
+    }
     let stats = Arc::new(LocalApplyStats::default());
+    let checkpoint = start_history_checkpoint(&stats, &options, started);
     let local_apply = LocalApply {
         src_root: src_root.to_path_buf(),
         sink,
-        prepare_source: Arc::clone(&fs_source),
-        plan_options: PlanOptions::default(),
+        prepare_source,
+        plan_options,
         mirror_scope_filter: options.filter.clone_without_cache(),
         dry_run: options.dry_run,
         sink_workers: if options.debug_mode {
@@ -632,20 +1107,23 @@ pub async fn run_local_session(
         },
         unreadable: Arc::clone(&unreadable),
         stats: Arc::clone(&stats),
+        delayed,
     };
 
     let source_cfg = SourceSessionConfig {
         hello: HelloConfig::default(),
         endpoint: SessionEndpoint::initiator(open),
-        plan_options: PlanOptions::default(),
+        plan_options,
         data_plane_host: None,
         instruments: SourceInstruments {
             progress: None,
             unreadable: Some(Arc::clone(&unreadable)),
+            stream_bytes: None,
             trace_data_plane: false,
             session_phase_trace: Default::default(),
             lifecycle_trace: Default::default(),
             small_file_probe: SmallFileProbe::disabled(),
+            file_count_warning: options.file_count_warning.clone(),
             #[cfg(test)]
             dial_test_samples: None,
             #[cfg(test)]
@@ -660,12 +1138,16 @@ pub async fn run_local_session(
         hello: HelloConfig::default(),
         endpoint: SessionEndpoint::Responder,
         data_plane_host: None,
+        diff_batch: None,
         receiver_capacity: None,
         instruments: DestinationInstruments {
+            progress,
             small_file_probe: SmallFileProbe::disabled(),
             ..Default::default()
         },
         local_apply: Some(local_apply),
+        fake_super: options.fake_super,
+        receive_sink: None,
     };
 
     let (a, b) = in_process_pair();
@@ -710,7 +1192,7 @@ pub async fn run_local_session(
         TransferOutcome::Transferred
     };
 
-    let summary = LocalMirrorSummary {
+    let mut summary = LocalMirrorSummary {
         planned_files: outcome.needed_paths.len(),
         copied_files,
         total_bytes: outcome.summary.bytes_transferred,
@@ -728,25 +1210,88 @@ pub async fn run_local_session(
         raw_bundle_bytes: 0,
         large_tasks: stats.large_tasks.load(Ordering::Relaxed) as usize,
         large_bytes: stats.large_bytes.load(Ordering::Relaxed),
+        wire_bytes: stats.wire_bytes.load(Ordering::Relaxed),
         outcome: outcome_class,
         unreadable_paths,
+        sparse_bytes: sparse_bytes.load(Ordering::Relaxed),
+        dedupe: DedupeStats::default(),
     };
 
-    record_local_history(&summary, &options);
+    if options.dedupe && !options.dry_run && !options.null_sink {
+        let root = dst_root.to_path_buf();
+        summary.dedupe = tokio::task::spawn_blocking(move || crate::dedupe::dedupe_tree(&root))
+            .await
+            .map_err(|err| eyre!("dedupe pass panicked: {err}"))??;
+    }
+
+    let checkpoint = checkpoint.map(|(checkpoint, _ticker)| checkpoint);
+    record_local_history(&summary, &options, checkpoint.as_ref());
 
     Ok(summary)
 }
 
+/// Checkpoint the run's perf-history record every
+/// [`CHECKPOINT_INTERVAL`] from the live counters, so a run that dies
+/// mid-transfer still lands in the history (flagged `interrupted`).
+/// The ticker stops when the returned guard drops.
+///
+/// [`CHECKPOINT_INTERVAL`]: crate::perf_history::CHECKPOINT_INTERVAL
+fn start_history_checkpoint(
+    stats: &Arc<LocalApplyStats>,
+    options: &LocalMirrorOptions,
+    started: Instant,
+) -> Option<(HistoryCheckpoint, AbortOnDrop<()>)> {
+    if !options.perf_history {
+        return None;
+    }
+    let checkpoint = match HistoryCheckpoint::start() {
+        Ok(checkpoint) => checkpoint?,
+        Err(err) => {
+            if options.verbose {
+                eprintln!("Failed to start performance history checkpoint: {err:?}");
+            }
+            return None;
+        }
+    };
+    let writer = checkpoint.clone();
+    let stats = Arc::clone(stats);
+    let options = options.clone();
+    let ticker = AbortOnDrop::new(tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(crate::perf_history::CHECKPOINT_INTERVAL);
+        // The first tick is immediate; a run shorter than one interval
+        // never touches disk.
+        ticks.tick().await;
+        loop {
+            ticks.tick().await;
+            let record = build_local_record(&stats.progress_summary(started), &options);
+            let writer = writer.clone();
+            let written = tokio::task::spawn_blocking(move || writer.write(&record)).await;
+            if let Ok(Err(err)) = written {
+                if options.verbose {
+                    eprintln!("Failed to checkpoint performance history: {err:?}");
+                }
+            }
+        }
+    }));
+    Some((checkpoint, ticker))
+}
+
 /// Perf-history row for a local session run (D3 in the slice doc:
 /// `blit profile` keeps its local data feed; the predictor and its
 /// planner/transfer split retired with the engine, so the whole wall
 /// time lands in `transfer_duration_ms`).
-fn record_local_history(summary: &LocalMirrorSummary, options: &LocalMirrorOptions) {
+fn record_local_history(
+    summary: &LocalMirrorSummary,
+    options: &LocalMirrorOptions,
+    checkpoint: Option<&HistoryCheckpoint>,
+) {
     if !options.perf_history {
         return;
     }
     let record = build_local_record(summary, options);
-    if let Err(err) = crate::perf_history::append_local_record(&record) {
+    let recorded = crate::perf_history::append_local_record(&record)
+        .and_then(|()| checkpoint.map_or(Ok(()), HistoryCheckpoint::complete));
+    if let Err(err) = recorded {
         if options.verbose {
             eprintln!("Failed to update performance history: {err:?}");
         }
@@ -817,6 +1362,7 @@ mod tests {
     use super::*;
     use crate::generated::{ComparisonMode, TransferSummary};
     use crate::transfer_session::DestinationOutcome;
+    use tokio::sync::oneshot;
 
     /// Delegates scan/prepare/open to a real fs source but drops one
     /// path at `check_availability`, recording it unreadable — the
@@ -918,6 +1464,7 @@ mod tests {
             sink_workers: 1,
             unreadable: Arc::clone(&unreadable),
             stats: Arc::new(LocalApplyStats::default()),
+            delayed: None,
         };
         let source_cfg = SourceSessionConfig {
             hello: HelloConfig::default(),
@@ -927,10 +1474,12 @@ mod tests {
             instruments: SourceInstruments {
                 progress: None,
                 unreadable: Some(Arc::clone(&unreadable)),
+                stream_bytes: None,
                 trace_data_plane: false,
                 session_phase_trace: Default::default(),
                 lifecycle_trace: Default::default(),
                 small_file_probe: SmallFileProbe::disabled(),
+                file_count_warning: None,
                 #[cfg(test)]
                 dial_test_samples: None,
                 #[cfg(test)]
@@ -945,12 +1494,15 @@ mod tests {
             hello: HelloConfig::default(),
             endpoint: SessionEndpoint::Responder,
             data_plane_host: None,
+            diff_batch: None,
             receiver_capacity: None,
             instruments: DestinationInstruments {
                 small_file_probe: SmallFileProbe::disabled(),
                 ..Default::default()
             },
             local_apply: Some(local_apply),
+            fake_super: false,
+            receive_sink: None,
         };
         let (a, b) = in_process_pair();
         let scan_source: Arc<dyn TransferSource> =
@@ -1136,87 +1688,88 @@ mod tests {
         assert_eq!(count, 2, "the forwarded count excludes the subtree");
     }
 
-    /// The streaming-overlap property, ported from the engine's
-    /// `first_work_lands_before_enumeration_completes`: with more than
-    /// one diff chunk of files, the first destination writes land
-    /// while the source scan is still running. A gating source holds
-    /// the manifest stream open after `DEST_DIFF_CHUNK` + a few
-    /// entries until the test observes a file at the destination.
-    #[tokio::test]
-    async fn first_apply_lands_before_enumeration_completes() {
-        use tokio::sync::oneshot;
-
-        struct GatedSource {
-            inner: Arc<dyn TransferSource>,
-            gate: StdMutex<Option<oneshot::Receiver<()>>>,
-        }
+    /// Delegates to a real fs source but holds the manifest stream open
+    /// once `hold_after` entries are out, until the gate fires — lets a
+    /// test observe the destination mid-transfer.
+    struct GatedSource {
+        inner: Arc<dyn TransferSource>,
+        gate: StdMutex<Option<oneshot::Receiver<()>>>,
+        hold_after: u64,
+    }
 
-        #[async_trait]
-        impl TransferSource for GatedSource {
-            fn scan(
-                &self,
-                filter: Option<FileFilter>,
-                unreadable_paths: Arc<StdMutex<Vec<String>>>,
-            ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
-                let (mut inner_rx, mut scan) = self.inner.scan(filter, unreadable_paths);
-                let (tx, rx) = mpsc::channel(8);
-                let gate = self
-                    .gate
-                    .lock()
-                    .expect("gate lock")
-                    .take()
-                    .expect("scan called once");
-                let handle = tokio::spawn(async move {
-                    let mut forwarded = 0u64;
-                    let mut gate = Some(gate);
-                    while let Some(h) = inner_rx.recv().await {
-                        forwarded += 1;
-                        if tx.send(h).await.is_err() {
-                            break;
-                        }
-                        // Hold the manifest open once a full diff chunk
-                        // (plus slack) is out, until the gate fires.
-                        if forwarded == 160 {
-                            if let Some(g) = gate.take() {
-                                let _ = g.await;
-                            }
+    #[async_trait]
+    impl TransferSource for GatedSource {
+        fn scan(
+            &self,
+            filter: Option<FileFilter>,
+            unreadable_paths: Arc<StdMutex<Vec<String>>>,
+        ) -> (mpsc::Receiver<FileHeader>, SourceScan) {
+            let (mut inner_rx, mut scan) = self.inner.scan(filter, unreadable_paths);
+            let (tx, rx) = mpsc::channel(8);
+            let gate = self
+                .gate
+                .lock()
+                .expect("gate lock")
+                .take()
+                .expect("scan called once");
+            let hold_after = self.hold_after;
+            let handle = tokio::spawn(async move {
+                let mut forwarded = 0u64;
+                let mut gate = Some(gate);
+                while let Some(h) = inner_rx.recv().await {
+                    forwarded += 1;
+                    if tx.send(h).await.is_err() {
+                        break;
+                    }
+                    if forwarded == hold_after {
+                        if let Some(g) = gate.take() {
+                            let _ = g.await;
                         }
                     }
-                    Ok(forwarded)
-                });
-                scan.replace_primary(handle);
-                (rx, scan)
-            }
+                }
+                Ok(forwarded)
+            });
+            scan.replace_primary(handle);
+            (rx, scan)
+        }
 
-            async fn prepare_payload(
-                &self,
-                payload: TransferPayload,
-            ) -> eyre::Result<crate::remote::transfer::payload::PreparedPayload> {
-                self.inner.prepare_payload(payload).await
-            }
+        async fn prepare_payload(
+            &self,
+            payload: TransferPayload,
+        ) -> eyre::Result<crate::remote::transfer::payload::PreparedPayload> {
+            self.inner.prepare_payload(payload).await
+        }
 
-            async fn check_availability(
-                &self,
-                headers: Vec<FileHeader>,
-                unreadable_paths: Arc<StdMutex<Vec<String>>>,
-            ) -> eyre::Result<Vec<FileHeader>> {
-                self.inner
-                    .check_availability(headers, unreadable_paths)
-                    .await
-            }
+        async fn check_availability(
+            &self,
+            headers: Vec<FileHeader>,
+            unreadable_paths: Arc<StdMutex<Vec<String>>>,
+        ) -> eyre::Result<Vec<FileHeader>> {
+            self.inner
+                .check_availability(headers, unreadable_paths)
+                .await
+        }
 
-            async fn open_file(
-                &self,
-                header: &FileHeader,
-            ) -> eyre::Result<Box<dyn tokio::io::AsyncRead + Unpin + Send>> {
-                self.inner.open_file(header).await
-            }
+        async fn open_file(
+            &self,
+            header: &FileHeader,
+        ) -> eyre::Result<Box<dyn tokio::io::AsyncRead + Unpin + Send>> {
+            self.inner.open_file(header).await
+        }
 
-            fn root(&self) -> &Path {
-                self.inner.root()
-            }
+        fn root(&self) -> &Path {
+            self.inner.root()
         }
+    }
 
+    /// The streaming-overlap property, ported from the engine's
+    /// `first_work_lands_before_enumeration_completes`: with more than
+    /// one diff chunk of files, the first destination writes land
+    /// while the source scan is still running. A gating source holds
+    /// the manifest stream open after one default diff batch + a few
+    /// entries until the test observes a file at the destination.
+    #[tokio::test]
+    async fn first_apply_lands_before_enumeration_completes() {
         let tmp = tempfile::tempdir().expect("tempdir");
         let src_root = tmp.path().join("src");
         let dst_root = tmp.path().join("dst");
@@ -1244,9 +1797,12 @@ mod tests {
             false
         });
 
+        // Hold the manifest open once a full diff chunk (plus slack) is
+        // out, until the gate fires.
         let scan_source: Arc<dyn TransferSource> = Arc::new(GatedSource {
             inner: Arc::new(FsTransferSource::new(src_root.clone())),
             gate: StdMutex::new(Some(gate_rx)),
+            hold_after: 160,
         });
         let sink: Arc<dyn TransferSink> = Arc::new(FsTransferSink::new(
             src_root.clone(),
@@ -1263,6 +1819,7 @@ mod tests {
             sink_workers: 1,
             unreadable: Arc::default(),
             stats: Arc::new(LocalApplyStats::default()),
+            delayed: None,
         };
         let open = SessionOpen {
             initiator_role: TransferRole::Source as i32,
@@ -1281,9 +1838,12 @@ mod tests {
             hello: HelloConfig::default(),
             endpoint: SessionEndpoint::Responder,
             data_plane_host: None,
+            diff_batch: None,
             receiver_capacity: None,
             instruments: DestinationInstruments::default(),
             local_apply: Some(local_apply),
+            fake_super: false,
+            receive_sink: None,
         };
         let (a, b) = in_process_pair();
         let (source_result, dest_result) =
@@ -1303,4 +1863,289 @@ mod tests {
             "a destination write must land before enumeration completes"
         );
     }
+
+    /// `--delay-updates`: mid-transfer, staged files exist only under
+    /// the staging directory — no final-named new file and no mirror
+    /// deletion is visible until the whole apply succeeded; afterward
+    /// the batch is in place, the staging directory is gone, and the
+    /// delete pass ran after the commit.
+    #[tokio::test]
+    async fn delay_updates_holds_every_commit_until_the_transfer_completes() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let src_root = tmp.path().join("src");
+        let dst_root = tmp.path().join("dst");
+        std::fs::create_dir_all(&src_root).expect("mkdir");
+        std::fs::create_dir_all(&dst_root).expect("mkdir");
+        for i in 0..200 {
+            std::fs::write(src_root.join(format!("f{i:03}.txt")), b"payload").expect("write");
+        }
+        std::fs::write(dst_root.join("stale.txt"), b"stale").expect("write");
+
+        let delayed = Arc::new(DelayedUpdates::new(&src_root, &dst_root, None).expect("stage"));
+        let stage_root = delayed.stage_root.clone();
+        assert_eq!(stage_root, dst_root.join(DELAY_UPDATES_DIR));
+
+        let (gate_tx, gate_rx) = oneshot::channel();
+        // Watcher: once a file is staged, snapshot the destination's
+        // visible names, then release the scan.
+        let (dst_watch, stage_watch) = (dst_root.clone(), stage_root.clone());
+        let watcher = tokio::spawn(async move {
+            for _ in 0..1000 {
+                let staged = std::fs::read_dir(&stage_watch)
+                    .map(|d| d.count())
+                    .unwrap_or(0);
+                if staged > 0 {
+                    let mut visible: Vec<String> = std::fs::read_dir(&dst_watch)
+                        .expect("read dst")
+                        .map(|e| e.expect("entry").file_name().to_string_lossy().into_owned())
+                        .collect();
+                    visible.sort();
+                    let _ = gate_tx.send(());
+                    return Some(visible);
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+            None
+        });
+
+        let scan_source: Arc<dyn TransferSource> = Arc::new(GatedSource {
+            inner: Arc::new(FsTransferSource::new(src_root.clone())),
+            gate: StdMutex::new(Some(gate_rx)),
+            hold_after: 160,
+        });
+        let sink: Arc<dyn TransferSink> = Arc::new(FsTransferSink::new(
+            src_root.clone(),
+            stage_root.clone(),
+            FsSinkConfig::default(),
+        ));
+        let local_apply = LocalApply {
+            src_root: src_root.clone(),
+            sink,
+            prepare_source: Arc::new(FsTransferSource::new(src_root.clone())),
+            plan_options: PlanOptions::default(),
+            mirror_scope_filter: FileFilter::default(),
+            dry_run: false,
+            sink_workers: 1,
+            unreadable: Arc::default(),
+            stats: Arc::new(LocalApplyStats::default()),
+            delayed: Some(delayed),
+        };
+        let open = SessionOpen {
+            initiator_role: TransferRole::Source as i32,
+            compare_mode: ComparisonMode::SizeMtime as i32,
+            in_stream_bytes: true,
+            mirror_enabled: true,
+            mirror_kind: MirrorMode::All as i32,
+            ..Default::default()
+        };
+        let source_cfg = SourceSessionConfig {
+            hello: HelloConfig::default(),
+            endpoint: SessionEndpoint::initiator(open),
+            plan_options: PlanOptions::default(),
+            data_plane_host: None,
+            instruments: SourceInstruments::default(),
+        };
+        let dest_cfg = DestinationSessionConfig {
+            hello: HelloConfig::default(),
+            endpoint: SessionEndpoint::Responder,
+            data_plane_host: None,
+            diff_batch: None,
+            receiver_capacity: None,
+            instruments: DestinationInstruments::default(),
+            local_apply: Some(local_apply),
+            fake_super: false,
+            receive_sink: None,
+        };
+        let (a, b) = in_process_pair();
+        let (source_result, dest_result) =
+            tokio::time::timeout(std::time::Duration::from_secs(30), async {
+                tokio::join!(
+                    run_source(source_cfg, a, scan_source),
+                    run_destination(dest_cfg, b, DestinationTarget::Fixed(dst_root.clone())),
+                )
+            })
+            .await
+            .expect("session timed out");
+        source_result.expect("source");
+        let outcome = dest_result.expect("destination");
+        assert_eq!(outcome.summary.files_transferred, 200);
+
+        let visible = watcher
+            .await
+            .expect("watcher")
+            .expect("a file must be staged before the scan completes");
+        assert_eq!(
+            visible,
+            vec![DELAY_UPDATES_DIR.to_string(), "stale.txt".to_string()],
+            "mid-transfer, only the staging dir and the untouched old tree are visible"
+        );
+        for i in 0..200 {
+            assert!(dst_root.join(format!("f{i:03}.txt")).is_file());
+        }
+        assert!(!stage_root.exists(), "commit removes the staging directory");
+        assert!(
+            !dst_root.join("stale.txt").exists(),
+            "the mirror purge runs after the commit"
+        );
+    }
+
+    /// A file-root transfer stages at a hidden sibling of the target
+    /// (the target names a file, not a directory to stage inside).
+    #[test]
+    fn delay_updates_file_root_stages_beside_the_target() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let src = tmp.path().join("one.bin");
+        let dst = tmp.path().join("out.bin");
+        std::fs::write(&src, b"payload").expect("write");
+
+        let delayed = DelayedUpdates::new(&src, &dst, None).expect("stage");
+        assert_eq!(delayed.stage_root, tmp.path().join(".out.bin.blit-delay"));
+        std::fs::write(&delayed.stage_root, b"payload").expect("stage write");
+        assert!(!dst.exists());
+        delayed.commit().expect("commit");
+        assert_eq!(std::fs::read(&dst).expect("read"), b"payload");
+        assert!(!delayed.stage_root.exists());
+    }
+
+    /// Staging defaults to a directory inside the destination; a
+    /// `--temp-dir` on the same filesystem takes the stage instead and
+    /// the commit still lands every file; a missing one is refused.
+    #[test]
+    fn delay_updates_temp_dir_relocates_the_stage() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let src = tmp.path().join("src");
+        let dst = tmp.path().join("dst");
+        let temps = tmp.path().join("temps");
+        std::fs::create_dir_all(&src).expect("mkdir");
+        std::fs::create_dir_all(&temps).expect("mkdir");
+
+        let default = DelayedUpdates::new(&src, &dst, None).expect("stage");
+        assert_eq!(default.stage_root, dst.join(DELAY_UPDATES_DIR));
+        default.discard();
+
+        let delayed = DelayedUpdates::new(&src, &dst, Some(&temps)).expect("stage");
+        assert_eq!(delayed.stage_root.parent(), Some(temps.as_path()));
+        assert!(dst.is_dir(), "the destination root exists before commit");
+        std::fs::create_dir_all(delayed.stage_root.join("sub")).expect("mkdir");
+        std::fs::write(delayed.stage_root.join("sub/a.txt"), b"a").expect("stage write");
+        delayed.commit().expect("commit");
+        assert_eq!(std::fs::read(dst.join("sub/a.txt")).expect("read"), b"a");
+        assert!(!dst.join(DELAY_UPDATES_DIR).exists());
+        assert_eq!(std::fs::read_dir(&temps).expect("read_dir").count(), 0);
+
+        let err = DelayedUpdates::new(&src, &dst, Some(&tmp.path().join("missing")))
+            .err()
+            .expect("a missing --temp-dir is refused");
+        assert!(format!("{err:#}").contains("is not a directory"));
+    }
+
+    /// A source snapshot redirects enumeration and reads: the frozen
+    /// view lands (not the live tree's later edit, nor a file added
+    /// after the snapshot), and the snapshot is deleted once the
+    /// session has joined.
+    #[tokio::test]
+    async fn snapshot_reads_go_through_the_read_root_and_clean_up() {
+        use std::sync::atomic::AtomicBool;
+
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let live = tmp.path().join("live");
+        let frozen = tmp.path().join("frozen");
+        let dst = tmp.path().join("dst");
+        std::fs::create_dir_all(&live).expect("mkdir");
+        std::fs::create_dir_all(&frozen).expect("mkdir");
+        std::fs::write(live.join("a.txt"), b"live edit").expect("write");
+        std::fs::write(live.join("added-later.txt"), b"new").expect("write");
+        std::fs::write(frozen.join("a.txt"), b"frozen").expect("write");
+
+        let deleted = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&deleted);
+        let snapshot = crate::source_snapshot::fake_snapshot(frozen.clone(), move || {
+            flag.store(true, Ordering::SeqCst)
+        });
+        let options = LocalMirrorOptions {
+            perf_history: false,
+            ..Default::default()
+        };
+        let summary = run_local_session_from(&live, Some(snapshot), &dst, options, None)
+            .await
+            .expect("session");
+
+        assert_eq!(summary.copied_files, 1);
+        assert_eq!(std::fs::read(dst.join("a.txt")).expect("read"), b"frozen");
+        assert!(!dst.join("added-later.txt").exists());
+        assert!(
+            deleted.load(Ordering::SeqCst),
+            "snapshot deleted after the run"
+        );
+    }
+
+    fn rename_options(specs: &[&str]) -> LocalMirrorOptions {
+        let specs: Vec<String> = specs.iter().map(|s| s.to_string()).collect();
+        LocalMirrorOptions {
+            rename: crate::path_rename::parse_rules(&specs).expect("rules"),
+            perf_history: false,
+            ..LocalMirrorOptions::default()
+        }
+    }
+
+    /// `--rename` prefix strip over both local payload shapes: several
+    /// small files ride a tar shard (member names rewritten), a lone
+    /// file rides a File payload (the sink copies from the original
+    /// path through the rename map).
+    #[tokio::test]
+    async fn rename_prefix_strip_lands_under_rewritten_paths() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(src.join("build/out/lib")).expect("mkdir");
+        std::fs::write(src.join("build/out/app.bin"), b"app").expect("write");
+        std::fs::write(src.join("build/out/lib/a.so"), b"lib").expect("write");
+        std::fs::write(src.join("README"), b"readme").expect("write");
+        let dst = tmp.path().join("dst");
+        run_local_session(&src, &dst, rename_options(&["^build/out/="]))
+            .await
+            .expect("renamed copy");
+        assert_eq!(std::fs::read(dst.join("app.bin")).unwrap(), b"app");
+        assert_eq!(std::fs::read(dst.join("lib/a.so")).unwrap(), b"lib");
+        assert_eq!(std::fs::read(dst.join("README")).unwrap(), b"readme");
+        assert!(!dst.join("build").exists());
+
+        let single = tmp.path().join("single");
+        std::fs::create_dir_all(single.join("deep")).expect("mkdir");
+        std::fs::write(single.join("deep/only.bin"), b"only").expect("write");
+        let single_dst = tmp.path().join("single_dst");
+        run_local_session(&single, &single_dst, rename_options(&["^deep/="]))
+            .await
+            .expect("renamed single-file copy");
+        assert_eq!(std::fs::read(single_dst.join("only.bin")).unwrap(), b"only");
+    }
+
+    /// A rewrite that escapes the destination root is rejected by the
+    /// wire-path validator before anything is written.
+    #[tokio::test]
+    async fn rename_escaping_the_root_is_rejected() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(&src).expect("mkdir");
+        std::fs::write(src.join("victim.txt"), b"x").expect("write");
+        let dst = tmp.path().join("dst");
+        let err = run_local_session(&src, &dst, rename_options(&["^=../"]))
+            .await
+            .expect_err("escaping rewrite must fail");
+        assert!(format!("{err:#}").contains("unsafe path"), "{err:#}");
+        assert!(!tmp.path().join("victim.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn rename_collision_is_an_error() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(src.join("a")).expect("mkdir");
+        std::fs::create_dir_all(src.join("b")).expect("mkdir");
+        std::fs::write(src.join("a/x"), b"a").expect("write");
+        std::fs::write(src.join("b/x"), b"b").expect("write");
+        let err = run_local_session(&src, &tmp.path().join("dst"), rename_options(&["^[ab]/="]))
+            .await
+            .expect_err("colliding rewrite must fail");
+        assert!(format!("{err:#}").contains("collision"), "{err:#}");
+    }
 }
@@ -14,12 +14,14 @@
 //! delegated otp-9 (see the slice list in the plan).
 
 mod data_plane;
+pub mod diff_batch;
+pub mod lane;
 pub mod local;
 pub mod transport;
 
 pub use local::{
-    run_local_session, LocalCompareMode, LocalMirrorDeleteScope, LocalMirrorOptions,
-    LocalMirrorSummary, TransferOutcome,
+    run_local_session, run_local_session_with_progress, LocalCompareMode, LocalMirrorDeleteScope,
+    LocalMirrorOptions, LocalMirrorSummary, TransferOutcome, DELAY_UPDATES_DIR,
 };
 
 use std::collections::{HashMap, HashSet};
@@ -33,21 +35,23 @@ use std::sync::{Arc, Mutex as StdMutex};
 use eyre::Result;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::sync::{mpsc, watch};
+use tracing::Instrument;
 
-use crate::copy::DEFAULT_BLOCK_SIZE;
+use crate::copy::{SparseMode, DEFAULT_BLOCK_SIZE};
 use crate::generated::transfer_frame::Frame;
 use crate::generated::{
     session_error, BlockHashList, BlockTransfer, BlockTransferComplete, CapacityProfile,
     ComparisonMode, DataPlaneResize, DataPlaneResizeAck, DataPlaneResizeOp, FileData, FileHeader,
-    FilterSpec, ManifestComplete, MirrorMode, NeedBatch, NeedComplete, NeedEntry, SessionAccept,
-    SessionError, SessionHello, SessionOpen, SourceDone, TarShardComplete, TarShardHeader,
-    TransferFrame, TransferRole, TransferSummary,
+    FileOwner, FilterSpec, ManifestComplete, MirrorMode, NeedBatch, NeedComplete, NeedEntry,
+    NeedVanished, SessionAccept, SessionError, SessionHello, SessionOpen, SourceDone,
+    TarShardComplete, TarShardHeader, TransferFrame, TransferRole, TransferSummary,
 };
 use crate::manifest::{header_transfer_status, CompareMode, CompareOptions, FileStatus};
 use crate::remote::transfer::diff_planner;
 use crate::remote::transfer::payload::{PreparedPayload, TransferPayload};
 use crate::remote::transfer::session_phase::{
-    BoundSessionPhaseTrace, SessionPhaseFields, SessionPhaseRole, SessionPhaseTrace,
+    session_trace_id, BoundSessionPhaseTrace, SessionPhaseFields, SessionPhaseRole,
+    SessionPhaseTrace,
 };
 use crate::remote::transfer::sink::{FsSinkConfig, FsTransferSink, TransferSink};
 use crate::remote::transfer::small_file_probe::{
@@ -57,10 +61,13 @@ use crate::remote::transfer::small_file_probe::{
 use crate::remote::transfer::source::SourceScan;
 use crate::remote::transfer::source::{FsTransferSource, TransferSource};
 use crate::remote::transfer::stall_guard::TRANSFER_STALL_TIMEOUT;
-use crate::remote::transfer::tar_safety::MAX_TAR_SHARD_BYTES;
+use crate::remote::transfer::tar_safety::{
+    tar_shard_digest, verify_tar_shard_digest, MAX_TAR_SHARD_BYTES, TAR_SHARD_DIGEST_LEN,
+};
 use crate::remote::transfer::{
-    AbortOnDrop, FaultedPath, MembershipOutcome, RemoteTransferProgress, CONTROL_PLANE_CHUNK_SIZE,
+    AbortOnDrop, FaultedPath, MembershipOutcome, RemoteTransferProgress,
 };
+use crate::remote_options::RemoteOptions;
 use crate::transfer_plan::PlanOptions;
 use transport::{FrameRx, FrameTransport, FrameTx};
 
@@ -75,17 +82,50 @@ use transport::{FrameRx, FrameTransport, FrameTx};
 /// v4: explicit Windows settable attributes and bounded named `$DATA`
 /// descriptors/content on manifest and payload records (rel-4).
 /// v5: explicit source-side Windows metadata downgrade policy.
-pub const CONTRACT_VERSION: u32 = 5;
-
-/// Payload chunk size on the in-stream carrier. Same unit the gRPC
-/// control plane uses today; the data plane (otp-4) has its own.
-const IN_STREAM_CHUNK: usize = CONTROL_PLANE_CHUNK_SIZE;
-
-/// Manifest entries buffered per destination diff batch. Mirrors the
-/// daemon push handler's `MANIFEST_CHECK_CHUNK` rationale (w4-4): the
-/// per-entry check is 2+ blocking syscalls, so it runs chunked on the
-/// blocking pool instead of inline per entry.
-const DEST_DIFF_CHUNK: usize = 128;
+/// v6: `SessionOpen.rename` source-side path rewrite rules.
+/// v7: `SessionOpen.dial_request` operator stream/chunk pins.
+/// v8: tar shards carry a Blake3 of their archive bytes (TCP record
+/// and `TarShardHeader.blake3`), verified before unpack;
+/// `SessionError.Code::DATA_LOSS` on mismatch.
+/// v9: `SessionOpen.metadata_only` destination-side metadata repair.
+/// v10: `TAR_STREAM` data-plane record — a tiny-file batch as one tar
+/// archive in length-framed chunks, unpacked as it arrives.
+/// v11: `FileHeader.crtime_seconds` and `SessionOpen.preserve_crtimes`
+/// (`--crtimes` creation-time preservation).
+/// v12: `FileHeader.directory` manifest entries; the destination
+/// creates every source directory, empty ones included.
+/// v13: `SessionOpen.prune_empty_dirs` (`--prune-empty-dirs`).
+/// v14: `FileHeader.owner` and `SessionOpen.preserve_owner` (`--super`
+/// / `--fake-super` ownership preservation).
+/// v15: `NeedVanished` frame and `TransferSummary.vanished_paths` — a
+/// needed file deleted after the manifest is withdrawn, not fatal.
+/// v16: `SessionOpen.existing_only` (`--existing`).
+/// v17: `LaneAttach` / `LaneAck` / `LaneClose` — the resumable control
+/// lane under the session (`--reconnect`, [`lane`]).
+/// v18: `SessionOpen.max_files` and the `FILE_LIMIT` error code
+/// (`--max-files`).
+/// v19: `SessionOpen.checksum_seed` (`--checksum-seed`).
+/// v20: `SessionOpen.chmod` (`--chmod` permission overrides).
+/// v21: `SessionOpen.chown` (`--chown` ownership override).
+/// v22: `FileHeader.xattrs` and `SessionOpen.xattrs` (`--xattrs`
+/// namespace-filtered extended attributes).
+/// v23: `SessionOpen.remote_options` and
+/// `SessionAccept.ignored_remote_options` (`--remote-option`).
+/// v24: `TAR_STREAM` records end with a Blake3 of their archive bytes
+/// after the terminator; `DATA_LOSS` on mismatch.
+pub const CONTRACT_VERSION: u32 = 24;
+
+/// How long a DESTINATION initiator waits, after sending its summary,
+/// for the SOURCE responder to close its half.
+const SUMMARY_CLOSE_GRACE: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Payload chunk size on the in-stream carrier: the installed
+/// control chunk (default 1 MiB; see
+/// [`crate::remote::message_limits`]). The data plane (otp-4) has its
+/// own.
+fn in_stream_chunk() -> usize {
+    crate::remote::message_limits::message_limits().control_chunk_bytes
+}
 
 /// Buffer of the in-memory pipe that feeds wire file-record bytes
 /// into `FsTransferSink::write_file_stream`. Bounds destination-side
@@ -285,6 +325,10 @@ pub struct SourceInstruments {
     /// inspect this after the session returns. `None` = the session
     /// keeps its own private accumulator (unchanged behavior).
     pub unreadable: Option<Arc<StdMutex<Vec<String>>>>,
+    /// Per-stream payload bytes, filled when the TCP data plane closes
+    /// cleanly. Untouched on the in-stream carrier (no streams), so the
+    /// push verb reports an empty breakdown there.
+    pub stream_bytes: Option<crate::remote::transfer::SharedStreamBytes>,
     /// Emit `[data-plane-client]` connect traces on the data-plane
     /// sockets this SOURCE acquires (`--trace-data-plane`).
     pub trace_data_plane: bool,
@@ -298,6 +342,10 @@ pub struct SourceInstruments {
     /// High-volume aggregate observer for otp-12 small-file attribution.
     /// Separate from the low-frequency phase trace and disabled by default.
     pub small_file_probe: SmallFileProbe,
+    /// `--warn-files`: pause the manifest scan for a confirmation once
+    /// it passes the threshold. Process-local — only an end that owns
+    /// its operator (the initiating client) sets it.
+    pub file_count_warning: Option<crate::remote::transfer::source::FileCountWarning>,
     /// Deterministic sample source used only by in-crate role guards. The
     /// production build has no such field and always samples live probes.
     #[cfg(test)]
@@ -336,6 +384,10 @@ pub struct DestinationSessionConfig {
     /// same profile drives the wire advertisement, epoch-0 floor, and resize
     /// admission in either connection layout.
     pub receiver_capacity: Option<CapacityProfile>,
+    /// When this end's manifest diff batch is full
+    /// ([`diff_batch::DiffBatchLimits`]). `None` snapshots the
+    /// process-wide [`diff_batch::diff_batch_limits`] at session start.
+    pub diff_batch: Option<diff_batch::DiffBatchLimits>,
     /// Caller-side observability hooks (otp-10b-2). All default-off unless
     /// the caller or daemon responder attaches its own instruments. Symmetric
     /// with [`SourceSessionConfig::instruments`].
@@ -349,6 +401,20 @@ pub struct DestinationSessionConfig {
     /// peer can ever select it. `None` (every remote caller and the
     /// daemon responder) keeps the wire carriers exactly as before.
     pub local_apply: Option<local::LocalApply>,
+    /// `--fake-super`: record carried owners (`FileHeader.owner`) in
+    /// the [`crate::ownership::FAKE_SUPER_XATTR`] xattr instead of
+    /// chowning. Process-local; a resolving Responder also honors its
+    /// [`ResolvedEndpoint::fake_super`].
+    pub fake_super: bool,
+    /// Where received payload records land instead of the
+    /// [`FsTransferSink`] over the destination root — a pull streamed
+    /// into a pipe as one tar ([`crate::remote::transfer::ArchiveSink`]).
+    /// The session still diffs against, and runs its directory and
+    /// metadata passes over, the destination root, so a caller that
+    /// swaps the sink passes an empty scratch root. Process-local, like
+    /// `local_apply`, which wins when both are set. `None` = the
+    /// filesystem sink.
+    pub receive_sink: Option<Arc<dyn TransferSink>>,
 }
 
 /// Observability hooks a DESTINATION-side caller can attach to its
@@ -429,6 +495,14 @@ pub struct SessionFault {
     /// `--retry`. Local evidence only: faults received from the peer
     /// (`from_wire`) carry `None`.
     pub io_kind: Option<std::io::ErrorKind>,
+    /// This INITIATOR could not open its epoch-0 data-plane sockets —
+    /// the granted port refused or timed out, typically a firewall that
+    /// passes the control port but not the responder's ephemeral data
+    /// port. The sockets are set up before the manifest streams, so no
+    /// payload has moved and the caller may rerun the session on the
+    /// in-stream carrier (`session_client` does). Local evidence only:
+    /// `from_wire` faults carry `false`.
+    pub data_plane_unreachable: bool,
 }
 
 impl SessionFault {
@@ -441,6 +515,25 @@ impl SessionFault {
             peer_notified: false,
             relative_path: None,
             io_kind: None,
+            data_plane_unreachable: false,
+        }
+    }
+
+    /// BUILD_MISMATCH naming both identities. Raised by the HELLO
+    /// exchange, and before any session by the connect-time
+    /// `Capabilities` probe (`remote::capabilities`), so both surfaces
+    /// report a stale end identically.
+    pub fn build_mismatch(local: &HelloConfig, peer_build_id: &str, peer_contract: u32) -> Self {
+        Self {
+            local_build_id: local.build_id.clone(),
+            peer_build_id: peer_build_id.to_string(),
+            ..Self::new(
+                session_error::Code::BuildMismatch,
+                format!(
+                    "same-build peers required (D-2026-07-05-2): local {} (contract v{}) vs peer {} (contract v{})",
+                    local.build_id, local.contract_version, peer_build_id, peer_contract,
+                ),
+            )
         }
     }
 
@@ -498,6 +591,12 @@ impl SessionFault {
         Self::new(session_error::Code::ReadOnly, message)
     }
 
+    /// DATA_LOSS: a received record failed its integrity check
+    /// (contract v8 — a tar shard's Blake3; v24 — a tar stream's).
+    pub(crate) fn data_loss(message: impl Into<String>) -> Self {
+        Self::new(session_error::Code::DataLoss, message)
+    }
+
     /// Public constructor for a caller-side refusal (e.g. the daemon's
     /// [`OpenResolver`] mapping a `tonic::Status` to a `SessionError`
     /// code). blit-core stays free of `tonic::Status`, so the caller
@@ -521,6 +620,7 @@ impl SessionFault {
             // Peer-reported fault: no local I/O evidence (codex
             // otp-10a F5 — io_kind is local-transport testimony only).
             io_kind: None,
+            data_plane_unreachable: false,
         }
     }
 
@@ -595,6 +695,10 @@ fn frame_name(f: &Option<Frame>) -> &'static str {
         Some(Frame::SourceDone(_)) => "SourceDone",
         Some(Frame::Summary(_)) => "TransferSummary",
         Some(Frame::Error(_)) => "SessionError",
+        Some(Frame::NeedVanished(_)) => "NeedVanished",
+        Some(Frame::LaneAttach(_)) => "LaneAttach",
+        Some(Frame::LaneAck(_)) => "LaneAck",
+        Some(Frame::LaneClose(_)) => "LaneClose",
         None => "empty frame",
     }
 }
@@ -641,6 +745,10 @@ pub struct ResolvedEndpoint {
     /// responder refuses `READ_ONLY`; a SOURCE responder (otp-5,
     /// daemon-send) does not care — reading a read-only module is fine.
     pub read_only: bool,
+    /// Whether this end fakes ownership (`fake_super` on the module,
+    /// [`crate::ownership`]): a DESTINATION records carried owners in
+    /// an xattr instead of chowning, a SOURCE reads them back from it.
+    pub fake_super: bool,
 }
 
 /// Async callback a Responder uses to turn a received (and
@@ -712,9 +820,29 @@ fn source_open_validator(open: &SessionOpen) -> std::result::Result<(), SessionF
                 .map_err(|e| SessionFault::protocol_violation(format!("invalid filter: {e:#}")))?;
         }
     }
+    // v6: `--rename` rules compile at OPEN for the same reason — a bad
+    // regex is a refusal, not a mid-scan fault.
+    crate::path_rename::PathRenamer::from_rules(&open.rename)
+        .map_err(|e| SessionFault::protocol_violation(format!("invalid rename: {e:#}")))?;
+    // v20: likewise `--chmod`.
+    if !open.chmod.is_empty() {
+        crate::chmod::ChmodSpec::parse(&open.chmod)
+            .map_err(|e| SessionFault::protocol_violation(format!("{e:#}")))?;
+    }
+    open_xattr_filter(open)?;
     Ok(())
 }
 
+/// The session's `--xattrs` filter (contract v22), `None` when it
+/// carries no attributes. Both validators call it, so a malformed spec
+/// is refused at OPEN.
+fn open_xattr_filter(
+    open: &SessionOpen,
+) -> std::result::Result<Option<crate::xattrs::XattrFilter>, SessionFault> {
+    crate::xattrs::XattrFilter::from_spec(&open.xattrs)
+        .map_err(|e| SessionFault::protocol_violation(format!("invalid --xattrs filter: {e:#}")))
+}
+
 fn destination_open_validator(open: &SessionOpen) -> std::result::Result<(), SessionFault> {
     // otp-6b: mirror is executed on the DESTINATION (the end that owns the
     // dest tree). An enabled mirror needs a concrete scope; reject the
@@ -727,6 +855,21 @@ fn destination_open_validator(open: &SessionOpen) -> std::result::Result<(), Ses
             ));
         }
     }
+    // v9: metadata-only repair is keyed on content equality, which only
+    // a Checksum session's manifest hashes can prove; and a touch pass
+    // must never delete.
+    if open.metadata_only {
+        if open.compare_mode != ComparisonMode::Checksum as i32 {
+            return Err(SessionFault::protocol_violation(
+                "metadata_only requires COMPARISON_MODE_CHECKSUM",
+            ));
+        }
+        if open.mirror_enabled {
+            return Err(SessionFault::protocol_violation(
+                "metadata_only cannot be combined with mirror",
+            ));
+        }
+    }
     // The dest enumerates its tree through this filter when scoping a
     // FilteredSubset mirror, so its globs must be valid — validate at OPEN
     // (peer-notified refusal), symmetric with `source_open_validator`.
@@ -736,6 +879,13 @@ fn destination_open_validator(open: &SessionOpen) -> std::result::Result<(), Ses
                 .map_err(|e| SessionFault::protocol_violation(format!("invalid filter: {e:#}")))?;
         }
     }
+    // v21: `--chown` names resolve on this end, so an unknown one is a
+    // refusal at OPEN rather than a warning after the bytes landed.
+    if !open.chown.is_empty() {
+        crate::ownership::ChownOverride::resolve(&open.chown)
+            .map_err(|e| SessionFault::protocol_violation(format!("{e:#}")))?;
+    }
+    open_xattr_filter(open)?;
     Ok(())
 }
 
@@ -776,11 +926,18 @@ struct Negotiated {
     /// received open, if one was supplied; `None` for an Initiator or a
     /// fixed-root Responder (the caller supplies the root then).
     resolved_root: Option<PathBuf>,
+    /// Whether this end fakes ownership: the resolved endpoint's
+    /// setting on a Responder, the caller's on an Initiator
+    /// ([`DestinationSessionConfig::fake_super`]).
+    fake_super: bool,
     /// The bound data-plane listener + credentials a DESTINATION
     /// Responder prepared before its `SessionAccept` (otp-4b). `None`
     /// on an Initiator, or when the responder granted no data plane
     /// (in-stream carrier). Consumed by the DESTINATION accept loop.
     responder_data_plane: Option<data_plane::ResponderDataPlane>,
+    /// The `--remote-option`s this end honors: resolved for its role on
+    /// a Responder, always the defaults on an Initiator (contract v23).
+    remote_options: RemoteOptions,
 }
 
 fn bind_session_phase_trace(
@@ -799,6 +956,49 @@ fn bind_session_phase_trace(
         .bind(session_token, endpoint_role, initiator_role)
 }
 
+/// The `tracing` span one endpoint runs a session under. `negotiate`
+/// and `transfer` are its child phases (`scan` and `mirror_delete` nest
+/// in `transfer`); the role and session id are recorded once the open
+/// is settled, the byte and file counts once the summary is. Inert
+/// unless the embedding process installs a subscriber.
+fn session_span() -> tracing::Span {
+    tracing::info_span!(
+        "transfer_session",
+        session_id = tracing::field::Empty,
+        endpoint_role = tracing::field::Empty,
+        initiator_role = tracing::field::Empty,
+    )
+}
+
+/// The `transfer` phase of `session`, with the settled roles and
+/// session id recorded on that parent. The session id is the phase
+/// trace's, so the two records join; the in-stream carrier has no
+/// session token and leaves it empty.
+fn transfer_span(
+    session: &tracing::Span,
+    negotiated: &Negotiated,
+    endpoint_role: SessionPhaseRole,
+) -> tracing::Span {
+    session.record("endpoint_role", tracing::field::debug(endpoint_role));
+    if let Ok(initiator) = TransferRole::try_from(negotiated.open.initiator_role) {
+        session.record("initiator_role", initiator.as_str_name());
+    }
+    if let Some(token) = negotiated_session_token(negotiated) {
+        session.record("session_id", &*session_trace_id(token));
+    }
+    tracing::info_span!(
+        parent: session,
+        "transfer",
+        bytes = tracing::field::Empty,
+        files = tracing::field::Empty,
+    )
+}
+
+fn record_summary(span: &tracing::Span, summary: &TransferSummary) {
+    span.record("bytes", summary.bytes_transferred);
+    span.record("files", summary.files_transferred);
+}
+
 fn negotiated_session_token(negotiated: &Negotiated) -> Option<&[u8]> {
     negotiated
         .responder_data_plane
@@ -881,19 +1081,8 @@ async fn exchange_hello(transport: &mut FrameTransport, hello: &HelloConfig) ->
     if peer_hello.build_id != hello.build_id
         || peer_hello.contract_version != hello.contract_version
     {
-        let fault = SessionFault {
-            code: session_error::Code::BuildMismatch,
-            message: format!(
-                "same-build peers required (D-2026-07-05-2): local {} (contract v{}) vs peer {} (contract v{})",
-                hello.build_id, hello.contract_version,
-                peer_hello.build_id, peer_hello.contract_version,
-            ),
-            local_build_id: hello.build_id.clone(),
-            peer_build_id: peer_hello.build_id.clone(),
-            peer_notified: false,
-            relative_path: None,
-            io_kind: None,
-        };
+        let fault =
+            SessionFault::build_mismatch(hello, &peer_hello.build_id, peer_hello.contract_version);
         return Err(notify_and_wrap(transport, fault).await);
     }
     Ok(())
@@ -948,12 +1137,34 @@ async fn responder_finish(
         // never a silent close (contract §Phase state machine).
         return Err(notify_and_wrap(transport, fault).await);
     }
+    // v23: `--remote-option`s are this responder's to interpret. A
+    // known key it cannot apply refuses the session; an unknown one is
+    // warned about here and named back in the accept.
+    let (remote_options, ignored_remote_options) =
+        match RemoteOptions::resolve(&open.remote_options, local_role) {
+            Ok(resolved) => resolved,
+            Err(err) => {
+                return Err(notify_and_wrap(
+                    transport,
+                    SessionFault::protocol_violation(format!("{err:#}")),
+                )
+                .await)
+            }
+        };
+    for key in &ignored_remote_options {
+        let role = match local_role {
+            TransferRole::Source => "source",
+            _ => "destination",
+        };
+        log::warn!("ignoring remote option {key}: unknown to a {role} end");
+    }
     // Responder endpoint resolution (otp-4): map the wire
     // module/path to a local root and enforce read-only, both
     // BEFORE SessionAccept so a refusal replaces the accept
     // (never follows it). The resolver is caller-supplied
     // (daemon module lookup); a fixed-root responder passes
     // None and resolves nothing here.
+    let mut fake_super = false;
     let resolved_root = match resolve_open {
         Some(resolve) => match resolve(&open).await {
             Ok(resolved) => {
@@ -967,6 +1178,7 @@ async fn responder_finish(
                     )
                     .await);
                 }
+                fake_super = resolved.fake_super;
                 Some(resolved.root)
             }
             Err(fault) => return Err(notify_and_wrap(transport, fault).await),
@@ -995,7 +1207,8 @@ async fn responder_finish(
     let responder_data_plane = if open.in_stream_bytes || policy.force_in_stream {
         None
     } else {
-        data_plane::prepare_responder_data_plane(receiver_capacity).await
+        data_plane::prepare_responder_data_plane(receiver_capacity, open.dial_request.as_ref())
+            .await
     };
     let accept = SessionAccept {
         // The byte RECEIVER advertises capacity at session
@@ -1004,13 +1217,16 @@ async fn responder_finish(
         receiver_capacity: local_receiver_capacity.cloned(),
         // Grant present ⇒ TCP data plane; absent ⇒ in-stream.
         data_plane: responder_data_plane.as_ref().map(|dp| dp.grant()),
+        ignored_remote_options,
     };
     transport.send(frame(Frame::Accept(accept.clone()))).await?;
     Ok(Negotiated {
         open,
         accept,
         resolved_root,
+        fake_super,
         responder_data_plane,
+        remote_options,
     })
 }
 
@@ -1034,7 +1250,9 @@ async fn establish(
     match endpoint {
         SessionEndpoint::Initiator { open } => {
             let open = open.as_ref().clone();
-            transport.send(frame(Frame::Open(open.clone()))).await?;
+            transport
+                .send(frame(Frame::Open(Box::new(open.clone()))))
+                .await?;
             let accept = match expect_frame(transport).await? {
                 Frame::Accept(a) => a,
                 other => {
@@ -1048,16 +1266,21 @@ async fn establish(
                     .await)
                 }
             };
+            for key in &accept.ignored_remote_options {
+                log::warn!("the remote end ignored remote option {key}");
+            }
             Ok(Negotiated {
                 open,
                 accept,
                 resolved_root: None,
+                fake_super: false,
                 responder_data_plane: None,
+                remote_options: RemoteOptions::default(),
             })
         }
         SessionEndpoint::Responder => {
             let open = match expect_frame(transport).await? {
-                Frame::Open(o) => o,
+                Frame::Open(o) => *o,
                 other => {
                     return Err(notify_and_wrap(
                         transport,
@@ -1240,6 +1463,7 @@ pub async fn run_source(
         }
     }
 
+    let span = session_span();
     let negotiated = establish_traced(
         &mut transport,
         &cfg.hello,
@@ -1255,6 +1479,7 @@ pub async fn run_source(
         None,
         &lifecycle_trace,
     )
+    .instrument(tracing::info_span!(parent: &span, "negotiate"))
     .await?;
 
     let result = drive_source(
@@ -1264,6 +1489,7 @@ pub async fn run_source(
         negotiated,
         transport,
         source,
+        span,
     )
     .await;
     lifecycle_trace.record(
@@ -1289,12 +1515,17 @@ async fn drive_source(
     mut negotiated: Negotiated,
     transport: FrameTransport,
     source: Arc<dyn TransferSource>,
+    session: tracing::Span,
 ) -> Result<TransferSummary> {
+    let transfer = transfer_span(&session, &negotiated, SessionPhaseRole::Source);
     let phase_trace = bind_session_phase_trace(
         instruments.session_phase_trace.clone(),
         &negotiated,
         SessionPhaseRole::Source,
     );
+    if let Some(trace) = &phase_trace {
+        trace.event("session_negotiated", SessionPhaseFields::default());
+    }
     let small_file_probe = bind_small_file_probe(
         instruments.small_file_probe.clone(),
         &negotiated,
@@ -1320,22 +1551,25 @@ async fn drive_source(
     let (fault_tx, fault_rx) = watch::channel(None::<SessionFault>);
     // AbortOnDrop: an early error return below must abort the receive
     // half instead of leaking it (same rationale as design-2 / w4-1).
-    let recv_guard = AbortOnDrop::new(tokio::spawn(source_recv_half(
-        rx,
-        Arc::clone(&sent),
-        Arc::clone(&manifest_sent),
-        resume_negotiated(&negotiated.open),
-        // otp-10a: the recv half owns need-batch arrival, which is the
-        // push-direction progress denominator (contract on
-        // `ProgressEvent::ManifestBatch`: "push: need-list batches").
-        instruments.progress.clone(),
-        phase_trace.clone(),
-        small_file_probe.clone(),
-        SourceEventSender {
-            tx: event_tx,
-            fault_signal: fault_tx,
-        },
-    )));
+    let recv_guard = AbortOnDrop::new(tokio::spawn(
+        source_recv_half(
+            rx,
+            Arc::clone(&sent),
+            Arc::clone(&manifest_sent),
+            resume_negotiated(&negotiated.open),
+            // otp-10a: the recv half owns need-batch arrival, which is the
+            // push-direction progress denominator (contract on
+            // `ProgressEvent::ManifestBatch`: "push: need-list batches").
+            instruments.progress.clone(),
+            phase_trace.clone(),
+            small_file_probe.clone(),
+            SourceEventSender {
+                tx: event_tx,
+                fault_signal: fault_tx,
+            },
+        )
+        .instrument(transfer.clone()),
+    ));
 
     let send_result = source_send_half(
         plan_options,
@@ -1352,6 +1586,7 @@ async fn drive_source(
         phase_trace.clone(),
         small_file_probe,
     )
+    .instrument(transfer.clone())
     .await;
     let mut result = match send_result {
         Ok(summary) => Ok(summary),
@@ -1374,6 +1609,9 @@ async fn drive_source(
             }
         }
     }
+    if let Ok(summary) = &result {
+        record_summary(&transfer, summary);
+    }
     flush_session_phase_trace(phase_trace.as_ref()).await;
     result
 }
@@ -1640,6 +1878,26 @@ async fn source_send_half(
     let mut resume: ResumeSendState = ResumeSendState::default();
     let mut need_complete = false;
 
+    // v6: `--rename`. Every payload read (data plane, in-stream records,
+    // resume diffs) goes through a renaming view so a rewritten manifest
+    // path opens the original file; the scan chain below is capped with a
+    // twin sharing the same rename map. Rules were compiled at OPEN
+    // (`source_open_validator`).
+    let (source, scan_base, renaming) = if negotiated.open.rename.is_empty() {
+        (Arc::clone(&source), source, None)
+    } else {
+        let renamer = crate::path_rename::PathRenamer::from_rules(&negotiated.open.rename)
+            .map_err(|e| {
+                eyre::Report::new(SessionFault::internal(format!("invalid rename: {e:#}")))
+            })?;
+        let renaming = Arc::new(crate::remote::transfer::source::RenamingSource::new(
+            Arc::clone(&source),
+            renamer,
+        ));
+        let payload_source: Arc<dyn TransferSource> = renaming.clone();
+        (payload_source, source, Some(renaming))
+    };
+
     // Data plane (otp-4b/5b): set up the send sockets up front — BEFORE
     // streaming the manifest — so the peer sees the connections promptly
     // rather than waiting out a bounded-accept/connect timeout while a long
@@ -1654,17 +1912,29 @@ async fn source_send_half(
         // advertised its capacity in the open (the byte RECEIVER advertises,
         // wherever it initiates); epoch 0 uses the same receiver-bounded
         // floor as the dial layout and later epochs use the same controller.
-        Some(bound) => Some(
-            data_plane::accept_source_data_plane(
+        //
+        // The accept races the receive half's fault signal: a DESTINATION
+        // initiator that cannot reach the granted port says so on the
+        // control lane (its epoch-0 dial fault), and this end must end on
+        // that framed reason now, not after the bounded-accept timeout.
+        Some(bound) => {
+            let accept = data_plane::accept_source_data_plane(
                 bound,
                 negotiated.open.receiver_capacity.as_ref(),
+                negotiated.open.dial_request.as_ref(),
                 Arc::clone(&source),
                 &instruments,
                 phase_trace.clone(),
                 small_file_probe.clone(),
-            )
-            .await?,
-        ),
+            );
+            tokio::select! {
+                biased;
+                fault = peer_fault_signalled(&mut fault_signal) => {
+                    return Err(eyre::Report::new(fault));
+                }
+                plane = accept => Some(plane?),
+            }
+        }
         // SOURCE initiator (push, otp-4b): dial the grant if the responder
         // granted a data plane; else in-stream.
         None => match &negotiated.accept.data_plane {
@@ -1679,6 +1949,7 @@ async fn source_send_half(
                         host,
                         grant,
                         negotiated.accept.receiver_capacity.as_ref(),
+                        negotiated.open.dial_request.as_ref(),
                         Arc::clone(&source),
                         &instruments,
                         phase_trace.clone(),
@@ -1729,11 +2000,11 @@ async fn source_send_half(
                             )))
                         })?;
                 Arc::new(crate::remote::transfer::source::FilteredSource::new(
-                    Arc::clone(&source),
+                    Arc::clone(&scan_base),
                     filter,
                 ))
             }
-            _ => Arc::clone(&source),
+            _ => Arc::clone(&scan_base),
         };
         // otp-10b-1: a Checksum session fills each manifest header's
         // checksum so the DESTINATION can skip content-equal files
@@ -1748,6 +2019,29 @@ async fn source_send_half(
             } else {
                 scan_source
             };
+        // v13: `--prune-empty-dirs` drops directory entries with no file
+        // under them. Wrapped OUTSIDE the filter so a directory the
+        // excludes emptied is pruned too.
+        let scan_source: Arc<dyn TransferSource> = if negotiated.open.prune_empty_dirs {
+            Arc::new(crate::remote::transfer::source::PruneEmptyDirsSource::new(
+                scan_source,
+            ))
+        } else {
+            scan_source
+        };
+        // v20: `--chmod` rewrites each entry's mode before it enters the
+        // manifest, so payloads (built from manifest headers) carry it too.
+        let scan_source: Arc<dyn TransferSource> = if negotiated.open.chmod.is_empty() {
+            scan_source
+        } else {
+            let spec = crate::chmod::ChmodSpec::parse(&negotiated.open.chmod).map_err(|e| {
+                eyre::Report::new(SessionFault::internal(format!("{e:#}")))
+            })?;
+            Arc::new(crate::remote::transfer::source::ChmodSource::new(
+                scan_source,
+                spec,
+            ))
+        };
         // Contract v5: strict preservation is the default. The only lossy
         // path is an explicit OPEN policy, applied at the SOURCE before a
         // header enters the manifest. This also prevents named-stream payload
@@ -1759,6 +2053,34 @@ async fn source_send_half(
         } else {
             scan_source
         };
+        // v6: `--rename` rewrites manifest paths outermost, so filters and
+        // hashing see the original names and the destination sees only the
+        // rewritten ones. A rewrite that escapes the root or collides fails
+        // the scan itself.
+        let scan_source: Arc<dyn TransferSource> = match renaming.as_ref() {
+            Some(renaming) => Arc::new(
+                crate::remote::transfer::source::RenamingSource::sharing(scan_source, renaming),
+            ),
+            None => scan_source,
+        };
+        // v18: `--max-files` (the open's hard cap) and `--warn-files`
+        // (this end's prompt) count the finished manifest, so they wrap
+        // outermost.
+        let scan_source: Arc<dyn TransferSource> = if negotiated.open.max_files > 0
+            || instruments.file_count_warning.is_some()
+        {
+            Arc::new(crate::remote::transfer::source::FileCountGuardSource::new(
+                scan_source,
+                (negotiated.open.max_files > 0).then_some(negotiated.open.max_files),
+                instruments.file_count_warning.clone(),
+            ))
+        } else {
+            scan_source
+        };
+        // v19: the key the DESTINATION hashed its partials under; every
+        // resume diff this end runs compares under the same one.
+        let hash_key =
+            crate::remote::transfer::resume_diff::block_hash_key(negotiated.open.checksum_seed);
         // otp-10a: callers that must not treat a partial transfer as success
         // (the push verb, `blit move`'s source-delete gate) supply their own
         // accumulator via `SourceInstruments` and inspect it after the
@@ -1766,6 +2088,10 @@ async fn source_send_half(
         let unreadable: Arc<StdMutex<Vec<String>>> =
             instruments.unreadable.clone().unwrap_or_default();
         let (mut header_rx, mut scan) = scan_source.scan(None, Arc::clone(&unreadable));
+        if let Some(trace) = &phase_trace {
+            trace.event("source_scan_begin", SessionPhaseFields::default());
+        }
+        let scan_span = tracing::info_span!("scan", entries = tracing::field::Empty);
         // The TCP carrier owns a separate authenticated lane, so an ordinary
         // copy can restore the old push driver's scan/transfer overlap as soon
         // as DESTINATION has authorized a batch through NeedBatch. Mirror and
@@ -1863,6 +2189,12 @@ async fn source_send_half(
                     header = header_rx.recv() => header,
                 };
                 let Some(header) = next_header else { break };
+                // A directory entry is never granted, so it stays out of
+                // the sent map the need lookups resolve against.
+                if header.directory {
+                    tx.send(frame(Frame::ManifestEntry(header))).await?;
+                    continue;
+                }
                 if let Some(probe) = &small_file_probe {
                     let wait_started = probe.start();
                     let mut sent = sent.lock().expect("sent-manifest lock poisoned");
@@ -1921,6 +2253,8 @@ async fn source_send_half(
                 },
             );
         }
+        scan_span.record("entries", scanned as u64);
+        drop(scan_span);
         #[cfg(test)]
         if let Some(gate) = &instruments.dial_terminal_test_gate {
             gate.hold().await;
@@ -1933,7 +2267,7 @@ async fn source_send_half(
         // The in-stream carrier reuses one read buffer across records; the
         // data plane owns its own pooled buffers, so skip that allocation.
         let mut read_buf = if data_plane.is_none() {
-            vec![0u8; IN_STREAM_CHUNK]
+            vec![0u8; in_stream_chunk()]
         } else {
             Vec::new()
         };
@@ -2023,6 +2357,7 @@ async fn source_send_half(
                                 header,
                                 block_size: hashes.block_size,
                                 dest_hashes: hashes.hashes,
+                                hash_key,
                             })
                             .collect();
                         queue_payloads_while_servicing_events(
@@ -2048,7 +2383,7 @@ async fn source_send_half(
                                 fault = peer_fault_signalled(&mut fault_signal) => {
                                     return Err(eyre::Report::new(fault));
                                 }
-                                prepared = prepare_in_stream_resume(&source, header, hashes) => prepared?,
+                                prepared = prepare_in_stream_resume(&source, header, hashes, hash_key) => prepared?,
                             };
                             let (header, block_size, dest_hashes) = prepared;
                             // codex 7b-2 G2: the whole in-stream record names
@@ -2066,6 +2401,7 @@ async fn source_send_half(
                                     &header,
                                     block_size,
                                     dest_hashes,
+                                    hash_key,
                                     instruments.progress.as_ref(),
                                 ) => {
                                     res.map_err(|e| tag_path(e, &header.relative_path))?;
@@ -2570,6 +2906,63 @@ async fn send_resize_proposal(
     Ok(())
 }
 
+/// Paths per `NeedVanished` frame, so a burst of deletions stays well
+/// inside the control lane's message limit.
+const VANISHED_FRAME_PATHS: usize = 512;
+
+/// Contract v15: drop the needs in `batch` whose file has vanished since
+/// the manifest listed it, withdrawing them with `NeedVanished` so the
+/// destination stops waiting for them — rsync's "file has vanished". Only
+/// `NotFound` counts; a file that is present but unreadable still fails
+/// when it is sent. Sources without local files are never checked.
+async fn withdraw_vanished(
+    batch: Vec<FileHeader>,
+    source: &Arc<dyn TransferSource>,
+    tx: &mut Box<dyn FrameTx>,
+) -> Result<Vec<FileHeader>> {
+    let paths: Vec<Option<PathBuf>> = batch.iter().map(|h| source.local_path(h)).collect();
+    if paths.iter().all(Option::is_none) {
+        return Ok(batch);
+    }
+    let gone: Vec<bool> = tokio::task::spawn_blocking(move || {
+        paths
+            .into_iter()
+            .map(|path| {
+                path.is_some_and(|path| {
+                    matches!(std::fs::symlink_metadata(path),
+                        Err(err) if err.kind() == std::io::ErrorKind::NotFound)
+                })
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| {
+        eyre::Report::new(SessionFault::internal(format!(
+            "vanished-file check panicked: {e}"
+        )))
+    })?;
+    let mut kept = Vec::with_capacity(batch.len());
+    let mut vanished = Vec::new();
+    for (header, gone) in batch.into_iter().zip(gone) {
+        if gone {
+            log::warn!(
+                "'{}' vanished before it could be sent; skipping it",
+                header.relative_path
+            );
+            vanished.push(header.relative_path);
+        } else {
+            kept.push(header);
+        }
+    }
+    for chunk in vanished.chunks(VANISHED_FRAME_PATHS) {
+        tx.send(frame(Frame::NeedVanished(NeedVanished {
+            relative_paths: chunk.to_vec(),
+        })))
+        .await?;
+    }
+    Ok(kept)
+}
+
 /// Plan and admit one need-authorized batch to the TCP data plane. Shared by
 /// the scan-overlap path and the post-manifest tail so batching, probes, phase
 /// traces, and control-lane servicing cannot diverge between them.
@@ -2589,6 +2982,10 @@ async fn queue_tcp_payload_batch(
     small_file_probe: Option<&BoundSmallFileProbe>,
     planner_batch_seq: &mut u64,
 ) -> Result<()> {
+    let batch = withdraw_vanished(batch, source, tx).await?;
+    if batch.is_empty() {
+        return Ok(());
+    }
     let batch_count = batch.len() as u64;
     if let Some(trace) = phase_trace {
         trace.event(
@@ -2602,7 +2999,7 @@ async fn queue_tcp_payload_batch(
     }
     let planner_input = batch.len();
     let planner_started = small_file_probe.map(BoundSmallFileProbe::start);
-    let payloads = diff_planner::plan_push_payloads(batch, source.root(), plan_options)?;
+    let payloads = diff_planner::plan_data_plane_push_payloads(batch, source.root(), plan_options)?;
     if let (Some(probe), Some(started)) = (small_file_probe, planner_started) {
         let (tar_shards, tar_members) = tar_payload_shape(&payloads);
         probe.note_planner(
@@ -2938,6 +3335,10 @@ async fn send_payload_records(
     progress: Option<&RemoteTransferProgress>,
     small_file_probe: Option<&BoundSmallFileProbe>,
 ) -> Result<()> {
+    let batch = withdraw_vanished(batch, source, tx).await?;
+    if batch.is_empty() {
+        return Ok(());
+    }
     let planner_input = batch.len();
     let planner_started = small_file_probe.map(BoundSmallFileProbe::start);
     let payloads = diff_planner::plan_push_payloads(batch, source.root(), plan_options)?;
@@ -2981,11 +3382,21 @@ async fn send_payload_records(
                     .map_err(|e| tag_path(e, &header.relative_path))?;
                 let mut remaining = header.size;
                 while remaining > 0 {
+                    // Fill a whole control chunk per frame: tokio's file
+                    // reads return at most 2 MiB, which would otherwise cap
+                    // frames below a raised `--control-chunk-size`.
                     let want = read_buf.len().min(remaining as usize);
-                    let got = reader
-                        .read(&mut read_buf[..want])
-                        .await
-                        .map_err(|e| tag_path(eyre::Report::new(e), &header.relative_path))?;
+                    let mut got = 0;
+                    while got < want {
+                        let n = reader
+                            .read(&mut read_buf[got..want])
+                            .await
+                            .map_err(|e| tag_path(eyre::Report::new(e), &header.relative_path))?;
+                        if n == 0 {
+                            break;
+                        }
+                        got += n;
+                    }
                     if got == 0 {
                         // Shorter on disk than the manifest promised —
                         // the record can no longer complete at
@@ -3026,9 +3437,10 @@ async fn send_payload_records(
                 tx.send(frame(Frame::TarShardHeader(TarShardHeader {
                     files: headers,
                     archive_size: data.len() as u64,
+                    blake3: tar_shard_digest(&data).to_vec(),
                 })))
                 .await?;
-                for chunk in data.chunks(IN_STREAM_CHUNK) {
+                for chunk in data.chunks(in_stream_chunk()) {
                     tx.send(frame(Frame::TarShardChunk(
                         crate::generated::TarShardChunk {
                             content: chunk.to_vec(),
@@ -3040,6 +3452,11 @@ async fn send_payload_records(
                     .await?;
                 report_files(&shard_files);
             }
+            // Planned for the TCP data plane only — its archive bytes
+            // ride framed on one socket, not protobuf frames.
+            PreparedPayload::TarStream { .. } => {
+                eyre::bail!("tar stream planned for the in-stream carrier");
+            }
             PreparedPayload::FileBlock { .. }
             | PreparedPayload::FileBlockComplete { .. }
             | PreparedPayload::ResumeFile { .. } => {
@@ -3055,7 +3472,9 @@ async fn send_payload_records(
 
 fn tar_payload_shape(payloads: &[TransferPayload]) -> (usize, usize) {
     payloads.iter().fold((0, 0), |(shards, members), payload| {
-        if let TransferPayload::TarShard { headers } = payload {
+        if let TransferPayload::TarShard { headers } | TransferPayload::TarStream { headers } =
+            payload
+        {
             (shards + 1, members + headers.len())
         } else {
             (shards, members)
@@ -3072,12 +3491,14 @@ async fn prepare_in_stream_resume(
     source: &Arc<dyn TransferSource>,
     header: FileHeader,
     hashes: BlockHashList,
+    hash_key: Option<[u8; 32]>,
 ) -> Result<(FileHeader, u32, Vec<Vec<u8>>)> {
     let prepared = source
         .prepare_payload(TransferPayload::ResumeFile {
             header,
             block_size: hashes.block_size,
             dest_hashes: hashes.hashes,
+            hash_key,
         })
         .await?;
     match prepared {
@@ -3085,6 +3506,7 @@ async fn prepare_in_stream_resume(
             header,
             block_size,
             dest_hashes,
+            ..
         } => Ok((header, block_size, dest_hashes)),
         _ => Err(eyre::eyre!(
             "resume metadata preparation returned a non-resume payload"
@@ -3114,6 +3536,7 @@ async fn send_resume_block_records(
     header: &FileHeader,
     block_size: u32,
     dest_hashes: Vec<Vec<u8>>,
+    hash_key: Option<[u8; 32]>,
     progress: Option<&RemoteTransferProgress>,
 ) -> Result<()> {
     use crate::remote::transfer::resume_diff::{ResumeBlockDiff, ResumeDiffEvent};
@@ -3122,7 +3545,9 @@ async fn send_resume_block_records(
     // stays unarmed: the control lane carries no receive stall guard,
     // so a silent scan cannot trip one (codex 7b-1 F1 is a data-plane
     // concern; `DataPlaneSink` arms it there).
-    let mut diff = ResumeBlockDiff::open(source, header, block_size as usize, dest_hashes).await?;
+    let mut diff = ResumeBlockDiff::open(source, header, block_size as usize, dest_hashes)
+        .await?
+        .with_hash_key(hash_key);
     let mut stale_bytes: u64 = 0;
     while let Some(event) = diff.next_event().await? {
         match event {
@@ -3367,13 +3792,15 @@ pub async fn run_destination(
         }
         SessionEndpoint::Responder => SessionEndpoint::Responder,
     };
+    let initiator = matches!(endpoint, SessionEndpoint::Initiator { .. });
 
     let resolve_open: Option<&OpenResolver> = match &target {
         DestinationTarget::Resolve(resolver) => Some(resolver.as_ref()),
         DestinationTarget::Fixed(_) => None,
     };
 
-    let negotiated = establish_traced(
+    let span = session_span();
+    let mut negotiated = establish_traced(
         &mut transport,
         &cfg.hello,
         &endpoint,
@@ -3383,7 +3810,9 @@ pub async fn run_destination(
         Some(&receiver_capacity),
         &lifecycle_trace,
     )
+    .instrument(tracing::info_span!(parent: &span, "negotiate"))
     .await?;
+    negotiated.fake_super |= cfg.fake_super;
 
     // The resolver's root (Responder + Resolve) wins; otherwise the
     // caller-supplied Fixed root.
@@ -3407,10 +3836,21 @@ pub async fn run_destination(
         negotiated,
         &dst_root,
         cfg.data_plane_host.as_deref(),
+        cfg.diff_batch.unwrap_or_else(diff_batch::diff_batch_limits),
         cfg.instruments,
         cfg.local_apply,
+        cfg.receive_sink,
+        span,
     )
     .await;
+    // The summary is this end's last frame. As the initiator, give the
+    // SOURCE responder the chance to read it and end its half before the
+    // call is torn down: dropping a gRPC call whose response is still
+    // open resets the stream, which can discard the summary in flight
+    // and leave the responder recording a failed session.
+    if initiator && result.is_ok() {
+        let _ = tokio::time::timeout(SUMMARY_CLOSE_GRACE, transport.recv()).await;
+    }
     lifecycle_trace.record(
         "session_body_return",
         Some(if result.is_ok() {
@@ -3426,25 +3866,36 @@ pub async fn run_destination(
 /// fault to a peer-notified report. Shared by [`run_destination`] and
 /// [`run_responder`] (the daemon DESTINATION responder), so the receive
 /// choreography is single-sourced.
+#[allow(clippy::too_many_arguments)]
 async fn drive_destination(
     transport: &mut FrameTransport,
     negotiated: Negotiated,
     dst_root: &Path,
     data_plane_host: Option<&str>,
+    diff_limits: diff_batch::DiffBatchLimits,
     instruments: DestinationInstruments,
     local_apply: Option<local::LocalApply>,
+    receive_sink: Option<Arc<dyn TransferSink>>,
+    session: tracing::Span,
 ) -> Result<DestinationOutcome> {
+    let transfer = transfer_span(&session, &negotiated, SessionPhaseRole::Destination);
     match destination_session(
         transport,
         negotiated,
         dst_root,
         data_plane_host,
+        diff_limits,
         instruments,
         local_apply,
+        receive_sink,
     )
+    .instrument(transfer.clone())
     .await
     {
-        Ok(outcome) => Ok(outcome),
+        Ok(outcome) => {
+            record_summary(&transfer, &outcome.summary);
+            Ok(outcome)
+        }
         Err(report) => {
             let mut fault = fault_from_report(report);
             if !fault.peer_notified {
@@ -3476,9 +3927,16 @@ pub async fn run_responder(
     policy: ResponderPolicy,
 ) -> Result<ResponderOutcome> {
     let mut transport = transport;
-    exchange_hello(&mut transport, &hello).await?;
-    let open = match expect_frame(&mut transport).await? {
-        Frame::Open(o) => o,
+    let span = session_span();
+    let negotiate = tracing::info_span!(parent: &span, "negotiate");
+    exchange_hello(&mut transport, &hello)
+        .instrument(negotiate.clone())
+        .await?;
+    let open = match expect_frame(&mut transport)
+        .instrument(negotiate.clone())
+        .await?
+    {
+        Frame::Open(o) => *o,
         other => {
             return Err(notify_and_wrap(
                 &mut transport,
@@ -3508,6 +3966,7 @@ pub async fn run_responder(
                 &policy,
                 Some(&receiver_capacity),
             )
+            .instrument(negotiate)
             .await?;
             let dst_root = match negotiated.resolved_root.clone() {
                 Some(root) => root,
@@ -3527,10 +3986,14 @@ pub async fn run_responder(
                 negotiated,
                 &dst_root,
                 None,
+                diff_batch::diff_batch_limits(),
                 instruments.destination,
                 // The serving daemon never applies locally — the local
-                // carrier exists only inside run_local_session's process.
+                // carrier exists only inside run_local_session's process
+                // — and always receives into its module.
+                None,
                 None,
+                span,
             )
             .await?;
             Ok(ResponderOutcome::Destination(outcome))
@@ -3550,6 +4013,7 @@ pub async fn run_responder(
                 &policy,
                 None,
             )
+            .instrument(negotiate)
             .await?;
             let source: Arc<dyn TransferSource> = match source_target {
                 SourceResponderTarget::Fixed(source) => source,
@@ -3562,7 +4026,14 @@ pub async fn run_responder(
                             "resolver target produced no source root",
                         ))
                     })?;
-                    Arc::new(FsTransferSource::new(root))
+                    Arc::new(
+                        FsTransferSource::new(root)
+                            .with_crtimes(negotiated.open.preserve_crtimes)
+                            .with_owner(negotiated.open.preserve_owner, negotiated.fake_super)
+                            .with_xattrs(
+                                open_xattr_filter(&negotiated.open).map_err(eyre::Report::new)?,
+                            ),
+                    )
                 }
             };
             // The SOURCE owns its planner knobs; a daemon-served source
@@ -3577,6 +4048,7 @@ pub async fn run_responder(
                 negotiated,
                 transport,
                 source,
+                span,
             )
             .await?;
             Ok(ResponderOutcome::Source(summary))
@@ -3614,13 +4086,181 @@ fn tag_path(report: eyre::Report, path: &str) -> eyre::Report {
     }
 }
 
+/// Create the manifest's directory entries under `dst_root` (contract
+/// v12). Each path passes the same containment check as a file write.
+/// An existing directory is left as is; a non-directory in the way
+/// fails the session rather than being replaced.
+fn create_manifest_directories(
+    dst_root: &Path,
+    canonical_dst_root: Option<&Path>,
+    dirs: &[String],
+) -> Result<()> {
+    for rel in dirs {
+        let path = match canonical_dst_root {
+            Some(canonical) => crate::path_safety::safe_join_contained(canonical, dst_root, rel),
+            None => crate::path_safety::safe_join(dst_root, rel),
+        }
+        .map_err(|err| {
+            violation_for(
+                rel,
+                format!("manifest directory '{rel}' escapes the destination root: {err:#}"),
+            )
+        })?;
+        match std::fs::symlink_metadata(&path) {
+            Ok(meta) if meta.is_dir() => {}
+            Ok(_) => {
+                return Err(tag_path(
+                    eyre::eyre!(
+                        "cannot create directory {}: a non-directory is in the way",
+                        path.display()
+                    ),
+                    rel,
+                ))
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                std::fs::create_dir_all(&path).map_err(|err| {
+                    tag_path(
+                        eyre::eyre!("create directory {}: {err}", path.display()),
+                        rel,
+                    )
+                })?;
+            }
+            Err(err) => return Err(tag_path(eyre::eyre!("stat {}: {err}", path.display()), rel)),
+        }
+    }
+    Ok(())
+}
+
+/// Apply the manifest's carried owners under `dst_root` (contract v14,
+/// [`crate::ownership`]). Best effort like the other metadata stamps: a
+/// refused chown (an unprivileged destination without `fake_super`) or
+/// a vanished entry is logged and the rest continue.
+fn apply_manifest_owners(
+    dst_root: &Path,
+    canonical_dst_root: Option<&Path>,
+    owners: &[(String, FileOwner)],
+    fake_super: bool,
+) {
+    for (rel, owner) in owners {
+        let path = match canonical_dst_root {
+            Some(canonical) => crate::path_safety::safe_join_contained(canonical, dst_root, rel),
+            None => crate::path_safety::safe_join(dst_root, rel),
+        };
+        let applied = path
+            .map_err(|err| std::io::Error::other(format!("{err:#}")))
+            .and_then(|path| crate::ownership::apply_owner(&path, owner, fake_super));
+        if let Err(err) = applied {
+            log::warn!("set ownership on {rel}: {err}");
+        }
+    }
+}
+
+/// Chown every manifest entry under `dst_root` to the `--chown` override
+/// (contract v21). Best effort like the other metadata passes: an entry
+/// the destination does not hold is passed over and a refusal is logged.
+fn apply_manifest_chown(
+    dst_root: &Path,
+    canonical_dst_root: Option<&Path>,
+    paths: &[String],
+    chown: &crate::ownership::ChownOverride,
+) {
+    for rel in paths {
+        let path = match canonical_dst_root {
+            Some(canonical) => crate::path_safety::safe_join_contained(canonical, dst_root, rel),
+            None => crate::path_safety::safe_join(dst_root, rel),
+        };
+        let applied = path
+            .map_err(|err| std::io::Error::other(format!("{err:#}")))
+            .and_then(|path| chown.apply(&path));
+        match applied {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => log::warn!("set ownership on {rel}: {err}"),
+        }
+    }
+}
+
+/// Set the manifest's extended attributes under `dst_root` (contract
+/// v22). Best effort like the other metadata passes: an entry the
+/// destination does not hold is passed over, and each attribute it
+/// cannot set is logged by [`crate::xattrs::XattrFilter::apply`].
+fn apply_manifest_xattrs(
+    dst_root: &Path,
+    canonical_dst_root: Option<&Path>,
+    xattrs: &[(String, Vec<crate::generated::Xattr>)],
+    filter: &crate::xattrs::XattrFilter,
+) {
+    for (rel, attrs) in xattrs {
+        let path = match canonical_dst_root {
+            Some(canonical) => crate::path_safety::safe_join_contained(canonical, dst_root, rel),
+            None => crate::path_safety::safe_join(dst_root, rel),
+        };
+        match path {
+            Ok(path) if path.symlink_metadata().is_ok() => filter.apply(&path, rel, attrs),
+            Ok(_) => {}
+            Err(err) => log::warn!("set xattrs on {rel}: {err:#}"),
+        }
+    }
+}
+
+/// Apply the manifest's `--chmod` modes under `dst_root` (contract
+/// v20). Best effort like the owners: an entry the destination does not
+/// hold (skipped by `--existing`, say) is passed over, a hard-linked
+/// file is copied apart first, and a refused `chmod` is logged and the
+/// rest continue. Unix only; elsewhere the
+/// modes have nothing to land on.
+fn apply_manifest_modes(
+    dst_root: &Path,
+    canonical_dst_root: Option<&Path>,
+    modes: &[(String, u32)],
+) {
+    #[cfg(unix)]
+    for (rel, mode) in modes {
+        use std::os::unix::fs::PermissionsExt;
+        let path = match canonical_dst_root {
+            Some(canonical) => crate::path_safety::safe_join_contained(canonical, dst_root, rel),
+            None => crate::path_safety::safe_join(dst_root, rel),
+        };
+        let path = match path {
+            Ok(path) => path,
+            Err(err) => {
+                log::warn!("set permissions on {rel}: {err:#}");
+                continue;
+            }
+        };
+        let Ok(meta) = std::fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if meta.file_type().is_symlink()
+            || crate::wire_metadata::permissions_mode(&meta) & 0o7777 == mode & 0o7777
+        {
+            continue;
+        }
+        // A mode lives on the inode: copy a shared file apart first so
+        // its other names keep theirs.
+        if let Err(err) = crate::fs_capability::unshare_hard_link(&path) {
+            log::warn!("unshare {}: {}", path.display(), err);
+            continue;
+        }
+        if let Err(err) =
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode & 0o7777))
+        {
+            log::warn!("set permissions on {}: {}", path.display(), err);
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = (dst_root, canonical_dst_root, modes);
+}
+
 /// otp-6b: the DESTINATION's mirror delete pass — the session's single
 /// delete rule. Plans (enumerate dest + diff against the complete source
 /// file set) and executes the extraneous deletions, all blocking FS work,
 /// so it runs on the blocking pool. Returns `(files, dirs)` deleted —
 /// split so the local carrier's summary (otp-11) can report both; wire
 /// summaries carry the sum. `execute: false` (local `--dry-run` only)
-/// plans and counts without touching the filesystem.
+/// plans and counts without touching the filesystem. Each entry removed
+/// (or planned) is reported on `progress` as a destination-relative
+/// [`ProgressEvent::Deleted`], for `--info=del`.
 ///
 /// Every target is containment-checked against the canonical destination
 /// root before any filesystem op (the same chokepoint the sink write paths
@@ -3639,12 +4279,20 @@ fn mirror_delete_pass(
     canonical_dst_root: Option<&Path>,
     abort: &AtomicBool,
     execute: bool,
+    progress: Option<&RemoteTransferProgress>,
 ) -> Result<(u64, u64)> {
     let plan = crate::mirror_planner::MirrorPlanner::new(false).plan_session_deletions(
         dst_root,
         source_files,
         filter,
     )?;
+    let report_deleted = progress.map(|progress| {
+        move |target: &Path| {
+            if let Ok(rel) = target.strip_prefix(dst_root) {
+                progress.report_deleted(crate::path_posix::relative_path_to_posix(rel));
+            }
+        }
+    });
     // codex otp-9b F2: a dropped session future (client disconnect,
     // CancelJob) cannot abort a running blocking task — the shared executor
     // checks this drop-guard before every filesystem operation.
@@ -3659,25 +4307,34 @@ fn mirror_delete_pass(
             directory_mode: crate::deletion::DirectoryMode::EmptyOnly {
                 tolerate_nonempty: tolerate_nonempty_dirs,
             },
+            on_deleted: report_deleted
+                .as_ref()
+                .map(|report| report as &dyn Fn(&Path)),
         },
     )
     .map_err(eyre::Report::new)?;
     Ok((stats.files, stats.dirs))
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn destination_session(
     transport: &mut FrameTransport,
     negotiated: Negotiated,
     dst_root: &Path,
     data_plane_host: Option<&str>,
+    diff_limits: diff_batch::DiffBatchLimits,
     instruments: DestinationInstruments,
     local_apply: Option<local::LocalApply>,
+    receive_sink: Option<Arc<dyn TransferSink>>,
 ) -> Result<DestinationOutcome> {
     let phase_trace = bind_session_phase_trace(
         instruments.session_phase_trace.clone(),
         &negotiated,
         SessionPhaseRole::Destination,
     );
+    if let Some(trace) = &phase_trace {
+        trace.event("session_negotiated", SessionPhaseFields::default());
+    }
     let small_file_probe = bind_small_file_probe(
         instruments.small_file_probe.clone(),
         &negotiated,
@@ -3688,8 +4345,10 @@ async fn destination_session(
         negotiated,
         dst_root,
         data_plane_host,
+        diff_limits,
         instruments,
         local_apply,
+        receive_sink,
         phase_trace.clone(),
         small_file_probe.clone(),
     )
@@ -3705,8 +4364,10 @@ async fn destination_session_inner(
     negotiated: Negotiated,
     dst_root: &Path,
     data_plane_host: Option<&str>,
+    diff_limits: diff_batch::DiffBatchLimits,
     instruments: DestinationInstruments,
     local_apply: Option<local::LocalApply>,
+    receive_sink: Option<Arc<dyn TransferSink>>,
     phase_trace: Option<BoundSessionPhaseTrace>,
     small_file_probe: Option<BoundSmallFileProbe>,
 ) -> Result<DestinationOutcome> {
@@ -3721,6 +4382,14 @@ async fn destination_session_inner(
     let compare_opts = CompareOptions {
         mode: compare_mode.into(),
         ignore_existing: negotiated.open.ignore_existing,
+        existing_only: negotiated.open.existing_only,
+    };
+    let metadata_only = if !negotiated.open.metadata_only {
+        MetadataOnly::Off
+    } else if local_apply.as_ref().is_some_and(|la| la.dry_run) {
+        MetadataOnly::Preview
+    } else {
+        MetadataOnly::Repair
     };
     // src_root is only consumed by local File payloads, which never
     // occur on a WIRE session destination (payload bytes arrive as
@@ -3728,9 +4397,10 @@ async fn destination_session_inner(
     // carrier (otp-11) brings its own fully-configured sink, where
     // File payloads are the point. `Arc` so the data-plane receive
     // task (otp-4b) can share the one sink across sockets.
-    let sink: Arc<dyn TransferSink> = match &local_apply {
-        Some(la) => Arc::clone(&la.sink),
-        None => {
+    let sink: Arc<dyn TransferSink> = match (&local_apply, receive_sink) {
+        (Some(la), _) => Arc::clone(&la.sink),
+        (None, Some(sink)) => sink,
+        (None, None) => {
             let mut sink = FsTransferSink::new(
                 PathBuf::new(),
                 dst_root.to_path_buf(),
@@ -3740,6 +4410,11 @@ async fn destination_session_inner(
                     checksum: None,
                     resume: false,
                     compare_mode,
+                    sparse: SparseMode::Auto,
+                    block_size: 0,
+                    preallocate: false,
+                    reflink_dest: None,
+                    fsync: negotiated.remote_options.fsync,
                 },
             );
             // otp-9a: applied payload bytes report against the caller's live
@@ -3789,6 +4464,33 @@ async fn destination_session_inner(
             crate::fs_enum::FileFilter::default()
         };
     let mut source_files: HashSet<String> = HashSet::new();
+    // Contract v12: directory entries from the manifest, created once
+    // the manifest is complete (parents first, in scan order).
+    let mut manifest_dirs: Vec<String> = Vec::new();
+    // Contract v14: carried owners, applied once every write is done.
+    let mut manifest_owners: Vec<(String, FileOwner)> = Vec::new();
+    // Contract v20: under `--chmod`, every entry's (already rewritten)
+    // mode, applied with the owners.
+    let apply_modes = !negotiated.open.chmod.is_empty();
+    let mut manifest_modes: Vec<(String, u32)> = Vec::new();
+    // Contract v21: the `--chown` override (validated at OPEN) and the
+    // entries it is applied to.
+    let chown = if negotiated.open.chown.is_empty() {
+        None
+    } else {
+        Some(
+            crate::ownership::ChownOverride::resolve(&negotiated.open.chown)
+                .map_err(|e| eyre::Report::new(SessionFault::internal(format!("{e:#}"))))?,
+        )
+    };
+    let mut manifest_paths: Vec<String> = Vec::new();
+    // Contract v22: the `--xattrs` filter (validated at OPEN) and the
+    // attributes each manifest entry carries.
+    let xattr_filter = open_xattr_filter(&negotiated.open).map_err(eyre::Report::new)?;
+    let mut manifest_xattrs: Vec<(String, Vec<crate::generated::Xattr>)> = Vec::new();
+    // Contract v9: content-equal entries a metadata-only diff found,
+    // stamped with the other metadata passes once every entry is diffed.
+    let mut metadata_repairs: Vec<FileHeader> = Vec::new();
 
     // otp-7a: resume. Headers of resume-granted needs are retained so a
     // record's completion can finalize with the manifest's
@@ -3871,6 +4573,7 @@ async fn destination_session_inner(
                         host,
                         grant,
                         negotiated.open.receiver_capacity.as_ref(),
+                        negotiated.open.dial_request.as_ref(),
                         recv_sink,
                         progress.clone(),
                         instruments.trace_data_plane,
@@ -3939,12 +4642,18 @@ async fn destination_session_inner(
             bs => bs.clamp(MIN_RESUME_BLOCK_SIZE, ceiling),
         }
     };
+    // v19: `--checksum-seed` keys the partial's block hashes; the
+    // SOURCE diffs under the same key from the open it received.
+    let resume_hash_key =
+        crate::remote::transfer::resume_diff::block_hash_key(negotiated.open.checksum_seed);
 
-    let mut pending: Vec<FileHeader> = Vec::new();
+    let mut pending = diff_batch::DiffBatch::new(diff_limits);
     let mut needed_paths: Vec<String> = Vec::new();
     let mut manifest_complete = false;
     let mut files_written: u64 = 0;
     let mut bytes_written: u64 = 0;
+    // Contract v15: needs the source withdrew because the file vanished.
+    let mut vanished_paths: Vec<String> = Vec::new();
     let mut need_batch_seq = 0u64;
 
     // otp-11: the LOCAL carrier's apply pipeline — spawned before the
@@ -3970,6 +4679,28 @@ async fn destination_session_inner(
                         header.relative_path
                     )));
                 }
+                if let Some(mut owner) = header.owner {
+                    if let Some(chown) = &chown {
+                        chown.override_owner(&mut owner);
+                    }
+                    manifest_owners.push((header.relative_path.clone(), owner));
+                }
+                if chown.is_some() {
+                    manifest_paths.push(header.relative_path.clone());
+                }
+                if xattr_filter.is_some() && !header.xattrs.is_empty() {
+                    manifest_xattrs.push((header.relative_path.clone(), header.xattrs.clone()));
+                }
+                if apply_modes && header.permissions != 0 {
+                    manifest_modes.push((header.relative_path.clone(), header.permissions));
+                }
+                if header.directory {
+                    if mirror_enabled {
+                        source_files.insert(header.relative_path.clone());
+                    }
+                    manifest_dirs.push(header.relative_path);
+                    continue;
+                }
                 crate::windows_metadata::validate_manifest(header.windows_metadata.as_ref())
                     .map_err(|error| {
                         violation_for(
@@ -3985,9 +4716,7 @@ async fn destination_session_inner(
                 if mirror_enabled {
                     source_files.insert(header.relative_path.clone());
                 }
-                pending.push(header);
-                if pending.len() >= DEST_DIFF_CHUNK {
-                    let chunk = std::mem::take(&mut pending);
+                if let Some(chunk) = pending.push(header) {
                     if let Some(la) = &local_apply {
                         diff_chunk_and_apply_local(
                             la,
@@ -3996,8 +4725,10 @@ async fn destination_session_inner(
                             dst_root,
                             canonical_dst_root.as_deref(),
                             &compare_opts,
+                            metadata_only,
                             &mut granted,
                             &mut needed_paths,
+                            &mut metadata_repairs,
                             progress.as_ref(),
                         )
                         .await?;
@@ -4008,13 +4739,16 @@ async fn destination_session_inner(
                             dst_root,
                             canonical_dst_root.as_deref(),
                             &compare_opts,
+                            metadata_only,
                             resume_enabled,
                             resume_block_size,
+                            resume_hash_key.as_ref(),
                             &resume_headers,
                             &mut granted,
                             &outstanding,
                             &granted_headers,
                             &mut needed_paths,
+                            &mut metadata_repairs,
                             progress.as_ref(),
                             phase_trace.as_ref(),
                             &mut need_batch_seq,
@@ -4057,7 +4791,27 @@ async fn destination_session_inner(
                          complete scan (move deletes the source afterwards)",
                     )));
                 }
-                let chunk = std::mem::take(&mut pending);
+                // Neither a metadata-only pass, an `--existing` pass,
+                // nor a local dry run creates anything.
+                let dirs = std::mem::take(&mut manifest_dirs);
+                if !dirs.is_empty()
+                    && metadata_only == MetadataOnly::Off
+                    && !compare_opts.existing_only
+                    && !local_apply.as_ref().is_some_and(|la| la.dry_run)
+                {
+                    let root = dst_root.to_path_buf();
+                    let canonical = canonical_dst_root.clone();
+                    tokio::task::spawn_blocking(move || {
+                        create_manifest_directories(&root, canonical.as_deref(), &dirs)
+                    })
+                    .await
+                    .map_err(|err| {
+                        eyre::Report::new(SessionFault::internal(format!(
+                            "directory creation task failed: {err}"
+                        )))
+                    })??;
+                }
+                let chunk = pending.take();
                 if let Some(la) = &local_apply {
                     diff_chunk_and_apply_local(
                         la,
@@ -4066,8 +4820,10 @@ async fn destination_session_inner(
                         dst_root,
                         canonical_dst_root.as_deref(),
                         &compare_opts,
+                        metadata_only,
                         &mut granted,
                         &mut needed_paths,
+                        &mut metadata_repairs,
                         progress.as_ref(),
                     )
                     .await?;
@@ -4078,13 +4834,16 @@ async fn destination_session_inner(
                         dst_root,
                         canonical_dst_root.as_deref(),
                         &compare_opts,
+                        metadata_only,
                         resume_enabled,
                         resume_block_size,
+                        resume_hash_key.as_ref(),
                         &resume_headers,
                         &mut granted,
                         &outstanding,
                         &granted_headers,
                         &mut needed_paths,
+                        &mut metadata_repairs,
                         progress.as_ref(),
                         phase_trace.as_ref(),
                         &mut need_batch_seq,
@@ -4101,7 +4860,7 @@ async fn destination_session_inner(
                 }
                 manifest_complete = true;
             }
-            Some(Frame::FileBegin(header)) => {
+            Some(Frame::FileBegin(mut header)) => {
                 // Payload records ride the control lane only under the
                 // in-stream carrier; with a TCP data plane active they
                 // flow over the sockets, so one here is a violation.
@@ -4170,6 +4929,9 @@ async fn destination_session_inner(
                         ),
                     ));
                 }
+                // `--crtimes`: the manifest grant is the authority for the
+                // creation time, as it is on the data plane.
+                header.crtime_seconds = manifest_header.crtime_seconds;
                 if !outstanding
                     .lock()
                     .expect("outstanding-needs lock poisoned")
@@ -4245,7 +5007,7 @@ async fn destination_session_inner(
                     p.report_file_complete(header.relative_path.clone());
                 }
             }
-            Some(Frame::TarShardHeader(shard)) => {
+            Some(Frame::TarShardHeader(mut shard)) => {
                 if data_plane_recv.is_some() {
                     return Err(violation(
                         "tar shard record on the control lane while a TCP data plane is active"
@@ -4273,7 +5035,7 @@ async fn destination_session_inner(
                     let retained = granted_headers
                         .lock()
                         .expect("granted-headers lock poisoned");
-                    for header in &shard.files {
+                    for header in &mut shard.files {
                         let manifest = retained.get(&header.relative_path).ok_or_else(|| {
                             violation_for(
                                 &header.relative_path,
@@ -4308,6 +5070,7 @@ async fn destination_session_inner(
                                 ),
                             ));
                         }
+                        header.crtime_seconds = manifest.crtime_seconds;
                     }
                 }
                 if let Some(probe) = &small_file_probe {
@@ -4377,6 +5140,24 @@ async fn destination_session_inner(
                     }
                 }
             }
+            Some(Frame::NeedVanished(vanished)) => {
+                // Contract v15: the source found these needed files gone
+                // before sending them. Each withdraws a still-outstanding
+                // need, so SourceDone's completion check does not count it.
+                let mut out = outstanding.lock().expect("outstanding-needs lock poisoned");
+                let mut retained = granted_headers
+                    .lock()
+                    .expect("granted-headers lock poisoned");
+                for path in vanished.relative_paths {
+                    if !out.remove(&path) {
+                        return Err(violation(format!(
+                            "vanished '{path}' which is not on the need list"
+                        )));
+                    }
+                    retained.remove(&path);
+                    vanished_paths.push(path);
+                }
+            }
             Some(Frame::Resize(resize)) => {
                 let state = resize_state.as_mut().ok_or_else(|| {
                     violation("DataPlaneResize on a session with no data plane".into())
@@ -4611,6 +5392,12 @@ async fn destination_session_inner(
                     // guard too — harmless, the task is already done.)
                     let abort = Arc::new(AtomicBool::new(false));
                     let _abort_guard = AbortFlagOnDrop(Arc::clone(&abort));
+                    if let Some(trace) = &phase_trace {
+                        trace.event("mirror_delete_begin", SessionPhaseFields::default());
+                    }
+                    let delete_span =
+                        tracing::info_span!("mirror_delete", entries = tracing::field::Empty);
+                    let deleted_progress = progress.clone();
                     let mut pass = tokio::task::spawn_blocking(move || {
                         mirror_delete_pass(
                             &dst,
@@ -4620,6 +5407,7 @@ async fn destination_session_inner(
                             canonical.as_deref(),
                             &abort,
                             execute,
+                            deleted_progress.as_ref(),
                         )
                     });
                     // codex otp-10b-2 F1: a PEER fault mid-purge (a
@@ -4670,6 +5458,17 @@ async fn destination_session_inner(
                             "mirror delete failed: {e:#}"
                         )))
                     })?;
+                    if let Some(trace) = &phase_trace {
+                        trace.event(
+                            "mirror_delete_end",
+                            SessionPhaseFields {
+                                count: Some(deleted_file_count + deleted_dir_count),
+                                ..Default::default()
+                            },
+                        );
+                    }
+                    delete_span.record("entries", deleted_file_count + deleted_dir_count);
+                    drop(delete_span);
                     // otp-11: the local summary reports the split; the
                     // wire summary keeps the one entries_deleted count.
                     if let Some(la) = &local_apply {
@@ -4684,17 +5483,144 @@ async fn destination_session_inner(
                 } else {
                     0
                 };
+                // Contract v21: `--chown` first among the metadata passes:
+                // a change of owner can clear set-id bits, which the mode
+                // and owner passes below then restore.
+                let paths = std::mem::take(&mut manifest_paths);
+                if let Some(chown) = chown.filter(|_| {
+                    !paths.is_empty()
+                        && metadata_only == MetadataOnly::Off
+                        && !local_apply.as_ref().is_some_and(|la| la.dry_run)
+                }) {
+                    if negotiated.fake_super {
+                        // A faking end only records ownership, through
+                        // the carried owners overridden above.
+                        if manifest_owners.is_empty() {
+                            log::warn!(
+                                "--chown {}: the destination fakes ownership and records \
+                                 it only with --super / --fake-super; leaving owners as they are",
+                                negotiated.open.chown
+                            );
+                        }
+                    } else if !crate::ownership::ChownOverride::can_apply() {
+                        log::warn!(
+                            "--chown {}: the destination cannot change ownership \
+                             (not running as root); leaving owners as they are",
+                            negotiated.open.chown
+                        );
+                    } else {
+                        let root = dst_root.to_path_buf();
+                        let canonical = canonical_dst_root.clone();
+                        tokio::task::spawn_blocking(move || {
+                            apply_manifest_chown(&root, canonical.as_deref(), &paths, &chown)
+                        })
+                        .await
+                        .map_err(|err| {
+                            eyre::Report::new(SessionFault::internal(format!(
+                                "chown task failed: {err}"
+                            )))
+                        })?;
+                    }
+                }
+                // Contract v22: extended attributes before the modes, so
+                // an unprivileged destination can still write them to an
+                // entry `--chmod` or the carried mode leaves read-only.
+                let xattrs = std::mem::take(&mut manifest_xattrs);
+                if let Some(filter) = xattr_filter.clone().filter(|_| {
+                    !xattrs.is_empty()
+                        && metadata_only == MetadataOnly::Off
+                        && !local_apply.as_ref().is_some_and(|la| la.dry_run)
+                }) {
+                    let root = dst_root.to_path_buf();
+                    let canonical = canonical_dst_root.clone();
+                    tokio::task::spawn_blocking(move || {
+                        apply_manifest_xattrs(&root, canonical.as_deref(), &xattrs, &filter)
+                    })
+                    .await
+                    .map_err(|err| {
+                        eyre::Report::new(SessionFault::internal(format!(
+                            "xattr task failed: {err}"
+                        )))
+                    })?;
+                }
+                // Contract v20: `--chmod` modes over the final tree, so a
+                // directory is only narrowed once nothing else lands in it
+                // and an unchanged file converges too. Gated like the owners.
+                let modes = std::mem::take(&mut manifest_modes);
+                if !modes.is_empty()
+                    && metadata_only == MetadataOnly::Off
+                    && !local_apply.as_ref().is_some_and(|la| la.dry_run)
+                {
+                    let root = dst_root.to_path_buf();
+                    let canonical = canonical_dst_root.clone();
+                    tokio::task::spawn_blocking(move || {
+                        apply_manifest_modes(&root, canonical.as_deref(), &modes)
+                    })
+                    .await
+                    .map_err(|err| {
+                        eyre::Report::new(SessionFault::internal(format!(
+                            "permission task failed: {err}"
+                        )))
+                    })?;
+                }
+                // Contract v9: the metadata-only repairs, now that the
+                // diff is over — computing it never touches the tree.
+                let repairs = std::mem::take(&mut metadata_repairs);
+                if !repairs.is_empty() && metadata_only == MetadataOnly::Repair {
+                    let root = dst_root.to_path_buf();
+                    let canonical = canonical_dst_root.clone();
+                    let fake_super = negotiated.fake_super;
+                    tokio::task::spawn_blocking(move || {
+                        apply_metadata_repairs(
+                            &root,
+                            canonical.as_deref(),
+                            &repairs,
+                            chown,
+                            fake_super,
+                        )
+                    })
+                    .await
+                    .map_err(|err| {
+                        eyre::Report::new(SessionFault::internal(format!(
+                            "metadata repair task failed: {err}"
+                        )))
+                    })?;
+                }
+                // Contract v14: ownership last, over the final tree —
+                // unchanged entries included, so it converges like the
+                // rest of the metadata. A metadata-only pass carries its
+                // owners through the repairs above, and a local dry run
+                // touches nothing.
+                let owners = std::mem::take(&mut manifest_owners);
+                if !owners.is_empty()
+                    && metadata_only == MetadataOnly::Off
+                    && !local_apply.as_ref().is_some_and(|la| la.dry_run)
+                {
+                    let root = dst_root.to_path_buf();
+                    let canonical = canonical_dst_root.clone();
+                    let fake_super = negotiated.fake_super;
+                    tokio::task::spawn_blocking(move || {
+                        apply_manifest_owners(&root, canonical.as_deref(), &owners, fake_super)
+                    })
+                    .await
+                    .map_err(|err| {
+                        eyre::Report::new(SessionFault::internal(format!(
+                            "ownership task failed: {err}"
+                        )))
+                    })?;
+                }
                 let summary = TransferSummary {
                     files_transferred: files_written,
                     bytes_transferred: bytes_written,
                     entries_deleted,
                     in_stream_carrier_used,
                     files_resumed: files_resumed.load(Ordering::Relaxed),
+                    vanished_paths: std::mem::take(&mut vanished_paths),
                 };
                 if let Some(trace) = &phase_trace {
                     trace.event("summary_send_begin", SessionPhaseFields::default());
                 }
-                transport.send(frame(Frame::Summary(summary))).await?;
+                transport.send(frame(Frame::Summary(summary.clone()))).await?;
                 if let Some(trace) = &phase_trace {
                     trace.event("summary_sent", SessionPhaseFields::default());
                 }
@@ -4761,8 +5687,10 @@ async fn diff_chunk_and_apply_local(
     dst_root: &Path,
     canonical_dst_root: Option<&Path>,
     compare_opts: &CompareOptions,
+    metadata_only: MetadataOnly,
     granted: &mut HashSet<String>,
     needed_paths: &mut Vec<String>,
+    repairs: &mut Vec<FileHeader>,
     progress: Option<&RemoteTransferProgress>,
 ) -> Result<()> {
     if chunk.is_empty() {
@@ -4785,9 +5713,18 @@ async fn diff_chunk_and_apply_local(
     // this one plans and applies them in-process. The resume flag is
     // meaningless here (the local carrier's block phase is
     // sink-level).
-    let needed = diff_chunk_verdicts(chunk, dst_root, canonical_dst_root, compare_opts).await?;
+    let verdicts = diff_chunk_verdicts(
+        chunk,
+        dst_root,
+        canonical_dst_root,
+        compare_opts,
+        metadata_only,
+    )
+    .await?;
+    repairs.extend(verdicts.repairs);
 
-    let fresh: Vec<FileHeader> = needed
+    let fresh: Vec<FileHeader> = verdicts
+        .needed
         .into_iter()
         .map(|(header, _)| header)
         .filter(|header| granted.insert(header.relative_path.clone()))
@@ -4844,8 +5781,10 @@ async fn diff_chunk_and_send_needs(
     dst_root: &Path,
     canonical_dst_root: Option<&Path>,
     compare_opts: &CompareOptions,
+    metadata_only: MetadataOnly,
     resume_enabled: bool,
     resume_block_size: usize,
+    resume_hash_key: Option<&[u8; 32]>,
     // Headers of resume-granted needs, retained for record finalization
     // (shared with the data-plane receive, otp-7b).
     resume_headers: &data_plane::ResumeHeaders,
@@ -4859,6 +5798,8 @@ async fn diff_chunk_and_send_needs(
     // Retained manifest descriptors for ordinary payload validation.
     granted_headers: &data_plane::GrantedHeaders,
     needed_paths: &mut Vec<String>,
+    // `--metadata-only`: content-equal entries for the apply phase.
+    repairs: &mut Vec<FileHeader>,
     // otp-10b-2: w6-1 denominator — each NeedBatch sent reports a
     // ManifestBatch (files this DESTINATION requested), mirroring what
     // the push SOURCE reports per NeedBatch received.
@@ -4872,12 +5813,20 @@ async fn diff_chunk_and_send_needs(
     // ONE diff core, both carriers (codex otp-11a F1); plan D2: a need
     // is resume-flagged only when the session negotiated resume AND a
     // non-empty dest partial exists to diff against.
-    let needed: Vec<(FileHeader, bool)> =
-        diff_chunk_verdicts(chunk, dst_root, canonical_dst_root, compare_opts)
-            .await?
-            .into_iter()
-            .map(|(header, resume_eligible)| (header, resume_enabled && resume_eligible))
-            .collect();
+    let verdicts = diff_chunk_verdicts(
+        chunk,
+        dst_root,
+        canonical_dst_root,
+        compare_opts,
+        metadata_only,
+    )
+    .await?;
+    repairs.extend(verdicts.repairs);
+    let needed: Vec<(FileHeader, bool)> = verdicts
+        .needed
+        .into_iter()
+        .map(|(header, resume_eligible)| (header, resume_enabled && resume_eligible))
+        .collect();
 
     // Dedup on the ever-granted set (no lock — control-loop-local), then
     // insert the freshly granted paths into the shared `outstanding`
@@ -4962,6 +5911,7 @@ async fn diff_chunk_and_send_needs(
             canonical_dst_root,
             &header.relative_path,
             resume_block_size,
+            resume_hash_key.copied(),
         )
         .await?;
         // Retain the grant BEFORE the hash list goes out (otp-7b): the
@@ -4987,7 +5937,7 @@ async fn diff_chunk_and_send_needs(
 /// Stat-and-compare one manifest chunk on the blocking pool (2+
 /// syscalls per entry — the daemon's w4-4 chunked-check rationale),
 /// abortable when the session dies: under Checksum compare this chunk
-/// hashes up to DEST_DIFF_CHUNK files (codex otp-10b-1 F3), so the
+/// hashes up to one diff batch of files (codex otp-10b-1 F3), so the
 /// guard's Drop flips the flag, the loop checks it per entry, and the
 /// hasher per 64 KiB chunk. The ONE diff core for both carriers
 /// (codex otp-11a F1): `diff_chunk_and_send_needs` grants the result
@@ -4999,14 +5949,15 @@ async fn diff_chunk_verdicts(
     dst_root: &Path,
     canonical_dst_root: Option<&Path>,
     compare_opts: &CompareOptions,
-) -> Result<Vec<(FileHeader, bool)>> {
+    metadata_only: MetadataOnly,
+) -> Result<ChunkVerdicts> {
     let dst_root_owned = dst_root.to_path_buf();
     let canonical = canonical_dst_root.map(Path::to_path_buf);
     let opts = compare_opts.clone();
     let abort = Arc::new(AtomicBool::new(false));
     let _abort_guard = AbortFlagOnDrop(Arc::clone(&abort));
-    tokio::task::spawn_blocking(move || -> Result<Vec<(FileHeader, bool)>> {
-        let mut needed = Vec::new();
+    tokio::task::spawn_blocking(move || -> Result<ChunkVerdicts> {
+        let mut verdicts = ChunkVerdicts::default();
         for header in chunk {
             if abort.load(Ordering::Acquire) {
                 eyre::bail!("destination diff aborted: session ended");
@@ -5016,15 +5967,17 @@ async fn diff_chunk_verdicts(
                 &dst_root_owned,
                 canonical.as_deref(),
                 &opts,
+                metadata_only,
                 &abort,
             )? {
                 NeedVerdict::Skip => {}
                 NeedVerdict::Transfer { resume_eligible } => {
-                    needed.push((header, resume_eligible));
+                    verdicts.needed.push((header, resume_eligible));
                 }
+                NeedVerdict::Repair => verdicts.repairs.push(header),
             }
         }
-        Ok(needed)
+        Ok(verdicts)
     })
     .await
     .map_err(|err| eyre::eyre!("destination diff task panicked: {err}"))?
@@ -5040,7 +5993,32 @@ async fn diff_chunk_verdicts(
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum NeedVerdict {
     Skip,
-    Transfer { resume_eligible: bool },
+    Transfer {
+        resume_eligible: bool,
+    },
+    /// `--metadata-only`: content-equal, so its metadata is stamped in
+    /// the apply phase ([`apply_metadata_repairs`]); the diff itself
+    /// never touches the destination.
+    Repair,
+}
+
+/// One diffed chunk: the entries to request (with their resume
+/// eligibility) and, under `--metadata-only`, the content-equal ones
+/// whose metadata the apply phase repairs.
+#[derive(Debug, Default)]
+struct ChunkVerdicts {
+    needed: Vec<(FileHeader, bool)>,
+    repairs: Vec<FileHeader>,
+}
+
+/// `SessionOpen.metadata_only` as the destination diff applies it
+/// (contract v9). `Preview` is the local carrier's `--dry-run`: every
+/// entry still diffs as Skip, but nothing is touched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MetadataOnly {
+    Off,
+    Repair,
+    Preview,
 }
 
 /// Does the destination need this manifest entry? Stats its own file
@@ -5052,6 +6030,7 @@ fn destination_needs(
     dst_root: &Path,
     canonical_dst_root: Option<&Path>,
     opts: &CompareOptions,
+    metadata_only: MetadataOnly,
     abort: &AtomicBool,
 ) -> Result<NeedVerdict> {
     let dst = match canonical_dst_root {
@@ -5072,22 +6051,20 @@ fn destination_needs(
     // touched and then rejected later by the sink.
     crate::windows_metadata::validate_destination_support(header.windows_metadata.as_ref())?;
 
-    let target = match std::fs::metadata(&dst) {
-        Ok(meta) if meta.is_file() => {
-            let mtime = match meta.modified() {
-                Ok(t) => match t.duration_since(std::time::UNIX_EPOCH) {
-                    Ok(d) => d.as_secs() as i64,
-                    Err(e) => -(e.duration().as_secs() as i64),
-                },
-                Err(_) => 0,
-            };
-            Some((meta.len(), mtime))
-        }
-        // Absent — or present as a directory/other, which a file
-        // write must replace: both diff as "target does not have it"
-        // (matches the push daemon's file_requires_upload).
-        _ => None,
-    };
+    // Absent — or present as a directory/other, which a file write
+    // must replace: both diff as "target does not have it" (matches the
+    // push daemon's file_requires_upload).
+    let target_meta = std::fs::metadata(&dst).ok().filter(|meta| meta.is_file());
+    let target = target_meta.as_ref().map(|meta| {
+        let mtime = match meta.modified() {
+            Ok(t) => match t.duration_since(std::time::UNIX_EPOCH) {
+                Ok(d) => d.as_secs() as i64,
+                Err(e) => -(e.duration().as_secs() as i64),
+            },
+            Err(_) => 0,
+        };
+        (meta.len(), mtime)
+    });
     // otp-10b-1: a Checksum session hashes the local candidate so a
     // content-equal file SKIPS regardless of mtime (the old pull's
     // `--checksum` behavior, now role-agnostic). Only the same-size
@@ -5114,6 +6091,18 @@ fn destination_needs(
         target.map(|(size, mtime)| (size, mtime, target_hash.as_slice())),
         opts,
     );
+    if metadata_only != MetadataOnly::Off {
+        // v9: never request bytes. Only a content-equal file (Checksum
+        // session, so `Unchanged` means same size and Blake3) is marked
+        // for repair, and only once the diff is over.
+        if status == FileStatus::Unchanged
+            && target_meta.is_some()
+            && metadata_only == MetadataOnly::Repair
+        {
+            return Ok(NeedVerdict::Repair);
+        }
+        return Ok(NeedVerdict::Skip);
+    }
     let metadata_matches = match status {
         FileStatus::Unchanged => {
             crate::windows_metadata::destination_matches(&dst, header.windows_metadata.as_ref())?
@@ -5123,6 +6112,103 @@ fn destination_needs(
     Ok(finalize_need_verdict(status, target, metadata_matches))
 }
 
+/// `--metadata-only`: stamp the content-equal files the diff marked for
+/// [`NeedVerdict::Repair`] under `dst_root`. Runs after the diff, with
+/// the other metadata passes; a file replaced or removed since is
+/// passed over.
+fn apply_metadata_repairs(
+    dst_root: &Path,
+    canonical_dst_root: Option<&Path>,
+    headers: &[FileHeader],
+    chown: Option<crate::ownership::ChownOverride>,
+    fake_super: bool,
+) {
+    for header in headers {
+        let rel = &header.relative_path;
+        let path = match canonical_dst_root {
+            Some(canonical) => crate::path_safety::safe_join_contained(canonical, dst_root, rel),
+            None => crate::path_safety::safe_join(dst_root, rel),
+        };
+        let path = match path {
+            Ok(path) => path,
+            Err(err) => {
+                log::warn!("repair metadata on {rel}: {err:#}");
+                continue;
+            }
+        };
+        let Ok(meta) = std::fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+        let owner = header.owner.map(|mut owner| {
+            if let Some(chown) = &chown {
+                chown.override_owner(&mut owner);
+            }
+            owner
+        });
+        repair_drifted_metadata(&path, &meta, header, owner.as_ref(), fake_super);
+    }
+}
+
+/// Apply the source header's mtime, Unix mode and — when the session
+/// carries ownership — uid/gid to a content-equal destination file where
+/// they drifted. Best-effort per attribute, with the same
+/// warn-and-continue as the sink's own metadata apply (cross-fs or
+/// root-owned destinations can refuse any of them). A file hard-linked
+/// elsewhere is copied apart before its first stamp so the other names
+/// keep their own metadata.
+fn repair_drifted_metadata(
+    dst: &Path,
+    meta: &std::fs::Metadata,
+    header: &FileHeader,
+    owner: Option<&FileOwner>,
+    fake_super: bool,
+) {
+    // Unshared lazily (content-equal files mostly need no stamp), once;
+    // a file that cannot be copied apart is left untouched.
+    let mut unshared: Option<bool> = None;
+    let mut unshare = || {
+        *unshared.get_or_insert_with(|| match crate::fs_capability::unshare_hard_link(dst) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("unshare {}: {}", dst.display(), e);
+                false
+            }
+        })
+    };
+    // The owner before the mode: a chown can clear set-id bits, which
+    // the mode stamp below restores.
+    if let Some(owner) = owner {
+        let current = crate::ownership::read_owner(dst, meta, fake_super);
+        if current.map(|c| (c.uid, c.gid)) != Some((owner.uid, owner.gid)) && unshare() {
+            if let Err(e) = crate::ownership::apply_owner(dst, owner, fake_super) {
+                log::warn!("set ownership on {}: {}", dst.display(), e);
+            }
+        }
+    }
+    let dst_mtime = crate::wire_metadata::mtime_seconds(meta);
+    if header.mtime_seconds > 0 && dst_mtime != Some(header.mtime_seconds) && unshare() {
+        let ft = filetime::FileTime::from_unix_time(header.mtime_seconds, 0);
+        if let Err(e) = filetime::set_file_mtime(dst, ft) {
+            log::warn!("set mtime on {}: {}", dst.display(), e);
+        }
+    }
+    #[cfg(unix)]
+    if header.permissions != 0
+        && crate::wire_metadata::permissions_mode(meta) & 0o7777 != header.permissions & 0o7777
+        && unshare()
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Err(e) =
+            std::fs::set_permissions(dst, std::fs::Permissions::from_mode(header.permissions))
+        {
+            log::warn!("set permissions on {}: {}", dst.display(), e);
+        }
+    }
+}
+
 fn finalize_need_verdict(
     status: FileStatus,
     target: Option<(u64, i64)>,
@@ -5150,9 +6236,21 @@ fn finalize_need_verdict(
 /// the destination diff's Checksum-mode hasher (codex otp-10b-1 F3:
 /// `checksum::hash_file` runs a whole file uninterruptibly; inside the
 /// diff's blocking chunk that must yield to a dead session's abort
-/// flag within one chunk).
+/// flag within one chunk). An installed `--checksum-cache` answers
+/// unchanged files without a read and records fresh digests.
 fn hash_file_abortable(path: &Path, abort: &AtomicBool) -> Result<Vec<u8>> {
     use std::io::Read;
+    let cache = crate::checksum_cache::active();
+    let meta = match cache {
+        Some(cache) => {
+            let meta = std::fs::metadata(path)?;
+            if let Some(digest) = cache.lookup(path, &meta) {
+                return Ok(digest);
+            }
+            Some(meta)
+        }
+        None => None,
+    };
     let mut file = std::fs::File::open(path)?;
     let mut hasher = blake3::Hasher::new();
     let mut buf = vec![0u8; 64 * 1024];
@@ -5166,13 +6264,18 @@ fn hash_file_abortable(path: &Path, abort: &AtomicBool) -> Result<Vec<u8>> {
         }
         hasher.update(&buf[..got]);
     }
-    Ok(hasher.finalize().as_bytes().to_vec())
+    let digest = hasher.finalize().as_bytes().to_vec();
+    if let (Some(cache), Some(meta)) = (cache, meta) {
+        cache.store(path, &meta, &digest);
+    }
+    Ok(digest)
 }
 
 /// otp-7a: hash the destination's existing partial for one
 /// resume-flagged grant — Blake3 per `block_size` block, in order (the
-/// wire shape of `BlockHashList.hashes`). Pure blocking FS work, so it
-/// runs on the blocking pool (same rationale as the diff chunks). A file
+/// wire shape of `BlockHashList.hashes`), keyed under `hash_key` when
+/// the open carried a `--checksum-seed` (v19). Pure blocking FS work, so
+/// it runs on the blocking pool (same rationale as the diff chunks). A file
 /// that vanished (or emptied) between the diff and this read yields an
 /// empty/short list — the implicit full-transfer fallback (plan D1): the
 /// source sends every block a hash does not vouch for.
@@ -5181,6 +6284,7 @@ async fn compute_resume_block_hashes(
     canonical_dst_root: Option<&Path>,
     relative_path: &str,
     block_size: usize,
+    hash_key: Option<[u8; 32]>,
 ) -> Result<Vec<Vec<u8>>> {
     let dst = match canonical_dst_root {
         Some(canonical) => {
@@ -5231,7 +6335,11 @@ async fn compute_resume_block_hashes(
             if filled == 0 {
                 break;
             }
-            hashes.push(blake3::hash(&buf[..filled]).as_bytes().to_vec());
+            hashes.push(
+                crate::remote::transfer::resume_diff::block_hash(hash_key.as_ref(), &buf[..filled])
+                    .as_bytes()
+                    .to_vec(),
+            );
             // A file growing concurrently with this read could blow past
             // the stat-time cap check — degrade, don't overshoot.
             if hashes.len() as u64 > MAX_RESUME_BLOCK_HASHES {
@@ -5531,6 +6639,12 @@ async fn receive_tar_record(
             shard.archive_size, MAX_TAR_SHARD_BYTES
         )));
     }
+    if shard.blake3.len() != TAR_SHARD_DIGEST_LEN {
+        return Err(violation(format!(
+            "tar shard record carries a {}-byte digest, expected {TAR_SHARD_DIGEST_LEN}",
+            shard.blake3.len()
+        )));
+    }
     let members = shard.files.len();
     let archive_bytes = shard.archive_size;
     let receive_started = small_file_probe.map(BoundSmallFileProbe::start);
@@ -5564,6 +6678,7 @@ async fn receive_tar_record(
                         shard.archive_size
                     )));
                 }
+                verify_tar_shard_digest(&shard.files, &data, &shard.blake3)?;
                 let decoded = receive_started.map(|_| std::time::Instant::now());
                 let shard_id = small_file_probe.map(|probe| probe.shard_id(&shard.files));
                 let correlated = receive_started.map(|_| std::time::Instant::now());
@@ -5701,9 +6816,12 @@ mod tests {
             hello: HelloConfig::default(),
             endpoint: SessionEndpoint::Responder,
             data_plane_host: None,
+            diff_batch: None,
             receiver_capacity: None,
             instruments: Default::default(),
             local_apply: None,
+            fake_super: false,
+            receive_sink: None,
         };
         let source: Arc<dyn TransferSource> = Arc::new(WindowsMetadataInjectingSource {
             inner: FsTransferSource::new(src_root.to_path_buf()),
@@ -5760,6 +6878,10 @@ mod tests {
                 file_attributes: 0x20,
                 named_streams: Vec::new(),
             }),
+            crtime_seconds: None,
+            directory: false,
+            owner: None,
+            xattrs: Vec::new(),
         };
 
         let error = destination_needs(
@@ -5767,6 +6889,7 @@ mod tests {
             tmp.path(),
             None,
             &CompareOptions::default(),
+            MetadataOnly::Off,
             &AtomicBool::new(false),
         )
         .expect_err("a non-Windows destination must reject strict metadata preservation");
@@ -5778,6 +6901,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn metadata_only_diff_marks_a_drifted_file_without_touching_it() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("same.bin");
+        std::fs::write(&path, b"same bytes").expect("write file");
+        filetime::set_file_mtime(&path, filetime::FileTime::from_unix_time(1_000, 0))
+            .expect("set mtime");
+        let header = FileHeader {
+            relative_path: "same.bin".into(),
+            size: 10,
+            mtime_seconds: 2_000,
+            permissions: 0,
+            checksum: blake3::hash(b"same bytes").as_bytes().to_vec(),
+            windows_metadata: None,
+            crtime_seconds: None,
+            directory: false,
+            owner: None,
+            xattrs: Vec::new(),
+        };
+        let opts = CompareOptions {
+            mode: CompareMode::Checksum,
+            ..CompareOptions::default()
+        };
+
+        for (metadata_only, expected) in [
+            (MetadataOnly::Repair, NeedVerdict::Repair),
+            (MetadataOnly::Preview, NeedVerdict::Skip),
+        ] {
+            let verdict = destination_needs(
+                &header,
+                tmp.path(),
+                None,
+                &opts,
+                metadata_only,
+                &AtomicBool::new(false),
+            )
+            .expect("diff");
+            assert_eq!(verdict, expected, "{metadata_only:?}");
+            let meta = std::fs::metadata(&path).expect("stat");
+            assert_eq!(
+                crate::wire_metadata::mtime_seconds(&meta),
+                Some(1_000),
+                "the diff stamped the file under {metadata_only:?}"
+            );
+        }
+    }
+
     #[tokio::test]
     async fn in_stream_resume_reuses_destination_hash_allocation_during_prepare() {
         let tmp = tempfile::tempdir().expect("tempdir");
@@ -5794,7 +6964,7 @@ mod tests {
         };
 
         let (header, block_size, prepared_hashes) =
-            prepare_in_stream_resume(&source, tar_test_header("resume.bin".into()), hashes)
+            prepare_in_stream_resume(&source, tar_test_header("resume.bin".into()), hashes, None)
                 .await
                 .expect("prepare resume payload");
 
@@ -5958,27 +7128,46 @@ mod tests {
 
     #[test]
     fn epoch0_grant_must_equal_receiver_bounded_floor() {
-        assert_eq!(data_plane::validate_epoch0_streams(4, None).unwrap(), 4);
-        assert!(data_plane::validate_epoch0_streams(1, None).is_err());
+        assert_eq!(
+            data_plane::validate_epoch0_streams(4, None, None).unwrap(),
+            4
+        );
+        assert!(data_plane::validate_epoch0_streams(1, None, None).is_err());
 
         let limited = CapacityProfile {
             max_streams: 2,
             ..Default::default()
         };
         assert_eq!(
-            data_plane::validate_epoch0_streams(2, Some(&limited)).unwrap(),
+            data_plane::validate_epoch0_streams(2, Some(&limited), None).unwrap(),
             2
         );
-        assert!(data_plane::validate_epoch0_streams(4, Some(&limited)).is_err());
+        assert!(data_plane::validate_epoch0_streams(4, Some(&limited), None).is_err());
 
         let unknown = CapacityProfile {
             max_streams: 0,
             ..Default::default()
         };
         assert_eq!(
-            data_plane::validate_epoch0_streams(4, Some(&unknown)).unwrap(),
+            data_plane::validate_epoch0_streams(4, Some(&unknown), None).unwrap(),
             4
         );
+
+        // An operator `--streams` pin replaces the floor on both ends,
+        // still clamped to the receiver's ceiling.
+        let pin = crate::generated::DialRequest {
+            streams: 6,
+            chunk_bytes: 0,
+        };
+        assert_eq!(
+            data_plane::validate_epoch0_streams(6, None, Some(&pin)).unwrap(),
+            6
+        );
+        assert!(data_plane::validate_epoch0_streams(4, None, Some(&pin)).is_err());
+        assert_eq!(
+            data_plane::validate_epoch0_streams(2, Some(&limited), Some(&pin)).unwrap(),
+            2
+        );
     }
 
     #[test]
@@ -6530,12 +7719,15 @@ mod tests {
             hello: HelloConfig::default(),
             endpoint: dest_endpoint,
             data_plane_host: dest_host,
+            diff_batch: None,
             receiver_capacity: Some(receiver_capacity),
             instruments: DestinationInstruments {
                 session_phase_trace: phase_trace,
                 ..Default::default()
             },
             local_apply: None,
+            fake_super: false,
+            receive_sink: None,
         };
 
         let gate = PayloadGate::new();
@@ -6867,12 +8059,15 @@ mod tests {
             hello: HelloConfig::default(),
             endpoint: dest_endpoint,
             data_plane_host: dest_host,
+            diff_batch: None,
             receiver_capacity: Some(constrained_profile(17)),
             instruments: DestinationInstruments {
                 session_phase_trace: phase_trace,
                 ..Default::default()
             },
             local_apply: None,
+            fake_super: false,
+            receive_sink: None,
         };
         let resize_frames: Arc<StdMutex<Vec<DataPlaneResize>>> = Arc::default();
         let (source_transport, dest_transport) = transport::in_process_pair();
@@ -6993,9 +8188,12 @@ mod tests {
                 ..Default::default()
             }),
             data_plane_host: Some("127.0.0.1".to_string()),
+            diff_batch: None,
             receiver_capacity: Some(constrained_profile(17)),
             instruments: Default::default(),
             local_apply: None,
+            fake_super: false,
+            receive_sink: None,
         };
         let (source_transport, destination_transport) = transport::in_process_pair();
         let session = tokio::spawn(async move {
@@ -7103,12 +8301,15 @@ mod tests {
             hello: HelloConfig::default(),
             endpoint: dest_endpoint,
             data_plane_host: dest_host,
+            diff_batch: None,
             receiver_capacity: Some(constrained_profile(17)),
             instruments: DestinationInstruments {
                 session_phase_trace: phase_trace,
                 ..Default::default()
             },
             local_apply: None,
+            fake_super: false,
+            receive_sink: None,
         };
 
         let source_resizes: Arc<StdMutex<Vec<DataPlaneResize>>> = Arc::default();
@@ -7317,12 +8518,15 @@ mod tests {
                 hello: HelloConfig::default(),
                 endpoint: dest_endpoint,
                 data_plane_host: dest_host,
+                diff_batch: None,
                 receiver_capacity: Some(constrained_profile(17)),
                 instruments: DestinationInstruments {
                     session_phase_trace: phase_trace,
                     ..Default::default()
                 },
                 local_apply: None,
+                fake_super: false,
+                receive_sink: None,
             };
             let fault_gate = DialTerminalTestGate::new();
             let source: Arc<dyn TransferSource> = Arc::new(PrepareFaultSource {
@@ -7438,12 +8642,15 @@ mod tests {
                 hello: HelloConfig::default(),
                 endpoint: dest_endpoint,
                 data_plane_host: dest_host,
+                diff_batch: None,
                 receiver_capacity: Some(constrained_profile(17)),
                 instruments: DestinationInstruments {
                     session_phase_trace: phase_trace,
                     ..Default::default()
                 },
                 local_apply: None,
+                fake_super: false,
+                receive_sink: None,
             };
             let resize_frames: Arc<StdMutex<Vec<DataPlaneResize>>> = Arc::default();
             let cancellation_fired = Arc::new(AtomicBool::new(false));
@@ -7782,6 +8989,7 @@ mod tests {
             Some(&elsewhere),
             &abort,
             true,
+            None,
         )
         .expect_err("a target outside the canonical root must refuse");
         assert!(
@@ -7803,6 +9011,7 @@ mod tests {
             Some(&real_root),
             &abort,
             true,
+            None,
         )
         .expect("in-root deletion proceeds");
         assert_eq!(deleted, (1, 0));
@@ -7828,6 +9037,7 @@ mod tests {
             None,
             &abort,
             false,
+            None,
         )
         .expect("plan-only pass");
         assert_eq!(counts, (2, 1));
@@ -7854,6 +9064,7 @@ mod tests {
             None,
             &abort,
             true,
+            None,
         )
         .expect("pass");
         assert_eq!(counts, (2, 2));
@@ -7909,6 +9120,7 @@ mod tests {
             peer_notified: true,
             relative_path: None,
             io_kind: None,
+            data_plane_unreachable: false,
         }))
         .expect("send fault");
 
@@ -7949,6 +9161,7 @@ mod tests {
             peer_notified: true,
             relative_path: None,
             io_kind: None,
+            data_plane_unreachable: false,
         }))
         .expect("send fault");
 
@@ -8058,6 +9271,7 @@ mod tests {
             peer_notified: false,
             relative_path: None,
             io_kind: None,
+            data_plane_unreachable: false,
         };
         let wire = fault.to_wire();
         let back = SessionFault::from_wire(wire);
@@ -8090,6 +9304,7 @@ mod tests {
             None,
             &abort,
             true,
+            None,
         );
         assert!(result.is_err(), "an aborted pass reports the abort");
         assert!(
@@ -8107,6 +9322,7 @@ mod tests {
             None,
             &abort,
             true,
+            None,
         )
         .expect("pass succeeds");
         assert_eq!(deleted, (1, 0));
@@ -8121,6 +9337,10 @@ mod tests {
             permissions: 0o644,
             checksum: Vec::new(),
             windows_metadata: None,
+            crtime_seconds: None,
+            directory: false,
+            owner: None,
+            xattrs: Vec::new(),
         }
     }
 
@@ -8263,7 +9483,7 @@ mod tests {
             small_count_target: Some(4096),
             ..PlanOptions::default()
         };
-        let mut read_buf = vec![0u8; IN_STREAM_CHUNK];
+        let mut read_buf = vec![0u8; in_stream_chunk()];
         send_payload_records(
             &mut tx,
             &source,
@@ -8299,4 +9519,103 @@ mod tests {
         }
         assert_eq!(total_files, 4096, "no file lost or duplicated by the split");
     }
+
+    /// A wide directory under tight diff-batch limits: every NeedBatch
+    /// the destination sends stays within the entry count, the byte
+    /// budget cuts some short, and the whole tree still lands.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn wide_directory_need_batches_respect_configured_limits() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let src_root = tmp.path().join("src");
+        let dst_root = tmp.path().join("dst");
+        std::fs::create_dir_all(src_root.join("wide")).expect("source dir");
+        std::fs::create_dir_all(&dst_root).expect("dest dir");
+        const FILES: usize = 3000;
+        for i in 0..FILES {
+            // Long names on every fifth file make the byte budget cut
+            // some batches short of the entry count.
+            let name = if i % 5 == 0 {
+                format!("{}-{i}", "n".repeat(180))
+            } else {
+                format!("f{i}")
+            };
+            std::fs::write(src_root.join("wide").join(name), b"x").expect("write");
+        }
+        let limits = diff_batch::DiffBatchLimits {
+            max_entries: 64,
+            max_bytes: 4 * 1024,
+        };
+
+        let events: Arc<StdMutex<Vec<crate::remote::transfer::session_phase::SessionPhaseEvent>>> =
+            Arc::default();
+        let captured = Arc::clone(&events);
+        let phase_trace = SessionPhaseTrace::capture("diff-batch", move |event| {
+            captured.lock().expect("capture lock").push(event);
+        });
+        let open = SessionOpen {
+            initiator_role: TransferRole::Source as i32,
+            compare_mode: ComparisonMode::SizeMtime as i32,
+            in_stream_bytes: false,
+            ..Default::default()
+        };
+        let source_cfg = SourceSessionConfig {
+            instruments: Default::default(),
+            hello: HelloConfig::default(),
+            endpoint: SessionEndpoint::initiator(open),
+            plan_options: PlanOptions::default(),
+            data_plane_host: Some("127.0.0.1".to_string()),
+        };
+        let destination_cfg = DestinationSessionConfig {
+            hello: HelloConfig::default(),
+            endpoint: SessionEndpoint::Responder,
+            data_plane_host: Some("127.0.0.1".to_string()),
+            diff_batch: Some(limits),
+            receiver_capacity: None,
+            instruments: DestinationInstruments {
+                session_phase_trace: phase_trace,
+                ..Default::default()
+            },
+            local_apply: None,
+            fake_super: false,
+            receive_sink: None,
+        };
+        let source: Arc<dyn TransferSource> = Arc::new(FsTransferSource::new(src_root.clone()));
+        let (source_transport, destination_transport) = transport::in_process_pair();
+        let (summary, outcome) = tokio::join!(
+            run_source(source_cfg, source_transport, source),
+            run_destination(
+                destination_cfg,
+                destination_transport,
+                DestinationTarget::Fixed(dst_root.clone())
+            )
+        );
+        summary.expect("source completes");
+        outcome.expect("destination completes");
+
+        let counts: Vec<u64> = events
+            .lock()
+            .expect("capture lock")
+            .iter()
+            .filter(|e| {
+                e.endpoint_role == SessionPhaseRole::Destination
+                    && e.event == "need_batch_send_begin"
+            })
+            .filter_map(|e| e.count)
+            .collect();
+        assert_eq!(counts.iter().sum::<u64>(), FILES as u64);
+        assert!(
+            counts.iter().all(|&n| n <= limits.max_entries as u64),
+            "a need batch exceeded the entry limit: {counts:?}"
+        );
+        assert!(
+            counts[..counts.len() - 1]
+                .iter()
+                .any(|&n| n < limits.max_entries as u64),
+            "the byte budget never cut a batch: {counts:?}"
+        );
+        assert_eq!(
+            std::fs::read_dir(dst_root.join("wide")).unwrap().count(),
+            FILES
+        );
+    }
 }
@@ -224,6 +224,14 @@ pub fn is_reserved_name(basename: &OsStr) -> bool {
     }
 }
 
+/// The `FILE_ATTRIBUTE_*` bits recorded for an entry. `metadata` must
+/// come from the entry itself (`symlink_metadata` for a link), so a
+/// hidden link to a visible target reads as hidden.
+pub fn file_attributes(metadata: &fs::Metadata) -> u32 {
+    use std::os::windows::fs::MetadataExt;
+    metadata.file_attributes()
+}
+
 /// Ensure a path is suitable for long-path operations by adding the `\\?\` prefix when needed.
 /// UNC paths will be rewritten as `\\?\UNC\server\share\...`.
 pub fn ensure_long_path(p: &Path) -> PathBuf {
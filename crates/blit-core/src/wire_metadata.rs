@@ -6,7 +6,7 @@
 //! existing negative-seconds representation.
 
 use std::fs::Metadata;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 fn system_time_to_unix_seconds(time: SystemTime) -> i64 {
     match time.duration_since(UNIX_EPOCH) {
@@ -22,6 +22,23 @@ pub fn mtime_seconds(metadata: &Metadata) -> Option<i64> {
     metadata.modified().ok().map(system_time_to_unix_seconds)
 }
 
+/// Convert filesystem creation (birth) time to signed Unix seconds.
+///
+/// Returns `None` when the platform or filesystem does not record one.
+pub fn crtime_seconds(metadata: &Metadata) -> Option<i64> {
+    metadata.created().ok().map(system_time_to_unix_seconds)
+}
+
+/// Inverse of the signed-seconds wire representation.
+pub fn unix_seconds_to_system_time(seconds: i64) -> SystemTime {
+    let magnitude = Duration::from_secs(seconds.unsigned_abs());
+    if seconds >= 0 {
+        UNIX_EPOCH + magnitude
+    } else {
+        UNIX_EPOCH - magnitude
+    }
+}
+
 /// Return the Unix permission mode carried by transfer headers.
 ///
 /// Non-Unix peers carry zero; Windows attributes use the separate Windows
@@ -43,7 +60,6 @@ pub fn permissions_mode(metadata: &Metadata) -> u32 {
 mod tests {
     use super::*;
     use std::fs;
-    use std::time::Duration;
 
     #[test]
     fn signed_unix_seconds_preserve_both_epoch_sides() {
@@ -51,6 +67,8 @@ mod tests {
         let before = UNIX_EPOCH - Duration::from_secs(9);
         assert_eq!(system_time_to_unix_seconds(after), 17);
         assert_eq!(system_time_to_unix_seconds(before), -9);
+        assert_eq!(unix_seconds_to_system_time(17), after);
+        assert_eq!(unix_seconds_to_system_time(-9), before);
     }
 
     #[test]
@@ -0,0 +1,231 @@
+//! `--xattrs`: extended attributes carried across, filtered by
+//! namespace.
+//!
+//! Some namespaces need privilege to set (`trusted.*`, most of
+//! `security.*`) or only mean something on the host that wrote them
+//! (`security.selinux`, `system.posix_acl_*`), so copying them blindly
+//! fails or mislabels the destination. An [`XattrFilter`] therefore lets
+//! through only `user.*` unless asked for more: `--xattrs-all` admits
+//! every namespace, and `--xattrs-include` / `--xattrs-exclude` add or
+//! remove one (`trusted`) or a single attribute (`user.mime_type`).
+//! Exclusions win. Blit's own `user.blit.*` records (the fake-super
+//! stat) never ride along: each end keeps its own.
+//!
+//! The filter rides `SessionOpen.xattrs` (contract v22) as its spec
+//! string. The SOURCE stamps each manifest header with the attributes it
+//! admits, and the DESTINATION applies them once the transfer settles,
+//! filtering again. Setting one it lacks the privilege for is a warning,
+//! never a failure. Unix only: elsewhere nothing is read.
+
+use eyre::{bail, Result};
+
+use crate::generated::Xattr;
+
+/// Namespace prefix of Blit's own attributes, which never transfer.
+const INTERNAL_PREFIX: &str = "user.blit";
+
+/// Which extended attributes a session carries. Build it from the CLI
+/// flags with [`XattrFilter::new`]; it crosses the wire as
+/// [`XattrFilter::spec`] and is rebuilt with [`XattrFilter::parse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XattrFilter {
+    /// `*` admits every name; otherwise each entry is a namespace or
+    /// attribute name.
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl XattrFilter {
+    /// `user.*` (every namespace with `all`) plus `include`, less
+    /// `exclude`.
+    pub fn new(all: bool, include: &[String], exclude: &[String]) -> Result<Self> {
+        let mut filter = Self {
+            include: vec![if all { "*" } else { "user" }.to_string()],
+            exclude: Vec::new(),
+        };
+        for name in include {
+            filter.include.push(validate(name)?);
+        }
+        for name in exclude {
+            filter.exclude.push(validate(name)?);
+        }
+        Ok(filter)
+    }
+
+    /// Rebuild a filter from its [`Self::spec`]: comma-separated names,
+    /// `*` for every namespace, `-` before an exclusion.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut filter = Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+        };
+        for rule in spec.split(',') {
+            match rule.strip_prefix('-') {
+                Some(name) => filter.exclude.push(validate(name)?),
+                None if rule == "*" => filter.include.push(rule.to_string()),
+                None => filter.include.push(validate(rule)?),
+            }
+        }
+        Ok(filter)
+    }
+
+    /// [`Self::parse`] for a spec that may be empty, meaning no
+    /// attributes cross (`None`).
+    pub fn from_spec(spec: &str) -> Result<Option<Self>> {
+        if spec.is_empty() {
+            return Ok(None);
+        }
+        Self::parse(spec).map(Some)
+    }
+
+    /// The wire form [`Self::parse`] reads back.
+    pub fn spec(&self) -> String {
+        self.include
+            .iter()
+            .cloned()
+            .chain(self.exclude.iter().map(|name| format!("-{name}")))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Whether the attribute `name` transfers.
+    pub fn admits(&self, name: &str) -> bool {
+        !covers(INTERNAL_PREFIX, name)
+            && self
+                .include
+                .iter()
+                .any(|rule| rule == "*" || covers(rule, name))
+            && !self.exclude.iter().any(|rule| covers(rule, name))
+    }
+
+    /// The admitted attributes of `path` itself (a symlink is not
+    /// followed), sorted by name. An attribute that vanishes between
+    /// listing and reading is passed over.
+    #[cfg(unix)]
+    pub fn read(&self, path: &std::path::Path) -> std::io::Result<Vec<Xattr>> {
+        let mut xattrs = Vec::new();
+        for name in xattr::list(path)? {
+            let Some(name) = name.to_str().filter(|name| self.admits(name)) else {
+                continue;
+            };
+            if let Some(value) = xattr::get(path, name)? {
+                xattrs.push(Xattr {
+                    name: name.to_string(),
+                    value,
+                });
+            }
+        }
+        xattrs.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(xattrs)
+    }
+
+    #[cfg(not(unix))]
+    pub fn read(&self, _path: &std::path::Path) -> std::io::Result<Vec<Xattr>> {
+        Ok(Vec::new())
+    }
+
+    /// Set each admitted attribute in `xattrs` on `path` whose value
+    /// differs. Best effort per attribute: a refusal (no privilege, a
+    /// filesystem without that namespace) is logged as `rel` and the
+    /// rest continue.
+    #[cfg(unix)]
+    pub fn apply(&self, path: &std::path::Path, rel: &str, xattrs: &[Xattr]) {
+        for attr in xattrs.iter().filter(|attr| self.admits(&attr.name)) {
+            if xattr::get(path, &attr.name).ok().flatten().as_ref() == Some(&attr.value) {
+                continue;
+            }
+            if let Err(err) = xattr::set(path, &attr.name, &attr.value) {
+                log::warn!("skipping xattr {} on {rel}: {err}", attr.name);
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn apply(&self, _path: &std::path::Path, _rel: &str, _xattrs: &[Xattr]) {}
+}
+
+/// Every extended attribute of `path` itself, Blit's own included,
+/// sorted by name — everything a hard link to `path` shares, whatever
+/// the transfer's filter.
+#[cfg(unix)]
+pub fn read_all(path: &std::path::Path) -> std::io::Result<Vec<(std::ffi::OsString, Vec<u8>)>> {
+    let mut xattrs = Vec::new();
+    for name in xattr::list(path)? {
+        if let Some(value) = xattr::get(path, &name)? {
+            xattrs.push((name, value));
+        }
+    }
+    xattrs.sort();
+    Ok(xattrs)
+}
+
+#[cfg(not(unix))]
+pub fn read_all(_path: &std::path::Path) -> std::io::Result<Vec<(std::ffi::OsString, Vec<u8>)>> {
+    Ok(Vec::new())
+}
+
+/// `rule` names `name` itself or a namespace above it.
+fn covers(rule: &str, name: &str) -> bool {
+    name.strip_prefix(rule)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+fn validate(name: &str) -> Result<String> {
+    let name = name.trim().trim_end_matches(".*");
+    if name.is_empty() || name.contains([',', '*']) || name.starts_with(['-', '.']) {
+        bail!("invalid xattr namespace '{name}'");
+    }
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn only_user_attributes_transfer_by_default() {
+        let filter = XattrFilter::new(false, &[], &[]).unwrap();
+        assert!(filter.admits("user.mime_type"));
+        assert!(!filter.admits("security.selinux"));
+        assert!(!filter.admits("trusted.overlay"));
+        assert!(!filter.admits("userland.x"));
+        assert!(!filter.admits("user.blit.stat"));
+    }
+
+    #[test]
+    fn includes_add_and_exclusions_win() {
+        let filter = XattrFilter::new(
+            false,
+            &names(&["trusted", "security.capability"]),
+            &names(&["user.secret.*"]),
+        )
+        .unwrap();
+        assert!(filter.admits("trusted.md5"));
+        assert!(filter.admits("security.capability"));
+        assert!(!filter.admits("security.selinux"));
+        assert!(!filter.admits("user.secret.key"));
+        assert!(filter.admits("user.secretive"));
+
+        let all = XattrFilter::new(true, &[], &names(&["system"])).unwrap();
+        assert!(all.admits("security.selinux"));
+        assert!(!all.admits("system.posix_acl_access"));
+        assert!(!all.admits("user.blit.stat"));
+    }
+
+    #[test]
+    fn the_spec_round_trips_and_bad_names_are_refused() {
+        let filter = XattrFilter::new(true, &names(&["trusted"]), &names(&["system"])).unwrap();
+        assert_eq!(filter.spec(), "*,trusted,-system");
+        assert_eq!(XattrFilter::parse(&filter.spec()).unwrap(), filter);
+        for bad in ["", "-user", "a,b", ".user"] {
+            assert!(
+                XattrFilter::new(false, &names(&[bad]), &[]).is_err(),
+                "{bad:?}"
+            );
+        }
+    }
+}
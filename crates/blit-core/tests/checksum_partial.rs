@@ -47,3 +47,91 @@ fn file_needs_copy_partial_short_circuit() -> Result<()> {
     assert!(needs(&a, &b, Some(ChecksumType::Blake3))?);
     Ok(())
 }
+
+/// Read + Seek wrapper that records how much was read and the largest
+/// single read, so the bounded-memory claim is pinned directly.
+struct CountingReader<R> {
+    inner: R,
+    bytes_read: u64,
+    largest_read: usize,
+}
+
+impl<R: std::io::Read> std::io::Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.largest_read = self.largest_read.max(buf.len());
+        let n = self.inner.read(buf)?;
+        self.bytes_read += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: std::io::Seek> std::io::Seek for CountingReader<R> {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+/// 4 GiB sparse file with marker bytes at each end; takes no disk.
+fn sparse_file(path: &std::path::Path, head: u8, tail: u8) -> Result<u64> {
+    use std::io::{Seek, SeekFrom};
+    let len = 4u64 << 30;
+    let mut f = fs::File::create(path)?;
+    f.set_len(len)?;
+    f.write_all(&[head])?;
+    f.seek(SeekFrom::End(-1))?;
+    f.write_all(&[tail])?;
+    Ok(len)
+}
+
+#[test]
+fn partial_hash_streams_only_the_two_windows_of_a_huge_file() -> Result<()> {
+    let tmp = tempfile::TempDir::new()?;
+    let path = tmp.path().join("huge.bin");
+    let len = sparse_file(&path, 1, 2)?;
+    let window = 16 * 1024 * 1024;
+
+    let mut reader = CountingReader {
+        inner: fs::File::open(&path)?,
+        bytes_read: 0,
+        largest_read: 0,
+    };
+    let streamed = checksum::partial_hash_reader(&mut reader, len, window)?;
+    assert_eq!(reader.bytes_read, 2 * window as u64);
+    assert!(
+        reader.largest_read <= checksum::HASH_READ_BUF_BYTES,
+        "read {} bytes at once for a {window}-byte window",
+        reader.largest_read
+    );
+    assert_eq!(streamed, checksum::partial_hash_first_last(&path, window)?);
+
+    // Same framing as the one-shot digest, so cached and on-wire
+    // partial hashes stay comparable.
+    let mut head = vec![0u8; window];
+    head[0] = 1;
+    let mut tail = vec![0u8; window];
+    tail[window - 1] = 2;
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"FIRST");
+    hasher.update(&head);
+    hasher.update(b"LAST");
+    hasher.update(&tail);
+    hasher.update(&len.to_le_bytes());
+    assert_eq!(streamed, hasher.finalize().as_bytes().to_vec());
+    Ok(())
+}
+
+#[test]
+fn checksum_compare_flags_a_partial_mismatch_on_a_huge_file() -> Result<()> {
+    use blit_core::copy::file_needs_copy_with_mode as needs;
+    use blit_core::generated::ComparisonMode;
+    let tmp = tempfile::TempDir::new()?;
+    let a = tmp.path().join("a.bin");
+    let b = tmp.path().join("b.bin");
+    sparse_file(&a, 1, 2)?;
+    sparse_file(&b, 9, 2)?;
+
+    // The bytes-read bound on this path is pinned by the unit tests in
+    // copy/compare.rs; here the public predicate just has to agree.
+    assert!(needs(&a, &b, ComparisonMode::Checksum)?);
+    Ok(())
+}
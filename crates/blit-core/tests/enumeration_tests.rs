@@ -1,6 +1,12 @@
-use blit_core::enumeration::{EntryKind, FileEnumerator};
+use blit_core::enumeration::{EntryKind, FileEnumerator, SortOrder};
 use blit_core::fs_enum::FileFilter;
+use blit_core::generated::FileHeader;
+use blit_core::remote::transfer::payload::{
+    build_tar_shard, plan_transfer_payloads, TransferPayload,
+};
+use blit_core::transfer_plan::PlanOptions;
 use eyre::Result;
+use std::path::Path;
 
 #[test]
 fn enumerator_returns_directories_and_files() -> Result<()> {
@@ -86,3 +92,241 @@ fn enumerator_reports_symlinks_when_requested() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(unix)]
+fn file_paths(entries: &[blit_core::enumeration::EnumeratedEntry]) -> Vec<String> {
+    let mut paths: Vec<String> = entries
+        .iter()
+        .filter(|e| matches!(e.kind, EntryKind::File { .. }))
+        .map(|e| e.relative_path.to_string_lossy().into_owned())
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[cfg(unix)]
+#[test]
+fn copy_dirlinks_descends_into_symlinked_directories() -> Result<()> {
+    use blit_core::enumeration::SymlinkDeref;
+    use std::os::unix::fs::symlink;
+
+    let temp = tempfile::tempdir()?;
+    let src = temp.path().join("src");
+    let outside = temp.path().join("outside");
+    std::fs::create_dir_all(outside.join("nested"))?;
+    std::fs::write(outside.join("nested/data.txt"), b"data")?;
+    std::fs::create_dir_all(&src)?;
+    std::fs::write(src.join("a.txt"), b"a")?;
+    symlink(&outside, src.join("linked"))?;
+    symlink(src.join("a.txt"), src.join("alias.txt"))?;
+
+    let enumerate = |deref| {
+        FileEnumerator::new(FileFilter::default())
+            .deref_symlinks(deref)
+            .enumerate_local_capturing(&src)
+    };
+
+    let (entries, outcome) = enumerate(SymlinkDeref::None)?;
+    assert_eq!(file_paths(&entries), vec!["a.txt"]);
+    assert!(outcome.suppressed_errors.is_empty());
+
+    let (entries, outcome) = enumerate(SymlinkDeref::Dirs)?;
+    assert_eq!(
+        file_paths(&entries),
+        vec!["a.txt", "linked/nested/data.txt"]
+    );
+    assert!(entries.iter().any(|e| {
+        matches!(e.kind, EntryKind::Directory) && e.relative_path == std::path::Path::new("linked")
+    }));
+    let data = entries
+        .iter()
+        .find(|e| e.relative_path == std::path::Path::new("linked/nested/data.txt"))
+        .unwrap();
+    assert_eq!(std::fs::read(&data.absolute_path)?, b"data");
+    assert!(outcome.suppressed_errors.is_empty());
+
+    let (entries, _) = enumerate(SymlinkDeref::All)?;
+    assert_eq!(
+        file_paths(&entries),
+        vec!["a.txt", "alias.txt", "linked/nested/data.txt"]
+    );
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn copy_dirlinks_skips_symlink_loops() -> Result<()> {
+    use blit_core::enumeration::SymlinkDeref;
+    use std::os::unix::fs::symlink;
+
+    let temp = tempfile::tempdir()?;
+    let src = temp.path().join("src");
+    let other = temp.path().join("other");
+    std::fs::create_dir_all(src.join("sub"))?;
+    std::fs::create_dir_all(&other)?;
+    std::fs::write(src.join("sub/file.txt"), b"x")?;
+    std::fs::write(other.join("o.txt"), b"o")?;
+    // Direct loop back to the root, and a two-hop loop that only
+    // closes after passing through another followed link.
+    symlink(&src, src.join("sub/to_root"))?;
+    symlink(&other, src.join("to_other"))?;
+    symlink(&src, other.join("back"))?;
+
+    let (entries, outcome) = FileEnumerator::new(FileFilter::default())
+        .deref_symlinks(SymlinkDeref::Dirs)
+        .enumerate_local_capturing(&src)?;
+
+    assert_eq!(file_paths(&entries), vec!["sub/file.txt", "to_other/o.txt"]);
+    assert!(
+        outcome.suppressed_errors.is_empty(),
+        "a skipped loop is not an incomplete scan: {:?}",
+        outcome.suppressed_errors
+    );
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn safe_links_and_copy_unsafe_links_split_links_by_where_they_resolve() -> Result<()> {
+    use blit_core::enumeration::SymlinkDeref;
+    use std::os::unix::fs::symlink;
+
+    let temp = tempfile::tempdir()?;
+    let src = temp.path().join("src");
+    let outside = temp.path().join("outside");
+    std::fs::create_dir_all(outside.join("dir"))?;
+    std::fs::write(outside.join("secret.txt"), b"secret")?;
+    std::fs::write(outside.join("dir/x.txt"), b"x")?;
+    std::fs::create_dir_all(&src)?;
+    std::fs::write(src.join("a.txt"), b"a")?;
+    symlink(src.join("a.txt"), src.join("inner.txt"))?;
+    symlink("../outside/secret.txt", src.join("escape.txt"))?;
+    symlink(outside.join("dir"), src.join("outdir"))?;
+
+    let enumerate = |deref, safe| {
+        FileEnumerator::new(FileFilter::default())
+            .deref_symlinks(deref)
+            .safe_links(safe)
+            .enumerate_local_capturing(&src)
+    };
+
+    // Default: no link is followed, in-tree or not.
+    let (entries, _) = enumerate(SymlinkDeref::None, false)?;
+    assert_eq!(file_paths(&entries), vec!["a.txt"]);
+
+    let (entries, _) = enumerate(SymlinkDeref::All, false)?;
+    assert_eq!(
+        file_paths(&entries),
+        vec!["a.txt", "escape.txt", "inner.txt", "outdir/x.txt"]
+    );
+
+    // --safe-links: the in-tree link is still followed, the escaping
+    // ones are dropped, and dropping them is not an incomplete scan.
+    let (entries, outcome) = enumerate(SymlinkDeref::All, true)?;
+    assert_eq!(file_paths(&entries), vec!["a.txt", "inner.txt"]);
+    assert!(!entries
+        .iter()
+        .any(|e| e.relative_path == std::path::Path::new("outdir")));
+    assert!(outcome.suppressed_errors.is_empty());
+
+    // --copy-unsafe-links: only the escaping links are dereferenced.
+    let (entries, outcome) = enumerate(SymlinkDeref::Unsafe, false)?;
+    assert_eq!(
+        file_paths(&entries),
+        vec!["a.txt", "escape.txt", "outdir/x.txt"]
+    );
+    let escaped = entries
+        .iter()
+        .find(|e| e.relative_path == std::path::Path::new("escape.txt"))
+        .unwrap();
+    assert_eq!(std::fs::read(&escaped.absolute_path)?, b"secret");
+    assert!(outcome.suppressed_errors.is_empty());
+
+    Ok(())
+}
+
+/// The same tree laid down in opposite creation orders, so each
+/// directory's on-disk listing order differs between the two copies.
+fn write_tree(root: &Path, reversed: bool) -> Result<()> {
+    let mut names: Vec<String> = (0..60).map(|i| format!("d{}/f{i:02}.txt", i % 3)).collect();
+    if reversed {
+        names.reverse();
+    }
+    for name in names {
+        let path = root.join(&name);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(&path, name.as_bytes())?;
+        filetime::set_file_mtime(&path, filetime::FileTime::from_unix_time(1_700_000_000, 0))?;
+    }
+    Ok(())
+}
+
+fn sorted_scan(root: &Path) -> Result<Vec<String>> {
+    Ok(FileEnumerator::new(FileFilter::default())
+        .sort_order(SortOrder::Name)
+        .enumerate_local(root)?
+        .into_iter()
+        .map(|e| e.relative_path.to_string_lossy().replace('\\', "/"))
+        .collect())
+}
+
+#[test]
+fn sort_order_name_visits_each_directory_in_name_order() -> Result<()> {
+    let temp = tempfile::tempdir()?;
+    write_tree(temp.path(), true)?;
+
+    let paths = sorted_scan(temp.path())?;
+    assert_eq!(paths.len(), 63);
+    assert_eq!(&paths[..3], ["d0", "d0/f00.txt", "d0/f03.txt"]);
+    assert!(paths.windows(2).all(|w| w[0] < w[1]), "{paths:?}");
+    Ok(())
+}
+
+/// `--sort-order name`: two runs over identical content produce the
+/// same manifest and byte-identical tar shards, whatever order the
+/// filesystem lists entries in.
+#[test]
+fn sort_order_name_builds_byte_identical_shards() -> Result<()> {
+    let shards_of = |root: &Path| -> Result<Vec<Vec<u8>>> {
+        let headers: Vec<FileHeader> = FileEnumerator::new(FileFilter::default())
+            .sort_order(SortOrder::Name)
+            .enumerate_local(root)?
+            .into_iter()
+            .filter_map(|e| match e.kind {
+                EntryKind::File { size } => Some(FileHeader {
+                    relative_path: e.relative_path.to_string_lossy().replace('\\', "/"),
+                    size,
+                    mtime_seconds: 1_700_000_000,
+                    permissions: 0o644,
+                    ..FileHeader::default()
+                }),
+                _ => None,
+            })
+            .collect();
+        let options = PlanOptions {
+            sort_order: SortOrder::Name,
+            shard_max_files: Some(16),
+            ..PlanOptions::default()
+        };
+        plan_transfer_payloads(headers, root, options)?
+            .into_iter()
+            .map(|payload| match payload {
+                TransferPayload::TarShard { headers } => build_tar_shard(root, &headers),
+                other => panic!("expected only tar shards, got {other:?}"),
+            })
+            .collect()
+    };
+
+    let temp = tempfile::tempdir()?;
+    let (forward, reversed) = (temp.path().join("forward"), temp.path().join("reversed"));
+    write_tree(&forward, false)?;
+    write_tree(&reversed, true)?;
+
+    assert_eq!(sorted_scan(&forward)?, sorted_scan(&reversed)?);
+    let shards = shards_of(&forward)?;
+    assert_eq!(shards.len(), 4);
+    assert_eq!(shards, shards_of(&reversed)?);
+    Ok(())
+}
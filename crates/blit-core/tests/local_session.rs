@@ -7,14 +7,18 @@
 //! observable contract on the session route.
 
 use blit_core::config;
+use blit_core::generated::session_error;
 use blit_core::perf_history;
+use blit_core::remote::transfer::source::FileCountWarning;
 use blit_core::transfer_session::run_local_session;
 use blit_core::transfer_session::{
-    LocalCompareMode, LocalMirrorDeleteScope, LocalMirrorOptions, TransferOutcome,
+    LocalCompareMode, LocalMirrorDeleteScope, LocalMirrorOptions, SessionFault, TransferOutcome,
 };
 use eyre::Result;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use tempfile::tempdir;
 use tokio::sync::Mutex;
 
@@ -138,6 +142,36 @@ async fn empty_source_dir_reports_source_empty() -> Result<()> {
     Ok(())
 }
 
+/// Empty source directories are recreated locally (contract v12
+/// directory entries); a dry run still creates none of them.
+#[tokio::test]
+async fn empty_directories_copy_and_dry_run_creates_none() -> Result<()> {
+    let tmp = tempdir()?;
+    let src = tmp.path().join("src");
+    let dest = tmp.path().join("dest");
+    fs::create_dir_all(src.join("empty/nested"))?;
+    fs::create_dir_all(src.join("full"))?;
+    fs::write(src.join("full/a.txt"), b"one")?;
+
+    let preview = run_local_session(
+        &src,
+        &dest,
+        LocalMirrorOptions {
+            dry_run: true,
+            ..options()
+        },
+    )
+    .await?;
+    assert!(preview.dry_run);
+    assert!(!dest.join("empty").exists(), "dry run must create nothing");
+
+    let summary = run_local_session(&src, &dest, options()).await?;
+    assert_eq!(summary.copied_files, 1);
+    assert!(dest.join("empty/nested").is_dir());
+    assert_eq!(fs::read(dest.join("full/a.txt"))?, b"one");
+    Ok(())
+}
+
 /// Port of `single_file_copy_records_history`: a file source root
 /// (empty wire relative path) copies to the exact destination path
 /// with scanned-feature accounting.
@@ -825,6 +859,131 @@ async fn ignore_existing_tree_keeps_existing_lands_missing() -> Result<()> {
     Ok(())
 }
 
+/// `--existing` over a tree: present destination entries update as
+/// the compare mode decides; absent files and directories never land.
+#[tokio::test]
+async fn existing_only_tree_updates_existing_skips_missing() -> Result<()> {
+    let tmp = tempdir()?;
+    let src = tmp.path().join("src");
+    let dest = tmp.path().join("dest");
+    fs::create_dir_all(src.join("fresh"))?;
+    fs::create_dir_all(&dest)?;
+    fs::write(src.join("exists.txt"), b"new content longer")?;
+    fs::write(src.join("missing.txt"), b"skipped")?;
+    fs::write(src.join("fresh/inner.txt"), b"skipped")?;
+    fs::write(dest.join("exists.txt"), b"old")?;
+
+    let summary = run_local_session(
+        &src,
+        &dest,
+        LocalMirrorOptions {
+            existing_only: true,
+            ..options()
+        },
+    )
+    .await?;
+    assert_eq!(summary.copied_files, 1);
+    assert_eq!(fs::read(dest.join("exists.txt"))?, b"new content longer");
+    assert!(!dest.join("missing.txt").exists());
+    assert!(!dest.join("fresh").exists());
+    Ok(())
+}
+
+/// `--max-files`: a tree over the cap is refused with FILE_LIMIT before
+/// anything lands; the same tree within the cap copies.
+#[tokio::test]
+async fn max_files_refuses_an_oversized_tree_and_copies_one_within() -> Result<()> {
+    let tmp = tempdir()?;
+    let src = tmp.path().join("src");
+    let dest = tmp.path().join("dest");
+    fs::create_dir_all(src.join("sub"))?;
+    fs::write(src.join("a.txt"), b"a")?;
+    fs::write(src.join("b.txt"), b"b")?;
+    fs::write(src.join("sub/c.txt"), b"c")?;
+
+    let err = run_local_session(
+        &src,
+        &dest,
+        LocalMirrorOptions {
+            max_files: 2,
+            ..options()
+        },
+    )
+    .await
+    .expect_err("three files over a cap of two must refuse");
+    let fault = err
+        .downcast_ref::<SessionFault>()
+        .unwrap_or_else(|| panic!("expected a session fault, got: {err:#}"));
+    assert_eq!(fault.code, session_error::Code::FileLimit, "{err:#}");
+    assert!(!dest.join("a.txt").exists(), "nothing lands on refusal");
+    assert!(!dest.join("sub").exists(), "nor any directory");
+
+    let summary = run_local_session(
+        &src,
+        &dest,
+        LocalMirrorOptions {
+            max_files: 3,
+            ..options()
+        },
+    )
+    .await?;
+    assert_eq!(summary.copied_files, 3);
+    assert_eq!(fs::read(dest.join("sub/c.txt"))?, b"c");
+    Ok(())
+}
+
+/// `--warn-files`: the scan asks once past the threshold; a "no"
+/// cancels the transfer, a "yes" lets it finish.
+#[tokio::test]
+async fn warn_files_asks_once_past_the_threshold() -> Result<()> {
+    let tmp = tempdir()?;
+    let src = tmp.path().join("src");
+    fs::create_dir_all(&src)?;
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        fs::write(src.join(name), name)?;
+    }
+    let run = |answer: bool, dest: PathBuf| {
+        let asked = Arc::new(AtomicU64::new(0));
+        let warning = FileCountWarning {
+            threshold: 1,
+            confirm: Arc::new({
+                let asked = Arc::clone(&asked);
+                move |threshold| {
+                    assert_eq!(threshold, 1);
+                    asked.fetch_add(1, Ordering::SeqCst);
+                    answer
+                }
+            }),
+        };
+        let src = src.clone();
+        async move {
+            let result = run_local_session(
+                &src,
+                &dest,
+                LocalMirrorOptions {
+                    file_count_warning: Some(warning),
+                    ..options()
+                },
+            )
+            .await;
+            (result, asked.load(Ordering::SeqCst))
+        }
+    };
+
+    let (declined, asked) = run(false, tmp.path().join("declined")).await;
+    let err = declined.expect_err("a declined prompt cancels");
+    let fault = err
+        .downcast_ref::<SessionFault>()
+        .unwrap_or_else(|| panic!("expected a session fault, got: {err:#}"));
+    assert_eq!(fault.code, session_error::Code::Cancelled, "{err:#}");
+    assert_eq!(asked, 1);
+
+    let (confirmed, asked) = run(true, tmp.path().join("confirmed")).await;
+    assert_eq!(confirmed?.copied_files, 3);
+    assert_eq!(asked, 1, "asked once, not per file");
+    Ok(())
+}
+
 /// Mirror scope under an INCLUDE filter: out-of-scope destination
 /// entries survive a FilteredSubset mirror.
 #[tokio::test]
@@ -1046,6 +1205,41 @@ async fn planner_mix_stats_populated_for_small_tree() -> Result<()> {
     Ok(())
 }
 
+/// `--shard-max-files` / `--no-shard` reach the local planner: a low
+/// member cap splits the same tree into more shards, and `--no-shard`
+/// copies every file as its own record.
+#[tokio::test]
+async fn shard_ceilings_reach_the_local_planner() -> Result<()> {
+    use blit_core::transfer_plan::PlanOptions;
+
+    let tmp = tempdir()?;
+    let src = tmp.path().join("src");
+    fs::create_dir_all(&src)?;
+    for idx in 0..50 {
+        fs::write(src.join(format!("f{idx:03}.txt")), b"tiny")?;
+    }
+
+    let mut capped = options();
+    capped.plan_options = PlanOptions {
+        shard_max_files: Some(10),
+        ..PlanOptions::default()
+    };
+    let summary = run_local_session(&src, &tmp.path().join("capped"), capped).await?;
+    assert_eq!(summary.copied_files, 50);
+    assert_eq!(summary.tar_shard_tasks, 5);
+    assert_eq!(summary.tar_shard_files, 50);
+
+    let mut unsharded = options();
+    unsharded.plan_options.no_shard = true;
+    let dest = tmp.path().join("unsharded");
+    let summary = run_local_session(&src, &dest, unsharded).await?;
+    assert_eq!(summary.copied_files, 50);
+    assert_eq!(summary.tar_shard_tasks, 0);
+    assert_eq!(summary.large_tasks, 50);
+    assert_eq!(fs::read(dest.join("f049.txt"))?, b"tiny");
+    Ok(())
+}
+
 /// Scanned-byte accounting across diff chunks: `scanned_bytes` is the
 /// exact post-filter source workload for a >1-chunk tree.
 #[tokio::test]
@@ -1161,3 +1355,221 @@ async fn unreadable_source_file_lands_in_summary_and_copy_continues() -> Result<
     );
     Ok(())
 }
+
+/// `--skip-locked`: a source file another process holds open with no
+/// sharing is recorded and skipped while the rest of the tree lands;
+/// without the flag the same lock fails the transfer.
+#[cfg(windows)]
+#[tokio::test]
+async fn skip_locked_skips_a_locked_file_and_copies_the_rest() -> Result<()> {
+    use std::os::windows::fs::OpenOptionsExt;
+
+    let tmp = tempdir()?;
+    let src = tmp.path().join("src");
+    let dest = tmp.path().join("dest");
+    fs::create_dir_all(src.join("nested"))?;
+    fs::write(src.join("ok.txt"), b"readable")?;
+    fs::write(src.join("nested/also.txt"), b"readable too")?;
+    fs::write(src.join("held.txt"), b"locked")?;
+    // Share mode 0: every other open fails with a sharing violation
+    // until this handle drops.
+    let _lock = fs::OpenOptions::new()
+        .read(true)
+        .share_mode(0)
+        .open(src.join("held.txt"))?;
+
+    let err = run_local_session(&src, &dest, options()).await.unwrap_err();
+    assert!(format!("{err:#}").contains("held.txt"));
+
+    let mut opts = options();
+    opts.skip_locked = true;
+    let summary = run_local_session(&src, &dest, opts).await?;
+    assert_eq!(fs::read(dest.join("ok.txt"))?, b"readable");
+    assert_eq!(fs::read(dest.join("nested/also.txt"))?, b"readable too");
+    assert!(!dest.join("held.txt").exists());
+    assert_eq!(summary.unreadable_paths.len(), 1);
+    assert!(summary.unreadable_paths[0].contains("locked by another process"));
+    Ok(())
+}
+
+/// `--vss` reads through a shadow copy, where no live handle's share
+/// mode applies: a file held open exclusively still lands.
+#[cfg(windows)]
+#[tokio::test]
+#[ignore = "needs an elevated Windows shell (creates a shadow copy)"]
+async fn vss_copies_a_file_locked_in_the_live_tree() -> Result<()> {
+    use std::os::windows::fs::OpenOptionsExt;
+
+    let tmp = tempdir()?;
+    let src = tmp.path().join("src");
+    let dest = tmp.path().join("dest");
+    fs::create_dir_all(&src)?;
+    fs::write(src.join("held.txt"), b"locked")?;
+    let _lock = fs::OpenOptions::new()
+        .read(true)
+        .share_mode(0)
+        .open(src.join("held.txt"))?;
+
+    let mut opts = options();
+    opts.vss = true;
+    run_local_session(&src, &dest, opts).await?;
+    assert_eq!(fs::read(dest.join("held.txt"))?, b"locked");
+    Ok(())
+}
+
+/// Loop-mounted btrfs image for the `--snapshot` pins; unmounted and
+/// detached on drop.
+#[cfg(target_os = "linux")]
+struct BtrfsLoop {
+    _tmp: tempfile::TempDir,
+    mount: PathBuf,
+}
+
+#[cfg(target_os = "linux")]
+impl BtrfsLoop {
+    fn new() -> Result<Self> {
+        use std::process::Command;
+
+        let tmp = tempdir()?;
+        let image = tmp.path().join("btrfs.img");
+        let mount = tmp.path().join("mnt");
+        fs::create_dir(&mount)?;
+        // mkfs.btrfs's minimum is ~114 MiB; the file stays sparse.
+        fs::File::create(&image)?.set_len(256 << 20)?;
+        for (program, args) in [
+            ("mkfs.btrfs", vec!["-q".as_ref(), image.as_os_str()]),
+            (
+                "mount",
+                vec![
+                    "-o".as_ref(),
+                    "loop".as_ref(),
+                    image.as_os_str(),
+                    mount.as_os_str(),
+                ],
+            ),
+        ] {
+            let status = Command::new(program).args(&args).status()?;
+            eyre::ensure!(status.success(), "{program} failed: {status}");
+        }
+        Ok(Self { _tmp: tmp, mount })
+    }
+
+    fn leftover_snapshots(&self) -> Vec<String> {
+        fs::read_dir(&self.mount)
+            .expect("read mount")
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .filter(|name| name.starts_with(".blit-snapshot-"))
+            .collect()
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for BtrfsLoop {
+    fn drop(&mut self) {
+        let _ = std::process::Command::new("umount")
+            .arg(&self.mount)
+            .status();
+    }
+}
+
+/// `--snapshot` on btrfs: reads come from a read-only snapshot of the
+/// enclosing subvolume (a later live edit is not seen), and the
+/// snapshot subvolume is deleted once the guard / session ends.
+#[cfg(target_os = "linux")]
+#[tokio::test]
+#[ignore = "needs root, btrfs-progs and a loop device"]
+async fn snapshot_reads_a_btrfs_snapshot_and_removes_it() -> Result<()> {
+    use blit_core::source_snapshot::SourceSnapshot;
+
+    let btrfs = BtrfsLoop::new()?;
+    let src = btrfs.mount.join("data");
+    fs::create_dir_all(src.join("nested"))?;
+    fs::write(src.join("a.txt"), b"before")?;
+    fs::write(src.join("nested/b.txt"), b"nested")?;
+
+    let snapshot = SourceSnapshot::create_fs(&src)?.expect("btrfs is snapshot-capable");
+    let read_root = snapshot.read_root().to_path_buf();
+    assert!(read_root.starts_with(&btrfs.mount));
+    assert_eq!(btrfs.leftover_snapshots().len(), 1);
+    fs::write(src.join("a.txt"), b"after")?;
+    assert_eq!(fs::read(read_root.join("a.txt"))?, b"before");
+    drop(snapshot);
+    assert!(btrfs.leftover_snapshots().is_empty());
+    assert!(!read_root.exists());
+
+    let dest = btrfs.mount.join("dest");
+    let mut opts = options();
+    opts.snapshot = true;
+    let summary = run_local_session(&src, &dest, opts).await?;
+    assert_eq!(summary.copied_files, 2);
+    assert_eq!(fs::read(dest.join("a.txt"))?, b"after");
+    assert_eq!(fs::read(dest.join("nested/b.txt"))?, b"nested");
+    assert!(btrfs.leftover_snapshots().is_empty());
+    Ok(())
+}
+
+/// `--delay-updates` lands a nested tree (and a file root) through the
+/// staging batch, leaves no staging directory behind, and refuses
+/// `--resume`.
+#[tokio::test]
+async fn delay_updates_commits_tree_and_cleans_staging() -> Result<()> {
+    let tmp = tempdir()?;
+    let src = tmp.path().join("src");
+    let dest = tmp.path().join("dest");
+    fs::create_dir_all(src.join("nested/deeper"))?;
+    fs::write(src.join("top.txt"), b"top")?;
+    fs::write(src.join("nested/deeper/leaf.txt"), b"leaf")?;
+
+    let mut opts = options();
+    opts.delay_updates = true;
+    let summary = run_local_session(&src, &dest, opts.clone()).await?;
+    assert_eq!(summary.copied_files, 2);
+    assert_eq!(fs::read(dest.join("top.txt"))?, b"top");
+    assert_eq!(fs::read(dest.join("nested/deeper/leaf.txt"))?, b"leaf");
+    assert!(!dest
+        .join(blit_core::transfer_session::DELAY_UPDATES_DIR)
+        .exists());
+
+    let single = tmp.path().join("single.txt");
+    run_local_session(&src.join("top.txt"), &single, opts.clone()).await?;
+    assert_eq!(fs::read(&single)?, b"top");
+    assert_eq!(
+        fs::read_dir(tmp.path())?.count(),
+        3,
+        "no sibling staging left"
+    );
+
+    opts.resume = true;
+    let err = run_local_session(&src, &dest, opts).await.unwrap_err();
+    assert!(format!("{err:#}").contains("--delay-updates cannot be combined with --resume"));
+    Ok(())
+}
+
+/// `--temp-dir` moves the `--delay-updates` stage out of the
+/// destination: the tree still lands whole and neither the temp dir nor
+/// the destination keeps a staging directory.
+#[tokio::test]
+async fn delay_updates_with_temp_dir_on_the_same_filesystem() -> Result<()> {
+    let tmp = tempdir()?;
+    let src = tmp.path().join("src");
+    let dest = tmp.path().join("dest");
+    let temps = tmp.path().join("temps");
+    fs::create_dir_all(src.join("nested"))?;
+    fs::create_dir_all(&temps)?;
+    fs::write(src.join("top.txt"), b"top")?;
+    fs::write(src.join("nested/leaf.txt"), b"leaf")?;
+
+    let mut opts = options();
+    opts.delay_updates = true;
+    opts.temp_dir = Some(temps.clone());
+    let summary = run_local_session(&src, &dest, opts).await?;
+    assert_eq!(summary.copied_files, 2);
+    assert_eq!(fs::read(dest.join("top.txt"))?, b"top");
+    assert_eq!(fs::read(dest.join("nested/leaf.txt"))?, b"leaf");
+    assert!(!dest
+        .join(blit_core::transfer_session::DELAY_UPDATES_DIR)
+        .exists());
+    assert_eq!(fs::read_dir(&temps)?.count(), 0, "the stage is removed");
+    Ok(())
+}
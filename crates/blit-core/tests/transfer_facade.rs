@@ -0,0 +1,72 @@
+//! The embedder entry point: `TransferFacade::copy_local` copies and
+//! mirrors a tree, returns the session summary, and hands the progress
+//! callback the full event stream before it returns.
+
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+use blit_core::remote::transfer::{ProgressEvent, ProgressTotals};
+use blit_core::transfer_facade::TransferFacade;
+use blit_core::transfer_session::LocalMirrorOptions;
+use eyre::Result;
+use tempfile::tempdir;
+
+fn options() -> LocalMirrorOptions {
+    LocalMirrorOptions {
+        perf_history: false,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn facade_copies_a_tree_and_reports_progress() -> Result<()> {
+    let tmp = tempdir()?;
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(src.join("nested"))?;
+    fs::write(src.join("a.txt"), b"alpha")?;
+    fs::write(src.join("nested/b.bin"), vec![7u8; 64 * 1024])?;
+
+    let events: Arc<Mutex<Vec<ProgressEvent>>> = Arc::default();
+    let sink = Arc::clone(&events);
+    let facade = TransferFacade::new().on_progress(move |event| {
+        sink.lock().unwrap().push(event);
+    });
+
+    let summary = facade.copy_local(&src, &dst, options(), false).await?;
+    assert_eq!(summary.copied_files, 2);
+    assert_eq!(summary.total_bytes, 5 + 64 * 1024);
+    assert_eq!(fs::read(dst.join("a.txt"))?, b"alpha");
+    assert_eq!(fs::read(dst.join("nested/b.bin"))?.len(), 64 * 1024);
+
+    let mut totals = ProgressTotals::default();
+    for event in events.lock().unwrap().iter() {
+        totals.apply(event);
+    }
+    assert_eq!(totals.bytes, summary.total_bytes);
+    assert_eq!(totals.files, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn facade_mirror_flag_deletes_extraneous_entries() -> Result<()> {
+    let tmp = tempdir()?;
+    let src = tmp.path().join("src");
+    let dst = tmp.path().join("dst");
+    fs::create_dir_all(&src)?;
+    fs::create_dir_all(&dst)?;
+    fs::write(src.join("keep.txt"), b"keep")?;
+    fs::write(dst.join("stale.txt"), b"stale")?;
+
+    let facade = TransferFacade::new();
+    let copy = facade.copy_local(&src, &dst, options(), false).await?;
+    assert_eq!(copy.deleted_files, 0);
+    assert!(dst.join("stale.txt").exists(), "copy never deletes");
+
+    // `mirror` is the argument, not `options.mirror`.
+    let mirror = facade.copy_local(&src, &dst, options(), true).await?;
+    assert_eq!(mirror.deleted_files, 1);
+    assert!(!dst.join("stale.txt").exists());
+    assert!(dst.join("keep.txt").exists());
+    Ok(())
+}
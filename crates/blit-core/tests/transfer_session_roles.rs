@@ -30,7 +30,8 @@ use blit_core::remote::transfer::{
     TransferPayload,
 };
 use blit_core::transfer_plan::PlanOptions;
-use blit_core::transfer_session::transport::{in_process_pair, FrameTransport};
+use blit_core::transfer_session::lane::{self, LaneRegistry, Redial};
+use blit_core::transfer_session::transport::{in_process_pair, FrameRx, FrameTransport, FrameTx};
 use blit_core::transfer_session::{
     run_destination, run_source, DestinationInstruments, DestinationOutcome,
     DestinationSessionConfig, DestinationTarget, HelloConfig, SessionEndpoint, SessionFault,
@@ -133,6 +134,23 @@ async fn run_session_with_open(
 ) -> (
     eyre::Result<TransferSummary>,
     eyre::Result<DestinationOutcome>,
+) {
+    let (a, b) = in_process_pair();
+    run_session_over(open, src_root, dst_root, plan_options, a, b).await
+}
+
+/// Like [`run_session_with_open`] over caller-built transports: `a`
+/// carries the SOURCE end, `b` the DESTINATION end.
+async fn run_session_over(
+    open: SessionOpen,
+    src_root: &Path,
+    dst_root: &Path,
+    plan_options: PlanOptions,
+    a: FrameTransport,
+    b: FrameTransport,
+) -> (
+    eyre::Result<TransferSummary>,
+    eyre::Result<DestinationOutcome>,
 ) {
     let initiator_role = TransferRole::try_from(open.initiator_role)
         .unwrap_or_else(|_| panic!("open carries a valid initiator role"));
@@ -152,11 +170,13 @@ async fn run_session_with_open(
         hello: HelloConfig::default(),
         endpoint: dest_endpoint,
         data_plane_host: None,
+        diff_batch: None,
         receiver_capacity: None,
         instruments: Default::default(),
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
-    let (a, b) = in_process_pair();
     let source = Arc::new(FsTransferSource::new(src_root.to_path_buf()));
     tokio::time::timeout(SUITE_TIMEOUT, async {
         tokio::join!(
@@ -323,6 +343,53 @@ async fn tiny_file_tree_tar_shard_records_under_both_initiators() {
     assert_eq!(needed.len(), 200);
 }
 
+/// One tar-shard member the destination cannot write (a non-empty
+/// directory occupies its path) fails the session NAMING that member —
+/// structurally, on both ends — while its shard-mates still land.
+#[tokio::test]
+async fn tar_shard_member_failure_names_the_file_and_lands_the_rest() {
+    for initiator_role in [TransferRole::Source, TransferRole::Destination] {
+        let tmp = tempfile::tempdir().unwrap();
+        let src_root = tmp.path().join("src");
+        let dst_root = tmp.path().join("dst");
+        let src: Vec<FileSpec> = vec![
+            ("shard/ok-1.txt", b"first".to_vec(), 1_600_200_001),
+            ("shard/blocked.txt", b"blocked".to_vec(), 1_600_200_002),
+            ("shard/ok-2.txt", b"second".to_vec(), 1_600_200_003),
+        ];
+        write_tree(&src_root, &src);
+        std::fs::create_dir_all(dst_root.join("shard/blocked.txt/occupied")).unwrap();
+        let plan = PlanOptions {
+            force_tar: true,
+            ..PlanOptions::default()
+        };
+
+        let (source_result, dest_result) =
+            run_session(initiator_role, &src_root, &dst_root, plan).await;
+
+        let dest_err = dest_result.expect_err("destination must fault");
+        assert_eq!(
+            fault_of(&dest_err).relative_path.as_deref(),
+            Some("shard/blocked.txt"),
+            "destination names the member (initiator {initiator_role:?})"
+        );
+        let source_err = source_result.expect_err("source must fault");
+        assert_eq!(
+            fault_of(&source_err).relative_path.as_deref(),
+            Some("shard/blocked.txt"),
+            "the member's identity crossed the wire (initiator {initiator_role:?})"
+        );
+        assert_eq!(
+            std::fs::read(dst_root.join("shard/ok-1.txt")).unwrap(),
+            b"first"
+        );
+        assert_eq!(
+            std::fs::read(dst_root.join("shard/ok-2.txt")).unwrap(),
+            b"second"
+        );
+    }
+}
+
 #[tokio::test]
 async fn incremental_transfer_needs_only_missing_and_changed() {
     let src: Vec<FileSpec> = vec![
@@ -360,6 +427,82 @@ async fn preexisting_identical_tree_yields_empty_need_list() {
     assert_eq!(summary.bytes_transferred, 0);
 }
 
+#[tokio::test]
+async fn empty_directories_are_recreated_under_both_initiators() {
+    // Contract v12: directories ride the manifest, so a source holding
+    // empty directories (nested ones included) reproduces them rather
+    // than only the parents of its files.
+    for initiator_role in [TransferRole::Source, TransferRole::Destination] {
+        let tmp = tempfile::tempdir().unwrap();
+        let src_root = tmp.path().join("src");
+        let dst_root = tmp.path().join("dst");
+        std::fs::create_dir_all(&dst_root).unwrap();
+        write_tree(
+            &src_root,
+            &[("sub/file.txt", b"data".to_vec(), 1_600_000_000)],
+        );
+        std::fs::create_dir_all(src_root.join("empty")).unwrap();
+        std::fs::create_dir_all(src_root.join("deep/er/still-empty")).unwrap();
+        std::fs::create_dir_all(src_root.join("sub/empty-sibling")).unwrap();
+
+        let (sr, dr) =
+            run_session(initiator_role, &src_root, &dst_root, PlanOptions::default()).await;
+        let summary =
+            sr.unwrap_or_else(|e| panic!("source failed (init {initiator_role:?}): {e:#}"));
+        dr.unwrap_or_else(|e| panic!("destination failed (init {initiator_role:?}): {e:#}"));
+
+        assert_eq!(summary.files_transferred, 1, "directories are not payloads");
+        for dir in ["empty", "deep/er/still-empty", "sub/empty-sibling"] {
+            assert!(
+                dst_root.join(dir).is_dir(),
+                "'{dir}' missing at the destination (init {initiator_role:?})"
+            );
+        }
+        assert_trees_identical(&src_root, &dst_root);
+    }
+}
+
+#[tokio::test]
+async fn prune_empty_dirs_skips_directories_the_filter_emptied() {
+    // Contract v13: an exclude that empties `logs/` still leaves the
+    // directory entry in the manifest — unless the open asks the SOURCE
+    // to prune directories with no file under them.
+    for prune in [false, true] {
+        for initiator_role in [TransferRole::Source, TransferRole::Destination] {
+            let tmp = tempfile::tempdir().unwrap();
+            let src_root = tmp.path().join("src");
+            let dst_root = tmp.path().join("dst");
+            std::fs::create_dir_all(&dst_root).unwrap();
+            write_tree(
+                &src_root,
+                &[
+                    ("keep/a.txt", b"data".to_vec(), 1_600_000_000),
+                    ("logs/old/run.log", b"noise".to_vec(), 1_600_000_000),
+                ],
+            );
+            let mut open = basic_open(initiator_role);
+            open.prune_empty_dirs = prune;
+            open.filter = Some(FilterSpec {
+                exclude: vec!["*.log".to_string()],
+                ..Default::default()
+            });
+
+            let (sr, dr) =
+                run_session_with_open(open, &src_root, &dst_root, PlanOptions::default()).await;
+            sr.unwrap_or_else(|e| panic!("source failed (prune {prune}): {e:#}"));
+            dr.unwrap_or_else(|e| panic!("destination failed (prune {prune}): {e:#}"));
+
+            assert!(dst_root.join("keep/a.txt").is_file());
+            assert!(!dst_root.join("logs/old/run.log").exists());
+            assert_eq!(
+                dst_root.join("logs/old").is_dir(),
+                !prune,
+                "emptied subtree created iff not pruning (init {initiator_role:?})"
+            );
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Resume block phase (otp-7a, docs/plan/OTP7_RESUME.md)
 // ---------------------------------------------------------------------------
@@ -494,9 +637,12 @@ async fn assert_resume_data_plane_invariant_across_roles(
             hello: HelloConfig::default(),
             endpoint: dest_endpoint,
             data_plane_host: dest_host,
+            diff_batch: None,
             receiver_capacity: None,
             instruments: Default::default(),
             local_apply: None,
+            fake_super: false,
+            receive_sink: None,
         };
         let (a, b) = in_process_pair();
         let source = Arc::new(FsTransferSource::new(src_root.clone()));
@@ -856,9 +1002,12 @@ async fn file_record_for_resume_flagged_path_is_protocol_violation() {
         hello: HelloConfig::default(),
         endpoint: SessionEndpoint::Responder,
         data_plane_host: None,
+        diff_batch: None,
         receiver_capacity: None,
         instruments: Default::default(),
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
     let (mut peer, dest_transport) = in_process_pair();
     let dest = tokio::spawn(run_destination(
@@ -869,10 +1018,10 @@ async fn file_record_for_resume_flagged_path_is_protocol_violation() {
 
     peer.send(hello_frame()).await.unwrap();
     assert!(matches!(recv_or_panic(&mut peer).await, Frame::Hello(_)));
-    peer.send(wire(Frame::Open(resume_open(
+    peer.send(wire(Frame::Open(Box::new(resume_open(
         TransferRole::Source,
         RESUME_BS,
-    ))))
+    )))))
     .await
     .unwrap();
     assert!(matches!(recv_or_panic(&mut peer).await, Frame::Accept(_)));
@@ -884,6 +1033,10 @@ async fn file_record_for_resume_flagged_path_is_protocol_violation() {
         permissions: 0o644,
         checksum: vec![],
         windows_metadata: None,
+        crtime_seconds: None,
+        directory: false,
+        owner: None,
+        xattrs: Vec::new(),
     };
     peer.send(wire(Frame::ManifestEntry(header.clone())))
         .await
@@ -1048,9 +1201,12 @@ async fn mid_resume_source_fault_surfaces_cleanly_to_both_ends() {
             hello: HelloConfig::default(),
             endpoint: dest_endpoint,
             data_plane_host: None,
+            diff_batch: None,
             receiver_capacity: None,
             instruments: Default::default(),
             local_apply: None,
+            fake_super: false,
+            receive_sink: None,
         };
         let (a, b) = in_process_pair();
         let source: Arc<dyn TransferSource> = Arc::new(TruncatedReadSource {
@@ -1248,6 +1404,248 @@ impl TransferSource for GatedManifestSource {
     }
 }
 
+/// Delegates to `FsTransferSource` but deletes `vanish` from disk once the
+/// whole manifest has been listed — a file removed from a live tree
+/// between enumeration and send.
+struct VanishingSource {
+    inner: FsTransferSource,
+    vanish: std::path::PathBuf,
+}
+
+#[async_trait::async_trait]
+impl TransferSource for VanishingSource {
+    fn scan(
+        &self,
+        filter: Option<blit_core::fs_enum::FileFilter>,
+        unreadable_paths: Arc<std::sync::Mutex<Vec<String>>>,
+    ) -> (tokio::sync::mpsc::Receiver<FileHeader>, SourceScan) {
+        let (mut inner_rx, mut scan) = self.inner.scan(filter, unreadable_paths);
+        let (tx, rx) = tokio::sync::mpsc::channel(64);
+        let vanish = self.vanish.clone();
+        let forwarder = tokio::spawn(async move {
+            while let Some(header) = inner_rx.recv().await {
+                if tx.send(header).await.is_err() {
+                    return;
+                }
+            }
+            std::fs::remove_file(&vanish).expect("delete the listed file");
+        });
+        scan.add_auxiliary(forwarder);
+        (rx, scan)
+    }
+
+    async fn prepare_payload(&self, payload: TransferPayload) -> eyre::Result<PreparedPayload> {
+        self.inner.prepare_payload(payload).await
+    }
+
+    async fn check_availability(
+        &self,
+        headers: Vec<FileHeader>,
+        unreadable_paths: Arc<std::sync::Mutex<Vec<String>>>,
+    ) -> eyre::Result<Vec<FileHeader>> {
+        self.inner
+            .check_availability(headers, unreadable_paths)
+            .await
+    }
+
+    async fn open_file(
+        &self,
+        header: &FileHeader,
+    ) -> eyre::Result<Box<dyn tokio::io::AsyncRead + Unpin + Send>> {
+        self.inner.open_file(header).await
+    }
+
+    fn root(&self) -> &Path {
+        self.inner.root()
+    }
+
+    fn local_path(&self, header: &FileHeader) -> Option<std::path::PathBuf> {
+        self.inner.local_path(header)
+    }
+}
+
+/// Contract v15: a needed file deleted after the manifest listed it is
+/// withdrawn with `NeedVanished` instead of failing the transfer, on
+/// both carriers and under both initiators, and the summary names it.
+/// The TCP run requires a complete scan so no payload is queued before
+/// the deletion.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn file_vanishing_before_send_is_skipped_and_reported() {
+    for in_stream_bytes in [true, false] {
+        for initiator_role in [TransferRole::Source, TransferRole::Destination] {
+            let tmp = tempfile::tempdir().unwrap();
+            let src_root = tmp.path().join("src");
+            let dst_root = tmp.path().join("dst");
+            std::fs::create_dir_all(&dst_root).unwrap();
+            write_tree(&src_root, &small_tree());
+            let vanish = "dir one/deeper/c.dat";
+
+            let open = SessionOpen {
+                initiator_role: initiator_role as i32,
+                compare_mode: ComparisonMode::SizeMtime as i32,
+                in_stream_bytes,
+                require_complete_scan: !in_stream_bytes,
+                ..Default::default()
+            };
+            let host = (!in_stream_bytes).then(|| "127.0.0.1".to_string());
+            let (source_endpoint, dest_endpoint, source_host, dest_host) = match initiator_role {
+                TransferRole::Source => (
+                    SessionEndpoint::initiator(open),
+                    SessionEndpoint::Responder,
+                    host,
+                    None,
+                ),
+                TransferRole::Destination => (
+                    SessionEndpoint::Responder,
+                    SessionEndpoint::initiator(open),
+                    None,
+                    host,
+                ),
+                TransferRole::Unspecified => unreachable!(),
+            };
+            let source_cfg = SourceSessionConfig {
+                hello: HelloConfig::default(),
+                endpoint: source_endpoint,
+                plan_options: PlanOptions::default(),
+                data_plane_host: source_host,
+                instruments: Default::default(),
+            };
+            let dest_cfg = DestinationSessionConfig {
+                hello: HelloConfig::default(),
+                endpoint: dest_endpoint,
+                data_plane_host: dest_host,
+                diff_batch: None,
+                receiver_capacity: None,
+                instruments: Default::default(),
+                local_apply: None,
+                fake_super: false,
+                receive_sink: None,
+            };
+            let source: Arc<dyn TransferSource> = Arc::new(VanishingSource {
+                inner: FsTransferSource::new(src_root.clone()),
+                vanish: src_root.join(vanish),
+            });
+            let (a, b) = in_process_pair();
+            let (src_res, dst_res) = tokio::time::timeout(SUITE_TIMEOUT, async {
+                tokio::join!(
+                    run_source(source_cfg, a, source),
+                    run_destination(dest_cfg, b, DestinationTarget::Fixed(dst_root.clone())),
+                )
+            })
+            .await
+            .expect("session run timed out");
+
+            let case = format!("{initiator_role:?}, in_stream={in_stream_bytes}");
+            let summary = src_res.unwrap_or_else(|e| panic!("{case}: source failed: {e:#}"));
+            let outcome = dst_res.unwrap_or_else(|e| panic!("{case}: destination failed: {e:#}"));
+            assert_eq!(summary, outcome.summary, "{case}");
+            assert_eq!(summary.vanished_paths, vec![vanish.to_string()], "{case}");
+            assert_eq!(
+                summary.files_transferred,
+                small_tree().len() as u64 - 1,
+                "{case}"
+            );
+            assert!(!dst_root.join(vanish).exists(), "{case}");
+            assert_trees_identical(&src_root, &dst_root);
+        }
+    }
+}
+
+/// A granted data port the initiator cannot reach (here: the responder
+/// listens on IPv4 only and the initiator dials IPv6 loopback — the
+/// shape of a firewall that passes the control port alone) must end
+/// BOTH sides promptly with a `DATA_PLANE_FAILED` the initiator flags
+/// as unreachable, before anything lands at the destination — which is
+/// what lets the session client rerun on the in-stream carrier.
+/// Skipped where the sandbox has no IPv6 loopback: a dial that fails
+/// for want of the address family is not the refused grant under test.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn unreachable_data_plane_grant_fails_fast_and_flags_the_initiator() {
+    if std::net::TcpListener::bind("[::1]:0").is_err() {
+        return;
+    }
+    for initiator_role in [TransferRole::Source, TransferRole::Destination] {
+        let tmp = tempfile::tempdir().unwrap();
+        let src_root = tmp.path().join("src");
+        let dst_root = tmp.path().join("dst");
+        std::fs::create_dir_all(&src_root).unwrap();
+        std::fs::create_dir_all(&dst_root).unwrap();
+        write_tree(&src_root, &small_tree());
+
+        let open = SessionOpen {
+            in_stream_bytes: false,
+            ..basic_open(initiator_role)
+        };
+        let unreachable = Some("[::1]".to_string());
+        let (source_endpoint, dest_endpoint, source_host, dest_host) = match initiator_role {
+            TransferRole::Source => (
+                SessionEndpoint::initiator(open),
+                SessionEndpoint::Responder,
+                unreachable,
+                None,
+            ),
+            TransferRole::Destination => (
+                SessionEndpoint::Responder,
+                SessionEndpoint::initiator(open),
+                None,
+                unreachable,
+            ),
+            TransferRole::Unspecified => unreachable!(),
+        };
+        let source_cfg = SourceSessionConfig {
+            instruments: Default::default(),
+            hello: HelloConfig::default(),
+            endpoint: source_endpoint,
+            plan_options: PlanOptions::default(),
+            data_plane_host: source_host,
+        };
+        let dest_cfg = DestinationSessionConfig {
+            hello: HelloConfig::default(),
+            endpoint: dest_endpoint,
+            data_plane_host: dest_host,
+            diff_batch: None,
+            receiver_capacity: None,
+            instruments: Default::default(),
+            local_apply: None,
+            fake_super: false,
+            receive_sink: None,
+        };
+        let (a, b) = in_process_pair();
+        let source = Arc::new(FsTransferSource::new(src_root.clone()));
+        let (source_result, dest_result) = tokio::time::timeout(Duration::from_secs(20), async {
+            tokio::join!(
+                run_source(source_cfg, a, source),
+                run_destination(dest_cfg, b, DestinationTarget::Fixed(dst_root.clone())),
+            )
+        })
+        .await
+        .unwrap_or_else(|_| panic!("unreachable grant hung (initiator {initiator_role:?})"));
+
+        let (initiator_err, responder_err) = match initiator_role {
+            TransferRole::Source => (source_result.unwrap_err(), dest_result.unwrap_err()),
+            _ => (dest_result.unwrap_err(), source_result.unwrap_err()),
+        };
+        let fault = fault_of(&initiator_err);
+        assert_eq!(
+            fault.code,
+            session_error::Code::DataPlaneFailed,
+            "initiator {initiator_role:?}: {initiator_err:#}"
+        );
+        assert!(
+            fault.data_plane_unreachable,
+            "initiator {initiator_role:?} must flag the unreachable grant: {initiator_err:#}"
+        );
+        assert!(
+            !fault_of(&responder_err).data_plane_unreachable,
+            "the flag is local evidence; the responder only hears the wire fault"
+        );
+        assert!(
+            collect_tree(&dst_root).is_empty(),
+            "nothing may land before the data plane is up (initiator {initiator_role:?})"
+        );
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn tcp_payload_overlaps_open_manifest_under_either_initiator() {
     // P2 regression guard: DESTINATION diffs and requests the first 128
@@ -1307,12 +1705,15 @@ async fn tcp_payload_overlaps_open_manifest_under_either_initiator() {
             hello: HelloConfig::default(),
             endpoint: dest_endpoint,
             data_plane_host: dest_host,
+            diff_batch: None,
             receiver_capacity: None,
             instruments: DestinationInstruments {
                 progress: Some(RemoteTransferProgress::new(progress_tx)),
                 ..Default::default()
             },
             local_apply: None,
+            fake_super: false,
+            receive_sink: None,
         };
 
         let gate_reached = Arc::new(tokio::sync::Semaphore::new(0));
@@ -1404,9 +1805,12 @@ async fn many_tiny_files_transfer_with_live_workers_when_source_initiates() {
         hello: HelloConfig::default(),
         endpoint: SessionEndpoint::Responder,
         data_plane_host: None,
+        diff_batch: None,
         receiver_capacity: None,
         instruments: Default::default(),
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
     let (a, b) = in_process_pair();
     let source = Arc::new(FsTransferSource::new(src_root.clone()));
@@ -1490,9 +1894,12 @@ async fn payload_completion_does_not_wait_for_a_tuner_decision_under_either_init
             hello: HelloConfig::default(),
             endpoint: dest_endpoint,
             data_plane_host: dest_host,
+            diff_batch: None,
             receiver_capacity: None,
             instruments: Default::default(),
             local_apply: None,
+            fake_super: false,
+            receive_sink: None,
         };
 
         let (source_transport, dest_transport) = in_process_pair();
@@ -1541,7 +1948,11 @@ struct PhaseTraceCase {
     events: Vec<SessionPhaseEvent>,
 }
 
-async fn run_phase_trace_case(initiator_role: TransferRole, trace_enabled: bool) -> PhaseTraceCase {
+async fn run_phase_trace_case(
+    initiator_role: TransferRole,
+    trace_enabled: bool,
+    mirror: bool,
+) -> PhaseTraceCase {
     const FILE_COUNT: usize = 256;
     let tmp = tempfile::tempdir().unwrap();
     let src_root = tmp.path().join("src");
@@ -1551,6 +1962,9 @@ async fn run_phase_trace_case(initiator_role: TransferRole, trace_enabled: bool)
     for i in 0..FILE_COUNT {
         std::fs::write(src_root.join(format!("f{i:04}.bin")), b"x").unwrap();
     }
+    if mirror {
+        std::fs::write(dst_root.join("stale.bin"), b"x").unwrap();
+    }
 
     let captured: Arc<Mutex<Vec<SessionPhaseEvent>>> = Arc::default();
     let phase_trace = if trace_enabled {
@@ -1568,6 +1982,12 @@ async fn run_phase_trace_case(initiator_role: TransferRole, trace_enabled: bool)
         initiator_role: initiator_role as i32,
         compare_mode: ComparisonMode::SizeMtime as i32,
         in_stream_bytes: false,
+        mirror_enabled: mirror,
+        mirror_kind: if mirror {
+            MirrorMode::FilteredSubset as i32
+        } else {
+            MirrorMode::Unspecified as i32
+        },
         ..Default::default()
     };
     let (source_endpoint, dest_endpoint, source_host, dest_host) = match initiator_role {
@@ -1599,12 +2019,15 @@ async fn run_phase_trace_case(initiator_role: TransferRole, trace_enabled: bool)
         hello: HelloConfig::default(),
         endpoint: dest_endpoint,
         data_plane_host: dest_host,
+        diff_batch: None,
         receiver_capacity: None,
         instruments: DestinationInstruments {
             session_phase_trace: phase_trace,
             ..Default::default()
         },
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
     let (source_transport, dest_transport) = in_process_pair();
     let source = Arc::new(FsTransferSource::new(src_root.clone()));
@@ -1881,10 +2304,10 @@ fn assert_phase_trace_partial_order(events: &[SessionPhaseEvent], initiator: Tra
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn session_phase_trace_is_complete_and_inert_under_both_initiators() {
-    let source_off = run_phase_trace_case(TransferRole::Source, false).await;
-    let source_on = run_phase_trace_case(TransferRole::Source, true).await;
-    let destination_off = run_phase_trace_case(TransferRole::Destination, false).await;
-    let destination_on = run_phase_trace_case(TransferRole::Destination, true).await;
+    let source_off = run_phase_trace_case(TransferRole::Source, false, false).await;
+    let source_on = run_phase_trace_case(TransferRole::Source, true, false).await;
+    let destination_off = run_phase_trace_case(TransferRole::Destination, false, false).await;
+    let destination_on = run_phase_trace_case(TransferRole::Destination, true, false).await;
 
     assert!(source_off.events.is_empty());
     assert!(destination_off.events.is_empty());
@@ -1958,6 +2381,203 @@ async fn session_phase_trace_is_complete_and_inert_under_both_initiators() {
     }
 }
 
+/// Each endpoint marks the coarse transfer phases in order: negotiated,
+/// scan, manifest, first payload, mirror delete, summary.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn session_phase_trace_marks_each_transfer_phase_in_order() {
+    let source = SessionPhaseRole::Source;
+    let destination = SessionPhaseRole::Destination;
+    for initiator in [TransferRole::Source, TransferRole::Destination] {
+        let case = run_phase_trace_case(initiator, true, true).await;
+        assert_eq!(case.summary.entries_deleted, 1);
+        let events = &case.events;
+        let ordered = |role: SessionPhaseRole, names: &[&str]| {
+            let positions: Vec<usize> = names
+                .iter()
+                .map(|name| phase_position(events, role, name, None, None))
+                .collect();
+            assert!(
+                positions.windows(2).all(|pair| pair[0] < pair[1]),
+                "{initiator:?} initiating: {role:?} phases out of order: {names:?} at {positions:?}"
+            );
+        };
+        ordered(
+            source,
+            &[
+                "session_negotiated",
+                "source_scan_begin",
+                "manifest_complete_sent",
+                "first_payload_queued",
+                "summary_received",
+            ],
+        );
+        let first_received = events
+            .iter()
+            .position(|event| {
+                event.endpoint_role == destination && event.event == "first_payload_received"
+            })
+            .expect("destination received payload");
+        let delete_begin = phase_position(events, destination, "mirror_delete_begin", None, None);
+        assert!(first_received < delete_begin);
+        ordered(
+            destination,
+            &[
+                "session_negotiated",
+                "manifest_complete_received",
+                "mirror_delete_begin",
+                "mirror_delete_end",
+                "summary_sent",
+            ],
+        );
+        assert_eq!(
+            one_phase_event(events, destination, "mirror_delete_end", None).count,
+            Some(1)
+        );
+    }
+}
+
+/// One span a [`SpanRecorder`] saw: its name, its parent's index in
+/// creation order, and every field recorded on it.
+#[derive(Debug)]
+struct RecordedSpan {
+    name: &'static str,
+    parent: Option<usize>,
+    fields: BTreeMap<String, String>,
+}
+
+struct FieldWriter<'a>(&'a mut BTreeMap<String, String>);
+
+impl tracing::field::Visit for FieldWriter<'_> {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+}
+
+/// Minimal subscriber that keeps every span in creation order. Span
+/// ids are creation index + 1; the entered-span stack resolves
+/// contextual parents, which is enough on a current-thread runtime.
+#[derive(Clone, Default)]
+struct SpanRecorder {
+    spans: Arc<Mutex<Vec<RecordedSpan>>>,
+    stack: Arc<Mutex<Vec<usize>>>,
+}
+
+impl tracing::Subscriber for SpanRecorder {
+    fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attrs: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        let parent = if let Some(parent) = attrs.parent() {
+            Some(parent.into_u64() as usize - 1)
+        } else if attrs.is_contextual() {
+            self.stack.lock().unwrap().last().copied()
+        } else {
+            None
+        };
+        let mut fields = BTreeMap::new();
+        attrs.record(&mut FieldWriter(&mut fields));
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(RecordedSpan {
+            name: attrs.metadata().name(),
+            parent,
+            fields,
+        });
+        tracing::span::Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &tracing::span::Id, values: &tracing::span::Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        let fields = &mut spans[span.into_u64() as usize - 1].fields;
+        values.record(&mut FieldWriter(fields));
+    }
+
+    fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+    fn event(&self, _: &tracing::Event<'_>) {}
+
+    fn enter(&self, span: &tracing::span::Id) {
+        self.stack
+            .lock()
+            .unwrap()
+            .push(span.into_u64() as usize - 1);
+    }
+
+    fn exit(&self, _: &tracing::span::Id) {
+        self.stack.lock().unwrap().pop();
+    }
+}
+
+/// Each endpoint runs its session under a `transfer_session` span
+/// carrying its role and the shared session id, with `negotiate` then
+/// `transfer` phases under it, `scan` (SOURCE) and `mirror_delete`
+/// (DESTINATION) inside `transfer`, and the summary counts on
+/// `transfer`.
+#[tokio::test]
+async fn session_spans_nest_each_transfer_phase_under_its_endpoint() {
+    for initiator in [TransferRole::Source, TransferRole::Destination] {
+        let recorder = SpanRecorder::default();
+        let case = {
+            let _default = tracing::subscriber::set_default(recorder.clone());
+            run_phase_trace_case(initiator, false, true).await
+        };
+        let spans = recorder.spans.lock().unwrap();
+        let children = |parent: usize| -> Vec<(usize, &'static str)> {
+            spans
+                .iter()
+                .enumerate()
+                .filter(|(_, span)| span.parent == Some(parent))
+                .map(|(index, span)| (index, span.name))
+                .collect()
+        };
+        let sessions: Vec<usize> = spans
+            .iter()
+            .enumerate()
+            .filter(|(_, span)| span.name == "transfer_session")
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(sessions.len(), 2, "{initiator:?} initiating: {spans:?}");
+        let session_ids: BTreeSet<&String> = sessions
+            .iter()
+            .map(|&index| &spans[index].fields["session_id"])
+            .collect();
+        assert_eq!(session_ids.len(), 1, "both ends name one session");
+        assert_eq!(session_ids.first().unwrap().len(), 16);
+
+        for (role, phase) in [("Source", "scan"), ("Destination", "mirror_delete")] {
+            let session = sessions
+                .iter()
+                .copied()
+                .find(|&index| spans[index].fields["endpoint_role"] == role)
+                .unwrap_or_else(|| panic!("{initiator:?} initiating: no {role} session span"));
+            let phases = children(session);
+            assert_eq!(
+                phases.iter().map(|(_, name)| *name).collect::<Vec<_>>(),
+                ["negotiate", "transfer"],
+                "{initiator:?} initiating: {role} phases"
+            );
+            let transfer = phases[1].0;
+            assert!(
+                children(transfer).iter().any(|(_, name)| *name == phase),
+                "{initiator:?} initiating: no {phase} under the {role} transfer"
+            );
+            let fields = &spans[transfer].fields;
+            assert_eq!(fields["bytes"], case.summary.bytes_transferred.to_string());
+            assert_eq!(fields["files"], case.summary.files_transferred.to_string());
+        }
+        let deleted = spans
+            .iter()
+            .find(|span| span.name == "mirror_delete")
+            .unwrap();
+        assert_eq!(deleted.fields["entries"], "1");
+    }
+}
+
 struct SmallFileProbeCase {
     summary: TransferSummary,
     needed_paths: Vec<String>,
@@ -2074,12 +2694,15 @@ async fn run_small_file_probe_case(
         hello: HelloConfig::default(),
         endpoint: dest_endpoint,
         data_plane_host: dest_host,
+        diff_batch: None,
         receiver_capacity: None,
         instruments: DestinationInstruments {
             small_file_probe: probe,
             ..Default::default()
         },
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
     let (source_transport, dest_transport) = in_process_pair();
     let source = Arc::new(FsTransferSource::new(src_root.clone()));
@@ -2451,9 +3074,12 @@ async fn pull_data_plane_lands_bytes() {
         hello: HelloConfig::default(),
         endpoint: SessionEndpoint::initiator(open), // dials + receives
         data_plane_host: Some("127.0.0.1".into()),
+        diff_batch: None,
         receiver_capacity: None,
         instruments: Default::default(),
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
     let (a, b) = in_process_pair();
     let source = Arc::new(FsTransferSource::new(src_root.clone()));
@@ -2523,9 +3149,12 @@ async fn many_tiny_files_transfer_with_live_workers_when_destination_initiates()
         hello: HelloConfig::default(),
         endpoint: SessionEndpoint::initiator(open), // dials + receives
         data_plane_host: Some("127.0.0.1".into()),
+        diff_batch: None,
         receiver_capacity: None,
         instruments: Default::default(),
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
     let (a, b) = in_process_pair();
     let source = Arc::new(FsTransferSource::new(src_root.clone()));
@@ -2617,9 +3246,12 @@ async fn build_mismatch_refused_under_both_initiators() {
             },
             endpoint: dest_endpoint,
             data_plane_host: None,
+            diff_batch: None,
             receiver_capacity: None,
             instruments: Default::default(),
             local_apply: None,
+            fake_super: false,
+            receive_sink: None,
         };
         let (a, b) = in_process_pair();
         let source = Arc::new(FsTransferSource::new(src_root.clone()));
@@ -2677,9 +3309,12 @@ async fn contract_version_mismatch_is_refused() {
         },
         endpoint: SessionEndpoint::Responder,
         data_plane_host: None,
+        diff_batch: None,
         receiver_capacity: None,
         instruments: Default::default(),
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
     let (a, b) = in_process_pair();
     let source = Arc::new(FsTransferSource::new(src_root));
@@ -2721,9 +3356,12 @@ async fn mirror_enabled_without_scope_is_refused() {
         hello: HelloConfig::default(),
         endpoint: SessionEndpoint::Responder,
         data_plane_host: None,
+        diff_batch: None,
         receiver_capacity: None,
         instruments: Default::default(),
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
     let (a, b) = in_process_pair();
     let source = Arc::new(FsTransferSource::new(src_root));
@@ -2813,6 +3451,35 @@ async fn mirror_all_purges_extraneous_under_both_initiators() {
     }
 }
 
+#[tokio::test]
+async fn mirror_all_keeps_source_empty_dirs_and_prunes_extraneous_ones() {
+    // Contract v12: an empty source directory is in the mirror's source
+    // set, so the purge keeps it; an empty directory only the
+    // destination has is still extraneous.
+    for initiator_role in [TransferRole::Source, TransferRole::Destination] {
+        let tmp = tempfile::tempdir().unwrap();
+        let src_root = tmp.path().join("src");
+        let dst_root = tmp.path().join("dst");
+        write_tree(&src_root, &[("keep.txt", b"new".to_vec(), 1_600_000_001)]);
+        std::fs::create_dir_all(src_root.join("kept-empty")).unwrap();
+        std::fs::create_dir_all(dst_root.join("kept-empty")).unwrap();
+        std::fs::create_dir_all(dst_root.join("stale-empty")).unwrap();
+
+        let (sr, dr) =
+            run_mirror_session(initiator_role, &src_root, &dst_root, MirrorMode::All, None).await;
+        let summary =
+            sr.unwrap_or_else(|e| panic!("source failed (init {initiator_role:?}): {e:#}"));
+        dr.unwrap_or_else(|e| panic!("destination failed (init {initiator_role:?}): {e:#}"));
+
+        assert_eq!(
+            summary.entries_deleted, 1,
+            "only the extraneous dir (init {initiator_role:?})"
+        );
+        assert!(dst_root.join("kept-empty").is_dir());
+        assert!(!dst_root.join("stale-empty").exists());
+    }
+}
+
 #[tokio::test]
 async fn mirror_filtered_subset_preserves_out_of_scope() {
     // otp-6b: FilteredSubset deletes only extraneous entries WITHIN the
@@ -2913,9 +3580,12 @@ async fn mirror_refused_when_source_scan_incomplete() {
         hello: HelloConfig::default(),
         endpoint: SessionEndpoint::Responder,
         data_plane_host: None,
+        diff_batch: None,
         receiver_capacity: None,
         instruments: Default::default(),
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
     let (mut peer, dest_transport) = in_process_pair();
     let dest = tokio::spawn(run_destination(
@@ -2929,7 +3599,7 @@ async fn mirror_refused_when_source_scan_incomplete() {
     open.mirror_kind = MirrorMode::All as i32;
     peer.send(hello_frame()).await.unwrap();
     assert!(matches!(recv_or_panic(&mut peer).await, Frame::Hello(_)));
-    peer.send(wire(Frame::Open(open))).await.unwrap();
+    peer.send(wire(Frame::Open(Box::new(open)))).await.unwrap();
     assert!(matches!(recv_or_panic(&mut peer).await, Frame::Accept(_)));
 
     // A manifest entry, then declare the scan INCOMPLETE.
@@ -2940,6 +3610,10 @@ async fn mirror_refused_when_source_scan_incomplete() {
         permissions: 0o644,
         checksum: vec![],
         windows_metadata: None,
+        crtime_seconds: None,
+        directory: false,
+        owner: None,
+        xattrs: Vec::new(),
     })))
     .await
     .unwrap();
@@ -2993,9 +3667,12 @@ async fn cancel_frame_during_mirror_purge_aborts_the_deletions() {
         hello: HelloConfig::default(),
         endpoint: SessionEndpoint::Responder,
         data_plane_host: None,
+        diff_batch: None,
         receiver_capacity: None,
         instruments: Default::default(),
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
     let (mut peer, dest_transport) = in_process_pair();
     let dest = tokio::spawn(run_destination(
@@ -3009,7 +3686,7 @@ async fn cancel_frame_during_mirror_purge_aborts_the_deletions() {
     open.mirror_kind = MirrorMode::All as i32;
     peer.send(hello_frame()).await.unwrap();
     assert!(matches!(recv_or_panic(&mut peer).await, Frame::Hello(_)));
-    peer.send(wire(Frame::Open(open))).await.unwrap();
+    peer.send(wire(Frame::Open(Box::new(open)))).await.unwrap();
     assert!(matches!(recv_or_panic(&mut peer).await, Frame::Accept(_)));
 
     // Empty complete scan → empty need list → the purge would delete
@@ -3071,9 +3748,12 @@ async fn cancel_mid_file_record_surfaces_the_peers_fault() {
         hello: HelloConfig::default(),
         endpoint: SessionEndpoint::Responder,
         data_plane_host: None,
+        diff_batch: None,
         receiver_capacity: None,
         instruments: Default::default(),
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
     let (mut peer, dest_transport) = in_process_pair();
     let dest = tokio::spawn(run_destination(
@@ -3084,9 +3764,11 @@ async fn cancel_mid_file_record_surfaces_the_peers_fault() {
 
     peer.send(hello_frame()).await.unwrap();
     assert!(matches!(recv_or_panic(&mut peer).await, Frame::Hello(_)));
-    peer.send(wire(Frame::Open(basic_open(TransferRole::Source))))
-        .await
-        .unwrap();
+    peer.send(wire(Frame::Open(Box::new(basic_open(
+        TransferRole::Source,
+    )))))
+    .await
+    .unwrap();
     assert!(matches!(recv_or_panic(&mut peer).await, Frame::Accept(_)));
 
     let size = 3 * 1024 * 1024 + 17;
@@ -3097,6 +3779,10 @@ async fn cancel_mid_file_record_surfaces_the_peers_fault() {
         permissions: 0o644,
         checksum: vec![],
         windows_metadata: None,
+        crtime_seconds: None,
+        directory: false,
+        owner: None,
+        xattrs: Vec::new(),
     };
     peer.send(wire(Frame::ManifestEntry(header.clone())))
         .await
@@ -3179,9 +3865,12 @@ async fn incomplete_scan_refused_when_completeness_required() {
         hello: HelloConfig::default(),
         endpoint: SessionEndpoint::Responder,
         data_plane_host: None,
+        diff_batch: None,
         receiver_capacity: None,
         instruments: Default::default(),
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
     let (mut peer, dest_transport) = in_process_pair();
     let dest = tokio::spawn(run_destination(
@@ -3194,7 +3883,7 @@ async fn incomplete_scan_refused_when_completeness_required() {
     open.require_complete_scan = true;
     peer.send(hello_frame()).await.unwrap();
     assert!(matches!(recv_or_panic(&mut peer).await, Frame::Hello(_)));
-    peer.send(wire(Frame::Open(open))).await.unwrap();
+    peer.send(wire(Frame::Open(Box::new(open)))).await.unwrap();
     assert!(matches!(recv_or_panic(&mut peer).await, Frame::Accept(_)));
 
     peer.send(wire(Frame::ManifestEntry(FileHeader {
@@ -3204,6 +3893,10 @@ async fn incomplete_scan_refused_when_completeness_required() {
         permissions: 0o644,
         checksum: vec![],
         windows_metadata: None,
+        crtime_seconds: None,
+        directory: false,
+        owner: None,
+        xattrs: Vec::new(),
     })))
     .await
     .unwrap();
@@ -3374,9 +4067,12 @@ async fn session_filters_via_chokepoint_not_scan_arg() {
         hello: HelloConfig::default(),
         endpoint: SessionEndpoint::Responder,
         data_plane_host: None,
+        diff_batch: None,
         receiver_capacity: None,
         instruments: Default::default(),
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
     let (a, b) = in_process_pair();
     let source = Arc::new(FilterIgnoringSource {
@@ -3437,9 +4133,12 @@ async fn in_stream_payload_before_manifest_complete_is_protocol_violation() {
         hello: HelloConfig::default(),
         endpoint: SessionEndpoint::Responder,
         data_plane_host: None,
+        diff_batch: None,
         receiver_capacity: None,
         instruments: Default::default(),
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
     let (mut peer, dest_transport) = in_process_pair();
     let dest = tokio::spawn(run_destination(
@@ -3453,9 +4152,11 @@ async fn in_stream_payload_before_manifest_complete_is_protocol_violation() {
     // may overlap after NeedBatch; this control-lane record may not.
     peer.send(hello_frame()).await.unwrap();
     assert!(matches!(recv_or_panic(&mut peer).await, Frame::Hello(_)));
-    peer.send(wire(Frame::Open(basic_open(TransferRole::Source))))
-        .await
-        .unwrap();
+    peer.send(wire(Frame::Open(Box::new(basic_open(
+        TransferRole::Source,
+    )))))
+    .await
+    .unwrap();
     assert!(matches!(recv_or_panic(&mut peer).await, Frame::Accept(_)));
 
     let header = FileHeader {
@@ -3465,6 +4166,10 @@ async fn in_stream_payload_before_manifest_complete_is_protocol_violation() {
         permissions: 0o644,
         checksum: vec![],
         windows_metadata: None,
+        crtime_seconds: None,
+        directory: false,
+        owner: None,
+        xattrs: Vec::new(),
     };
     peer.send(wire(Frame::ManifestEntry(header.clone())))
         .await
@@ -3694,9 +4399,12 @@ async fn manifest_entry_after_manifest_complete_is_protocol_violation() {
         hello: HelloConfig::default(),
         endpoint: SessionEndpoint::Responder,
         data_plane_host: None,
+        diff_batch: None,
         receiver_capacity: None,
         instruments: Default::default(),
         local_apply: None,
+        fake_super: false,
+        receive_sink: None,
     };
     let (mut peer, dest_transport) = in_process_pair();
     let dest = tokio::spawn(run_destination(
@@ -3707,9 +4415,11 @@ async fn manifest_entry_after_manifest_complete_is_protocol_violation() {
 
     peer.send(hello_frame()).await.unwrap();
     assert!(matches!(recv_or_panic(&mut peer).await, Frame::Hello(_)));
-    peer.send(wire(Frame::Open(basic_open(TransferRole::Source))))
-        .await
-        .unwrap();
+    peer.send(wire(Frame::Open(Box::new(basic_open(
+        TransferRole::Source,
+    )))))
+    .await
+    .unwrap();
     assert!(matches!(recv_or_panic(&mut peer).await, Frame::Accept(_)));
 
     peer.send(wire(Frame::ManifestComplete(ManifestComplete {
@@ -3724,6 +4434,10 @@ async fn manifest_entry_after_manifest_complete_is_protocol_violation() {
         permissions: 0o644,
         checksum: vec![],
         windows_metadata: None,
+        crtime_seconds: None,
+        directory: false,
+        owner: None,
+        xattrs: Vec::new(),
     })))
     .await
     .unwrap();
@@ -3776,6 +4490,103 @@ async fn checksum_compare_skips_content_equal_files_regardless_of_mtime() {
     }
 }
 
+/// v9 `--metadata-only`: a content-identical file whose mtime and mode
+/// drifted is repaired in place with zero bytes moved, under both
+/// initiator layouts. A file whose content differs is left exactly as
+/// it was (a touch pass never copies), and a missing file stays missing.
+#[cfg(unix)]
+#[tokio::test]
+async fn metadata_only_repairs_drifted_metadata_with_zero_bytes() {
+    use std::os::unix::fs::PermissionsExt;
+    let mode_of = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o7777;
+    for initiator_role in [TransferRole::Source, TransferRole::Destination] {
+        let tmp = tempfile::tempdir().unwrap();
+        let src_root = tmp.path().join("src");
+        let dst_root = tmp.path().join("dst");
+        std::fs::create_dir_all(&src_root).unwrap();
+        std::fs::create_dir_all(&dst_root).unwrap();
+        write_tree(
+            &src_root,
+            &[
+                ("same.bin", vec![7u8; 4096], 2_000),
+                ("differs.bin", vec![1u8; 4096], 2_000),
+                ("new.bin", vec![3u8; 16], 2_000),
+            ],
+        );
+        write_tree(
+            &dst_root,
+            &[
+                ("same.bin", vec![7u8; 4096], 1_000),
+                ("differs.bin", vec![2u8; 4096], 1_000),
+            ],
+        );
+        std::fs::set_permissions(
+            src_root.join("same.bin"),
+            std::fs::Permissions::from_mode(0o640),
+        )
+        .unwrap();
+        std::fs::set_permissions(
+            dst_root.join("same.bin"),
+            std::fs::Permissions::from_mode(0o600),
+        )
+        .unwrap();
+
+        let open = SessionOpen {
+            compare_mode: ComparisonMode::Checksum as i32,
+            metadata_only: true,
+            ..basic_open(initiator_role)
+        };
+        let (source_result, dest_result) =
+            run_session_with_open(open, &src_root, &dst_root, PlanOptions::default()).await;
+        let summary = source_result
+            .unwrap_or_else(|e| panic!("source failed (initiator {initiator_role:?}): {e:#}"));
+        dest_result
+            .unwrap_or_else(|e| panic!("destination failed (initiator {initiator_role:?}): {e:#}"));
+
+        assert_eq!(summary.files_transferred, 0, "initiator {initiator_role:?}");
+        assert_eq!(summary.bytes_transferred, 0, "initiator {initiator_role:?}");
+        let same = std::fs::metadata(dst_root.join("same.bin")).unwrap();
+        assert_eq!(
+            filetime::FileTime::from_last_modification_time(&same).unix_seconds(),
+            2_000,
+            "mtime repaired under initiator {initiator_role:?}"
+        );
+        assert_eq!(mode_of(&dst_root.join("same.bin")), 0o640);
+        assert_eq!(
+            std::fs::read(dst_root.join("differs.bin")).unwrap(),
+            vec![2u8; 4096]
+        );
+        let differs = std::fs::metadata(dst_root.join("differs.bin")).unwrap();
+        assert_eq!(
+            filetime::FileTime::from_last_modification_time(&differs).unix_seconds(),
+            1_000
+        );
+        assert!(!dst_root.join("new.bin").exists());
+    }
+}
+
+/// v9: a metadata-only open without a Checksum compare cannot prove
+/// content equality, so the DESTINATION refuses it at OPEN.
+#[tokio::test]
+async fn metadata_only_without_checksum_compare_is_refused_at_open() {
+    let tmp = tempfile::tempdir().unwrap();
+    let src_root = tmp.path().join("src");
+    let dst_root = tmp.path().join("dst");
+    std::fs::create_dir_all(&src_root).unwrap();
+    std::fs::create_dir_all(&dst_root).unwrap();
+    write_tree(&src_root, &[("a.bin", vec![1u8; 16], 2_000)]);
+
+    let open = SessionOpen {
+        metadata_only: true,
+        ..basic_open(TransferRole::Source)
+    };
+    let (_, dest_result) =
+        run_session_with_open(open, &src_root, &dst_root, PlanOptions::default()).await;
+    let err = dest_result.expect_err("metadata_only + SizeMtime must be refused");
+    assert_eq!(fault_of(&err).code, session_error::Code::ProtocolViolation);
+    assert!(!dst_root.join("a.bin").exists());
+}
+
 /// otp-10b-1: the cell SizeMtime provably misses — same size, same
 /// mtime, DIFFERENT content — must transfer under Checksum, both
 /// initiator layouts. Control first: SizeMtime skips it (that is the
@@ -3817,3 +4628,158 @@ async fn checksum_compare_transfers_content_change_size_mtime_misses() {
         }
     }
 }
+
+// ---------------------------------------------------------------------------
+// Resumable control lane (`--reconnect`, contract v17)
+// ---------------------------------------------------------------------------
+
+/// The initiator's first control transport: it carries `frames` frames
+/// (either direction) and then breaks both ways, as a dropped RPC does.
+fn dropping_after(transport: FrameTransport, frames: usize) -> FrameTransport {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    struct Tx(Box<dyn FrameTx>, Arc<AtomicUsize>);
+    struct Rx(Box<dyn FrameRx>, Arc<AtomicUsize>);
+    #[async_trait::async_trait]
+    impl FrameTx for Tx {
+        async fn send(&mut self, frame: TransferFrame) -> eyre::Result<()> {
+            if self.1.fetch_sub(1, Ordering::SeqCst) == 0 {
+                self.1.store(0, Ordering::SeqCst);
+                eyre::bail!("control stream dropped");
+            }
+            self.0.send(frame).await
+        }
+    }
+    #[async_trait::async_trait]
+    impl FrameRx for Rx {
+        async fn recv(&mut self) -> eyre::Result<Option<TransferFrame>> {
+            let frame = self.0.recv().await?;
+            if self.1.fetch_sub(1, Ordering::SeqCst) == 0 {
+                self.1.store(0, Ordering::SeqCst);
+                eyre::bail!("control stream dropped");
+            }
+            Ok(frame)
+        }
+    }
+    let budget = Arc::new(AtomicUsize::new(frames));
+    let (tx, rx) = transport.split();
+    FrameTransport::new(
+        Box::new(Tx(tx, Arc::clone(&budget))),
+        Box::new(Rx(rx, budget)),
+    )
+}
+
+/// The control stream drops mid-transfer — after the handshake and
+/// manifest, while the in-stream carrier's payload frames are moving —
+/// and the initiator re-attaches over a fresh transport. Both ends keep
+/// their session through the outage and finish with the same summary
+/// and a byte-identical destination, under either initiator.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn control_stream_drop_mid_transfer_reattaches_and_completes() {
+    let src_files = small_tree();
+    for initiator_role in [TransferRole::Source, TransferRole::Destination] {
+        let tmp = tempfile::tempdir().unwrap();
+        let src_root = tmp.path().join("src");
+        let dst_root = tmp.path().join("dst");
+        std::fs::create_dir_all(&src_root).unwrap();
+        std::fs::create_dir_all(&dst_root).unwrap();
+        write_tree(&src_root, &src_files);
+
+        let registry = LaneRegistry::default();
+        let redials = Arc::new(Mutex::new(0usize));
+        let redial: Box<Redial> = {
+            let registry = registry.clone();
+            let redials = Arc::clone(&redials);
+            Box::new(move || {
+                let registry = registry.clone();
+                *redials.lock().unwrap() += 1;
+                Box::pin(async move {
+                    let (initiator, responder) = in_process_pair();
+                    tokio::spawn(async move { registry.reattach(responder).await });
+                    Ok(initiator)
+                })
+            })
+        };
+        let (initiator, responder) = in_process_pair();
+        let (responder, initiator) = tokio::join!(
+            registry.respond(responder),
+            lane::initiate(
+                dropping_after(initiator, 12),
+                Duration::from_secs(30),
+                redial
+            ),
+        );
+        let (initiator, initiator_lane) = initiator.unwrap();
+        let (responder, responder_lane) = responder.unwrap();
+        let (a, b) = match initiator_role {
+            TransferRole::Source => (initiator, responder),
+            _ => (responder, initiator),
+        };
+
+        let (source_result, dest_result) = run_session_over(
+            basic_open(initiator_role),
+            &src_root,
+            &dst_root,
+            PlanOptions::default(),
+            a,
+            b,
+        )
+        .await;
+        let (summary, outcome) = expect_session_success(
+            source_result,
+            dest_result,
+            &format!("reconnecting session under initiator {initiator_role:?}"),
+        );
+        initiator_lane.finish().await.unwrap();
+        responder_lane.finish().await.unwrap();
+
+        assert!(
+            *redials.lock().unwrap() >= 1,
+            "the control stream must have been re-attached (initiator {initiator_role:?})"
+        );
+        assert_eq!(summary, outcome.summary);
+        assert_eq!(summary.files_transferred, src_files.len() as u64);
+        assert_trees_identical(&src_root, &dst_root);
+        assert!(registry.is_empty(), "a finished lane leaves the registry");
+    }
+}
+
+/// v23 `--remote-option`: a DESTINATION responder honors `fsync` and
+/// names only the key it does not know back in the accept, for the
+/// initiator to warn about.
+#[tokio::test]
+async fn destination_accept_names_only_the_ignored_remote_options() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dest_cfg = DestinationSessionConfig {
+        hello: HelloConfig::default(),
+        endpoint: SessionEndpoint::Responder,
+        data_plane_host: None,
+        diff_batch: None,
+        receiver_capacity: None,
+        instruments: Default::default(),
+        local_apply: None,
+        fake_super: false,
+        receive_sink: None,
+    };
+    let (mut peer, dest_transport) = in_process_pair();
+    let dest = tokio::spawn(run_destination(
+        dest_cfg,
+        dest_transport,
+        DestinationTarget::Fixed(tmp.path().to_path_buf()),
+    ));
+
+    let mut open = basic_open(TransferRole::Source);
+    open.remote_options = [("fsync", "true"), ("bogus", "1")]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    peer.send(hello_frame()).await.unwrap();
+    assert!(matches!(recv_or_panic(&mut peer).await, Frame::Hello(_)));
+    peer.send(wire(Frame::Open(Box::new(open)))).await.unwrap();
+    match recv_or_panic(&mut peer).await {
+        Frame::Accept(accept) => {
+            assert_eq!(accept.ignored_remote_options, vec!["bogus".to_string()])
+        }
+        other => panic!("expected SessionAccept, got {other:?}"),
+    }
+    dest.abort();
+}
@@ -334,6 +334,7 @@ impl ActiveJobProgress {
             ProgressEvent::FileComplete { .. } => {
                 atomic_saturating_add(&self.counters.files_completed, 1);
             }
+            ProgressEvent::Deleted { .. } => {}
         }
     }
 
@@ -553,7 +554,10 @@ impl ActiveJobs {
     /// is **not** fired in that case — handlers that don't
     /// race the token would silently keep running, and the
     /// caller would be lied to.
-    #[cfg(test)]
+    ///
+    /// No caller check: production reaches this only through
+    /// `CancelJob` with a verified admin token; peers go through
+    /// [`cancel_authorized`].
     pub fn cancel(&self, transfer_id: &str) -> CancelOutcome {
         let guard = self.inner.table.lock().unwrap_or_else(|e| e.into_inner());
         match guard.get(transfer_id) {
@@ -597,6 +601,16 @@ impl ActiveJobs {
         }
     }
 
+    /// Number of active rows, for `Health.active_transfers`: the
+    /// count without cloning every row the way [`Self::snapshot`] does.
+    pub fn active_count(&self) -> usize {
+        self.inner
+            .table
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+
     /// Snapshot of every active row for `GetState.active[]`.
     ///
     /// `bytes_completed` is loaded from the per-row atomic
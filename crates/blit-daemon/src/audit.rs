@@ -0,0 +1,131 @@
+//! Opt-in audit trail of what peers did to the daemon's modules.
+//!
+//! A module with an `audit_log` — or any module, when `[daemon]
+//! audit_log` is set — gets one JSON line per served push, pull and
+//! purge appended to that file: when it ended, who asked (the peer
+//! address), which module and path, the files it touched, byte and
+//! delete counts, and whether it succeeded. A module's own log takes
+//! precedence over the daemon-wide one.
+//!
+//! Records are appended whole, one `write` per line, under a process-wide
+//! lock, so concurrent transfers never interleave within a line. A log
+//! that cannot be written is reported with `log::warn!`; the operation it
+//! describes has already happened and is not undone.
+
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// One audited operation, serialized as one line of the log.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AuditRecord {
+    /// When the operation finished, in Unix milliseconds.
+    pub(crate) timestamp_unix_ms: u64,
+    /// `push`, `pull` or `purge`.
+    pub(crate) operation: String,
+    pub(crate) peer: String,
+    pub(crate) module: String,
+    /// The module-relative path the request named (empty for the root).
+    pub(crate) path: String,
+    pub(crate) ok: bool,
+    pub(crate) error: Option<String>,
+    pub(crate) files: u64,
+    pub(crate) bytes: u64,
+    pub(crate) entries_deleted: u64,
+    /// Files written or sent (push, pull), or the paths a purge named.
+    pub(crate) paths: Vec<String>,
+}
+
+/// Where each module's records go: its own `audit_log`, else the
+/// daemon-wide one.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AuditTargets {
+    pub(crate) global: Option<PathBuf>,
+    pub(crate) modules: HashMap<String, PathBuf>,
+}
+
+impl AuditTargets {
+    pub(crate) fn new(
+        global: Option<PathBuf>,
+        modules: &HashMap<String, crate::runtime::ModuleConfig>,
+    ) -> Self {
+        Self {
+            global,
+            modules: modules
+                .iter()
+                .filter_map(|(name, config)| Some((name.clone(), config.audit_log.clone()?)))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.global.is_some() || !self.modules.is_empty()
+    }
+
+    /// The log a record for `module` goes to, if it is audited.
+    pub(crate) fn log_for(&self, module: &str) -> Option<&Path> {
+        self.modules
+            .get(module)
+            .or(self.global.as_ref())
+            .map(PathBuf::as_path)
+    }
+}
+
+/// Paths and delete counts a served transfer session reports for its
+/// audit record, gathered while it runs.
+#[derive(Debug, Default)]
+pub(crate) struct SessionTally {
+    pub(crate) paths: Vec<String>,
+    pub(crate) entries_deleted: u64,
+}
+
+pub(crate) fn now_unix_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Append `record` to the log at `log` as one JSON line.
+pub(crate) fn append(log: &Path, record: &AuditRecord) {
+    static WRITE_LOCK: Mutex<()> = Mutex::new(());
+    let mut line = match serde_json::to_vec(record) {
+        Ok(line) => line,
+        Err(err) => {
+            log::warn!("audit: cannot encode record: {err}");
+            return;
+        }
+    };
+    line.push(b'\n');
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log)
+        .and_then(|mut file| file.write_all(&line));
+    if let Err(err) = written {
+        log::warn!("audit: cannot append to {}: {err}", log.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_log_wins_over_the_daemon_wide_one() {
+        let mut targets = AuditTargets::default();
+        assert!(!targets.is_enabled());
+        targets.modules.insert("m".into(), PathBuf::from("/m.log"));
+        assert_eq!(targets.log_for("m"), Some(Path::new("/m.log")));
+        assert_eq!(targets.log_for("other"), None);
+        targets.global = Some(PathBuf::from("/all.log"));
+        assert_eq!(targets.log_for("other"), Some(Path::new("/all.log")));
+        assert_eq!(targets.log_for("m"), Some(Path::new("/m.log")));
+    }
+}
@@ -0,0 +1,147 @@
+//! Content-addressed deduplication for modules with a `dedupe_store`.
+//!
+//! After a push into such a module completes, every file the session
+//! wrote is hashed (BLAKE3) and folded into the store: the first copy of
+//! some content is hard-linked in as the blob `<store>/<aa>/<hex>`, and
+//! later copies — in this push or any other, in any module sharing the
+//! store — are replaced by hard links to that blob. The module tree
+//! keeps ordinary paths, so listings, pulls and `Checksum` read through
+//! the links with no special casing, and a mirror delete only drops one
+//! reference.
+//!
+//! The store lives outside the module tree (so it never shows up in a
+//! listing or a mirror's delete pass) on the same filesystem (hard links
+//! cannot cross devices); [`prepare_store`] checks both at config load.
+//!
+//! Files are re-pointed at their blob with
+//! [`blit_core::dedupe::link_over`], the same step the local `--dedupe`
+//! pass uses; its docs carry why a later push to one path leaves the
+//! blob and its other references alone.
+//!
+//! Hard links share one inode, and with it mode, mtime, owner and
+//! extended attributes — in a `fake_super` module, the `user.blit.stat`
+//! record of each file's logical owner. A file is only linked to a blob
+//! that matches it in all of those
+//! ([`blit_core::dedupe::link_compatible`]); same content with different
+//! metadata keeps its own copy rather than having its metadata
+//! rewritten. Blobs are never collected: one whose link count has dropped
+//! to 1 is no longer referenced by any module.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use blit_core::checksum::{hash_file, ChecksumType};
+use eyre::{bail, Context, Result};
+
+/// What one [`absorb`] pass did.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DedupeStats {
+    /// Files whose content was new to the store and became its blob.
+    pub(crate) blobs_added: u64,
+    /// Files replaced by a link to an existing blob.
+    pub(crate) files_linked: u64,
+    /// Bytes no longer stored twice thanks to `files_linked`.
+    pub(crate) bytes_saved: u64,
+}
+
+/// Create `store` if needed and check it can back `module_root`:
+/// outside the module tree, on the same filesystem. Returns the
+/// canonical store path.
+pub(crate) fn prepare_store(module_root: &Path, store: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(store)
+        .with_context(|| format!("creating dedupe store {}", store.display()))?;
+    let store = fs::canonicalize(store)
+        .with_context(|| format!("resolving dedupe store {}", store.display()))?;
+    if store.starts_with(module_root) {
+        bail!(
+            "dedupe store {} is inside the module it serves",
+            store.display()
+        );
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let store_dev = fs::metadata(&store)?.dev();
+        let module_dev = fs::metadata(module_root)?.dev();
+        if store_dev != module_dev {
+            bail!(
+                "dedupe store {} is on a different filesystem from {}; hard links cannot cross",
+                store.display(),
+                module_root.display()
+            );
+        }
+        Ok(store)
+    }
+    // Writes only break shared links on Unix (`detach_hard_link`); a
+    // push elsewhere would rewrite every copy of a blob at once.
+    #[cfg(not(unix))]
+    bail!("dedupe_store is only supported on Unix daemons")
+}
+
+/// Where the blob for `digest` lives: fanned out by its first byte so
+/// no single directory grows with the whole store.
+pub(crate) fn blob_path(store: &Path, digest: &[u8]) -> PathBuf {
+    let hex: String = digest.iter().map(|b| format!("{b:02x}")).collect();
+    store.join(&hex[..2]).join(hex)
+}
+
+/// Fold the files a session wrote (`rel_paths`, relative to
+/// `dest_root`) into `store`. Best effort per file: a file that cannot
+/// be hashed or linked is logged and left as written, and the rest of
+/// the pass continues.
+pub(crate) fn absorb(store: &Path, dest_root: &Path, rel_paths: &[String]) -> DedupeStats {
+    let mut stats = DedupeStats::default();
+    for rel in rel_paths {
+        if let Err(err) = absorb_one(store, dest_root, rel, &mut stats) {
+            log::warn!("dedupe: leaving {rel} unshared: {err:#}");
+        }
+    }
+    stats
+}
+
+fn absorb_one(store: &Path, dest_root: &Path, rel: &str, stats: &mut DedupeStats) -> Result<()> {
+    let path = blit_core::path_safety::safe_join(dest_root, rel)?;
+    let meta = match fs::symlink_metadata(&path) {
+        Ok(meta) if meta.is_file() => meta,
+        // Directories, symlinks, and paths a later phase removed.
+        Ok(_) => return Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err).with_context(|| format!("stat {}", path.display())),
+    };
+    let digest = hash_file(&path, ChecksumType::Blake3)?;
+    let blob = blob_path(store, &digest);
+    let blob_meta = match fs::symlink_metadata(&blob) {
+        Ok(blob_meta) => blob_meta,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            fs::create_dir_all(blob.parent().expect("blob has a fan-out parent"))?;
+            fs::hard_link(&path, &blob)
+                .with_context(|| format!("linking {} into the store", path.display()))?;
+            stats.blobs_added += 1;
+            return Ok(());
+        }
+        Err(err) => return Err(err).with_context(|| format!("stat {}", blob.display())),
+    };
+    if same_inode(&meta, &blob_meta)
+        || !blit_core::dedupe::link_compatible(&path, &meta, &blob, &blob_meta)
+    {
+        return Ok(());
+    }
+    if !blit_core::dedupe::link_over(&blob, &path, &meta)? {
+        return Ok(());
+    }
+    stats.files_linked += 1;
+    stats.bytes_saved += meta.len();
+    Ok(())
+}
+
+#[cfg(unix)]
+fn same_inode(a: &fs::Metadata, b: &fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.dev() == b.dev() && a.ino() == b.ino()
+}
+
+#[cfg(not(unix))]
+fn same_inode(_a: &fs::Metadata, _b: &fs::Metadata) -> bool {
+    false
+}
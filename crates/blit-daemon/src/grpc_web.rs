@@ -0,0 +1,224 @@
+//! Optional gRPC-Web front (`[daemon] grpc_web = true`) for browser
+//! clients and reverse proxies that cannot speak raw HTTP/2.
+//!
+//! `tonic_web::GrpcWebLayer` translates `application/grpc-web*`
+//! requests (HTTP/1.1 or HTTP/2) into ordinary gRPC; native gRPC
+//! passes through untouched. gRPC-Web has no client streaming, so the
+//! bidirectional `Transfer` session — pulls included — cannot ride it,
+//! and the front is read-path only: [`ReadPathOnlyLayer`] answers any
+//! method outside [`READ_PATH_METHODS`] that arrives as gRPC-Web with
+//! PERMISSION_DENIED before it is translated, so exposing the daemon
+//! through a web proxy never exposes `Purge`, `CancelJob` or
+//! `DelegatedPull`.
+
+use std::task::{Context, Poll};
+
+use tonic::codegen::http::{self, header::CONTENT_TYPE, HeaderValue};
+use tonic::codegen::{BoxFuture, Service};
+use tonic::transport::Server;
+use tonic_web::GrpcWebLayer;
+use tower_layer::{Identity, Layer, Stack};
+
+/// Methods a gRPC-Web caller may reach. Everything here only reads:
+/// listings, searches, usage, file digests, completion, and daemon
+/// state.
+pub(crate) const READ_PATH_METHODS: &[&str] = &[
+    "/blit.v2.Blit/List",
+    "/blit.v2.Blit/Find",
+    "/blit.v2.Blit/DiskUsage",
+    "/blit.v2.Blit/FilesystemStats",
+    "/blit.v2.Blit/Checksum",
+    "/blit.v2.Blit/ListModules",
+    "/blit.v2.Blit/CompletePath",
+    "/blit.v2.Blit/GetState",
+    "/blit.v2.Blit/Subscribe",
+    "/blit.v2.Blit/Capabilities",
+    "/blit.v2.Blit/Health",
+];
+
+/// The production builder (keepalive included) with HTTP/1.1 accepted
+/// and the gRPC-Web translation behind the read-path guard.
+pub(crate) fn grpc_web_server_builder(
+) -> Server<Stack<GrpcWebLayer, Stack<ReadPathOnlyLayer, Identity>>> {
+    blit_core::remote::grpc_server::production_server_builder()
+        .accept_http1(true)
+        .layer(ReadPathOnlyLayer)
+        .layer(GrpcWebLayer::new())
+}
+
+const GRPC_WEB_CONTENT_TYPE: &str = "application/grpc-web";
+
+fn is_grpc_web<B>(req: &http::Request<B>) -> bool {
+    req.headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with(GRPC_WEB_CONTENT_TYPE))
+}
+
+/// Layer refusing gRPC-Web calls to anything but [`READ_PATH_METHODS`].
+/// Must sit outside `GrpcWebLayer`, which rewrites the content type.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ReadPathOnlyLayer;
+
+impl<S> Layer<S> for ReadPathOnlyLayer {
+    type Service = ReadPathOnly<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ReadPathOnly { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ReadPathOnly<S> {
+    inner: S,
+}
+
+impl<S, B> Service<http::Request<B>> for ReadPathOnly<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<tonic::body::Body>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        if is_grpc_web(&req) && !READ_PATH_METHODS.contains(&req.uri().path()) {
+            let status = tonic::Status::permission_denied(format!(
+                "{} is not available over gRPC-Web; only read-path RPCs are",
+                req.uri().path()
+            ));
+            // Trailers-only reply: the status rides the headers, which a
+            // gRPC-Web client reads the same way as a native one.
+            let mut response = status.into_http::<tonic::body::Body>();
+            response.headers_mut().insert(
+                CONTENT_TYPE,
+                HeaderValue::from_static("application/grpc-web+proto"),
+            );
+            return Box::pin(async move { Ok(response) });
+        }
+        Box::pin(self.inner.call(req))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! `List`/`Find` driven through the gRPC-Web translation by a
+    //! gRPC-Web client, and the read-path guard refusing `Purge`.
+    use std::collections::HashMap;
+
+    use blit_core::generated::blit_client::BlitClient;
+    use blit_core::generated::{FindRequest, ListRequest, PurgeRequest};
+    use hyper_util::client::legacy::Client;
+    use hyper_util::rt::TokioExecutor;
+    use tokio::sync::oneshot;
+    use tonic_web::GrpcWebClientService;
+
+    use super::grpc_web_server_builder;
+    use crate::runtime::ModuleConfig;
+    use crate::service::BlitService;
+
+    #[tokio::test]
+    async fn list_and_find_answer_grpc_web_and_purge_is_refused() {
+        let module = tempfile::tempdir().unwrap();
+        let root = module.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("docs")).unwrap();
+        std::fs::write(root.join("docs/readme.txt"), b"hello").unwrap();
+        std::fs::write(root.join("notes.md"), b"x").unwrap();
+
+        let mut modules = HashMap::new();
+        modules.insert(
+            "test".to_string(),
+            ModuleConfig {
+                name: "test".into(),
+                path: root.clone(),
+                canonical_root: root.clone(),
+                read_only: false,
+                _comment: None,
+                delegation_allowed: true,
+                default_subpath: None,
+                dedupe_store: None,
+                fake_super: false,
+                audit_log: None,
+                allowed_subpaths: Vec::new(),
+            },
+        );
+        let service = BlitService::from_runtime(
+            modules,
+            None,
+            false,
+            true,
+            crate::metrics::TransferMetrics::disabled(),
+            crate::delegation_gate::DelegationConfig::default(),
+        );
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let server = tokio::spawn(async move {
+            grpc_web_server_builder()
+                .add_service(blit_core::remote::message_limits::blit_server(service))
+                .serve_with_incoming_shutdown(
+                    tokio_stream::wrappers::TcpListenerStream::new(listener),
+                    async {
+                        let _ = shutdown_rx.await;
+                    },
+                )
+                .await
+                .expect("gRPC-Web daemon serves");
+        });
+
+        // Plain HTTP/1.1, as a browser-facing proxy would forward it.
+        let http1 = Client::builder(TokioExecutor::new()).build_http();
+        let mut client = BlitClient::with_origin(
+            GrpcWebClientService::new(http1),
+            format!("http://127.0.0.1:{port}").parse().unwrap(),
+        );
+
+        let listing = client
+            .list(ListRequest {
+                module: "test".into(),
+                path: String::new(),
+            })
+            .await
+            .expect("List over gRPC-Web")
+            .into_inner();
+        let mut names: Vec<_> = listing.entries.into_iter().map(|e| e.name).collect();
+        names.sort();
+        assert_eq!(names, ["docs", "notes.md"]);
+
+        let mut found = client
+            .find(FindRequest {
+                module: "test".into(),
+                pattern: "*.txt".into(),
+                include_files: true,
+                ..Default::default()
+            })
+            .await
+            .expect("Find over gRPC-Web")
+            .into_inner();
+        let mut paths = Vec::new();
+        while let Some(entry) = found.message().await.expect("Find stream") {
+            paths.push(entry.relative_path);
+        }
+        assert_eq!(paths, ["docs/readme.txt"]);
+
+        let err = client
+            .purge(PurgeRequest {
+                module: "test".into(),
+                paths_to_delete: vec!["notes.md".into()],
+            })
+            .await
+            .expect_err("Purge must not ride gRPC-Web");
+        assert_eq!(err.code(), tonic::Code::PermissionDenied, "{err:?}");
+        assert!(root.join("notes.md").exists());
+
+        let _ = shutdown_tx.send(());
+        server.await.unwrap();
+    }
+}
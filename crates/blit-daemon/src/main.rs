@@ -1,5 +1,8 @@
 mod active_jobs;
+mod audit;
+mod dedupe;
 mod delegation_gate;
+mod grpc_web;
 mod metrics;
 mod net_timeout;
 mod recents_store;
@@ -8,7 +11,7 @@ mod service;
 
 use crate::metrics::TransferMetrics;
 use crate::runtime::{load_runtime, DaemonArgs, DaemonRuntime};
-use crate::service::{spawn_progress_ticker, BlitServer, BlitService};
+use crate::service::{spawn_progress_ticker, BlitService};
 use blit_core::mdns::{self, AdvertiseOptions, MdnsAdvertiser};
 use clap::Parser;
 use eyre::Result;
@@ -21,6 +24,20 @@ async fn main() -> Result<()> {
     blit_core::stderr_log::init("blitd");
     let args = DaemonArgs::parse();
     let runtime = load_runtime(&args)?;
+    if args.check_config {
+        // Validation only: a broken config already failed above with
+        // every problem listed; nothing binds or advertises here.
+        for warning in &runtime.warnings {
+            println!("warning: {warning}");
+        }
+        println!(
+            "config OK: {} module(s), would listen on {}:{}",
+            runtime.modules.len(),
+            runtime.bind_host,
+            runtime.port
+        );
+        return Ok(());
+    }
     let DaemonRuntime {
         bind_host,
         port,
@@ -31,14 +48,24 @@ async fn main() -> Result<()> {
         warnings,
         server_checksums_enabled,
         delegation,
+        socket_overrides,
+        message_limits,
+        diff_batch_limits,
+        admin_token,
+        grpc_web,
+        checksum_workers,
+        audit_log,
     } = runtime;
+    blit_core::remote::transfer::socket::set_data_socket_overrides(socket_overrides);
+    blit_core::remote::message_limits::set_message_limits(message_limits);
+    blit_core::transfer_session::diff_batch::set_diff_batch_limits(diff_batch_limits);
 
     for warning in &warnings {
         log::warn!("{warning}");
     }
 
     let addr: SocketAddr = format!("{}:{}", bind_host, port).parse()?;
-    if let Some(motd) = motd {
+    if let Some(motd) = &motd {
         println!("motd: {motd}");
     }
     if let Some(root) = &default_root {
@@ -109,7 +136,11 @@ async fn main() -> Result<()> {
         server_checksums_enabled,
         metrics,
         delegation,
-    );
+    )
+    .with_motd(motd)
+    .with_admin_token(admin_token)
+    .with_checksum_workers(checksum_workers)
+    .with_audit_log(audit_log);
     // c-4: kick off the periodic `TransferProgress` emitter.
     // The handle is owned by the runtime for the daemon's
     // lifetime; on process exit tokio aborts in-flight tasks.
@@ -131,10 +162,19 @@ async fn main() -> Result<()> {
     // `blit_core::remote::grpc_server` — the shared builder the
     // in-process test servers also start from, so test/prod server
     // config cannot drift (w9-3).
-    blit_core::remote::grpc_server::production_server_builder()
-        .add_service(BlitServer::new(service))
-        .serve(addr)
-        .await?;
+    let blit = blit_core::remote::message_limits::blit_server(service);
+    if grpc_web {
+        eprintln!("blitd: gRPC-Web enabled for read-path RPCs");
+        grpc_web::grpc_web_server_builder()
+            .add_service(blit)
+            .serve(addr)
+            .await?;
+    } else {
+        blit_core::remote::grpc_server::production_server_builder()
+            .add_service(blit)
+            .serve(addr)
+            .await?;
+    }
 
     drop(mdns_guard);
 
@@ -1,11 +1,14 @@
 use clap::Parser;
 use eyre::{eyre, Context, Result};
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::delegation_gate::{parse_allow_entry, DelegationConfig};
+use blit_core::remote::message_limits::MessageLimits;
+use blit_core::remote::transfer::socket::DataSocketOverrides;
+use blit_core::transfer_session::diff_batch::DiffBatchLimits;
 
 #[derive(Debug, Clone)]
 pub(crate) struct ModuleConfig {
@@ -35,6 +38,26 @@ pub(crate) struct ModuleConfig {
     /// widen it: if `allow_delegated_pull = false` daemon-wide, this
     /// flag has no effect and delegation remains denied.
     pub(crate) delegation_allowed: bool,
+    /// Subpath template a write lands under when the client names only
+    /// the module (`blit copy ./x server:/backups/`). Expanded per
+    /// transfer by [`expand_default_subpath`]; validated at load.
+    pub(crate) default_subpath: Option<String>,
+    /// Canonical content-addressed store that pushes into this module
+    /// are deduplicated against (`dedupe_store`); see `crate::dedupe`.
+    pub(crate) dedupe_store: Option<PathBuf>,
+    /// `fake_super`: keep the ownership a session carries in the
+    /// `user.blit.stat` xattr instead of chowning, and read it back when
+    /// the module is pulled from (`blit_core::ownership`). For daemons
+    /// that run without the privilege to chown.
+    pub(crate) fake_super: bool,
+    /// JSON-lines audit log for this module's pushes, pulls and purges
+    /// (`audit_log`), in place of `[daemon] audit_log`; see `crate::audit`.
+    pub(crate) audit_log: Option<PathBuf>,
+    /// `allowed_subpaths`: the subtrees, relative to the module root,
+    /// that writes (pushes, delegated pulls, purges) may target. Empty =
+    /// the whole module. Validated at load; enforced by
+    /// `service::util::enforce_allowed_subpaths`.
+    pub(crate) allowed_subpaths: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -72,6 +95,42 @@ pub(crate) struct DaemonRuntime {
     /// false; the operator must opt the daemon in (and may further
     /// constrain via `allowed_source_hosts`).
     pub(crate) delegation: DelegationConfig,
+    /// Data-plane socket overrides from `[daemon]` `tcp_buffer_size` /
+    /// `tcp_keepalive`; installed process-wide at startup.
+    pub(crate) socket_overrides: DataSocketOverrides,
+    /// gRPC message ceiling and in-stream chunk from `[daemon]`
+    /// `max_message_size` / `control_chunk_size`; installed
+    /// process-wide at startup.
+    pub(crate) message_limits: MessageLimits,
+    /// Destination diff batch limits from `[daemon]`
+    /// `manifest_batch_entries` / `manifest_batch_size`; installed
+    /// process-wide at startup.
+    pub(crate) diff_batch_limits: DiffBatchLimits,
+    /// `[daemon] admin_token`. When set, a `CancelJob` presenting it
+    /// may cancel any active transfer; without it the audit-9 rule
+    /// (only the starting host) applies to every caller.
+    pub(crate) admin_token: Option<String>,
+    /// `[daemon] grpc_web`: serve the read-path RPCs to gRPC-Web
+    /// clients as well; see `crate::grpc_web`.
+    pub(crate) grpc_web: bool,
+    /// `[daemon] checksum_workers`: files one `Checksum` call hashes
+    /// concurrently. Defaults to [`default_checksum_workers`].
+    pub(crate) checksum_workers: usize,
+    /// `[daemon] audit_log`: audit every module without its own
+    /// `audit_log` to this file; see `crate::audit`.
+    pub(crate) audit_log: Option<PathBuf>,
+}
+
+/// Upper bound on the default `checksum_workers`; hashing is I/O-bound
+/// past a handful of threads on most storage.
+const DEFAULT_CHECKSUM_WORKERS_MAX: usize = 8;
+
+/// Default `checksum_workers`: one per core, at most
+/// [`DEFAULT_CHECKSUM_WORKERS_MAX`].
+pub(crate) fn default_checksum_workers() -> usize {
+    std::thread::available_parallelism()
+        .map_or(1, |cores| cores.get())
+        .min(DEFAULT_CHECKSUM_WORKERS_MAX)
 }
 
 #[derive(Parser, Debug)]
@@ -111,6 +170,10 @@ pub(crate) struct DaemonArgs {
     /// skipped entirely when disabled.
     #[arg(long)]
     pub(crate) metrics: bool,
+    /// Load and validate the configuration, report every problem, and
+    /// exit without binding.
+    #[arg(long)]
+    pub(crate) check_config: bool,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -135,6 +198,27 @@ struct RawDaemonSection {
     root_read_only: bool,
     #[serde(default)]
     no_server_checksums: bool,
+    /// Data-plane SO_SNDBUF/SO_RCVBUF, e.g. "4Mi".
+    tcp_buffer_size: Option<String>,
+    /// Data-plane keepalive idle before the first probe, e.g. "20s".
+    tcp_keepalive: Option<String>,
+    /// Control-plane gRPC message ceiling, e.g. "128Mi".
+    max_message_size: Option<String>,
+    /// In-stream payload bytes per control-plane frame, e.g. "4Mi".
+    control_chunk_size: Option<String>,
+    /// Manifest entries diffed per batch when receiving, e.g. 4096.
+    manifest_batch_entries: Option<usize>,
+    /// Encoded manifest bytes diffed per batch when receiving, e.g. "256Ki".
+    manifest_batch_size: Option<String>,
+    /// Shared secret that lets `CancelJob` cancel any transfer.
+    admin_token: Option<String>,
+    /// Also answer read-path RPCs over gRPC-Web (HTTP/1.1 included).
+    #[serde(default)]
+    grpc_web: bool,
+    /// Files one `Checksum` call hashes concurrently.
+    checksum_workers: Option<usize>,
+    /// JSON-lines audit log for every module without its own.
+    audit_log: Option<PathBuf>,
 }
 
 /// `[delegation]` block from the daemon config. Default: feature off.
@@ -160,12 +244,185 @@ struct RawModule {
     /// configs unaffected. See `ModuleConfig::delegation_allowed`.
     #[serde(default = "default_true")]
     delegation_allowed: bool,
+    /// Where writes that name only the module land, e.g.
+    /// `"incoming/{date}"`. See `ModuleConfig::default_subpath`.
+    #[serde(default)]
+    default_subpath: Option<String>,
+    /// Content-addressed store for deduplicating pushed files. See
+    /// `ModuleConfig::dedupe_store`.
+    #[serde(default)]
+    dedupe_store: Option<PathBuf>,
+    /// Fake ownership in xattrs. See `ModuleConfig::fake_super`.
+    #[serde(default)]
+    fake_super: bool,
+    /// This module's audit log. See `ModuleConfig::audit_log`.
+    #[serde(default)]
+    audit_log: Option<PathBuf>,
+    /// Subtrees writes are confined to, e.g. `["incoming", "shared/drop"]`.
+    /// See `ModuleConfig::allowed_subpaths`.
+    #[serde(default)]
+    allowed_subpaths: Vec<String>,
 }
 
 fn default_true() -> bool {
     true
 }
 
+/// Expand a leading `~` (the home directory) and `${VAR}` references
+/// in a configured path, so one config can serve several machines.
+/// A referenced variable that is unset is an error, never an empty
+/// string. Non-UTF-8 paths are returned untouched.
+fn expand_config_path(path: &Path) -> Result<PathBuf> {
+    let Some(raw) = path.to_str() else {
+        return Ok(path.to_path_buf());
+    };
+
+    let mut expanded = String::with_capacity(raw.len());
+    let mut rest = raw;
+    if let Some(tail) = rest.strip_prefix('~') {
+        if tail.is_empty() || tail.starts_with('/') || tail.starts_with(std::path::MAIN_SEPARATOR) {
+            let home = directories::BaseDirs::new()
+                .ok_or_else(|| eyre!("cannot expand '~' in '{raw}': no home directory"))?;
+            expanded.push_str(&home.home_dir().to_string_lossy());
+            rest = tail;
+        }
+    }
+    while let Some(start) = rest.find("${") {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| eyre!("unterminated '${{' in '{raw}'"))?;
+        let name = &after[..end];
+        if name.is_empty() {
+            return Err(eyre!("empty '${{}}' in '{raw}'"));
+        }
+        let value = std::env::var(name)
+            .map_err(|_| eyre!("environment variable '{name}' referenced in '{raw}' is not set"))?;
+        expanded.push_str(&value);
+        rest = &after[end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(PathBuf::from(expanded))
+}
+
+/// Validate a module's `allowed_subpaths` entries as module-relative
+/// paths (no `..`, not absolute) and normalize them. An entry naming the
+/// module root itself is refused: it would allow everything, which is
+/// what leaving the list empty already means.
+fn parse_allowed_subpaths(entries: &[String]) -> Result<Vec<PathBuf>> {
+    let mut allowed = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let normalized = blit_core::path_safety::validate_wire_path(entry.trim_end_matches('/'))
+            .map_err(|err| eyre!("allowed_subpaths entry '{entry}': {err}"))?;
+        if normalized.as_os_str().is_empty() {
+            return Err(eyre!(
+                "allowed_subpaths entry '{entry}' names the module root; \
+                 leave allowed_subpaths empty to allow the whole module"
+            ));
+        }
+        allowed.push(normalized);
+    }
+    Ok(allowed)
+}
+
+/// Placeholder a glob module's `name` uses for each matched directory.
+const BASENAME_PLACEHOLDER: &str = "{basename}";
+
+/// Placeholder in a module's `default_subpath` for the transfer's UTC
+/// date, as `YYYY-MM-DD`.
+const DATE_PLACEHOLDER: &str = "{date}";
+
+/// Expand a module's `default_subpath` template for a transfer starting
+/// at `now`. The result must be a plain relative path inside the module
+/// (no `..`, not absolute); any placeholder other than `{date}` is an
+/// error.
+pub(crate) fn expand_default_subpath(
+    template: &str,
+    now: std::time::SystemTime,
+) -> Result<PathBuf> {
+    let expanded = template.replace(DATE_PLACEHOLDER, &utc_date(now));
+    if expanded.contains(['{', '}']) {
+        return Err(eyre!(
+            "default_subpath '{template}': only {DATE_PLACEHOLDER} is supported"
+        ));
+    }
+    let rel = blit_core::path_safety::validate_wire_path(&expanded)
+        .map_err(|err| eyre!("default_subpath '{template}': {err}"))?;
+    if rel.as_os_str().is_empty() {
+        return Err(eyre!("default_subpath '{template}' is empty"));
+    }
+    Ok(rel)
+}
+
+/// `now` as a UTC calendar date, `YYYY-MM-DD`.
+fn utc_date(now: std::time::SystemTime) -> String {
+    let secs = now
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64);
+    // Howard Hinnant's civil_from_days.
+    let z = secs.div_euclid(86_400) + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Expand one `[[module]]` into its exports. A plain path is one
+/// module; a path whose last component is a glob (`/srv/exports/*`)
+/// becomes one module per matching directory, named by substituting
+/// the directory name for `{basename}` in `name`. Name collisions are
+/// left to the caller's duplicate check.
+///
+/// Only `*` and `?` make a component a glob: `[` and `{` are ordinary
+/// in directory names (`data[1]`), and a config that exported one
+/// before globs existed must keep loading. Within a glob component
+/// they keep their class/alternation meaning.
+fn expand_module_glob(name: &str, path: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let is_glob = |s: &str| s.contains(['*', '?']);
+    let pattern = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    if !is_glob(pattern) {
+        return Ok(vec![(name.to_string(), path.to_path_buf())]);
+    }
+    let parent = path.parent().unwrap_or(Path::new("."));
+    if parent.to_str().is_some_and(is_glob) {
+        return Err(eyre!(
+            "only the last component of '{}' may be a glob",
+            path.display()
+        ));
+    }
+    if !name.contains(BASENAME_PLACEHOLDER) {
+        return Err(eyre!(
+            "glob path '{}' needs '{BASENAME_PLACEHOLDER}' in the module name",
+            path.display()
+        ));
+    }
+    let matcher = globset::Glob::new(pattern)
+        .with_context(|| format!("invalid glob '{}'", path.display()))?
+        .compile_matcher();
+
+    let mut exports = Vec::new();
+    let entries =
+        fs::read_dir(parent).with_context(|| format!("failed to read '{}'", parent.display()))?;
+    for entry in entries {
+        let entry = entry.with_context(|| format!("failed to read '{}'", parent.display()))?;
+        let Some(basename) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        let dir = entry.path();
+        if matcher.is_match(&basename) && dir.is_dir() {
+            exports.push((name.replace(BASENAME_PLACEHOLDER, &basename), dir));
+        }
+    }
+    exports.sort();
+    Ok(exports)
+}
+
 fn default_config_path() -> PathBuf {
     if cfg!(windows) {
         PathBuf::from(r"C:\ProgramData\Blit\config.toml")
@@ -174,8 +431,12 @@ fn default_config_path() -> PathBuf {
     }
 }
 
+/// Load the daemon configuration. A file that cannot be read or parsed
+/// fails at once; past that, every invalid setting is collected so one
+/// error (and one `--check-config` run) reports them all.
 pub(crate) fn load_runtime(args: &DaemonArgs) -> Result<DaemonRuntime> {
     let mut warnings = Vec::new();
+    let mut problems: Vec<String> = Vec::new();
 
     let config_path = if let Some(path) = &args.config {
         Some(path.clone())
@@ -206,6 +467,14 @@ pub(crate) fn load_runtime(args: &DaemonArgs) -> Result<DaemonRuntime> {
         .port
         .or(raw.daemon.port)
         .unwrap_or(blit_core::remote::endpoint::RemoteEndpoint::DEFAULT_PORT);
+    if format!("{bind_host}:{port}")
+        .parse::<std::net::SocketAddr>()
+        .is_err()
+    {
+        problems.push(format!(
+            "invalid bind address '{bind_host}' (expected an IP address; bracket IPv6, e.g. [::])"
+        ));
+    }
 
     let motd = raw.daemon.motd.clone();
     let mdns_disabled = if args.no_mdns {
@@ -226,51 +495,187 @@ pub(crate) fn load_runtime(args: &DaemonArgs) -> Result<DaemonRuntime> {
         !raw.daemon.no_server_checksums
     };
 
+    let socket_overrides = DataSocketOverrides::parse(
+        raw.daemon.tcp_buffer_size.as_deref(),
+        raw.daemon.tcp_keepalive.as_deref(),
+    )
+    .context("invalid data-plane socket settings in [daemon]")
+    .unwrap_or_else(|err| {
+        problems.push(format!("{err:#}"));
+        DataSocketOverrides::default()
+    });
+
+    let message_limits = MessageLimits::parse(
+        raw.daemon.max_message_size.as_deref(),
+        raw.daemon.control_chunk_size.as_deref(),
+    )
+    .context("invalid control-plane message settings in [daemon]")
+    .unwrap_or_else(|err| {
+        problems.push(format!("{err:#}"));
+        MessageLimits::default()
+    });
+
+    let diff_batch_limits = DiffBatchLimits::parse(
+        raw.daemon
+            .manifest_batch_entries
+            .map(|entries| entries.to_string())
+            .as_deref(),
+        raw.daemon.manifest_batch_size.as_deref(),
+    )
+    .context("invalid manifest batch settings in [daemon]")
+    .unwrap_or_else(|err| {
+        problems.push(format!("{err:#}"));
+        DiffBatchLimits::default()
+    });
+
+    let admin_token = match raw.daemon.admin_token.as_deref().map(str::trim) {
+        Some("") => {
+            problems.push("[daemon] admin_token must not be empty".to_string());
+            None
+        }
+        token => token.map(str::to_string),
+    };
+
+    let checksum_workers = match raw.daemon.checksum_workers {
+        Some(0) => {
+            problems.push("[daemon] checksum_workers must be at least 1".to_string());
+            default_checksum_workers()
+        }
+        Some(workers) => workers,
+        None => default_checksum_workers(),
+    };
+
+    let audit_log = match raw.daemon.audit_log.as_deref().map(expand_config_path) {
+        Some(Ok(log)) => Some(log),
+        Some(Err(err)) => {
+            problems.push(format!("[daemon] audit_log: {err}"));
+            None
+        }
+        None => None,
+    };
+
     // Parse delegation gate config first so an invalid CIDR / bad
     // hostname surfaces as a config-load error before we touch any
     // module paths. This is the §4.3.2 contract: invalid entries fail
     // config load loudly.
     let mut allowed_source_hosts = Vec::with_capacity(raw.delegation.allowed_source_hosts.len());
     for entry in &raw.delegation.allowed_source_hosts {
-        let parsed = parse_allow_entry(entry).with_context(|| {
+        match parse_allow_entry(entry).with_context(|| {
             format!(
                 "failed to parse allowed_source_hosts entry '{}' in [delegation]",
                 entry
             )
-        })?;
-        allowed_source_hosts.push(parsed);
+        }) {
+            Ok(parsed) => allowed_source_hosts.push(parsed),
+            Err(err) => problems.push(format!("{err:#}")),
+        }
     }
     let delegation = DelegationConfig {
         allow_delegated_pull: raw.delegation.allow_delegated_pull,
         allowed_source_hosts,
     };
 
+    let configured_modules = raw.modules.len();
+    let mut seen_modules = HashSet::new();
     let mut modules = HashMap::new();
     for module in raw.modules {
         if module.name.trim().is_empty() {
-            return Err(eyre!("module names cannot be empty"));
+            problems.push("module names cannot be empty".to_string());
+            continue;
         }
-        if modules.contains_key(&module.name) {
-            return Err(eyre!("duplicate module '{}' in config", module.name));
+        let path = match expand_config_path(&module.path) {
+            Ok(path) => path,
+            Err(err) => {
+                problems.push(format!("module '{}': {err}", module.name));
+                continue;
+            }
+        };
+        if let Some(template) = &module.default_subpath {
+            if let Err(err) = expand_default_subpath(template, std::time::SystemTime::now()) {
+                problems.push(format!("module '{}': {err}", module.name));
+                continue;
+            }
+        }
+        let dedupe_store = match module.dedupe_store.as_deref().map(expand_config_path) {
+            Some(Ok(store)) => Some(store),
+            Some(Err(err)) => {
+                problems.push(format!("module '{}': dedupe_store: {err}", module.name));
+                continue;
+            }
+            None => None,
+        };
+        let module_audit_log = match module.audit_log.as_deref().map(expand_config_path) {
+            Some(Ok(log)) => Some(log),
+            Some(Err(err)) => {
+                problems.push(format!("module '{}': audit_log: {err}", module.name));
+                continue;
+            }
+            None => None,
+        };
+        let allowed_subpaths = match parse_allowed_subpaths(&module.allowed_subpaths) {
+            Ok(allowed) => allowed,
+            Err(err) => {
+                problems.push(format!("module '{}': {err}", module.name));
+                continue;
+            }
+        };
+        let exports = match expand_module_glob(&module.name, &path) {
+            Ok(exports) => exports,
+            Err(err) => {
+                problems.push(format!("module '{}': {err}", module.name));
+                continue;
+            }
+        };
+        if exports.is_empty() {
+            warnings.push(format!(
+                "module '{}': glob '{}' matched no directories",
+                module.name,
+                path.display()
+            ));
+        }
+        for (name, path) in exports {
+            if !seen_modules.insert(name.clone()) {
+                problems.push(format!("duplicate module '{name}' in config"));
+                continue;
+            }
+            let canonical = match fs::canonicalize(&path) {
+                Ok(canonical) => canonical,
+                Err(err) => {
+                    problems.push(format!(
+                        "failed to resolve path '{}' for module '{name}': {err}",
+                        path.display()
+                    ));
+                    continue;
+                }
+            };
+            let dedupe_store = match dedupe_store
+                .as_deref()
+                .map(|store| crate::dedupe::prepare_store(&canonical, store))
+                .transpose()
+            {
+                Ok(store) => store,
+                Err(err) => {
+                    problems.push(format!("module '{name}': {err:#}"));
+                    continue;
+                }
+            };
+            modules.insert(
+                name.clone(),
+                ModuleConfig {
+                    name,
+                    path: canonical.clone(),
+                    canonical_root: canonical,
+                    read_only: module.read_only,
+                    _comment: module.comment.clone(),
+                    delegation_allowed: module.delegation_allowed,
+                    default_subpath: module.default_subpath.clone(),
+                    dedupe_store,
+                    fake_super: module.fake_super,
+                    audit_log: module_audit_log.clone(),
+                    allowed_subpaths: allowed_subpaths.clone(),
+                },
+            );
         }
-        let canonical = fs::canonicalize(&module.path).with_context(|| {
-            format!(
-                "failed to resolve path '{}' for module '{}'",
-                module.path.display(),
-                module.name
-            )
-        })?;
-        modules.insert(
-            module.name.clone(),
-            ModuleConfig {
-                name: module.name,
-                path: canonical.clone(),
-                canonical_root: canonical,
-                read_only: module.read_only,
-                _comment: module.comment,
-                delegation_allowed: module.delegation_allowed,
-            },
-        );
     }
 
     let mut root_spec = if let Some(cli_root) = &args.root {
@@ -279,17 +684,25 @@ pub(crate) fn load_runtime(args: &DaemonArgs) -> Result<DaemonRuntime> {
             read_only: false,
         })
     } else if let Some(cfg_root) = raw.daemon.root.clone() {
-        Some(RootSpec {
-            path: cfg_root,
-            read_only: raw.daemon.root_read_only,
-        })
+        match expand_config_path(&cfg_root) {
+            Ok(path) => Some(RootSpec {
+                path,
+                read_only: raw.daemon.root_read_only,
+            }),
+            Err(err) => {
+                problems.push(format!("[daemon] root: {err}"));
+                None
+            }
+        }
     } else {
         None
     };
 
     let mut default_root = None;
 
-    if modules.is_empty() {
+    // A module that failed validation is not "no modules": the
+    // working-directory fallback applies only to a config without any.
+    if configured_modules == 0 {
         let chosen = if let Some(spec) = root_spec.take() {
             spec
         } else {
@@ -303,42 +716,51 @@ pub(crate) fn load_runtime(args: &DaemonArgs) -> Result<DaemonRuntime> {
                 read_only: false,
             }
         };
-        let canonical = fs::canonicalize(&chosen.path).with_context(|| {
-            format!(
-                "failed to resolve default export path '{}'",
+        match fs::canonicalize(&chosen.path) {
+            Ok(canonical) => {
+                modules.insert(
+                    "default".to_string(),
+                    ModuleConfig {
+                        name: "default".to_string(),
+                        path: canonical.clone(),
+                        canonical_root: canonical.clone(),
+                        read_only: chosen.read_only,
+                        _comment: None,
+                        // Implicit "default" module follows the daemon-wide
+                        // delegation policy without further narrowing.
+                        delegation_allowed: true,
+                        default_subpath: None,
+                        dedupe_store: None,
+                        fake_super: false,
+                        audit_log: None,
+                        allowed_subpaths: Vec::new(),
+                    },
+                );
+                default_root = Some(RootExport {
+                    path: canonical.clone(),
+                    canonical_root: canonical,
+                    read_only: chosen.read_only,
+                });
+            }
+            Err(err) => problems.push(format!(
+                "failed to resolve default export path '{}': {err}",
                 chosen.path.display()
-            )
-        })?;
-        modules.insert(
-            "default".to_string(),
-            ModuleConfig {
-                name: "default".to_string(),
-                path: canonical.clone(),
-                canonical_root: canonical.clone(),
-                read_only: chosen.read_only,
-                _comment: None,
-                // Implicit "default" module follows the daemon-wide
-                // delegation policy without further narrowing.
-                delegation_allowed: true,
-            },
-        );
-        default_root = Some(RootExport {
-            path: canonical.clone(),
-            canonical_root: canonical,
-            read_only: chosen.read_only,
-        });
+            )),
+        }
     } else if let Some(spec) = root_spec {
-        let canonical = fs::canonicalize(&spec.path).with_context(|| {
-            format!(
-                "failed to resolve root export path '{}'",
+        match fs::canonicalize(&spec.path) {
+            Ok(canonical) => {
+                default_root = Some(RootExport {
+                    path: canonical.clone(),
+                    canonical_root: canonical,
+                    read_only: spec.read_only,
+                });
+            }
+            Err(err) => problems.push(format!(
+                "failed to resolve root export path '{}': {err}",
                 spec.path.display()
-            )
-        })?;
-        default_root = Some(RootExport {
-            path: canonical.clone(),
-            canonical_root: canonical,
-            read_only: spec.read_only,
-        });
+            )),
+        }
     } else if !modules.contains_key("default") {
         warnings.push(
             "no default root configured; server:// requests will be rejected until --root or config root is provided"
@@ -346,6 +768,18 @@ pub(crate) fn load_runtime(args: &DaemonArgs) -> Result<DaemonRuntime> {
         );
     }
 
+    if !problems.is_empty() {
+        let source = config_path
+            .as_ref()
+            .map(|path| format!("config {}", path.display()))
+            .unwrap_or_else(|| "daemon settings".to_string());
+        return Err(eyre!(
+            "{source} has {} problem(s):\n  - {}",
+            problems.len(),
+            problems.join("\n  - ")
+        ));
+    }
+
     Ok(DaemonRuntime {
         bind_host,
         port,
@@ -356,6 +790,13 @@ pub(crate) fn load_runtime(args: &DaemonArgs) -> Result<DaemonRuntime> {
         warnings,
         server_checksums_enabled,
         delegation,
+        socket_overrides,
+        message_limits,
+        diff_batch_limits,
+        admin_token,
+        grpc_web: raw.daemon.grpc_web,
+        checksum_workers,
+        audit_log,
     })
 }
 
@@ -382,6 +823,7 @@ mod delegation_config_tests {
             force_grpc_data: false,
             no_server_checksums: false,
             metrics: false,
+            check_config: false,
         };
         (dir, args)
     }
@@ -441,6 +883,377 @@ mod delegation_config_tests {
         assert!(runtime.delegation.allowed_source_hosts.is_empty());
     }
 
+    #[test]
+    fn socket_overrides_load_from_daemon_section() {
+        let toml = r#"
+            [daemon]
+            tcp_buffer_size = "2Mi"
+            tcp_keepalive = "15s"
+        "#;
+        let (_dir, args) = with_config(toml);
+        let runtime = load_runtime(&args).expect("config loads");
+        assert_eq!(runtime.socket_overrides.tcp_buffer_size, Some(2 << 20));
+        assert_eq!(
+            runtime.socket_overrides.keepalive_idle,
+            Some(std::time::Duration::from_secs(15))
+        );
+
+        let (_dir, args) = with_config("[daemon]\ntcp_keepalive = \"0s\"\n");
+        assert!(
+            load_runtime(&args).is_err(),
+            "zero keepalive must fail load"
+        );
+    }
+
+    #[test]
+    fn message_limits_load_from_daemon_section() {
+        let (_dir, args) = with_config("");
+        let runtime = load_runtime(&args).expect("default load");
+        assert_eq!(runtime.message_limits, MessageLimits::default());
+
+        let toml = r#"
+            [daemon]
+            max_message_size = "128Mi"
+            control_chunk_size = "8Mi"
+        "#;
+        let (_dir, args) = with_config(toml);
+        let runtime = load_runtime(&args).expect("config loads");
+        assert_eq!(runtime.message_limits.max_message_bytes, 128 << 20);
+        assert_eq!(runtime.message_limits.control_chunk_bytes, 8 << 20);
+
+        let (_dir, args) = with_config(
+            "[daemon]
+control_chunk_size = \"80Mi\"\n",
+        );
+        let err = load_runtime(&args).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("does not fit the max message size"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn manifest_batch_limits_load_from_daemon_section() {
+        let (_dir, args) = with_config("");
+        let runtime = load_runtime(&args).expect("default load");
+        assert_eq!(runtime.diff_batch_limits, DiffBatchLimits::default());
+
+        let toml = r#"
+            [daemon]
+            manifest_batch_entries = 4096
+            manifest_batch_size = "256Ki"
+        "#;
+        let (_dir, args) = with_config(toml);
+        let runtime = load_runtime(&args).expect("config loads");
+        assert_eq!(runtime.diff_batch_limits.max_entries, 4096);
+        assert_eq!(runtime.diff_batch_limits.max_bytes, 256 << 10);
+
+        let (_dir, args) = with_config("[daemon]\nmanifest_batch_entries = 0\n");
+        let err = load_runtime(&args).unwrap_err();
+        assert!(format!("{err:#}").contains("at least 1"), "{err:#}");
+    }
+
+    #[test]
+    fn grpc_web_is_off_unless_the_daemon_section_enables_it() {
+        let (_dir, args) = with_config("");
+        assert!(!load_runtime(&args).expect("default load").grpc_web);
+
+        let (_dir, args) = with_config("[daemon]\ngrpc_web = true\n");
+        assert!(load_runtime(&args).expect("config loads").grpc_web);
+    }
+
+    #[test]
+    fn checksum_workers_defaults_per_core_and_rejects_zero() {
+        let (_dir, args) = with_config("");
+        let workers = load_runtime(&args).expect("default load").checksum_workers;
+        assert!((1..=DEFAULT_CHECKSUM_WORKERS_MAX).contains(&workers));
+
+        let (_dir, args) = with_config("[daemon]\nchecksum_workers = 16\n");
+        assert_eq!(
+            load_runtime(&args).expect("config loads").checksum_workers,
+            16
+        );
+
+        let (_dir, args) = with_config("[daemon]\nchecksum_workers = 0\n");
+        let err = load_runtime(&args).unwrap_err();
+        assert!(format!("{err:#}").contains("checksum_workers"), "{err:#}");
+    }
+
+    #[test]
+    fn admin_token_loads_from_daemon_section_and_rejects_blank() {
+        let (_dir, args) = with_config("[daemon]\nadmin_token = \" s3cret \"\n");
+        let runtime = load_runtime(&args).expect("config loads");
+        assert_eq!(runtime.admin_token.as_deref(), Some("s3cret"));
+
+        let (_dir, args) = with_config("[daemon]\nadmin_token = \"  \"\n");
+        assert!(load_runtime(&args).is_err(), "blank token must fail load");
+    }
+
+    #[test]
+    fn every_config_problem_is_reported_together() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mod_path = dir.path().join("mod1");
+        std::fs::create_dir_all(&mod_path).expect("create module dir");
+        let missing = dir.path().join("missing");
+        let toml = format!(
+            r#"
+                [daemon]
+                bind = "not-an-address"
+
+                [[module]]
+                name = "alpha"
+                path = {present:?}
+
+                [[module]]
+                name = "alpha"
+                path = {present:?}
+
+                [[module]]
+                name = "beta"
+                path = {missing:?}
+            "#,
+            present = mod_path.to_str().unwrap(),
+            missing = missing.to_str().unwrap(),
+        );
+        let (_dir, args) = with_config(&toml);
+        let msg = format!("{:#}", load_runtime(&args).unwrap_err());
+        assert!(msg.contains("3 problem(s)"), "{msg}");
+        assert!(
+            msg.contains("invalid bind address 'not-an-address'"),
+            "{msg}"
+        );
+        assert!(msg.contains("duplicate module 'alpha'"), "{msg}");
+        assert!(
+            msg.contains("failed to resolve path") && msg.contains("module 'beta'"),
+            "{msg}"
+        );
+    }
+
+    fn home() -> PathBuf {
+        directories::BaseDirs::new()
+            .expect("home directory")
+            .home_dir()
+            .to_path_buf()
+    }
+
+    #[test]
+    fn module_and_root_paths_expand_env_vars_and_home() {
+        let var = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+        if std::env::var_os(var).is_none() {
+            return;
+        }
+        let toml = format!(
+            r#"
+                [daemon]
+                root = "~"
+
+                [[module]]
+                name = "env"
+                path = "${{{var}}}"
+
+                [[module]]
+                name = "tilde"
+                path = "~"
+            "#
+        );
+        let (_dir, mut args) = with_config(&toml);
+        args.root = None;
+        let runtime = load_runtime(&args).expect("config loads");
+        let home = std::fs::canonicalize(home()).unwrap();
+        assert_eq!(
+            runtime.modules["env"].canonical_root,
+            std::fs::canonicalize(std::env::var(var).unwrap()).unwrap()
+        );
+        assert_eq!(runtime.modules["tilde"].canonical_root, home);
+        assert_eq!(runtime.default_root.unwrap().canonical_root, home);
+    }
+
+    #[test]
+    fn unset_env_var_in_module_path_fails_load() {
+        let toml = r#"
+            [[module]]
+            name = "alpha"
+            path = "/srv/${BLIT_TEST_UNSET_MODULE_ROOT}/data"
+        "#;
+        let (_dir, args) = with_config(toml);
+        let msg = format!("{:#}", load_runtime(&args).unwrap_err());
+        assert!(
+            msg.contains("module 'alpha'")
+                && msg.contains("'BLIT_TEST_UNSET_MODULE_ROOT'")
+                && msg.contains("is not set"),
+            "{msg}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dedupe_store_is_created_and_refused_inside_its_module() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("data")).unwrap();
+        let toml = format!(
+            r#"
+                [[module]]
+                name = "data"
+                path = "{root}/data"
+                dedupe_store = "{root}/store"
+            "#,
+            root = root.display()
+        );
+        let (_dir, args) = with_config(&toml);
+        let runtime = load_runtime(&args).expect("config loads");
+        assert_eq!(
+            runtime.modules["data"].dedupe_store.as_deref(),
+            Some(root.join("store").as_path())
+        );
+        assert!(root.join("store").is_dir());
+
+        let (_dir, args) = with_config(&toml.replace("/store", "/data/.store"));
+        let msg = format!("{:#}", load_runtime(&args).unwrap_err());
+        assert!(msg.contains("inside the module"), "{msg}");
+    }
+
+    #[test]
+    fn fake_super_is_a_per_module_switch() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for name in ["backups", "plain"] {
+            std::fs::create_dir_all(dir.path().join(name)).unwrap();
+        }
+        let toml = format!(
+            r#"
+                [[module]]
+                name = "backups"
+                path = "{root}/backups"
+                fake_super = true
+
+                [[module]]
+                name = "plain"
+                path = "{root}/plain"
+            "#,
+            root = dir.path().display()
+        );
+        let (_dir, args) = with_config(&toml);
+        let runtime = load_runtime(&args).expect("config loads");
+        assert!(runtime.modules["backups"].fake_super);
+        assert!(!runtime.modules["plain"].fake_super);
+    }
+
+    #[test]
+    fn audit_log_loads_daemon_wide_and_per_module() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for name in ["audited", "plain"] {
+            std::fs::create_dir_all(dir.path().join(name)).unwrap();
+        }
+        let toml = format!(
+            r#"
+                [daemon]
+                audit_log = "{root}/all.jsonl"
+
+                [[module]]
+                name = "audited"
+                path = "{root}/audited"
+                audit_log = "{root}/audited.jsonl"
+
+                [[module]]
+                name = "plain"
+                path = "{root}/plain"
+            "#,
+            root = dir.path().display()
+        );
+        let (_dir, args) = with_config(&toml);
+        let runtime = load_runtime(&args).expect("config loads");
+        assert_eq!(runtime.audit_log, Some(dir.path().join("all.jsonl")));
+        assert_eq!(
+            runtime.modules["audited"].audit_log,
+            Some(dir.path().join("audited.jsonl"))
+        );
+        assert_eq!(runtime.modules["plain"].audit_log, None);
+    }
+
+    #[tokio::test]
+    async fn glob_module_exports_each_matching_directory() {
+        use blit_core::generated::blit_server::Blit;
+        use blit_core::generated::ListModulesRequest;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let exports = dir.path().join("exports");
+        for sub in ["alpha", "beta", "gamma"] {
+            std::fs::create_dir_all(exports.join(sub)).unwrap();
+        }
+        std::fs::write(exports.join("notes.txt"), b"not a directory").unwrap();
+        let toml = format!(
+            r#"
+                [[module]]
+                name = "site-{{basename}}"
+                path = "{}/*"
+                read_only = true
+            "#,
+            exports.to_str().unwrap().replace('\\', "/")
+        );
+        let (_dir, mut args) = with_config(&toml);
+        args.root = None;
+        let runtime = load_runtime(&args).expect("config loads");
+        assert!(runtime.modules.values().all(|m| m.read_only));
+
+        let service = crate::service::BlitService::with_modules(runtime.modules, false);
+        let listed = service
+            .list_modules(tonic::Request::new(ListModulesRequest {}))
+            .await
+            .unwrap()
+            .into_inner()
+            .modules;
+        let names: Vec<&str> = listed.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["site-alpha", "site-beta", "site-gamma"]);
+    }
+
+    #[test]
+    fn literal_brackets_and_braces_in_a_module_path_are_not_a_glob() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for sub in ["data[1]", "data1", "set{a,b}"] {
+            std::fs::create_dir_all(dir.path().join(sub)).unwrap();
+        }
+        let toml = format!(
+            r#"
+                [[module]]
+                name = "data"
+                path = "{root}/data[1]"
+
+                [[module]]
+                name = "set"
+                path = "{root}/set{{a,b}}"
+            "#,
+            root = dir.path().to_str().unwrap().replace('\\', "/")
+        );
+        let (_dir, args) = with_config(&toml);
+        let runtime = load_runtime(&args).expect("literal paths load");
+        assert_eq!(runtime.modules.len(), 2);
+        assert!(runtime.modules["data"].path.ends_with("data[1]"));
+        assert!(runtime.modules["set"].path.ends_with("set{a,b}"));
+    }
+
+    #[test]
+    fn glob_module_name_collision_fails_load() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let exports = dir.path().join("exports");
+        std::fs::create_dir_all(exports.join("alpha")).unwrap();
+        std::fs::create_dir_all(exports.join("beta")).unwrap();
+        let toml = format!(
+            r#"
+                [[module]]
+                name = "{{basename}}"
+                path = "{exports}/*"
+
+                [[module]]
+                name = "beta"
+                path = "{exports}/beta"
+            "#,
+            exports = exports.to_str().unwrap().replace('\\', "/")
+        );
+        let (_dir, args) = with_config(&toml);
+        let msg = format!("{:#}", load_runtime(&args).unwrap_err());
+        assert!(msg.contains("duplicate module 'beta'"), "{msg}");
+    }
+
     #[test]
     fn per_module_delegation_allowed_defaults_true() {
         // A module without an explicit `delegation_allowed` setting
@@ -468,6 +1281,7 @@ mod delegation_config_tests {
             force_grpc_data: false,
             no_server_checksums: false,
             metrics: false,
+            check_config: false,
         };
         let runtime = load_runtime(&args).expect("config loads");
         assert!(runtime.modules["alpha"].delegation_allowed);
@@ -499,10 +1313,96 @@ mod delegation_config_tests {
             force_grpc_data: false,
             no_server_checksums: false,
             metrics: false,
+            check_config: false,
         };
         let runtime = load_runtime(&args).expect("config loads");
         assert!(!runtime.modules["alpha"].delegation_allowed);
     }
+
+    #[test]
+    fn default_subpath_expands_the_utc_date() {
+        let at = |secs: u64| std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+        assert_eq!(utc_date(at(0)), "1970-01-01");
+        assert_eq!(utc_date(at(951_782_400)), "2000-02-29");
+        assert_eq!(utc_date(at(1_700_000_000)), "2023-11-14");
+        assert_eq!(
+            expand_default_subpath("incoming/{date}/raw", at(1_700_000_000)).unwrap(),
+            PathBuf::from("incoming/2023-11-14/raw")
+        );
+        for bad in ["../{date}", "/abs/{date}", "{host}/{date}", ""] {
+            assert!(
+                expand_default_subpath(bad, at(0)).is_err(),
+                "{bad:?} must be refused"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_default_subpath_fails_config_load() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mod_path = dir.path().join("mod1");
+        std::fs::create_dir_all(&mod_path).expect("create module dir");
+        let cfg_path = dir.path().join("config.toml");
+        let toml = format!(
+            r#"
+                [[module]]
+                name = "alpha"
+                path = {path:?}
+                default_subpath = "../escape/{{date}}"
+            "#,
+            path = mod_path.canonicalize().unwrap().to_str().unwrap()
+        );
+        std::fs::write(&cfg_path, toml).expect("write config");
+        let args = DaemonArgs {
+            config: Some(cfg_path),
+            bind: None,
+            port: None,
+            root: None,
+            no_mdns: true,
+            mdns_name: None,
+            force_grpc_data: false,
+            no_server_checksums: false,
+            metrics: false,
+            check_config: false,
+        };
+        let err = load_runtime(&args).unwrap_err();
+        assert!(format!("{err:#}").contains("default_subpath"), "{err:#}");
+    }
+
+    #[test]
+    fn invalid_allowed_subpaths_fail_config_load() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mod_path = dir.path().join("mod1");
+        std::fs::create_dir_all(&mod_path).expect("create module dir");
+        let cfg_path = dir.path().join("config.toml");
+        let toml = format!(
+            r#"
+                [[module]]
+                name = "alpha"
+                path = {path:?}
+                allowed_subpaths = ["incoming", "../escape"]
+            "#,
+            path = mod_path.canonicalize().unwrap().to_str().unwrap()
+        );
+        std::fs::write(&cfg_path, toml).expect("write config");
+        let args = DaemonArgs {
+            config: Some(cfg_path),
+            bind: None,
+            port: None,
+            root: None,
+            no_mdns: true,
+            mdns_name: None,
+            force_grpc_data: false,
+            no_server_checksums: false,
+            metrics: false,
+            check_config: false,
+        };
+        let err = load_runtime(&args).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("allowed_subpaths entry '../escape'"),
+            "{err:#}"
+        );
+    }
 }
 
 #[cfg(test)]
@@ -510,6 +1410,12 @@ mod cli_surface_tests {
     use super::*;
     use clap::error::ErrorKind;
 
+    #[test]
+    fn check_config_flag_parses() {
+        let args = DaemonArgs::try_parse_from(["blit-daemon", "--check-config"]).unwrap();
+        assert!(args.check_config);
+    }
+
     #[test]
     fn version_reports_the_exact_session_build_identity() {
         let error = DaemonArgs::try_parse_from(["blit-daemon", "--version"])
@@ -1,7 +1,8 @@
-use super::{DiskUsageSender, FindSender};
+use super::{ChecksumSender, DiskUsageSender, FindSender};
+use blit_core::checksum::{hash_reader, ChecksumType};
 use blit_core::enumeration::{EntryKind, FileEnumerator};
 use blit_core::fs_enum::FileFilter;
-use blit_core::generated::{DiskUsageEntry, FilesystemStatsResponse, FindEntry};
+use blit_core::generated::{ChecksumEntry, DiskUsageEntry, FilesystemStatsResponse, FindEntry};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -63,6 +64,7 @@ fn delete_rel_paths_sync(
             abort: None,
             execute: true,
             directory_mode: blit_core::deletion::DirectoryMode::Recursive,
+            on_deleted: None,
         },
     )
     .map_err(deletion_status)
@@ -169,10 +171,22 @@ struct UsageAccum {
     dirs: u64,
 }
 
+/// The walk filter for `du` / `find`: each `exclude` glob drops a
+/// matching file and prunes a matching directory (any path component),
+/// so `node_modules` or `.git` is never traversed.
+pub(crate) fn walk_exclude_filter(exclude: Vec<String>) -> Result<FileFilter, Status> {
+    let mut filter = FileFilter::default();
+    filter.exclude_files = exclude.clone();
+    filter.exclude_dirs = exclude;
+    filter.validate_globs().map_err(Status::invalid_argument)?;
+    Ok(filter)
+}
+
 pub(crate) fn stream_disk_usage(
     module_root: PathBuf,
     start_rel: PathBuf,
     max_depth: Option<usize>,
+    filter: FileFilter,
     sender: &DiskUsageSender,
 ) -> Result<(), Status> {
     let start_abs = module_root.join(&start_rel);
@@ -232,7 +246,7 @@ pub(crate) fn stream_disk_usage(
         if start_rel != Path::new(".") {
             add_dir(&mut accum, &start_rel, max_depth);
         }
-        let enumerator = FileEnumerator::new(FileFilter::default());
+        let enumerator = FileEnumerator::new(filter);
         enumerator
             .enumerate_local_streaming(&start_abs, |entry| {
                 let rel_from_root = if start_rel == Path::new(".") {
@@ -315,6 +329,7 @@ pub(crate) fn stream_find_entries(
     include_files: bool,
     include_dirs: bool,
     max_results: Option<usize>,
+    filter: FileFilter,
     sender: &FindSender,
 ) -> Result<(), Status> {
     let start_abs = module_root.join(&start_rel);
@@ -416,7 +431,7 @@ pub(crate) fn stream_find_entries(
         maybe_emit(start_rel.clone(), metadata, true)?;
     }
 
-    let enumerator = FileEnumerator::new(FileFilter::default());
+    let enumerator = FileEnumerator::new(filter);
     enumerator
         .enumerate_local_streaming(&start_abs, |entry| {
             let rel_from_root = if start_rel == Path::new(".") {
@@ -437,6 +452,68 @@ pub(crate) fn stream_find_entries(
     Ok(())
 }
 
+/// Hash each of `rel_paths` (already sanitized) under `module_root`
+/// on up to `workers` blocking threads at once, streaming each entry
+/// as its hash completes — completion order, not request order; the
+/// entry's `relative_path` names the file it belongs to. In-flight
+/// work is bounded by `workers`, so open files and read buffers are
+/// too. Stops at the first path that is missing, escapes the module,
+/// or is not a regular file.
+pub(crate) async fn stream_checksums(
+    module_root: PathBuf,
+    rel_paths: Vec<PathBuf>,
+    algorithm: ChecksumType,
+    workers: usize,
+    sender: &ChecksumSender,
+) -> Result<(), Status> {
+    let mut pending = rel_paths.into_iter();
+    let mut running = task::JoinSet::new();
+    loop {
+        while running.len() < workers.max(1) {
+            let Some(rel) = pending.next() else {
+                break;
+            };
+            let root = module_root.clone();
+            running.spawn_blocking(move || checksum_file(&root, &rel, algorithm));
+        }
+        let Some(joined) = running.join_next().await else {
+            return Ok(());
+        };
+        let entry = joined.map_err(|err| internal_err("checksum worker failed", err))??;
+        sender
+            .send(Ok(entry))
+            .await
+            .map_err(|_| response_channel_closed("sending checksum result"))?;
+    }
+}
+
+fn checksum_file(
+    module_root: &Path,
+    rel: &Path,
+    algorithm: ChecksumType,
+) -> Result<ChecksumEntry, Status> {
+    let display = request_path_to_posix(rel);
+    let abs = module_root.join(rel);
+    let metadata =
+        fs::metadata(&abs).map_err(|err| io_to_status(format!("stat {display}"), err))?;
+    blit_core::path_safety::verify_contained(module_root, &abs)
+        .map_err(|e| Status::permission_denied(format!("path containment: {e:#}")))?;
+    if !metadata.is_file() {
+        return Err(Status::invalid_argument(format!(
+            "{display} is not a regular file"
+        )));
+    }
+    let mut file =
+        fs::File::open(&abs).map_err(|err| io_to_status(format!("open {display}"), err))?;
+    let digest = hash_reader(&mut file, algorithm)
+        .map_err(|err| internal_err(format!("hashing {display}"), err))?;
+    Ok(ChecksumEntry {
+        relative_path: display,
+        digest,
+        size: metadata.len(),
+    })
+}
+
 pub(crate) fn filesystem_stats_for_path(path: &Path) -> Result<FilesystemStatsResponse, Status> {
     let canonical = fs::canonicalize(path).map_err(|err| {
         io_to_status(
@@ -458,35 +535,44 @@ pub(crate) fn filesystem_stats_for_path(path: &Path) -> Result<FilesystemStatsRe
     };
 
     let disks = Disks::new_with_refreshed_list();
-
-    let mut best_match = None;
-    let mut best_len = 0usize;
-    for disk in disks.iter() {
-        let mount = disk.mount_point();
-        if canonical.starts_with(mount) {
-            let depth = mount.components().count();
-            if depth >= best_len {
-                best_len = depth;
-                best_match = Some(disk);
-            }
-        }
-    }
-
-    let disk = best_match.ok_or_else(|| {
-        Status::internal(format!(
-            "no filesystem information available for {}",
-            path.display()
-        ))
-    })?;
+    let disk = deepest_mount(&canonical, disks.iter().map(|disk| disk.mount_point()))
+        .map(|idx| &disks.list()[idx])
+        .ok_or_else(|| {
+            Status::failed_precondition(format!(
+                "no mounted filesystem on the daemon host contains {} (module path {}); \
+                 the host's mount table does not list it, so free space cannot be reported",
+                canonical.display(),
+                path.display()
+            ))
+        })?;
 
     Ok(FilesystemStatsResponse {
         module: request_path_to_posix(path),
         total_bytes: disk.total_space(),
         used_bytes: disk.total_space().saturating_sub(disk.available_space()),
         free_bytes: disk.available_space(),
+        mount_point: disk.mount_point().to_string_lossy().into_owned(),
+        filesystem: disk.file_system().to_string_lossy().into_owned(),
     })
 }
 
+/// Index of the mount that backs `canonical`: the deepest mount point
+/// it sits under, so a bind or overlay mount nested inside `/` wins
+/// over `/`. On a tie (the same point mounted twice) the later entry
+/// wins, matching the kernel, where the last mount shadows the rest.
+fn deepest_mount<'a>(canonical: &Path, mounts: impl Iterator<Item = &'a Path>) -> Option<usize> {
+    let mut best: Option<(usize, usize)> = None;
+    for (idx, mount) in mounts.enumerate() {
+        if canonical.starts_with(mount) {
+            let depth = mount.components().count();
+            if best.is_none_or(|(_, best_depth)| depth >= best_depth) {
+                best = Some((idx, depth));
+            }
+        }
+    }
+    best.map(|(idx, _)| idx)
+}
+
 #[cfg(test)]
 mod disk_usage_depth_tests {
     //! d-41 R2: pins the `stream_disk_usage` depth contract the
@@ -541,7 +627,13 @@ mod disk_usage_depth_tests {
         let (tx, mut rx) = mpsc::channel::<Result<DiskUsageEntry, Status>>(4096);
         let module_root = root.clone();
         let handle = std::thread::spawn(move || {
-            stream_disk_usage(module_root, PathBuf::from("."), Some(1), &tx)
+            stream_disk_usage(
+                module_root,
+                PathBuf::from("."),
+                Some(1),
+                FileFilter::default(),
+                &tx,
+            )
         });
         let mut entries = Vec::new();
         while let Some(item) = rx.blocking_recv() {
@@ -601,7 +693,13 @@ mod disk_usage_depth_tests {
         let (tx, mut rx) = mpsc::channel::<Result<DiskUsageEntry, Status>>(4096);
         let module_root = root.clone();
         let handle = std::thread::spawn(move || {
-            stream_disk_usage(module_root, PathBuf::from("."), None, &tx)
+            stream_disk_usage(
+                module_root,
+                PathBuf::from("."),
+                None,
+                FileFilter::default(),
+                &tx,
+            )
         });
         let mut paths = Vec::new();
         while let Some(item) = rx.blocking_recv() {
@@ -651,6 +749,7 @@ mod status_mapping_tests {
             module_root.clone(),
             PathBuf::from("entry"),
             None,
+            FileFilter::default(),
             &disk_sender,
         )
         .expect_err("closed disk-usage stream");
@@ -670,6 +769,7 @@ mod status_mapping_tests {
             true,
             false,
             None,
+            FileFilter::default(),
             &find_sender,
         )
         .expect_err("closed find stream");
@@ -680,3 +780,111 @@ mod status_mapping_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod walk_exclude_tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    /// An excluded directory is pruned from the walk: nothing under it
+    /// counts toward the du totals or shows up in find, and an invalid
+    /// glob is refused before any walk.
+    #[test]
+    fn excluded_directory_is_omitted_from_du_totals_and_find_results() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = fs::canonicalize(temp.path()).expect("canonical tempdir");
+        fs::create_dir_all(root.join("app/node_modules/dep")).expect("mkdir");
+        fs::create_dir_all(root.join(".git")).expect("mkdir");
+        fs::write(root.join("app/main.js"), [0u8; 10]).expect("write");
+        fs::write(root.join("app/node_modules/dep/index.js"), [0u8; 1000]).expect("write");
+        fs::write(root.join(".git/HEAD"), [0u8; 100]).expect("write");
+        let exclude = vec!["node_modules".to_string(), ".git".to_string()];
+
+        let (tx, mut rx) = mpsc::channel(64);
+        let module_root = root.clone();
+        let filter = walk_exclude_filter(exclude.clone()).expect("filter");
+        let du = std::thread::spawn(move || {
+            stream_disk_usage(module_root, PathBuf::from("."), None, filter, &tx)
+        });
+        let mut usage = Vec::new();
+        while let Some(item) = rx.blocking_recv() {
+            usage.push(item.expect("du entry"));
+        }
+        du.join().unwrap().expect("du ok");
+        let total = usage
+            .iter()
+            .find(|e| e.relative_path == ".")
+            .expect("root row");
+        assert_eq!((total.byte_total, total.file_count), (10, 1));
+        assert!(usage
+            .iter()
+            .all(|e| !e.relative_path.contains("node_modules") && e.relative_path != ".git"));
+
+        let (tx, mut rx) = mpsc::channel(64);
+        let module_root = root.clone();
+        let filter = walk_exclude_filter(exclude).expect("filter");
+        let find = std::thread::spawn(move || {
+            stream_find_entries(
+                module_root,
+                PathBuf::from("."),
+                String::new(),
+                true,
+                true,
+                true,
+                None,
+                filter,
+                &tx,
+            )
+        });
+        let mut found = Vec::new();
+        while let Some(item) = rx.blocking_recv() {
+            found.push(item.expect("find entry").relative_path);
+        }
+        find.join().unwrap().expect("find ok");
+        found.sort();
+        assert_eq!(found, ["app", "app/main.js"]);
+
+        let err = walk_exclude_filter(vec!["[".to_string()]).expect_err("bad glob");
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+}
+
+#[cfg(test)]
+mod filesystem_stats_tests {
+    use super::*;
+
+    #[test]
+    fn reports_the_mount_backing_the_module() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let stats = filesystem_stats_for_path(temp.path()).expect("stats for a tempdir");
+        let canonical = fs::canonicalize(temp.path()).expect("canonical tempdir");
+        assert!(!stats.mount_point.is_empty());
+        assert!(
+            canonical.starts_with(&stats.mount_point),
+            "{} is not under reported mount {}",
+            canonical.display(),
+            stats.mount_point
+        );
+        assert!(!stats.filesystem.is_empty());
+    }
+
+    #[test]
+    fn nested_mounts_win_over_their_parents() {
+        let mounts = [
+            Path::new("/"),
+            Path::new("/srv"),
+            Path::new("/srv/data"),
+            Path::new("/srv/database"),
+        ];
+        let pick = |path: &str| deepest_mount(Path::new(path), mounts.iter().copied());
+        assert_eq!(pick("/srv/data/photos"), Some(2));
+        // Component-wise, not string-prefix: `/srv/datastore` is not
+        // under `/srv/data`.
+        assert_eq!(pick("/srv/datastore"), Some(1));
+        assert_eq!(pick("/home/u"), Some(0));
+        assert_eq!(
+            deepest_mount(Path::new("/x"), [Path::new("/y")].into_iter()),
+            None
+        );
+    }
+}
@@ -1,25 +1,34 @@
 use super::admin::{
     delete_rel_paths, filesystem_stats_for_path, list_completions, sanitize_request_paths,
-    split_completion_prefix, stream_disk_usage, stream_find_entries,
+    split_completion_prefix, stream_checksums, stream_disk_usage, stream_find_entries,
+    walk_exclude_filter,
 };
 use super::util::{
-    internal_err, io_to_status, resolve_contained_path, resolve_module, resolve_relative_path,
+    enforce_allowed_subpaths, internal_err, io_to_status, resolve_contained_path, resolve_module,
+    resolve_relative_path,
 };
-use super::{DiskUsageSender, FindSender};
+use super::{ChecksumSender, DiskUsageSender, FindSender};
 use crate::active_jobs::{ActiveJobKind, ActiveJobs, CancelOutcome};
+use crate::audit::{AuditRecord, AuditTargets, SessionTally};
 use crate::metrics::TransferMetrics;
 use crate::runtime::{ModuleConfig, RootExport};
+use blit_core::checksum::ChecksumType;
 use blit_core::generated::blit_server::Blit;
-pub use blit_core::generated::blit_server::BlitServer;
 use blit_core::generated::{
-    daemon_event, ActiveTransfer, CancelJobRequest, CancelJobResponse, ClearRecentRequest,
+    daemon_event, ActiveTransfer, CancelJobRequest, CancelJobResponse, CapabilitiesRequest,
+    CapabilitiesResponse, ChecksumAlgorithm, ChecksumEntry, ChecksumRequest, ClearRecentRequest,
     ClearRecentResponse, CompletionRequest, CompletionResponse, Counters, DaemonEvent, DaemonState,
     DelegatedPullProgress, DelegatedPullRequest, DiskUsageEntry, DiskUsageRequest, FileInfo,
     FilesystemStatsRequest, FilesystemStatsResponse, FindEntry, FindRequest, GetStateRequest,
-    ListModulesRequest, ListModulesResponse, ListRequest, ListResponse, ModuleInfo, PurgeRequest,
-    PurgeResponse, SubscribeRequest, TransferComplete, TransferError, TransferProgress,
-    TransferRecord, TransferStarted,
+    HealthRequest, HealthResponse, ListModulesRequest, ListModulesResponse, ListRequest,
+    ListResponse, ModuleInfo, PurgeRequest, PurgeResponse, SubscribeRequest, TransferComplete,
+    TransferError, TransferProgress, TransferRecord, TransferStarted,
 };
+use blit_core::remote::ADMIN_TOKEN_METADATA;
+use blit_core::transfer_session::lane::{
+    LaneRegistry, LANE_ATTACH, LANE_METADATA_KEY, LANE_RESUME,
+};
+use blit_core::transfer_session::transport::grpc_daemon_transport;
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
@@ -85,6 +94,18 @@ pub struct BlitService {
     /// Captured once so a clock jump between construction and
     /// the GetState call doesn't show up as negative uptime.
     started_at: std::time::Instant,
+    /// Operator's `[daemon] motd`, reported by `Capabilities`.
+    motd: Option<String>,
+    /// Operator's `[daemon] admin_token`; see [`Self::with_admin_token`].
+    admin_token: Option<String>,
+    /// Files one `Checksum` call hashes concurrently (`[daemon]
+    /// checksum_workers`).
+    checksum_workers: usize,
+    /// Operator's `[daemon] audit_log`; see [`Self::with_audit_log`].
+    audit_log: Option<PathBuf>,
+    /// Resumable control lanes of served `--reconnect` sessions, keyed
+    /// by lane id; a re-attach RPC is handed to its lane through here.
+    lanes: LaneRegistry,
 }
 
 impl BlitService {
@@ -107,6 +128,62 @@ impl BlitService {
             active_jobs: ActiveJobs::new(),
             events_tx,
             started_at: std::time::Instant::now(),
+            motd: None,
+            admin_token: None,
+            checksum_workers: crate::runtime::default_checksum_workers(),
+            audit_log: None,
+            lanes: LaneRegistry::default(),
+        }
+    }
+
+    /// Attach the operator's message of the day for `Capabilities`.
+    pub(crate) fn with_motd(mut self, motd: Option<String>) -> Self {
+        self.motd = motd;
+        self
+    }
+
+    /// Attach the operator's admin token. A `CancelJob` carrying it in
+    /// [`ADMIN_TOKEN_METADATA`] skips the starting-host check and may
+    /// cancel any active transfer.
+    pub(crate) fn with_admin_token(mut self, admin_token: Option<String>) -> Self {
+        self.admin_token = admin_token;
+        self
+    }
+
+    /// Set how many files one `Checksum` call hashes at once.
+    pub(crate) fn with_checksum_workers(mut self, workers: usize) -> Self {
+        self.checksum_workers = workers.max(1);
+        self
+    }
+
+    /// Audit every module without its own `audit_log` to `audit_log`
+    /// (`crate::audit`).
+    pub(crate) fn with_audit_log(mut self, audit_log: Option<PathBuf>) -> Self {
+        self.audit_log = audit_log;
+        self
+    }
+
+    /// Where this daemon's modules are audited to, as configured now.
+    async fn audit_targets(&self) -> AuditTargets {
+        AuditTargets::new(self.audit_log.clone(), &*self.modules.lock().await)
+    }
+
+    /// Whether `request` presents an admin token: `Ok(true)` when it
+    /// matches this daemon's, `Ok(false)` when none is presented, and
+    /// `PermissionDenied` for a wrong token or a daemon without one —
+    /// a rejected token never silently falls back to the peer rule.
+    fn admin_token_presented<T>(&self, request: &Request<T>) -> Result<bool, Status> {
+        let Some(presented) = request.metadata().get(ADMIN_TOKEN_METADATA) else {
+            return Ok(false);
+        };
+        match &self.admin_token {
+            Some(expected) if constant_time_eq(presented.as_bytes(), expected.as_bytes()) => {
+                Ok(true)
+            }
+            Some(_) => Err(Status::permission_denied("admin token rejected")),
+            None => Err(Status::permission_denied(
+                "this daemon has no [daemon] admin_token configured",
+            )),
         }
     }
 
@@ -138,6 +215,9 @@ impl BlitService {
             )));
         }
         let sanitized = sanitize_request_paths(req.paths_to_delete)?;
+        for rel in &sanitized {
+            enforce_allowed_subpaths(&module, &module.path.join(rel))?;
+        }
         if sanitized.is_empty() {
             return Ok(Response::new(PurgeResponse { files_deleted: 0 }));
         }
@@ -332,6 +412,7 @@ pub(crate) fn build_transfer_finished_event(
 impl Blit for BlitService {
     type FindStream = ReceiverStream<Result<FindEntry, Status>>;
     type DiskUsageStream = ReceiverStream<Result<DiskUsageEntry, Status>>;
+    type ChecksumStream = ReceiverStream<Result<ChecksumEntry, Status>>;
     type DelegatedPullStream = ReceiverStream<Result<DelegatedPullProgress, Status>>;
     type SubscribeStream =
         std::pin::Pin<Box<dyn tokio_stream::Stream<Item = Result<DaemonEvent, Status>> + Send>>;
@@ -351,6 +432,37 @@ impl Blit for BlitService {
         request: Request<tonic::Streaming<blit_core::generated::TransferFrame>>,
     ) -> Result<Response<Self::TransferStream>, Status> {
         let peer = peer_addr_string(&request);
+        // `--reconnect` (contract v17): the client marks a lane RPC in
+        // metadata. A re-attach serves no session of its own — it only
+        // hands its stream to the dropped lane it names — so it gets no
+        // jobs row, metrics or events.
+        let lanes = match request
+            .metadata()
+            .get(LANE_METADATA_KEY)
+            .map(|mark| mark.to_str().unwrap_or_default())
+        {
+            None => None,
+            Some(LANE_ATTACH) => Some(super::transfer::ServedLane {
+                registry: self.lanes.clone(),
+                terminator: Arc::default(),
+            }),
+            Some(LANE_RESUME) => {
+                let (tx, rx) = mpsc::channel(32);
+                let transport = grpc_daemon_transport(tx, request.into_inner());
+                let lanes = self.lanes.clone();
+                tokio::spawn(async move {
+                    if let Err(err) = lanes.reattach(transport).await {
+                        log::warn!("control lane re-attach from {peer} refused: {err:#}");
+                    }
+                });
+                return Ok(Response::new(ReceiverStream::new(rx)));
+            }
+            Some(other) => {
+                return Err(Status::invalid_argument(format!(
+                    "unknown {LANE_METADATA_KEY} mark '{other}'"
+                )))
+            }
+        };
         let modules = Arc::clone(&self.modules);
         let default_root = self.default_root.clone();
         // Operator policy applies to served sessions exactly as it did
@@ -380,7 +492,16 @@ impl Blit for BlitService {
         let started = std::time::Instant::now();
         let events_tx = self.events_tx();
         let started_emitted = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        // Audit: the open fills in what was served where; the tally
+        // collects the files it touched (`crate::audit`).
+        let audit = self.audit_targets().await;
+        let tally = audit
+            .is_enabled()
+            .then(|| Arc::new(std::sync::Mutex::new(SessionTally::default())));
+        let audit_open: Arc<std::sync::Mutex<Option<(ActiveJobKind, String, String)>>> =
+            Arc::default();
         let on_open: Arc<super::transfer::OnSessionOpen> = {
+            let audit_open = Arc::clone(&audit_open);
             let updater = job.updater();
             let active_jobs = self.active_jobs.clone();
             let events_tx = self.events_tx();
@@ -397,6 +518,8 @@ impl Blit for BlitService {
                     _ => metrics.inc_pull(),
                 }
                 updater.set_kind_and_endpoint(kind, module.to_string(), path.to_string());
+                *audit_open.lock().expect("audit slot poisoned") =
+                    Some((kind, module.to_string(), path.to_string()));
                 started_emitted.store(true, std::sync::atomic::Ordering::Release);
                 let event = DaemonEvent {
                     payload: Some(daemon_event::Payload::TransferStarted(TransferStarted {
@@ -421,21 +544,30 @@ impl Blit for BlitService {
             let byte_progress = job.bytes_counter();
             let job_progress = job.progress();
             // Session variant: cancel surfaces as a framed
-            // SessionError{CANCELLED}, not a bare Status (codex F1).
+            // SessionError{CANCELLED}, not a bare Status (codex F1). A
+            // lane session outlives its first RPC, so that RPC's hangup
+            // is not the client's: the lane's reconnect window decides,
+            // and a cancel is framed through the lane (see ServedLane).
+            let detach = lanes.is_some();
+            let lane_terminator = lanes.as_ref().map(|lane| Arc::clone(&lane.terminator));
             let (ok, err_msg) = resolve_transfer_session_outcome(
                 super::transfer::run_transfer_session(
                     modules,
                     default_root,
                     inbound,
                     tx.clone(),
+                    lanes,
                     policy,
                     byte_progress,
                     job_progress,
                     on_open,
+                    tally.clone(),
                 ),
                 &tx,
+                lane_terminator.as_deref(),
                 &cancel_token,
                 &metrics,
+                detach,
             )
             .await;
             // A session that died before its open resolved never fired
@@ -457,6 +589,35 @@ impl Blit for BlitService {
                 active_jobs.emit_event(&events_tx, job.transfer_id(), event);
             }
             job.record_outcome(ok, err_msg.clone());
+            let opened = audit_open.lock().expect("audit slot poisoned").take();
+            if let Some((kind, module, path)) = opened {
+                if let Some(log) = audit.log_for(&module) {
+                    let progress = job.progress_snapshot();
+                    let tally = tally
+                        .map(|tally| {
+                            std::mem::take(&mut *tally.lock().expect("audit tally poisoned"))
+                        })
+                        .unwrap_or_default();
+                    let record = AuditRecord {
+                        timestamp_unix_ms: crate::audit::now_unix_ms(),
+                        operation: match kind {
+                            ActiveJobKind::Push => "push",
+                            _ => "pull",
+                        }
+                        .into(),
+                        peer: peer_for_task.clone(),
+                        module,
+                        path,
+                        ok,
+                        error: err_msg.clone(),
+                        files: progress.files_completed,
+                        bytes: progress.bytes_completed,
+                        entries_deleted: tally.entries_deleted,
+                        paths: tally.paths,
+                    };
+                    crate::audit::append(log, &record);
+                }
+            }
             let finished_event = build_transfer_finished_event(&job, ok, err_msg.as_deref());
             drop(job);
             drop(guard);
@@ -781,6 +942,7 @@ impl Blit for BlitService {
                         size: metadata.len(),
                         mtime_seconds: blit_core::wire_metadata::mtime_seconds(&metadata)
                             .unwrap_or(0),
+                        permissions: blit_core::wire_metadata::permissions_mode(&metadata),
                     };
                     Ok(vec![info])
                 } else if metadata.is_dir() {
@@ -803,6 +965,7 @@ impl Blit for BlitService {
                             size: meta.len(),
                             mtime_seconds: blit_core::wire_metadata::mtime_seconds(&meta)
                                 .unwrap_or(0),
+                            permissions: blit_core::wire_metadata::permissions_mode(&meta),
                         });
                     }
                     infos.sort_by(|a, b| a.name.cmp(&b.name));
@@ -826,6 +989,7 @@ impl Blit for BlitService {
         &self,
         request: Request<PurgeRequest>,
     ) -> Result<Response<PurgeResponse>, Status> {
+        let peer = peer_addr_string(&request);
         let req = request.into_inner();
         // F5: counters mark dispatch attempts (matching push/pull
         // semantics). Previously inc_purge fired only after a
@@ -835,12 +999,39 @@ impl Blit for BlitService {
         // Pre-fix `purge_ops` was visible only on later push/pull
         // logs, never on the purge RPC itself.
         let started = std::time::Instant::now();
+        let audit = self.audit_targets().await;
+        let audited = audit.log_for(&req.module).map(|log| {
+            (
+                log.to_path_buf(),
+                req.module.clone(),
+                req.paths_to_delete.clone(),
+            )
+        });
         let result = self.purge_inner(req).await;
         let ok = result.is_ok();
         if result.is_err() {
             self.metrics.inc_error();
         }
         self.metrics.log_completion("purge", started.elapsed(), ok);
+        if let Some((log, module, paths)) = audited {
+            let record = AuditRecord {
+                timestamp_unix_ms: crate::audit::now_unix_ms(),
+                operation: "purge".into(),
+                peer,
+                module,
+                ok,
+                error: result
+                    .as_ref()
+                    .err()
+                    .map(|status| status.message().to_string()),
+                entries_deleted: result
+                    .as_ref()
+                    .map_or(0, |response| response.get_ref().files_deleted),
+                paths,
+                ..Default::default()
+            };
+            crate::audit::append(&log, &record);
+        }
         result
     }
 
@@ -896,6 +1087,50 @@ impl Blit for BlitService {
         Ok(Response::new(ListModulesResponse { modules }))
     }
 
+    async fn capabilities(
+        &self,
+        _request: Request<CapabilitiesRequest>,
+    ) -> Result<Response<CapabilitiesResponse>, Status> {
+        let policy = blit_core::remote::capabilities::DaemonPolicy {
+            force_grpc_data: self.force_grpc_data,
+            server_checksums_enabled: self.server_checksums_enabled,
+            delegation_enabled: self.delegation.allow_delegated_pull,
+        };
+        Ok(Response::new(blit_core::remote::capabilities::advertise(
+            policy,
+            self.motd.as_deref(),
+        )))
+    }
+
+    async fn health(
+        &self,
+        request: Request<HealthRequest>,
+    ) -> Result<Response<HealthResponse>, Status> {
+        let req = request.into_inner();
+        let active_transfers = u32::try_from(self.active_jobs.active_count()).unwrap_or(u32::MAX);
+        let mut response = HealthResponse {
+            ready: true,
+            active_transfers,
+            ..Default::default()
+        };
+        if !req.module.is_empty() {
+            let stats = match resolve_module(&self.modules, self.default_root.as_ref(), &req.module)
+                .await
+            {
+                Ok(module) => filesystem_stats_for_path(&module.path),
+                Err(status) => Err(status),
+            };
+            match stats {
+                Ok(stats) => response.free_bytes = stats.free_bytes,
+                Err(status) => {
+                    response.ready = false;
+                    response.reason = status.message().to_string();
+                }
+            }
+        }
+        Ok(Response::new(response))
+    }
+
     async fn get_state(
         &self,
         request: Request<GetStateRequest>,
@@ -1001,8 +1236,9 @@ impl Blit for BlitService {
     ) -> Result<Response<CancelJobResponse>, Status> {
         // Capture the caller's address before consuming the request:
         // audit-9 authorizes the cancel against the peer that started
-        // the transfer.
+        // the transfer, unless it carries the operator's admin token.
         let caller = request.remote_addr();
+        let admin = self.admin_token_presented(&request)?;
         let req = request.into_inner();
         if req.transfer_id.trim().is_empty() {
             return Err(Status::invalid_argument(
@@ -1013,7 +1249,12 @@ impl Blit for BlitService {
         // critical section is one `HashMap::get` + an IP comparison +
         // (when authorized and cancellable) one
         // `CancellationToken::cancel()`. No async work to do.
-        match self.active_jobs.cancel_authorized(&req.transfer_id, caller) {
+        let outcome = if admin {
+            self.active_jobs.cancel(&req.transfer_id)
+        } else {
+            self.active_jobs.cancel_authorized(&req.transfer_id, caller)
+        };
+        match outcome {
             CancelOutcome::Cancelled => Ok(Response::new(CancelJobResponse {
                 transfer_id: req.transfer_id,
             })),
@@ -1066,6 +1307,7 @@ impl Blit for BlitService {
         } else {
             Some(req.max_depth as usize)
         };
+        let filter = walk_exclude_filter(req.exclude)?;
 
         let (tx, rx): (
             DiskUsageSender,
@@ -1076,7 +1318,7 @@ impl Blit for BlitService {
         tokio::spawn(async move {
             let err_sender = tx.clone();
             let result = tokio::task::spawn_blocking(move || {
-                stream_disk_usage(module_root, start_rel, max_depth, &tx)
+                stream_disk_usage(module_root, start_rel, max_depth, filter, &tx)
             })
             .await;
 
@@ -1125,6 +1367,7 @@ impl Blit for BlitService {
         } else {
             Some(req.max_results as usize)
         };
+        let filter = walk_exclude_filter(req.exclude)?;
 
         tokio::spawn(async move {
             let err_sender = tx.clone();
@@ -1137,6 +1380,7 @@ impl Blit for BlitService {
                     include_files,
                     include_dirs,
                     max_results,
+                    filter,
                     &tx,
                 )
             })
@@ -1168,6 +1412,56 @@ impl Blit for BlitService {
 
         Ok(Response::new(stats))
     }
+
+    async fn checksum(
+        &self,
+        request: Request<ChecksumRequest>,
+    ) -> Result<Response<Self::ChecksumStream>, Status> {
+        if !self.server_checksums_enabled {
+            return Err(Status::failed_precondition(
+                "daemon-side checksums are disabled on this daemon (--no-server-checksums)",
+            ));
+        }
+        let req = request.into_inner();
+        if req.paths.is_empty() {
+            return Err(Status::invalid_argument("checksum request names no paths"));
+        }
+        let algorithm = match req.algorithm() {
+            ChecksumAlgorithm::Unspecified | ChecksumAlgorithm::Blake3 => ChecksumType::Blake3,
+            ChecksumAlgorithm::Sha256 => ChecksumType::Sha256,
+            ChecksumAlgorithm::Xxhash3 => ChecksumType::XxHash3,
+            ChecksumAlgorithm::Md5 => ChecksumType::Md5,
+        };
+        let module = resolve_module(&self.modules, self.default_root.as_ref(), &req.module).await?;
+        let rel_paths = req
+            .paths
+            .iter()
+            .map(|raw| resolve_relative_path(raw))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (tx, rx): (
+            ChecksumSender,
+            mpsc::Receiver<Result<ChecksumEntry, Status>>,
+        ) = mpsc::channel(32);
+        let module_root = module.path.clone();
+        let workers = self.checksum_workers;
+        tokio::spawn(async move {
+            if let Err(status) =
+                stream_checksums(module_root, rel_paths, algorithm, workers, &tx).await
+            {
+                let _ = tx.send(Err(status)).await;
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+/// Byte comparison whose running time does not depend on where the
+/// inputs first differ, so a probing client learns nothing about the
+/// admin token from response latency.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
 }
 
 /// Format the remote peer of a tonic request as `<ip>:<port>`,
@@ -1228,23 +1522,32 @@ where
 /// handler against client hangup and the row's `CancelJob` token via
 /// [`resolve_transfer_outcome`] (the w4-3 shape the deleted
 /// push/pull_sync dispatchers used). On `CancelJob` it emits a framed
-/// `SessionError{CANCELLED}` on the response stream instead of a bare
-/// `Status::cancelled` (otp-4a codex F1). The session speaks `TransferFrame`s, so the client reads the
-/// framed error — and the aborted session future can't send it itself
-/// once the select drops it, so the dispatcher does. A session that
-/// faults on its own already framed the reason; the trailing `Status`
-/// on that branch is belt-and-braces for a pre-frame transport break.
+/// `SessionError{CANCELLED}` instead of a bare `Status::cancelled`
+/// (otp-4a codex F1). The session speaks `TransferFrame`s, so the
+/// client reads the framed error — and the aborted session future can't
+/// send it itself once it is dropped, so the dispatcher does. A lane
+/// session's frame goes through `lane_terminator`, taken while the
+/// session still holds the lane open, so it reaches the RPC the client
+/// is re-attached over; otherwise it goes on the response stream `tx`.
+/// A session that faults on its own already framed the reason; the
+/// trailing `Status` on that branch is belt-and-braces for a pre-frame
+/// transport break.
 async fn resolve_transfer_session_outcome<H>(
     handler: H,
     tx: &mpsc::Sender<Result<blit_core::generated::TransferFrame, Status>>,
+    lane_terminator: Option<
+        &std::sync::OnceLock<mpsc::WeakSender<blit_core::generated::TransferFrame>>,
+    >,
     cancel_token: &CancellationToken,
     metrics: &TransferMetrics,
+    detach: bool,
 ) -> (bool, Option<String>)
 where
     H: std::future::Future<Output = Result<(), Status>>,
 {
+    let mut handler = Box::pin(handler);
     let outcome =
-        resolve_transfer_outcome(handler, tx.closed(), cancel_token.cancelled(), false).await;
+        resolve_transfer_outcome(&mut handler, tx.closed(), cancel_token.cancelled(), detach).await;
     match outcome {
         Some(result) => {
             let (ok, err_msg) = outcome_from_status(&result);
@@ -1255,12 +1558,22 @@ where
             (ok, err_msg)
         }
         None if cancel_token.is_cancelled() => {
-            let _ = tx
-                .send(Ok(blit_core::transfer_session::session_error_frame(
-                    blit_core::generated::session_error::Code::Cancelled,
-                    "transfer cancelled via CancelJob",
-                )))
-                .await;
+            let frame = blit_core::transfer_session::session_error_frame(
+                blit_core::generated::session_error::Code::Cancelled,
+                "transfer cancelled via CancelJob",
+            );
+            let lane = lane_terminator
+                .and_then(std::sync::OnceLock::get)
+                .and_then(mpsc::WeakSender::upgrade);
+            drop(handler);
+            match lane {
+                Some(lane) => {
+                    let _ = lane.send(frame).await;
+                }
+                None => {
+                    let _ = tx.send(Ok(frame)).await;
+                }
+            }
             (false, Some("cancelled via CancelJob".to_string()))
         }
         None => (false, Some("client cancelled".to_string())),
@@ -1342,7 +1655,8 @@ mod tests {
         let metrics = TransferMetrics::disabled();
         // A session that never completes on its own — cancel must win.
         let never = std::future::pending::<Result<(), Status>>();
-        let (ok, msg) = resolve_transfer_session_outcome(never, &tx, &cancel, &metrics).await;
+        let (ok, msg) =
+            resolve_transfer_session_outcome(never, &tx, None, &cancel, &metrics, false).await;
         assert!(!ok, "cancel is not a success");
         assert_eq!(msg.as_deref(), Some("cancelled via CancelJob"));
 
@@ -1361,6 +1675,49 @@ mod tests {
         }
     }
 
+    /// `--reconnect`: a lane session outlives the RPC that opened it, so
+    /// the `CancelJob` frame goes through the lane's session sender —
+    /// which forwards to whichever RPC the client re-attached over —
+    /// and never on the opening RPC's response stream. The handler here
+    /// holds the lane open the way a running session's transport does.
+    #[tokio::test]
+    async fn lane_session_cancel_is_framed_through_the_lane() {
+        use blit_core::generated::session_error::Code;
+        use blit_core::generated::transfer_frame::Frame as WireFrame;
+
+        let (tx, mut rx) = mpsc::channel::<Result<blit_core::generated::TransferFrame, Status>>(4);
+        let (lane_tx, mut lane_rx) = mpsc::channel(4);
+        let terminator = std::sync::OnceLock::new();
+        terminator.set(lane_tx.downgrade()).unwrap();
+        let session = async move {
+            let _lane_open = lane_tx;
+            std::future::pending::<Result<(), Status>>().await
+        };
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let metrics = TransferMetrics::disabled();
+        let (ok, msg) = resolve_transfer_session_outcome(
+            session,
+            &tx,
+            Some(&terminator),
+            &cancel,
+            &metrics,
+            true,
+        )
+        .await;
+        assert!(!ok);
+        assert_eq!(msg.as_deref(), Some("cancelled via CancelJob"));
+
+        match lane_rx.recv().await.expect("a frame on the lane").frame {
+            Some(WireFrame::Error(err)) => assert_eq!(err.code, Code::Cancelled as i32),
+            other => panic!("expected a CANCELLED error frame, got {other:?}"),
+        }
+        assert!(
+            rx.try_recv().is_err(),
+            "the opening RPC's stream must not carry the cancel"
+        );
+    }
+
     /// audit-10 / m-jobs-3: with `detach = true` the client-hangup branch
     /// is disabled, so a closed tx must NOT terminate the pull.
     #[tokio::test]
@@ -1398,8 +1755,10 @@ mod tests {
         let (ok, err) = resolve_transfer_session_outcome(
             pending::<Result<(), Status>>(),
             &tx,
+            None,
             &token,
             &metrics,
+            false,
         )
         .await;
         assert!(!ok, "a hangup-terminated transfer must record ok=false");
@@ -1419,7 +1778,8 @@ mod tests {
         token.cancel();
         let metrics = TransferMetrics::disabled();
         let (ok, err) =
-            resolve_transfer_session_outcome(ready(Ok(())), &tx, &token, &metrics).await;
+            resolve_transfer_session_outcome(ready(Ok(())), &tx, None, &token, &metrics, false)
+                .await;
         assert!(ok, "a completed handler must beat simultaneous cancels");
         assert_eq!(err, None);
     }
@@ -1436,8 +1796,10 @@ mod tests {
         let (ok, err) = resolve_transfer_session_outcome(
             ready(Err(Status::internal("boom"))),
             &tx,
+            None,
             &token,
             &metrics,
+            false,
         )
         .await;
         assert!(!ok);
@@ -1461,7 +1823,8 @@ mod tests {
         let token = CancellationToken::new();
         let metrics = TransferMetrics::disabled();
         let (ok, err) =
-            resolve_transfer_session_outcome(ready(Ok(())), &tx, &token, &metrics).await;
+            resolve_transfer_session_outcome(ready(Ok(())), &tx, None, &token, &metrics, false)
+                .await;
         assert!(ok);
         assert!(err.is_none());
         drop(tx);
@@ -1491,6 +1854,68 @@ mod tests {
         assert_eq!(counters.transfer_errors_total, 0);
     }
 
+    #[tokio::test]
+    async fn capabilities_report_identity_motd_and_operator_policy() {
+        use blit_core::remote::capabilities::{
+            FEATURE_DELEGATED_PULL, FEATURE_SERVER_CHECKSUMS, FEATURE_TCP_DATA_PLANE,
+        };
+        let svc = BlitService::with_modules(HashMap::new(), true)
+            .with_motd(Some("welcome to the archive".into()));
+        let caps = svc
+            .capabilities(Request::new(CapabilitiesRequest {}))
+            .await
+            .expect("capabilities ok")
+            .into_inner();
+        assert_eq!(caps.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            caps.build_id,
+            blit_core::transfer_session::session_build_id()
+        );
+        assert_eq!(
+            caps.contract_version,
+            blit_core::transfer_session::CONTRACT_VERSION
+        );
+        assert_eq!(caps.motd, "welcome to the archive");
+        // --force-grpc-data withdraws the data plane; checksums stay on,
+        // delegation is default-off.
+        assert!(!caps.features.iter().any(|f| f == FEATURE_TCP_DATA_PLANE));
+        assert!(caps.features.iter().any(|f| f == FEATURE_SERVER_CHECKSUMS));
+        assert!(!caps.features.iter().any(|f| f == FEATURE_DELEGATED_PULL));
+    }
+
+    #[tokio::test]
+    async fn health_reports_ready_active_count_and_module_free_space() {
+        let tmp = tempfile::tempdir().unwrap();
+        let svc = checksum_service(tmp.path(), true);
+        let probe = |module: &str| {
+            svc.health(Request::new(HealthRequest {
+                module: module.into(),
+            }))
+        };
+
+        let idle = probe("").await.expect("health ok").into_inner();
+        assert!(idle.ready);
+        assert_eq!(idle.active_transfers, 0);
+        assert_eq!(idle.free_bytes, 0);
+
+        let _guard = svc.active_jobs.register(
+            ActiveJobKind::Push,
+            "10.0.0.5:443".to_string(),
+            "test".to_string(),
+            String::new(),
+        );
+        let busy = probe("test").await.expect("health ok").into_inner();
+        assert!(busy.ready, "{}", busy.reason);
+        assert_eq!(busy.active_transfers, 1);
+        assert!(busy.free_bytes > 0);
+
+        // An unknown module is a not-ready answer, not an RPC error.
+        let missing = probe("nope").await.expect("health ok").into_inner();
+        assert!(!missing.ready);
+        assert_eq!(missing.active_transfers, 1);
+        assert!(missing.reason.contains("not found"), "{}", missing.reason);
+    }
+
     #[tokio::test]
     async fn get_state_surfaces_live_active_row_and_recent_row() {
         let svc = empty_service();
@@ -1666,6 +2091,83 @@ mod tests {
         }
     }
 
+    /// An operator on another host sees a started transfer in
+    /// `GetState.active[]` and can cancel it only by presenting the
+    /// daemon's admin token; a wrong token is refused outright.
+    #[tokio::test]
+    async fn admin_token_cancels_a_transfer_started_from_another_host() {
+        let svc = empty_service().with_admin_token(Some("s3cret".into()));
+        let guard = svc.active_jobs.register(
+            ActiveJobKind::Push,
+            "10.0.0.5:40000".to_string(),
+            "archive".to_string(),
+            "photos".to_string(),
+        );
+        let id = guard.transfer_id().to_string();
+        let token = guard.cancellation_token().clone();
+
+        let state = svc
+            .get_state(Request::new(GetStateRequest { recent_limit: 0 }))
+            .await
+            .expect("get_state ok")
+            .into_inner();
+        let row = state
+            .active
+            .iter()
+            .find(|row| row.transfer_id == id)
+            .expect("the started transfer is listed");
+        assert_eq!(row.module, "archive");
+        assert_eq!(row.peer, "10.0.0.5:40000");
+        assert!(row.start_unix_ms > 0);
+
+        let cancel_from = |token: Option<&str>| {
+            let mut request = Request::new(CancelJobRequest {
+                transfer_id: id.clone(),
+            });
+            request
+                .extensions_mut()
+                .insert(tonic::transport::server::TcpConnectInfo {
+                    local_addr: None,
+                    remote_addr: Some("10.0.0.9:50000".parse().unwrap()),
+                });
+            if let Some(token) = token {
+                request
+                    .metadata_mut()
+                    .insert(ADMIN_TOKEN_METADATA, token.parse().unwrap());
+            }
+            request
+        };
+
+        let err = svc.cancel_job(cancel_from(None)).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+        let err = svc
+            .cancel_job(cancel_from(Some("guess")))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+        assert!(!token.is_cancelled());
+
+        svc.cancel_job(cancel_from(Some("s3cret")))
+            .await
+            .expect("the admin token authorizes the cancel");
+        assert!(token.is_cancelled());
+        drop(guard);
+    }
+
+    #[tokio::test]
+    async fn admin_token_is_refused_by_a_daemon_without_one() {
+        let svc = empty_service();
+        let mut request = Request::new(CancelJobRequest {
+            transfer_id: "any".into(),
+        });
+        request
+            .metadata_mut()
+            .insert(ADMIN_TOKEN_METADATA, "s3cret".parse().unwrap());
+        let err = svc.cancel_job(request).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+        assert!(err.message().contains("admin_token"), "{}", err.message());
+    }
+
     #[tokio::test]
     async fn cancel_job_failed_precondition_for_history_only_pull() {
         // The one kind still gated off by dispatch policy (`Pull` rows
@@ -2652,4 +3154,159 @@ mod tests {
         assert!(!state.recent[0].ok);
         assert_eq!(state.recent[0].error_message, "module not found");
     }
+
+    fn checksum_service(root: &std::path::Path, server_checksums_enabled: bool) -> BlitService {
+        let mut modules = HashMap::new();
+        modules.insert(
+            "test".to_string(),
+            ModuleConfig {
+                name: "test".into(),
+                path: root.to_path_buf(),
+                canonical_root: root.to_path_buf(),
+                read_only: true,
+                _comment: None,
+                delegation_allowed: false,
+                default_subpath: None,
+                dedupe_store: None,
+                fake_super: false,
+                audit_log: None,
+                allowed_subpaths: Vec::new(),
+            },
+        );
+        BlitService::from_runtime(
+            modules,
+            None,
+            false,
+            server_checksums_enabled,
+            TransferMetrics::disabled(),
+            crate::delegation_gate::DelegationConfig::default(),
+        )
+    }
+
+    async fn checksum_rows(
+        svc: &BlitService,
+        paths: &[&str],
+        algorithm: ChecksumAlgorithm,
+    ) -> Result<Vec<ChecksumEntry>, Status> {
+        let mut stream = svc
+            .checksum(Request::new(ChecksumRequest {
+                module: "test".into(),
+                paths: paths.iter().map(|p| p.to_string()).collect(),
+                algorithm: algorithm as i32,
+            }))
+            .await?
+            .into_inner();
+        let mut rows = Vec::new();
+        while let Some(row) = tokio_stream::StreamExt::next(&mut stream).await {
+            rows.push(row?);
+        }
+        Ok(rows)
+    }
+
+    #[tokio::test]
+    async fn checksum_rpc_hashes_module_files_with_the_requested_algorithm() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = fs::canonicalize(tmp.path()).unwrap();
+        fs::create_dir(root.join("docs")).unwrap();
+        fs::write(root.join("docs/abc.txt"), b"abc").unwrap();
+        fs::write(root.join("empty"), b"").unwrap();
+        let svc = checksum_service(&root, true);
+
+        let mut rows = checksum_rows(&svc, &["docs/abc.txt", "empty"], ChecksumAlgorithm::Blake3)
+            .await
+            .expect("blake3 digests");
+        rows.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+        let paths: Vec<_> = rows.iter().map(|r| r.relative_path.as_str()).collect();
+        assert_eq!(paths, ["docs/abc.txt", "empty"]);
+        assert_eq!(
+            hex(&rows[0].digest),
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+        assert_eq!(rows[0].size, 3);
+        assert_eq!(rows[1].digest, blake3::hash(b"").as_bytes());
+
+        let rows = checksum_rows(&svc, &["docs/abc.txt"], ChecksumAlgorithm::Sha256)
+            .await
+            .expect("sha-256 digest");
+        assert_eq!(
+            hex(&rows[0].digest),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        // UNSPECIFIED means BLAKE3.
+        let rows = checksum_rows(&svc, &["docs/abc.txt"], ChecksumAlgorithm::Unspecified)
+            .await
+            .expect("default digest");
+        assert_eq!(rows[0].digest, blake3::hash(b"abc").as_bytes());
+    }
+
+    #[tokio::test]
+    async fn checksum_rpc_refuses_missing_paths_directories_and_disabled_daemons() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = fs::canonicalize(tmp.path()).unwrap();
+        fs::create_dir(root.join("docs")).unwrap();
+        fs::write(root.join("docs/abc.txt"), b"abc").unwrap();
+        let svc = checksum_service(&root, true);
+
+        let err = checksum_rows(&svc, &["docs/missing.txt"], ChecksumAlgorithm::Blake3)
+            .await
+            .expect_err("missing path");
+        assert_eq!(err.code(), tonic::Code::NotFound, "{err:?}");
+        assert!(err.message().contains("docs/missing.txt"), "{err:?}");
+
+        let err = checksum_rows(&svc, &["docs"], ChecksumAlgorithm::Blake3)
+            .await
+            .expect_err("directory");
+        assert_eq!(err.code(), tonic::Code::InvalidArgument, "{err:?}");
+
+        let err = checksum_rows(&svc, &["../escape"], ChecksumAlgorithm::Blake3)
+            .await
+            .expect_err("traversal");
+        assert_eq!(err.code(), tonic::Code::InvalidArgument, "{err:?}");
+
+        let disabled = checksum_service(&root, false);
+        let err = checksum_rows(&disabled, &["docs/abc.txt"], ChecksumAlgorithm::Blake3)
+            .await
+            .expect_err("hashing disabled");
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition, "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn checksum_rpc_hashes_many_files_concurrently_keeping_digests_with_their_paths() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = fs::canonicalize(tmp.path()).unwrap();
+        fs::create_dir(root.join("many")).unwrap();
+        // Sizes vary so workers finish out of request order.
+        let mut expected = HashMap::new();
+        for i in 0..200usize {
+            let rel = format!("many/f{i:03}.bin");
+            let body: Vec<u8> = (0..(i * 997) % 300_000).map(|b| (b ^ i) as u8).collect();
+            fs::write(root.join(&rel), &body).unwrap();
+            expected.insert(rel, (blake3::hash(&body).as_bytes().to_vec(), body.len()));
+        }
+        let mut request: Vec<&str> = expected.keys().map(String::as_str).collect();
+        request.sort();
+        let svc = checksum_service(&root, true).with_checksum_workers(4);
+
+        let rows = checksum_rows(&svc, &request, ChecksumAlgorithm::Blake3)
+            .await
+            .expect("every file hashes");
+        assert_eq!(rows.len(), expected.len());
+        for row in rows {
+            let (digest, size) = expected
+                .remove(&row.relative_path)
+                .unwrap_or_else(|| panic!("unexpected or repeated {}", row.relative_path));
+            assert_eq!(row.digest, digest, "{}", row.relative_path);
+            assert_eq!(row.size, size as u64, "{}", row.relative_path);
+        }
+        assert!(
+            expected.is_empty(),
+            "missing entries: {:?}",
+            expected.keys()
+        );
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
 }
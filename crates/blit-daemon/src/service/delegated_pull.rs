@@ -14,7 +14,7 @@
 //! the session reroute.
 
 use std::future::Future;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -41,7 +41,9 @@ use crate::active_jobs::{ActiveJobProgress, JobProgressSnapshot};
 use crate::delegation_gate::{validate_source, GateDenial, HostResolver, LocatorView, StdResolver};
 use crate::metrics::TransferMetrics;
 use crate::runtime::{ModuleConfig, RootExport};
-use crate::service::util::{resolve_contained_path, resolve_module};
+use crate::service::util::{
+    default_destination, enforce_allowed_subpaths, resolve_contained_path, resolve_module,
+};
 
 /// Validate the wire spec via the same `NormalizedTransferOperation::from_spec`
 /// boundary that push and pull_sync use (R30-F3). Catches bad
@@ -100,6 +102,7 @@ fn session_error_phase(err: &eyre::Report) -> blit_core::generated::delegated_pu
             | session_error::Code::ReadOnly
             | session_error::Code::DelegationRefused
             | session_error::Code::ScanIncomplete
+            | session_error::Code::FileLimit
             // otp-10b-1: refused at OPEN, before any transfer work.
             | session_error::Code::ChecksumDisabled,
         ) => Phase::Negotiate,
@@ -329,13 +332,18 @@ async fn run_delegated_pull<R: HostResolver + ?Sized>(
     }
 
     // Step 6: F2 canonical-path containment on dst_destination_path.
-    let dst_rel = if req.dst_destination_path.trim().is_empty() {
-        PathBuf::from(".")
+    // A bare module lands under its `default_subpath`, if configured.
+    let dest_root = if req.dst_destination_path.trim().is_empty() {
+        match default_destination(&module) {
+            Ok(Some(root)) => Ok(root),
+            Ok(None) => resolve_contained_path(&module, Path::new(".")),
+            Err(status) => Err(status),
+        }
     } else {
-        PathBuf::from(&req.dst_destination_path)
-    };
-    let dest_root = resolve_contained_path(&module, &dst_rel)
-        .map_err(|status| err_progress(Phase::Apply as i32, status.message().to_string()))?;
+        resolve_contained_path(&module, Path::new(&req.dst_destination_path))
+    }
+    .and_then(|root| enforce_allowed_subpaths(&module, &root).map(|()| root))
+    .map_err(|status| err_progress(Phase::Apply as i32, status.message().to_string()))?;
 
     // Step 7: metrics RAII. inc_pull because — from this daemon's
     // perspective — the body of work is a pull from src.
@@ -420,12 +428,32 @@ async fn run_delegated_pull<R: HostResolver + ?Sized>(
         compare_mode: ComparisonMode::try_from(spec.compare_mode)
             .unwrap_or(ComparisonMode::SizeMtime),
         ignore_existing: spec.ignore_existing,
+        // The delegated spec carries no metadata-only, existing-only,
+        // file-cap, checksum-seed, chmod, chown, xattrs or remote-option
+        // field; the CLI refuses all eight for remote→remote.
+        existing_only: false,
+        max_files: 0,
+        checksum_seed: 0,
+        chmod: String::new(),
+        chown: String::new(),
+        xattrs: String::new(),
+        remote_options: Default::default(),
+        metadata_only: false,
         require_complete_scan: spec.require_complete_scan,
         drop_windows_metadata: spec.drop_windows_metadata,
+        // The operation spec carries no `--crtimes`; the CLI refuses it
+        // on the delegated route.
+        preserve_crtimes: false,
+        // Nor `--prune-empty-dirs` or `--super`; refused the same way.
+        prune_empty_dirs: false,
+        preserve_owner: false,
+        fake_super: false,
         in_stream_bytes: spec.force_grpc,
         resume: spec.resume.as_ref().is_some_and(|r| r.enabled),
         resume_block_size: spec.resume.as_ref().map_or(0, |r| r.block_size),
         filter: spec.filter.clone(),
+        rename: spec.rename.clone(),
+        dial_request: spec.dial_request,
         mirror_enabled: mirror_active,
         mirror_kind: if mirror_active {
             MirrorMode::try_from(spec.mirror_mode).unwrap_or(MirrorMode::Off)
@@ -436,6 +464,12 @@ async fn run_delegated_pull<R: HostResolver + ?Sized>(
         progress: None,
         trace_data_plane: false,
         lifecycle_trace: lifecycle_trace.clone(),
+        // The operation spec carries no reconnect window either.
+        reconnect: None,
+        // core.rs owns cancellation for this future (see above).
+        cancel: None,
+        // The module root is always a directory tree.
+        receive_sink: None,
     };
     let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
     let mut options = options;
@@ -640,6 +674,8 @@ mod tests {
             ignore_existing: false,
             require_complete_scan: false,
             drop_windows_metadata: false,
+            rename: Vec::new(),
+            dial_request: None,
         }
     }
 
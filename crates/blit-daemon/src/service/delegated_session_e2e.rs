@@ -54,6 +54,11 @@ impl Daemon {
                 read_only: false,
                 _comment: None,
                 delegation_allowed: true,
+                default_subpath: None,
+                dedupe_store: None,
+                fake_super: false,
+                audit_log: None,
+                allowed_subpaths: Vec::new(),
             },
         );
         let delegation = DelegationConfig {
@@ -78,7 +83,7 @@ impl Daemon {
         let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
         let server = tokio::spawn(async move {
             blit_core::remote::grpc_server::production_server_builder()
-                .add_service(blit_core::generated::blit_server::BlitServer::new(service))
+                .add_service(blit_core::remote::message_limits::blit_server(service))
                 .serve_with_incoming_shutdown(
                     tokio_stream::wrappers::TcpListenerStream::new(listener),
                     async {
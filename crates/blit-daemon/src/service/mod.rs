@@ -8,11 +8,12 @@ mod transfer;
 mod transfer_session_e2e;
 mod util;
 
-pub use core::{spawn_progress_ticker, BlitServer, BlitService};
+pub use core::{spawn_progress_ticker, BlitService};
 
-use blit_core::generated::{DiskUsageEntry, FindEntry};
+use blit_core::generated::{ChecksumEntry, DiskUsageEntry, FindEntry};
 use tokio::sync::mpsc;
 use tonic::Status;
 
 pub(crate) type FindSender = mpsc::Sender<Result<FindEntry, Status>>;
 pub(crate) type DiskUsageSender = mpsc::Sender<Result<DiskUsageEntry, Status>>;
+pub(crate) type ChecksumSender = mpsc::Sender<Result<ChecksumEntry, Status>>;
@@ -25,6 +25,7 @@
 //! progress through a small relay, so either served role reports live bytes.
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use tokio::sync::mpsc;
@@ -33,7 +34,8 @@ use tonic::{Status, Streaming};
 
 use blit_core::generated::session_error::Code;
 use blit_core::generated::{SessionOpen, TransferFrame};
-use blit_core::remote::transfer::{ByteProgressSink, RemoteTransferProgress};
+use blit_core::remote::transfer::{ByteProgressSink, ProgressEvent, RemoteTransferProgress};
+use blit_core::transfer_session::lane::LaneRegistry;
 use blit_core::transfer_session::transport::grpc_daemon_transport;
 use blit_core::transfer_session::{
     run_responder, DestinationInstruments, DestinationTarget, HelloConfig, OpenResolver,
@@ -41,9 +43,13 @@ use blit_core::transfer_session::{
     SourceResponderTarget,
 };
 
-use super::util::{resolve_contained_path, resolve_module, resolve_relative_path};
+use super::util::{
+    default_destination, enforce_allowed_subpaths, resolve_contained_path, resolve_module,
+    resolve_relative_path,
+};
 use crate::active_jobs::ActiveJobKind;
 use crate::active_jobs::ActiveJobProgress;
+use crate::audit::SessionTally;
 use crate::runtime::{ModuleConfig, RootExport};
 
 /// The dispatcher's open hook (codex otp-10b-2 F4): called exactly once
@@ -77,6 +83,39 @@ fn with_open_hook(
     })
 }
 
+/// Where a push's finished files are folded in: the resolved
+/// destination root and its module's `dedupe_store`.
+type DedupeTarget = (PathBuf, PathBuf);
+
+/// Wrap the DESTINATION resolver so a resolved open into a module with a
+/// `dedupe_store` records where the session writes; the session's
+/// outcome then says which files to fold into the store.
+fn with_dedupe_capture(
+    inner: Box<OpenResolver>,
+    modules: Arc<Mutex<HashMap<String, ModuleConfig>>>,
+    default_root: Option<RootExport>,
+    slot: Arc<std::sync::Mutex<Option<DedupeTarget>>>,
+) -> Box<OpenResolver> {
+    Box::new(move |open: &SessionOpen| {
+        let fut = inner(open);
+        let modules = Arc::clone(&modules);
+        let default_root = default_root.clone();
+        let slot = Arc::clone(&slot);
+        let module_name = open.module.clone();
+        Box::pin(async move {
+            let resolved = fut.await?;
+            if let Ok(config) = resolve_module(&modules, default_root.as_ref(), &module_name).await
+            {
+                if let Some(store) = config.dedupe_store {
+                    *slot.lock().expect("dedupe slot poisoned") =
+                        Some((resolved.root.clone(), store));
+                }
+            }
+            Ok(resolved)
+        })
+    })
+}
+
 /// Map a resolver `tonic::Status` onto a `SessionError` code. blit-core
 /// is deliberately `Status`-free, so the daemon picks the wire code:
 /// an unknown module is `MODULE_UNKNOWN`, a bad or escaping path is a
@@ -93,14 +132,20 @@ fn status_to_fault(status: Status) -> SessionFault {
 
 /// Build the daemon's [`OpenResolver`]: given a received `SessionOpen`,
 /// resolve its module + path to an absolute local root and report the
-/// module's read-only flag. Mirrors the push Header sequence
-/// (`resolve_module` → path validation → F2 canonical containment via
-/// `resolve_contained_path`), refusing with a `SessionError` instead of
-/// a `tonic::Status`. The closure is `Fn` (callable once per session)
+/// module's read-only and `fake_super` flags. Mirrors the push Header
+/// sequence (`resolve_module` → path validation → F2 canonical
+/// containment via `resolve_contained_path`), refusing with a
+/// `SessionError` instead of a `tonic::Status`. The closure is `Fn` (callable once per session)
 /// and clones its captured handles per call so it stays `Send + Sync`.
+///
+/// `writes` marks the DESTINATION resolver: an empty path there lands
+/// under the module's `default_subpath` when it has one, and the root
+/// must fall inside the module's `allowed_subpaths`. Reads keep the
+/// module root.
 pub(crate) fn make_open_resolver(
     modules: Arc<Mutex<HashMap<String, ModuleConfig>>>,
     default_root: Option<RootExport>,
+    writes: bool,
 ) -> Box<OpenResolver> {
     Box::new(move |open: &SessionOpen| {
         let modules = Arc::clone(&modules);
@@ -115,20 +160,41 @@ pub(crate) fn make_open_resolver(
             // validated and contained against the module's canonical
             // root (F2 symlink-escape protection — the same chokepoint
             // the per-file write path uses).
-            let root = if wire_path.is_empty() {
+            let default_destination = if writes && wire_path.is_empty() {
+                default_destination(&config).map_err(status_to_fault)?
+            } else {
+                None
+            };
+            let root = if let Some(root) = default_destination {
+                root
+            } else if wire_path.is_empty() {
                 config.path.clone()
             } else {
                 let rel = resolve_relative_path(&wire_path).map_err(status_to_fault)?;
                 resolve_contained_path(&config, &rel).map_err(status_to_fault)?
             };
+            if writes {
+                enforce_allowed_subpaths(&config, &root).map_err(status_to_fault)?;
+            }
             Ok(ResolvedEndpoint {
                 root,
                 read_only: config.read_only,
+                fake_super: config.fake_super,
             })
         })
     })
 }
 
+/// A served session's resumable lane: the daemon's registry it joins,
+/// and the slot it leaves its session sender in once accepted. The lane
+/// outlives the `Transfer` RPC that opened it, so the dispatcher frames
+/// a `CancelJob` through this sender — which reaches the RPC the client
+/// is attached over now — rather than the opening RPC's response stream.
+pub(crate) struct ServedLane {
+    pub(crate) registry: LaneRegistry,
+    pub(crate) terminator: Arc<std::sync::OnceLock<mpsc::WeakSender<TransferFrame>>>,
+}
+
 /// Run one daemon-side transfer session to completion, dispatching on
 /// the client's declared initiator role via [`run_responder`]: a SOURCE
 /// initiator makes the daemon the DESTINATION (push-equivalent, otp-4);
@@ -144,6 +210,9 @@ pub(crate) async fn run_transfer_session(
     default_root: Option<RootExport>,
     inbound: Streaming<TransferFrame>,
     tx: mpsc::Sender<Result<TransferFrame, Status>>,
+    // `--reconnect` (contract v17): the session rides a resumable lane
+    // held in this registry.
+    lanes: Option<ServedLane>,
     // Operator policy from the daemon runtime config: `--force-grpc-data`
     // (codex otp-10a F3) and `--no-server-checksums` (otp-10b-1) apply
     // to served sessions exactly as they did to the old handlers.
@@ -153,20 +222,39 @@ pub(crate) async fn run_transfer_session(
     // Fires once at a successful open resolve with this session's job
     // kind + endpoint (codex otp-10b-2 F4).
     on_open: Arc<OnSessionOpen>,
+    // Audited daemons only: the files this session sent or wrote and
+    // its delete count, for the audit record (`crate::audit`).
+    tally: Option<Arc<std::sync::Mutex<SessionTally>>>,
 ) -> Result<(), Status> {
     let transport = grpc_daemon_transport(tx, inbound);
+    let (transport, lane) = match lanes {
+        Some(lanes) => {
+            let (transport, lane) = lanes
+                .registry
+                .respond(transport)
+                .await
+                .map_err(|err| Status::invalid_argument(format!("{err:#}")))?;
+            let _ = lanes.terminator.set(lane.weak_terminator());
+            (transport, Some(lane))
+        }
+        None => (transport, None),
+    };
     let (source_progress_tx, mut source_progress_rx) = mpsc::unbounded_channel();
     let source_job_progress = job_progress.clone();
+    let source_tally = tally.clone();
     let relay_source_bytes = async move {
         while let Some(event) = source_progress_rx.recv().await {
             source_job_progress.report_source_event(&event);
+            tally_file(source_tally.as_ref(), &event);
         }
     };
     let (destination_progress_tx, mut destination_progress_rx) = mpsc::unbounded_channel();
     let destination_job_progress = job_progress.clone();
+    let destination_tally = tally.clone();
     let relay_destination_progress = async move {
         while let Some(event) = destination_progress_rx.recv().await {
             destination_job_progress.report_destination_event(&event);
+            tally_file(destination_tally.as_ref(), &event);
         }
     };
     // The same module→root resolver serves both roles; only the one the
@@ -177,12 +265,18 @@ pub(crate) async fn run_transfer_session(
     // client pulls — the old PullSync verbs' kind), a consulted
     // dest-resolver means the daemon receives (push-equivalent).
     let source_resolver = with_open_hook(
-        make_open_resolver(Arc::clone(&modules), default_root.clone()),
+        make_open_resolver(Arc::clone(&modules), default_root.clone(), false),
         Arc::clone(&on_open),
         ActiveJobKind::PullSync,
     );
+    let dedupe_target = Arc::new(std::sync::Mutex::new(None));
     let dest_resolver = with_open_hook(
-        make_open_resolver(modules, default_root),
+        with_dedupe_capture(
+            make_open_resolver(Arc::clone(&modules), default_root.clone(), true),
+            modules,
+            default_root,
+            Arc::clone(&dedupe_target),
+        ),
         on_open,
         ActiveJobKind::Push,
     );
@@ -209,6 +303,13 @@ pub(crate) async fn run_transfer_session(
         relay_source_bytes,
         relay_destination_progress,
     );
+    // A finished session's lane still owes the client its close; a
+    // failed one is left to wind down on its own.
+    if let (Ok(_), Some(lane)) = (&outcome, lane) {
+        if let Err(err) = lane.finish().await {
+            log::warn!("control lane did not close cleanly: {err:#}");
+        }
+    }
     match outcome {
         // Either role completing cleanly is a successful transfer; the
         // daemon record does not distinguish push- from pull-equivalent
@@ -218,9 +319,17 @@ pub(crate) async fn run_transfer_session(
             let summary = match outcome {
                 blit_core::transfer_session::ResponderOutcome::Source(summary) => summary,
                 blit_core::transfer_session::ResponderOutcome::Destination(outcome) => {
+                    let target = dedupe_target.lock().expect("dedupe slot poisoned").take();
+                    if let Some((root, store)) = target {
+                        absorb_into_store(store, root, outcome.needed_paths).await;
+                    }
                     outcome.summary
                 }
             };
+            if let Some(tally) = &tally {
+                tally.lock().expect("audit tally poisoned").entries_deleted =
+                    summary.entries_deleted;
+            }
             job_progress.finish(
                 summary.files_transferred,
                 summary.bytes_transferred,
@@ -240,6 +349,38 @@ pub(crate) async fn run_transfer_session(
     }
 }
 
+/// Record a finished file in the session's audit tally, if it has one.
+fn tally_file(tally: Option<&Arc<std::sync::Mutex<SessionTally>>>, event: &ProgressEvent) {
+    if let (Some(tally), ProgressEvent::FileComplete { path }) = (tally, event) {
+        tally
+            .lock()
+            .expect("audit tally poisoned")
+            .paths
+            .push(path.clone());
+    }
+}
+
+/// Fold a finished push's files into its module's dedupe store. The
+/// pass runs before the session's outcome is recorded, so a job that
+/// reads as finished has already been deduplicated; per-file failures
+/// only leave that file unshared (see [`crate::dedupe::absorb`]).
+async fn absorb_into_store(store: PathBuf, root: PathBuf, paths: Vec<String>) {
+    if paths.is_empty() {
+        return;
+    }
+    let joined =
+        tokio::task::spawn_blocking(move || crate::dedupe::absorb(&store, &root, &paths)).await;
+    match joined {
+        Ok(stats) => log::info!(
+            "dedupe: {} new blob(s), {} file(s) linked, {} byte(s) saved",
+            stats.blobs_added,
+            stats.files_linked,
+            stats.bytes_saved
+        ),
+        Err(err) => log::warn!("dedupe pass failed: {err}"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -37,15 +37,17 @@ use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use blit_core::fs_enum::FileFilter;
-use blit_core::generated::blit_server::BlitServer;
-use blit_core::generated::{session_error, ComparisonMode};
+use blit_core::generated::{session_error, ComparisonMode, DialRequest};
+use blit_core::remote::capabilities;
+use blit_core::remote::transfer::archive_source::ArchiveTransferSource;
 use blit_core::remote::transfer::session_client::{
-    connect_transfer_client_with_trace, run_pull_session, run_push_session, PullSessionOptions,
-    PushSessionOptions,
+    connect_transfer_client, connect_transfer_client_with_trace, run_pull_session,
+    run_push_session, PullSessionOptions, PushSessionOptions,
 };
 use blit_core::remote::transfer::source::FsTransferSource;
 use blit_core::remote::transfer::{
-    SessionPhaseRole, TransferLifecycleEvent, TransferLifecycleOutcome, TransferLifecycleTrace,
+    ProgressTotals, RemoteTransferProgress, SessionPhaseRole, TransferLifecycleEvent,
+    TransferLifecycleOutcome, TransferLifecycleTrace,
 };
 use blit_core::remote::{RemoteEndpoint, RemotePath};
 use blit_core::transfer_session::SessionFault;
@@ -71,47 +73,112 @@ struct Daemon {
 
 impl Daemon {
     async fn start(read_only: bool) -> Self {
-        Self::start_with(read_only, true).await
+        Self::start_with(read_only, true, false, "127.0.0.1", |_| {}).await
     }
 
     /// otp-10b-1: variant for a daemon whose operator disabled
     /// server-side checksum hashing (`--no-server-checksums`).
     async fn start_with_checksums_disabled() -> Self {
-        Self::start_with(false, false).await
+        Self::start_with(false, false, false, "127.0.0.1", |_| {}).await
     }
 
-    async fn start_with(read_only: bool, server_checksums_enabled: bool) -> Self {
+    /// Control plane on IPv6 loopback only. The session's data-plane
+    /// listener binds IPv4, so the grant this daemon issues is
+    /// unreachable from the client — a firewalled data port in miniature.
+    /// `None` where the host has no IPv6 loopback to listen on.
+    async fn start_with_unreachable_data_port() -> Option<Self> {
+        std::net::TcpListener::bind("[::1]:0").ok()?;
+        Some(Self::start_with(false, true, false, "::1", |_| {}).await)
+    }
+
+    /// A daemon run with `--force-grpc-data`: it grants no TCP data
+    /// plane and its `Capabilities` say so.
+    async fn start_forcing_grpc_data() -> Self {
+        Self::start_with(false, true, true, "127.0.0.1", |_| {}).await
+    }
+
+    /// Module "test" configured with `default_subpath = template`.
+    async fn start_with_default_subpath(template: &str) -> Self {
+        Self::start_with(false, true, false, "127.0.0.1", |module| {
+            module.default_subpath = Some(template.into())
+        })
+        .await
+    }
+
+    /// Module "test" configured with `allowed_subpaths = subpaths`.
+    async fn start_with_allowed_subpaths(subpaths: &[&str]) -> Self {
+        Self::start_with(false, true, false, "127.0.0.1", |module| {
+            module.allowed_subpaths = subpaths.iter().map(PathBuf::from).collect()
+        })
+        .await
+    }
+
+    /// Module "test" configured with `dedupe_store = store`.
+    async fn start_with_dedupe_store(store: &Path) -> Self {
+        Self::start_with(false, true, false, "127.0.0.1", |module| {
+            module.dedupe_store = Some(store.to_path_buf())
+        })
+        .await
+    }
+
+    /// Module "test" configured with `fake_super = true`.
+    async fn start_with_fake_super() -> Self {
+        Self::start_with(false, true, false, "127.0.0.1", |module| {
+            module.fake_super = true
+        })
+        .await
+    }
+
+    /// Module "test" configured with `audit_log = log`.
+    async fn start_with_audit_log(log: &Path) -> Self {
+        Self::start_with(false, true, false, "127.0.0.1", |module| {
+            module.audit_log = Some(log.to_path_buf())
+        })
+        .await
+    }
+
+    async fn start_with(
+        read_only: bool,
+        server_checksums_enabled: bool,
+        force_grpc_data: bool,
+        host: &str,
+        configure: impl FnOnce(&mut ModuleConfig),
+    ) -> Self {
         let dest = tempfile::tempdir().expect("dest dir");
         let canonical = dest.path().canonicalize().expect("canonical dest");
+        let mut module = ModuleConfig {
+            name: "test".into(),
+            path: canonical.clone(),
+            canonical_root: canonical.clone(),
+            read_only,
+            _comment: None,
+            delegation_allowed: true,
+            default_subpath: None,
+            dedupe_store: None,
+            fake_super: false,
+            audit_log: None,
+            allowed_subpaths: Vec::new(),
+        };
+        configure(&mut module);
         let mut modules = HashMap::new();
-        modules.insert(
-            "test".to_string(),
-            ModuleConfig {
-                name: "test".into(),
-                path: canonical.clone(),
-                canonical_root: canonical.clone(),
-                read_only,
-                _comment: None,
-                delegation_allowed: true,
-            },
-        );
+        modules.insert("test".to_string(), module);
         let service = BlitService::from_runtime(
             modules,
             None,
-            false,
+            force_grpc_data,
             server_checksums_enabled,
             crate::metrics::TransferMetrics::disabled(),
             crate::delegation_gate::DelegationConfig::default(),
         );
         let active_jobs = service.active_jobs.clone();
-        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+        let listener = tokio::net::TcpListener::bind((host, 0))
             .await
             .expect("bind loopback listener");
         let port = listener.local_addr().expect("listener addr").port();
         let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
         let server = tokio::spawn(async move {
             blit_core::remote::grpc_server::production_server_builder()
-                .add_service(BlitServer::new(service))
+                .add_service(blit_core::remote::message_limits::blit_server(service))
                 .serve_with_incoming_shutdown(
                     tokio_stream::wrappers::TcpListenerStream::new(listener),
                     async {
@@ -122,7 +189,7 @@ impl Daemon {
                 .expect("in-process daemon serves");
         });
         let endpoint = RemoteEndpoint {
-            host: "127.0.0.1".into(),
+            host: host.into(),
             port,
             path: RemotePath::Module {
                 module: "test".into(),
@@ -658,6 +725,470 @@ async fn session_push_lands_identical_tree_with_exact_counts() {
     daemon.stop().await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn module_only_push_lands_under_the_expanded_default_subpath() {
+    let src = tempfile::tempdir().unwrap();
+    let fixture = small_tree();
+    write_tree(src.path(), &fixture);
+
+    let daemon = Daemon::start_with_default_subpath("incoming/{date}").await;
+    let landed = daemon.dest_root.join("incoming").join(
+        crate::runtime::expand_default_subpath("{date}", std::time::SystemTime::now()).unwrap(),
+    );
+    let summary = run_push_session(
+        &daemon.endpoint,
+        Arc::new(FsTransferSource::new(src.path().to_path_buf())),
+        PushSessionOptions::default(),
+    )
+    .await
+    .expect("session push succeeds");
+    assert_eq!(summary.files_transferred, fixture.len() as u64);
+    assert_trees_identical(src.path(), &landed);
+
+    // A push that names a path keeps it; the default applies only to
+    // the bare module.
+    let mut named = daemon.endpoint.clone();
+    named.path = RemotePath::Module {
+        module: "test".into(),
+        rel_path: PathBuf::from("explicit"),
+    };
+    run_push_session(
+        &named,
+        Arc::new(FsTransferSource::new(src.path().to_path_buf())),
+        PushSessionOptions::default(),
+    )
+    .await
+    .expect("named push succeeds");
+    assert_trees_identical(src.path(), &daemon.dest_root.join("explicit"));
+
+    daemon.stop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn allowed_subpaths_admit_writes_inside_and_refuse_siblings() {
+    let src = tempfile::tempdir().unwrap();
+    write_tree(src.path(), &small_tree());
+    let daemon = Daemon::start_with_allowed_subpaths(&["incoming"]).await;
+    let push_to = |rel: &str| {
+        let mut endpoint = daemon.endpoint.clone();
+        endpoint.path = RemotePath::Module {
+            module: "test".into(),
+            rel_path: PathBuf::from(rel),
+        };
+        let source = Arc::new(FsTransferSource::new(src.path().to_path_buf()));
+        async move { run_push_session(&endpoint, source, PushSessionOptions::default()).await }
+    };
+
+    push_to("incoming/host-a")
+        .await
+        .expect("push inside the allowed subpath succeeds");
+    assert_trees_identical(src.path(), &daemon.dest_root.join("incoming/host-a"));
+
+    // A sibling, the bare module and a path that only shares a name
+    // prefix are all outside the allowlist.
+    for rel in ["outgoing", "", "incoming-not"] {
+        let err = push_to(rel)
+            .await
+            .expect_err("push outside the allowed subpaths must be refused");
+        let fault = fault_of(&err);
+        assert_eq!(
+            fault.code,
+            session_error::Code::ProtocolViolation,
+            "{rel:?}"
+        );
+        assert!(
+            fault.message.contains("only accepts writes under"),
+            "{fault:?}"
+        );
+    }
+    assert!(!daemon.dest_root.join("outgoing").exists());
+    assert!(!daemon.dest_root.join("incoming-not").exists());
+
+    // A symlink inside the allowed subtree cannot carry a write out of it.
+    #[cfg(unix)]
+    {
+        std::fs::create_dir(daemon.dest_root.join("outgoing")).unwrap();
+        std::os::unix::fs::symlink("../outgoing", daemon.dest_root.join("incoming/escape"))
+            .unwrap();
+        let err = push_to("incoming/escape/x")
+            .await
+            .expect_err("push through a symlink out of the allowlist must be refused");
+        assert_eq!(fault_of(&err).code, session_error::Code::ProtocolViolation);
+        assert!(collect_tree(&daemon.dest_root.join("outgoing")).is_empty());
+    }
+
+    daemon.stop().await;
+}
+
+/// Files in `store`'s fan-out directories.
+#[cfg(unix)]
+fn store_blobs(store: &Path) -> Vec<PathBuf> {
+    let mut blobs = Vec::new();
+    for fan in std::fs::read_dir(store).unwrap() {
+        for blob in std::fs::read_dir(fan.unwrap().path()).unwrap() {
+            blobs.push(blob.unwrap().path());
+        }
+    }
+    blobs
+}
+
+/// The dedupe pass runs as the daemon closes the session, so the client
+/// can see success a moment before the store settles.
+#[cfg(unix)]
+async fn wait_for_blobs(store: &Path, count: usize) -> Vec<PathBuf> {
+    for _ in 0..200 {
+        let blobs = store_blobs(store);
+        if blobs.len() == count {
+            return blobs;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+    }
+    panic!(
+        "store never reached {count} blob(s): {:?}",
+        store_blobs(store)
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn dedupe_module_stores_identical_pushed_files_once() {
+    use std::os::unix::fs::MetadataExt;
+    let ino = |path: &Path| std::fs::metadata(path).unwrap().ino();
+
+    let src = tempfile::tempdir().unwrap();
+    write_tree(
+        src.path(),
+        &[
+            ("a.txt", b"same bytes", 1_600_000_001),
+            ("copy/a.txt", b"same bytes", 1_600_000_001),
+            ("other.txt", b"different", 1_600_000_002),
+        ],
+    );
+    let store = tempfile::tempdir().unwrap();
+    let daemon = Daemon::start_with_dedupe_store(store.path()).await;
+    run_push_session(
+        &daemon.endpoint,
+        Arc::new(FsTransferSource::new(src.path().to_path_buf())),
+        PushSessionOptions::default(),
+    )
+    .await
+    .expect("session push succeeds");
+
+    let blobs = wait_for_blobs(store.path(), 2).await;
+    let a = daemon.dest_root.join("a.txt");
+    let copy = daemon.dest_root.join("copy/a.txt");
+    for _ in 0..200 {
+        if ino(&a) == ino(&copy) {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+    }
+    assert_eq!(ino(&a), ino(&copy), "identical files share one blob");
+    assert!(blobs.iter().any(|blob| ino(blob) == ino(&a)));
+    assert_trees_identical(src.path(), &daemon.dest_root);
+
+    // New content at one path replaces that path only: the blob and the
+    // other reference keep the old bytes.
+    write_tree(src.path(), &[("a.txt", b"changed", 1_600_000_009)]);
+    run_push_session(
+        &daemon.endpoint,
+        Arc::new(FsTransferSource::new(src.path().to_path_buf())),
+        PushSessionOptions::default(),
+    )
+    .await
+    .expect("second push succeeds");
+    wait_for_blobs(store.path(), 3).await;
+    assert_eq!(std::fs::read(&a).unwrap(), b"changed");
+    assert_eq!(std::fs::read(&copy).unwrap(), b"same bytes");
+    assert!(blobs
+        .iter()
+        .any(|blob| std::fs::read(blob).unwrap() == b"same bytes"));
+
+    daemon.stop().await;
+}
+
+/// Same bytes under two owners in a fake-super dedupe module: each file
+/// keeps its own `user.blit.stat` record, so the pass must not link them
+/// into one inode and hand both the first file's owner.
+#[cfg(target_os = "linux")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn dedupe_never_links_files_pushed_with_different_owners() {
+    use std::os::unix::fs::MetadataExt;
+    let ino = |path: &Path| std::fs::metadata(path).unwrap().ino();
+
+    let src = tempfile::tempdir().unwrap();
+    write_tree(
+        src.path(),
+        &[
+            ("a.txt", b"same bytes", 1_600_000_001),
+            ("b.txt", b"same bytes", 1_600_000_001),
+        ],
+    );
+    // A second owner needs root.
+    if std::os::unix::fs::chown(src.path().join("b.txt"), Some(4242), Some(4343)).is_err() {
+        return;
+    }
+    let a_meta = std::fs::metadata(src.path().join("a.txt")).unwrap();
+
+    let store = tempfile::tempdir().unwrap();
+    let daemon = Daemon::start_with(false, true, false, "127.0.0.1", |module| {
+        module.dedupe_store = Some(store.path().to_path_buf());
+        module.fake_super = true;
+    })
+    .await;
+    run_push_session(
+        &daemon.endpoint,
+        Arc::new(FsTransferSource::new(src.path().to_path_buf()).with_owner(true, false)),
+        PushSessionOptions {
+            preserve_owner: true,
+            ..PushSessionOptions::default()
+        },
+    )
+    .await
+    .expect("session push succeeds");
+
+    // The pass runs before the outcome is recorded.
+    wait_for_blobs(store.path(), 1).await;
+    for _ in 0..200 {
+        if !daemon.active_jobs.recent().is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+    }
+    assert_eq!(daemon.active_jobs.recent().len(), 1);
+
+    let a = daemon.dest_root.join("a.txt");
+    let b = daemon.dest_root.join("b.txt");
+    assert_ne!(ino(&a), ino(&b), "different owners never share an inode");
+    let owner = |path: &Path| {
+        let record =
+            blit_core::ownership::read_owner(path, &std::fs::metadata(path).unwrap(), true)
+                .unwrap();
+        (record.uid, record.gid)
+    };
+    assert_eq!(owner(&a), (a_meta.uid(), a_meta.gid()));
+    assert_eq!(owner(&b), (4242, 4343));
+
+    daemon.stop().await;
+}
+
+/// `--metadata-only` with ownership carried: a content-equal file whose
+/// owner drifted gets the source's uid/gid back along with its mtime,
+/// with no bytes moved.
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn metadata_only_push_repairs_a_drifted_owner() {
+    use std::os::unix::fs::MetadataExt;
+
+    let src = tempfile::tempdir().unwrap();
+    write_tree(src.path(), &[("same.bin", b"same bytes", 1_600_000_002)]);
+    // Changing owners needs root.
+    if std::os::unix::fs::chown(src.path().join("same.bin"), Some(4242), Some(4343)).is_err() {
+        return;
+    }
+    let daemon = Daemon::start(false).await;
+    write_tree(
+        &daemon.dest_root,
+        &[("same.bin", b"same bytes", 1_600_000_001)],
+    );
+    let landed = daemon.dest_root.join("same.bin");
+    std::os::unix::fs::chown(&landed, Some(1111), Some(2222)).unwrap();
+
+    let summary = run_push_session(
+        &daemon.endpoint,
+        Arc::new(FsTransferSource::new(src.path().to_path_buf()).with_owner(true, false)),
+        PushSessionOptions {
+            compare_mode: ComparisonMode::Checksum,
+            metadata_only: true,
+            preserve_owner: true,
+            ..PushSessionOptions::default()
+        },
+    )
+    .await
+    .expect("metadata-only push succeeds");
+
+    assert_eq!(summary.files_transferred, 0);
+    let meta = std::fs::metadata(&landed).unwrap();
+    assert_eq!((meta.uid(), meta.gid()), (4242, 4343));
+    assert_eq!(meta.mtime(), 1_600_000_002);
+
+    daemon.stop().await;
+}
+
+/// `fake_super`: a push carrying ownership into a module that does not
+/// chown records each entry's owner and mode in its `user.blit.stat`
+/// xattr and leaves the inode as the daemon wrote it; a pull back out
+/// of the module reads the record and the client restores both.
+#[cfg(target_os = "linux")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn fake_super_module_round_trips_ownership_through_xattrs() {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+    let mode = |path: &Path| std::fs::metadata(path).unwrap().mode() & 0o7777;
+
+    let src = tempfile::tempdir().unwrap();
+    write_tree(
+        src.path(),
+        &[
+            ("a.txt", b"alpha", 1_600_000_001),
+            ("sub/b.txt", b"bravo", 1_600_000_002),
+        ],
+    );
+    let a = src.path().join("a.txt");
+    // An arbitrary owner needs root; unprivileged, the runner's own ids
+    // still have to survive the trip through the record.
+    let (uid, gid) = match std::os::unix::fs::chown(&a, Some(4242), Some(4343)) {
+        Ok(()) => (4242, 4343),
+        Err(_) => {
+            let meta = std::fs::metadata(&a).unwrap();
+            (meta.uid(), meta.gid())
+        }
+    };
+    std::fs::set_permissions(&a, std::fs::Permissions::from_mode(0o400)).unwrap();
+    std::fs::set_permissions(
+        src.path().join("sub"),
+        std::fs::Permissions::from_mode(0o750),
+    )
+    .unwrap();
+
+    let daemon = Daemon::start_with_fake_super().await;
+    run_push_session(
+        &daemon.endpoint,
+        Arc::new(FsTransferSource::new(src.path().to_path_buf()).with_owner(true, false)),
+        PushSessionOptions {
+            preserve_owner: true,
+            ..PushSessionOptions::default()
+        },
+    )
+    .await
+    .expect("session push succeeds");
+
+    let stored = daemon.dest_root.join("a.txt");
+    let meta = std::fs::metadata(&stored).unwrap();
+    assert_eq!(
+        mode(&stored),
+        0o600,
+        "kept readable and writable by the daemon"
+    );
+    if uid == 4242 {
+        assert_ne!(meta.uid(), 4242, "a fake-super module never chowns");
+    }
+    let record = blit_core::ownership::read_owner(&stored, &meta, true).unwrap();
+    assert_eq!((record.uid, record.gid, record.mode), (uid, gid, 0o400));
+    let sub = daemon.dest_root.join("sub");
+    let sub_record =
+        blit_core::ownership::read_owner(&sub, &std::fs::metadata(&sub).unwrap(), true).unwrap();
+    assert_eq!(sub_record.mode, 0o750);
+
+    let dest = tempfile::tempdir().unwrap();
+    run_pull_session(
+        &daemon.endpoint,
+        dest.path().to_path_buf(),
+        PullSessionOptions {
+            preserve_owner: true,
+            ..PullSessionOptions::default()
+        },
+    )
+    .await
+    .expect("session pull succeeds");
+
+    let restored = dest.path().join("a.txt");
+    let meta = std::fs::metadata(&restored).unwrap();
+    assert_eq!((meta.uid(), meta.gid()), (uid, gid));
+    assert_eq!(mode(&restored), 0o400);
+    assert_eq!(mode(&dest.path().join("sub")), 0o750);
+    assert_eq!(std::fs::read(&restored).unwrap(), b"alpha");
+
+    daemon.stop().await;
+}
+
+/// An audited module gets one JSON line per push, pull and purge, each
+/// naming the peer, module, files and counts, and whether it succeeded.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn audited_module_records_push_pull_and_purge() {
+    use crate::audit::AuditRecord;
+    use blit_core::generated::blit_client::BlitClient;
+    use blit_core::generated::PurgeRequest;
+
+    let logs = tempfile::tempdir().unwrap();
+    let log = logs.path().join("audit.jsonl");
+    // Whole lines only: the log may not exist yet, or be mid-append.
+    let records = || -> Vec<AuditRecord> {
+        let text = std::fs::read_to_string(&log).unwrap_or_default();
+        let complete = text.rfind('\n').map_or("", |end| &text[..end]);
+        complete
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    };
+    let src = tempfile::tempdir().unwrap();
+    write_tree(
+        src.path(),
+        &[
+            ("a.txt", b"alpha", 1_600_000_001),
+            ("sub/b.txt", b"bravo!", 1_600_000_002),
+        ],
+    );
+
+    let daemon = Daemon::start_with_audit_log(&log).await;
+    run_push_session(
+        &daemon.endpoint,
+        Arc::new(FsTransferSource::new(src.path().to_path_buf())),
+        PushSessionOptions::default(),
+    )
+    .await
+    .expect("session push succeeds");
+    let dest = tempfile::tempdir().unwrap();
+    run_pull_session(
+        &daemon.endpoint,
+        dest.path().to_path_buf(),
+        PullSessionOptions::default(),
+    )
+    .await
+    .expect("session pull succeeds");
+    let mut client = BlitClient::connect(format!("http://127.0.0.1:{}", daemon.endpoint.port))
+        .await
+        .unwrap();
+    client
+        .purge(PurgeRequest {
+            module: "test".into(),
+            paths_to_delete: vec!["sub/b.txt".into()],
+        })
+        .await
+        .expect("purge succeeds");
+    // The session's record lands just after its client sees the summary.
+    tokio::time::timeout(std::time::Duration::from_secs(10), async {
+        while records().len() < 3 {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("three audit records");
+
+    let mut records = records();
+    records.sort_by_key(|record| record.operation.clone());
+    for record in &mut records {
+        assert!(record.ok, "{record:?}");
+        assert_eq!(record.error, None);
+        assert_eq!(record.module, "test");
+        assert!(record.peer.starts_with("127.0.0.1:"), "{record:?}");
+        assert!(record.timestamp_unix_ms > 0);
+        record.paths.sort();
+    }
+    let [pull, purge, push] = &records[..] else {
+        panic!("expected push, pull and purge: {records:?}");
+    };
+    for transfer in [push, pull] {
+        assert_eq!(transfer.paths, vec!["a.txt", "sub/b.txt"], "{transfer:?}");
+        assert_eq!((transfer.files, transfer.bytes), (2, 11), "{transfer:?}");
+    }
+    assert_eq!(push.operation, "push");
+    assert_eq!(pull.operation, "pull");
+    assert_eq!(purge.paths, vec!["sub/b.txt"]);
+    assert_eq!(purge.entries_deleted, 1);
+
+    daemon.stop().await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn read_only_module_refuses_the_session() {
     let daemon = Daemon::start(true).await; // read-only
@@ -934,6 +1465,84 @@ async fn mid_resume_cancel_surfaces_cancelled_over_the_data_plane() {
     daemon.stop().await;
 }
 
+/// A client-side cancel (`PushSessionOptions::cancel`) fired mid-file
+/// over the data plane returns a `Cancelled` fault promptly, and the
+/// daemon ends its half and drains the job row instead of waiting out
+/// the stalled record. The partial destination file never claims
+/// completion: it never received the source's mtime.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn client_cancel_mid_push_tears_down_both_ends() {
+    let daemon = Daemon::start(false).await;
+    let src = tempfile::tempdir().unwrap();
+    let source_mtime = filetime::FileTime::from_unix_time(1_600_000_100, 0);
+    std::fs::write(src.path().join("big.bin"), vec![0xCDu8; 4 * 1024 * 1024]).unwrap();
+    filetime::set_file_mtime(src.path().join("big.bin"), source_mtime).unwrap();
+
+    let started = Arc::new(tokio::sync::Notify::new());
+    let source = Arc::new(StuckAfterFirstChunkSource {
+        inner: FsTransferSource::new(src.path().to_path_buf()),
+        started: Arc::clone(&started),
+    });
+
+    let cancel = tokio_util::sync::CancellationToken::new();
+    let ep = daemon.endpoint.clone();
+    let client = tokio::spawn({
+        let cancel = cancel.clone();
+        async move {
+            run_push_session(
+                &ep,
+                source,
+                PushSessionOptions {
+                    cancel: Some(cancel),
+                    ..PushSessionOptions::default()
+                },
+            )
+            .await
+        }
+    });
+
+    tokio::time::timeout(std::time::Duration::from_secs(10), started.notified())
+        .await
+        .expect("payload bytes should flow before cancel");
+    assert_eq!(daemon.active_jobs.snapshot().len(), 1);
+    cancel.cancel();
+
+    let result = tokio::time::timeout(std::time::Duration::from_secs(10), client)
+        .await
+        .expect("client must return promptly once cancelled")
+        .expect("client task joins");
+    let err = result.expect_err("a cancelled push fails");
+    assert_eq!(
+        fault_of(&err).code,
+        session_error::Code::Cancelled,
+        "the client reports its own cancel: {err:#}"
+    );
+    assert_eq!(
+        blit_core::remote::error_category::categorize(&err),
+        blit_core::remote::error_category::ErrorCategory::Cancelled
+    );
+
+    let mut drained = false;
+    for _ in 0..500 {
+        if daemon.active_jobs.snapshot().is_empty() {
+            drained = true;
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert!(drained, "the daemon must end the cancelled session's job");
+
+    if let Ok(meta) = std::fs::metadata(daemon.dest_root.join("big.bin")) {
+        assert_ne!(
+            filetime::FileTime::from_last_modification_time(&meta),
+            source_mtime,
+            "a cancelled partial must not look complete"
+        );
+    }
+
+    daemon.stop().await;
+}
+
 /// otp-7b-2 fault-injection source: the reader for one path yields only
 /// the first `limit` bytes then EOF, provably short of the manifested
 /// size — the mid-record fault D4 documents. Everything else delegates
@@ -1409,6 +2018,142 @@ async fn pull_session_lands_bytes_over_the_data_plane() {
     daemon.stop().await;
 }
 
+/// An unreachable data-plane grant is not fatal: the client's epoch-0
+/// dial fails before anything moves, and the session client reruns the
+/// session on the in-stream (gRPC) carrier — both directions, same
+/// bytes, and the summary says which carrier actually ran. Progress
+/// counts the rerun once: the abandoned attempt reports nothing.
+/// Skipped where the sandbox has no IPv6 loopback.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn unreachable_data_port_falls_back_to_in_stream_carrier() {
+    let Some(daemon) = Daemon::start_with_unreachable_data_port().await else {
+        return;
+    };
+    let src = tempfile::tempdir().unwrap();
+    write_tree(src.path(), &small_tree());
+    let files = small_tree().len() as u64;
+    let totals = |rx: &mut tokio::sync::mpsc::UnboundedReceiver<_>| {
+        let mut totals = ProgressTotals::default();
+        while let Ok(event) = rx.try_recv() {
+            totals.apply(&event);
+        }
+        totals
+    };
+
+    let source = Arc::new(FsTransferSource::new(src.path().to_path_buf()));
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+    let summary = tokio::time::timeout(
+        std::time::Duration::from_secs(20),
+        run_push_session(
+            &daemon.endpoint,
+            source,
+            PushSessionOptions {
+                progress: Some(RemoteTransferProgress::new(progress_tx)),
+                ..Default::default()
+            },
+        ),
+    )
+    .await
+    .expect("push must not hang on an unreachable data port")
+    .expect("push falls back instead of failing");
+    assert!(summary.in_stream_carrier_used);
+    assert_eq!(summary.files_transferred, files);
+    assert_trees_identical(src.path(), &daemon.dest_root);
+    let pushed = totals(&mut progress_rx);
+    assert_eq!((pushed.manifest_files, pushed.files), (files, files));
+
+    let dest = tempfile::tempdir().unwrap();
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+    let outcome = tokio::time::timeout(
+        std::time::Duration::from_secs(20),
+        run_pull_session(
+            &daemon.endpoint,
+            dest.path().to_path_buf(),
+            PullSessionOptions {
+                progress: Some(RemoteTransferProgress::new(progress_tx)),
+                ..Default::default()
+            },
+        ),
+    )
+    .await
+    .expect("pull must not hang on an unreachable data port")
+    .expect("pull falls back instead of failing");
+    assert!(outcome.summary.in_stream_carrier_used);
+    assert!(outcome.data_plane_streams.is_none());
+    assert_trees_identical(&daemon.dest_root, dest.path());
+    let pulled = totals(&mut progress_rx);
+    assert_eq!((pulled.manifest_files, pulled.files), (files, files));
+    daemon.stop().await;
+}
+
+/// The connect-time probe reports a `--force-grpc-data` daemon's
+/// missing data plane, and a push that asked for dial pins detects it,
+/// drops them, and lands over the in-stream carrier instead of failing.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn push_skips_unadvertised_data_plane_and_dial_pins() {
+    let daemon = Daemon::start_forcing_grpc_data().await;
+    let mut client = connect_transfer_client(&daemon.endpoint)
+        .await
+        .expect("connect");
+    let caps = capabilities::probe(&mut client)
+        .await
+        .expect("probe succeeds")
+        .expect("daemon serves Capabilities");
+    assert!(!caps.supports(capabilities::FEATURE_TCP_DATA_PLANE));
+    assert!(!caps.supports(capabilities::FEATURE_DIAL_PINS));
+    assert!(caps.ensure_same_build().is_ok());
+
+    let src = tempfile::tempdir().unwrap();
+    write_tree(src.path(), &small_tree());
+    let source = Arc::new(FsTransferSource::new(src.path().to_path_buf()));
+    let summary = run_push_session(
+        &daemon.endpoint,
+        source,
+        PushSessionOptions {
+            dial_request: Some(DialRequest {
+                streams: 4,
+                chunk_bytes: 1 << 20,
+            }),
+            ..PushSessionOptions::default()
+        },
+    )
+    .await
+    .expect("push proceeds without the unsupported features");
+    assert!(summary.in_stream_carrier_used);
+    assert_eq!(summary.files_transferred, small_tree().len() as u64);
+    assert_trees_identical(src.path(), &daemon.dest_root);
+    daemon.stop().await;
+}
+
+/// `blit copy --unpack`: an archive-backed source pushes the tarball's
+/// members as the tree — the destination ends up holding what
+/// extracting the archive would have produced, with no local extract.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn push_unpacks_an_archive_source_into_the_module() {
+    let daemon = Daemon::start(false).await;
+    let tree = tempfile::tempdir().unwrap();
+    write_tree(tree.path(), &small_tree());
+    let staging = tempfile::tempdir().unwrap();
+    let archive = staging.path().join("tree.tar");
+    let mut builder = tar::Builder::new(std::fs::File::create(&archive).unwrap());
+    builder.append_dir_all(".", tree.path()).unwrap();
+    builder.into_inner().unwrap();
+
+    let source = Arc::new(ArchiveTransferSource::open(&archive).expect("index archive"));
+    let summary = run_push_session(&daemon.endpoint, source, PushSessionOptions::default())
+        .await
+        .expect("archive push succeeds");
+    assert_eq!(summary.files_transferred, small_tree().len() as u64);
+    assert_trees_identical(tree.path(), &daemon.dest_root);
+    let landed = std::fs::metadata(daemon.dest_root.join("dir one/b.log")).unwrap();
+    assert_eq!(
+        filetime::FileTime::from_last_modification_time(&landed).unix_seconds(),
+        1_600_000_003,
+        "member mtimes ride the manifest"
+    );
+    daemon.stop().await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn pull_session_lands_bytes_over_in_stream_carrier() {
     // The in-stream carrier is the pull fallback (diagnostics / unreachable
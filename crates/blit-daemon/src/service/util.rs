@@ -21,6 +21,9 @@ pub(crate) fn io_to_status(context: impl Display, error: io::Error) -> Status {
     match kind {
         io::ErrorKind::NotFound => Status::not_found(message),
         io::ErrorKind::PermissionDenied => Status::permission_denied(message),
+        io::ErrorKind::StorageFull | io::ErrorKind::QuotaExceeded => {
+            Status::resource_exhausted(message)
+        }
         _ => Status::internal(message),
     }
 }
@@ -49,6 +52,11 @@ pub(crate) async fn resolve_module(
                 // Synthesized "default" module follows the daemon-wide
                 // delegation policy without further narrowing.
                 delegation_allowed: true,
+                default_subpath: None,
+                dedupe_store: None,
+                fake_super: false,
+                audit_log: None,
+                allowed_subpaths: Vec::new(),
             });
         } else {
             return Err(Status::not_found(
@@ -124,6 +132,67 @@ pub(crate) fn resolve_contained_path(module: &ModuleConfig, rel: &Path) -> Resul
     Ok(target)
 }
 
+/// Refuse a write under `target` (an absolute path built from
+/// `module.path`) unless it falls inside one of the module's
+/// `allowed_subpaths`. A module without any allows every write.
+///
+/// Checked twice: lexically, so a destination that does not exist yet
+/// is judged by its name, and on the deepest existing ancestor after
+/// symlink expansion, so a link inside an allowed subtree cannot carry
+/// a write into a sibling one.
+#[allow(clippy::result_large_err)]
+pub(crate) fn enforce_allowed_subpaths(module: &ModuleConfig, target: &Path) -> Result<(), Status> {
+    if module.allowed_subpaths.is_empty() {
+        return Ok(());
+    }
+    let rel = target
+        .strip_prefix(&module.path)
+        .or_else(|_| target.strip_prefix(&module.canonical_root))
+        .unwrap_or(target);
+    let mut ancestor = target.to_path_buf();
+    let canonical = loop {
+        match std::fs::canonicalize(&ancestor) {
+            Ok(canonical) => break Some(canonical),
+            Err(err) if err.kind() == io::ErrorKind::NotFound && ancestor.pop() => continue,
+            Err(_) => break None,
+        }
+    };
+    let allowed = module.allowed_subpaths.iter().any(|subpath| {
+        let allowed_root = module.canonical_root.join(subpath);
+        rel.starts_with(subpath)
+            && canonical.as_deref().is_some_and(|canonical| {
+                canonical.starts_with(&allowed_root) || allowed_root.starts_with(canonical)
+            })
+    });
+    if allowed {
+        return Ok(());
+    }
+    let listed: Vec<String> = module
+        .allowed_subpaths
+        .iter()
+        .map(|subpath| subpath.display().to_string())
+        .collect();
+    Err(Status::permission_denied(format!(
+        "module '{}' only accepts writes under {}; '{}' is outside them",
+        module.name,
+        listed.join(", "),
+        rel.display()
+    )))
+}
+
+/// Where a write that names only the module lands: the module's
+/// expanded `default_subpath`, contained like any client path, or
+/// `None` when the module has none (the module root).
+#[allow(clippy::result_large_err)]
+pub(crate) fn default_destination(module: &ModuleConfig) -> Result<Option<PathBuf>, Status> {
+    let Some(template) = &module.default_subpath else {
+        return Ok(None);
+    };
+    let rel = crate::runtime::expand_default_subpath(template, std::time::SystemTime::now())
+        .map_err(|e| Status::invalid_argument(format!("module '{}': {e:#}", module.name)))?;
+    resolve_contained_path(module, &rel).map(Some)
+}
+
 #[cfg(test)]
 mod status_tests {
     use super::*;
@@ -790,6 +790,7 @@ mod tests {
             is_dir,
             size: 100,
             mtime_seconds: 0,
+            permissions: 0,
         }
     }
 
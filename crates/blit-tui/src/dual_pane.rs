@@ -894,6 +894,7 @@ mod tests {
                 is_dir: true,
                 size: 0,
                 mtime_seconds: 10,
+                permissions: 0,
             }],
         ));
 
@@ -5,7 +5,9 @@
 //! AppState, no I/O); the spawn_* tasks call them.
 
 use crate::f3pull;
+use blit_core::enumeration::SymlinkDeref;
 use blit_core::remote::endpoint::RemoteEndpoint;
+use blit_core::transfer_plan::PlanOptions;
 
 /// d-55 R2 / d-57: build the [`DelegatedSpecOptions`] for a DELEGATED
 /// F1 remote→remote transfer (the wire `TransferOperationSpec` is
@@ -75,8 +77,11 @@ pub(crate) fn build_f3_pull_execution(
     PullExecution {
         remote,
         dest_root,
+        pack: false,
         // No filter UI on the F3 trigger — the session scans everything.
         filter: None,
+        rename: Vec::new(),
+        dial_request: None,
         mirror_mode: mirror,
         mirror_kind: if mirror {
             MirrorMode::FilteredSubset
@@ -87,10 +92,23 @@ pub(crate) fn build_f3_pull_execution(
         trace_data_plane: false,
         require_complete_scan: kind == f3pull::PullKind::Move,
         drop_windows_metadata: false,
+        crtimes: false,
+        owner: false,
+        fake_super: false,
+        prune_empty_dirs: false,
         resume: false,
         resume_block_size: 0,
         compare_mode,
         ignore_existing: false,
+        existing_only: false,
+        max_files: 0,
+        checksum_seed: 0,
+        chmod: String::new(),
+        chown: String::new(),
+        xattrs: String::new(),
+        remote_options: Default::default(),
+        metadata_only: false,
+        reconnect: None,
         remote_label,
         lifecycle_trace: Default::default(),
     }
@@ -124,6 +142,14 @@ pub(crate) fn build_f1_push_execution(
         remote,
         // No filter UI on the F1 trigger — the session scans everything.
         filter: None,
+        rename: Vec::new(),
+        unpack: false,
+        symlink_deref: SymlinkDeref::None,
+        safe_links: false,
+        vss: false,
+        snapshot: false,
+        dial_request: None,
+        plan_options: PlanOptions::default(),
         mirror_mode: mirror,
         mirror_kind: if mirror {
             MirrorMode::All
@@ -134,6 +160,11 @@ pub(crate) fn build_f1_push_execution(
         trace_data_plane: false,
         require_complete_scan: mirror,
         drop_windows_metadata: false,
+        crtimes: false,
+        owner: false,
+        fake_super: false,
+        manifest_checkpoint: None,
+        prune_empty_dirs: false,
         resume: false,
         resume_block_size: 0,
         // codex otp-10a F1 via the ONE mapping (codex otp-10b-2 F6):
@@ -149,6 +180,17 @@ pub(crate) fn build_f1_push_execution(
         },
         // No ignore-existing toggle on the F1 trigger.
         ignore_existing: false,
+        existing_only: false,
+        max_files: 0,
+        checksum_seed: 0,
+        chmod: String::new(),
+        chown: String::new(),
+        xattrs: String::new(),
+        remote_options: Default::default(),
+        file_count_warning: None,
+        metadata_only: false,
+        reconnect: None,
+        remove_source_files: false,
         remote_label,
         lifecycle_trace: Default::default(),
     }
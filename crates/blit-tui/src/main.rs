@@ -3202,10 +3202,17 @@ async fn run_f3_du_total(remote: &RemoteEndpoint) -> eyre::Result<(u64, u64)> {
     let (module, rel_path) = module_and_rel_path(remote)?;
     let start_path = rel_path_to_string(&rel_path);
     let mut acc: Option<(u64, u64)> = None;
-    du::stream(remote, module, start_path, F3_DU_MAX_DEPTH, |entry| {
-        acc = du_total_from_entries(acc, entry.bytes, entry.files);
-        Ok(())
-    })
+    du::stream(
+        remote,
+        module,
+        start_path,
+        F3_DU_MAX_DEPTH,
+        Vec::new(),
+        |entry| {
+            acc = du_total_from_entries(acc, entry.bytes, entry.files);
+            Ok(())
+        },
+    )
     .await?;
     acc.ok_or_else(|| eyre::eyre!("no disk-usage data returned"))
 }
@@ -3902,7 +3909,7 @@ fn spawn_cancel_transfer(
     tx: mpsc::Sender<CancelReply>,
 ) {
     tokio::spawn(async move {
-        let result = blit_app::admin::jobs::cancel(&endpoint, &transfer_id)
+        let result = blit_app::admin::jobs::cancel(&endpoint, &transfer_id, None)
             .await
             .map_err(|err| format!("{err:#}"));
         let _ = tx